@@ -5,5 +5,19 @@ fn main() {
         println!("cargo:rustc-link-lib=framework=AVFoundation");
     }
     
+    // Build identity of the bundled kinematics WASM (reported by get_component_versions)
+    let wasm_path = "../src/utils/kinematics-wasm/reachy_mini_kinematics_wasm_bg.wasm";
+    println!("cargo:rerun-if-changed={}", wasm_path);
+    let wasm_hash = std::fs::read(wasm_path)
+        .map(|bytes| {
+            // FNV-1a 64: deterministic across toolchains, good enough to tell builds apart
+            let hash = bytes.iter().fold(0xcbf29ce484222325u64, |hash, byte| {
+                (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+            });
+            format!("{:016x}", hash)
+        })
+        .unwrap_or_default();
+    println!("cargo:rustc-env=KINEMATICS_WASM_HASH={}", wasm_hash);
+    
     tauri_build::build()
 }
@@ -0,0 +1,282 @@
+/// Anonymous usage metrics
+///
+/// Records usage events (daemon starts in simulation or USB mode, daemon crashes,
+/// app installs, emergency stops, features used in the UI) to `<app data>/analytics.json`,
+/// only while the user has opted in (`telemetry_opt_in` setting). Events carry a random
+/// install id and scalar properties only, never hosts, paths or serial numbers.
+///
+/// When `analytics_endpoint` is set, pending events are POSTed there in batches every
+/// hour. Everything recorded can be viewed and purged from the app.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Manager, State};
+
+use crate::settings::SettingsState;
+
+const ANALYTICS_FILE: &str = "analytics.json";
+/// Oldest events are dropped beyond this
+const MAX_EVENTS: usize = 5000;
+const UPLOAD_INTERVAL: Duration = Duration::from_secs(60 * 60);
+const UPLOAD_BATCH_SIZE: usize = 500;
+const UPLOAD_TIMEOUT: Duration = Duration::from_secs(15);
+const MAX_NAME_LEN: usize = 64;
+const MAX_STRING_PROPERTY_LEN: usize = 64;
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UsageEvent {
+    /// Unix millis
+    pub t: u64,
+    pub name: String,
+    pub properties: serde_json::Map<String, serde_json::Value>,
+    pub uploaded: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+struct AnalyticsStore {
+    install_id: String,
+    events: Vec<UsageEvent>,
+}
+
+/// What the local viewer shows
+#[derive(Debug, Serialize, Clone)]
+pub struct UsageReport {
+    pub enabled: bool,
+    pub endpoint: Option<String>,
+    pub install_id: String,
+    pub pending: usize,
+    pub events: Vec<UsageEvent>,
+}
+
+#[derive(Debug, Serialize)]
+struct UploadBatch<'a> {
+    install_id: &'a str,
+    app_version: String,
+    os: &'static str,
+    arch: &'static str,
+    events: Vec<&'a UsageEvent>,
+}
+
+pub struct AnalyticsState {
+    store: Mutex<AnalyticsStore>,
+    path: Mutex<Option<PathBuf>>,
+}
+
+// ============================================================================
+// HELPER FUNCTIONS
+// ============================================================================
+
+/// 16 random bytes, hex encoded (not derived from anything on the machine)
+fn generate_install_id() -> String {
+    let mut bytes = [0u8; 16];
+    let _ = getrandom::getrandom(&mut bytes);
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= MAX_NAME_LEN
+        && name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '.')
+}
+
+/// Keep booleans, numbers and short strings: nothing free-form can leak in
+fn sanitize(properties: serde_json::Map<String, serde_json::Value>) -> serde_json::Map<String, serde_json::Value> {
+    properties
+        .into_iter()
+        .filter(|(key, value)| {
+            is_valid_name(key)
+                && match value {
+                    serde_json::Value::Bool(_) | serde_json::Value::Number(_) => true,
+                    serde_json::Value::String(s) => s.len() <= MAX_STRING_PROPERTY_LEN,
+                    _ => false,
+                }
+        })
+        .collect()
+}
+
+impl AnalyticsState {
+    pub fn new() -> Self {
+        Self {
+            store: Mutex::new(AnalyticsStore::default()),
+            path: Mutex::new(None),
+        }
+    }
+
+    /// Load recorded events from the app data directory (new install id if missing or corrupted)
+    pub fn load(&self, app_data_dir: &Path) {
+        let path = app_data_dir.join(ANALYTICS_FILE);
+        let mut store: AnalyticsStore = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        if store.install_id.is_empty() {
+            store.install_id = generate_install_id();
+        }
+        *self.store.lock().unwrap() = store;
+        *self.path.lock().unwrap() = Some(path);
+    }
+
+    fn save(&self, store: &AnalyticsStore) {
+        let Some(path) = self.path.lock().unwrap().clone() else { return };
+        match serde_json::to_string(store) {
+            Ok(content) => {
+                if let Err(e) = std::fs::write(&path, content) {
+                    eprintln!("[analytics] ⚠️ Failed to save usage events: {}", e);
+                }
+            }
+            Err(e) => eprintln!("[analytics] ⚠️ Failed to serialize usage events: {}", e),
+        }
+    }
+
+    fn push(&self, name: &str, properties: serde_json::Map<String, serde_json::Value>) {
+        let mut store = self.store.lock().unwrap();
+        store.events.push(UsageEvent {
+            t: crate::daemon::history::now_millis(),
+            name: name.to_string(),
+            properties: sanitize(properties),
+            uploaded: false,
+        });
+        if store.events.len() > MAX_EVENTS {
+            let excess = store.events.len() - MAX_EVENTS;
+            store.events.drain(..excess);
+        }
+        self.save(&store);
+    }
+}
+
+impl Default for AnalyticsState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Record a usage event if the user opted in
+pub fn record(app_handle: &AppHandle, name: &str, properties: serde_json::Value) {
+    if !app_handle.state::<SettingsState>().get().telemetry_opt_in {
+        return;
+    }
+    let serde_json::Value::Object(properties) = properties else {
+        return;
+    };
+    app_handle.state::<AnalyticsState>().push(name, properties);
+}
+
+/// Send pending events to the configured endpoint, returns how many were sent
+async fn upload_pending(app_handle: &AppHandle) -> Result<usize, String> {
+    let settings = app_handle.state::<SettingsState>().get();
+    if !settings.telemetry_opt_in {
+        return Ok(0);
+    }
+    let Some(endpoint) = settings.analytics_endpoint else {
+        return Ok(0);
+    };
+
+    let state = app_handle.state::<AnalyticsState>();
+    let (body, sent_until) = {
+        let store = state.store.lock().unwrap();
+        let pending: Vec<&UsageEvent> = store.events.iter().filter(|event| !event.uploaded).take(UPLOAD_BATCH_SIZE).collect();
+        let Some(last) = pending.last() else {
+            return Ok(0);
+        };
+        let sent_until = last.t;
+        let batch = UploadBatch {
+            install_id: &store.install_id,
+            app_version: app_handle.package_info().version.to_string(),
+            os: std::env::consts::OS,
+            arch: std::env::consts::ARCH,
+            events: pending,
+        };
+        let body = serde_json::to_value(&batch).map_err(|e| format!("Failed to serialize usage events: {}", e))?;
+        (body, sent_until)
+    };
+
+    let response = reqwest::Client::new()
+        .post(&endpoint)
+        .timeout(UPLOAD_TIMEOUT)
+        .json(&body)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to upload usage events: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("Usage events endpoint returned {}", response.status()));
+    }
+
+    let mut store = state.store.lock().unwrap();
+    let mut sent = 0;
+    for event in store.events.iter_mut().filter(|event| !event.uploaded && event.t <= sent_until).take(UPLOAD_BATCH_SIZE) {
+        event.uploaded = true;
+        sent += 1;
+    }
+    state.save(&store);
+    Ok(sent)
+}
+
+/// Upload pending events every hour (call once in setup)
+pub fn start_uploader(app_handle: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(UPLOAD_INTERVAL).await;
+            match upload_pending(&app_handle).await {
+                Ok(0) => {}
+                Ok(sent) => println!("[analytics] 📤 Uploaded {} usage events", sent),
+                Err(e) => eprintln!("[analytics] ⚠️ {}", e),
+            }
+        }
+    });
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Record a feature usage event from the UI (dropped unless opted in)
+#[tauri::command]
+pub fn track_usage_event(app_handle: AppHandle, name: String, properties: Option<serde_json::Value>) -> Result<(), String> {
+    if !is_valid_name(&name) {
+        return Err(format!("Invalid event name: {}", name));
+    }
+    record(&app_handle, &name, properties.unwrap_or_else(|| serde_json::json!({})));
+    Ok(())
+}
+
+/// Everything recorded on this computer, newest first
+#[tauri::command]
+pub fn get_usage_events(
+    state: State<AnalyticsState>,
+    settings: State<SettingsState>,
+    limit: Option<usize>,
+) -> UsageReport {
+    let settings = settings.get();
+    let store = state.store.lock().unwrap();
+    UsageReport {
+        enabled: settings.telemetry_opt_in,
+        endpoint: settings.analytics_endpoint,
+        install_id: store.install_id.clone(),
+        pending: store.events.iter().filter(|event| !event.uploaded).count(),
+        events: store.events.iter().rev().take(limit.unwrap_or(usize::MAX)).cloned().collect(),
+    }
+}
+
+/// Upload pending events now, returns how many were sent
+#[tauri::command]
+pub async fn upload_usage_events(app_handle: AppHandle) -> Result<usize, String> {
+    upload_pending(&app_handle).await
+}
+
+/// Delete every recorded event and start over with a new install id
+#[tauri::command]
+pub fn purge_usage_events(state: State<AnalyticsState>) {
+    let mut store = state.store.lock().unwrap();
+    *store = AnalyticsStore {
+        install_id: generate_install_id(),
+        events: Vec::new(),
+    };
+    state.save(&store);
+    println!("[analytics] 🗑️ Usage events purged");
+}
@@ -0,0 +1,303 @@
+/// Robot apps (behaviors) management
+///
+/// Lists, starts and stops apps through the daemon API, and installs them either
+/// through the daemon (catalog apps) or with the local venv's pip (local folders,
+/// wheels, PyPI requirements and git URLs, which the daemon can't install).
+/// Native binaries installed with an app are re-signed on macOS.
+///
+/// Emits `app-job-progress` with { job, app, status, message, percent } while
+/// installing / uninstalling.
+///
+/// Apps with a sandboxed execution policy run outside the daemon (see sandbox).
+
+#[cfg(target_os = "linux")]
+mod netns;
+pub mod sandbox;
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::daemon_api::{AppInfo, AppStatus, DaemonClient};
+use crate::local_proxy::LocalProxyState;
+
+const JOB_POLL_INTERVAL: Duration = Duration::from_millis(1000);
+/// Installs download dependencies (torch, models...): be generous
+const JOB_TIMEOUT: Duration = Duration::from_secs(20 * 60);
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+/// Where to install an app from
+#[derive(Debug, Deserialize, Clone)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AppSource {
+    /// An entry of the daemon catalog (AppInfo as listed by /api/apps/list-available)
+    Catalog { info: serde_json::Value },
+    /// A project folder, wheel or sdist on this computer
+    Local { path: String },
+    /// Anything pip accepts: PyPI name, `git+https://...` URL
+    Pip { requirement: String },
+}
+
+#[derive(Debug, Serialize, Clone)]
+struct AppJobProgress {
+    /// "install" | "remove"
+    job: &'static str,
+    app: String,
+    /// "running" | "completed" | "failed"
+    status: &'static str,
+    message: String,
+    /// Download percentage of the current package (local pip installs only)
+    percent: Option<u8>,
+}
+
+fn emit_progress(app_handle: &AppHandle, job: &'static str, app: &str, status: &'static str, message: String, percent: Option<u8>) {
+    let _ = app_handle.emit("app-job-progress", AppJobProgress {
+        job,
+        app: app.to_string(),
+        status,
+        message,
+        percent,
+    });
+}
+
+// ============================================================================
+// HELPER FUNCTIONS
+// ============================================================================
+
+/// Follow a daemon install / remove job, forwarding its new log lines, until it ends
+async fn follow_daemon_job(
+    app_handle: &AppHandle,
+    client: &DaemonClient,
+    job: &'static str,
+    app: &str,
+    job_id: &str,
+) -> Result<(), String> {
+    let deadline = Instant::now() + JOB_TIMEOUT;
+    let mut logs_seen = 0;
+
+    while Instant::now() < deadline {
+        tokio::time::sleep(JOB_POLL_INTERVAL).await;
+        // The daemon can be busy (or a system popup pending): keep polling
+        let Ok(status) = client.job_status(job_id).await else {
+            continue;
+        };
+        for line in status.logs.iter().skip(logs_seen) {
+            emit_progress(app_handle, job, app, "running", line.clone(), None);
+        }
+        logs_seen = logs_seen.max(status.logs.len());
+
+        match status.outcome() {
+            Some(true) => return Ok(()),
+            Some(false) => {
+                let summary = status.logs.iter().rev().take(2).rev().cloned().collect::<Vec<_>>().join(" | ");
+                return Err(format!("{} of {} failed: {}", job, app, summary));
+            }
+            None => {}
+        }
+    }
+    Err(format!("{} of {} timed out", job, app))
+}
+
+/// pip install into the local venv, forwarding pip progress
+async fn pip_install(app_handle: &AppHandle, app: &str, target: String) -> Result<(), String> {
+    let venv_path = crate::update::get_local_venv_path(app_handle)?;
+    let pip_path = crate::update::get_pip_path(&venv_path)?;
+    let args = vec!["install".to_string(), target];
+
+    let handle = app_handle.clone();
+    let app_name = app.to_string();
+    tokio::task::spawn_blocking(move || {
+        crate::update::run_pip_with_progress(&pip_path, &args, |progress| {
+            emit_progress(&handle, "install", &app_name, "running", progress.message, progress.percent);
+        })
+    })
+    .await
+    .map_err(|e| format!("pip task failed: {}", e))?
+}
+
+/// Re-sign native binaries an install added to the venv (macOS Team ID)
+async fn resign_python_binaries() {
+    #[cfg(target_os = "macos")]
+    let signed = crate::signing::sign_python_binaries().await;
+    #[cfg(not(target_os = "macos"))]
+    let signed = crate::signing::sign_python_binaries();
+    if let Err(e) = signed {
+        eprintln!("[apps] ⚠️ Failed to re-sign Python binaries: {}", e);
+    }
+}
+
+/// Start an app through the daemon, or sandboxed if its policy says so (one app at a time)
+pub(crate) async fn launch_app(app_handle: &AppHandle, client: &DaemonClient, name: &str) -> Result<(), String> {
+    let policy = app_handle.state::<sandbox::SandboxState>().policy_for(name);
+    if !policy.sandboxed {
+        sandbox::stop(app_handle);
+        return client.start_app(name).await;
+    }
+
+    let proxy = app_handle.state::<Arc<LocalProxyState>>();
+    if proxy.target_host.read().await.is_some() {
+        return Err("Sandboxed apps need the robot on USB or in simulation (the app runs on this computer)".to_string());
+    }
+    if client.running_app().await.is_some() {
+        client.stop_current_app().await?;
+    }
+    let handle = app_handle.clone();
+    let name = name.to_string();
+    tauri::async_runtime::spawn_blocking(move || sandbox::launch(&handle, &name, &policy))
+        .await
+        .map_err(|e| format!("Sandbox launch task failed: {}", e))?
+}
+
+/// Stop the running app, sandboxed or in the daemon
+pub(crate) async fn stop_running_app(app_handle: &AppHandle, client: &DaemonClient) -> Result<(), String> {
+    let handle = app_handle.clone();
+    let stopped = tauri::async_runtime::spawn_blocking(move || sandbox::stop(&handle))
+        .await
+        .unwrap_or(false);
+    if stopped {
+        return Ok(());
+    }
+    client.stop_current_app().await
+}
+
+/// Status of the sandboxed app if one was launched, else of the daemon's app
+pub(crate) async fn running_app_status(app_handle: &AppHandle, client: &DaemonClient) -> Result<Option<AppStatus>, String> {
+    match sandbox::status(app_handle) {
+        Some(status) => Ok(Some(status)),
+        None => client.current_app_status().await,
+    }
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+#[tauri::command]
+pub async fn list_installed_apps(proxy: State<'_, Arc<LocalProxyState>>) -> Result<Vec<AppInfo>, String> {
+    DaemonClient::for_proxy(&proxy).await.installed_apps().await
+}
+
+/// App starting or running in the daemon (None if idle)
+#[tauri::command]
+pub async fn get_current_app(
+    app_handle: AppHandle,
+    proxy: State<'_, Arc<LocalProxyState>>,
+) -> Result<Option<AppStatus>, String> {
+    let client = DaemonClient::for_proxy(&proxy).await;
+    running_app_status(&app_handle, &client).await
+}
+
+#[tauri::command]
+pub async fn start_app(app_handle: AppHandle, proxy: State<'_, Arc<LocalProxyState>>, name: String) -> Result<(), String> {
+    println!("[apps] ▶️ Starting app {}", name);
+    let client = DaemonClient::for_proxy(&proxy).await;
+    launch_app(&app_handle, &client, &name).await?;
+    crate::onboarding::mark_done(&app_handle, crate::onboarding::OnboardingStep::FirstAppRun);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn stop_app(app_handle: AppHandle, proxy: State<'_, Arc<LocalProxyState>>) -> Result<(), String> {
+    println!("[apps] ⏹️ Stopping current app");
+    let client = DaemonClient::for_proxy(&proxy).await;
+    stop_running_app(&app_handle, &client).await
+}
+
+/// Install an app, following the job until it ends
+#[tauri::command]
+pub async fn install_app(
+    app_handle: AppHandle,
+    proxy: State<'_, Arc<LocalProxyState>>,
+    source: AppSource,
+) -> Result<String, String> {
+    let client = DaemonClient::for_proxy(&proxy).await;
+
+    let source_kind = match &source {
+        AppSource::Catalog { .. } => "catalog",
+        AppSource::Local { .. } => "local",
+        AppSource::Pip { .. } => "pip",
+    };
+    let (app, result) = match source {
+        AppSource::Catalog { info } => {
+            let app = info["name"].as_str().ok_or("App info has no name")?.to_string();
+            emit_progress(&app_handle, "install", &app, "running", format!("Installing {}...", app), None);
+            let result = match client.install_app(&info).await {
+                Ok(job_id) => follow_daemon_job(&app_handle, &client, "install", &app, &job_id).await,
+                Err(e) => Err(e),
+            };
+            (app, result)
+        }
+        AppSource::Local { .. } | AppSource::Pip { .. } if proxy.target_host.read().await.is_some() => {
+            return Err("Local installs need the robot on USB or in simulation (the app runs where the daemon runs)".to_string());
+        }
+        AppSource::Local { path } => {
+            let path_buf = PathBuf::from(&path);
+            if !path_buf.exists() {
+                return Err(format!("Path not found: {}", path));
+            }
+            let app = path_buf
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_else(|| path.clone());
+            emit_progress(&app_handle, "install", &app, "running", format!("Installing {} from {}...", app, path), None);
+            let result = pip_install(&app_handle, &app, path).await;
+            (app, result)
+        }
+        AppSource::Pip { requirement } => {
+            let requirement = requirement.trim().to_string();
+            if requirement.is_empty() || requirement.starts_with('-') {
+                return Err(format!("Invalid requirement: {:?}", requirement));
+            }
+            emit_progress(&app_handle, "install", &requirement, "running", format!("Installing {}...", requirement), None);
+            let result = pip_install(&app_handle, &requirement, requirement.clone()).await;
+            (requirement, result)
+        }
+    };
+
+    if let Err(e) = result {
+        eprintln!("[apps] ❌ {}", e);
+        emit_progress(&app_handle, "install", &app, "failed", e.clone(), None);
+        return Err(e);
+    }
+
+    resign_python_binaries().await;
+    println!("[apps] ✅ Installed {}", app);
+    crate::analytics::record(&app_handle, "app_install", serde_json::json!({ "source": source_kind }));
+    emit_progress(&app_handle, "install", &app, "completed", format!("{} installed", app), None);
+    Ok(app)
+}
+
+/// Uninstall an app through the daemon, following the job until it ends
+#[tauri::command]
+pub async fn uninstall_app(
+    app_handle: AppHandle,
+    proxy: State<'_, Arc<LocalProxyState>>,
+    name: String,
+) -> Result<(), String> {
+    crate::kiosk::ensure_allowed("Uninstalling apps")?;
+    crate::lock::ensure_unlocked("Uninstalling apps")?;
+    let client = DaemonClient::for_proxy(&proxy).await;
+    emit_progress(&app_handle, "remove", &name, "running", format!("Removing {}...", name), None);
+
+    let result = match client.remove_app(&name).await {
+        Ok(job_id) => follow_daemon_job(&app_handle, &client, "remove", &name, &job_id).await,
+        Err(e) => Err(e),
+    };
+    match result {
+        Ok(()) => {
+            println!("[apps] 🗑️ Removed {}", name);
+            emit_progress(&app_handle, "remove", &name, "completed", format!("{} removed", name), None);
+            Ok(())
+        }
+        Err(e) => {
+            eprintln!("[apps] ❌ {}", e);
+            emit_progress(&app_handle, "remove", &name, "failed", e.clone(), None);
+            Err(e)
+        }
+    }
+}
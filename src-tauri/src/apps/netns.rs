@@ -0,0 +1,346 @@
+/// Network isolation of sandboxed apps (Linux)
+///
+/// The app runs in its own user and network namespaces: a loopback interface and nothing
+/// else, so no route out, no DNS, no UDP. Between fork and exec the child opens listening
+/// sockets on its loopback for the daemon ports and hands them to the desktop app over a
+/// socket pair; the desktop app accepts on them and relays each connection to the real
+/// daemon on the host's localhost (the local proxy when the robot is on WiFi).
+///
+/// Needs unprivileged user namespaces. Kernels that disable them make the launch fail with
+/// the error of `unshare`, the app is never started without its isolation.
+
+use std::ffi::CStr;
+use std::io::{self, Read, Write};
+use std::net::{Ipv4Addr, Shutdown, TcpListener, TcpStream};
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd, RawFd};
+use std::os::unix::process::CommandExt;
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Most ports forwarded into a namespace (daemon ports and the SDK port)
+const MAX_PORTS: usize = 8;
+
+/// Socket pair set up by `isolate`, turned into a PortForwarder once the app is spawned
+pub struct PendingForwarder {
+    parent: OwnedFd,
+    child: OwnedFd,
+    ports: Vec<u16>,
+}
+
+/// Relays connections to the daemon ports inside the app's namespace to the host; stops
+/// when dropped
+pub struct PortForwarder {
+    listeners: Vec<Arc<TcpListener>>,
+    stopped: Arc<AtomicBool>,
+}
+
+impl Drop for PortForwarder {
+    fn drop(&mut self) {
+        self.stopped.store(true, Ordering::SeqCst);
+        // Wakes the threads blocked in accept (the Arc keeps the fd open until they exit)
+        for listener in &self.listeners {
+            unsafe {
+                libc::shutdown(listener.as_raw_fd(), libc::SHUT_RDWR);
+            }
+        }
+    }
+}
+
+fn check(result: libc::c_int) -> io::Result<libc::c_int> {
+    if result < 0 {
+        Err(io::Error::last_os_error())
+    } else {
+        Ok(result)
+    }
+}
+
+/// Start `command` in its own network namespace, with `ports` of its loopback forwarded
+/// to the host's. Call `PendingForwarder::start` once the command is spawned.
+pub fn isolate(command: &mut Command, ports: &[u16]) -> io::Result<PendingForwarder> {
+    if ports.len() > MAX_PORTS {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "Too many ports to forward"));
+    }
+
+    let mut fds = [0; 2];
+    check(unsafe { libc::socketpair(libc::AF_UNIX, libc::SOCK_SEQPACKET | libc::SOCK_CLOEXEC, 0, fds.as_mut_ptr()) })?;
+    let (parent, child) = unsafe { (OwnedFd::from_raw_fd(fds[0]), OwnedFd::from_raw_fd(fds[1])) };
+
+    // Everything the child needs is prepared here: no allocation between fork and exec
+    let (uid, gid) = unsafe { (libc::getuid(), libc::getgid()) };
+    let uid_map = format!("{} {} 1", uid, uid).into_bytes();
+    let gid_map = format!("{} {} 1", gid, gid).into_bytes();
+    let mut port_list = [0u16; MAX_PORTS];
+    port_list[..ports.len()].copy_from_slice(ports);
+    let port_count = ports.len();
+    let channel = child.as_raw_fd();
+
+    unsafe {
+        command.pre_exec(move || enter_namespace(channel, &port_list[..port_count], &uid_map, &gid_map));
+    }
+    Ok(PendingForwarder {
+        parent,
+        child,
+        ports: ports.to_vec(),
+    })
+}
+
+impl PendingForwarder {
+    /// Collect the listening sockets sent by the spawned child and start relaying
+    pub fn start(self) -> io::Result<PortForwarder> {
+        // The child end is only needed by the child
+        drop(self.child);
+        let listeners = receive_fds(self.parent.as_raw_fd(), self.ports.len())?
+            .into_iter()
+            .map(|fd| Arc::new(unsafe { TcpListener::from_raw_fd(fd) }))
+            .collect::<Vec<_>>();
+
+        let stopped = Arc::new(AtomicBool::new(false));
+        for (listener, &port) in listeners.iter().zip(&self.ports) {
+            let listener = listener.clone();
+            let stopped = stopped.clone();
+            std::thread::spawn(move || accept_loop(&listener, port, &stopped));
+        }
+        Ok(PortForwarder { listeners, stopped })
+    }
+}
+
+fn accept_loop(listener: &TcpListener, port: u16, stopped: &AtomicBool) {
+    while !stopped.load(Ordering::SeqCst) {
+        match listener.accept() {
+            Ok((inside, _)) => {
+                std::thread::spawn(move || relay(inside, port));
+            }
+            Err(_) if stopped.load(Ordering::SeqCst) => break,
+            Err(_) => std::thread::sleep(Duration::from_millis(100)),
+        }
+    }
+}
+
+/// Copy both ways between a connection from the app and the daemon on the host
+fn relay(inside: TcpStream, port: u16) {
+    let Ok(outside) = TcpStream::connect((Ipv4Addr::LOCALHOST, port)) else {
+        return;
+    };
+    let (Ok(inside_read), Ok(outside_write)) = (inside.try_clone(), outside.try_clone()) else {
+        return;
+    };
+    let upstream = std::thread::spawn(move || copy_then_close(inside_read, outside_write));
+    copy_then_close(outside, inside);
+    let _ = upstream.join();
+}
+
+fn copy_then_close(mut from: TcpStream, mut to: TcpStream) {
+    let mut buffer = [0u8; 16 * 1024];
+    while let Ok(read) = from.read(&mut buffer) {
+        if read == 0 || to.write_all(&buffer[..read]).is_err() {
+            break;
+        }
+    }
+    let _ = to.shutdown(Shutdown::Write);
+}
+
+// ============================================================================
+// CHILD SIDE (between fork and exec: syscalls only, no allocation)
+// ============================================================================
+
+unsafe fn write_proc_file(path: &CStr, content: &[u8]) -> io::Result<()> {
+    let fd = check(libc::open(path.as_ptr(), libc::O_WRONLY | libc::O_CLOEXEC))?;
+    let written = libc::write(fd, content.as_ptr().cast(), content.len());
+    libc::close(fd);
+    if written < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// `struct ifreq` with the flags member of its union
+#[repr(C)]
+struct IfReqFlags {
+    name: [u8; libc::IFNAMSIZ],
+    flags: libc::c_short,
+    _pad: [u8; 22],
+}
+
+/// A new network namespace has its loopback down
+unsafe fn loopback_up() -> io::Result<()> {
+    let fd = check(libc::socket(libc::AF_INET, libc::SOCK_DGRAM | libc::SOCK_CLOEXEC, 0))?;
+    let mut request = IfReqFlags {
+        name: [0; libc::IFNAMSIZ],
+        flags: 0,
+        _pad: [0; 22],
+    };
+    request.name[..2].copy_from_slice(b"lo");
+    let result = check(libc::ioctl(fd, libc::SIOCGIFFLAGS, &mut request as *mut IfReqFlags)).and_then(|_| {
+        request.flags |= libc::IFF_UP as libc::c_short;
+        check(libc::ioctl(fd, libc::SIOCSIFFLAGS, &request as *const IfReqFlags))
+    });
+    libc::close(fd);
+    result.map(|_| ())
+}
+
+unsafe fn listen_on_loopback(port: u16) -> io::Result<RawFd> {
+    let fd = check(libc::socket(libc::AF_INET, libc::SOCK_STREAM | libc::SOCK_CLOEXEC, 0))?;
+    let address = libc::sockaddr_in {
+        sin_family: libc::AF_INET as libc::sa_family_t,
+        sin_port: port.to_be(),
+        sin_addr: libc::in_addr {
+            s_addr: u32::from(Ipv4Addr::LOCALHOST).to_be(),
+        },
+        sin_zero: [0; 8],
+    };
+    let bound = check(libc::bind(
+        fd,
+        &address as *const libc::sockaddr_in as *const libc::sockaddr,
+        std::mem::size_of::<libc::sockaddr_in>() as libc::socklen_t,
+    ))
+    .and_then(|_| check(libc::listen(fd, 128)));
+    if let Err(e) = bound {
+        libc::close(fd);
+        return Err(e);
+    }
+    Ok(fd)
+}
+
+/// Control message buffer for up to MAX_PORTS descriptors (aligned for cmsghdr)
+#[repr(C, align(8))]
+struct ControlBuffer([u8; 64]);
+
+unsafe fn send_fds(channel: RawFd, fds: &[RawFd]) -> io::Result<()> {
+    let mut byte = [0u8; 1];
+    let mut iov = libc::iovec {
+        iov_base: byte.as_mut_ptr().cast(),
+        iov_len: 1,
+    };
+    let mut control = ControlBuffer([0; 64]);
+    let fds_len = std::mem::size_of_val(fds) as libc::c_uint;
+    let mut message: libc::msghdr = std::mem::zeroed();
+    message.msg_iov = &mut iov;
+    message.msg_iovlen = 1;
+    message.msg_control = control.0.as_mut_ptr().cast();
+    message.msg_controllen = libc::CMSG_SPACE(fds_len) as _;
+
+    let header = libc::CMSG_FIRSTHDR(&message);
+    (*header).cmsg_level = libc::SOL_SOCKET;
+    (*header).cmsg_type = libc::SCM_RIGHTS;
+    (*header).cmsg_len = libc::CMSG_LEN(fds_len) as _;
+    std::ptr::copy_nonoverlapping(fds.as_ptr(), libc::CMSG_DATA(header).cast::<RawFd>(), fds.len());
+
+    if libc::sendmsg(channel, &message, 0) < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+fn enter_namespace(channel: RawFd, ports: &[u16], uid_map: &[u8], gid_map: &[u8]) -> io::Result<()> {
+    unsafe {
+        check(libc::unshare(libc::CLONE_NEWUSER | libc::CLONE_NEWNET))?;
+        // Keep our own ids inside the namespace (else files show up as owned by nobody)
+        write_proc_file(c"/proc/self/setgroups", b"deny")?;
+        write_proc_file(c"/proc/self/uid_map", uid_map)?;
+        write_proc_file(c"/proc/self/gid_map", gid_map)?;
+        loopback_up()?;
+
+        let mut fds = [0 as RawFd; MAX_PORTS];
+        for (fd, &port) in fds.iter_mut().zip(ports) {
+            *fd = listen_on_loopback(port)?;
+        }
+        let fds = &fds[..ports.len()];
+        let sent = send_fds(channel, fds);
+        // The desktop app has its own copies now (and SOCK_CLOEXEC closes them at exec anyway)
+        for &fd in fds {
+            libc::close(fd);
+        }
+        sent
+    }
+}
+
+// ============================================================================
+// PARENT SIDE
+// ============================================================================
+
+fn receive_fds(channel: RawFd, count: usize) -> io::Result<Vec<RawFd>> {
+    unsafe {
+        let mut byte = [0u8; 1];
+        let mut iov = libc::iovec {
+            iov_base: byte.as_mut_ptr().cast(),
+            iov_len: 1,
+        };
+        let mut control = ControlBuffer([0; 64]);
+        let mut message: libc::msghdr = std::mem::zeroed();
+        message.msg_iov = &mut iov;
+        message.msg_iovlen = 1;
+        message.msg_control = control.0.as_mut_ptr().cast();
+        message.msg_controllen = control.0.len() as _;
+
+        // Sent before exec, so already queued once spawn returned
+        if libc::recvmsg(channel, &mut message, libc::MSG_DONTWAIT | libc::MSG_CMSG_CLOEXEC) < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        let mut fds = Vec::with_capacity(count);
+        let mut header = libc::CMSG_FIRSTHDR(&message);
+        while !header.is_null() {
+            if (*header).cmsg_level == libc::SOL_SOCKET && (*header).cmsg_type == libc::SCM_RIGHTS {
+                let data_len = (*header).cmsg_len as usize - libc::CMSG_LEN(0) as usize;
+                let data = libc::CMSG_DATA(header).cast::<RawFd>();
+                for i in 0..data_len / std::mem::size_of::<RawFd>() {
+                    fds.push(std::ptr::read_unaligned(data.add(i)));
+                }
+            }
+            header = libc::CMSG_NXTHDR(&message, header);
+        }
+        if fds.len() != count {
+            for fd in fds {
+                libc::close(fd);
+            }
+            return Err(io::Error::other("The sandboxed app did not send its forwarded ports"));
+        }
+        Ok(fds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Stdio;
+
+    #[test]
+    fn forwards_only_the_daemon_ports() {
+        if std::process::Command::new("unshare").args(["--user", "--net", "true"]).status().map_or(true, |s| !s.success()) {
+            eprintln!("unprivileged user namespaces unavailable, skipped");
+            return;
+        }
+        // Stand-in for the daemon on the host
+        let daemon = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).unwrap();
+        let port = daemon.local_addr().unwrap().port();
+        std::thread::spawn(move || {
+            for mut stream in daemon.incoming().flatten() {
+                let _ = stream.write_all(b"daemon");
+            }
+        });
+
+        let mut command = Command::new("python3");
+        command.args([
+            "-c",
+            &format!(
+                "import socket\n\
+                 print(socket.create_connection(('127.0.0.1', {port})).recv(16).decode())\n\
+                 try:\n    socket.create_connection(('1.1.1.1', 80), timeout=2); print('out')\n\
+                 except OSError: print('blocked')"
+            ),
+        ]);
+        command.stdout(Stdio::piped());
+        let pending = isolate(&mut command, &[port]).unwrap();
+        let child = match command.spawn() {
+            Ok(child) => child,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return,
+            Err(e) => panic!("{}", e),
+        };
+        let _forwarder = pending.start().unwrap();
+
+        let output = child.wait_with_output().unwrap();
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "daemon\nblocked\n");
+    }
+}
@@ -0,0 +1,394 @@
+/// Execution policy for robot apps
+///
+/// The daemon runs apps as its own subprocesses: same environment, same working
+/// directory, same privileges. An app whose policy is `sandboxed` is launched by the
+/// desktop app from the venv instead, and reaches the robot through the SDK like any
+/// script:
+/// - environment scrubbed down to a minimal set plus the policy's allowlist
+/// - its own working directory and HOME, `<app data>/app-sandboxes/<app>`
+/// - optionally no network but the daemon (Linux): its own network namespace, where only
+///   the daemon and SDK ports of localhost are reachable, relayed to the host (see netns)
+///
+/// Policies are per app, with a default for apps without one, in `<app data>/app_policies.json`.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager, State};
+
+use crate::daemon_api::{AppInfo, AppStatus};
+
+const POLICIES_FILE: &str = "app_policies.json";
+const SANDBOXES_DIR: &str = "app-sandboxes";
+/// Time an app gets to exit after SIGTERM before it is killed
+const STOP_GRACE: Duration = Duration::from_secs(3);
+/// SDK connection to the daemon (zenoh), forwarded with NetworkAccess::DaemonOnly
+#[cfg(target_os = "linux")]
+const SDK_PORT: u16 = 7447;
+/// Variables every app keeps (the interpreter and locale need them)
+const BASE_ENV: &[&str] = &["PATH", "LANG", "LC_ALL", "TZ", "SYSTEMROOT", "WINDIR"];
+
+/// Run the app's entry point the way the daemon does
+const LAUNCHER: &str = r#"
+import sys
+from importlib.metadata import entry_points
+
+name = sys.argv[1]
+matches = [ep for ep in entry_points(group="reachy_mini_apps") if ep.name == name]
+if not matches:
+    sys.exit(f"App {name} is not installed")
+matches[0].load()().wrapped_run()
+"#;
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+/// Outgoing network of a sandboxed app
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum NetworkAccess {
+    #[default]
+    Unrestricted,
+    /// Own network namespace (Linux): no network but the daemon and SDK ports on localhost
+    #[serde(alias = "daemon_tcp_ports_only")]
+    DaemonOnly,
+}
+
+impl NetworkAccess {
+    /// Shown with the app's status, so the restriction isn't mistaken for no network at all
+    pub fn label(self) -> &'static str {
+        match self {
+            NetworkAccess::Unrestricted => "unrestricted",
+            NetworkAccess::DaemonOnly => "daemon only (isolated network namespace)",
+        }
+    }
+}
+
+/// Also reads the former `network: bool` (false restricted the network to the daemon)
+fn deserialize_network<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<NetworkAccess, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Stored {
+        Flag(bool),
+        Access(NetworkAccess),
+    }
+    Ok(match Stored::deserialize(deserializer)? {
+        Stored::Flag(true) => NetworkAccess::Unrestricted,
+        Stored::Flag(false) => NetworkAccess::DaemonOnly,
+        Stored::Access(access) => access,
+    })
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct ExecutionPolicy {
+    /// Launch outside the daemon with the restrictions below
+    pub sandboxed: bool,
+    /// Variables passed through besides PATH, locale and time zone (e.g. HF_TOKEN)
+    pub env_allowlist: Vec<String>,
+    #[serde(deserialize_with = "deserialize_network")]
+    pub network: NetworkAccess,
+}
+
+impl Default for ExecutionPolicy {
+    fn default() -> Self {
+        Self {
+            sandboxed: false,
+            env_allowlist: Vec::new(),
+            network: NetworkAccess::Unrestricted,
+        }
+    }
+}
+
+impl ExecutionPolicy {
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(name) = self.env_allowlist.iter().find(|name| name.is_empty() || name.contains('=')) {
+            return Err(format!("Invalid environment variable name: {:?}", name));
+        }
+        if self.network == NetworkAccess::DaemonOnly && !cfg!(target_os = "linux") {
+            return Err("Isolating an app's network is only supported on Linux".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct AppPolicies {
+    /// For apps without their own policy
+    pub default: ExecutionPolicy,
+    pub apps: BTreeMap<String, ExecutionPolicy>,
+}
+
+struct SandboxedApp {
+    name: String,
+    network: NetworkAccess,
+    child: Child,
+    /// Relays the daemon ports into the app's namespace (NetworkAccess::DaemonOnly)
+    #[cfg(target_os = "linux")]
+    _forwarder: Option<super::netns::PortForwarder>,
+}
+
+pub struct SandboxState {
+    policies: Mutex<AppPolicies>,
+    path: Mutex<Option<PathBuf>>,
+    running: Mutex<Option<SandboxedApp>>,
+}
+
+// ============================================================================
+// HELPER FUNCTIONS
+// ============================================================================
+
+impl SandboxState {
+    pub fn new() -> Self {
+        Self {
+            policies: Mutex::new(AppPolicies::default()),
+            path: Mutex::new(None),
+            running: Mutex::new(None),
+        }
+    }
+
+    /// Load the policies from the app data directory (defaults if missing or corrupted)
+    pub fn load(&self, app_data_dir: &Path) {
+        let path = app_data_dir.join(POLICIES_FILE);
+        let policies = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        *self.policies.lock().unwrap() = policies;
+        *self.path.lock().unwrap() = Some(path);
+    }
+
+    pub fn policies(&self) -> AppPolicies {
+        self.policies.lock().unwrap().clone()
+    }
+
+    /// Policy of an app (the default one unless it has its own)
+    pub fn policy_for(&self, name: &str) -> ExecutionPolicy {
+        let policies = self.policies.lock().unwrap();
+        policies.apps.get(name).unwrap_or(&policies.default).clone()
+    }
+
+    /// Apply a change to the policies and persist them
+    fn update(&self, change: impl FnOnce(&mut AppPolicies)) -> Result<AppPolicies, String> {
+        let mut policies = self.policies.lock().unwrap();
+        change(&mut policies);
+
+        if let Some(path) = self.path.lock().unwrap().as_ref() {
+            let content = serde_json::to_string_pretty(&*policies)
+                .map_err(|e| format!("Failed to serialize app policies: {}", e))?;
+            std::fs::write(path, content).map_err(|e| format!("Failed to write app policies: {}", e))?;
+        }
+        Ok(policies.clone())
+    }
+}
+
+impl Default for SandboxState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// App names become directory names: keep them to what entry points use
+fn check_app_name(name: &str) -> Result<(), String> {
+    let valid = !name.is_empty()
+        && !name.starts_with('.')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("Invalid app name: {:?}", name))
+    }
+}
+
+/// Print the app's output with its name, like the daemon logs
+fn forward_output(name: &str, output: impl Read + Send + 'static) {
+    let name = name.to_string();
+    std::thread::spawn(move || {
+        for line in BufReader::new(output).lines().map_while(Result::ok) {
+            println!("[app:{}] {}", name, line);
+        }
+    });
+}
+
+fn terminate(mut app: SandboxedApp) {
+    #[cfg(not(target_os = "windows"))]
+    {
+        unsafe {
+            libc::kill(app.child.id() as libc::pid_t, libc::SIGTERM);
+        }
+        let deadline = Instant::now() + STOP_GRACE;
+        while Instant::now() < deadline {
+            if let Ok(Some(_)) = app.child.try_wait() {
+                println!("[apps] ⏹️ Sandboxed app {} stopped", app.name);
+                return;
+            }
+            std::thread::sleep(Duration::from_millis(100));
+        }
+        println!("[apps] ⚠️ Sandboxed app {} still running, killing it", app.name);
+    }
+    let _ = app.child.kill();
+    let _ = app.child.wait();
+}
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// Launch an app from the venv with its policy (stops the previous sandboxed app)
+pub fn launch(app_handle: &AppHandle, name: &str, policy: &ExecutionPolicy) -> Result<(), String> {
+    check_app_name(name)?;
+    stop(app_handle);
+
+    let venv_path = crate::update::get_local_venv_path(app_handle)?;
+    let python = crate::update::get_python_path(&venv_path)?;
+    let workdir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?
+        .join(SANDBOXES_DIR)
+        .join(name);
+    let tmp = workdir.join("tmp");
+    std::fs::create_dir_all(&tmp).map_err(|e| format!("Failed to create {:?}: {}", tmp, e))?;
+
+    let mut command = Command::new(&python);
+    command
+        .args(["-c", LAUNCHER, name])
+        .current_dir(&workdir)
+        .env_clear()
+        .envs(
+            BASE_ENV
+                .iter()
+                .map(|var| var.to_string())
+                .chain(policy.env_allowlist.iter().cloned())
+                .filter_map(|var| std::env::var(&var).ok().map(|value| (var, value))),
+        )
+        .env("HOME", &workdir)
+        .env("USERPROFILE", &workdir)
+        .env("TMPDIR", &tmp)
+        .env("TEMP", &tmp)
+        .env("TMP", &tmp)
+        .env("PYTHONNOUSERSITE", "1")
+        .env("PYTHONUNBUFFERED", "1")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    #[cfg(target_os = "linux")]
+    let pending_forwarder = match policy.network {
+        NetworkAccess::DaemonOnly => {
+            let ports: Vec<u16> = crate::daemon::DAEMON_PORTS.iter().copied().chain(std::iter::once(SDK_PORT)).collect();
+            Some(super::netns::isolate(&mut command, &ports).map_err(|e| format!("Failed to isolate {}'s network: {}", name, e))?)
+        }
+        NetworkAccess::Unrestricted => None,
+    };
+
+    let mut child = command
+        .spawn()
+        .map_err(|e| format!("Failed to launch {} in its sandbox: {}", name, e))?;
+    #[cfg(target_os = "linux")]
+    let forwarder = match pending_forwarder.map(|pending| pending.start()).transpose() {
+        Ok(forwarder) => forwarder,
+        Err(e) => {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(format!("Failed to forward the daemon ports to {}: {}", name, e));
+        }
+    };
+    if let Some(stdout) = child.stdout.take() {
+        forward_output(name, stdout);
+    }
+    if let Some(stderr) = child.stderr.take() {
+        forward_output(name, stderr);
+    }
+    println!(
+        "[apps] 🧱 Sandboxed app {} started in {:?} (network: {})",
+        name,
+        workdir,
+        policy.network.label()
+    );
+    *app_handle.state::<SandboxState>().running.lock().unwrap() = Some(SandboxedApp {
+        name: name.to_string(),
+        network: policy.network,
+        child,
+        #[cfg(target_os = "linux")]
+        _forwarder: forwarder,
+    });
+    Ok(())
+}
+
+/// Stop the sandboxed app, if any (returns whether one was running)
+pub fn stop(app_handle: &AppHandle) -> bool {
+    let running = app_handle.state::<SandboxState>().running.lock().unwrap().take();
+    match running {
+        Some(app) => {
+            terminate(app);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Status of the sandboxed app, in the daemon's format (None if none was launched)
+pub fn status(app_handle: &AppHandle) -> Option<AppStatus> {
+    let state = app_handle.state::<SandboxState>();
+    let mut running = state.running.lock().unwrap();
+    let app = running.as_mut()?;
+    let (state, error) = match app.child.try_wait() {
+        Ok(None) => ("running", None),
+        Ok(Some(exit)) if exit.success() => ("done", None),
+        Ok(Some(exit)) => ("error", Some(format!("App exited with {}", exit))),
+        Err(e) => ("error", Some(e.to_string())),
+    };
+    Some(AppStatus {
+        info: AppInfo {
+            name: app.name.clone(),
+            source_kind: Some("sandboxed".to_string()),
+            extra: serde_json::Map::from_iter([("network".to_string(), app.network.label().into())]),
+        },
+        state: state.to_string(),
+        error,
+    })
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+#[tauri::command]
+pub fn get_app_policies(state: State<SandboxState>) -> AppPolicies {
+    state.policies()
+}
+
+/// Set the policy of an app (`name` None: the default policy)
+#[tauri::command]
+pub fn set_app_policy(
+    state: State<SandboxState>,
+    name: Option<String>,
+    policy: ExecutionPolicy,
+) -> Result<AppPolicies, String> {
+    crate::lock::ensure_unlocked("Changing app sandbox policies")?;
+    policy.validate()?;
+    match name {
+        Some(name) => {
+            check_app_name(&name)?;
+            state.update(|policies| {
+                policies.apps.insert(name, policy);
+            })
+        }
+        None => state.update(|policies| policies.default = policy),
+    }
+}
+
+/// Remove an app's own policy (it follows the default one again)
+#[tauri::command]
+pub fn remove_app_policy(state: State<SandboxState>, name: String) -> Result<AppPolicies, String> {
+    state.update(|policies| {
+        policies.apps.remove(&name);
+    })
+}
@@ -0,0 +1,295 @@
+/// Sound and voice packs
+///
+/// Downloadable packs of sounds / voice clips for the robot: a zip archive fetched from
+/// its URL (through the download cache), checked against its published sha256 and
+/// extracted to `<app data>/sound-packs/<id>/`. Installed packs and the active one are
+/// kept in `<app data>/sound_packs.json`.
+///
+/// The local daemon finds the active pack through `REACHY_MINI_SOUND_PACK_DIR` in its
+/// environment, so a change applies at the next daemon start (commands tell when a
+/// restart is needed). A robot reached over WiFi keeps its own sounds.
+
+use serde::{Deserialize, Serialize};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager, State};
+
+use crate::daemon::DaemonState;
+
+const PACKS_FILE: &str = "sound_packs.json";
+const PACKS_DIR: &str = "sound-packs";
+pub const DAEMON_ENV_VAR: &str = "REACHY_MINI_SOUND_PACK_DIR";
+/// Files extracted from a pack (anything else in the archive is skipped)
+const PACK_EXTENSIONS: &[&str] = &["wav", "mp3", "ogg", "flac", "json", "txt", "md"];
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SoundPack {
+    /// Directory name, e.g. "robot-voice-fr"
+    pub id: String,
+    pub name: String,
+    pub url: String,
+    pub sha256: String,
+    pub files: usize,
+    pub size_bytes: u64,
+    /// Unix millis
+    pub installed_at: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct SoundPackList {
+    pub packs: Vec<SoundPack>,
+    pub active: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct SoundPackChange {
+    pub list: SoundPackList,
+    /// The running local daemon still uses the previous pack until restarted
+    pub restart_required: bool,
+}
+
+pub struct SoundPackState {
+    list: Mutex<SoundPackList>,
+    /// `<app data>` (set by `load`)
+    dir: Mutex<Option<PathBuf>>,
+}
+
+// ============================================================================
+// HELPER FUNCTIONS
+// ============================================================================
+
+fn validate_id(id: &str) -> Result<(), String> {
+    let valid = !id.is_empty()
+        && id.len() <= 64
+        && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(format!("Invalid pack id (letters, digits, - and _ only): {}", id))
+    }
+}
+
+/// Extract the pack's sound files into `dest` (unsafe paths rejected); returns (files, bytes)
+fn extract_pack(bytes: &[u8], dest: &Path) -> Result<(usize, u64), String> {
+    let mut archive = zip::ZipArchive::new(std::io::Cursor::new(bytes))
+        .map_err(|e| format!("Not a sound pack archive: {}", e))?;
+    let (mut files, mut size) = (0, 0);
+    for index in 0..archive.len() {
+        let mut entry = archive
+            .by_index(index)
+            .map_err(|e| format!("Corrupted sound pack: {}", e))?;
+        let Some(relative) = entry.enclosed_name() else {
+            return Err(format!("Unsafe path in sound pack: {}", entry.name()));
+        };
+        let extension = relative.extension().and_then(|e| e.to_str()).unwrap_or_default().to_lowercase();
+        if entry.is_dir() || !PACK_EXTENSIONS.contains(&extension.as_str()) {
+            continue;
+        }
+        let path = dest.join(relative);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create {:?}: {}", parent, e))?;
+        }
+        let mut content = Vec::new();
+        entry
+            .read_to_end(&mut content)
+            .map_err(|e| format!("Failed to read {} from the pack: {}", entry.name(), e))?;
+        std::fs::write(&path, &content).map_err(|e| format!("Failed to write {:?}: {}", path, e))?;
+        files += 1;
+        size += content.len() as u64;
+    }
+    if files == 0 {
+        return Err("The sound pack contains no sound files".to_string());
+    }
+    Ok((files, size))
+}
+
+/// A local daemon is running (it reads the pack at start)
+fn daemon_running(app_handle: &AppHandle) -> bool {
+    app_handle.state::<DaemonState>().process.lock().unwrap().is_some()
+}
+
+impl SoundPackState {
+    pub fn new() -> Self {
+        Self {
+            list: Mutex::new(SoundPackList::default()),
+            dir: Mutex::new(None),
+        }
+    }
+
+    /// Load installed packs from the app data directory (none if missing or corrupted)
+    pub fn load(&self, app_data_dir: &Path) {
+        let list = std::fs::read_to_string(app_data_dir.join(PACKS_FILE))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        *self.list.lock().unwrap() = list;
+        *self.dir.lock().unwrap() = Some(app_data_dir.to_path_buf());
+    }
+
+    pub fn list(&self) -> SoundPackList {
+        self.list.lock().unwrap().clone()
+    }
+
+    fn packs_dir(&self) -> Result<PathBuf, String> {
+        self.dir
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|dir| dir.join(PACKS_DIR))
+            .ok_or_else(|| "App data directory not available".to_string())
+    }
+
+    /// Directory of the active pack, if it is still installed
+    pub fn active_dir(&self) -> Option<PathBuf> {
+        let active = self.list.lock().unwrap().active.clone()?;
+        Some(self.packs_dir().ok()?.join(active)).filter(|dir| dir.is_dir())
+    }
+
+    /// Apply a change to the pack list and persist it
+    fn update(&self, change: impl FnOnce(&mut SoundPackList)) -> Result<SoundPackList, String> {
+        let mut list = self.list.lock().unwrap();
+        change(&mut list);
+
+        if let Some(dir) = self.dir.lock().unwrap().as_ref() {
+            let content = serde_json::to_string_pretty(&*list)
+                .map_err(|e| format!("Failed to serialize sound packs: {}", e))?;
+            std::fs::write(dir.join(PACKS_FILE), content)
+                .map_err(|e| format!("Failed to write sound packs: {}", e))?;
+        }
+        Ok(list.clone())
+    }
+}
+
+impl Default for SoundPackState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// Daemon environment pointing to the active pack
+pub fn daemon_env(app_handle: &AppHandle) -> Vec<(String, String)> {
+    app_handle
+        .state::<SoundPackState>()
+        .active_dir()
+        .map(|dir| vec![(DAEMON_ENV_VAR.to_string(), dir.to_string_lossy().to_string())])
+        .unwrap_or_default()
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Installed packs and the active one
+#[tauri::command]
+pub fn list_sound_packs(state: State<SoundPackState>) -> SoundPackList {
+    state.list()
+}
+
+/// Download a pack, check its sha256 and install it (replaces a pack with the same id)
+#[tauri::command]
+pub async fn download_sound_pack(
+    app_handle: AppHandle,
+    state: State<'_, SoundPackState>,
+    id: String,
+    name: String,
+    url: String,
+    sha256: String,
+) -> Result<SoundPackChange, String> {
+    validate_id(&id)?;
+    if !url.starts_with("https://") {
+        return Err(format!("Sound pack URL must be https: {}", url));
+    }
+
+    // Cached by hash: reinstalling a pack doesn't download it again
+    let archive = crate::downloads::fetch(&app_handle, &url, &sha256, &format!("sound pack {}", id)).await?;
+    let bytes = std::fs::read(&archive).map_err(|e| format!("Failed to read the sound pack: {}", e))?;
+    let actual = sha256.trim().to_lowercase();
+
+    // Extract next to the final directory, then swap, so a failure leaves the old pack intact
+    let packs_dir = state.packs_dir()?;
+    let staging = packs_dir.join(format!(".{}.partial", id));
+    let target = packs_dir.join(&id);
+    let _ = std::fs::remove_dir_all(&staging);
+    let extracted = extract_pack(&bytes, &staging);
+    let (files, size_bytes) = match extracted {
+        Ok(extracted) => extracted,
+        Err(e) => {
+            let _ = std::fs::remove_dir_all(&staging);
+            return Err(e);
+        }
+    };
+    let _ = std::fs::remove_dir_all(&target);
+    std::fs::rename(&staging, &target).map_err(|e| format!("Failed to install the sound pack: {}", e))?;
+
+    let pack = SoundPack {
+        id: id.clone(),
+        name: name.trim().to_string(),
+        url,
+        sha256: actual,
+        files,
+        size_bytes,
+        installed_at: crate::daemon::history::now_millis(),
+    };
+    let list = state.update(|list| {
+        list.packs.retain(|other| other.id != pack.id);
+        list.packs.push(pack);
+    })?;
+    println!("[assets] ✅ Sound pack {} installed ({} files)", id, files);
+    Ok(SoundPackChange {
+        restart_required: list.active.as_deref() == Some(id.as_str()) && daemon_running(&app_handle),
+        list,
+    })
+}
+
+/// Delete an installed pack (the daemon falls back to its built-in sounds if it was active)
+#[tauri::command]
+pub fn delete_sound_pack(app_handle: AppHandle, state: State<SoundPackState>, id: String) -> Result<SoundPackChange, String> {
+    validate_id(&id)?;
+    let was_active = state.list().active.as_deref() == Some(id.as_str());
+    let dir = state.packs_dir()?.join(&id);
+    if dir.exists() {
+        std::fs::remove_dir_all(&dir).map_err(|e| format!("Failed to delete the sound pack: {}", e))?;
+    }
+    let list = state.update(|list| {
+        list.packs.retain(|pack| pack.id != id);
+        if was_active {
+            list.active = None;
+        }
+    })?;
+    println!("[assets] 🗑️ Sound pack {} deleted", id);
+    Ok(SoundPackChange {
+        restart_required: was_active && daemon_running(&app_handle),
+        list,
+    })
+}
+
+/// Make a pack the active one (None = the daemon's built-in sounds)
+#[tauri::command]
+pub fn set_active_sound_pack(
+    app_handle: AppHandle,
+    state: State<SoundPackState>,
+    id: Option<String>,
+) -> Result<SoundPackChange, String> {
+    let current = state.list();
+    if let Some(id) = &id {
+        if !current.packs.iter().any(|pack| &pack.id == id) {
+            return Err(format!("Sound pack not installed: {}", id));
+        }
+    }
+    let changed = current.active != id;
+    let list = state.update(|list| list.active = id)?;
+    Ok(SoundPackChange {
+        restart_required: changed && daemon_running(&app_handle),
+        list,
+    })
+}
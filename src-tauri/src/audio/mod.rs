@@ -0,0 +1,348 @@
+/// Audio devices module
+///
+/// Lists the system microphones and speakers (so users with USB headsets or
+/// external speakers can pick where Reachy listens and speaks) and routes the
+/// daemon's audio to the selected devices:
+/// - Linux: PulseAudio / PipeWire, through `PULSE_SINK` / `PULSE_SOURCE` in the
+///   daemon environment
+/// - macOS / Windows: no per-process routing without daemon support, the daemon
+///   follows the system default devices (`routing_supported` is false)
+///
+/// The selection is persisted in settings and applied at the next daemon start.
+///
+/// `say_text` tests the whole chain (daemon, robot speaker) by speaking a text through
+/// the daemon's text-to-speech, reporting playback as `tts-status` events
+/// { id, status: "playing" | "finished" | "failed", error }.
+
+use serde::Serialize;
+use std::process::Command;
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, State};
+
+use crate::daemon_api::{DaemonClient, Voice};
+use crate::local_proxy::LocalProxyState;
+use crate::settings::{AppSettings, SettingsState};
+
+const MAX_SPEECH_CHARS: usize = 500;
+/// Speaking rate used when the daemon doesn't give the duration
+const SPEECH_CHARS_PER_SECOND: f64 = 14.0;
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum AudioDirection {
+    Input,
+    Output,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct AudioDevice {
+    /// Stable identifier (PulseAudio name, CoreAudio / endpoint name elsewhere)
+    pub id: String,
+    pub name: String,
+    pub direction: AudioDirection,
+    /// System default device for its direction
+    pub is_default: bool,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct SpeechPlayback {
+    pub id: String,
+    /// Seconds
+    pub duration: f64,
+    /// The duration was estimated from the text length
+    pub duration_estimated: bool,
+}
+
+#[derive(Debug, Serialize, Clone)]
+struct SpeechStatus {
+    id: String,
+    status: &'static str,
+    error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct AudioDevices {
+    pub devices: Vec<AudioDevice>,
+    /// The daemon can be routed to the selected devices on this platform
+    pub routing_supported: bool,
+    pub selected_input: Option<String>,
+    pub selected_output: Option<String>,
+}
+
+// ============================================================================
+// HELPER FUNCTIONS
+// ============================================================================
+
+/// `pactl list sinks|sources`: "Name:" / "Description:" pairs (monitors of outputs skipped)
+#[cfg(target_os = "linux")]
+fn list_pulse_devices(kind: &str, direction: AudioDirection, default: Option<&str>) -> Vec<AudioDevice> {
+    let Ok(output) = Command::new("pactl").args(["list", kind]).output() else {
+        return Vec::new();
+    };
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    let mut devices = Vec::new();
+    let mut name: Option<String> = None;
+    for line in stdout.lines().map(str::trim) {
+        if let Some(value) = line.strip_prefix("Name: ") {
+            name = Some(value.to_string());
+        } else if let Some(description) = line.strip_prefix("Description: ") {
+            let Some(id) = name.take() else { continue };
+            if id.ends_with(".monitor") {
+                continue;
+            }
+            devices.push(AudioDevice {
+                is_default: default == Some(id.as_str()),
+                id,
+                name: description.to_string(),
+                direction,
+            });
+        }
+    }
+    devices
+}
+
+#[cfg(target_os = "linux")]
+fn list_devices_linux() -> Result<Vec<AudioDevice>, String> {
+    let info = Command::new("pactl")
+        .arg("info")
+        .output()
+        .map_err(|e| format!("Failed to run pactl (PulseAudio / PipeWire required): {}", e))?;
+    let info = String::from_utf8_lossy(&info.stdout);
+    let default = |key: &str| {
+        info.lines()
+            .find_map(|line| line.strip_prefix(key))
+            .map(|value| value.trim().to_string())
+    };
+    let default_sink = default("Default Sink:");
+    let default_source = default("Default Source:");
+
+    let mut devices = list_pulse_devices("sources", AudioDirection::Input, default_source.as_deref());
+    devices.extend(list_pulse_devices("sinks", AudioDirection::Output, default_sink.as_deref()));
+    Ok(devices)
+}
+
+/// `system_profiler SPAudioDataType -json`: one item per CoreAudio device, with its
+/// input / output channel counts and default flags
+#[cfg(target_os = "macos")]
+fn list_devices_macos() -> Result<Vec<AudioDevice>, String> {
+    let output = Command::new("system_profiler")
+        .args(["SPAudioDataType", "-json"])
+        .output()
+        .map_err(|e| format!("Failed to run system_profiler: {}", e))?;
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout)
+        .map_err(|e| format!("Invalid system_profiler output: {}", e))?;
+
+    let mut devices = Vec::new();
+    let items = json["SPAudioDataType"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .flat_map(|entry| entry["_items"].as_array().cloned().unwrap_or_default());
+    for item in items {
+        let Some(name) = item["_name"].as_str() else { continue };
+        let is_yes = |key: &str| item[key].as_str() == Some("spaudio_yes");
+        for (direction, channels_key, default_key) in [
+            (AudioDirection::Input, "coreaudio_device_input", "coreaudio_default_audio_input_device"),
+            (AudioDirection::Output, "coreaudio_device_output", "coreaudio_default_audio_output_device"),
+        ] {
+            if item[channels_key].as_u64().unwrap_or(0) > 0 {
+                devices.push(AudioDevice {
+                    id: name.to_string(),
+                    name: name.to_string(),
+                    direction,
+                    is_default: is_yes(default_key),
+                });
+            }
+        }
+    }
+    Ok(devices)
+}
+
+/// Audio endpoints from PnP: render endpoints have IDs under `{0.0.0.00000000}`,
+/// capture endpoints under `{0.0.1.00000000}`
+#[cfg(target_os = "windows")]
+fn list_devices_windows() -> Result<Vec<AudioDevice>, String> {
+    let output = Command::new("powershell")
+        .args([
+            "-NoProfile",
+            "-Command",
+            "Get-PnpDevice -Class AudioEndpoint -PresentOnly | Select-Object FriendlyName,InstanceId | ConvertTo-Json",
+        ])
+        .output()
+        .map_err(|e| format!("Failed to run powershell: {}", e))?;
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap_or(serde_json::Value::Null);
+    // A single device is serialized as an object, several as an array
+    let entries = match json {
+        serde_json::Value::Array(entries) => entries,
+        serde_json::Value::Null => Vec::new(),
+        entry => vec![entry],
+    };
+
+    Ok(entries
+        .iter()
+        .filter_map(|entry| {
+            let name = entry["FriendlyName"].as_str()?;
+            let instance_id = entry["InstanceId"].as_str()?;
+            let direction = if instance_id.contains("{0.0.1.") {
+                AudioDirection::Input
+            } else {
+                AudioDirection::Output
+            };
+            Some(AudioDevice {
+                id: name.to_string(),
+                name: name.to_string(),
+                direction,
+                is_default: false,
+            })
+        })
+        .collect())
+}
+
+fn list_devices() -> Result<Vec<AudioDevice>, String> {
+    #[cfg(target_os = "linux")]
+    {
+        list_devices_linux()
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        list_devices_macos()
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        list_devices_windows()
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    {
+        Ok(Vec::new())
+    }
+}
+
+/// Environment routing the daemon's audio to the selected devices
+pub fn daemon_env(settings: &AppSettings) -> Vec<(String, String)> {
+    let mut env = Vec::new();
+    if cfg!(target_os = "linux") {
+        if let Some(output) = &settings.audio_output_device {
+            env.push(("PULSE_SINK".to_string(), output.clone()));
+        }
+        if let Some(input) = &settings.audio_input_device {
+            env.push(("PULSE_SOURCE".to_string(), input.clone()));
+        }
+    }
+    env
+}
+
+/// Daemon errors for endpoints it doesn't have, in user terms
+fn tts_error(error: String) -> String {
+    if error.contains(" 404 ") {
+        "This daemon version has no text-to-speech: update the daemon to test the voice".to_string()
+    } else {
+        error
+    }
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+#[tauri::command]
+pub async fn list_audio_devices(state: State<'_, SettingsState>) -> Result<AudioDevices, String> {
+    let devices = tauri::async_runtime::spawn_blocking(list_devices)
+        .await
+        .map_err(|e| format!("Task join error: {}", e))??;
+    let settings = state.get();
+    Ok(AudioDevices {
+        devices,
+        routing_supported: cfg!(target_os = "linux"),
+        selected_input: settings.audio_input_device,
+        selected_output: settings.audio_output_device,
+    })
+}
+
+/// Select the daemon microphone / speaker (None = system default), applied at the next daemon start
+#[tauri::command]
+pub fn set_daemon_audio_devices(
+    state: State<SettingsState>,
+    input: Option<String>,
+    output: Option<String>,
+) -> Result<AppSettings, String> {
+    state.update(|settings| {
+        settings.audio_input_device = input.filter(|id| !id.is_empty());
+        settings.audio_output_device = output.filter(|id| !id.is_empty());
+    })
+}
+
+/// Voices of the daemon's text-to-speech
+#[tauri::command]
+pub async fn list_voices(proxy: State<'_, Arc<LocalProxyState>>) -> Result<Vec<Voice>, String> {
+    DaemonClient::for_proxy(&proxy).await.voices().await.map_err(tts_error)
+}
+
+/// Speak a text through the robot (voice None = daemon default); playback is reported
+/// as `tts-status` events
+#[tauri::command]
+pub async fn say_text(
+    app_handle: AppHandle,
+    proxy: State<'_, Arc<LocalProxyState>>,
+    text: String,
+    voice: Option<String>,
+) -> Result<SpeechPlayback, String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err("Nothing to say".to_string());
+    }
+    if text.chars().count() > MAX_SPEECH_CHARS {
+        return Err(format!("Text too long (max {} characters)", MAX_SPEECH_CHARS));
+    }
+
+    let client = DaemonClient::for_proxy(&proxy).await;
+    let response = client.say(text, voice.as_deref().filter(|voice| !voice.is_empty())).await;
+    let response = match response {
+        Ok(response) => response,
+        Err(e) => {
+            let error = tts_error(e);
+            eprintln!("[audio] ❌ Text-to-speech failed: {}", error);
+            let _ = app_handle.emit("tts-status", SpeechStatus {
+                id: String::new(),
+                status: "failed",
+                error: Some(error.clone()),
+            });
+            return Err(error);
+        }
+    };
+
+    let id = response
+        .uuid
+        .unwrap_or_else(|| crate::daemon::history::now_millis().to_string());
+    let playback = SpeechPlayback {
+        id: id.clone(),
+        duration: response
+            .duration
+            .unwrap_or(text.chars().count() as f64 / SPEECH_CHARS_PER_SECOND),
+        duration_estimated: response.duration.is_none(),
+    };
+    println!("[audio] 🗣️ Speaking {} characters ({:.1}s)", text.chars().count(), playback.duration);
+    let _ = app_handle.emit("tts-status", SpeechStatus {
+        id: id.clone(),
+        status: "playing",
+        error: None,
+    });
+    let duration = Duration::from_secs_f64(playback.duration.max(0.0));
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(duration).await;
+        let _ = app_handle.emit("tts-status", SpeechStatus {
+            id,
+            status: "finished",
+            error: None,
+        });
+    });
+    Ok(playback)
+}
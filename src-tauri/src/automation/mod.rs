@@ -0,0 +1,413 @@
+/// Local automation server
+///
+/// Lets external scripts (e.g. a Python demo) drive a few app actions without going
+/// through the webview IPC. Listens on a Unix socket (`<app data>/automation.sock`) or,
+/// on Windows, the named pipe `\\.\pipe\reachy-mini-automation`, only while the
+/// `automation_enabled` setting is on.
+///
+/// Protocol: one JSON object per line. The client first sends { "token": "..." } (the
+/// token is in `<app data>/automation.json`, readable by the user only), then requests
+/// { "id": 1, "action": "run_app", "params": { "name": "..." } }, each answered with
+/// { "id": 1, "ok": true, "result": ... } or { "id": 1, "ok": false, "error": "..." }.
+/// Only the actions in `ACTIONS` exist. See docs/AUTOMATION.md.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Manager, State};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+
+use crate::daemon::DaemonState;
+use crate::daemon_api::DaemonClient;
+use crate::local_proxy::LocalProxyState;
+use crate::settings::SettingsState;
+
+const AUTOMATION_FILE: &str = "automation.json";
+#[cfg(unix)]
+const SOCKET_FILE: &str = "automation.sock";
+#[cfg(windows)]
+const PIPE_NAME: &str = r"\\.\pipe\reachy-mini-automation";
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
+const SNAPSHOTS_DIR: &str = "snapshots";
+
+/// Actions scripts may call
+pub const ACTIONS: &[&str] = &["status", "start_daemon", "play_motion", "run_app", "stop_app", "take_snapshot"];
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+/// Content of automation.json, for scripts to find the server
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct AutomationFile {
+    address: String,
+    token: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct AutomationInfo {
+    pub enabled: bool,
+    pub running: bool,
+    /// Socket path or pipe name
+    pub address: String,
+    pub token: String,
+    /// Where scripts read the address and token from
+    pub info_file: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Handshake {
+    token: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Request {
+    #[serde(default)]
+    id: serde_json::Value,
+    action: String,
+    #[serde(default)]
+    params: serde_json::Value,
+}
+
+#[derive(Debug, Serialize)]
+struct Response {
+    id: serde_json::Value,
+    ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+#[derive(Default)]
+pub struct AutomationState {
+    server: Mutex<Option<JoinHandle<()>>>,
+    token: Mutex<String>,
+    dir: Mutex<Option<PathBuf>>,
+}
+
+// ============================================================================
+// HELPER FUNCTIONS
+// ============================================================================
+
+#[cfg(unix)]
+fn address(dir: &Path) -> String {
+    dir.join(SOCKET_FILE).to_string_lossy().to_string()
+}
+
+#[cfg(windows)]
+fn address(_dir: &Path) -> String {
+    PIPE_NAME.to_string()
+}
+
+/// Write a file only the current user can read (it holds the token)
+fn write_private(path: &Path, content: &str) -> Result<(), String> {
+    use std::io::Write;
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    // Created private rather than restricted after the write: never readable by others
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let mut file = options
+        .open(path)
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    // The mode only applies to new files: a file left by an older version is restricted
+    // before it gets the token
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(std::fs::Permissions::from_mode(0o600))
+            .map_err(|e| format!("Failed to restrict {}: {}", path.display(), e))?;
+    }
+    file.write_all(content.as_bytes())
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+impl AutomationState {
+    /// Load the token from the app data directory (a new one is created if missing;
+    /// empty if none could be generated, and the server then refuses to start)
+    pub fn load(&self, app_data_dir: &Path) {
+        let file: Option<AutomationFile> = std::fs::read_to_string(app_data_dir.join(AUTOMATION_FILE))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok());
+        *self.token.lock().unwrap() = match file {
+            Some(file) if !file.token.is_empty() => file.token,
+            _ => crate::local_proxy::auth::generate_token().unwrap_or_else(|e| {
+                eprintln!("[automation] ⚠️ {}", e);
+                String::new()
+            }),
+        };
+        *self.dir.lock().unwrap() = Some(app_data_dir.to_path_buf());
+    }
+
+    fn dir(&self) -> Result<PathBuf, String> {
+        self.dir.lock().unwrap().clone().ok_or_else(|| "App data directory not loaded".to_string())
+    }
+
+    fn save(&self) -> Result<(), String> {
+        let dir = self.dir()?;
+        let file = AutomationFile {
+            address: address(&dir),
+            token: self.token.lock().unwrap().clone(),
+        };
+        let content = serde_json::to_string_pretty(&file)
+            .map_err(|e| format!("Failed to serialize automation info: {}", e))?;
+        write_private(&dir.join(AUTOMATION_FILE), &content)
+    }
+
+    fn is_running(&self) -> bool {
+        self.server.lock().unwrap().is_some()
+    }
+
+    fn info(&self, enabled: bool) -> AutomationInfo {
+        let dir = self.dir.lock().unwrap().clone();
+        AutomationInfo {
+            enabled,
+            running: self.is_running(),
+            address: dir.as_deref().map(address).unwrap_or_default(),
+            token: self.token.lock().unwrap().clone(),
+            info_file: dir.map(|dir| dir.join(AUTOMATION_FILE).to_string_lossy().to_string()),
+        }
+    }
+}
+
+fn param(params: &serde_json::Value, name: &str) -> Result<String, String> {
+    params
+        .get(name)
+        .and_then(|value| value.as_str())
+        .map(|value| value.to_string())
+        .ok_or_else(|| format!("Missing parameter: {}", name))
+}
+
+/// Run a whitelisted action (also used by scheduled routines)
+pub(crate) async fn dispatch(app_handle: &AppHandle, action: &str, params: &serde_json::Value) -> Result<serde_json::Value, String> {
+    let proxy = app_handle.state::<Arc<LocalProxyState>>();
+    let client = DaemonClient::for_proxy(&proxy).await;
+
+    match action {
+        "status" => Ok(serde_json::json!({
+            "daemon": client.daemon_status().await.ok(),
+            "app": crate::apps::running_app_status(app_handle, &client).await.ok().flatten(),
+        })),
+        "start_daemon" => {
+            let sim_mode = params.get("sim_mode").and_then(|value| value.as_bool());
+            let handle = app_handle.clone();
+            let message = tauri::async_runtime::spawn_blocking(move || {
+                crate::start_daemon(handle.clone(), handle.state::<DaemonState>(), sim_mode, None, None)
+            })
+            .await
+            .map_err(|e| format!("Failed to start daemon: {}", e))??;
+            Ok(serde_json::json!(message))
+        }
+        "play_motion" => {
+            let uuid = client.play_recorded_move(&param(params, "dataset")?, &param(params, "name")?).await?;
+            Ok(serde_json::json!({ "uuid": uuid }))
+        }
+        "run_app" => {
+            let name = param(params, "name")?;
+            crate::apps::launch_app(app_handle, &client, &name).await?;
+            crate::onboarding::mark_done(app_handle, crate::onboarding::OnboardingStep::FirstAppRun);
+            Ok(serde_json::Value::Null)
+        }
+        "stop_app" => {
+            crate::apps::stop_running_app(app_handle, &client).await?;
+            Ok(serde_json::Value::Null)
+        }
+        "take_snapshot" => {
+            let frame = app_handle
+                .state::<crate::camera::CameraState>()
+                .latest_frame()
+                .ok_or("No camera frame: start the camera preview first")?;
+            let path = match params.get("path").and_then(|value| value.as_str()) {
+                Some(path) => PathBuf::from(path),
+                None => {
+                    let dir = app_handle.state::<AutomationState>().dir()?.join(SNAPSHOTS_DIR);
+                    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create snapshots dir: {}", e))?;
+                    dir.join(format!("snapshot-{}.jpg", crate::daemon::history::now_millis()))
+                }
+            };
+            std::fs::write(&path, frame.as_slice()).map_err(|e| format!("Failed to save snapshot: {}", e))?;
+            Ok(serde_json::json!({ "path": path.to_string_lossy() }))
+        }
+        _ => Err(format!("Unknown action: {} (available: {})", action, ACTIONS.join(", "))),
+    }
+}
+
+async fn write_line<W: AsyncWrite + Unpin>(writer: &mut W, value: &impl Serialize) -> std::io::Result<()> {
+    let mut line = serde_json::to_vec(value).unwrap_or_default();
+    line.push(b'\n');
+    writer.write_all(&line).await
+}
+
+/// Handshake, then answer requests until the client disconnects
+async fn handle_client<S: AsyncRead + AsyncWrite + Unpin>(app_handle: AppHandle, stream: S) -> std::io::Result<()> {
+    let (reader, mut writer) = tokio::io::split(stream);
+    let mut lines = BufReader::new(reader).lines();
+
+    let first = tokio::time::timeout(HANDSHAKE_TIMEOUT, lines.next_line()).await.ok().transpose()?.flatten();
+    let handshake: Option<Handshake> = first.and_then(|line| serde_json::from_str(&line).ok());
+    let expected = app_handle.state::<AutomationState>().token.lock().unwrap().clone();
+    if expected.is_empty() || handshake.map(|handshake| handshake.token) != Some(expected) {
+        println!("[automation] 🚫 Client rejected (invalid token)");
+        return write_line(&mut writer, &serde_json::json!({ "ok": false, "error": "Invalid token" })).await;
+    }
+    write_line(&mut writer, &serde_json::json!({ "ok": true, "actions": ACTIONS })).await?;
+    println!("[automation] 🔌 Client connected");
+
+    while let Some(line) = lines.next_line().await? {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = match serde_json::from_str::<Request>(&line) {
+            Ok(request) => {
+                println!("[automation] ▶️ {}", request.action);
+                match dispatch(&app_handle, &request.action, &request.params).await {
+                    Ok(result) => Response { id: request.id, ok: true, result: Some(result), error: None },
+                    Err(e) => Response { id: request.id, ok: false, result: None, error: Some(e) },
+                }
+            }
+            Err(e) => Response {
+                id: serde_json::Value::Null,
+                ok: false,
+                result: None,
+                error: Some(format!("Invalid request: {}", e)),
+            },
+        };
+        write_line(&mut writer, &response).await?;
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+async fn serve(app_handle: AppHandle, address: String) -> Result<JoinHandle<()>, String> {
+    // Left behind if the app didn't exit cleanly
+    let _ = std::fs::remove_file(&address);
+    let listener = tokio::net::UnixListener::bind(&address)
+        .map_err(|e| format!("Failed to listen on {}: {}", address, e))?;
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let _ = std::fs::set_permissions(&address, std::fs::Permissions::from_mode(0o600));
+    }
+
+    Ok(tauri::async_runtime::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            let handle = app_handle.clone();
+            tokio::spawn(async move {
+                let _ = handle_client(handle, stream).await;
+            });
+        }
+    }))
+}
+
+#[cfg(windows)]
+async fn serve(app_handle: AppHandle, address: String) -> Result<JoinHandle<()>, String> {
+    use tokio::net::windows::named_pipe::ServerOptions;
+
+    // Remote clients are rejected by default
+    let mut server = ServerOptions::new()
+        .first_pipe_instance(true)
+        .create(&address)
+        .map_err(|e| format!("Failed to listen on {}: {}", address, e))?;
+
+    Ok(tauri::async_runtime::spawn(async move {
+        while server.connect().await.is_ok() {
+            let client = server;
+            server = match ServerOptions::new().create(&address) {
+                Ok(server) => server,
+                Err(e) => {
+                    eprintln!("[automation] ❌ Failed to create pipe instance: {}", e);
+                    return;
+                }
+            };
+            let handle = app_handle.clone();
+            tokio::spawn(async move {
+                let _ = handle_client(handle, client).await;
+            });
+        }
+    }))
+}
+
+async fn start(app_handle: &AppHandle) -> Result<(), String> {
+    let state = app_handle.state::<AutomationState>();
+    if state.is_running() {
+        return Ok(());
+    }
+    if state.token.lock().unwrap().is_empty() {
+        let token = crate::local_proxy::auth::generate_token()?;
+        *state.token.lock().unwrap() = token;
+    }
+    state.save()?;
+    let address = address(&state.dir()?);
+    let server = serve(app_handle.clone(), address.clone()).await?;
+    *state.server.lock().unwrap() = Some(server);
+    println!("[automation] 🤖 Listening on {}", address);
+    Ok(())
+}
+
+fn stop(app_handle: &AppHandle) {
+    let state = app_handle.state::<AutomationState>();
+    let Some(server) = state.server.lock().unwrap().take() else {
+        return;
+    };
+    server.abort();
+    #[cfg(unix)]
+    if let Ok(dir) = state.dir() {
+        let _ = std::fs::remove_file(address(&dir));
+    }
+    println!("[automation] ⏹️ Automation server stopped");
+}
+
+// ============================================================================
+// SETUP
+// ============================================================================
+
+/// Start the server if enabled (call once in setup, after `load`)
+pub fn init(app_handle: &AppHandle) {
+    if !app_handle.state::<SettingsState>().get().automation_enabled {
+        return;
+    }
+    let handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        if let Err(e) = start(&handle).await {
+            eprintln!("[automation] ❌ {}", e);
+        }
+    });
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+#[tauri::command]
+pub fn get_automation_info(state: State<AutomationState>, settings: State<SettingsState>) -> AutomationInfo {
+    state.info(settings.get().automation_enabled)
+}
+
+/// Turn the automation server on or off (persisted)
+#[tauri::command]
+pub async fn set_automation_enabled(app_handle: AppHandle, enabled: bool) -> Result<AutomationInfo, String> {
+    crate::lock::ensure_unlocked("Changing the automation server")?;
+    app_handle
+        .state::<SettingsState>()
+        .update(|settings| settings.automation_enabled = enabled)?;
+    if enabled {
+        start(&app_handle).await?;
+    } else {
+        stop(&app_handle);
+    }
+    Ok(app_handle.state::<AutomationState>().info(enabled))
+}
+
+/// New token: scripts must read automation.json again (connected clients stay connected)
+#[tauri::command]
+pub fn regenerate_automation_token(
+    state: State<AutomationState>,
+    settings: State<SettingsState>,
+) -> Result<AutomationInfo, String> {
+    *state.token.lock().unwrap() = crate::local_proxy::auth::generate_token()?;
+    state.save()?;
+    Ok(state.info(settings.get().automation_enabled))
+}
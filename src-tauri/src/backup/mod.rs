@@ -0,0 +1,292 @@
+/// Backup and restore of user data
+///
+/// `create_backup` writes a single zip with what a user would lose when moving to
+/// another computer: settings, known robots (registry, configured robots, WiFi
+/// profiles), pose presets, the daemon configuration, telemetry recordings (recorded motions) and the
+/// list of installed apps. `restore_backup` puts it all back and reloads it live.
+///
+/// Not included: robot tokens (they stay in the OS keychain, profiles are restored
+/// without them) and motor calibration, which lives on the robot itself.
+/// Installed apps can't be reinstalled from the list alone: the ones missing after a
+/// restore are returned so the frontend can offer to reinstall them.
+
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tauri::{AppHandle, Manager};
+
+use crate::daemon_api::{AppInfo, DaemonClient};
+use crate::local_proxy::LocalProxyState;
+use crate::settings::{AppSettings, SettingsState};
+use crate::telemetry::recording::RECORDINGS_DIR;
+
+/// Bumped when the archive layout changes incompatibly
+const BACKUP_FORMAT: u32 = 1;
+const MANIFEST_ENTRY: &str = "manifest.json";
+const APPS_ENTRY: &str = "installed_apps.json";
+const DAEMON_CONFIG_ENTRY: &str = "daemon_config.json";
+/// Files copied as-is from the app data directory
+const APP_DATA_FILES: &[&str] = &["settings.json", "robot_registry.json", "robots.json", "remote_profiles.json", "presets.json"];
+const PROFILES_FILE: &str = "remote_profiles.json";
+const SETTINGS_FILE: &str = "settings.json";
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BackupManifest {
+    pub format: u32,
+    /// Unix millis
+    pub created_at: u64,
+    pub app_version: String,
+    pub os: String,
+    pub entries: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct RestoreSummary {
+    pub manifest: BackupManifest,
+    pub restored: Vec<String>,
+    /// Apps of the backup that are not installed here
+    pub apps_to_reinstall: Vec<AppInfo>,
+    /// Entries that could not be restored, with the reason
+    pub errors: Vec<String>,
+}
+
+// ============================================================================
+// HELPER FUNCTIONS
+// ============================================================================
+
+fn app_data_dir(app_handle: &AppHandle) -> Result<PathBuf, String> {
+    app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))
+}
+
+fn add_entry<W: Write + std::io::Seek>(zip: &mut zip::ZipWriter<W>, name: &str, content: &[u8]) -> Result<(), String> {
+    let options = zip::write::SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    zip.start_file(name, options)
+        .map_err(|e| format!("Failed to add {} to backup: {}", name, e))?;
+    zip.write_all(content)
+        .map_err(|e| format!("Failed to write {} to backup: {}", name, e))
+}
+
+fn read_entry<R: Read + std::io::Seek>(archive: &mut zip::ZipArchive<R>, name: &str) -> Result<Vec<u8>, String> {
+    let mut file = archive
+        .by_name(name)
+        .map_err(|e| format!("Failed to read {} from backup: {}", name, e))?;
+    let mut content = Vec::new();
+    file.read_to_end(&mut content)
+        .map_err(|e| format!("Failed to read {} from backup: {}", name, e))?;
+    Ok(content)
+}
+
+/// Tokens are not in the backup: restored profiles must not claim one
+fn strip_profile_tokens(content: &[u8]) -> Result<Vec<u8>, String> {
+    let mut profiles: Vec<serde_json::Value> =
+        serde_json::from_slice(content).map_err(|e| format!("Invalid {}: {}", PROFILES_FILE, e))?;
+    for profile in &mut profiles {
+        profile["has_token"] = serde_json::Value::Bool(false);
+    }
+    serde_json::to_vec_pretty(&profiles).map_err(|e| format!("Failed to serialize {}: {}", PROFILES_FILE, e))
+}
+
+/// Reload everything restored into the app data directory
+fn reload_states(app_handle: &AppHandle, dir: &Path) {
+    let settings = app_handle.state::<SettingsState>();
+    settings.load(app_handle, dir);
+    // Notify the frontend (settings-changed)
+    let _ = settings.update(|_| {});
+    app_handle.state::<crate::usb::registry::RobotRegistryState>().load(dir);
+    app_handle.state::<crate::robots::RobotsState>().load(dir);
+    app_handle.state::<Arc<LocalProxyState>>().profiles.load(dir);
+    app_handle.state::<crate::presets::PresetsState>().load(dir);
+}
+
+fn changed<T: Serialize>(previous: &T, restored: &T) -> bool {
+    serde_json::to_value(previous).ok() != serde_json::to_value(restored).ok()
+}
+
+/// Apply the restored settings that the app only reads at startup or through their own
+/// command (proxy, USB monitor, language, kiosk, MQTT, automation server)
+async fn apply_restored_settings(app_handle: &AppHandle, previous: &AppSettings, errors: &mut Vec<String>) {
+    let settings = app_handle.state::<SettingsState>().get();
+
+    let proxy = app_handle.state::<Arc<LocalProxyState>>();
+    proxy.auth.write().await.require_local_token = settings.proxy_require_local_token;
+    proxy.firewall.set_config(settings.proxy_firewall.clone());
+    if changed(&previous.proxy_ports, &settings.proxy_ports) {
+        if let Err(e) = crate::local_proxy::set_port_mappings(&proxy, settings.proxy_ports.clone()).await {
+            errors.push(format!("Failed to apply the proxy ports: {}", e));
+        }
+    }
+
+    crate::usb::set_watch_list(settings.usb_watch_list.clone());
+    if let Err(e) = crate::i18n::set_language_preference(
+        app_handle.clone(),
+        app_handle.state::<SettingsState>(),
+        settings.language.clone(),
+    ) {
+        errors.push(format!("Failed to apply the language: {}", e));
+    }
+
+    if changed(&previous.kiosk, &settings.kiosk) {
+        let kiosk = settings.kiosk.clone();
+        if let Err(e) = crate::kiosk::set_kiosk_mode(app_handle.clone(), kiosk.enabled, kiosk.app, Some(kiosk.sim_mode)) {
+            errors.push(format!("Failed to apply kiosk mode: {}", e));
+        }
+    }
+    if previous.mqtt != settings.mqtt {
+        if let Err(e) = crate::mqtt::set_mqtt_settings(app_handle.clone(), settings.mqtt.clone(), None).await {
+            errors.push(format!("Failed to apply the MQTT bridge: {}", e));
+        }
+    }
+    if let Err(e) = crate::automation::set_automation_enabled(app_handle.clone(), settings.automation_enabled).await {
+        errors.push(format!("Failed to apply the automation server: {}", e));
+    }
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Write a backup zip at `path`, returns its manifest
+#[tauri::command]
+pub async fn create_backup(app_handle: AppHandle, path: String) -> Result<BackupManifest, String> {
+    let dir = app_data_dir(&app_handle)?;
+    println!("[backup] 📦 Creating backup at {}", path);
+
+    let file = std::fs::File::create(&path).map_err(|e| format!("Failed to create backup file: {}", e))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let mut entries = Vec::new();
+
+    for name in APP_DATA_FILES {
+        if let Ok(content) = std::fs::read(dir.join(name)) {
+            add_entry(&mut zip, name, &content)?;
+            entries.push(name.to_string());
+        }
+    }
+
+    if let Ok(venv_path) = crate::update::get_local_venv_path(&app_handle) {
+        if let Ok(content) = std::fs::read(venv_path.join(DAEMON_CONFIG_ENTRY)) {
+            add_entry(&mut zip, DAEMON_CONFIG_ENTRY, &content)?;
+            entries.push(DAEMON_CONFIG_ENTRY.to_string());
+        }
+    }
+
+    if let Ok(recordings) = std::fs::read_dir(dir.join(RECORDINGS_DIR)) {
+        for recording in recordings.flatten().filter(|entry| entry.path().is_file()) {
+            let name = format!("{}/{}", RECORDINGS_DIR, recording.file_name().to_string_lossy());
+            let content = std::fs::read(recording.path())
+                .map_err(|e| format!("Failed to read recording {}: {}", name, e))?;
+            add_entry(&mut zip, &name, &content)?;
+            entries.push(name);
+        }
+    }
+
+    // Installed apps: only known while a daemon answers
+    let proxy = app_handle.state::<Arc<LocalProxyState>>();
+    match DaemonClient::for_proxy(&proxy).await.installed_apps().await {
+        Ok(apps) => {
+            let content = serde_json::to_vec_pretty(&apps).map_err(|e| format!("Failed to serialize app list: {}", e))?;
+            add_entry(&mut zip, APPS_ENTRY, &content)?;
+            entries.push(APPS_ENTRY.to_string());
+        }
+        Err(e) => eprintln!("[backup] ⚠️ Installed apps not included: {}", e),
+    }
+
+    let manifest = BackupManifest {
+        format: BACKUP_FORMAT,
+        created_at: crate::daemon::history::now_millis(),
+        app_version: app_handle.package_info().version.to_string(),
+        os: std::env::consts::OS.to_string(),
+        entries,
+    };
+    let content = serde_json::to_vec_pretty(&manifest).map_err(|e| format!("Failed to serialize manifest: {}", e))?;
+    add_entry(&mut zip, MANIFEST_ENTRY, &content)?;
+    zip.finish().map_err(|e| format!("Failed to finalize backup: {}", e))?;
+
+    println!("[backup] ✅ Backup written ({} entries)", manifest.entries.len());
+    Ok(manifest)
+}
+
+/// Restore a backup zip over the current data
+#[tauri::command]
+pub async fn restore_backup(app_handle: AppHandle, path: String) -> Result<RestoreSummary, String> {
+    // Overwrites settings.json, which holds the proxy firewall, update channel...
+    crate::lock::ensure_unlocked("Restoring a backup")?;
+    let dir = app_data_dir(&app_handle)?;
+    println!("[backup] 📥 Restoring backup from {}", path);
+
+    let file = std::fs::File::open(&path).map_err(|e| format!("Failed to open backup: {}", e))?;
+    let mut archive = zip::ZipArchive::new(file).map_err(|e| format!("Not a backup archive: {}", e))?;
+    let manifest: BackupManifest = serde_json::from_slice(&read_entry(&mut archive, MANIFEST_ENTRY)?)
+        .map_err(|e| format!("Invalid backup manifest: {}", e))?;
+    if manifest.format > BACKUP_FORMAT {
+        return Err(format!("Backup made by a newer version of the app ({})", manifest.app_version));
+    }
+
+    let mut restored = Vec::new();
+    let mut errors = Vec::new();
+    let mut backup_apps: Vec<AppInfo> = Vec::new();
+    let previous_settings = app_handle.state::<SettingsState>().get();
+    std::fs::create_dir_all(dir.join(RECORDINGS_DIR))
+        .map_err(|e| format!("Failed to create recordings dir: {}", e))?;
+
+    for name in &manifest.entries {
+        let write = |path: PathBuf, content: &[u8]| {
+            std::fs::write(path, content).map_err(|e| format!("Failed to restore {}: {}", name, e))
+        };
+        let result = read_entry(&mut archive, name).and_then(|content| {
+            if name == PROFILES_FILE {
+                write(dir.join(name), &strip_profile_tokens(&content)?)
+            } else if name == SETTINGS_FILE {
+                // Out-of-range values would be dropped on load: refuse them here instead
+                crate::settings::parse_settings(&content).map_err(|e| format!("{} not restored: {}", name, e))?;
+                write(dir.join(name), &content)
+            } else if APP_DATA_FILES.contains(&name.as_str()) {
+                write(dir.join(name), &content)
+            } else if name == DAEMON_CONFIG_ENTRY {
+                let venv_path = crate::update::get_local_venv_path(&app_handle)?;
+                write(venv_path.join(DAEMON_CONFIG_ENTRY), &content)
+            } else if name == APPS_ENTRY {
+                backup_apps = serde_json::from_slice(&content).map_err(|e| format!("Invalid app list: {}", e))?;
+                Ok(())
+            } else if let Some(file_name) = name.strip_prefix(&format!("{}/", RECORDINGS_DIR)) {
+                // Never write outside the recordings directory
+                if file_name.is_empty() || file_name.contains(['/', '\\']) || file_name.starts_with('.') {
+                    return Err(format!("Unexpected recording name: {}", name));
+                }
+                write(dir.join(RECORDINGS_DIR).join(file_name), &content)
+            } else {
+                Err(format!("Unknown entry: {}", name))
+            }
+        });
+        match result {
+            Ok(()) => restored.push(name.clone()),
+            Err(e) => errors.push(e),
+        }
+    }
+
+    reload_states(&app_handle, &dir);
+    apply_restored_settings(&app_handle, &previous_settings, &mut errors).await;
+
+    // Apps to reinstall: compared with what the daemon has (all of them if it doesn't answer)
+    let proxy = app_handle.state::<Arc<LocalProxyState>>();
+    let installed = DaemonClient::for_proxy(&proxy).await.installed_apps().await.unwrap_or_default();
+    let apps_to_reinstall = backup_apps
+        .into_iter()
+        .filter(|app| !installed.iter().any(|installed| installed.name == app.name))
+        .collect();
+
+    println!("[backup] ✅ Restored {} entries ({} errors)", restored.len(), errors.len());
+    Ok(RestoreSummary {
+        manifest,
+        restored,
+        apps_to_reinstall,
+        errors,
+    })
+}
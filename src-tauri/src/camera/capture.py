@@ -0,0 +1,76 @@
+"""Camera helper run with the daemon venv's Python (OpenCV ships with reachy_mini).
+
+probe:                                    print a JSON list of the cameras that open
+stream <index> <width> <height> <fps> <quality>:
+                                          write JPEG frames to stdout, each prefixed
+                                          with its length (4 bytes, big endian)
+"""
+
+import json
+import struct
+import sys
+
+import cv2
+
+MAX_PROBED_INDEX = 6
+
+
+def open_camera(index):
+    camera = cv2.VideoCapture(index)
+    return camera if camera.isOpened() else None
+
+
+def describe(camera):
+    return {
+        "width": int(camera.get(cv2.CAP_PROP_FRAME_WIDTH)),
+        "height": int(camera.get(cv2.CAP_PROP_FRAME_HEIGHT)),
+        "fps": float(camera.get(cv2.CAP_PROP_FPS)),
+    }
+
+
+def probe():
+    cameras = []
+    for index in range(MAX_PROBED_INDEX):
+        camera = open_camera(index)
+        if camera is None:
+            continue
+        cameras.append({"index": index, **describe(camera)})
+        camera.release()
+    print(json.dumps(cameras))
+
+
+def stream(index, width, height, fps, quality):
+    camera = open_camera(index)
+    if camera is None:
+        sys.exit(f"Camera {index} could not be opened")
+    if width and height:
+        camera.set(cv2.CAP_PROP_FRAME_WIDTH, width)
+        camera.set(cv2.CAP_PROP_FRAME_HEIGHT, height)
+    if fps:
+        camera.set(cv2.CAP_PROP_FPS, fps)
+    # First line: what the camera actually accepted
+    sys.stdout.buffer.write((json.dumps(describe(camera)) + "\n").encode())
+    sys.stdout.buffer.flush()
+
+    params = [cv2.IMWRITE_JPEG_QUALITY, quality]
+    while True:
+        ok, frame = camera.read()
+        if not ok:
+            sys.exit("Camera stopped delivering frames")
+        ok, jpeg = cv2.imencode(".jpg", frame, params)
+        if not ok:
+            continue
+        data = jpeg.tobytes()
+        try:
+            sys.stdout.buffer.write(struct.pack(">I", len(data)) + data)
+            sys.stdout.buffer.flush()
+        except BrokenPipeError:
+            break
+    camera.release()
+
+
+if __name__ == "__main__":
+    if sys.argv[1] == "probe":
+        probe()
+    else:
+        stream(*(int(arg) for arg in sys.argv[2:7]))
@@ -0,0 +1,317 @@
+/// Camera preview module
+///
+/// Opens the robot's camera (a USB webcam on Reachy Mini Lite) or the computer's
+/// own, independently of the daemon's port-8042 stream, which doesn't exist in every
+/// mode. Capture runs in the daemon venv's Python with OpenCV (capture.py); frames are
+/// served to the webview as MJPEG from a loopback HTTP server:
+/// - `http://127.0.0.1:<port>/stream`: multipart MJPEG, usable as an <img> source
+/// - `http://127.0.0.1:<port>/snapshot`: latest frame as a single JPEG
+///
+/// Emits `camera-preview-stopped` with { error } when the capture ends (stopped,
+/// camera unplugged or taken by another process).
+
+use serde::Serialize;
+use std::io::{BufRead, BufReader, Read};
+use std::path::PathBuf;
+use std::process::{Child, Command, Stdio};
+use std::sync::{Arc, Mutex};
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Emitter, State};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::watch;
+
+const CAPTURE_SCRIPT: &str = include_str!("capture.py");
+const JPEG_QUALITY: u32 = 80;
+/// Larger frames mean a broken stream, not a camera
+const MAX_FRAME_BYTES: usize = 16 * 1024 * 1024;
+const BOUNDARY: &str = "reachyframe";
+
+type Frame = Option<Arc<Vec<u8>>>;
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+#[derive(Debug, Serialize, Clone)]
+pub struct CameraInfo {
+    /// OpenCV camera index
+    pub index: u32,
+    pub name: String,
+    pub width: u32,
+    pub height: u32,
+    pub fps: f64,
+    /// Looks like the Reachy Mini camera (from the device name, Linux only)
+    pub is_robot: bool,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct CameraPreview {
+    pub index: u32,
+    pub url: String,
+    pub snapshot_url: String,
+    /// What the camera actually delivers (may differ from the request)
+    pub width: u32,
+    pub height: u32,
+    pub fps: f64,
+}
+
+#[derive(Debug, Serialize, Clone)]
+struct PreviewStopped {
+    error: Option<String>,
+}
+
+struct RunningPreview {
+    child: Child,
+    server: JoinHandle<()>,
+    frames: watch::Receiver<Frame>,
+    info: CameraPreview,
+}
+
+impl Drop for RunningPreview {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+        self.server.abort();
+    }
+}
+
+#[derive(Default)]
+pub struct CameraState {
+    preview: Mutex<Option<RunningPreview>>,
+}
+
+// ============================================================================
+// HELPER FUNCTIONS
+// ============================================================================
+
+impl CameraState {
+    /// Latest JPEG frame of the running preview
+    pub fn latest_frame(&self) -> Option<Arc<Vec<u8>>> {
+        self.preview.lock().unwrap().as_ref().and_then(|preview| preview.frames.borrow().clone())
+    }
+}
+
+fn capture_command(app_handle: &AppHandle) -> Result<Command, String> {
+    let venv_path = crate::update::get_local_venv_path(app_handle)?;
+    let python_path = crate::update::get_python_path(&venv_path)?;
+    let mut command = Command::new(python_path);
+    command.args(["-c", CAPTURE_SCRIPT]);
+    Ok(command)
+}
+
+/// Device name from V4L2 sysfs (OpenCV index N is /dev/videoN)
+fn camera_name(index: u32) -> String {
+    let sysfs: PathBuf = ["/sys/class/video4linux", &format!("video{}", index), "name"].iter().collect();
+    std::fs::read_to_string(sysfs)
+        .map(|name| name.trim().to_string())
+        .unwrap_or_else(|_| format!("Camera {}", index))
+}
+
+/// Read length-prefixed JPEG frames from the capture process until it exits
+fn read_frames(mut stdout: impl Read, frames: watch::Sender<Frame>) {
+    let mut length = [0u8; 4];
+    while stdout.read_exact(&mut length).is_ok() {
+        let size = u32::from_be_bytes(length) as usize;
+        if size > MAX_FRAME_BYTES {
+            break;
+        }
+        let mut frame = vec![0u8; size];
+        if stdout.read_exact(&mut frame).is_err() {
+            break;
+        }
+        let _ = frames.send(Some(Arc::new(frame)));
+    }
+}
+
+async fn write_response(socket: &mut TcpStream, status: &str, content_type: &str, body: &[u8]) -> std::io::Result<()> {
+    let head = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nAccess-Control-Allow-Origin: *\r\nCache-Control: no-cache\r\nConnection: close\r\n\r\n",
+        status,
+        content_type,
+        body.len()
+    );
+    socket.write_all(head.as_bytes()).await?;
+    socket.write_all(body).await
+}
+
+async fn serve_client(mut socket: TcpStream, mut frames: watch::Receiver<Frame>) -> std::io::Result<()> {
+    let mut request = [0u8; 2048];
+    let n = socket.read(&mut request).await?;
+    let request = String::from_utf8_lossy(&request[..n]);
+    let path = request.split_whitespace().nth(1).unwrap_or("/");
+    let path = path.split('?').next().unwrap_or(path);
+
+    match path {
+        "/snapshot" => {
+            let frame = frames.borrow().clone();
+            match frame {
+                Some(frame) => write_response(&mut socket, "200 OK", "image/jpeg", &frame).await,
+                None => write_response(&mut socket, "503 Service Unavailable", "text/plain", b"No frame yet").await,
+            }
+        }
+        "/stream" => {
+            let head = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: multipart/x-mixed-replace; boundary={}\r\nAccess-Control-Allow-Origin: *\r\nCache-Control: no-cache\r\nConnection: close\r\n\r\n",
+                BOUNDARY
+            );
+            socket.write_all(head.as_bytes()).await?;
+            loop {
+                let frame = frames.borrow_and_update().clone();
+                if let Some(frame) = frame {
+                    let part = format!(
+                        "--{}\r\nContent-Type: image/jpeg\r\nContent-Length: {}\r\n\r\n",
+                        BOUNDARY,
+                        frame.len()
+                    );
+                    socket.write_all(part.as_bytes()).await?;
+                    socket.write_all(&frame).await?;
+                    socket.write_all(b"\r\n").await?;
+                }
+                // Sender dropped: the capture ended
+                if frames.changed().await.is_err() {
+                    return Ok(());
+                }
+            }
+        }
+        _ => write_response(&mut socket, "404 Not Found", "text/plain", b"Not found").await,
+    }
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Cameras OpenCV can open, with their default resolution and frame rate
+#[tauri::command]
+pub async fn list_cameras(app_handle: AppHandle) -> Result<Vec<CameraInfo>, String> {
+    let mut command = capture_command(&app_handle)?;
+    let output = tauri::async_runtime::spawn_blocking(move || command.arg("probe").output())
+        .await
+        .map_err(|e| format!("Task join error: {}", e))?
+        .map_err(|e| format!("Failed to run camera probe: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "Camera probe failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    let probed: Vec<serde_json::Value> = serde_json::from_slice(&output.stdout)
+        .map_err(|e| format!("Invalid camera probe output: {}", e))?;
+    Ok(probed
+        .iter()
+        .filter_map(|camera| {
+            let index = camera["index"].as_u64()? as u32;
+            let name = camera_name(index);
+            Some(CameraInfo {
+                index,
+                is_robot: name.to_lowercase().contains("reachy"),
+                name,
+                width: camera["width"].as_u64().unwrap_or(0) as u32,
+                height: camera["height"].as_u64().unwrap_or(0) as u32,
+                fps: camera["fps"].as_f64().unwrap_or(0.0),
+            })
+        })
+        .collect())
+}
+
+/// Open a camera and serve its preview (replaces a running preview)
+#[tauri::command]
+pub async fn start_camera_preview(
+    app_handle: AppHandle,
+    state: State<'_, CameraState>,
+    index: u32,
+    width: Option<u32>,
+    height: Option<u32>,
+    fps: Option<u32>,
+) -> Result<CameraPreview, String> {
+    // The previous capture must release the device first
+    state.preview.lock().unwrap().take();
+
+    let mut child = capture_command(&app_handle)?
+        .arg("stream")
+        .args([index, width.unwrap_or(0), height.unwrap_or(0), fps.unwrap_or(0), JPEG_QUALITY].map(|n| n.to_string()))
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to start camera capture: {}", e))?;
+
+    // First line: the settings the camera accepted
+    let mut stdout = BufReader::new(child.stdout.take().ok_or("No capture output")?);
+    let mut stderr = child.stderr.take();
+    let (stdout, first_line) = tauri::async_runtime::spawn_blocking(move || {
+        let mut line = String::new();
+        let _ = stdout.read_line(&mut line);
+        (stdout, line)
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))?;
+    let accepted: serde_json::Value = match serde_json::from_str(&first_line) {
+        Ok(accepted) => accepted,
+        Err(_) => {
+            let _ = child.wait();
+            let mut error = String::new();
+            if let Some(pipe) = stderr.as_mut() {
+                let _ = pipe.read_to_string(&mut error);
+            }
+            return Err(format!("Camera {} could not be opened: {}", index, error.trim()));
+        }
+    };
+
+    let listener = TcpListener::bind("127.0.0.1:0")
+        .await
+        .map_err(|e| format!("Failed to start preview server: {}", e))?;
+    let port = listener
+        .local_addr()
+        .map_err(|e| format!("Failed to start preview server: {}", e))?
+        .port();
+
+    let (frames_tx, frames_rx) = watch::channel::<Frame>(None);
+    let handle = app_handle.clone();
+    std::thread::spawn(move || {
+        read_frames(stdout, frames_tx);
+        let mut error = String::new();
+        if let Some(pipe) = stderr.as_mut() {
+            let _ = pipe.read_to_string(&mut error);
+        }
+        let error = Some(error.trim().to_string()).filter(|e| !e.is_empty());
+        println!("[camera] 📷 Capture ended{}", error.as_deref().map(|e| format!(": {}", e)).unwrap_or_default());
+        let _ = handle.emit("camera-preview-stopped", PreviewStopped { error });
+    });
+
+    let frames = frames_rx.clone();
+    let server = tauri::async_runtime::spawn(async move {
+        while let Ok((socket, _)) = listener.accept().await {
+            let frames = frames_rx.clone();
+            tokio::spawn(async move {
+                let _ = serve_client(socket, frames).await;
+            });
+        }
+    });
+
+    let info = CameraPreview {
+        index,
+        url: format!("http://127.0.0.1:{}/stream", port),
+        snapshot_url: format!("http://127.0.0.1:{}/snapshot", port),
+        width: accepted["width"].as_u64().unwrap_or(0) as u32,
+        height: accepted["height"].as_u64().unwrap_or(0) as u32,
+        fps: accepted["fps"].as_f64().unwrap_or(0.0),
+    };
+    println!("[camera] 📷 Previewing camera {} at {}", index, info.url);
+    *state.preview.lock().unwrap() = Some(RunningPreview { child, server, frames, info: info.clone() });
+    Ok(info)
+}
+
+/// Running preview, if any
+#[tauri::command]
+pub fn get_camera_preview(state: State<CameraState>) -> Option<CameraPreview> {
+    state.preview.lock().unwrap().as_ref().map(|preview| preview.info.clone())
+}
+
+#[tauri::command]
+pub fn stop_camera_preview(state: State<CameraState>) {
+    if state.preview.lock().unwrap().take().is_some() {
+        println!("[camera] 📷 Preview stopped");
+    }
+}
@@ -0,0 +1,310 @@
+/// Daemon API compatibility gate
+///
+/// When a robot connects (local daemon ready, or WiFi mode), the daemon's version and
+/// OpenAPI schema (`/openapi.json`) are checked against what this app supports: the
+/// daemon version range and every endpoint the app calls. A missing endpoint otherwise
+/// shows up as a button that does nothing.
+///
+/// - `block`: an endpoint the app relies on is missing, or the daemon is older than
+///   `MIN_DAEMON_VERSION`; the robot can't be used safely until one side is updated
+/// - `warn`: an optional feature is missing, the daemon is newer than the tested range,
+///   or the schema could not be read
+/// - `ok`
+///
+/// The suggested update path is the daemon when it is the older side, the app otherwise.
+/// Emits `daemon-compatibility` with the report after every check.
+
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::connection::{ConnectionManager, ConnectionState};
+use crate::daemon_api::DaemonClient;
+use crate::local_proxy::LocalProxyState;
+
+/// Oldest daemon version this app works with
+const MIN_DAEMON_VERSION: &str = "1.0.0";
+/// First daemon version this app was not tested with (newer ones get a warning)
+const MAX_DAEMON_VERSION: &str = "2.0.0";
+const CHECK_INTERVAL: Duration = Duration::from_secs(2);
+const SPEC_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// (method, path, feature, required) of every endpoint the app calls
+const ENDPOINTS: &[(&str, &str, &str, bool)] = &[
+    ("GET", "/api/daemon/status", "Connection", true),
+    ("POST", "/api/daemon/stop", "Connection", true),
+    ("GET", "/api/state/full", "Robot state", true),
+    ("GET", "/api/motors/status", "Motors", true),
+    ("POST", "/api/motors/set_mode/{mode}", "Motors", true),
+    ("POST", "/api/move/play/goto_sleep", "Sleep / park", true),
+    ("POST", "/api/move/set_target", "Teleoperation", true),
+    ("POST", "/api/move/goto", "Moves", true),
+    ("GET", "/api/move/running", "Moves", true),
+    ("POST", "/api/move/play/recorded-move-dataset/{dataset}/{name}", "Emotions", false),
+    ("GET", "/api/apps/list-available/installed", "Apps", true),
+    ("GET", "/api/apps/current-app-status", "Apps", true),
+    ("POST", "/api/apps/start-app/{name}", "Apps", true),
+    ("POST", "/api/apps/stop-current-app", "Apps", true),
+    ("POST", "/api/apps/install", "App store", false),
+    ("POST", "/api/apps/remove/{name}", "App store", false),
+    ("GET", "/api/apps/job-status/{job_id}", "App store", false),
+    ("POST", "/api/audio/say", "Voice", false),
+    ("GET", "/api/audio/voices", "Voice", false),
+    ("GET", "/api/system/hostname", "Robot name", false),
+    ("POST", "/api/system/hostname", "Robot name", false),
+    ("POST", "/api/update/start", "Robot update", false),
+    ("GET", "/api/update/info", "Robot update", false),
+    ("GET", "/api/simulation/parameters", "Simulation physics", false),
+    ("POST", "/api/simulation/parameters", "Simulation physics", false),
+];
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum CompatibilityStatus {
+    Ok,
+    Warn,
+    Block,
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum UpdatePath {
+    App,
+    Daemon,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct MissingEndpoint {
+    pub method: String,
+    pub path: String,
+    pub feature: String,
+    pub required: bool,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct CompatibilityReport {
+    pub status: CompatibilityStatus,
+    pub daemon_version: Option<String>,
+    pub app_version: String,
+    /// e.g. ">=1.0.0, <2.0.0"
+    pub supported_range: String,
+    pub missing_endpoints: Vec<MissingEndpoint>,
+    /// Features that won't work with this daemon
+    pub unavailable_features: Vec<String>,
+    pub suggested_update: Option<UpdatePath>,
+    /// Human-readable summary (empty when compatible)
+    pub messages: Vec<String>,
+    /// The OpenAPI schema was read (false: only the version was checked)
+    pub schema_checked: bool,
+}
+
+#[derive(Default)]
+pub struct CompatibilityState {
+    report: Mutex<Option<CompatibilityReport>>,
+}
+
+// ============================================================================
+// HELPER FUNCTIONS
+// ============================================================================
+
+/// Path with its parameters anonymized: "/api/apps/remove/{name}" → "/api/apps/remove/{}"
+fn normalize_path(path: &str) -> String {
+    path.split('/')
+        .map(|segment| if segment.starts_with('{') && segment.ends_with('}') { "{}" } else { segment })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// (METHOD, normalized path) of every operation in an OpenAPI schema
+fn spec_operations(spec: &serde_json::Value) -> Vec<(String, String)> {
+    let Some(paths) = spec.get("paths").and_then(|paths| paths.as_object()) else {
+        return Vec::new();
+    };
+    paths
+        .iter()
+        .flat_map(|(path, operations)| {
+            let path = normalize_path(path);
+            operations
+                .as_object()
+                .into_iter()
+                .flat_map(|operations| operations.keys())
+                .map(move |method| (method.to_uppercase(), path.clone()))
+        })
+        .collect()
+}
+
+/// Compare the daemon version and schema (None if unavailable) with what the app supports
+fn evaluate(app_version: &str, daemon_version: Option<&str>, spec: Option<&serde_json::Value>) -> CompatibilityReport {
+    let mut status = CompatibilityStatus::Ok;
+    let mut suggested_update = None;
+    let mut messages = Vec::new();
+    let mut raise = |level: CompatibilityStatus, message: String| {
+        if level == CompatibilityStatus::Block || status == CompatibilityStatus::Ok {
+            status = level;
+        }
+        messages.push(message);
+    };
+
+    let parsed = daemon_version.map(|version| (version, crate::update::parse_version(version)));
+    match parsed {
+        Some((version, Ok(parsed))) => {
+            let min = crate::update::parse_version(MIN_DAEMON_VERSION).expect("valid MIN_DAEMON_VERSION");
+            let max = crate::update::parse_version(MAX_DAEMON_VERSION).expect("valid MAX_DAEMON_VERSION");
+            if parsed < min {
+                suggested_update = Some(UpdatePath::Daemon);
+                raise(
+                    CompatibilityStatus::Block,
+                    format!("Daemon {} is too old for this app (requires {} or later)", version, MIN_DAEMON_VERSION),
+                );
+            } else if parsed >= max {
+                suggested_update = Some(UpdatePath::App);
+                raise(
+                    CompatibilityStatus::Warn,
+                    format!("Daemon {} is newer than this app supports (below {})", version, MAX_DAEMON_VERSION),
+                );
+            }
+        }
+        Some((version, Err(_))) => {
+            raise(CompatibilityStatus::Warn, format!("Unrecognized daemon version: {}", version));
+        }
+        None => raise(CompatibilityStatus::Warn, "The daemon did not report its version".to_string()),
+    }
+
+    let mut missing_endpoints = Vec::new();
+    match spec.map(spec_operations) {
+        Some(operations) if !operations.is_empty() => {
+            for (method, path, feature, required) in ENDPOINTS {
+                let key = (method.to_string(), normalize_path(path));
+                if !operations.contains(&key) {
+                    missing_endpoints.push(MissingEndpoint {
+                        method: method.to_string(),
+                        path: path.to_string(),
+                        feature: feature.to_string(),
+                        required: *required,
+                    });
+                }
+            }
+        }
+        _ => raise(
+            CompatibilityStatus::Warn,
+            "Could not read the daemon API schema, only its version was checked".to_string(),
+        ),
+    }
+
+    let mut unavailable_features: Vec<String> = Vec::new();
+    for endpoint in &missing_endpoints {
+        if !unavailable_features.contains(&endpoint.feature) {
+            unavailable_features.push(endpoint.feature.clone());
+        }
+    }
+    if !missing_endpoints.is_empty() {
+        let required = missing_endpoints.iter().any(|endpoint| endpoint.required);
+        let level = if required { CompatibilityStatus::Block } else { CompatibilityStatus::Warn };
+        raise(
+            level,
+            format!("The daemon is missing endpoints for: {}", unavailable_features.join(", ")),
+        );
+        // Within the supported range, the daemon is the side lacking the endpoints
+        if suggested_update.is_none() {
+            suggested_update = Some(UpdatePath::Daemon);
+        }
+    }
+
+    CompatibilityReport {
+        status,
+        daemon_version: daemon_version.map(|version| version.to_string()),
+        app_version: app_version.to_string(),
+        supported_range: format!(">={}, <{}", MIN_DAEMON_VERSION, MAX_DAEMON_VERSION),
+        missing_endpoints,
+        unavailable_features,
+        suggested_update,
+        messages,
+        schema_checked: spec.is_some(),
+    }
+}
+
+/// Fetch the daemon's version and schema and evaluate them
+async fn check(app_handle: &AppHandle) -> Result<CompatibilityReport, String> {
+    let proxy = app_handle.state::<Arc<LocalProxyState>>();
+    let client = DaemonClient::for_proxy(&proxy).await.with_timeout(SPEC_TIMEOUT);
+    let status = client.daemon_status().await?;
+    let spec = match client.openapi_spec().await {
+        Ok(spec) => Some(spec),
+        Err(e) => {
+            eprintln!("[compatibility] ⚠️ {}", e);
+            None
+        }
+    };
+    let daemon_version = status
+        .version
+        .or_else(|| spec.as_ref().and_then(|spec| spec["info"]["version"].as_str()).map(|v| v.to_string()));
+
+    let app_version = app_handle.package_info().version.to_string();
+    let report = evaluate(&app_version, daemon_version.as_deref(), spec.as_ref());
+    match report.status {
+        CompatibilityStatus::Ok => println!("[compatibility] ✅ Daemon {:?} compatible", report.daemon_version),
+        _ => eprintln!("[compatibility] ⚠️ {:?}: {}", report.status, report.messages.join("; ")),
+    }
+    *app_handle.state::<CompatibilityState>().report.lock().unwrap() = Some(report.clone());
+    let _ = app_handle.emit("daemon-compatibility", &report);
+    Ok(report)
+}
+
+/// Which daemon the connection reaches (None while not connected)
+fn connected_daemon(state: &ConnectionState) -> Option<String> {
+    match state {
+        ConnectionState::Ready { sim_mode, .. } => Some(format!("local (sim: {})", sim_mode)),
+        ConnectionState::WifiMode { host, .. } => Some(host.clone()),
+        _ => None,
+    }
+}
+
+async fn run_watcher(app_handle: AppHandle) {
+    let mut checked: Option<String> = None;
+    loop {
+        let daemon = connected_daemon(&app_handle.state::<ConnectionManager>().get());
+        if daemon.is_none() {
+            *app_handle.state::<CompatibilityState>().report.lock().unwrap() = None;
+            checked = None;
+        } else if daemon != checked {
+            match check(&app_handle).await {
+                Ok(_) => checked = daemon,
+                Err(e) => eprintln!("[compatibility] ⚠️ Check failed, retrying: {}", e),
+            }
+        }
+        tokio::time::sleep(CHECK_INTERVAL).await;
+    }
+}
+
+// ============================================================================
+// SETUP
+// ============================================================================
+
+/// Check every newly connected daemon (call once in setup)
+pub fn start_watcher(app_handle: AppHandle) {
+    tauri::async_runtime::spawn(run_watcher(app_handle));
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Report of the connected daemon (None while not connected or not checked yet)
+#[tauri::command]
+pub fn get_daemon_compatibility(state: State<CompatibilityState>) -> Option<CompatibilityReport> {
+    state.report.lock().unwrap().clone()
+}
+
+/// Check the connected daemon again (e.g. after updating it)
+#[tauri::command]
+pub async fn check_daemon_compatibility(app_handle: AppHandle) -> Result<CompatibilityReport, String> {
+    if connected_daemon(&app_handle.state::<ConnectionManager>().get()).is_none() {
+        return Err("No robot connected".to_string());
+    }
+    check(&app_handle).await
+}
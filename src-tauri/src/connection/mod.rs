@@ -0,0 +1,231 @@
+/// Robot connection manager
+///
+/// Owns the single connection state the frontend follows, derived from the USB monitor,
+/// the daemon sidecar (process, health endpoint, run history) and the WiFi-mode local
+/// proxy (robots found by discovery are reached through its target host):
+///
+/// Disconnected → UsbDetected → DaemonStarting → Ready, or WifiMode when a remote robot
+/// is targeted. Error when the daemon crashes or never becomes ready, until it is
+/// restarted or the robot is plugged/unplugged.
+///
+/// Emits `connection-state-changed` with the new state on every transition.
+
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::daemon::DaemonState;
+use crate::daemon_api::DaemonClient;
+use crate::local_proxy::LocalProxyState;
+
+const CHECK_INTERVAL: Duration = Duration::from_millis(1000);
+/// A daemon still not answering after this long is reported as an error
+const STARTING_TIMEOUT: Duration = Duration::from_secs(60);
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum ConnectionState {
+    Disconnected,
+    UsbDetected {
+        port: String,
+        serial_number: Option<String>,
+    },
+    DaemonStarting {
+        sim_mode: bool,
+        port: Option<String>,
+    },
+    Ready {
+        sim_mode: bool,
+        port: Option<String>,
+    },
+    WifiMode {
+        host: String,
+        /// Robot missed several proxy heartbeats
+        degraded: bool,
+    },
+    Error {
+        message: String,
+    },
+}
+
+/// Everything the state is derived from, sampled once per check
+struct Inputs {
+    usb_robot: Option<crate::usb::UsbRobot>,
+    daemon_running: bool,
+    daemon_healthy: bool,
+    sim_mode: bool,
+    /// Crash reason of the last run, if it ended on its own with an error
+    last_crash: Option<String>,
+    wifi_host: Option<String>,
+    wifi_degraded: bool,
+}
+
+pub struct ConnectionManager {
+    state: Mutex<ConnectionState>,
+}
+
+impl ConnectionManager {
+    pub fn new() -> Self {
+        Self {
+            state: Mutex::new(ConnectionState::Disconnected),
+        }
+    }
+
+    pub fn get(&self) -> ConnectionState {
+        self.state.lock().unwrap().clone()
+    }
+}
+
+impl Default for ConnectionManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ============================================================================
+// STATE MACHINE
+// ============================================================================
+
+/// Next state from the previous one and the current inputs
+fn next_state(previous: &ConnectionState, starting_since: Option<Instant>, inputs: &Inputs) -> ConnectionState {
+    let usb_port = inputs.usb_robot.as_ref().map(|robot| robot.port_name.clone());
+
+    if let Some(host) = &inputs.wifi_host {
+        return ConnectionState::WifiMode { host: host.clone(), degraded: inputs.wifi_degraded };
+    }
+
+    if inputs.daemon_running {
+        if inputs.daemon_healthy {
+            return ConnectionState::Ready { sim_mode: inputs.sim_mode, port: usb_port };
+        }
+        match previous {
+            // Was answering, process still alive: stalled
+            ConnectionState::Ready { .. } => {
+                return ConnectionState::Error { message: "Daemon stopped responding".to_string() };
+            }
+            ConnectionState::Error { .. } => return previous.clone(),
+            _ => {}
+        }
+        if starting_since.is_some_and(|since| since.elapsed() > STARTING_TIMEOUT) {
+            return ConnectionState::Error { message: "Daemon did not become ready".to_string() };
+        }
+        return ConnectionState::DaemonStarting { sim_mode: inputs.sim_mode, port: usb_port };
+    }
+
+    match previous {
+        // The daemon went away without being stopped by the app
+        ConnectionState::DaemonStarting { .. } | ConnectionState::Ready { .. } => {
+            if let Some(reason) = &inputs.last_crash {
+                return ConnectionState::Error { message: reason.clone() };
+            }
+        }
+        // Sticky until the robot is plugged/unplugged or the daemon restarted
+        ConnectionState::Error { .. } => return previous.clone(),
+        _ => {}
+    }
+
+    match &inputs.usb_robot {
+        Some(robot) => ConnectionState::UsbDetected {
+            port: robot.port_name.clone(),
+            serial_number: robot.serial_number.clone(),
+        },
+        None => ConnectionState::Disconnected,
+    }
+}
+
+async fn sample_inputs(app_handle: &AppHandle, client: &DaemonClient) -> Inputs {
+    let daemon_state = app_handle.state::<DaemonState>();
+    let daemon_running = daemon_state.process.lock().unwrap().is_some();
+    let (sim_mode, last_crash) = {
+        let history = daemon_state.history.lock().unwrap();
+        let last_crash = history
+            .last_run()
+            .filter(|run| run.crashed)
+            .map(|run| match &run.crash_reason {
+                Some(reason) => format!("Daemon crashed: {}", reason),
+                None => format!("Daemon crashed (exit code {:?})", run.exit_code),
+            });
+        (history.current_mode().unwrap_or(false), last_crash)
+    };
+
+    let daemon_healthy = daemon_running && client.is_healthy().await;
+
+    let proxy = app_handle.state::<Arc<LocalProxyState>>().status().await;
+
+    Inputs {
+        usb_robot: crate::usb::get_reachy_robots().into_iter().next(),
+        daemon_running,
+        daemon_healthy,
+        sim_mode,
+        last_crash,
+        wifi_host: proxy.target_host,
+        wifi_degraded: proxy.degraded,
+    }
+}
+
+/// Start the background state machine (call once in setup)
+pub fn start(app_handle: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let client = DaemonClient::new();
+        let mut starting_since: Option<Instant> = None;
+        let mut daemon_was_running = false;
+        let mut last_usb_port: Option<String> = None;
+
+        loop {
+            let inputs = sample_inputs(&app_handle, &client).await;
+            let previous = app_handle.state::<ConnectionManager>().get();
+
+            let daemon_started = inputs.daemon_running && !daemon_was_running;
+            daemon_was_running = inputs.daemon_running;
+            if !inputs.daemon_running {
+                starting_since = None;
+            } else if daemon_started {
+                starting_since = Some(Instant::now());
+            }
+
+            // A plugged/unplugged robot or a restarted daemon clears a sticky error
+            let usb_port = inputs.usb_robot.as_ref().map(|robot| robot.port_name.clone());
+            let replugged = usb_port != last_usb_port;
+            last_usb_port = usb_port;
+            let previous = if (replugged || daemon_started) && matches!(previous, ConnectionState::Error { .. }) {
+                ConnectionState::Disconnected
+            } else {
+                previous
+            };
+
+            let next = next_state(&previous, starting_since, &inputs);
+            let manager = app_handle.state::<ConnectionManager>();
+            let changed = {
+                let mut current = manager.state.lock().unwrap();
+                let changed = *current != next;
+                *current = next.clone();
+                changed
+            };
+            if changed {
+                println!("[connection] 🔄 {:?}", next);
+                crate::timeline::record_event(crate::timeline::Source::App, "connection-state-changed", &next);
+                if matches!(next, ConnectionState::Ready { .. }) {
+                    crate::profiling::mark_ready();
+                }
+                let _ = app_handle.emit("connection-state-changed", next);
+            }
+
+            tokio::time::sleep(CHECK_INTERVAL).await;
+        }
+    });
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Current robot connection state (also emitted as `connection-state-changed`)
+#[tauri::command]
+pub fn get_connection_state(state: State<ConnectionManager>) -> ConnectionState {
+    state.get()
+}
@@ -0,0 +1,137 @@
+/// GitHub issue reporter
+///
+/// Opens a pre-filled "new issue" page of the desktop app repository in the browser:
+/// the user's description followed by the environment (versions, OS, connection) and,
+/// optionally, the failed diagnostics checks. GitHub can't attach files through the URL,
+/// so the diagnostics bundle (crash report zip) is revealed in the file manager and the
+/// issue body asks to drop it into the issue.
+
+use serde::Serialize;
+use std::fmt::Write;
+use tauri::{AppHandle, Manager, State};
+use tauri_plugin_opener::OpenerExt;
+
+use crate::connection::ConnectionManager;
+use crate::daemon::DaemonState;
+use crate::diagnostics::{CheckStatus, DiagnosticsReport};
+
+const NEW_ISSUE_URL: &str = "https://github.com/pollen-robotics/reachy-mini-desktop-app/issues/new";
+/// Longer "new issue" URLs are rejected by GitHub
+const MAX_URL_LEN: usize = 8000;
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+#[derive(Debug, Serialize, Clone)]
+pub struct IssueReport {
+    /// Page opened in the browser
+    pub url: String,
+    /// Diagnostics bundle to attach (when requested)
+    pub bundle_path: Option<String>,
+}
+
+// ============================================================================
+// HELPER FUNCTIONS
+// ============================================================================
+
+fn environment_section(app_handle: &AppHandle) -> String {
+    let versions = crate::versions::collect_component_versions(app_handle);
+    let system = super::collect_system_info();
+    let connection = app_handle.state::<ConnectionManager>().get();
+    let unknown = || "unknown".to_string();
+
+    let mut section = String::from("### Environment\n\n| | |\n|---|---|\n");
+    let rows = [
+        ("App", versions.app),
+        ("Daemon (reachy-mini)", versions.daemon.unwrap_or_else(unknown)),
+        ("Python", versions.python.unwrap_or_else(unknown)),
+        ("Firmware", versions.firmware.unwrap_or_else(|| "robot not plugged in".to_string())),
+        ("OS", format!("{} ({})", system.os, system.arch)),
+        ("OS version", system.os_version),
+        ("Connection", serde_json::to_string(&connection).unwrap_or_else(|_| unknown())),
+    ];
+    for (name, value) in rows {
+        let _ = writeln!(section, "| {} | `{}` |", name, value.replace('|', "\\|").replace('`', "'"));
+    }
+    section
+}
+
+fn diagnostics_section(diagnostics: &DiagnosticsReport, bundle_name: &str) -> String {
+    let mut section = format!("### Diagnostics\n\nOverall: **{:?}**\n\n", diagnostics.overall);
+    let problems: Vec<_> = diagnostics
+        .checks
+        .iter()
+        .filter(|check| matches!(check.status, CheckStatus::Warn | CheckStatus::Fail))
+        .collect();
+    if problems.is_empty() {
+        section.push_str("All checks passed.\n");
+    }
+    for check in problems {
+        let _ = writeln!(section, "- {:?} **{}**: {}", check.status, check.name, check.message);
+    }
+    let _ = write!(
+        section,
+        "\n> Diagnostics bundle: please drag `{}` (opened in your file manager) into this issue.\n",
+        bundle_name
+    );
+    section
+}
+
+/// "New issue" URL, with the body shortened if the URL would be too long
+fn issue_url(title: &str, body: &str) -> Result<String, String> {
+    const TRUNCATED: &str = "\n\n_(truncated)_";
+    let mut body = body.to_string();
+    loop {
+        let url = reqwest::Url::parse_with_params(NEW_ISSUE_URL, [("title", title), ("body", &body)])
+            .map_err(|e| format!("Failed to build the issue URL: {}", e))?;
+        let url = url.to_string();
+        if url.len() <= MAX_URL_LEN || body.is_empty() {
+            return Ok(url);
+        }
+        let keep = body.chars().count() * 9 / 10;
+        body = body.chars().take(keep.saturating_sub(TRUNCATED.len())).collect::<String>() + TRUNCATED;
+    }
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Open a pre-filled GitHub issue in the browser
+///
+/// With `include_diagnostics`, a crash report zip is generated, revealed in the file
+/// manager and its failed checks are summarized in the issue.
+#[tauri::command]
+pub async fn report_issue(
+    app_handle: AppHandle,
+    state: State<'_, DaemonState>,
+    title: String,
+    description: String,
+    include_diagnostics: bool,
+) -> Result<IssueReport, String> {
+    let title = title.trim();
+    if title.is_empty() {
+        return Err("Issue title cannot be empty".to_string());
+    }
+
+    let mut body = format!("{}\n\n{}", description.trim(), environment_section(&app_handle));
+    let mut bundle_path = None;
+    if include_diagnostics {
+        let (path, diagnostics) = super::build_report(&app_handle, &state, None, None).await?;
+        let bundle_name = path.file_name().unwrap_or_default().to_string_lossy().to_string();
+        let _ = write!(body, "\n{}", diagnostics_section(&diagnostics, &bundle_name));
+        if let Err(e) = app_handle.opener().reveal_item_in_dir(&path) {
+            eprintln!("[crash-report] ⚠️ Failed to reveal the diagnostics bundle: {}", e);
+        }
+        bundle_path = Some(path.to_string_lossy().to_string());
+    }
+
+    let url = issue_url(title, &body)?;
+    app_handle
+        .opener()
+        .open_url(&url, None::<&str>)
+        .map_err(|e| format!("Failed to open the browser: {}", e))?;
+    println!("[crash-report] 🐛 Issue page opened ({} chars)", url.len());
+    Ok(IssueReport { url, bundle_path })
+}
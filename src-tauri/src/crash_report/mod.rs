@@ -0,0 +1,269 @@
+/// Crash report module
+///
+/// Bundles everything support usually asks for (daemon logs, versions, OS info,
+/// USB devices, kinematics self-test, diagnostics self-check, app panics) into a
+/// single zip the user can attach to a GitHub issue, or send in one click when
+/// `crash_report_endpoint` is set. `report_issue` (see issue.rs) opens a pre-filled
+/// GitHub issue for it.
+
+pub mod issue;
+pub mod panic_hook;
+
+use serde::Serialize;
+use std::io::Write;
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager, State};
+
+use crate::daemon::DaemonState;
+
+/// Default number of daemon log lines included in a report
+const DEFAULT_LOG_LINES: usize = 500;
+const UPLOAD_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(60);
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+#[derive(Debug, Serialize, Clone)]
+pub struct SystemInfo {
+    pub os: String,
+    pub arch: String,
+    pub os_version: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct UsbDeviceInfo {
+    pub port_name: String,
+    pub vid: Option<String>,
+    pub pid: Option<String>,
+    pub serial_number: Option<String>,
+    pub manufacturer: Option<String>,
+    pub product: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct ReportSummary {
+    generated_at: u128,
+    versions: crate::versions::ComponentVersions,
+    system: SystemInfo,
+}
+
+// ============================================================================
+// COLLECTORS
+// ============================================================================
+
+/// Collect OS name, architecture and version string
+pub fn collect_system_info() -> SystemInfo {
+    use std::process::Command;
+
+    #[cfg(target_os = "macos")]
+    let version_cmd = Command::new("sw_vers").output();
+    #[cfg(target_os = "windows")]
+    let version_cmd = Command::new("cmd").args(["/C", "ver"]).output();
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    let version_cmd = Command::new("uname").arg("-a").output();
+
+    let os_version = version_cmd
+        .ok()
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().replace('\n', " | "))
+        .unwrap_or_else(|| "unknown".to_string());
+
+    SystemInfo {
+        os: std::env::consts::OS.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        os_version,
+    }
+}
+
+/// List every serial port with its USB descriptor (if any)
+pub fn collect_usb_devices() -> Vec<UsbDeviceInfo> {
+    serialport::available_ports()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|port| match port.port_type {
+            serialport::SerialPortType::UsbPort(usb) => UsbDeviceInfo {
+                port_name: port.port_name,
+                vid: Some(format!("{:04x}", usb.vid)),
+                pid: Some(format!("{:04x}", usb.pid)),
+                serial_number: usb.serial_number,
+                manufacturer: usb.manufacturer,
+                product: usb.product,
+            },
+            _ => UsbDeviceInfo {
+                port_name: port.port_name,
+                vid: None,
+                pid: None,
+                serial_number: None,
+                manufacturer: None,
+                product: None,
+            },
+        })
+        .collect()
+}
+
+/// Directory where crash reports are written (<app data>/crash-reports)
+pub fn get_reports_dir(app_handle: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?
+        .join("crash-reports");
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create crash reports dir: {}", e))?;
+    Ok(dir)
+}
+
+fn pretty_json<T: Serialize>(value: &T) -> String {
+    serde_json::to_string_pretty(value).unwrap_or_default()
+}
+
+/// Add a text file to the zip archive
+fn add_zip_entry<W: Write + std::io::Seek>(
+    zip: &mut zip::ZipWriter<W>,
+    name: &str,
+    content: &[u8],
+) -> Result<(), String> {
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+    zip.start_file(name, options)
+        .map_err(|e| format!("Failed to add {} to report: {}", name, e))?;
+    zip.write_all(content)
+        .map_err(|e| format!("Failed to write {} to report: {}", name, e))
+}
+
+/// Write the report zip and return its path, with the diagnostics it includes
+async fn build_report(
+    app_handle: &AppHandle,
+    state: &DaemonState,
+    log_lines: Option<usize>,
+    kinematics_self_test: Option<serde_json::Value>,
+) -> Result<(PathBuf, crate::diagnostics::DiagnosticsReport), String> {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    let log_lines = log_lines.unwrap_or(DEFAULT_LOG_LINES);
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or(0);
+
+    println!("[crash-report] 📦 Generating crash report...");
+
+    // 1. Versions
+    let summary = ReportSummary {
+        generated_at: timestamp,
+        versions: crate::versions::collect_component_versions(app_handle),
+        system: collect_system_info(),
+    };
+
+    // 2. Logs (last N sidecar lines + app logs)
+    let daemon_logs = {
+        let logs = state.sidecar_logs.lock().unwrap();
+        let skip = logs.len().saturating_sub(log_lines);
+        logs.iter().skip(skip).cloned().collect::<Vec<_>>().join("\n")
+    };
+    let app_logs = state.logs.lock().unwrap().iter().cloned().collect::<Vec<_>>().join("\n");
+
+    // 3. Self-check (its serial test is skipped while the daemon runs)
+    let diagnostics = crate::diagnostics::collect(app_handle, kinematics_self_test.as_ref(), None).await;
+
+    // 4. Write the zip
+    let reports_dir = get_reports_dir(app_handle)?;
+    let report_path = reports_dir
+        .join(format!("reachy-mini-crash-report-{}.zip", timestamp));
+    let file = std::fs::File::create(&report_path)
+        .map_err(|e| format!("Failed to create report file: {}", e))?;
+    let mut zip = zip::ZipWriter::new(file);
+
+    add_zip_entry(&mut zip, "summary.json", pretty_json(&summary).as_bytes())?;
+    add_zip_entry(&mut zip, "usb_devices.json", pretty_json(&collect_usb_devices()).as_bytes())?;
+    add_zip_entry(&mut zip, "daemon.log", daemon_logs.as_bytes())?;
+    add_zip_entry(&mut zip, "app.log", app_logs.as_bytes())?;
+
+    let kinematics = kinematics_self_test
+        .unwrap_or_else(|| serde_json::json!({ "status": "not_run" }));
+    add_zip_entry(&mut zip, "kinematics_self_test.json", pretty_json(&kinematics).as_bytes())?;
+    add_zip_entry(&mut zip, "diagnostics.json", pretty_json(&diagnostics).as_bytes())?;
+
+    // 5. App panics not sent yet
+    for (_, panic) in panic_hook::pending_reports(&reports_dir) {
+        add_zip_entry(&mut zip, &format!("panics/panic-{}.json", panic.t), pretty_json(&panic).as_bytes())?;
+    }
+
+    zip.finish()
+        .map_err(|e| format!("Failed to finalize report: {}", e))?;
+
+    println!("[crash-report] ✅ Report written to {:?}", report_path);
+    Ok((report_path, diagnostics))
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Generate a crash report zip and return its path
+///
+/// # Arguments
+/// * `log_lines` - Number of daemon log lines to include (default: 500)
+/// * `kinematics_self_test` - Self-test results from the kinematics WASM module
+///   (it only runs in the webview, so the frontend passes them in)
+#[tauri::command]
+pub async fn generate_crash_report(
+    app_handle: AppHandle,
+    state: State<'_, DaemonState>,
+    log_lines: Option<usize>,
+    kinematics_self_test: Option<serde_json::Value>,
+) -> Result<String, String> {
+    let (report_path, _) = build_report(&app_handle, &state, log_lines, kinematics_self_test).await?;
+    Ok(report_path.to_string_lossy().to_string())
+}
+
+/// Generate a crash report and upload it to `crash_report_endpoint`, returns the zip path
+///
+/// Pending panic reports are cleared once uploaded (they are kept in the zip).
+#[tauri::command]
+pub async fn send_crash_report(
+    app_handle: AppHandle,
+    state: State<'_, DaemonState>,
+    settings: State<'_, crate::settings::SettingsState>,
+    kinematics_self_test: Option<serde_json::Value>,
+) -> Result<String, String> {
+    let endpoint = settings
+        .get()
+        .crash_report_endpoint
+        .ok_or("No crash report endpoint configured")?;
+    let (report_path, _) = build_report(&app_handle, &state, None, kinematics_self_test).await?;
+    let content = std::fs::read(&report_path)
+        .map_err(|e| format!("Failed to read report: {}", e))?;
+
+    println!("[crash-report] 📤 Sending crash report ({} bytes)", content.len());
+    let response = reqwest::Client::new()
+        .post(&endpoint)
+        .timeout(UPLOAD_TIMEOUT)
+        .header("Content-Type", "application/zip")
+        .header("X-App-Version", app_handle.package_info().version.to_string())
+        .body(content)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to send crash report: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("Crash report endpoint returned {}", response.status()));
+    }
+
+    panic_hook::clear_reports(&get_reports_dir(&app_handle)?);
+    println!("[crash-report] ✅ Crash report sent");
+    Ok(report_path.to_string_lossy().to_string())
+}
+
+/// App panics recorded and not sent or dismissed yet, newest first
+#[tauri::command]
+pub fn get_panic_reports(app_handle: AppHandle) -> Result<Vec<panic_hook::PanicReport>, String> {
+    let dir = get_reports_dir(&app_handle)?;
+    Ok(panic_hook::pending_reports(&dir).into_iter().map(|(_, report)| report).collect())
+}
+
+/// Forget pending panic reports without sending them
+#[tauri::command]
+pub fn dismiss_panic_reports(app_handle: AppHandle) -> Result<(), String> {
+    panic_hook::clear_reports(&get_reports_dir(&app_handle)?);
+    Ok(())
+}
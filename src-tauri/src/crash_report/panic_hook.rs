@@ -0,0 +1,136 @@
+/// Panic capture
+///
+/// A panic in a command used to kill the call silently (the webview promise never
+/// settles). The hook writes `<app data>/crash-reports/panic-<unix millis>.json` with
+/// the message, location, backtrace and last daemon logs, then emits `app-panic` so the
+/// frontend can offer to send a report (see `send_crash_report`).
+///
+/// Reports not sent or dismissed yet are still listed after a restart. In kiosk mode, a
+/// panic on the main thread relaunches the app.
+
+use serde::{Deserialize, Serialize};
+use std::backtrace::Backtrace;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::daemon::DaemonState;
+
+/// Daemon log lines saved with a panic
+const PANIC_LOG_LINES: usize = 200;
+const PANIC_FILE_PREFIX: &str = "panic-";
+
+static APP_HANDLE: OnceLock<AppHandle> = OnceLock::new();
+static REPORTS_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PanicReport {
+    /// Unix millis
+    pub t: u64,
+    pub message: String,
+    /// file:line:column
+    pub location: Option<String>,
+    pub thread: Option<String>,
+    pub backtrace: String,
+    pub app_version: String,
+    pub os: String,
+    pub arch: String,
+    pub daemon_logs: Vec<String>,
+}
+
+/// Message of the panic payload (`panic!` with a literal or a formatted string)
+fn payload_message(info: &std::panic::PanicHookInfo) -> String {
+    let payload = info.payload();
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "Unknown panic payload".to_string()
+    }
+}
+
+/// Last daemon lines, skipped if the panic happened while the log buffer was locked
+fn recent_daemon_logs(app_handle: &AppHandle) -> Vec<String> {
+    let state = app_handle.state::<DaemonState>();
+    let Ok(logs) = state.sidecar_logs.try_lock() else {
+        return Vec::new();
+    };
+    let skip = logs.len().saturating_sub(PANIC_LOG_LINES);
+    logs.iter().skip(skip).cloned().collect()
+}
+
+fn write_report(dir: &Path, report: &PanicReport) -> Result<PathBuf, String> {
+    std::fs::create_dir_all(dir).map_err(|e| format!("Failed to create crash reports dir: {}", e))?;
+    let path = dir.join(format!("{}{}.json", PANIC_FILE_PREFIX, report.t));
+    let content = serde_json::to_string_pretty(report).map_err(|e| format!("Failed to serialize panic report: {}", e))?;
+    std::fs::write(&path, content).map_err(|e| format!("Failed to write panic report: {}", e))?;
+    Ok(path)
+}
+
+/// Install the panic hook (call once in setup, the default hook still prints to stderr)
+pub fn install(app_handle: AppHandle, reports_dir: PathBuf) {
+    let _ = APP_HANDLE.set(app_handle);
+    let _ = REPORTS_DIR.set(reports_dir);
+
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        default_hook(info);
+
+        let (Some(app_handle), Some(dir)) = (APP_HANDLE.get(), REPORTS_DIR.get()) else {
+            return;
+        };
+        let report = PanicReport {
+            t: crate::daemon::history::now_millis(),
+            message: payload_message(info),
+            location: info.location().map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column())),
+            thread: std::thread::current().name().map(String::from),
+            backtrace: Backtrace::force_capture().to_string(),
+            app_version: app_handle.package_info().version.to_string(),
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            daemon_logs: recent_daemon_logs(app_handle),
+        };
+        match write_report(dir, &report) {
+            Ok(path) => eprintln!("[crash-report] 💥 Panic report written to {:?}", path),
+            Err(e) => eprintln!("[crash-report] ❌ {}", e),
+        }
+        let _ = app_handle.emit("app-panic", &report);
+
+        // 🔒 Kiosk: a panic on the main thread takes the app down, start it again
+        if crate::kiosk::is_active() && report.thread.as_deref() == Some("main") {
+            eprintln!("[crash-report] 🔁 Kiosk mode: relaunching the app");
+            app_handle.restart();
+        }
+    }));
+}
+
+/// Panic reports waiting to be sent or dismissed, newest first
+pub fn pending_reports(dir: &Path) -> Vec<(PathBuf, PanicReport)> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut reports: Vec<(PathBuf, PanicReport)> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            let name = path.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+            name.starts_with(PANIC_FILE_PREFIX) && name.ends_with(".json")
+        })
+        .filter_map(|path| {
+            let report = serde_json::from_str(&std::fs::read_to_string(&path).ok()?).ok()?;
+            Some((path, report))
+        })
+        .collect();
+    reports.sort_by_key(|(_, report)| std::cmp::Reverse(report.t));
+    reports
+}
+
+/// Delete pending panic reports (once sent or dismissed)
+pub fn clear_reports(dir: &Path) {
+    for (path, _) in pending_reports(dir) {
+        if let Err(e) = std::fs::remove_file(&path) {
+            eprintln!("[crash-report] ⚠️ Failed to remove {:?}: {}", path, e);
+        }
+    }
+}
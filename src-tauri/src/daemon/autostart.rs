@@ -0,0 +1,108 @@
+/// Daemon auto-start on USB robot connection
+///
+/// Watches the USB monitor for robot plug/unplug transitions and, when enabled in
+/// settings, starts the daemon on connection and stops it (or makes the motors
+/// compliant) on disconnection.
+///
+/// Emits `daemon-auto-start` with { action, port } so the frontend can follow along.
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::daemon_api::{DaemonClient, MotorMode};
+use crate::settings::{DisconnectAction, SettingsState};
+use super::DaemonState;
+
+/// Interval between USB state checks (reads cached monitor state on Windows)
+const CHECK_INTERVAL_MS: u64 = 2000;
+
+#[derive(Debug, Serialize, Clone)]
+struct AutoStartEvent {
+    action: &'static str,
+    port: Option<String>,
+}
+
+/// Start the background watcher (call once in setup)
+pub fn start_watcher(app_handle: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut last_port = crate::usb::get_reachy_port();
+
+        loop {
+            tokio::time::sleep(tokio::time::Duration::from_millis(CHECK_INTERVAL_MS)).await;
+
+            let port = crate::usb::get_reachy_port();
+            if port == last_port {
+                continue;
+            }
+
+            let settings = app_handle.state::<SettingsState>().get();
+            if settings.auto_start_daemon {
+                match (&last_port, &port) {
+                    (None, Some(new_port)) => on_robot_connected(&app_handle, new_port).await,
+                    (Some(_), None) => on_robot_disconnected(&app_handle, settings.on_robot_disconnect).await,
+                    _ => {}
+                }
+            }
+
+            last_port = port;
+        }
+    });
+}
+
+fn is_daemon_running(app_handle: &AppHandle) -> bool {
+    app_handle.state::<DaemonState>().process.lock().unwrap().is_some()
+}
+
+async fn on_robot_connected(app_handle: &AppHandle, port: &str) {
+    if is_daemon_running(app_handle) {
+        return;
+    }
+
+    println!("[autostart] 🔌 Robot connected on {} - starting daemon", port);
+    let handle = app_handle.clone();
+    let daemon_port = port.to_string();
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        let state = handle.state::<DaemonState>();
+        crate::start_daemon(handle.clone(), state, Some(false), None, Some(daemon_port))
+    })
+    .await
+    .map_err(|e| e.to_string())
+    .and_then(|r| r);
+
+    match result {
+        Ok(_) => {
+            let _ = app_handle.emit("daemon-auto-start", AutoStartEvent {
+                action: "started",
+                port: Some(port.to_string()),
+            });
+        }
+        Err(e) => eprintln!("[autostart] ❌ Failed to auto-start daemon: {}", e),
+    }
+}
+
+async fn on_robot_disconnected(app_handle: &AppHandle, action: DisconnectAction) {
+    if !is_daemon_running(app_handle) {
+        return;
+    }
+
+    match action {
+        DisconnectAction::StopDaemon => {
+            println!("[autostart] 🔌 Robot disconnected - stopping daemon");
+            let handle = app_handle.clone();
+            let _ = tauri::async_runtime::spawn_blocking(move || {
+                super::kill_daemon(&handle.state::<DaemonState>());
+            })
+            .await;
+            let _ = app_handle.emit("daemon-auto-start", AutoStartEvent { action: "stopped", port: None });
+        }
+        DisconnectAction::SetCompliant => {
+            println!("[autostart] 🔌 Robot disconnected - setting motors compliant");
+            let result = DaemonClient::new().set_motor_mode(MotorMode::Disabled).await;
+            if let Err(e) = result {
+                eprintln!("[autostart] ⚠️ Failed to set motors compliant: {}", e);
+            }
+            let _ = app_handle.emit("daemon-auto-start", AutoStartEvent { action: "compliant", port: None });
+        }
+        DisconnectAction::Nothing => {}
+    }
+}
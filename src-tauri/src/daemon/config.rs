@@ -0,0 +1,271 @@
+/// Daemon configuration editor
+///
+/// The daemon is configured by its command-line options. Their values are kept in
+/// `daemon_config.json` in the venv directory (next to `.venv`), validated here against
+/// `schema()` and turned into arguments at every daemon spawn (see python::build_daemon_args).
+///
+/// Saving a changed configuration restarts the local daemon, if one is running, and
+/// relaunches the app it was running.
+
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use std::path::PathBuf;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+use super::DaemonState;
+use crate::daemon_api::DaemonClient;
+
+const CONFIG_FILE: &str = "daemon_config.json";
+const HEALTHY_TIMEOUT: Duration = Duration::from_secs(60);
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum OptionKind {
+    /// Flag present when true
+    Bool,
+    /// Flag followed by the value
+    Number { min: f64, max: f64 },
+    Choice { values: Vec<&'static str> },
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ConfigOption {
+    pub key: &'static str,
+    pub label: &'static str,
+    /// kinematics, control or features
+    pub group: &'static str,
+    #[serde(flatten)]
+    pub kind: OptionKind,
+    pub default: Value,
+    /// Daemon command-line flag
+    pub flag: &'static str,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct DaemonConfig {
+    pub schema: Vec<ConfigOption>,
+    /// Every option, defaults filled in
+    pub values: Map<String, Value>,
+    pub path: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct DaemonConfigUpdate {
+    pub values: Map<String, Value>,
+    /// The running daemon was restarted to apply the change
+    pub restarted: bool,
+}
+
+// ============================================================================
+// HELPER FUNCTIONS
+// ============================================================================
+
+pub fn schema() -> Vec<ConfigOption> {
+    vec![
+        ConfigOption {
+            key: "kinematics_engine",
+            label: "Kinematics engine",
+            group: "kinematics",
+            kind: OptionKind::Choice { values: vec!["AnalyticalKinematics", "Placo", "NN"] },
+            default: json!("AnalyticalKinematics"),
+            flag: "--kinematics-engine",
+        },
+        ConfigOption {
+            key: "check_collision",
+            label: "Check head collisions (Placo only)",
+            group: "kinematics",
+            kind: OptionKind::Bool,
+            default: json!(false),
+            flag: "--check-collision",
+        },
+        ConfigOption {
+            key: "timeout_health_check",
+            label: "Stop when no client checked in for (seconds)",
+            group: "control",
+            kind: OptionKind::Number { min: 1.0, max: 3600.0 },
+            default: Value::Null,
+            flag: "--timeout-health-check",
+        },
+        ConfigOption {
+            key: "sleep_on_start",
+            label: "Start with the robot asleep",
+            group: "control",
+            kind: OptionKind::Bool,
+            default: json!(true),
+            flag: "--no-wake-up-on-start",
+        },
+        ConfigOption {
+            key: "preload_datasets",
+            label: "Download emotions and dances at startup",
+            group: "features",
+            kind: OptionKind::Bool,
+            default: json!(true),
+            flag: "--preload-datasets",
+        },
+        ConfigOption {
+            key: "deactivate_audio",
+            label: "Disable audio",
+            group: "features",
+            kind: OptionKind::Bool,
+            default: json!(false),
+            flag: "--deactivate-audio",
+        },
+    ]
+}
+
+fn config_path(app_handle: &AppHandle) -> Result<PathBuf, String> {
+    Ok(crate::update::get_local_venv_path(app_handle)?.join(CONFIG_FILE))
+}
+
+/// Every problem with a configuration (empty when valid)
+pub fn validate(values: &Map<String, Value>) -> Vec<String> {
+    let schema = schema();
+    let mut errors = Vec::new();
+
+    for (key, value) in values {
+        let Some(option) = schema.iter().find(|option| option.key == key) else {
+            errors.push(format!("Unknown option: {}", key));
+            continue;
+        };
+        let valid = match (&option.kind, value) {
+            (_, Value::Null) => option.default.is_null(),
+            (OptionKind::Bool, Value::Bool(_)) => true,
+            (OptionKind::Number { min, max }, Value::Number(n)) => {
+                n.as_f64().is_some_and(|n| n >= *min && n <= *max)
+            }
+            (OptionKind::Choice { values }, Value::String(s)) => values.contains(&s.as_str()),
+            _ => false,
+        };
+        if !valid {
+            errors.push(format!("Invalid value for {}: {}", option.label, value));
+        }
+    }
+
+    let engine = values.get("kinematics_engine").and_then(Value::as_str).unwrap_or("AnalyticalKinematics");
+    if values.get("check_collision") == Some(&Value::Bool(true)) && engine != "Placo" {
+        errors.push("Collision checking needs the Placo kinematics engine".to_string());
+    }
+    errors
+}
+
+/// Saved values over the defaults (defaults only if the file is missing or invalid)
+fn with_defaults(saved: Map<String, Value>) -> Map<String, Value> {
+    let mut values: Map<String, Value> = schema()
+        .into_iter()
+        .map(|option| (option.key.to_string(), option.default))
+        .collect();
+    if validate(&saved).is_empty() {
+        values.extend(saved);
+    } else {
+        eprintln!("[daemon-config] ⚠️ Invalid {}, using defaults", CONFIG_FILE);
+    }
+    values
+}
+
+/// Current configuration
+pub fn load(app_handle: &AppHandle) -> Map<String, Value> {
+    let saved = config_path(app_handle)
+        .ok()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default();
+    with_defaults(saved)
+}
+
+/// Daemon arguments for a configuration
+pub fn to_args(values: &Map<String, Value>) -> Vec<String> {
+    let mut args = Vec::new();
+    for option in schema() {
+        match values.get(option.key) {
+            Some(Value::Bool(true)) => args.push(option.flag.to_string()),
+            Some(Value::Bool(false)) | Some(Value::Null) | None => {}
+            Some(Value::String(s)) => args.extend([option.flag.to_string(), s.clone()]),
+            Some(value) => args.extend([option.flag.to_string(), value.to_string()]),
+        }
+    }
+    args
+}
+
+/// Restart the local daemon in its current mode and relaunch its app
+async fn restart_daemon(app_handle: &AppHandle) -> Result<(), String> {
+    let Some(sim_mode) = app_handle.state::<DaemonState>().history.lock().unwrap().current_mode() else {
+        return Err("No local daemon to restart".to_string());
+    };
+    let client = DaemonClient::new();
+    let running_app = client.running_app().await;
+
+    println!("[daemon-config] 🔄 Restarting daemon to apply the configuration");
+    let handle = app_handle.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        super::kill_daemon(&handle.state::<DaemonState>());
+    })
+    .await
+    .map_err(|e| format!("Failed to stop daemon: {}", e))?;
+    super::mode_switch::wait_for_port_release().await;
+
+    let handle = app_handle.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::start_daemon(handle.clone(), handle.state::<DaemonState>(), Some(sim_mode), None, None)
+    })
+    .await
+    .map_err(|e| format!("Failed to start daemon: {}", e))??;
+
+    if !client.wait_healthy(HEALTHY_TIMEOUT).await {
+        return Err("Daemon did not become ready with the new configuration".to_string());
+    }
+    if let Some(app) = running_app {
+        if let Err(e) = client.start_app(&app).await {
+            eprintln!("[daemon-config] ⚠️ Failed to restart app {}: {}", app, e);
+        }
+    }
+    Ok(())
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+#[tauri::command]
+pub fn get_daemon_config(app_handle: AppHandle) -> DaemonConfig {
+    DaemonConfig {
+        schema: schema(),
+        values: load(&app_handle),
+        path: config_path(&app_handle).ok().map(|path| path.to_string_lossy().to_string()),
+    }
+}
+
+/// Problems with a configuration, without saving it (empty when valid)
+#[tauri::command]
+pub fn validate_daemon_config(values: Map<String, Value>) -> Vec<String> {
+    validate(&values)
+}
+
+/// Validate and save the configuration, restarting the local daemon if it changed
+#[tauri::command]
+pub async fn set_daemon_config(app_handle: AppHandle, values: Map<String, Value>) -> Result<DaemonConfigUpdate, String> {
+    let errors = validate(&values);
+    if !errors.is_empty() {
+        return Err(errors.join("; "));
+    }
+    let previous = load(&app_handle);
+    let values = with_defaults(values);
+
+    let path = config_path(&app_handle)?;
+    let content = serde_json::to_string_pretty(&values)
+        .map_err(|e| format!("Failed to serialize daemon configuration: {}", e))?;
+    std::fs::write(&path, content)
+        .map_err(|e| format!("Failed to write daemon configuration: {}", e))?;
+    println!("[daemon-config] 💾 Configuration saved to {:?}", path);
+
+    let daemon_running = app_handle.state::<DaemonState>().process.lock().unwrap().is_some();
+    let restarted = values != previous && daemon_running;
+    if restarted {
+        restart_daemon(&app_handle).await?;
+    }
+    Ok(DaemonConfigUpdate { values, restarted })
+}
@@ -0,0 +1,206 @@
+/// Daemon log parsing into typed events
+///
+/// Recognizes known daemon log patterns and turns them into structured Tauri events,
+/// so the frontend doesn't have to regex raw log strings.
+///
+/// Emitted events:
+/// - `motor-warning`: { motor, kind, message }
+/// - `calibration-needed`: { message }
+/// - `websocket-client`: { connected, message }
+/// - `uv-trampoline-event`: { event, ... } (venv setup progress, spawn, exit; see uv_wrapper::TrampolineEvent)
+
+use serde::Serialize;
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum MotorWarningKind {
+    Overheating,
+    Overload,
+    Voltage,
+    Communication,
+}
+
+#[derive(Debug, Serialize, Clone)]
+#[serde(untagged)]
+pub enum DaemonEvent {
+    MotorWarning {
+        motor: Option<String>,
+        kind: MotorWarningKind,
+        message: String,
+    },
+    CalibrationNeeded {
+        message: String,
+    },
+    WebSocketClient {
+        connected: bool,
+        message: String,
+    },
+}
+
+impl DaemonEvent {
+    /// Tauri event name for this event
+    pub fn name(&self) -> &'static str {
+        match self {
+            DaemonEvent::MotorWarning { .. } => "motor-warning",
+            DaemonEvent::CalibrationNeeded { .. } => "calibration-needed",
+            DaemonEvent::WebSocketClient { .. } => "websocket-client",
+        }
+    }
+}
+
+/// Extract the motor name following "motor" (e.g. "Motor stewart_3 overheating" -> "stewart_3")
+fn extract_motor_name(line: &str) -> Option<String> {
+    const KEYWORD: &str = "motor ";
+    // Searched in `line` itself: offsets in a lowercased copy may not be char boundaries here
+    let pos = line
+        .char_indices()
+        .map(|(i, _)| i)
+        .find(|&i| line[i..].get(..KEYWORD.len()).is_some_and(|word| word.eq_ignore_ascii_case(KEYWORD)))?;
+    line[pos + KEYWORD.len()..]
+        .split_whitespace()
+        .next()
+        .map(|name| name.trim_matches(|c: char| !c.is_alphanumeric() && c != '_').to_string())
+        .filter(|name| !name.is_empty())
+}
+
+/// Line reports a problem (log level or wording), not just a routine reading
+/// like "motor temperature: 35°C"
+fn is_alert(lower: &str) -> bool {
+    ["warning", "error", "critical", "too high", "too low", "exceed", "limit"]
+        .iter()
+        .any(|keyword| lower.contains(keyword))
+}
+
+/// Parse a single daemon log line (stdout or stderr)
+pub fn parse_log_line(line: &str) -> Option<DaemonEvent> {
+    let lower = line.to_lowercase();
+    let message = line.trim().to_string();
+
+    if lower.contains("motor") {
+        let kind = if lower.contains("overheat") || (lower.contains("temperature") && is_alert(&lower)) {
+            Some(MotorWarningKind::Overheating)
+        } else if lower.contains("overload") {
+            Some(MotorWarningKind::Overload)
+        } else if lower.contains("voltage") && is_alert(&lower) {
+            Some(MotorWarningKind::Voltage)
+        } else if lower.contains("no response") || lower.contains("not responding") {
+            Some(MotorWarningKind::Communication)
+        } else {
+            None
+        };
+
+        if let Some(kind) = kind {
+            return Some(DaemonEvent::MotorWarning {
+                motor: extract_motor_name(line),
+                kind,
+                message,
+            });
+        }
+    }
+
+    if lower.contains("calibration required")
+        || lower.contains("calibration needed")
+        || lower.contains("not calibrated")
+    {
+        return Some(DaemonEvent::CalibrationNeeded { message });
+    }
+
+    if lower.contains("websocket") {
+        if ["disconnected", "connection closed", "connection lost"].iter().any(|p| lower.contains(p)) {
+            return Some(DaemonEvent::WebSocketClient { connected: false, message });
+        }
+        // Specific phrases only: a bare "connected" also matches "not connected (yet)"
+        if ["client connected", "connection accepted", "[accepted]"].iter().any(|p| lower.contains(p)) {
+            return Some(DaemonEvent::WebSocketClient { connected: true, message });
+        }
+    }
+
+    None
+}
+
+/// Structured event line printed by `uv-trampoline --json` (passed through as-is)
+pub fn parse_trampoline_event(line: &str) -> Option<serde_json::Value> {
+    let json = line.trim_end().strip_prefix(uv_wrapper::EVENT_PREFIX)?;
+    serde_json::from_str(json).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn motor_warning(line: &str) -> Option<(Option<String>, MotorWarningKind)> {
+        match parse_log_line(line)? {
+            DaemonEvent::MotorWarning { motor, kind, .. } => Some((motor, kind)),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn parses_motor_warnings() {
+        assert_eq!(
+            motor_warning("WARNING: Motor stewart_3 overheating"),
+            Some((Some("stewart_3".to_string()), MotorWarningKind::Overheating))
+        );
+        assert_eq!(
+            motor_warning("ERROR - motor body_rotation temperature too high (72°C)"),
+            Some((Some("body_rotation".to_string()), MotorWarningKind::Overheating))
+        );
+        assert_eq!(
+            motor_warning("Motor stewart_1 overload detected"),
+            Some((Some("stewart_1".to_string()), MotorWarningKind::Overload))
+        );
+        assert_eq!(
+            motor_warning("WARNING motor stewart_2 voltage out of range"),
+            Some((Some("stewart_2".to_string()), MotorWarningKind::Voltage))
+        );
+        assert_eq!(
+            motor_warning("Motor left_antenna not responding"),
+            Some((Some("left_antenna".to_string()), MotorWarningKind::Communication))
+        );
+    }
+
+    #[test]
+    fn ignores_routine_motor_readings() {
+        assert_eq!(motor_warning("INFO motor temperature: 35°C"), None);
+        assert_eq!(motor_warning("motor stewart_4 voltage: 7.4V"), None);
+        assert_eq!(motor_warning("Motors enabled"), None);
+    }
+
+    #[test]
+    fn extracts_motor_names_after_multibyte_characters() {
+        // "İ" lowercases to 3 bytes instead of 2: offsets from a lowercased copy would be off
+        assert_eq!(extract_motor_name("İİİ MOTOR stewart_5 overheating"), Some("stewart_5".to_string()));
+        assert_eq!(extract_motor_name("İ motor: x"), None);
+        assert_eq!(extract_motor_name("température du moteur élevée"), None);
+        assert_eq!(
+            motor_warning("WARNING İ motor stewart_6 overheating").map(|(motor, _)| motor),
+            Some(Some("stewart_6".to_string()))
+        );
+    }
+
+    #[test]
+    fn parses_calibration_and_websocket_events() {
+        assert!(matches!(
+            parse_log_line("Robot not calibrated, please run calibration"),
+            Some(DaemonEvent::CalibrationNeeded { .. })
+        ));
+        assert!(matches!(
+            parse_log_line("WebSocket client disconnected"),
+            Some(DaemonEvent::WebSocketClient { connected: false, .. })
+        ));
+        assert!(matches!(
+            parse_log_line("WebSocket connection accepted"),
+            Some(DaemonEvent::WebSocketClient { connected: true, .. })
+        ));
+        assert!(matches!(
+            parse_log_line("WebSocket client connected from 127.0.0.1"),
+            Some(DaemonEvent::WebSocketClient { connected: true, .. })
+        ));
+        assert!(parse_log_line("Uvicorn running on http://0.0.0.0:8000").is_none());
+        assert!(parse_log_line("WebSocket not connected yet, retrying").is_none());
+        assert!(matches!(
+            parse_log_line("WebSocket connection lost / not connected yet"),
+            Some(DaemonEvent::WebSocketClient { connected: false, .. })
+        ));
+    }
+}
@@ -0,0 +1,176 @@
+/// Daemon run history
+///
+/// Persists one record per daemon run (start/stop time, exit code, mode, crash reason)
+/// to `<app data>/daemon_run_history.json` so support and the UI can see crash patterns.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Maximum number of runs kept on disk
+const MAX_RUNS: usize = 200;
+
+const HISTORY_FILE: &str = "daemon_run_history.json";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DaemonRun {
+    /// Unix millis
+    pub started_at: u64,
+    /// Unix millis (None while running)
+    pub stopped_at: Option<u64>,
+    pub exit_code: Option<i32>,
+    pub sim_mode: bool,
+    /// True if the daemon terminated on its own (not stopped by the app)
+    pub crashed: bool,
+    pub crash_reason: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct DaemonRunStats {
+    pub total_runs: usize,
+    pub total_crashes: usize,
+    pub crashes_last_hour: usize,
+    pub crashes_last_day: usize,
+    pub last_crash_reason: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct DaemonRunHistory {
+    pub runs: Vec<DaemonRun>,
+    pub stats: DaemonRunStats,
+}
+
+/// In-memory run history, mirrored to disk on every change
+#[derive(Default)]
+pub struct RunHistory {
+    runs: Vec<DaemonRun>,
+    path: Option<PathBuf>,
+}
+
+pub fn now_millis() -> u64 {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+impl RunHistory {
+    /// Load history from the app data directory (starts empty if missing or corrupted)
+    pub fn load(app_data_dir: &Path) -> Self {
+        let path = app_data_dir.join(HISTORY_FILE);
+        let runs = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        Self { runs, path: Some(path) }
+    }
+
+    fn save(&self) {
+        let Some(path) = &self.path else { return };
+        if let Some(parent) = path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        match serde_json::to_string_pretty(&self.runs) {
+            Ok(content) => {
+                if let Err(e) = std::fs::write(path, content) {
+                    eprintln!("[history] ⚠️ Failed to save run history: {}", e);
+                }
+            }
+            Err(e) => eprintln!("[history] ⚠️ Failed to serialize run history: {}", e),
+        }
+    }
+
+    /// Record a new daemon run
+    pub fn record_start(&mut self, sim_mode: bool) {
+        // A run still open here was never closed (app killed) - mark it as unknown stop
+        self.close_open_run(None, false, None);
+        self.runs.push(DaemonRun {
+            started_at: now_millis(),
+            stopped_at: None,
+            exit_code: None,
+            sim_mode,
+            crashed: false,
+            crash_reason: None,
+        });
+        if self.runs.len() > MAX_RUNS {
+            let excess = self.runs.len() - MAX_RUNS;
+            self.runs.drain(..excess);
+        }
+        self.save();
+    }
+
+    /// Record a stop requested by the app
+    pub fn record_stop(&mut self) {
+        if self.close_open_run(None, false, None) {
+            self.save();
+        }
+    }
+
+    /// Record a daemon that terminated on its own, returns true if it crashed
+    pub fn record_termination(&mut self, exit_code: Option<i32>, crash_reason: Option<String>) -> bool {
+        let crashed = exit_code != Some(0);
+        if self.close_open_run(exit_code, crashed, crash_reason) {
+            self.save();
+            return crashed;
+        }
+        false
+    }
+
+    fn close_open_run(&mut self, exit_code: Option<i32>, crashed: bool, crash_reason: Option<String>) -> bool {
+        match self.runs.last_mut() {
+            Some(run) if run.stopped_at.is_none() => {
+                run.stopped_at = Some(now_millis());
+                run.exit_code = exit_code;
+                run.crashed = crashed;
+                run.crash_reason = crash_reason;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Mode of the currently running daemon (None if no run is open)
+    pub fn current_mode(&self) -> Option<bool> {
+        self.runs
+            .last()
+            .filter(|run| run.stopped_at.is_none())
+            .map(|run| run.sim_mode)
+    }
+
+    /// Most recent run (open or closed)
+    pub fn last_run(&self) -> Option<&DaemonRun> {
+        self.runs.last()
+    }
+
+    /// Snapshot of the history with aggregated statistics
+    pub fn snapshot(&self) -> DaemonRunHistory {
+        const HOUR_MS: u64 = 60 * 60 * 1000;
+        let now = now_millis();
+        let crashes = || self.runs.iter().filter(|r| r.crashed);
+        let crashes_since = |window: u64| {
+            crashes()
+                .filter(|r| r.stopped_at.unwrap_or(r.started_at) + window >= now)
+                .count()
+        };
+
+        DaemonRunHistory {
+            runs: self.runs.clone(),
+            stats: DaemonRunStats {
+                total_runs: self.runs.len(),
+                total_crashes: crashes().count(),
+                crashes_last_hour: crashes_since(HOUR_MS),
+                crashes_last_day: crashes_since(24 * HOUR_MS),
+                last_crash_reason: crashes().next_back().and_then(|r| r.crash_reason.clone()),
+            },
+        }
+    }
+}
+
+/// Guess the crash reason from the last lines of sidecar output
+pub fn extract_crash_reason<'a>(lines: impl DoubleEndedIterator<Item = &'a String>) -> Option<String> {
+    lines
+        .rev()
+        .take(50)
+        .find(|line| line.contains("Error") || line.contains("Exception") || line.contains("Traceback"))
+        .map(|line| line.trim().to_string())
+}
@@ -0,0 +1,100 @@
+/// Cross-process daemon ownership lock
+///
+/// Each app instance that spawns a daemon writes `<app data>/daemon.lock` with its PID.
+/// A second app instance checks the lock (owner PID alive + daemon port listening)
+/// before spawning or cleaning up, so two instances never fight over the serial port.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+const LOCK_FILE: &str = "daemon.lock";
+
+static LOCK_PATH: OnceLock<PathBuf> = OnceLock::new();
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DaemonOwner {
+    pub app_pid: u32,
+    /// Unix millis
+    pub started_at: u64,
+}
+
+impl DaemonOwner {
+    /// Encode as a command error string: "DAEMON_INSTANCE_CONFLICT:{json}" - parsed by frontend
+    pub fn to_error_string(&self) -> String {
+        format!(
+            "DAEMON_INSTANCE_CONFLICT:{}",
+            serde_json::to_string(self).unwrap_or_default()
+        )
+    }
+}
+
+/// Set the lock file location (called once in setup)
+pub fn init(app_data_dir: PathBuf) {
+    let _ = std::fs::create_dir_all(&app_data_dir);
+    let _ = LOCK_PATH.set(app_data_dir.join(LOCK_FILE));
+}
+
+fn read_lock() -> Option<DaemonOwner> {
+    let path = LOCK_PATH.get()?;
+    let content = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Check if a process is still running
+fn is_process_alive(pid: u32) -> bool {
+    use std::process::Command;
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        Command::new("kill")
+            .args(["-0", &pid.to_string()])
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false)
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        Command::new("tasklist")
+            .args(["/FI", &format!("PID eq {}", pid), "/FO", "CSV", "/NH"])
+            .output()
+            .map(|o| String::from_utf8_lossy(&o.stdout).contains(&format!("\"{}\"", pid)))
+            .unwrap_or(false)
+    }
+}
+
+/// Return the owner if a live daemon belongs to another app instance
+/// A lock whose owner is dead or whose daemon port is free is considered stale
+pub fn foreign_owner() -> Option<DaemonOwner> {
+    let owner = read_lock()?;
+    if owner.app_pid == std::process::id() || !is_process_alive(owner.app_pid) {
+        return None;
+    }
+    if super::find_listening_pids(8000).is_empty() {
+        return None;
+    }
+    Some(owner)
+}
+
+/// Claim daemon ownership for this app instance
+pub fn acquire() {
+    let Some(path) = LOCK_PATH.get() else { return };
+    let owner = DaemonOwner {
+        app_pid: std::process::id(),
+        started_at: super::history::now_millis(),
+    };
+    if let Ok(content) = serde_json::to_string(&owner) {
+        if let Err(e) = std::fs::write(path, content) {
+            eprintln!("[daemon] ⚠️ Failed to write daemon lock: {}", e);
+        }
+    }
+}
+
+/// Release daemon ownership (only if this instance holds it)
+pub fn release() {
+    let Some(path) = LOCK_PATH.get() else { return };
+    if read_lock().map(|o| o.app_pid == std::process::id()).unwrap_or(false) {
+        let _ = std::fs::remove_file(path);
+    }
+}
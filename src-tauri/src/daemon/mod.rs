@@ -1,5 +1,14 @@
+pub mod autostart;
+pub mod config;
+pub mod events;
+pub mod history;
+pub mod instance_lock;
+pub mod mode_switch;
+pub mod park;
+
 use std::sync::Mutex;
 use std::collections::VecDeque;
+use serde::Serialize;
 use tauri::State;
 use tauri_plugin_shell::{
     process::CommandChild,
@@ -8,22 +17,50 @@ use tauri_plugin_shell::{
 pub struct DaemonState {
     pub process: Mutex<Option<CommandChild>>,
     pub logs: Mutex<VecDeque<String>>,
+    /// Raw sidecar output (stdout + stderr), kept for crash reports
+    pub sidecar_logs: Mutex<VecDeque<String>>,
+    /// Persistent record of daemon runs (loaded in setup)
+    pub history: Mutex<history::RunHistory>,
+}
+
+impl DaemonState {
+    pub fn new() -> Self {
+        Self {
+            process: Mutex::new(None),
+            logs: Mutex::new(VecDeque::new()),
+            sidecar_logs: Mutex::new(VecDeque::new()),
+            history: Mutex::new(history::RunHistory::default()),
+        }
+    }
+}
+
+impl Default for DaemonState {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 pub const MAX_LOGS: usize = 50;
+pub const MAX_SIDECAR_LOGS: usize = 1000;
+
+/// Ports used by the daemon (8000: REST API, 8042: video stream)
+pub const DAEMON_PORTS: &[u16] = &[8000, 8042];
+
+/// Marker argument passed to every daemon we spawn (see python::build_daemon_args)
+const DESKTOP_APP_DAEMON_ARG: &str = "--desktop-app-daemon";
+
+/// Time the daemon gets to shut down after SIGTERM before its process group is killed
+#[cfg(not(target_os = "windows"))]
+const SIDECAR_STOP_GRACE: std::time::Duration = std::time::Duration::from_secs(3);
 
 // ============================================================================
 // LOG MANAGEMENT
 // ============================================================================
 
 pub fn add_log(state: &State<DaemonState>, message: String) {
-    use std::time::{SystemTime, UNIX_EPOCH};
-    
-    // Add timestamp prefix (Unix millis) for proper chronological sorting
-    let timestamp = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .map(|d| d.as_millis())
-        .unwrap_or(0);
+    // Add timestamp prefix (Unix millis, timeline clock) for proper chronological sorting
+    let timestamp = crate::timeline::now_millis();
+    crate::timeline::record(crate::timeline::Source::App, None, message.as_str(), None);
     
     // Format: "TIMESTAMP|MESSAGE" - will be parsed by frontend
     let timestamped_message = format!("{}|{}", timestamp, message);
@@ -35,6 +72,16 @@ pub fn add_log(state: &State<DaemonState>, message: String) {
     }
 }
 
+/// Keep a line of raw sidecar output (ring buffer of MAX_SIDECAR_LOGS lines)
+pub fn add_sidecar_log(state: &DaemonState, line: String) {
+    crate::timeline::record_line(crate::timeline::Source::Daemon, &line);
+    let mut logs = state.sidecar_logs.lock().unwrap();
+    logs.push_back(line);
+    if logs.len() > MAX_SIDECAR_LOGS {
+        logs.pop_front();
+    }
+}
+
 // ============================================================================
 // DAEMON LIFECYCLE MANAGEMENT
 // ============================================================================
@@ -64,8 +111,193 @@ pub fn kill_processes_on_port(port: u16, signal: Option<&str>) {
     }
 }
 
+// ============================================================================
+// PORT CONFLICT DETECTION
+// ============================================================================
+
+/// A daemon port held by a process we did not spawn
+#[derive(Debug, Serialize, Clone)]
+pub struct PortConflict {
+    pub port: u16,
+    pub pid: u32,
+    pub process_name: String,
+}
+
+impl PortConflict {
+    /// Encode as a command error string: "PORT_CONFLICT:{json}" - parsed by frontend
+    pub fn to_error_string(&self) -> String {
+        format!(
+            "PORT_CONFLICT:{}",
+            serde_json::to_string(self).unwrap_or_default()
+        )
+    }
+}
+
+/// Find PIDs of processes listening on a specific port
+#[cfg(not(target_os = "windows"))]
+pub fn find_listening_pids(port: u16) -> Vec<u32> {
+    use std::process::Command;
+    
+    // -sTCP:LISTEN: only servers (not clients like the webview connected to the daemon)
+    let output = Command::new("lsof")
+        .args(["-nP", &format!("-iTCP:{}", port), "-sTCP:LISTEN", "-t"])
+        .output();
+    
+    let mut pids = Vec::new();
+    if let Ok(output) = output {
+        for line in String::from_utf8_lossy(&output.stdout).lines() {
+            if let Ok(pid) = line.trim().parse::<u32>() {
+                if !pids.contains(&pid) {
+                    pids.push(pid);
+                }
+            }
+        }
+    }
+    pids
+}
+
+/// Find PIDs of processes listening on a specific port
+#[cfg(target_os = "windows")]
+pub fn find_listening_pids(port: u16) -> Vec<u32> {
+    use std::process::Command;
+    
+    let output = Command::new("netstat").args(["-ano"]).output();
+    
+    let mut pids = Vec::new();
+    if let Ok(output) = output {
+        let port_suffix = format!(":{}", port);
+        for line in String::from_utf8_lossy(&output.stdout).lines() {
+            // Format: "  TCP    0.0.0.0:8000    0.0.0.0:0    LISTENING    1234"
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            if parts.len() >= 5 && parts[1].ends_with(&port_suffix) && parts[3] == "LISTENING" {
+                if let Ok(pid) = parts[4].parse::<u32>() {
+                    if pid != 0 && !pids.contains(&pid) {
+                        pids.push(pid);
+                    }
+                }
+            }
+        }
+    }
+    pids
+}
+
+/// Get the executable name of a process
+pub fn get_process_name(pid: u32) -> String {
+    use std::process::Command;
+    
+    #[cfg(not(target_os = "windows"))]
+    let name = Command::new("ps")
+        .args(["-p", &pid.to_string(), "-o", "comm="])
+        .output()
+        .ok()
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string());
+    
+    // Format: "python.exe","1234","Console","1","25,000 K"
+    #[cfg(target_os = "windows")]
+    let name = Command::new("tasklist")
+        .args(["/FI", &format!("PID eq {}", pid), "/FO", "CSV", "/NH"])
+        .output()
+        .ok()
+        .and_then(|o| {
+            String::from_utf8_lossy(&o.stdout)
+                .split(',')
+                .next()
+                .map(|s| s.trim().trim_matches('"').to_string())
+        });
+    
+    name.filter(|n| !n.is_empty() && !n.starts_with("INFO:"))
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// Get the full command line of a process (empty if unavailable)
+fn get_process_command_line(pid: u32) -> String {
+    use std::process::Command;
+    
+    #[cfg(not(target_os = "windows"))]
+    let output = Command::new("ps")
+        .args(["-p", &pid.to_string(), "-o", "command="])
+        .output();
+    
+    #[cfg(target_os = "windows")]
+    let output = Command::new("powershell")
+        .args([
+            "-NoProfile",
+            "-Command",
+            &format!("(Get-CimInstance Win32_Process -Filter 'ProcessId={}').CommandLine", pid),
+        ])
+        .output();
+    
+    output
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+        .unwrap_or_default()
+}
+
+/// Check whether a process is a daemon spawned by this app (possibly a zombie from a previous run)
+/// These are safe to clean up automatically
+fn is_desktop_app_daemon(pid: u32) -> bool {
+    get_process_command_line(pid).contains(DESKTOP_APP_DAEMON_ARG)
+}
+
+/// Probe daemon ports and report those held by foreign processes
+/// Zombie daemons from this app are not reported (cleanup handles them)
+pub fn detect_port_conflicts() -> Vec<PortConflict> {
+    let mut conflicts = Vec::new();
+    for &port in DAEMON_PORTS {
+        for pid in find_listening_pids(port) {
+            if pid == std::process::id() || is_desktop_app_daemon(pid) {
+                continue;
+            }
+            conflicts.push(PortConflict {
+                port,
+                pid,
+                process_name: get_process_name(pid),
+            });
+        }
+    }
+    conflicts
+}
+
+/// Force kill a process by PID
+pub fn kill_process(pid: u32) -> Result<(), String> {
+    use std::process::Command;
+    
+    #[cfg(not(target_os = "windows"))]
+    let output = Command::new("kill").args(["-9", &pid.to_string()]).output();
+    
+    #[cfg(target_os = "windows")]
+    let output = Command::new("taskkill").args(["/PID", &pid.to_string(), "/F"]).output();
+    
+    let output = output.map_err(|e| format!("Failed to kill process {}: {}", pid, e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "Failed to kill process {}: {}",
+            pid,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(())
+}
+
+/// Kill every process listening on a daemon port (after user confirmation)
+pub fn kill_port_owners(port: u16) -> Result<usize, String> {
+    let mut killed = 0;
+    for pid in find_listening_pids(port) {
+        if pid != std::process::id() {
+            kill_process(pid)?;
+            killed += 1;
+        }
+    }
+    Ok(killed)
+}
+
 /// Clean up all daemon processes running on the system (via port 8000)
+/// Skipped when the daemon belongs to another running app instance
 pub fn cleanup_system_daemons() {
+    if let Some(owner) = instance_lock::foreign_owner() {
+        println!("[daemon] ⏭️  Daemon owned by another app instance (PID {}) - skipping cleanup", owner.app_pid);
+        return;
+    }
+    
     #[cfg(not(target_os = "windows"))]
     {
         use std::process::Command;
@@ -93,39 +325,65 @@ pub fn cleanup_system_daemons() {
         use std::process::Command;
 
         // Windows: Use netstat and taskkill to find and kill processes on port 8000
-        let output = Command::new("netstat").args(&["-ano"]).output();
-
-        if let Ok(output) = output {
-            let output_str = String::from_utf8_lossy(&output.stdout);
-            let mut pids = Vec::new();
-            for line in output_str.lines() {
-                if line.contains(":8000") && line.contains("LISTENING") {
-                    let parts: Vec<&str> = line.split_whitespace().collect();
-                    let pid = parts.last().unwrap().to_string();
-                    if pid != "0" && !pids.contains(&pid) {
-                        pids.push(pid);
-                    }
-                }
+        for pid in find_listening_pids(8000) {
+            println!("Killing process with PID: {}", pid);
+            let _ = Command::new("taskkill")
+                .args(&["/PID", &pid.to_string(), "/F"])
+                .output();
+        }
+    }
+    
+    instance_lock::release();
+}
+
+/// Send a signal to a whole process group; false if no process of the group is left
+#[cfg(not(target_os = "windows"))]
+fn signal_process_group(pgid: u32, signal: &str) -> bool {
+    std::process::Command::new("kill")
+        .args(["-s", signal, "--", &format!("-{}", pgid)])
+        .output()
+        .map(|output| output.status.success())
+        .unwrap_or(false)
+}
+
+/// Stop the sidecar and everything it launched (uvicorn workers, app subprocesses)
+///
+/// uv-trampoline leads its own process group (Unix) or Job Object (Windows), so
+/// grandchildren that re-parent are reached too.
+fn terminate_sidecar_tree(child: CommandChild) {
+    #[cfg(not(target_os = "windows"))]
+    {
+        let pgid = child.pid();
+        // SIGTERM first so the daemon can release motor torque
+        if signal_process_group(pgid, "TERM") {
+            let deadline = std::time::Instant::now() + SIDECAR_STOP_GRACE;
+            while std::time::Instant::now() < deadline && signal_process_group(pgid, "0") {
+                std::thread::sleep(std::time::Duration::from_millis(100));
             }
-            for pid_str in pids {
-                println!("Killing process with PID: {}", pid_str);
-                let _ = Command::new("taskkill")
-                    .args(&["/PID", &pid_str, "/F"])
-                    .output();
+            if signal_process_group(pgid, "0") {
+                println!("[daemon] ⚠️  Sidecar process group {} still alive, killing it", pgid);
+                signal_process_group(pgid, "KILL");
             }
         }
     }
+    
+    // Windows: killing the trampoline closes its Job Object, which kills the tree
+    let _ = child.kill();
 }
 
 /// Kill daemon completely (local sidecar process + system)
 pub fn kill_daemon(state: &State<DaemonState>) {
-    // Clear the stored process reference
-    // Note: CommandChild doesn't expose kill() method, so we rely on cleanup_system_daemons()
-    // which kills processes via port 8000 (more reliable)
+    // Take the stored process, then stop its whole process tree
     let mut process_lock = state.process.lock().unwrap();
-    process_lock.take();
+    let child = process_lock.take();
     drop(process_lock);
     
+    // Close the current run before killing so the termination isn't recorded as a crash
+    if let Some(child) = child {
+        state.history.lock().unwrap().record_stop();
+        terminate_sidecar_tree(child);
+    }
+    
     // Clean up system processes (kills via port 8000 and process name)
     cleanup_system_daemons();
 }
@@ -138,14 +396,17 @@ pub fn kill_daemon(state: &State<DaemonState>) {
 /// Avoids duplication while working around private Receiver type
 #[macro_export]
 macro_rules! spawn_sidecar_monitor {
-    ($rx:ident, $app_handle:ident, $prefix:expr) => {
+    ($rx:ident, $app_handle:ident, $prefix:expr, $pid:expr) => {
         {
             let prefix = $prefix;
+            let pid: u32 = $pid;
             let app_handle_clone = $app_handle.clone();
             tauri::async_runtime::spawn(async move {
-                use tauri::Emitter;
+                use tauri::{Emitter, Manager};
                 use tauri_plugin_shell::process::CommandEvent;
                 
+                let daemon_state = app_handle_clone.state::<$crate::daemon::DaemonState>();
+                
                 if let Some(ref p) = prefix {
                     println!("[tauri] Starting sidecar output monitoring ({})...", p);
                 } else {
@@ -156,12 +417,22 @@ macro_rules! spawn_sidecar_monitor {
                     match event {
                         CommandEvent::Stdout(line_bytes) => {
                             let line = String::from_utf8_lossy(&line_bytes);
+                            // Structured setup/status from uv-trampoline: forward instead of logging
+                            if let Some(event) = $crate::daemon::events::parse_trampoline_event(&line) {
+                                $crate::profiling::on_trampoline_event(&event);
+                                let _ = app_handle_clone.emit("uv-trampoline-event", event);
+                                continue;
+                            }
                             let prefixed_line = prefix
                                 .as_ref()
                                 .map(|p| format!("[{}] {}", p, line))
                                 .unwrap_or_else(|| line.to_string());
                             println!("Sidecar stdout: {}", prefixed_line);
+                            $crate::daemon::add_sidecar_log(&daemon_state, prefixed_line.clone());
                             let _ = app_handle_clone.emit("sidecar-stdout", prefixed_line.clone());
+                            if let Some(event) = $crate::daemon::events::parse_log_line(&line) {
+                                let _ = app_handle_clone.emit(event.name(), event);
+                            }
                         }
                         CommandEvent::Stderr(line_bytes) => {
                             let line = String::from_utf8_lossy(&line_bytes);
@@ -170,13 +441,46 @@ macro_rules! spawn_sidecar_monitor {
                                 .map(|p| format!("[{}] {}", p, line))
                                 .unwrap_or_else(|| line.to_string());
                             eprintln!("Sidecar stderr: {}", prefixed_line);
+                            $crate::daemon::add_sidecar_log(&daemon_state, prefixed_line.clone());
                             let _ = app_handle_clone.emit("sidecar-stderr", prefixed_line.clone());
+                            // Python logging writes to stderr, so known patterns show up here too
+                            if let Some(event) = $crate::daemon::events::parse_log_line(&line) {
+                                let _ = app_handle_clone.emit(event.name(), event);
+                            }
                         }
                         CommandEvent::Terminated(status) => {
                             if let Some(ref p) = prefix {
                                 println!("[tauri] [{}] Process terminated with status: {:?}", p, status);
                             } else {
                                 println!("[tauri] Sidecar process terminated with status: {:?}", status);
+                                // A child spawned since (stop then start) owns the open run: a late
+                                // termination of the old one must not close it as a crash
+                                let superseded = {
+                                    let mut process = daemon_state.process.lock().unwrap();
+                                    match process.as_ref().map(|child| child.pid()) {
+                                        Some(current) if current != pid => true,
+                                        // Forget the dead child so a restart (e.g. autostart on
+                                        // reconnect) isn't skipped
+                                        Some(_) => {
+                                            *process = None;
+                                            false
+                                        }
+                                        None => false,
+                                    }
+                                };
+                                if superseded {
+                                    continue;
+                                }
+                                
+                                // Record the run end (no-op if the app stopped the daemon itself)
+                                let crash_reason = $crate::daemon::history::extract_crash_reason(
+                                    daemon_state.sidecar_logs.lock().unwrap().iter()
+                                );
+                                let crashed = daemon_state.history.lock().unwrap().record_termination(status.code, crash_reason);
+                                if crashed {
+                                    $crate::analytics::record(&app_handle_clone, "daemon_crash", serde_json::json!({ "exit_code": status.code }));
+                                }
+                                
                                 // ✅ Emit event to frontend so it can detect the crash
                                 let status_str = format!("{:?}", status);
                                 let _ = app_handle_clone.emit("sidecar-terminated", status_str);
@@ -196,12 +500,15 @@ macro_rules! spawn_sidecar_monitor {
 /// * `app_handle` - Tauri app handle
 /// * `state` - Daemon state
 /// * `sim_mode` - If true, launch daemon in simulation mode (mockup-sim) with --mockup-sim flag
+/// * `serial_port` - Robot serial port to use (None: let the daemon auto-detect)
 pub fn spawn_and_monitor_sidecar(
     app_handle: tauri::AppHandle,
     state: &State<DaemonState>,
     sim_mode: bool,
+    serial_port: Option<&str>,
 ) -> Result<(), String> {
     use crate::python::build_daemon_args;
+    use tauri::Manager;
     use tauri_plugin_shell::ShellExt;
     
     // Check if a sidecar process already exists
@@ -211,35 +518,49 @@ pub fn spawn_and_monitor_sidecar(
         return Ok(());
     }
     drop(process_lock);
+    crate::profiling::begin("daemon_spawn");
     
     // Build daemon arguments dynamically
-    let daemon_args = build_daemon_args(sim_mode)?;
+    let settings = app_handle.state::<crate::settings::SettingsState>().get();
+    let config_args = config::to_args(&config::load(&app_handle));
+    let daemon_args = build_daemon_args(sim_mode, serial_port, settings.daemon_log_verbosity.as_daemon_arg(), &config_args)?;
     
     // Note: libpython3.12.dylib signing is now handled by uv-trampoline
     // which runs in the correct working directory context
     
     if sim_mode {
         println!("[tauri] 🎭 Launching daemon in simulation mode (mockup-sim)");
+    } else if let Some(port) = serial_port {
+        println!("[tauri] 🔌 Launching daemon on serial port {}", port);
     }
     
     // Convert Vec<String> to Vec<&str> for args()
     let daemon_args_refs: Vec<&str> = daemon_args.iter().map(|s| s.as_str()).collect();
     
+    // --json: uv-trampoline reports venv setup progress as event lines
     let sidecar_command = app_handle
         .shell()
         .sidecar("uv-trampoline")
         .map_err(|e| e.to_string())?
-        .args(daemon_args_refs);
+        .arg("--json")
+        .args(daemon_args_refs)
+        .envs(crate::audio::daemon_env(&settings))
+        .envs(crate::assets::daemon_env(&app_handle));
     
     let (mut rx, child) = sidecar_command.spawn().map_err(|e| e.to_string())?;
+    let pid = child.pid();
 
     // Store the child process in DaemonState
     let mut process_lock = state.process.lock().unwrap();
     *process_lock = Some(child);
     drop(process_lock);
+    
+    state.history.lock().unwrap().record_start(sim_mode);
+    crate::analytics::record(&app_handle, "daemon_start", serde_json::json!({ "mode": if sim_mode { "simulation" } else { "usb" } }));
+    instance_lock::acquire();
 
     // Spawn async task to monitor sidecar output
-    crate::spawn_sidecar_monitor!(rx, app_handle, None::<String>);
+    crate::spawn_sidecar_monitor!(rx, app_handle, None::<String>, pid);
 
     Ok(())
 }
@@ -0,0 +1,100 @@
+/// Sim/hardware daemon mode hot-switch
+///
+/// Stops the running daemon, waits for its ports to be released (no zombie on 8000),
+/// restarts it in the other mode and relaunches the app that was running before.
+///
+/// Emits `daemon-mode-switch` with { step, message, sim_mode } at each step.
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+
+use super::DaemonState;
+use crate::daemon_api::DaemonClient;
+
+const PORT_RELEASE_TIMEOUT_MS: u64 = 10_000;
+const HEALTHY_TIMEOUT_MS: u64 = 60_000;
+const POLL_INTERVAL_MS: u64 = 500;
+
+#[derive(Debug, Serialize, Clone)]
+struct ModeSwitchProgress {
+    step: &'static str,
+    message: String,
+    sim_mode: bool,
+}
+
+fn emit_progress(app_handle: &AppHandle, step: &'static str, message: String, sim_mode: bool) {
+    println!("[mode-switch] {}", message);
+    let _ = app_handle.emit("daemon-mode-switch", ModeSwitchProgress { step, message, sim_mode });
+}
+
+/// Wait until nothing listens on port 8000 anymore, force killing leftovers on timeout
+pub async fn wait_for_port_release() {
+    let deadline = std::time::Instant::now() + std::time::Duration::from_millis(PORT_RELEASE_TIMEOUT_MS);
+    while std::time::Instant::now() < deadline {
+        if super::find_listening_pids(8000).is_empty() {
+            return;
+        }
+        tokio::time::sleep(tokio::time::Duration::from_millis(POLL_INTERVAL_MS)).await;
+    }
+    eprintln!("[mode-switch] ⚠️ Port 8000 still in use - force killing leftover process");
+    let _ = super::kill_port_owners(8000);
+}
+
+/// Switch the local daemon between simulation and hardware mode
+#[tauri::command]
+pub async fn switch_daemon_mode(app_handle: AppHandle, sim: bool) -> Result<String, String> {
+    let current_mode = app_handle.state::<DaemonState>().history.lock().unwrap().current_mode();
+    if current_mode == Some(sim) {
+        return Ok("Daemon already running in requested mode".to_string());
+    }
+
+    let mode_label = if sim { "simulation" } else { "hardware" };
+    let client = DaemonClient::new();
+
+    // 1. Remember the running app so we can relaunch it
+    let running_app = client.running_app().await;
+    emit_progress(&app_handle, "saving_session", match &running_app {
+        Some(app) => format!("Saving session (running app: {})", app),
+        None => "Saving session (no app running)".to_string(),
+    }, sim);
+
+    // 2. Stop the current daemon
+    emit_progress(&app_handle, "stopping", "Stopping current daemon...".to_string(), sim);
+    let handle = app_handle.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        super::kill_daemon(&handle.state::<DaemonState>());
+    })
+    .await
+    .map_err(|e| format!("Failed to stop daemon: {}", e))?;
+
+    // 3. Make sure the old daemon released its port
+    emit_progress(&app_handle, "waiting_port", "Waiting for port 8000 to be released...".to_string(), sim);
+    wait_for_port_release().await;
+
+    // 4. Start in the other mode
+    emit_progress(&app_handle, "starting", format!("Starting daemon in {} mode...", mode_label), sim);
+    let handle = app_handle.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::start_daemon(handle.clone(), handle.state::<DaemonState>(), Some(sim), None, None)
+    })
+    .await
+    .map_err(|e| format!("Failed to start daemon: {}", e))??;
+
+    emit_progress(&app_handle, "waiting_healthy", "Waiting for daemon to be ready...".to_string(), sim);
+    if !client.wait_healthy(std::time::Duration::from_millis(HEALTHY_TIMEOUT_MS)).await {
+        let message = format!("Daemon did not become ready in {} mode", mode_label);
+        emit_progress(&app_handle, "error", message.clone(), sim);
+        return Err(message);
+    }
+
+    // 5. Relaunch the app that was running before the switch
+    if let Some(app) = running_app {
+        emit_progress(&app_handle, "restoring_session", format!("Restarting app {}...", app), sim);
+        if let Err(e) = client.start_app(&app).await {
+            eprintln!("[mode-switch] ⚠️ Failed to restart app {}: {}", app, e);
+        }
+    }
+
+    emit_progress(&app_handle, "done", format!("Daemon running in {} mode", mode_label), sim);
+    Ok(format!("Daemon switched to {} mode", mode_label))
+}
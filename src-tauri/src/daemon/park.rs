@@ -0,0 +1,114 @@
+/// Park sequence on quit
+///
+/// Killing the daemon cuts motor torque wherever the head is, so it drops. Before
+/// the main window closes or the app exits, a USB robot is parked instead (when the
+/// `park_on_exit` setting is on): running app stopped, go-to-sleep move played,
+/// motors set compliant, mode confirmed. Bounded by `PARK_TIMEOUT`, then the usual
+/// cleanup runs whatever the outcome.
+///
+/// Not done in simulation or WiFi mode (the remote robot keeps running without this app).
+/// Emits `robot-park` with { status: "started" | "done" | "failed", error }.
+
+use serde::Serialize;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+use super::DaemonState;
+use crate::daemon_api::{DaemonClient, MotorMode};
+use crate::local_proxy::LocalProxyState;
+use crate::settings::SettingsState;
+
+const PARK_TIMEOUT: Duration = Duration::from_secs(8);
+const MOVE_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+const IDLE: u8 = 0;
+const IN_PROGRESS: u8 = 1;
+const DONE: u8 = 2;
+
+static PARK_STATE: AtomicU8 = AtomicU8::new(IDLE);
+
+#[derive(Debug, Serialize, Clone)]
+struct ParkProgress {
+    status: &'static str,
+    error: Option<String>,
+}
+
+/// The robot is already at rest (e.g. powered down from the tray): skip the sequence
+pub fn mark_done() {
+    PARK_STATE.store(DONE, Ordering::SeqCst);
+}
+
+/// Stop the running app, play the sleep move and set the motors compliant
+pub(crate) async fn park(client: &DaemonClient) -> Result<(), String> {
+    if client.running_app().await.is_some() {
+        client.stop_current_app().await?;
+    }
+
+    client.goto_sleep().await?;
+    // The move is registered asynchronously: give it a moment before polling
+    tokio::time::sleep(MOVE_POLL_INTERVAL).await;
+    while !client.running_moves().await?.is_empty() {
+        tokio::time::sleep(MOVE_POLL_INTERVAL).await;
+    }
+
+    client.set_motor_mode(MotorMode::Disabled).await?;
+    let mode = client.motors_status().await?.mode;
+    if mode.as_deref() != Some("disabled") {
+        return Err(format!("Motors still in mode {:?}", mode));
+    }
+    Ok(())
+}
+
+/// A local hardware daemon is running and parking is enabled
+async fn should_park(app_handle: &AppHandle) -> bool {
+    if !app_handle.state::<SettingsState>().get().park_on_exit {
+        return false;
+    }
+    let daemon_state = app_handle.state::<DaemonState>();
+    let hardware_daemon = daemon_state.process.lock().unwrap().is_some()
+        && daemon_state.history.lock().unwrap().current_mode() == Some(false);
+    let wifi_mode = app_handle
+        .state::<std::sync::Arc<LocalProxyState>>()
+        .target_host
+        .read()
+        .await
+        .is_some();
+    hardware_daemon && !wifi_mode
+}
+
+/// Park the robot before quitting, then call `then` (which closes / exits again)
+///
+/// Returns true when the close / exit request must be prevented (the sequence runs
+/// first, or is still running), false once it has run.
+pub fn park_before_exit(app_handle: &AppHandle, then: impl FnOnce(&AppHandle) + Send + 'static) -> bool {
+    match PARK_STATE.compare_exchange(IDLE, IN_PROGRESS, Ordering::SeqCst, Ordering::SeqCst) {
+        Ok(_) => {}
+        Err(state) => return state == IN_PROGRESS,
+    }
+
+    let app_handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        let handle = app_handle.clone();
+        let _ = tauri::async_runtime::spawn_blocking(move || crate::apps::sandbox::stop(&handle)).await;
+        if should_park(&app_handle).await {
+            println!("[park] 💤 Parking robot before quitting");
+            let _ = app_handle.emit("robot-park", ParkProgress { status: "started", error: None });
+            let client = DaemonClient::new();
+            let error = match tokio::time::timeout(PARK_TIMEOUT, park(&client)).await {
+                Ok(Ok(())) => None,
+                Ok(Err(e)) => Some(e),
+                Err(_) => Some(format!("Park sequence timed out after {:?}", PARK_TIMEOUT)),
+            };
+            match &error {
+                Some(e) => eprintln!("[park] ⚠️ {}", e),
+                None => println!("[park] ✅ Robot parked"),
+            }
+            let status = if error.is_some() { "failed" } else { "done" };
+            let _ = app_handle.emit("robot-park", ParkProgress { status, error });
+        }
+        mark_done();
+        then(&app_handle);
+    });
+    true
+}
@@ -0,0 +1,580 @@
+/// Daemon REST client
+///
+/// Typed access to the daemon HTTP API (status, robot state, motors, apps) so the
+/// backend can orchestrate flows itself (update → wait healthy → restart app) instead
+/// of going through the webview fetch path.
+///
+/// Always talks to localhost:8000: the local daemon, or the remote robot through the
+/// WiFi-mode local proxy (the proxy session token is sent along).
+/// Response types only name the fields the app uses; the rest is kept in `extra`.
+
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tauri::State;
+
+use crate::local_proxy::{auth::LOCAL_TOKEN_HEADER, LocalProxyState};
+
+pub const DAEMON_URL: &str = "http://localhost:8000";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+const HEALTH_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+/// GET /api/daemon/status
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DaemonStatus {
+    #[serde(default)]
+    pub robot_name: Option<String>,
+    /// "starting", "running", "stopping", "stopped", "error"
+    #[serde(default)]
+    pub state: Option<String>,
+    #[serde(default)]
+    pub version: Option<String>,
+    #[serde(default)]
+    pub simulation_enabled: Option<bool>,
+    #[serde(default)]
+    pub backend_status: Option<serde_json::Value>,
+    #[serde(default)]
+    pub error: Option<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// GET /api/state/full
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RobotState {
+    #[serde(default)]
+    pub control_mode: Option<String>,
+    #[serde(default)]
+    pub head_joints: Option<Vec<f64>>,
+    #[serde(default)]
+    pub body_yaw: Option<f64>,
+    #[serde(default)]
+    pub antennas_position: Option<Vec<f64>>,
+    #[serde(default)]
+    pub head_pose: Option<HeadPose>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Motor control mode (POST /api/motors/set_mode/{mode})
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum MotorMode {
+    Enabled,
+    /// Compliant: motors can be moved by hand
+    Disabled,
+    GravityCompensation,
+}
+
+impl MotorMode {
+    fn as_path(self) -> &'static str {
+        match self {
+            Self::Enabled => "enabled",
+            Self::Disabled => "disabled",
+            Self::GravityCompensation => "gravity_compensation",
+        }
+    }
+}
+
+/// Head pose target: position in meters, rotations in radians
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+pub struct HeadPose {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub pitch: f64,
+    pub yaw: f64,
+    pub roll: f64,
+}
+
+impl HeadPose {
+    /// Within the ranges the daemon accepts (±5 cm, pitch ±0.8, yaw ±1.2, roll ±0.5 rad)
+    pub fn clamped(self) -> Self {
+        Self {
+            x: self.x.clamp(-0.05, 0.05),
+            y: self.y.clamp(-0.05, 0.05),
+            z: self.z.clamp(-0.05, 0.05),
+            pitch: self.pitch.clamp(-0.8, 0.8),
+            yaw: self.yaw.clamp(-1.2, 1.2),
+            roll: self.roll.clamp(-0.5, 0.5),
+        }
+    }
+}
+
+/// Body yaw range accepted by set_target (±160°)
+pub const BODY_YAW_LIMIT: f64 = 160.0 * std::f64::consts::PI / 180.0;
+
+/// POST /api/move/set_target (reached right away, no interpolation)
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct MoveTarget {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_head_pose: Option<HeadPose>,
+    /// [left, right] in radians
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_antennas: Option<[f64; 2]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_body_yaw: Option<f64>,
+}
+
+/// POST /api/move/goto: interpolated move to the targets over `duration` seconds
+#[derive(Debug, Serialize, Clone)]
+pub struct GotoRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub head_pose: Option<HeadPose>,
+    /// [left, right] in radians
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub antennas: Option<[f64; 2]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body_yaw: Option<f64>,
+    pub duration: f64,
+    /// "linear", "minjerk", "ease" or "cartoon"
+    pub interpolation: String,
+}
+
+/// GET /api/motors/status
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MotorsStatus {
+    #[serde(default)]
+    pub mode: Option<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// An installed app (GET /api/apps/list-available/installed)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AppInfo {
+    pub name: String,
+    #[serde(default)]
+    pub source_kind: Option<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// GET /api/apps/current-app-status (null when no app runs)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AppStatus {
+    pub info: AppInfo,
+    /// "starting", "running", "done", "stopping", "error"
+    pub state: String,
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+impl AppStatus {
+    pub fn is_active(&self) -> bool {
+        matches!(self.state.as_str(), "starting" | "running")
+    }
+}
+
+/// GET /api/apps/job-status/{job_id} (install / remove jobs), GET /api/update/info (self-update)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct JobStatus {
+    /// "pending", "in_progress", "completed", "failed"
+    #[serde(default)]
+    pub status: Option<String>,
+    #[serde(default)]
+    pub logs: Vec<String>,
+}
+
+impl JobStatus {
+    /// Finished, and whether it succeeded (older daemons only report completion in the logs)
+    pub fn outcome(&self) -> Option<bool> {
+        match self.status.as_deref() {
+            Some("completed") => Some(true),
+            Some("failed") => Some(false),
+            _ => {
+                let logs = self.logs.join("\n").to_lowercase();
+                (logs.contains("completed successfully") || logs.contains("' completed")).then_some(true)
+            }
+        }
+    }
+}
+
+/// Text-to-speech voice (GET /api/audio/voices)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Voice {
+    pub id: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    /// e.g. "en-US"
+    #[serde(default)]
+    pub language: Option<String>,
+}
+
+/// Response of POST /api/audio/say
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct SpeechResponse {
+    #[serde(default)]
+    pub uuid: Option<String>,
+    /// Seconds of audio, when the daemon knows it
+    #[serde(default)]
+    pub duration: Option<f64>,
+}
+
+/// Physics parameters of the simulation backend (GET / POST /api/simulation/parameters);
+/// None leaves a parameter unchanged
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct SimParameters {
+    /// 1.0 = real time, below slows the simulation down
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub realtime_factor: Option<f64>,
+    /// m/s², downwards
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gravity: Option<f64>,
+    /// Physics step, seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestep: Option<f64>,
+}
+
+/// Robot hostname and display name (GET / POST /api/system/hostname)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RobotHostname {
+    /// e.g. "reachy-mini-3" (announced as reachy-mini-3.local)
+    pub hostname: String,
+    #[serde(default)]
+    pub display_name: Option<String>,
+}
+
+/// Response of the install / remove endpoints: `{ "job_id": ... }`, or `{ "<job_id>": ... }`
+/// on older daemons
+fn job_id(response: serde_json::Value) -> Result<String, String> {
+    let object = response.as_object().ok_or("Invalid job response")?;
+    object
+        .get("job_id")
+        .and_then(|id| id.as_str())
+        .map(String::from)
+        .or_else(|| object.keys().next().cloned())
+        .ok_or_else(|| "No job_id returned by the daemon".to_string())
+}
+
+/// Percent-encode a path segment: app names come from the daemon's catalog or the
+/// caller, and a `/`, `?` or `#` in one would reach another route
+fn path_segment(value: &str) -> Result<String, String> {
+    // Dot segments are resolved by the URL parser even when encoded
+    if matches!(value, "" | "." | "..") {
+        return Err(format!("Invalid path segment: {:?}", value));
+    }
+    Ok(value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect())
+}
+
+// ============================================================================
+// CLIENT
+// ============================================================================
+
+#[derive(Clone)]
+pub struct DaemonClient {
+    http: reqwest::Client,
+    base_url: String,
+    proxy_token: Option<String>,
+    timeout: Duration,
+}
+
+/// Id of a move started by the daemon ({ "uuid": ... })
+fn move_uuid(response: &serde_json::Value) -> Result<String, String> {
+    response
+        .get("uuid")
+        .and_then(|uuid| uuid.as_str())
+        .map(|uuid| uuid.to_string())
+        .ok_or_else(|| "Daemon did not return a move id".to_string())
+}
+
+impl DaemonClient {
+    pub fn new() -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            base_url: DAEMON_URL.to_string(),
+            proxy_token: None,
+            timeout: REQUEST_TIMEOUT,
+        }
+    }
+
+    /// Same client with another per-request timeout
+    pub fn with_timeout(self, timeout: Duration) -> Self {
+        Self { timeout, ..self }
+    }
+
+    /// Client that also passes the local proxy when it requires its session token
+    pub async fn for_proxy(proxy: &LocalProxyState) -> Self {
+        let auth = proxy.auth.read().await;
+        Self {
+            proxy_token: auth.require_local_token.then(|| auth.local_token.clone()),
+            ..Self::new()
+        }
+    }
+
+    fn request(&self, method: reqwest::Method, path: &str) -> reqwest::RequestBuilder {
+        let request = self
+            .http
+            .request(method, format!("{}{}", self.base_url, path))
+            .timeout(self.timeout);
+        match &self.proxy_token {
+            Some(token) => request.header(LOCAL_TOKEN_HEADER, token),
+            None => request,
+        }
+    }
+
+    async fn send(&self, request: reqwest::RequestBuilder, path: &str) -> Result<reqwest::Response, String> {
+        let response = request
+            .send()
+            .await
+            .map_err(|e| format!("Daemon request {} failed: {}", path, e))?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(format!("Daemon returned {} for {}: {}", status, path, body.trim()));
+        }
+        Ok(response)
+    }
+
+    async fn get<T: serde::de::DeserializeOwned>(&self, path: &str) -> Result<T, String> {
+        self.send(self.request(reqwest::Method::GET, path), path)
+            .await?
+            .json()
+            .await
+            .map_err(|e| format!("Invalid daemon response for {}: {}", path, e))
+    }
+
+    async fn post(&self, path: &str) -> Result<(), String> {
+        self.send(self.request(reqwest::Method::POST, path), path).await?;
+        Ok(())
+    }
+
+    async fn post_json<T: serde::de::DeserializeOwned>(
+        &self,
+        path: &str,
+        body: Option<&serde_json::Value>,
+    ) -> Result<T, String> {
+        let mut request = self.request(reqwest::Method::POST, path);
+        if let Some(body) = body {
+            request = request.json(body);
+        }
+        self.send(request, path)
+            .await?
+            .json()
+            .await
+            .map_err(|e| format!("Invalid daemon response for {}: {}", path, e))
+    }
+
+    pub async fn daemon_status(&self) -> Result<DaemonStatus, String> {
+        self.get("/api/daemon/status").await
+    }
+
+    /// The daemon answers its status endpoint
+    pub async fn is_healthy(&self) -> bool {
+        self.send(self.request(reqwest::Method::GET, "/api/daemon/status"), "/api/daemon/status")
+            .await
+            .is_ok()
+    }
+
+    /// Poll the status endpoint until the daemon answers (false on timeout)
+    pub async fn wait_healthy(&self, timeout: Duration) -> bool {
+        let deadline = Instant::now() + timeout;
+        while Instant::now() < deadline {
+            if self.is_healthy().await {
+                return true;
+            }
+            tokio::time::sleep(HEALTH_POLL_INTERVAL).await;
+        }
+        false
+    }
+
+    /// Stop the robot backend, first moving the head to its rest pose when `goto_sleep`
+    pub async fn stop_daemon(&self, goto_sleep: bool) -> Result<(), String> {
+        self.post(&format!("/api/daemon/stop?goto_sleep={}", goto_sleep)).await
+    }
+
+    pub async fn robot_state(&self) -> Result<RobotState, String> {
+        self.get("/api/state/full?with_control_mode=true&with_head_joints=true&with_body_yaw=true&with_antenna_positions=true&with_head_pose=true&use_pose_matrix=false")
+            .await
+    }
+
+    pub async fn motors_status(&self) -> Result<MotorsStatus, String> {
+        self.get("/api/motors/status").await
+    }
+
+    pub async fn set_motor_mode(&self, mode: MotorMode) -> Result<(), String> {
+        self.post(&format!("/api/motors/set_mode/{}", mode.as_path())).await
+    }
+
+    /// Play the go-to-sleep move (head lowered onto its rest position)
+    pub async fn goto_sleep(&self) -> Result<(), String> {
+        self.post_json::<serde_json::Value>("/api/move/play/goto_sleep", None).await?;
+        Ok(())
+    }
+
+    /// Set head / antennas / body targets directly (streaming control)
+    pub async fn set_target(&self, target: &MoveTarget) -> Result<(), String> {
+        let path = "/api/move/set_target";
+        self.send(self.request(reqwest::Method::POST, path).json(target), path).await?;
+        Ok(())
+    }
+
+    /// Start an interpolated move, returns its uuid (see running_moves)
+    pub async fn goto(&self, request: &GotoRequest) -> Result<String, String> {
+        let body = serde_json::to_value(request).map_err(|e| format!("Failed to serialize move: {}", e))?;
+        let response: serde_json::Value = self.post_json("/api/move/goto", Some(&body)).await?;
+        move_uuid(&response)
+    }
+
+    /// Play a move of a recorded dataset (e.g. "pollen-robotics/reachy-mini-emotions-library"),
+    /// returns its uuid
+    pub async fn play_recorded_move(&self, dataset: &str, name: &str) -> Result<String, String> {
+        let path = format!("/api/move/play/recorded-move-dataset/{}/{}", dataset, name);
+        let response: serde_json::Value = self.post_json(&path, None).await?;
+        move_uuid(&response)
+    }
+
+    /// Moves currently playing
+    pub async fn running_moves(&self) -> Result<Vec<serde_json::Value>, String> {
+        self.get("/api/move/running").await
+    }
+
+    pub async fn installed_apps(&self) -> Result<Vec<AppInfo>, String> {
+        self.get("/api/apps/list-available/installed").await
+    }
+
+    pub async fn current_app_status(&self) -> Result<Option<AppStatus>, String> {
+        self.get("/api/apps/current-app-status").await
+    }
+
+    /// Name of the app starting or running, if any
+    pub async fn running_app(&self) -> Option<String> {
+        self.current_app_status()
+            .await
+            .ok()
+            .flatten()
+            .filter(AppStatus::is_active)
+            .map(|status| status.info.name)
+    }
+
+    pub async fn start_app(&self, name: &str) -> Result<(), String> {
+        self.post(&format!("/api/apps/start-app/{}", path_segment(name)?)).await
+    }
+
+    pub async fn stop_current_app(&self) -> Result<(), String> {
+        self.post("/api/apps/stop-current-app").await
+    }
+
+    /// Start installing an app from the daemon catalog (AppInfo as listed by the daemon), returns the job id
+    pub async fn install_app(&self, info: &serde_json::Value) -> Result<String, String> {
+        job_id(self.post_json("/api/apps/install", Some(info)).await?)
+    }
+
+    /// Start removing an installed app, returns the job id
+    pub async fn remove_app(&self, name: &str) -> Result<String, String> {
+        job_id(self.post_json(&format!("/api/apps/remove/{}", path_segment(name)?), None).await?)
+    }
+
+    pub async fn job_status(&self, job_id: &str) -> Result<JobStatus, String> {
+        self.get(&format!("/api/apps/job-status/{}", path_segment(job_id)?)).await
+    }
+
+    /// Speak a text through the robot's speaker (voice None = daemon default)
+    pub async fn say(&self, text: &str, voice: Option<&str>) -> Result<SpeechResponse, String> {
+        let body = serde_json::json!({ "text": text, "voice": voice });
+        let response: serde_json::Value = self.post_json("/api/audio/say", Some(&body)).await?;
+        Ok(serde_json::from_value(response).unwrap_or_default())
+    }
+
+    pub async fn voices(&self) -> Result<Vec<Voice>, String> {
+        self.get("/api/audio/voices").await
+    }
+
+    pub async fn sim_parameters(&self) -> Result<SimParameters, String> {
+        self.get("/api/simulation/parameters").await
+    }
+
+    /// Apply physics parameters, returns the resulting ones
+    pub async fn set_sim_parameters(&self, parameters: &SimParameters) -> Result<SimParameters, String> {
+        let body = serde_json::to_value(parameters)
+            .map_err(|e| format!("Failed to serialize simulation parameters: {}", e))?;
+        self.post_json("/api/simulation/parameters", Some(&body)).await
+    }
+
+    pub async fn robot_hostname(&self) -> Result<RobotHostname, String> {
+        self.get("/api/system/hostname").await
+    }
+
+    /// Rename the robot, returns the name now in use
+    pub async fn set_robot_hostname(&self, hostname: &RobotHostname) -> Result<RobotHostname, String> {
+        let body = serde_json::to_value(hostname).map_err(|e| format!("Failed to serialize hostname: {}", e))?;
+        self.post_json("/api/system/hostname", Some(&body)).await
+    }
+
+    /// Re-announce the robot over mDNS under its current hostname
+    pub async fn refresh_mdns(&self) -> Result<(), String> {
+        self.post("/api/system/mdns/refresh").await
+    }
+
+    /// Start the robot daemon's self-update to `version` (pip index of the app's update
+    /// channel), returns the job id
+    pub async fn start_self_update(&self, version: &str, index_url: &str, pre_release: bool) -> Result<String, String> {
+        let body = serde_json::json!({ "version": version, "index_url": index_url, "pre_release": pre_release });
+        job_id(self.post_json("/api/update/start", Some(&body)).await?)
+    }
+
+    pub async fn self_update_status(&self, job_id: &str) -> Result<JobStatus, String> {
+        self.get(&format!("/api/update/info?job_id={}", job_id)).await
+    }
+
+    /// OpenAPI schema of the daemon (FastAPI serves it at /openapi.json)
+    pub async fn openapi_spec(&self) -> Result<serde_json::Value, String> {
+        self.get("/openapi.json").await
+    }
+}
+
+impl Default for DaemonClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+#[tauri::command]
+pub async fn get_daemon_status(proxy: State<'_, Arc<LocalProxyState>>) -> Result<DaemonStatus, String> {
+    DaemonClient::for_proxy(&proxy).await.daemon_status().await
+}
+
+#[tauri::command]
+pub async fn get_robot_state(proxy: State<'_, Arc<LocalProxyState>>) -> Result<RobotState, String> {
+    DaemonClient::for_proxy(&proxy).await.robot_state().await
+}
+
+#[tauri::command]
+pub async fn get_motors_status(proxy: State<'_, Arc<LocalProxyState>>) -> Result<MotorsStatus, String> {
+    DaemonClient::for_proxy(&proxy).await.motors_status().await
+}
+
+#[tauri::command]
+pub async fn set_motor_mode(proxy: State<'_, Arc<LocalProxyState>>, mode: MotorMode) -> Result<(), String> {
+    DaemonClient::for_proxy(&proxy).await.set_motor_mode(mode).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encodes_app_names_as_a_single_path_segment() {
+        assert_eq!(path_segment("reachy_mini_conversation-app.v2").unwrap(), "reachy_mini_conversation-app.v2");
+        assert_eq!(path_segment("../daemon/stop").unwrap(), "..%2Fdaemon%2Fstop");
+        assert_eq!(path_segment("app?goto_sleep=true#x").unwrap(), "app%3Fgoto_sleep%3Dtrue%23x");
+        assert_eq!(path_segment("café app").unwrap(), "caf%C3%A9%20app");
+        assert!(path_segment("..").is_err());
+        assert!(path_segment(".").is_err());
+        assert!(path_segment("").is_err());
+    }
+}
@@ -0,0 +1,213 @@
+/// Deep-link module
+///
+/// Handles `reachy-mini://` links so the community hub can open the app on an action:
+/// - `reachy-mini://install-app?repo=<owner>/<space>`: install a Hugging Face Space app
+/// - `reachy-mini://connect?host=<hostname or IP>`: connect to a robot over WiFi
+///
+/// A web page can open these links without the user noticing, so nothing runs on
+/// arrival: the action is validated, the window brought to front and the request
+/// emitted as `deep-link-request` with { id, action }. The frontend asks for
+/// confirmation, then calls `confirm_deep_link` (or `dismiss_deep_link`).
+/// Legacy `reachymini://install/<app>` links are still handled by the frontend.
+
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter, Manager, State, Url};
+use tauri_plugin_deep_link::DeepLinkExt;
+
+use crate::apps::AppSource;
+use crate::local_proxy::LocalProxyState;
+
+pub const SCHEME: &str = "reachy-mini";
+const HF_SPACES_URL: &str = "https://huggingface.co/spaces";
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum DeepLinkAction {
+    /// Hugging Face Space id, e.g. "pollen-robotics/hello_world"
+    InstallApp { repo: String },
+    Connect { host: String },
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct DeepLinkRequest {
+    pub id: u64,
+    #[serde(flatten)]
+    pub action: DeepLinkAction,
+}
+
+/// Last link received, waiting for the user's confirmation
+#[derive(Default)]
+pub struct DeepLinkState {
+    pending: Mutex<Option<DeepLinkRequest>>,
+    next_id: Mutex<u64>,
+}
+
+// ============================================================================
+// HELPER FUNCTIONS
+// ============================================================================
+
+/// `owner/name`, letters, digits, `-`, `_` and `.` only
+fn is_valid_repo(repo: &str) -> bool {
+    let parts: Vec<&str> = repo.split('/').collect();
+    parts.len() == 2
+        && parts.iter().all(|part| {
+            !part.is_empty()
+                && !part.starts_with('.')
+                && part.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        })
+}
+
+/// Hostname or IP address, optionally with a port
+fn is_valid_host(host: &str) -> bool {
+    Url::parse(&format!("http://{}", host))
+        .ok()
+        .and_then(|url| {
+            let parsed = match url.port() {
+                Some(port) => format!("{}:{}", url.host_str()?, port),
+                None => url.host_str()?.to_string(),
+            };
+            Some(parsed.eq_ignore_ascii_case(host) && url.path() == "/")
+        })
+        .unwrap_or(false)
+}
+
+/// Parse a `reachy-mini://` link into the action it requests
+pub fn parse(url: &Url) -> Result<DeepLinkAction, String> {
+    if url.scheme() != SCHEME {
+        return Err(format!("Unsupported scheme: {}", url.scheme()));
+    }
+    let param = |name: &str| {
+        url.query_pairs()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+            .ok_or_else(|| format!("Missing '{}' in {}", name, url))
+    };
+
+    // `reachy-mini://connect?...` has the action as host, `reachy-mini:connect?...` as path
+    let action = url.host_str().unwrap_or_else(|| url.path().trim_matches('/'));
+    match action {
+        "install-app" => {
+            let repo = param("repo")?;
+            let repo = repo
+                .strip_prefix(HF_SPACES_URL)
+                .map(|id| id.trim_matches('/').to_string())
+                .unwrap_or(repo);
+            if !is_valid_repo(&repo) {
+                return Err(format!("Invalid app repository: {}", repo));
+            }
+            Ok(DeepLinkAction::InstallApp { repo })
+        }
+        "connect" => {
+            let host = param("host")?;
+            if !is_valid_host(&host) {
+                return Err(format!("Invalid robot host: {}", host));
+            }
+            Ok(DeepLinkAction::Connect { host })
+        }
+        other => Err(format!("Unknown deep-link action: {}", other)),
+    }
+}
+
+/// Catalog entry the daemon installs a Space app from
+fn space_app_source(repo: &str) -> AppSource {
+    let name = repo.rsplit('/').next().unwrap_or(repo);
+    AppSource::Catalog {
+        info: serde_json::json!({
+            "name": name,
+            "source_kind": "hf_space",
+            "url": format!("{}/{}", HF_SPACES_URL, repo),
+            "description": "",
+        }),
+    }
+}
+
+fn handle_urls(app_handle: &AppHandle, urls: Vec<Url>) {
+    for url in urls.into_iter().filter(|url| url.scheme() == SCHEME) {
+        let action = match parse(&url) {
+            Ok(action) => action,
+            Err(e) => {
+                eprintln!("[deep-link] ⚠️ Ignoring {}: {}", url, e);
+                continue;
+            }
+        };
+        println!("[deep-link] 🔗 {:?}", action);
+
+        let state = app_handle.state::<DeepLinkState>();
+        let request = {
+            let mut next_id = state.next_id.lock().unwrap();
+            *next_id += 1;
+            DeepLinkRequest { id: *next_id, action }
+        };
+        *state.pending.lock().unwrap() = Some(request.clone());
+
+        crate::tray::show_main_window(app_handle);
+        let _ = app_handle.emit("deep-link-request", request);
+    }
+}
+
+/// Handle the link the app was launched with and the ones opened while it runs (call once in setup)
+pub fn init(app_handle: &AppHandle) {
+    // Installed builds register the scheme; AppImages and Windows dev builds must do it themselves
+    #[cfg(any(target_os = "linux", all(debug_assertions, windows)))]
+    if let Err(e) = app_handle.deep_link().register(SCHEME) {
+        eprintln!("[deep-link] ⚠️ Failed to register {}://: {}", SCHEME, e);
+    }
+
+    if let Ok(Some(urls)) = app_handle.deep_link().get_current() {
+        handle_urls(app_handle, urls);
+    }
+
+    let handle = app_handle.clone();
+    app_handle.deep_link().on_open_url(move |event| handle_urls(&handle, event.urls()));
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Link waiting for confirmation (the frontend may have missed the event at launch)
+#[tauri::command]
+pub fn get_pending_deep_link(state: State<DeepLinkState>) -> Option<DeepLinkRequest> {
+    state.pending.lock().unwrap().clone()
+}
+
+/// Run the confirmed action: installs the app (returns its name) or connects to the robot
+#[tauri::command]
+pub async fn confirm_deep_link(
+    app_handle: AppHandle,
+    state: State<'_, DeepLinkState>,
+    proxy: State<'_, Arc<LocalProxyState>>,
+    id: u64,
+) -> Result<Option<String>, String> {
+    let request = {
+        let mut pending = state.pending.lock().unwrap();
+        match pending.as_ref() {
+            Some(request) if request.id == id => pending.take().unwrap(),
+            _ => return Err("This link request is no longer pending".to_string()),
+        }
+    };
+
+    match request.action {
+        DeepLinkAction::InstallApp { repo } => {
+            crate::apps::install_app(app_handle, proxy, space_app_source(&repo)).await.map(Some)
+        }
+        DeepLinkAction::Connect { host } => {
+            crate::local_proxy::set_target_host(&proxy, host).await;
+            Ok(None)
+        }
+    }
+}
+
+#[tauri::command]
+pub fn dismiss_deep_link(state: State<DeepLinkState>, id: u64) {
+    let mut pending = state.pending.lock().unwrap();
+    if pending.as_ref().is_some_and(|request| request.id == id) {
+        *pending = None;
+    }
+}
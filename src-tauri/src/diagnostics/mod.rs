@@ -0,0 +1,401 @@
+/// Diagnostics self-check
+///
+/// Runs an ordered battery of checks and returns a pass / warn / fail report, shown
+/// in the UI and included in crash reports:
+/// venv integrity, python importability, USB presence, USB driver (Windows), serial latency, daemon ports,
+/// daemon health, kinematics self-test, disk space, permissions.
+///
+/// Checks never stop the daemon: those needing the serial port are skipped while it
+/// runs (`run_usb_diagnostics` does the full link test). The kinematics self-test and
+/// macOS permission statuses only exist in the webview, so the frontend passes them in.
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Command;
+use std::time::Instant;
+use tauri::{AppHandle, Manager};
+
+use crate::daemon::DaemonState;
+use crate::daemon_api::DaemonClient;
+
+/// Free disk space below which updates and app installs may fail
+const DISK_WARN_BYTES: u64 = 5 * 1024 * 1024 * 1024;
+const DISK_FAIL_BYTES: u64 = 1024 * 1024 * 1024;
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckStatus {
+    /// Not applicable right now (no robot, daemon holding the port...)
+    Skipped,
+    Pass,
+    Warn,
+    Fail,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct DiagnosticCheck {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub status: CheckStatus,
+    pub message: String,
+    pub duration_ms: u64,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct DiagnosticsReport {
+    /// Unix millis
+    pub generated_at: u64,
+    /// Worst status of all checks
+    pub overall: CheckStatus,
+    pub checks: Vec<DiagnosticCheck>,
+}
+
+type Outcome = (CheckStatus, String);
+
+fn pass(message: impl Into<String>) -> Outcome {
+    (CheckStatus::Pass, message.into())
+}
+
+fn warn(message: impl Into<String>) -> Outcome {
+    (CheckStatus::Warn, message.into())
+}
+
+fn fail(message: impl Into<String>) -> Outcome {
+    (CheckStatus::Fail, message.into())
+}
+
+fn skipped(message: impl Into<String>) -> Outcome {
+    (CheckStatus::Skipped, message.into())
+}
+
+// ============================================================================
+// CHECKS
+// ============================================================================
+
+fn check_venv(app_handle: &AppHandle) -> Outcome {
+    let venv_path = match crate::update::get_local_venv_path(app_handle) {
+        Ok(path) => path,
+        Err(e) => return fail(e),
+    };
+    let python = crate::update::get_python_path(&venv_path);
+    let pip = crate::update::get_pip_path(&venv_path);
+    let (Ok(_), Ok(pip)) = (python, pip) else {
+        return fail(format!("Python environment incomplete at {:?}", venv_path));
+    };
+
+    match Command::new(pip).arg("check").output() {
+        Ok(output) if output.status.success() => match crate::update::get_local_daemon_version(&venv_path) {
+            Ok(version) => pass(format!("reachy-mini {} installed, dependencies consistent", version)),
+            Err(e) => fail(e),
+        },
+        Ok(output) => warn(format!(
+            "Broken dependencies: {}",
+            String::from_utf8_lossy(&output.stdout).trim()
+        )),
+        Err(e) => fail(format!("Failed to run pip check: {}", e)),
+    }
+}
+
+fn check_python_import(app_handle: &AppHandle) -> Outcome {
+    let python = match crate::update::get_local_venv_path(app_handle).and_then(|venv| crate::update::get_python_path(&venv)) {
+        Ok(python) => python,
+        Err(e) => return skipped(e),
+    };
+    let output = Command::new(python)
+        .args(["-c", "import reachy_mini, reachy_mini.daemon.app.main; import sys; print(sys.version.split()[0])"])
+        .output();
+    match output {
+        Ok(output) if output.status.success() => {
+            pass(format!("reachy_mini imports with Python {}", String::from_utf8_lossy(&output.stdout).trim()))
+        }
+        Ok(output) => {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            fail(format!("reachy_mini cannot be imported: {}", stderr.trim().lines().last().unwrap_or("")))
+        }
+        Err(e) => fail(format!("Failed to run python: {}", e)),
+    }
+}
+
+fn check_usb(robots: &[crate::usb::UsbRobot]) -> Outcome {
+    match robots {
+        [] => skipped("No Reachy Mini connected over USB"),
+        [robot] => pass(format!("Reachy Mini on {}", robot.port_name)),
+        robots => warn(format!(
+            "{} robots connected ({}), the preferred one is used",
+            robots.len(),
+            robots.iter().map(|robot| robot.port_name.as_str()).collect::<Vec<_>>().join(", ")
+        )),
+    }
+}
+
+/// Windows only: a robot enumerated without a working driver has no COM port
+fn check_usb_driver() -> Outcome {
+    use crate::usb::driver::DriverState;
+    match crate::usb::driver::driver_status() {
+        Ok(report) => match report.state {
+            DriverState::NotNeeded | DriverState::NoDevice => skipped(report.message),
+            DriverState::Working => pass(report.message),
+            DriverState::Missing | DriverState::Failed => fail(report.message),
+        },
+        Err(e) => warn(e),
+    }
+}
+
+fn check_serial_latency(robot: Option<&crate::usb::UsbRobot>, daemon_running: bool) -> Outcome {
+    let Some(robot) = robot else {
+        return skipped("No robot connected");
+    };
+    if daemon_running {
+        return skipped("The daemon holds the serial port (run the USB diagnostics for a full test)");
+    }
+    match crate::usb::diagnostics::run_quick_diagnostics(&robot.port_name) {
+        Ok(report) => {
+            let message = format!(
+                "{} motors, p95 {:.2} ms, {:.1}% errors",
+                report.motor_ids.len(),
+                report.latency.p95_ms,
+                report.error_rate * 100.0
+            );
+            match report.verdict {
+                "good" => pass(message),
+                "degraded" => warn(message),
+                _ => fail(message),
+            }
+        }
+        Err(e) => fail(e),
+    }
+}
+
+fn check_ports() -> Outcome {
+    let conflicts = crate::daemon::detect_port_conflicts();
+    if conflicts.is_empty() {
+        return pass("Daemon ports are free");
+    }
+    fail(
+        conflicts
+            .iter()
+            .map(|conflict| format!("port {} used by {} (PID {})", conflict.port, conflict.process_name, conflict.pid))
+            .collect::<Vec<_>>()
+            .join(", "),
+    )
+}
+
+async fn check_daemon_health(daemon_running: bool) -> Outcome {
+    if !daemon_running {
+        return skipped("Daemon not running");
+    }
+    match DaemonClient::new().daemon_status().await {
+        Ok(status) => match (status.state.as_deref(), status.error) {
+            (_, Some(error)) => fail(format!("Daemon reports an error: {}", error)),
+            (Some("running"), None) => pass(format!(
+                "Daemon {} running",
+                status.version.as_deref().unwrap_or("")
+            )),
+            (state, None) => warn(format!("Daemon is {}", state.unwrap_or("in an unknown state"))),
+        },
+        Err(e) => fail(e),
+    }
+}
+
+/// `{ "passed": bool }` or `{ "status": "pass" | "fail" | "not_run", ... }` from the WASM module
+fn check_kinematics(self_test: Option<&serde_json::Value>) -> Outcome {
+    let Some(result) = self_test else {
+        return skipped("Self-test runs in the app window and was not provided");
+    };
+    let passed = result["passed"].as_bool().or_else(|| match result["status"].as_str() {
+        Some("pass" | "passed" | "ok") => Some(true),
+        Some("fail" | "failed" | "error") => Some(false),
+        _ => None,
+    });
+    match passed {
+        Some(true) => pass("Kinematics self-test passed"),
+        Some(false) => fail(format!("Kinematics self-test failed: {}", result)),
+        None => skipped("Kinematics self-test not run"),
+    }
+}
+
+/// Free bytes on the disk holding `path`
+fn free_disk_bytes(path: &Path) -> Option<u64> {
+    #[cfg(not(target_os = "windows"))]
+    {
+        // POSIX format: Filesystem 1024-blocks Used Available Capacity Mounted-on
+        let output = Command::new("df").arg("-Pk").arg(path).output().ok()?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let available_kb: u64 = stdout.lines().nth(1)?.split_whitespace().nth(3)?.parse().ok()?;
+        Some(available_kb * 1024)
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        let drive = path.to_string_lossy().chars().next()?;
+        let output = Command::new("powershell")
+            .args(["-NoProfile", "-Command", &format!("(Get-PSDrive -Name {}).Free", drive)])
+            .output()
+            .ok()?;
+        String::from_utf8_lossy(&output.stdout).trim().parse().ok()
+    }
+}
+
+fn check_disk_space(app_handle: &AppHandle) -> Outcome {
+    let dir = match app_handle.path().app_data_dir() {
+        Ok(dir) => dir,
+        Err(e) => return skipped(format!("Failed to resolve app data dir: {}", e)),
+    };
+    let existing = dir.ancestors().find(|path| path.exists()).unwrap_or(&dir);
+    let Some(free) = free_disk_bytes(existing) else {
+        return skipped("Free space could not be read");
+    };
+    let message = format!("{:.1} GB free", free as f64 / 1024.0 / 1024.0 / 1024.0);
+    if free < DISK_FAIL_BYTES {
+        fail(message)
+    } else if free < DISK_WARN_BYTES {
+        warn(message)
+    } else {
+        pass(message)
+    }
+}
+
+/// Serial port access (Linux dialout group), app data writes and the statuses passed by the frontend
+fn check_permissions(
+    app_handle: &AppHandle,
+    robot: Option<&crate::usb::UsbRobot>,
+    permissions: Option<&HashMap<String, bool>>,
+) -> Outcome {
+    let mut problems = Vec::new();
+
+    #[cfg(target_os = "linux")]
+    if let Some(robot) = robot {
+        let path = std::ffi::CString::new(robot.port_name.as_str()).unwrap_or_default();
+        if unsafe { libc::access(path.as_ptr(), libc::R_OK | libc::W_OK) } != 0 {
+            let access = crate::permissions::linux::serial_access();
+            problems.push(format!("no read/write access to {} ({})", robot.port_name, access.message));
+        }
+    }
+    #[cfg(not(target_os = "linux"))]
+    let _ = robot;
+
+    if let Ok(dir) = app_handle.path().app_data_dir() {
+        let probe = dir.join(".diagnostics-write-test");
+        let writable = std::fs::create_dir_all(&dir).and_then(|_| std::fs::write(&probe, b"")).is_ok();
+        let _ = std::fs::remove_file(&probe);
+        if !writable {
+            problems.push(format!("app data directory {:?} is not writable", dir));
+        }
+    }
+
+    let denied: Vec<&str> = permissions
+        .into_iter()
+        .flatten()
+        .filter(|(_, granted)| !**granted)
+        .map(|(name, _)| name.as_str())
+        .collect();
+
+    if !problems.is_empty() {
+        fail(problems.join("; "))
+    } else if !denied.is_empty() {
+        warn(format!("Not granted: {}", denied.join(", ")))
+    } else {
+        pass("Serial port and app data accessible")
+    }
+}
+
+// ============================================================================
+// RUNNER
+// ============================================================================
+
+fn record(checks: &mut Vec<DiagnosticCheck>, id: &'static str, name: &'static str, started: Instant, outcome: Outcome) {
+    let (status, message) = outcome;
+    checks.push(DiagnosticCheck {
+        id,
+        name,
+        status,
+        message,
+        duration_ms: started.elapsed().as_millis() as u64,
+    });
+}
+
+/// Run every check in order
+pub async fn collect(
+    app_handle: &AppHandle,
+    kinematics_self_test: Option<&serde_json::Value>,
+    permissions: Option<&HashMap<String, bool>>,
+) -> DiagnosticsReport {
+    let robots = crate::usb::get_reachy_robots();
+    let daemon_running = app_handle.state::<DaemonState>().process.lock().unwrap().is_some();
+    let mut checks = Vec::new();
+
+    // Blocking checks (processes, serial port) off the async runtime
+    let blocking = |check: fn(&AppHandle) -> Outcome| {
+        let handle = app_handle.clone();
+        async move {
+            tauri::async_runtime::spawn_blocking(move || check(&handle))
+                .await
+                .unwrap_or_else(|e| fail(format!("Task join error: {}", e)))
+        }
+    };
+
+    let started = Instant::now();
+    record(&mut checks, "venv", "Python environment", started, blocking(check_venv).await);
+    let started = Instant::now();
+    record(&mut checks, "python_import", "Daemon importable", started, blocking(check_python_import).await);
+    let started = Instant::now();
+    record(&mut checks, "usb", "USB robot", started, check_usb(&robots));
+
+    let started = Instant::now();
+    let driver = tauri::async_runtime::spawn_blocking(check_usb_driver)
+        .await
+        .unwrap_or_else(|e| fail(format!("Task join error: {}", e)));
+    record(&mut checks, "usb_driver", "USB driver", started, driver);
+
+    let started = Instant::now();
+    let robot = robots.first().cloned();
+    let latency = tauri::async_runtime::spawn_blocking(move || check_serial_latency(robot.as_ref(), daemon_running))
+        .await
+        .unwrap_or_else(|e| fail(format!("Task join error: {}", e)));
+    record(&mut checks, "serial_latency", "Serial link", started, latency);
+
+    let started = Instant::now();
+    record(&mut checks, "ports", "Daemon ports", started, check_ports());
+    let started = Instant::now();
+    record(&mut checks, "daemon_health", "Daemon health", started, check_daemon_health(daemon_running).await);
+    let started = Instant::now();
+    record(&mut checks, "kinematics", "Kinematics self-test", started, check_kinematics(kinematics_self_test));
+    let started = Instant::now();
+    record(&mut checks, "disk_space", "Disk space", started, check_disk_space(app_handle));
+    let started = Instant::now();
+    record(&mut checks, "permissions", "Permissions", started, check_permissions(app_handle, robots.first(), permissions));
+
+    DiagnosticsReport {
+        generated_at: crate::daemon::history::now_millis(),
+        overall: checks.iter().map(|check| check.status).max().unwrap_or(CheckStatus::Skipped),
+        checks,
+    }
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Run the self-check suite
+///
+/// # Arguments
+/// * `kinematics_self_test` - Self-test results from the kinematics WASM module
+/// * `permissions` - macOS permission statuses, e.g. { "camera": true, "microphone": false }
+#[tauri::command]
+pub async fn run_diagnostics(
+    app_handle: AppHandle,
+    kinematics_self_test: Option<serde_json::Value>,
+    permissions: Option<HashMap<String, bool>>,
+) -> Result<DiagnosticsReport, String> {
+    println!("[diagnostics] 🩺 Running self-check...");
+    let report = collect(&app_handle, kinematics_self_test.as_ref(), permissions.as_ref()).await;
+    for check in &report.checks {
+        println!("[diagnostics] {:?} {}: {}", check.status, check.name, check.message);
+    }
+    Ok(report)
+}
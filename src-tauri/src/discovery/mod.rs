@@ -0,0 +1,220 @@
+/// Discovery module
+///
+/// Finds Reachy Mini robots on the LAN without the user typing an IP:
+/// - resolves the robot's known hostnames through the system resolver, and the `.local`
+///   hosts of saved profiles (renamed robots)
+/// - sends its own mDNS query for `reachy-mini.local` (for systems whose resolver
+///   doesn't do mDNS)
+/// - checks every candidate against the daemon API on port 8000
+///
+/// A reachable robot can be handed straight to local_proxy::set_target_host.
+
+use serde::Serialize;
+use std::collections::BTreeSet;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tauri::State;
+use tokio::net::UdpSocket;
+
+use crate::local_proxy::{self, LocalProxyState};
+
+/// Hostnames the robot announces (same list the frontend tries)
+const ROBOT_HOSTNAMES: &[&str] = &["reachy-mini.local", "reachy-mini.home"];
+const MDNS_HOSTNAME: &str = "reachy-mini.local";
+const MDNS_ADDR: SocketAddr = SocketAddr::new(IpAddr::V4(Ipv4Addr::new(224, 0, 0, 251)), 5353);
+const MDNS_LISTEN_TIMEOUT: Duration = Duration::from_secs(2);
+const DAEMON_PORT: u16 = 8000;
+const REACHABILITY_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Serialize, Clone)]
+pub struct DiscoveredRobot {
+    pub ip: String,
+    /// Hostname the IP was found through ("mdns" for our own query)
+    pub source: String,
+    /// Daemon API answered on port 8000
+    pub reachable: bool,
+    pub latency_ms: Option<u64>,
+}
+
+// ============================================================================
+// mDNS QUERY
+// ============================================================================
+
+/// Build a one-question mDNS query for the A record of `hostname`
+/// (QU bit set so responders answer us directly instead of multicasting)
+fn build_mdns_query(hostname: &str) -> Vec<u8> {
+    let mut packet = vec![0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0]; // id, flags, 1 question
+    for label in hostname.split('.') {
+        packet.push(label.len() as u8);
+        packet.extend_from_slice(label.as_bytes());
+    }
+    packet.push(0);
+    packet.extend_from_slice(&[0, 1]); // type A
+    packet.extend_from_slice(&[0x80, 1]); // class IN + unicast response
+    packet
+}
+
+/// Skip a (possibly compressed) DNS name, returning the offset right after it
+fn skip_name(packet: &[u8], mut offset: usize) -> Option<usize> {
+    loop {
+        let len = *packet.get(offset)? as usize;
+        if len & 0xC0 == 0xC0 {
+            return Some(offset + 2);
+        }
+        if len == 0 {
+            return Some(offset + 1);
+        }
+        offset += len + 1;
+    }
+}
+
+/// Extract the IPv4 addresses of every A record in an mDNS response
+fn parse_mdns_response(packet: &[u8]) -> Vec<Ipv4Addr> {
+    let read_u16 = |offset: usize| -> Option<u16> {
+        Some(u16::from_be_bytes([*packet.get(offset)?, *packet.get(offset + 1)?]))
+    };
+
+    let parse = || -> Option<Vec<Ipv4Addr>> {
+        let questions = read_u16(4)?;
+        let records = read_u16(6)? + read_u16(8)? + read_u16(10)?;
+
+        let mut offset = 12;
+        for _ in 0..questions {
+            offset = skip_name(packet, offset)? + 4;
+        }
+
+        let mut addresses = Vec::new();
+        for _ in 0..records {
+            offset = skip_name(packet, offset)?;
+            let record_type = read_u16(offset)?;
+            let data_len = read_u16(offset + 8)? as usize;
+            let data = packet.get(offset + 10..offset + 10 + data_len)?;
+            if record_type == 1 && data_len == 4 {
+                addresses.push(Ipv4Addr::new(data[0], data[1], data[2], data[3]));
+            }
+            offset += 10 + data_len;
+        }
+        Some(addresses)
+    };
+
+    parse().unwrap_or_default()
+}
+
+/// Ask the LAN for `reachy-mini.local` directly
+async fn query_mdns() -> Vec<Ipv4Addr> {
+    let socket = match UdpSocket::bind("0.0.0.0:0").await {
+        Ok(socket) => socket,
+        Err(e) => {
+            eprintln!("[discovery] ⚠️ Failed to open mDNS socket: {}", e);
+            return Vec::new();
+        }
+    };
+    if let Err(e) = socket.send_to(&build_mdns_query(MDNS_HOSTNAME), MDNS_ADDR).await {
+        eprintln!("[discovery] ⚠️ Failed to send mDNS query: {}", e);
+        return Vec::new();
+    }
+
+    let mut addresses = Vec::new();
+    let mut buffer = [0u8; 1500];
+    let deadline = Instant::now() + MDNS_LISTEN_TIMEOUT;
+    while let Some(remaining) = deadline.checked_duration_since(Instant::now()) {
+        match tokio::time::timeout(remaining, socket.recv_from(&mut buffer)).await {
+            Ok(Ok((len, _))) => addresses.extend(parse_mdns_response(&buffer[..len])),
+            _ => break,
+        }
+    }
+    addresses
+}
+
+// ============================================================================
+// HOSTNAME RESOLUTION & REACHABILITY
+// ============================================================================
+
+async fn resolve_hostname(hostname: &str) -> Vec<IpAddr> {
+    match tokio::net::lookup_host((hostname, DAEMON_PORT)).await {
+        Ok(addresses) => addresses.map(|a| a.ip()).filter(|ip| ip.is_ipv4()).collect(),
+        Err(_) => Vec::new(),
+    }
+}
+
+async fn check_reachable(client: &reqwest::Client, ip: &str) -> Option<u64> {
+    let started = Instant::now();
+    let response = client
+        .get(format!("http://{}:{}/api/daemon/status", ip, DAEMON_PORT))
+        .timeout(REACHABILITY_TIMEOUT)
+        .send()
+        .await
+        .ok()?;
+    response
+        .status()
+        .is_success()
+        .then(|| started.elapsed().as_millis() as u64)
+}
+
+/// Find candidate robots (the default hostnames and `extra_hostnames`) and check which ones answer
+pub async fn discover(extra_hostnames: &[String]) -> Vec<DiscoveredRobot> {
+    let mut candidates: Vec<(String, String)> = Vec::new();
+    let mut seen = BTreeSet::new();
+
+    let hostnames = ROBOT_HOSTNAMES.iter().map(|hostname| hostname.to_string()).chain(extra_hostnames.iter().cloned());
+    for hostname in hostnames {
+        for ip in resolve_hostname(&hostname).await {
+            if seen.insert(ip.to_string()) {
+                candidates.push((ip.to_string(), hostname.clone()));
+            }
+        }
+    }
+    for ip in query_mdns().await {
+        if seen.insert(ip.to_string()) {
+            candidates.push((ip.to_string(), "mdns".to_string()));
+        }
+    }
+
+    let client = reqwest::Client::new();
+    let mut robots = Vec::new();
+    for (ip, source) in candidates {
+        let latency_ms = check_reachable(&client, &ip).await;
+        robots.push(DiscoveredRobot {
+            ip,
+            source,
+            reachable: latency_ms.is_some(),
+            latency_ms,
+        });
+    }
+
+    // Reachable first, fastest first
+    robots.sort_by_key(|r| (!r.reachable, r.latency_ms.unwrap_or(u64::MAX)));
+    println!("[discovery] Found {} candidate(s): {:?}", robots.len(), robots);
+    robots
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Discover robots on the LAN
+/// With `auto_select`, a single reachable robot becomes the local proxy target
+#[tauri::command]
+pub async fn discover_robots(
+    proxy_state: State<'_, Arc<LocalProxyState>>,
+    auto_select: Option<bool>,
+) -> Result<Vec<DiscoveredRobot>, String> {
+    let saved_hostnames: Vec<String> = proxy_state
+        .profiles
+        .list()
+        .into_iter()
+        .map(|profile| profile.host)
+        .filter(|host| host.ends_with(".local") && !ROBOT_HOSTNAMES.contains(&host.as_str()))
+        .collect();
+    let robots = discover(&saved_hostnames).await;
+
+    if auto_select.unwrap_or(false) {
+        let mut reachable = robots.iter().filter(|r| r.reachable);
+        if let (Some(robot), None) = (reachable.next(), reachable.next()) {
+            local_proxy::set_target_host(&proxy_state, robot.ip.clone()).await;
+        }
+    }
+
+    Ok(robots)
+}
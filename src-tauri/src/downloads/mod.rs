@@ -0,0 +1,409 @@
+/// Content-addressable download cache
+///
+/// Artifacts with a published sha256 (daemon releases, sound packs) are downloaded once
+/// into `<app data>/download-cache/<sha256>` and served from there afterwards, whatever
+/// URL they are requested from. Their metadata is kept in `download-cache/index.json`.
+///
+/// Downloads stream to `<sha256>.part` and resume from it with an HTTP range request,
+/// after a dropped connection (retried here with backoff) or in a later session. At most
+/// `MAX_CONCURRENT` downloads run at once; requests for a blob already being downloaded
+/// wait for it. A file is only moved into the cache once its hash matches.
+///
+/// Emits `download-progress` (DownloadProgress) as bytes arrive.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashSet};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, State};
+use tokio::sync::Semaphore;
+
+const CACHE_DIR: &str = "download-cache";
+const INDEX_FILE: &str = "index.json";
+const PARTIAL_EXTENSION: &str = "part";
+const MAX_CONCURRENT: usize = 2;
+const MAX_ATTEMPTS: u32 = 4;
+/// Multiplied by the attempt number
+const RETRY_DELAY: Duration = Duration::from_secs(2);
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(15);
+/// No byte received for this long: the attempt is abandoned (and resumed)
+const STALL_TIMEOUT: Duration = Duration::from_secs(30);
+const WAIT_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CacheEntry {
+    pub sha256: String,
+    pub size_bytes: u64,
+    /// Last URL and name it was requested with
+    pub url: String,
+    pub name: String,
+    /// Unix millis
+    pub created_at: u64,
+    pub last_used_at: u64,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct CacheSummary {
+    pub dir: Option<String>,
+    /// Most recently used first
+    pub entries: Vec<CacheEntry>,
+    pub total_bytes: u64,
+    /// Interrupted downloads waiting to be resumed
+    pub partial_bytes: u64,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct CachePurge {
+    pub removed: usize,
+    pub reclaimed_bytes: u64,
+}
+
+/// Payload of the `download-progress` event
+#[derive(Debug, Serialize, Clone)]
+pub struct DownloadProgress {
+    pub name: String,
+    pub sha256: String,
+    pub downloaded_bytes: u64,
+    /// None when the server doesn't send a length
+    pub total_bytes: Option<u64>,
+    pub percent: Option<u8>,
+    /// Continued from an earlier partial download
+    pub resumed: bool,
+}
+
+pub struct DownloadCache {
+    index: Mutex<BTreeMap<String, CacheEntry>>,
+    /// `<app data>/download-cache` (set by `load`)
+    dir: Mutex<Option<PathBuf>>,
+    slots: Semaphore,
+    /// Blobs being downloaded
+    in_flight: Mutex<HashSet<String>>,
+}
+
+/// Releases a blob claimed for download
+struct InFlight<'a> {
+    cache: &'a DownloadCache,
+    sha256: String,
+}
+
+impl Drop for InFlight<'_> {
+    fn drop(&mut self) {
+        self.cache.in_flight.lock().unwrap().remove(&self.sha256);
+    }
+}
+
+// ============================================================================
+// HELPER FUNCTIONS
+// ============================================================================
+
+/// Lowercase hex sha256 (64 characters)
+fn normalize_hash(sha256: &str) -> Result<String, String> {
+    let sha256 = sha256.trim().to_lowercase();
+    if sha256.len() == 64 && sha256.chars().all(|c| c.is_ascii_hexdigit()) {
+        Ok(sha256)
+    } else {
+        Err(format!("Invalid sha256: {}", sha256))
+    }
+}
+
+/// Lowercase hex sha256 of a file, read in chunks
+pub(crate) fn sha256_file(path: &Path) -> Result<String, String> {
+    let mut file = std::fs::File::open(path).map_err(|e| format!("Failed to open {:?}: {}", path, e))?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 64 * 1024];
+    loop {
+        let read = file.read(&mut buffer).map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+    Ok(hasher.finalize().iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+fn file_size(path: &Path) -> u64 {
+    std::fs::metadata(path).map(|metadata| metadata.len()).unwrap_or(0)
+}
+
+impl DownloadCache {
+    pub fn new() -> Self {
+        Self {
+            index: Mutex::new(BTreeMap::new()),
+            dir: Mutex::new(None),
+            slots: Semaphore::new(MAX_CONCURRENT),
+            in_flight: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// Load the cache index from the app data directory (empty if missing or corrupted);
+    /// entries whose file is gone are dropped
+    pub fn load(&self, app_data_dir: &Path) {
+        let dir = app_data_dir.join(CACHE_DIR);
+        let mut index: BTreeMap<String, CacheEntry> = std::fs::read_to_string(dir.join(INDEX_FILE))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        index.retain(|sha256, _| dir.join(sha256).is_file());
+        *self.index.lock().unwrap() = index;
+        *self.dir.lock().unwrap() = Some(dir);
+    }
+
+    fn cache_dir(&self) -> Result<PathBuf, String> {
+        self.dir
+            .lock()
+            .unwrap()
+            .clone()
+            .ok_or_else(|| "App data directory not available".to_string())
+    }
+
+    /// Apply a change to the index and persist it
+    fn update(&self, change: impl FnOnce(&mut BTreeMap<String, CacheEntry>)) -> Result<(), String> {
+        let mut index = self.index.lock().unwrap();
+        change(&mut index);
+
+        let dir = self.cache_dir()?;
+        std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {:?}: {}", dir, e))?;
+        let content = serde_json::to_string_pretty(&*index)
+            .map_err(|e| format!("Failed to serialize the download cache index: {}", e))?;
+        std::fs::write(dir.join(INDEX_FILE), content)
+            .map_err(|e| format!("Failed to write the download cache index: {}", e))
+    }
+
+    /// Record a use of a blob (added if new)
+    fn touch(&self, sha256: &str, url: &str, name: &str, size_bytes: u64) {
+        let now = crate::daemon::history::now_millis();
+        let result = self.update(|index| {
+            let entry = index.entry(sha256.to_string()).or_insert_with(|| CacheEntry {
+                sha256: sha256.to_string(),
+                size_bytes,
+                url: url.to_string(),
+                name: name.to_string(),
+                created_at: now,
+                last_used_at: now,
+            });
+            entry.url = url.to_string();
+            entry.name = name.to_string();
+            entry.last_used_at = now;
+        });
+        if let Err(e) = result {
+            eprintln!("[downloads] ⚠️ {}", e);
+        }
+    }
+
+    /// Claim a blob for download, waiting while another request downloads it
+    async fn claim(&self, sha256: &str) -> InFlight<'_> {
+        loop {
+            if self.in_flight.lock().unwrap().insert(sha256.to_string()) {
+                return InFlight {
+                    cache: self,
+                    sha256: sha256.to_string(),
+                };
+            }
+            tokio::time::sleep(WAIT_POLL_INTERVAL).await;
+        }
+    }
+
+    pub fn summary(&self) -> CacheSummary {
+        let dir = self.cache_dir().ok();
+        let mut entries: Vec<CacheEntry> = self.index.lock().unwrap().values().cloned().collect();
+        entries.sort_by_key(|entry| std::cmp::Reverse(entry.last_used_at));
+        let partial_bytes = dir
+            .as_ref()
+            .and_then(|dir| std::fs::read_dir(dir).ok())
+            .map(|files| {
+                files
+                    .flatten()
+                    .filter(|file| file.path().extension().is_some_and(|ext| ext == PARTIAL_EXTENSION))
+                    .map(|file| file_size(&file.path()))
+                    .sum()
+            })
+            .unwrap_or(0);
+        CacheSummary {
+            dir: dir.map(|dir| dir.to_string_lossy().to_string()),
+            total_bytes: entries.iter().map(|entry| entry.size_bytes).sum(),
+            entries,
+            partial_bytes,
+        }
+    }
+}
+
+impl Default for DownloadCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// One attempt: stream `url` into `partial`, continuing from its current length
+async fn download_to(app_handle: &AppHandle, url: &str, partial: &Path, name: &str, sha256: &str) -> Result<(), String> {
+    let offset = file_size(partial);
+    let client = reqwest::Client::builder()
+        .connect_timeout(CONNECT_TIMEOUT)
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+    let mut request = client.get(url);
+    if offset > 0 {
+        request = request.header(reqwest::header::RANGE, format!("bytes={}-", offset));
+    }
+    let response = request
+        .send()
+        .await
+        .map_err(|e| format!("Failed to download {}: {}", name, e))?;
+    if offset > 0 && response.status() == reqwest::StatusCode::RANGE_NOT_SATISFIABLE {
+        // Nothing left past the partial file: it is complete (the hash check decides)
+        return Ok(());
+    }
+    let mut response = response
+        .error_for_status()
+        .map_err(|e| format!("Failed to download {}: {}", name, e))?;
+
+    // A server ignoring the range sends the whole file again
+    let resumed = response.status() == reqwest::StatusCode::PARTIAL_CONTENT;
+    let mut file = if resumed {
+        println!("[downloads] ⏯️ Resuming {} at {} bytes", name, offset);
+        std::fs::OpenOptions::new().append(true).open(partial)
+    } else {
+        std::fs::File::create(partial)
+    }
+    .map_err(|e| format!("Failed to open {:?}: {}", partial, e))?;
+
+    let mut downloaded = if resumed { offset } else { 0 };
+    let total_bytes = response.content_length().map(|length| length + downloaded);
+    // Emitted on the first chunk, then on each percent step
+    let mut last_percent: Option<Option<u8>> = None;
+    loop {
+        let chunk = tokio::time::timeout(STALL_TIMEOUT, response.chunk())
+            .await
+            .map_err(|_| format!("Download of {} stalled", name))?
+            .map_err(|e| format!("Failed to download {}: {}", name, e))?;
+        let Some(chunk) = chunk else {
+            break;
+        };
+        file.write_all(&chunk).map_err(|e| format!("Failed to write {:?}: {}", partial, e))?;
+        downloaded += chunk.len() as u64;
+
+        let percent = total_bytes.map(|total| (downloaded * 100 / total.max(1)).min(100) as u8);
+        if last_percent != Some(percent) {
+            last_percent = Some(percent);
+            let _ = app_handle.emit("download-progress", DownloadProgress {
+                name: name.to_string(),
+                sha256: sha256.to_string(),
+                downloaded_bytes: downloaded,
+                total_bytes,
+                percent,
+                resumed,
+            });
+        }
+    }
+    Ok(())
+}
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// Path of the blob with this sha256, downloaded from `url` unless already cached
+///
+/// `name` labels the download in logs, errors and progress events.
+pub async fn fetch(app_handle: &AppHandle, url: &str, sha256: &str, name: &str) -> Result<PathBuf, String> {
+    let sha256 = normalize_hash(sha256)?;
+    let cache = app_handle.state::<DownloadCache>();
+    let dir = cache.cache_dir()?;
+    let _claim = cache.claim(&sha256).await;
+
+    let blob = dir.join(&sha256);
+    if blob.is_file() {
+        if sha256_file(&blob)? == sha256 {
+            println!("[downloads] ♻️ {} served from the cache", name);
+            cache.touch(&sha256, url, name, file_size(&blob));
+            return Ok(blob);
+        }
+        eprintln!("[downloads] ⚠️ Cached {} is corrupted, downloading it again", name);
+        let _ = std::fs::remove_file(&blob);
+    }
+
+    let _slot = cache
+        .slots
+        .acquire()
+        .await
+        .map_err(|e| format!("Download queue closed: {}", e))?;
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {:?}: {}", dir, e))?;
+    let partial = dir.join(format!("{}.{}", sha256, PARTIAL_EXTENSION));
+
+    println!("[downloads] 📥 Downloading {} ...", name);
+    let mut attempt = 1;
+    while let Err(e) = download_to(app_handle, url, &partial, name, &sha256).await {
+        if attempt >= MAX_ATTEMPTS {
+            return Err(format!("{} (after {} attempts)", e, MAX_ATTEMPTS));
+        }
+        eprintln!("[downloads] ⚠️ {} (attempt {}/{}), retrying", e, attempt, MAX_ATTEMPTS);
+        tokio::time::sleep(RETRY_DELAY * attempt).await;
+        attempt += 1;
+    }
+
+    let actual = sha256_file(&partial)?;
+    if actual != sha256 {
+        let _ = std::fs::remove_file(&partial);
+        return Err(format!(
+            "Hash mismatch for {}: expected sha256 {}, got {}. The download was discarded.",
+            name, sha256, actual
+        ));
+    }
+    std::fs::rename(&partial, &blob).map_err(|e| format!("Failed to store {} in the cache: {}", name, e))?;
+    println!("[downloads] ✅ sha256 verified for {}", name);
+    cache.touch(&sha256, url, name, file_size(&blob));
+    Ok(blob)
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Cached artifacts and the space they take
+#[tauri::command]
+pub fn get_download_cache(state: State<DownloadCache>) -> CacheSummary {
+    state.summary()
+}
+
+/// Delete one cached artifact (by sha256), or everything including partial downloads
+/// (downloads running right now are left alone)
+#[tauri::command]
+pub fn purge_download_cache(state: State<DownloadCache>, sha256: Option<String>) -> Result<CachePurge, String> {
+    let dir = state.cache_dir()?;
+    let in_flight = state.in_flight.lock().unwrap().clone();
+    let targets: Vec<PathBuf> = match sha256 {
+        Some(sha256) => vec![dir.join(normalize_hash(&sha256)?)],
+        None => std::fs::read_dir(&dir)
+            .map(|files| files.flatten().map(|file| file.path()).collect())
+            .unwrap_or_default(),
+    };
+
+    let mut purge = CachePurge { removed: 0, reclaimed_bytes: 0 };
+    let mut removed_hashes = Vec::new();
+    for path in targets {
+        let Some(stem) = path.file_stem().map(|stem| stem.to_string_lossy().to_string()) else {
+            continue;
+        };
+        if normalize_hash(&stem).is_err() || in_flight.contains(&stem) || !path.is_file() {
+            continue;
+        }
+        let size = file_size(&path);
+        match std::fs::remove_file(&path) {
+            Ok(()) => {
+                purge.removed += 1;
+                purge.reclaimed_bytes += size;
+                removed_hashes.push(stem);
+            }
+            Err(e) => eprintln!("[downloads] ⚠️ Failed to remove {:?}: {}", path, e),
+        }
+    }
+    state.update(|index| index.retain(|sha256, _| !removed_hashes.contains(sha256)))?;
+    println!("[downloads] 🧹 Purged {} file(s), {} bytes", purge.removed, purge.reclaimed_bytes);
+    Ok(purge)
+}
@@ -0,0 +1,111 @@
+/// Emergency stop
+///
+/// Makes every motor compliant right away, from a command or a global keyboard
+/// shortcut (see shortcut), even when the daemon no longer answers:
+/// 1. Daemon: motors set to `disabled`, with a short timeout
+/// 2. Fallback (USB robot): the daemon is killed to free the serial port and a
+///    broadcast Torque Enable = 0 is written directly on the motor bus
+///
+/// Emits `emergency-stop` with the result, whichever way it was triggered.
+
+pub mod shortcut;
+
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::daemon::DaemonState;
+use crate::daemon_api::{DaemonClient, MotorMode};
+use crate::local_proxy::LocalProxyState;
+
+/// The daemon gets this long to answer before the serial fallback
+const DAEMON_TIMEOUT: Duration = Duration::from_millis(800);
+const PORT_RELEASE_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum StopMethod {
+    Daemon,
+    Serial,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct EmergencyStopResult {
+    pub stopped: bool,
+    pub method: Option<StopMethod>,
+    /// Why the daemon (and the fallback, if tried) failed
+    pub errors: Vec<String>,
+}
+
+/// Kill the daemon and write the torque-off broadcast on the robot port
+fn serial_fallback(app_handle: &AppHandle, port: &str) -> Result<(), String> {
+    crate::serial_console::release_for_daemon(app_handle);
+    crate::daemon::kill_daemon(&app_handle.state::<DaemonState>());
+    crate::usb::wait_for_port_release(port, PORT_RELEASE_TIMEOUT)?;
+    crate::usb::identity::broadcast_torque_off(port)
+}
+
+/// Stop the motors by the first way that works
+pub async fn stop(app_handle: &AppHandle) -> EmergencyStopResult {
+    eprintln!("[emergency] 🛑 Emergency stop requested");
+    let mut errors = Vec::new();
+
+    // A sandboxed app talks to the daemon on its own: stop it from sending new moves
+    let handle = app_handle.clone();
+    tauri::async_runtime::spawn_blocking(move || crate::apps::sandbox::stop(&handle));
+
+    let proxy = app_handle.state::<Arc<LocalProxyState>>();
+    let client = DaemonClient::for_proxy(&proxy).await.with_timeout(DAEMON_TIMEOUT);
+    let mut method = match client.set_motor_mode(MotorMode::Disabled).await {
+        Ok(()) => Some(StopMethod::Daemon),
+        Err(e) => {
+            errors.push(e);
+            None
+        }
+    };
+
+    // Serial fallback: only for a robot on this computer's USB
+    let wifi_mode = proxy.target_host.read().await.is_some();
+    if method.is_none() && !wifi_mode {
+        match crate::usb::get_reachy_port() {
+            Some(port) => {
+                eprintln!("[emergency] ⚠️ Daemon unresponsive, stopping motors over {}", port);
+                let handle = app_handle.clone();
+                let result = tauri::async_runtime::spawn_blocking(move || serial_fallback(&handle, &port))
+                    .await
+                    .map_err(|e| format!("Task join error: {}", e))
+                    .and_then(|r| r);
+                match result {
+                    Ok(()) => method = Some(StopMethod::Serial),
+                    Err(e) => errors.push(e),
+                }
+            }
+            None => errors.push("No USB robot for the serial fallback".to_string()),
+        }
+    }
+
+    let result = EmergencyStopResult {
+        stopped: method.is_some(),
+        method,
+        errors,
+    };
+    match method {
+        Some(method) => eprintln!("[emergency] ✅ Motors compliant ({:?})", method),
+        None => eprintln!("[emergency] ❌ Emergency stop failed: {}", result.errors.join(" | ")),
+    }
+    crate::analytics::record(app_handle, "emergency_stop", serde_json::json!({
+        "method": method.map(|method| if method == StopMethod::Daemon { "daemon" } else { "serial" }),
+    }));
+    let _ = app_handle.emit("emergency-stop", result.clone());
+    result
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+#[tauri::command]
+pub async fn emergency_stop(app_handle: AppHandle) -> EmergencyStopResult {
+    stop(&app_handle).await
+}
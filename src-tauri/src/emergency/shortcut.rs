@@ -0,0 +1,36 @@
+//! Global emergency-stop shortcut
+//!
+//! ⌘⇧Space (macOS) / Ctrl+Shift+Space: works while the app is in the background
+//! or hidden in the tray.
+
+use tauri::App;
+use tauri_plugin_global_shortcut::{Code, GlobalShortcutExt, Modifiers, Shortcut, ShortcutState};
+
+#[cfg(target_os = "macos")]
+const MODIFIERS: Modifiers = Modifiers::SUPER.union(Modifiers::SHIFT);
+#[cfg(not(target_os = "macos"))]
+const MODIFIERS: Modifiers = Modifiers::CONTROL.union(Modifiers::SHIFT);
+
+/// Register the shortcut (call once in setup)
+pub fn register(app: &App) -> Result<(), String> {
+    let stop_shortcut = Shortcut::new(Some(MODIFIERS), Code::Space);
+
+    app.handle()
+        .plugin(
+            tauri_plugin_global_shortcut::Builder::new()
+                .with_handler(move |app_handle, shortcut, event| {
+                    if shortcut == &stop_shortcut && event.state() == ShortcutState::Pressed {
+                        let app_handle = app_handle.clone();
+                        tauri::async_runtime::spawn(async move {
+                            super::stop(&app_handle).await;
+                        });
+                    }
+                })
+                .build(),
+        )
+        .map_err(|e| format!("Failed to init global shortcuts: {}", e))?;
+
+    app.global_shortcut()
+        .register(stop_shortcut)
+        .map_err(|e| format!("Failed to register emergency stop shortcut: {}", e))
+}
@@ -0,0 +1,232 @@
+/// Firmware module
+///
+/// Flashes motor-controller firmware over the robot's serial port from the app:
+/// - stops the daemon (it holds the port) and restarts it afterwards
+/// - runs the flashing tool shipped with the reachy_mini package in the daemon venv,
+///   which implements the bootloader protocol, and relays its progress
+/// - verifies the result by pinging the motor bus
+///
+/// Emits `firmware-flash-progress` with { step, percent, message }.
+
+use serde::Serialize;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::daemon::DaemonState;
+use crate::usb::identity::{read_robot_identity, MotorInfo};
+
+/// Flashing tool module (installed with the daemon)
+const FLASH_TOOL_MODULE: &str = "reachy_mini.tools.flash_firmware";
+/// How long to wait for the daemon to release the serial port
+const PORT_RELEASE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Only one flash at a time
+static FLASHING: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Serialize, Clone)]
+struct FlashProgress {
+    /// "preparing" | "stopping_daemon" | "flashing" | "verifying" | "restarting_daemon" | "done" | "failed"
+    step: &'static str,
+    percent: Option<u8>,
+    message: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct FlashResult {
+    pub firmware_sha256: String,
+    /// Motors answering on the bus after flashing
+    pub motors: Vec<MotorInfo>,
+    /// The daemon was running before and has been restarted
+    pub daemon_restarted: bool,
+}
+
+fn emit_progress(app_handle: &AppHandle, step: &'static str, percent: Option<u8>, message: impl Into<String>) {
+    let message = message.into();
+    println!("[firmware] {}", message);
+    let _ = app_handle.emit("firmware-flash-progress", FlashProgress { step, percent, message });
+}
+
+// ============================================================================
+// HELPERS
+// ============================================================================
+
+/// Hash of the firmware file, refusing an empty one
+fn firmware_sha256(path: &Path) -> Result<String, String> {
+    let size = std::fs::metadata(path)
+        .map_err(|e| format!("Failed to read firmware file: {}", e))?
+        .len();
+    if size == 0 {
+        return Err("Firmware file is empty".to_string());
+    }
+    crate::downloads::sha256_file(path)
+}
+
+/// Check that the installed daemon ships the flashing tool
+fn check_flash_tool(python_path: &Path) -> Result<(), String> {
+    let script = format!(
+        "import importlib.util, sys; sys.exit(0 if importlib.util.find_spec('{}') else 1)",
+        FLASH_TOOL_MODULE
+    );
+    let output = std::process::Command::new(python_path)
+        .args(["-c", &script])
+        .output()
+        .map_err(|e| format!("Failed to run python: {}", e))?;
+    if !output.status.success() {
+        return Err("The installed daemon has no firmware flashing tool - update the daemon first".to_string());
+    }
+    Ok(())
+}
+
+/// Percentage in a tool output line ("Writing... 42%")
+fn parse_percent(line: &str) -> Option<u8> {
+    let before = &line[..line.find('%')?];
+    let digits: String = before
+        .chars()
+        .rev()
+        .take_while(|c| c.is_ascii_digit())
+        .collect::<Vec<_>>()
+        .into_iter()
+        .rev()
+        .collect();
+    digits.parse::<u8>().ok().filter(|percent| *percent <= 100)
+}
+
+/// Run the flashing tool, relaying its output as progress events
+fn run_flash_tool(app_handle: &AppHandle, python_path: &Path, port: &str, firmware_path: &Path) -> Result<(), String> {
+    use std::io::{BufRead, BufReader, Read};
+    use std::process::Stdio;
+
+    let mut child = std::process::Command::new(python_path)
+        .args(["-m", FLASH_TOOL_MODULE, "--port", port])
+        .arg(firmware_path)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to run the flashing tool: {}", e))?;
+
+    // Drain stderr on its own thread so a full pipe can't block the tool
+    let mut stderr_pipe = child.stderr.take();
+    let stderr_thread = std::thread::spawn(move || {
+        let mut stderr = String::new();
+        if let Some(pipe) = stderr_pipe.as_mut() {
+            let _ = pipe.read_to_string(&mut stderr);
+        }
+        stderr
+    });
+
+    if let Some(stdout) = child.stdout.take() {
+        for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+            let line = line.trim();
+            if !line.is_empty() {
+                emit_progress(app_handle, "flashing", parse_percent(line), line);
+            }
+        }
+    }
+
+    let status = child
+        .wait()
+        .map_err(|e| format!("Failed to wait for the flashing tool: {}", e))?;
+    let stderr = stderr_thread.join().unwrap_or_default();
+
+    if !status.success() {
+        return Err(format!(
+            "Flashing failed with exit code {:?}:\n{}",
+            status.code(),
+            stderr.trim()
+        ));
+    }
+    Ok(())
+}
+
+/// Stop the daemon, flash, verify (the daemon is restarted by the caller)
+fn flash(app_handle: &AppHandle, port: &str, firmware_path: &Path, daemon_running: bool) -> Result<FlashResult, String> {
+    emit_progress(app_handle, "preparing", None, "Checking firmware file...");
+    let firmware_sha256 = firmware_sha256(firmware_path)?;
+    let venv_path = crate::update::get_local_venv_path(app_handle)?;
+    let python_path = crate::update::get_python_path(&venv_path)?;
+    check_flash_tool(&python_path)?;
+
+    if daemon_running {
+        emit_progress(app_handle, "stopping_daemon", None, "Stopping daemon to free the serial port...");
+        crate::daemon::kill_daemon(&app_handle.state::<DaemonState>());
+    }
+    crate::usb::wait_for_port_release(port, PORT_RELEASE_TIMEOUT)?;
+
+    emit_progress(app_handle, "flashing", Some(0), format!("Flashing firmware on {}...", port));
+    run_flash_tool(app_handle, &python_path, port, firmware_path)?;
+
+    emit_progress(app_handle, "verifying", None, "Verifying motors...");
+    let motors = read_robot_identity(port, true)?.motors.unwrap_or_default();
+    if motors.is_empty() {
+        return Err("No motor answered after flashing".to_string());
+    }
+
+    Ok(FlashResult {
+        firmware_sha256,
+        motors,
+        daemon_restarted: false,
+    })
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Flash motor-controller firmware on the robot connected to `port`
+#[tauri::command]
+pub async fn flash_firmware(app_handle: AppHandle, port: String, firmware_path: String) -> Result<FlashResult, String> {
+    crate::kiosk::ensure_allowed("Flashing firmware")?;
+    crate::lock::ensure_unlocked("Flashing firmware")?;
+    if FLASHING.swap(true, Ordering::SeqCst) {
+        return Err("A firmware flash is already in progress".to_string());
+    }
+
+    let daemon_running = app_handle.state::<DaemonState>().process.lock().unwrap().is_some();
+    let handle = app_handle.clone();
+    let flash_port = port.clone();
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        flash(&handle, &flash_port, Path::new(&firmware_path), daemon_running)
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))
+    .and_then(|r| r);
+
+    // Give the port back to the daemon, whatever happened (if we stopped it)
+    let mut daemon_restarted = false;
+    let daemon_stopped = app_handle.state::<DaemonState>().process.lock().unwrap().is_none();
+    if daemon_running && daemon_stopped {
+        emit_progress(&app_handle, "restarting_daemon", None, "Restarting daemon...");
+        let handle = app_handle.clone();
+        let daemon_port = port.clone();
+        match tauri::async_runtime::spawn_blocking(move || {
+            crate::start_daemon(handle.clone(), handle.state::<DaemonState>(), Some(false), None, Some(daemon_port))
+        })
+        .await
+        {
+            Ok(Ok(_)) => daemon_restarted = true,
+            Ok(Err(e)) => eprintln!("[firmware] ⚠️ Failed to restart daemon: {}", e),
+            Err(e) => eprintln!("[firmware] ⚠️ Restart task failed: {}", e),
+        }
+    }
+
+    FLASHING.store(false, Ordering::SeqCst);
+
+    match result {
+        Ok(mut flash_result) => {
+            flash_result.daemon_restarted = daemon_restarted;
+            emit_progress(
+                &app_handle,
+                "done",
+                Some(100),
+                format!("Firmware flashed, {} motor(s) answering", flash_result.motors.len()),
+            );
+            Ok(flash_result)
+        }
+        Err(e) => {
+            emit_progress(&app_handle, "failed", None, e.clone());
+            Err(e)
+        }
+    }
+}
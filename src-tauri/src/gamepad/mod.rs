@@ -0,0 +1,275 @@
+/// Gamepad head control
+///
+/// Reads gamepads natively (gilrs) instead of the webview Gamepad API, which only polls
+/// while the window has focus. Mapped axes drive head yaw / pitch / roll and body yaw
+/// targets: full deflection reaches the mapping's `range_deg`, sticks at rest (inside the
+/// deadzone) bring the target back to center.
+///
+/// Targets are sent at `gamepad.rate_hz` (only when they changed) from a dedicated thread
+/// owning the gilrs context. The first connected gamepad is used.
+/// Emits `gamepad-connected` / `gamepad-disconnected` with { id, name }.
+
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::daemon_api::{DaemonClient, HeadPose, MoveTarget, BODY_YAW_LIMIT};
+use crate::local_proxy::LocalProxyState;
+use crate::settings::SettingsState;
+
+const SET_TARGET_TIMEOUT: Duration = Duration::from_millis(500);
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum GamepadAxis {
+    LeftStickX,
+    LeftStickY,
+    RightStickX,
+    RightStickY,
+    LeftZ,
+    RightZ,
+    DpadX,
+    DpadY,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum GamepadTarget {
+    HeadYaw,
+    HeadPitch,
+    HeadRoll,
+    BodyYaw,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AxisMapping {
+    pub axis: GamepadAxis,
+    pub target: GamepadTarget,
+    /// Angle reached at full deflection
+    pub range_deg: f64,
+    pub invert: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct GamepadSettings {
+    /// Axis values under this are ignored (stick drift), 0 to 0.9
+    pub deadzone: f32,
+    pub rate_hz: u32,
+    pub mappings: Vec<AxisMapping>,
+}
+
+impl Default for GamepadSettings {
+    fn default() -> Self {
+        // Right stick: head (pushing right / up looks right / up), left stick: body
+        Self {
+            deadzone: 0.15,
+            rate_hz: 30,
+            mappings: vec![
+                AxisMapping { axis: GamepadAxis::RightStickX, target: GamepadTarget::HeadYaw, range_deg: 60.0, invert: true },
+                AxisMapping { axis: GamepadAxis::RightStickY, target: GamepadTarget::HeadPitch, range_deg: 35.0, invert: true },
+                AxisMapping { axis: GamepadAxis::LeftStickX, target: GamepadTarget::BodyYaw, range_deg: 90.0, invert: true },
+            ],
+        }
+    }
+}
+
+impl GamepadSettings {
+    pub fn validate(&self) -> Result<(), String> {
+        if !(0.0..=0.9).contains(&self.deadzone) {
+            return Err(format!("Gamepad deadzone must be between 0 and 0.9: {}", self.deadzone));
+        }
+        if !(5..=100).contains(&self.rate_hz) {
+            return Err(format!("Gamepad rate must be between 5 and 100 Hz: {}", self.rate_hz));
+        }
+        if let Some(mapping) = self.mappings.iter().find(|mapping| !(0.0..=180.0).contains(&mapping.range_deg)) {
+            return Err(format!("Gamepad range must be between 0 and 180°: {}", mapping.range_deg));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct GamepadInfo {
+    pub id: usize,
+    pub name: String,
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct GamepadStatus {
+    pub active: bool,
+    pub gamepads: Vec<GamepadInfo>,
+    /// Last target sent to the daemon
+    pub target: Option<MoveTarget>,
+    pub error: Option<String>,
+}
+
+#[derive(Default)]
+pub struct GamepadState {
+    /// Set to stop the reader thread
+    stop: Mutex<Option<Arc<AtomicBool>>>,
+    status: Mutex<GamepadStatus>,
+}
+
+// ============================================================================
+// HELPER FUNCTIONS
+// ============================================================================
+
+impl GamepadAxis {
+    fn to_gilrs(self) -> gilrs::Axis {
+        match self {
+            Self::LeftStickX => gilrs::Axis::LeftStickX,
+            Self::LeftStickY => gilrs::Axis::LeftStickY,
+            Self::RightStickX => gilrs::Axis::RightStickX,
+            Self::RightStickY => gilrs::Axis::RightStickY,
+            Self::LeftZ => gilrs::Axis::LeftZ,
+            Self::RightZ => gilrs::Axis::RightZ,
+            Self::DpadX => gilrs::Axis::DPadX,
+            Self::DpadY => gilrs::Axis::DPadY,
+        }
+    }
+}
+
+/// Zero inside the deadzone, rescaled to keep the full -1..1 range outside it
+fn apply_deadzone(value: f32, deadzone: f32) -> f64 {
+    if value.abs() <= deadzone {
+        return 0.0;
+    }
+    let scaled = (value.abs() - deadzone) / (1.0 - deadzone);
+    (scaled.min(1.0) * value.signum()) as f64
+}
+
+/// Target for the current axis values of a gamepad
+fn compute_target(gamepad: &gilrs::Gamepad, settings: &GamepadSettings) -> MoveTarget {
+    let mut head = HeadPose::default();
+    let mut body_yaw = 0.0;
+    for mapping in &settings.mappings {
+        let value = apply_deadzone(gamepad.value(mapping.axis.to_gilrs()), settings.deadzone);
+        let sign = if mapping.invert { -1.0 } else { 1.0 };
+        let angle = sign * value * mapping.range_deg.to_radians();
+        match mapping.target {
+            GamepadTarget::HeadYaw => head.yaw += angle,
+            GamepadTarget::HeadPitch => head.pitch += angle,
+            GamepadTarget::HeadRoll => head.roll += angle,
+            GamepadTarget::BodyYaw => body_yaw += angle,
+        }
+    }
+    MoveTarget {
+        target_head_pose: Some(head.clamped()),
+        target_body_yaw: Some(body_yaw.clamp(-BODY_YAW_LIMIT, BODY_YAW_LIMIT)),
+        ..Default::default()
+    }
+}
+
+fn connected_gamepads(gilrs: &gilrs::Gilrs) -> Vec<GamepadInfo> {
+    gilrs
+        .gamepads()
+        .map(|(id, gamepad)| GamepadInfo { id: id.into(), name: gamepad.name().to_string() })
+        .collect()
+}
+
+/// Reader thread: poll the gamepad and stream targets until `stop` is set
+fn run(app_handle: AppHandle, mut gilrs: gilrs::Gilrs, stop: Arc<AtomicBool>) {
+    let proxy = app_handle.state::<Arc<LocalProxyState>>().inner().clone();
+    let client = tauri::async_runtime::block_on(DaemonClient::for_proxy(&proxy)).with_timeout(SET_TARGET_TIMEOUT);
+    let state = app_handle.state::<GamepadState>();
+    let mut last_sent: Option<(Option<HeadPose>, Option<f64>)> = None;
+
+    while !stop.load(Ordering::SeqCst) {
+        let started = Instant::now();
+        let settings = app_handle.state::<SettingsState>().get().gamepad;
+
+        // Events update gilrs' view of the axes, connections are reported to the frontend
+        while let Some(event) = gilrs.next_event() {
+            let info = GamepadInfo { id: event.id.into(), name: gilrs.gamepad(event.id).name().to_string() };
+            match event.event {
+                gilrs::EventType::Connected => {
+                    println!("[gamepad] 🎮 Connected: {}", info.name);
+                    let _ = app_handle.emit("gamepad-connected", &info);
+                }
+                gilrs::EventType::Disconnected => {
+                    println!("[gamepad] 🔌 Disconnected: {}", info.name);
+                    let _ = app_handle.emit("gamepad-disconnected", &info);
+                }
+                _ => {}
+            }
+        }
+        state.status.lock().unwrap().gamepads = connected_gamepads(&gilrs);
+
+        if let Some((_, gamepad)) = gilrs.gamepads().next() {
+            let target = compute_target(&gamepad, &settings);
+            let key = (target.target_head_pose, target.target_body_yaw);
+            if last_sent != Some(key) {
+                let result = tauri::async_runtime::block_on(client.set_target(&target));
+                let mut status = state.status.lock().unwrap();
+                match result {
+                    Ok(()) => {
+                        last_sent = Some(key);
+                        status.target = Some(target);
+                        status.error = None;
+                    }
+                    Err(e) => {
+                        // Logged once per outage, retried at the next tick
+                        if status.error.is_none() {
+                            eprintln!("[gamepad] ⚠️ {}", e);
+                        }
+                        status.error = Some(e);
+                    }
+                }
+            }
+        } else {
+            // Next gamepad starts from its own stick positions
+            last_sent = None;
+        }
+
+        let period = Duration::from_secs_f64(1.0 / settings.rate_hz.max(1) as f64);
+        std::thread::sleep(period.saturating_sub(started.elapsed()));
+    }
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Start streaming gamepad targets to the daemon
+#[tauri::command]
+pub fn start_gamepad_control(app_handle: AppHandle, state: State<GamepadState>) -> Result<GamepadStatus, String> {
+    let mut stop_flag = state.stop.lock().unwrap();
+    if stop_flag.is_none() {
+        let gilrs = gilrs::Gilrs::new().map_err(|e| format!("Gamepad input unavailable: {}", e))?;
+        let stop = Arc::new(AtomicBool::new(false));
+        let handle = app_handle.clone();
+        let thread_stop = stop.clone();
+        // Created here so a missing backend is reported to the caller
+        std::thread::Builder::new()
+            .name("gamepad".to_string())
+            .spawn(move || run(handle, gilrs, thread_stop))
+            .map_err(|e| format!("Failed to start gamepad thread: {}", e))?;
+        *stop_flag = Some(stop);
+        *state.status.lock().unwrap() = GamepadStatus { active: true, ..Default::default() };
+        println!("[gamepad] 🎮 Gamepad control started");
+    }
+    drop(stop_flag);
+    Ok(state.status.lock().unwrap().clone())
+}
+
+#[tauri::command]
+pub fn stop_gamepad_control(state: State<GamepadState>) {
+    if let Some(stop) = state.stop.lock().unwrap().take() {
+        stop.store(true, Ordering::SeqCst);
+        state.status.lock().unwrap().active = false;
+        println!("[gamepad] ⏹️ Gamepad control stopped");
+    }
+}
+
+/// Connected gamepads, last target sent and last error
+#[tauri::command]
+pub fn get_gamepad_status(state: State<GamepadState>) -> GamepadStatus {
+    state.status.lock().unwrap().clone()
+}
@@ -0,0 +1,166 @@
+/// Robot HUD
+///
+/// Small always-on-top window (see `window::HUD`) for presenters running other software
+/// fullscreen: connection state, hottest motor temperature, battery level when the daemon
+/// reports one, and an emergency stop button (the `emergency_stop` command).
+///
+/// While the HUD is open, its status is refreshed every `REFRESH_INTERVAL` and emitted as
+/// `hud-status` when it changed, right away on `connection-state-changed`. The motor
+/// health monitor is started for it if nothing else runs it, and stopped with the HUD.
+
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Emitter, EventId, Listener, Manager, State};
+
+use crate::connection::{ConnectionManager, ConnectionState};
+use crate::daemon_api::DaemonClient;
+use crate::local_proxy::LocalProxyState;
+use crate::motor_health::{AlertLevel, MotorHealthState};
+
+const REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+const STATUS_TIMEOUT: Duration = Duration::from_millis(800);
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct HudStatus {
+    pub connection: ConnectionState,
+    pub hottest_motor: Option<String>,
+    /// °C
+    pub max_temperature: Option<f64>,
+    pub overheating: Option<AlertLevel>,
+    /// Percent, wireless robot only
+    pub battery_percent: Option<f64>,
+}
+
+struct HudSession {
+    task: JoinHandle<()>,
+    listener: EventId,
+    /// The motor monitor was started for the HUD
+    started_monitor: bool,
+}
+
+#[derive(Default)]
+pub struct HudState {
+    session: Mutex<Option<HudSession>>,
+}
+
+// ============================================================================
+// HELPER FUNCTIONS
+// ============================================================================
+
+async fn status(app_handle: &AppHandle, client: &DaemonClient) -> HudStatus {
+    let connection = app_handle.state::<ConnectionManager>().get();
+    let hottest = crate::motor_health::get_motor_health(app_handle.state())
+        .into_iter()
+        .filter_map(|motor| Some((motor.latest.temperature?, motor)))
+        .max_by(|(a, _), (b, _)| a.total_cmp(b));
+    let battery = match connection {
+        ConnectionState::Ready { .. } | ConnectionState::WifiMode { .. } => {
+            client
+                .daemon_status()
+                .await
+                .ok()
+                .as_ref()
+                .and_then(crate::power::parse_battery)
+                .map(|power| power.percent)
+        }
+        _ => None,
+    };
+    HudStatus {
+        connection,
+        hottest_motor: hottest.as_ref().map(|(_, motor)| motor.motor.clone()),
+        max_temperature: hottest.as_ref().map(|(temperature, _)| *temperature),
+        overheating: hottest.and_then(|(_, motor)| motor.overheating),
+        battery_percent: battery,
+    }
+}
+
+/// Emit the status while the HUD is open (first status right away)
+async fn run(app_handle: AppHandle) {
+    let proxy = app_handle.state::<Arc<LocalProxyState>>();
+    let client = DaemonClient::for_proxy(&proxy).await.with_timeout(STATUS_TIMEOUT);
+    let mut last: Option<HudStatus> = None;
+    loop {
+        let current = status(&app_handle, &client).await;
+        if last.as_ref() != Some(&current) {
+            let _ = app_handle.emit("hud-status", &current);
+            last = Some(current);
+        }
+        tokio::time::sleep(REFRESH_INTERVAL).await;
+    }
+}
+
+/// HUD window closed: stop refreshing (and the motor monitor if the HUD started it)
+fn close(app_handle: &AppHandle) {
+    let Some(session) = app_handle.state::<HudState>().session.lock().unwrap().take() else {
+        return;
+    };
+    session.task.abort();
+    app_handle.unlisten(session.listener);
+    if session.started_monitor {
+        crate::motor_health::stop_motor_health_monitor(app_handle.state());
+    }
+    println!("[hud] ⏹️ HUD closed");
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Open (or focus) the always-on-top HUD window
+#[tauri::command]
+pub fn open_hud_window(app_handle: AppHandle) -> Result<(), String> {
+    let Some(window) = crate::window::open_aux(&app_handle, &crate::window::HUD)? else {
+        return Ok(());
+    };
+
+    let monitor = app_handle.state::<MotorHealthState>();
+    let started_monitor = !monitor.is_monitoring();
+    if started_monitor {
+        crate::motor_health::start_motor_health_monitor(app_handle.clone(), monitor, None);
+    }
+
+    let handle = app_handle.clone();
+    let listener = app_handle.listen_any("connection-state-changed", move |_| {
+        let handle = handle.clone();
+        tauri::async_runtime::spawn(async move {
+            let proxy = handle.state::<Arc<LocalProxyState>>();
+            let client = DaemonClient::for_proxy(&proxy).await.with_timeout(STATUS_TIMEOUT);
+            let _ = handle.emit("hud-status", status(&handle, &client).await);
+        });
+    });
+    let task = tauri::async_runtime::spawn(run(app_handle.clone()));
+    let previous = app_handle.state::<HudState>().session.lock().unwrap().replace(HudSession {
+        task,
+        listener,
+        started_monitor,
+    });
+    if let Some(previous) = previous {
+        previous.task.abort();
+        app_handle.unlisten(previous.listener);
+    }
+
+    let handle = app_handle.clone();
+    window.on_window_event(move |event| {
+        if let tauri::WindowEvent::Destroyed = event {
+            close(&handle);
+        }
+    });
+    println!("[hud] 📟 HUD opened");
+    Ok(())
+}
+
+/// Current HUD status (also emitted as `hud-status`)
+#[tauri::command]
+pub async fn get_hud_status(
+    app_handle: AppHandle,
+    proxy: State<'_, Arc<LocalProxyState>>,
+) -> Result<HudStatus, String> {
+    let client = DaemonClient::for_proxy(&proxy).await.with_timeout(STATUS_TIMEOUT);
+    Ok(status(&app_handle, &client).await)
+}
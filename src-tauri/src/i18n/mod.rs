@@ -0,0 +1,272 @@
+/// Localization module
+///
+/// String catalogs: flat JSON objects (key -> text, `{name}` placeholders). English and
+/// French are bundled (`src-tauri/locales`); catalogs downloaded with
+/// `download_locale_catalog` are saved to `<app data>/locales/<lang>.json` and override or
+/// add languages.
+///
+/// The language is the `language` setting, else the OS locale. Backend strings go through
+/// `tr` / `tr_with` (missing keys fall back to English, then to the key itself) and the
+/// frontend gets the same catalog from `get_locale_strings`. Changing the language emits
+/// `locale-changed` with the new catalog.
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+use tauri::{AppHandle, Emitter, State};
+
+use crate::settings::SettingsState;
+
+const FALLBACK_LANGUAGE: &str = "en";
+const BUNDLED: &[(&str, &str)] = &[
+    ("en", include_str!("../../locales/en.json")),
+    ("fr", include_str!("../../locales/fr.json")),
+];
+const DOWNLOAD_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+
+type Catalog = BTreeMap<String, String>;
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+#[derive(Debug, Serialize, Clone)]
+pub struct LocaleStrings {
+    pub language: String,
+    /// Every key, English where the language has no translation
+    pub strings: Catalog,
+}
+
+struct Localization {
+    language: String,
+    /// `<app data>/locales`
+    dir: Option<PathBuf>,
+    downloaded: BTreeMap<String, Catalog>,
+    /// Catalog of `language`, merged over English
+    current: Catalog,
+}
+
+static LOCALIZATION: RwLock<Localization> = RwLock::new(Localization {
+    language: String::new(),
+    dir: None,
+    downloaded: BTreeMap::new(),
+    current: BTreeMap::new(),
+});
+
+// ============================================================================
+// HELPER FUNCTIONS
+// ============================================================================
+
+fn parse_catalog(content: &str) -> Result<Catalog, String> {
+    serde_json::from_str(content).map_err(|e| format!("Invalid locale catalog: {}", e))
+}
+
+/// Primary language subtag: "fr_FR.UTF-8" / "fr-FR" -> "fr"
+fn normalize(language: &str) -> Result<String, String> {
+    let primary = language
+        .split(['-', '_', '.', '@'])
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    if (2..=3).contains(&primary.len()) && primary.chars().all(|c| c.is_ascii_lowercase()) {
+        Ok(primary)
+    } else {
+        Err(format!("Invalid language code: {}", language))
+    }
+}
+
+/// Bundled catalog, then the downloaded one, over English
+fn merged_catalog(language: &str, downloaded: &BTreeMap<String, Catalog>) -> Catalog {
+    let mut catalog = Catalog::new();
+    for lang in [FALLBACK_LANGUAGE, language] {
+        if let Some((_, content)) = BUNDLED.iter().find(|(bundled, _)| *bundled == lang) {
+            catalog.extend(parse_catalog(content).unwrap_or_default());
+        }
+        if let Some(strings) = downloaded.get(lang) {
+            catalog.extend(strings.clone());
+        }
+    }
+    catalog
+}
+
+fn load_downloaded(dir: &Path) -> BTreeMap<String, Catalog> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return BTreeMap::new();
+    };
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            let language = normalize(path.file_stem()?.to_str()?).ok()?;
+            match std::fs::read_to_string(&path).map_err(|e| e.to_string()).and_then(|c| parse_catalog(&c)) {
+                Ok(catalog) => Some((language, catalog)),
+                Err(e) => {
+                    eprintln!("[i18n] ⚠️ Ignoring {:?}: {}", path, e);
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+/// Language of the OS user interface ("en" if unknown)
+pub fn system_locale() -> String {
+    #[cfg(target_os = "macos")]
+    let locale = std::process::Command::new("defaults")
+        .args(["read", "-g", "AppleLocale"])
+        .output()
+        .ok()
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string());
+    #[cfg(target_os = "windows")]
+    let locale = std::process::Command::new("powershell")
+        .args(["-NoProfile", "-Command", "(Get-UICulture).Name"])
+        .output()
+        .ok()
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string());
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    let locale = ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|value| !value.is_empty() && value != "C" && value != "POSIX");
+
+    locale
+        .and_then(|locale| normalize(&locale).ok())
+        .unwrap_or_else(|| FALLBACK_LANGUAGE.to_string())
+}
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// Load downloaded catalogs and select the language (call once in setup)
+pub fn init(app_data_dir: &Path, language: Option<&str>) {
+    let dir = app_data_dir.join("locales");
+    let mut localization = LOCALIZATION.write().unwrap();
+    localization.downloaded = load_downloaded(&dir);
+    localization.dir = Some(dir);
+    drop(localization);
+    set_language(language);
+}
+
+/// Select the language (None = OS locale)
+pub fn set_language(language: Option<&str>) {
+    let language = language
+        .and_then(|language| normalize(language).ok())
+        .unwrap_or_else(system_locale);
+    let mut localization = LOCALIZATION.write().unwrap();
+    localization.current = merged_catalog(&language, &localization.downloaded);
+    println!("[i18n] 🌐 Language: {}", language);
+    localization.language = language;
+}
+
+pub fn locale_strings() -> LocaleStrings {
+    let localization = LOCALIZATION.read().unwrap();
+    LocaleStrings {
+        language: localization.language.clone(),
+        strings: localization.current.clone(),
+    }
+}
+
+/// Text of `key` in the current language
+pub fn tr(key: &str) -> String {
+    tr_with(key, &[])
+}
+
+/// Text of `key` in the current language, with `{name}` placeholders replaced
+pub fn tr_with(key: &str, args: &[(&str, &str)]) -> String {
+    let localization = LOCALIZATION.read().unwrap();
+    let mut text = localization.current.get(key).cloned().unwrap_or_else(|| key.to_string());
+    for (name, value) in args {
+        text = text.replace(&format!("{{{}}}", name), value);
+    }
+    text
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Catalog of a language (None = current language), English where untranslated
+#[tauri::command]
+pub fn get_locale_strings(lang: Option<String>) -> Result<LocaleStrings, String> {
+    let Some(lang) = lang else {
+        return Ok(locale_strings());
+    };
+    let language = normalize(&lang)?;
+    let strings = merged_catalog(&language, &LOCALIZATION.read().unwrap().downloaded);
+    Ok(LocaleStrings { language, strings })
+}
+
+/// Language of the OS user interface, e.g. "fr"
+#[tauri::command]
+pub fn get_system_locale() -> String {
+    system_locale()
+}
+
+/// Languages with a bundled or downloaded catalog
+#[tauri::command]
+pub fn get_available_locales() -> Vec<String> {
+    let localization = LOCALIZATION.read().unwrap();
+    let mut languages: Vec<String> = BUNDLED.iter().map(|(lang, _)| lang.to_string()).collect();
+    languages.extend(localization.downloaded.keys().cloned());
+    languages.sort();
+    languages.dedup();
+    languages
+}
+
+/// Persist and apply the language (None = follow the OS), emits `locale-changed`
+#[tauri::command]
+pub fn set_language_preference(
+    app_handle: AppHandle,
+    settings: State<SettingsState>,
+    language: Option<String>,
+) -> Result<LocaleStrings, String> {
+    let language = language.map(|language| normalize(&language)).transpose()?;
+    settings.update(|settings| settings.language = language.clone())?;
+    set_language(language.as_deref());
+    let strings = locale_strings();
+    let _ = app_handle.emit("locale-changed", &strings);
+    Ok(strings)
+}
+
+/// Download a catalog (JSON object of strings) for a language and save it next to the
+/// bundled ones; returns the number of strings
+#[tauri::command]
+pub async fn download_locale_catalog(app_handle: AppHandle, lang: String, url: String) -> Result<usize, String> {
+    let language = normalize(&lang)?;
+    if !url.starts_with("https://") {
+        return Err(format!("Catalog URL must be https: {}", url));
+    }
+    let response = reqwest::Client::new()
+        .get(&url)
+        .timeout(DOWNLOAD_TIMEOUT)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to download the catalog: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("Catalog download returned {}", response.status()));
+    }
+    let content = response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to download the catalog: {}", e))?;
+    let catalog = parse_catalog(&content)?;
+    let count = catalog.len();
+
+    let dir = LOCALIZATION.read().unwrap().dir.clone().ok_or("Localization not initialized")?;
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {:?}: {}", dir, e))?;
+    let path = dir.join(format!("{}.json", language));
+    let pretty = serde_json::to_string_pretty(&catalog).map_err(|e| e.to_string())?;
+    std::fs::write(&path, pretty).map_err(|e| format!("Failed to save {:?}: {}", path, e))?;
+    println!("[i18n] 📥 Catalog {} saved ({} strings)", language, count);
+
+    let mut localization = LOCALIZATION.write().unwrap();
+    localization.downloaded.insert(language.clone(), catalog);
+    if localization.language == language {
+        localization.current = merged_catalog(&language, &localization.downloaded);
+        drop(localization);
+        let _ = app_handle.emit("locale-changed", locale_strings());
+    }
+    Ok(count)
+}
@@ -0,0 +1,136 @@
+/// HID button boxes (Stream Deck and similar, hidapi)
+///
+/// Input reports are read as one byte per button: a byte going from 0 to non-zero is a
+/// press of button `index - key_offset`. Without a configured offset it is guessed: 1 for
+/// the original Stream Deck and the Mini, 4 for the other Elgato models (MK.2, XL,
+/// Plus...), 0 for anything else. Unplugged devices are reopened every RETRY_INTERVAL.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+use super::{DeviceKind, HidSource, InputDevice, InputState, Trigger};
+
+const ELGATO_VENDOR_ID: u16 = 0x0fd9;
+/// Stream Deck original (v1), Mini and Mini (2022): key states right after the report id
+const ELGATO_SHORT_HEADER: &[u16] = &[0x0060, 0x0063, 0x0090];
+const READ_TIMEOUT_MS: i32 = 200;
+const RETRY_INTERVAL: Duration = Duration::from_secs(2);
+const REPORT_SIZE: usize = 512;
+
+fn key_offset(source: &HidSource) -> usize {
+    source.key_offset.unwrap_or(match (source.vendor_id, source.product_id) {
+        (ELGATO_VENDOR_ID, product_id) if ELGATO_SHORT_HEADER.contains(&product_id) => 1,
+        (ELGATO_VENDOR_ID, _) => 4,
+        _ => 0,
+    })
+}
+
+fn device_name(product: Option<&str>, vendor_id: u16, product_id: u16) -> String {
+    match product.map(str::trim) {
+        Some(product) if !product.is_empty() => product.to_string(),
+        _ => format!("HID {:04x}:{:04x}", vendor_id, product_id),
+    }
+}
+
+/// HID devices currently plugged in (one entry per vendor / product id)
+pub fn devices() -> Result<Vec<InputDevice>, String> {
+    let api = hidapi::HidApi::new().map_err(|e| format!("HID unavailable: {}", e))?;
+    let mut devices: Vec<InputDevice> = Vec::new();
+    for info in api.device_list() {
+        let (vendor_id, product_id) = (info.vendor_id(), info.product_id());
+        let known = devices
+            .iter()
+            .any(|device| device.vendor_id == Some(vendor_id) && device.product_id == Some(product_id));
+        if vendor_id == 0 || known {
+            continue;
+        }
+        devices.push(InputDevice {
+            kind: DeviceKind::Hid,
+            name: device_name(info.product_string(), vendor_id, product_id),
+            vendor_id: Some(vendor_id),
+            product_id: Some(product_id),
+            connected: false,
+        });
+    }
+    Ok(devices)
+}
+
+fn open(source: &HidSource) -> Result<(hidapi::HidDevice, String), String> {
+    let api = hidapi::HidApi::new().map_err(|e| format!("HID unavailable: {}", e))?;
+    let device = api
+        .open(source.vendor_id, source.product_id)
+        .map_err(|e| format!("Failed to open HID device {:04x}:{:04x}: {}", source.vendor_id, source.product_id, e))?;
+    let product = device.get_product_string().ok().flatten();
+    let name = device_name(product.as_deref(), source.vendor_id, source.product_id);
+    Ok((device, name))
+}
+
+/// Sleep up to `duration`, false if `stop` was set meanwhile
+fn wait(duration: Duration, stop: &AtomicBool) -> bool {
+    let step = Duration::from_millis(READ_TIMEOUT_MS as u64);
+    let mut waited = Duration::ZERO;
+    while waited < duration && !stop.load(Ordering::SeqCst) {
+        std::thread::sleep(step);
+        waited += step;
+    }
+    !stop.load(Ordering::SeqCst)
+}
+
+/// Listener thread: read the device's buttons until `stop` is set
+pub fn run(app_handle: AppHandle, source: HidSource, stop: Arc<AtomicBool>) {
+    let state = app_handle.state::<InputState>();
+    let offset = key_offset(&source);
+    let mut reported = false;
+
+    while !stop.load(Ordering::SeqCst) {
+        let (device, name) = match open(&source) {
+            Ok(opened) => opened,
+            Err(e) => {
+                // Reported once per outage, retried quietly
+                if !reported {
+                    state.set_error(e);
+                    reported = true;
+                }
+                if !wait(RETRY_INTERVAL, &stop) {
+                    return;
+                }
+                continue;
+            }
+        };
+        println!("[input] 🎛️ HID device connected: {}", name);
+        state.set_connected(&name, true);
+        reported = false;
+
+        let mut buf = [0u8; REPORT_SIZE];
+        let mut pressed: Vec<bool> = Vec::new();
+        while !stop.load(Ordering::SeqCst) {
+            let len = match device.read_timeout(&mut buf, READ_TIMEOUT_MS) {
+                Ok(0) => continue,
+                Ok(len) => len,
+                Err(e) => {
+                    eprintln!("[input] 🔌 HID device {} lost: {}", name, e);
+                    state.set_connected(&name, false);
+                    break;
+                }
+            };
+            let keys = buf.get(offset..len).unwrap_or_default();
+            pressed.resize(keys.len(), false);
+            for (button, (&value, was_pressed)) in keys.iter().zip(pressed.iter_mut()).enumerate() {
+                let is_pressed = value != 0;
+                if is_pressed && !*was_pressed {
+                    super::fire(
+                        &app_handle,
+                        Trigger::HidButton {
+                            vendor_id: source.vendor_id,
+                            product_id: source.product_id,
+                            button: button as u16,
+                        },
+                    );
+                }
+                *was_pressed = is_pressed;
+            }
+        }
+    }
+}
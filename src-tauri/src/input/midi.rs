@@ -0,0 +1,127 @@
+/// MIDI controllers (midir)
+///
+/// Note On (velocity > 0) fires a note trigger. A Control Change fires when its value
+/// crosses 64 upwards, so pads and footswitches sending 127 then 0 fire once per press.
+/// Ports are rescanned every RESCAN_INTERVAL: controllers plugged in later are picked up.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+
+use super::{DeviceKind, InputDevice, InputState, Trigger};
+
+const CLIENT_NAME: &str = "Reachy Mini Control";
+const RESCAN_INTERVAL: Duration = Duration::from_secs(2);
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+fn new_input() -> Result<midir::MidiInput, String> {
+    let mut input = midir::MidiInput::new(CLIENT_NAME).map_err(|e| format!("MIDI unavailable: {}", e))?;
+    input.ignore(midir::Ignore::All);
+    Ok(input)
+}
+
+/// Trigger for a MIDI message, if it is a press (`controls` keeps the last CC values)
+fn parse(message: &[u8], controls: &mut HashMap<(u8, u8), u8>) -> Option<Trigger> {
+    let [status, data1, data2, ..] = *message else {
+        return None;
+    };
+    let channel = (status & 0x0F) + 1;
+    match status & 0xF0 {
+        0x90 if data2 > 0 => Some(Trigger::MidiNote { channel, note: data1 }),
+        0xB0 => {
+            let previous = controls.insert((channel, data1), data2).unwrap_or(0);
+            (previous < 64 && data2 >= 64).then_some(Trigger::MidiControl { channel, controller: data1 })
+        }
+        _ => None,
+    }
+}
+
+/// Input ports currently available
+pub fn devices() -> Result<Vec<InputDevice>, String> {
+    let input = new_input()?;
+    Ok(input
+        .ports()
+        .iter()
+        .filter_map(|port| input.port_name(port).ok())
+        .map(|name| InputDevice {
+            kind: DeviceKind::Midi,
+            name,
+            vendor_id: None,
+            product_id: None,
+            connected: false,
+        })
+        .collect())
+}
+
+/// Listener thread: keep the wanted ports (all if empty) connected until `stop` is set
+pub fn run(app_handle: AppHandle, wanted: Vec<String>, stop: Arc<AtomicBool>) {
+    let state = app_handle.state::<InputState>();
+    let mut connections: HashMap<String, midir::MidiInputConnection<()>> = HashMap::new();
+    let mut last_scan: Option<Instant> = None;
+    // Ports that failed to open are reported once, retried quietly
+    let mut failed: Vec<String> = Vec::new();
+
+    while !stop.load(Ordering::SeqCst) {
+        if last_scan.is_none_or(|scanned| scanned.elapsed() >= RESCAN_INTERVAL) {
+            last_scan = Some(Instant::now());
+            let available: Vec<String> = devices().unwrap_or_default().into_iter().map(|device| device.name).collect();
+
+            // Unplugged ports: drop their connection
+            connections.retain(|name, _| {
+                let present = available.contains(name);
+                if !present {
+                    println!("[input] 🔌 MIDI port gone: {}", name);
+                    state.set_connected(name, false);
+                }
+                present
+            });
+
+            let new_ports: Vec<&String> = available
+                .iter()
+                .filter(|name| wanted.is_empty() || wanted.contains(name))
+                .filter(|name| !connections.contains_key(*name))
+                .collect();
+            for name in new_ports {
+                match connect(&app_handle, name) {
+                    Ok(connection) => {
+                        println!("[input] 🎹 MIDI port connected: {}", name);
+                        state.set_connected(name, true);
+                        connections.insert(name.clone(), connection);
+                        failed.retain(|other| other != name);
+                    }
+                    Err(e) if !failed.contains(name) => {
+                        state.set_error(e);
+                        failed.push(name.clone());
+                    }
+                    Err(_) => {}
+                }
+            }
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
+
+fn connect(app_handle: &AppHandle, name: &str) -> Result<midir::MidiInputConnection<()>, String> {
+    let input = new_input()?;
+    let port = input
+        .ports()
+        .into_iter()
+        .find(|port| input.port_name(port).ok().as_deref() == Some(name))
+        .ok_or_else(|| format!("MIDI port not found: {}", name))?;
+    let handle = app_handle.clone();
+    let mut controls = HashMap::new();
+    input
+        .connect(
+            &port,
+            "reachy-mini-input",
+            move |_, message, _| {
+                if let Some(trigger) = parse(message, &mut controls) {
+                    super::fire(&handle, trigger);
+                }
+            },
+            (),
+        )
+        .map_err(|e| format!("Failed to open MIDI port {}: {}", name, e))
+}
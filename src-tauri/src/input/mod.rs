@@ -0,0 +1,424 @@
+/// Live-cue controllers (MIDI, Stream Deck and other HID button boxes)
+///
+/// Performers map controller buttons to robot actions (play a motion, start / stop an
+/// app, emergency stop) so they can cue the robot without touching the laptop. Sources
+/// (MIDI ports, HID devices) and mappings are saved in `<app data>/input-integrations.json`
+/// and listened to while `enabled` is on, from one thread per source (see midi, hid).
+///
+/// Every trigger, mapped or not, is emitted as `input-trigger` (InputTriggerEvent) so the
+/// mapping editor can "learn" a button by waiting for the next one. A mapping whose
+/// action is still running ignores new presses, except the emergency stop.
+
+pub mod hid;
+pub mod midi;
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::daemon_api::DaemonClient;
+use crate::local_proxy::LocalProxyState;
+
+const INPUT_FILE: &str = "input-integrations.json";
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+/// A button press on a controller
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Trigger {
+    /// Note On, channel 1-16
+    MidiNote { channel: u8, note: u8 },
+    /// Control Change crossing 64 upwards, channel 1-16
+    MidiControl { channel: u8, controller: u8 },
+    /// Button index from 0 (Stream Deck keys in reading order)
+    HidButton { vendor_id: u16, product_id: u16, button: u16 },
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum InputAction {
+    /// Recorded move, e.g. dataset "pollen-robotics/reachy-mini-emotions-library", name "wave"
+    PlayMotion { dataset: String, name: String },
+    /// Start the app, or stop it if it is the one running
+    ToggleApp { name: String },
+    EmergencyStop,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InputMapping {
+    pub id: String,
+    pub label: String,
+    pub trigger: Trigger,
+    pub action: InputAction,
+}
+
+/// A mapping as created or edited in the UI (id None = new)
+#[derive(Debug, Deserialize, Clone)]
+pub struct MappingDraft {
+    #[serde(default)]
+    pub id: Option<String>,
+    #[serde(default)]
+    pub label: String,
+    pub trigger: Trigger,
+    pub action: InputAction,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct HidSource {
+    pub vendor_id: u16,
+    pub product_id: u16,
+    /// First report byte holding a button state, None = guessed (see hid)
+    #[serde(default)]
+    pub key_offset: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct InputConfig {
+    pub enabled: bool,
+    /// MIDI input port names listened to (empty = every port)
+    pub midi_ports: Vec<String>,
+    pub hid_devices: Vec<HidSource>,
+    pub mappings: Vec<InputMapping>,
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DeviceKind {
+    Midi,
+    Hid,
+}
+
+/// A controller that can be listened to
+#[derive(Debug, Serialize, Clone)]
+pub struct InputDevice {
+    pub kind: DeviceKind,
+    pub name: String,
+    pub vendor_id: Option<u16>,
+    pub product_id: Option<u16>,
+    /// Listened to right now
+    pub connected: bool,
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct InputStatus {
+    pub listening: bool,
+    /// Names of the MIDI ports and HID devices currently open
+    pub connected: Vec<String>,
+    pub last_error: Option<String>,
+}
+
+/// Payload of the `input-trigger` event
+#[derive(Debug, Serialize, Clone)]
+pub struct InputTriggerEvent {
+    pub trigger: Trigger,
+    /// Mapping run for it, None if unmapped (or busy)
+    pub mapping_id: Option<String>,
+}
+
+pub struct InputState {
+    config: Mutex<InputConfig>,
+    path: Mutex<Option<PathBuf>>,
+    status: Mutex<InputStatus>,
+    /// Set to stop the current listener threads
+    stop: Mutex<Option<Arc<AtomicBool>>>,
+    /// Mappings whose action is running
+    busy: Mutex<HashSet<String>>,
+}
+
+// ============================================================================
+// HELPER FUNCTIONS
+// ============================================================================
+
+fn validate(draft: &MappingDraft) -> Result<(), String> {
+    match &draft.trigger {
+        Trigger::MidiNote { channel, note } if !(1..=16).contains(channel) || *note > 127 => {
+            return Err("Invalid MIDI note (channel 1-16, note 0-127)".to_string());
+        }
+        Trigger::MidiControl { channel, controller } if !(1..=16).contains(channel) || *controller > 127 => {
+            return Err("Invalid MIDI control (channel 1-16, controller 0-127)".to_string());
+        }
+        _ => {}
+    }
+    match &draft.action {
+        InputAction::PlayMotion { dataset, name } if dataset.trim().is_empty() || name.trim().is_empty() => {
+            Err("Choose the motion to play".to_string())
+        }
+        InputAction::ToggleApp { name } if name.trim().is_empty() => Err("Choose the app to toggle".to_string()),
+        _ => Ok(()),
+    }
+}
+
+fn label_for(action: &InputAction) -> String {
+    match action {
+        InputAction::PlayMotion { name, .. } => format!("Play {}", name),
+        InputAction::ToggleApp { name } => format!("Toggle {}", name),
+        InputAction::EmergencyStop => "Emergency stop".to_string(),
+    }
+}
+
+impl InputState {
+    pub fn new() -> Self {
+        Self {
+            config: Mutex::new(InputConfig::default()),
+            path: Mutex::new(None),
+            status: Mutex::new(InputStatus::default()),
+            stop: Mutex::new(None),
+            busy: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// Load sources and mappings from the app data directory (none if missing or corrupted)
+    pub fn load(&self, app_data_dir: &Path) {
+        let path = app_data_dir.join(INPUT_FILE);
+        let config = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        *self.config.lock().unwrap() = config;
+        *self.path.lock().unwrap() = Some(path);
+    }
+
+    pub fn config(&self) -> InputConfig {
+        self.config.lock().unwrap().clone()
+    }
+
+    /// Apply a change and persist it
+    fn update<T>(&self, change: impl FnOnce(&mut InputConfig) -> Result<T, String>) -> Result<T, String> {
+        let mut config = self.config.lock().unwrap();
+        let result = change(&mut config)?;
+
+        if let Some(path) = self.path.lock().unwrap().as_ref() {
+            let content = serde_json::to_string_pretty(&*config)
+                .map_err(|e| format!("Failed to serialize input mappings: {}", e))?;
+            std::fs::write(path, content).map_err(|e| format!("Failed to write input mappings: {}", e))?;
+        }
+
+        Ok(result)
+    }
+
+    /// Record a source opened (true) or closed (false) by a listener thread
+    pub(crate) fn set_connected(&self, name: &str, connected: bool) {
+        let mut status = self.status.lock().unwrap();
+        status.connected.retain(|other| other != name);
+        if connected {
+            status.connected.push(name.to_string());
+        }
+    }
+
+    pub(crate) fn set_error(&self, error: String) {
+        eprintln!("[input] ⚠️ {}", error);
+        self.status.lock().unwrap().last_error = Some(error);
+    }
+}
+
+impl Default for InputState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+async fn perform(app_handle: &AppHandle, action: &InputAction) -> Result<(), String> {
+    match action {
+        InputAction::EmergencyStop => {
+            let result = crate::emergency::stop(app_handle).await;
+            if result.stopped {
+                Ok(())
+            } else {
+                Err(format!("Emergency stop failed: {}", result.errors.join("; ")))
+            }
+        }
+        InputAction::PlayMotion { dataset, name } => {
+            let params = serde_json::json!({ "dataset": dataset, "name": name });
+            crate::automation::dispatch(app_handle, "play_motion", &params).await.map(|_| ())
+        }
+        InputAction::ToggleApp { name } => {
+            let proxy = app_handle.state::<Arc<LocalProxyState>>();
+            let client = DaemonClient::for_proxy(&proxy).await;
+            let running = client.running_app().await.as_deref() == Some(name.as_str());
+            let params = serde_json::json!({ "name": name });
+            let action = if running { "stop_app" } else { "run_app" };
+            crate::automation::dispatch(app_handle, action, &params).await.map(|_| ())
+        }
+    }
+}
+
+/// Run the mapping of a trigger, if any (called from the listener threads)
+pub(crate) fn fire(app_handle: &AppHandle, trigger: Trigger) {
+    let state = app_handle.state::<InputState>();
+    let mapping = state.config.lock().unwrap().mappings.iter().find(|mapping| mapping.trigger == trigger).cloned();
+    let mapping = mapping.filter(|mapping| {
+        mapping.action == InputAction::EmergencyStop || state.busy.lock().unwrap().insert(mapping.id.clone())
+    });
+    let _ = app_handle.emit(
+        "input-trigger",
+        InputTriggerEvent {
+            trigger,
+            mapping_id: mapping.as_ref().map(|mapping| mapping.id.clone()),
+        },
+    );
+    let Some(mapping) = mapping else {
+        return;
+    };
+
+    println!("[input] 🎛️ {:?} -> {}", trigger, mapping.label);
+    let handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        let result = perform(&handle, &mapping.action).await;
+        let state = handle.state::<InputState>();
+        state.busy.lock().unwrap().remove(&mapping.id);
+        if let Err(e) = result {
+            state.set_error(format!("{}: {}", mapping.label, e));
+        }
+    });
+}
+
+/// Stop the listener threads, then start new ones for the saved sources if enabled
+fn restart(app_handle: &AppHandle) {
+    let state = app_handle.state::<InputState>();
+    let mut stop_flag = state.stop.lock().unwrap();
+    if let Some(stop) = stop_flag.take() {
+        stop.store(true, Ordering::SeqCst);
+    }
+    *state.status.lock().unwrap() = InputStatus::default();
+
+    let config = state.config();
+    if !config.enabled {
+        return;
+    }
+    let stop = Arc::new(AtomicBool::new(false));
+    let mut spawned = Vec::new();
+    let handle = app_handle.clone();
+    let thread_stop = stop.clone();
+    spawned.push(
+        std::thread::Builder::new()
+            .name("input-midi".to_string())
+            .spawn(move || midi::run(handle, config.midi_ports, thread_stop)),
+    );
+    for source in config.hid_devices {
+        let handle = app_handle.clone();
+        let thread_stop = stop.clone();
+        spawned.push(
+            std::thread::Builder::new()
+                .name("input-hid".to_string())
+                .spawn(move || hid::run(handle, source, thread_stop)),
+        );
+    }
+    if let Some(Err(e)) = spawned.into_iter().find(Result::is_err) {
+        state.set_error(format!("Failed to start input listener: {}", e));
+    }
+    state.status.lock().unwrap().listening = true;
+    *stop_flag = Some(stop);
+    println!("[input] 🎛️ Listening to controllers");
+}
+
+// ============================================================================
+// SETUP
+// ============================================================================
+
+/// Start listening if enabled (call once in setup, after the config is loaded)
+pub fn init(app_handle: &AppHandle) {
+    if app_handle.state::<InputState>().config().enabled {
+        restart(app_handle);
+    }
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+#[tauri::command]
+pub fn get_input_config(state: State<InputState>) -> InputConfig {
+    state.config()
+}
+
+#[tauri::command]
+pub fn get_input_status(state: State<InputState>) -> InputStatus {
+    state.status.lock().unwrap().clone()
+}
+
+/// MIDI ports and HID devices plugged in now
+#[tauri::command]
+pub async fn list_input_devices(state: State<'_, InputState>) -> Result<Vec<InputDevice>, String> {
+    // One backend missing (e.g. no ALSA) still lists the other's devices
+    let (midi, hid) = tauri::async_runtime::spawn_blocking(|| (midi::devices(), hid::devices()))
+        .await
+        .map_err(|e| format!("Failed to list input devices: {}", e))?;
+    let mut devices = match (midi, hid) {
+        (Err(e), Err(_)) => return Err(e),
+        (midi, hid) => {
+            let mut devices = midi.unwrap_or_default();
+            devices.extend(hid.unwrap_or_default());
+            devices
+        }
+    };
+    let connected = state.status.lock().unwrap().connected.clone();
+    for device in &mut devices {
+        device.connected = connected.contains(&device.name);
+    }
+    Ok(devices)
+}
+
+/// Choose the controllers listened to and turn listening on or off (applied now)
+#[tauri::command]
+pub fn set_input_sources(
+    app_handle: AppHandle,
+    state: State<InputState>,
+    enabled: bool,
+    midi_ports: Vec<String>,
+    hid_devices: Vec<HidSource>,
+) -> Result<InputStatus, String> {
+    state.update(|config| {
+        config.enabled = enabled;
+        config.midi_ports = midi_ports;
+        config.hid_devices = hid_devices;
+        Ok(())
+    })?;
+    restart(&app_handle);
+    Ok(state.status.lock().unwrap().clone())
+}
+
+/// Create or replace a mapping (one mapping per trigger)
+#[tauri::command]
+pub fn save_input_mapping(state: State<InputState>, draft: MappingDraft) -> Result<InputMapping, String> {
+    validate(&draft)?;
+    let mapping = InputMapping {
+        id: draft.id.clone().unwrap_or_else(crate::local_proxy::profiles::generate_id),
+        label: match draft.label.trim() {
+            "" => label_for(&draft.action),
+            label => label.to_string(),
+        },
+        trigger: draft.trigger,
+        action: draft.action,
+    };
+    state.update(|config| {
+        if let Some(other) = config.mappings.iter().find(|other| other.trigger == mapping.trigger && other.id != mapping.id) {
+            return Err(format!("This button is already mapped to \"{}\"", other.label));
+        }
+        match config.mappings.iter_mut().find(|other| other.id == mapping.id) {
+            Some(existing) => *existing = mapping.clone(),
+            None if draft.id.is_some() => return Err(format!("Mapping not found: {}", mapping.id)),
+            None => config.mappings.push(mapping.clone()),
+        }
+        Ok(())
+    })?;
+    Ok(mapping)
+}
+
+#[tauri::command]
+pub fn delete_input_mapping(state: State<InputState>, id: String) -> Result<(), String> {
+    state.update(|config| {
+        let before = config.mappings.len();
+        config.mappings.retain(|mapping| mapping.id != id);
+        if config.mappings.len() == before {
+            return Err(format!("Mapping not found: {}", id));
+        }
+        Ok(())
+    })
+}
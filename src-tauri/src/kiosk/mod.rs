@@ -0,0 +1,302 @@
+/// Kiosk / exhibit mode
+///
+/// Turned on by the `kiosk` settings or for one launch with `--kiosk` on the command
+/// line (`--kiosk-app=<name>` picks the app, `--kiosk-sim` runs in simulation). While
+/// active:
+/// - the daemon is started at launch and restarted whenever it stops or crashes, then
+///   the chosen robot app is started and restarted the same way (with backoff)
+/// - the main window is fullscreen and can't be closed, minimized or resized, and quitting
+///   from the OS (Cmd+Q, last window closed) is refused; a panic on the main thread
+///   relaunches the app
+/// - background update checks stop and destructive commands (updates, rollbacks,
+///   firmware flashing, app removal...) are refused through `ensure_allowed`
+///
+/// The app updater runs in the frontend, which hides it when `get_kiosk_status` reports
+/// the mode as active. `set_kiosk_mode` turns the mode on or off immediately.
+///
+/// Emits `kiosk-status` (KioskStatus) when the mode changes and after each restart.
+
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::connection::{ConnectionManager, ConnectionState};
+use crate::daemon::DaemonState;
+use crate::daemon_api::DaemonClient;
+use crate::local_proxy::LocalProxyState;
+use crate::settings::{KioskSettings, SettingsState};
+
+const CLI_FLAG: &str = "--kiosk";
+const CLI_APP_PREFIX: &str = "--kiosk-app=";
+const CLI_SIM_FLAG: &str = "--kiosk-sim";
+const SUPERVISE_INTERVAL: Duration = Duration::from_secs(5);
+const MIN_BACKOFF: Duration = Duration::from_secs(5);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Read by the guards without going through the state
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum KioskSource {
+    Settings,
+    CommandLine,
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct KioskStatus {
+    pub active: bool,
+    pub source: Option<KioskSource>,
+    pub app: Option<String>,
+    pub sim_mode: bool,
+    pub daemon_restarts: u32,
+    pub app_restarts: u32,
+    pub last_error: Option<String>,
+}
+
+#[derive(Default)]
+pub struct KioskState {
+    status: Mutex<KioskStatus>,
+    supervisor: Mutex<Option<JoinHandle<()>>>,
+}
+
+/// Retry delay doubling after each failure
+struct Backoff {
+    delay: Duration,
+    next_try: Instant,
+}
+
+impl Backoff {
+    fn new() -> Self {
+        Self {
+            delay: MIN_BACKOFF,
+            next_try: Instant::now(),
+        }
+    }
+
+    fn ready(&self) -> bool {
+        Instant::now() >= self.next_try
+    }
+
+    fn failed(&mut self) {
+        self.next_try = Instant::now() + self.delay;
+        self.delay = (self.delay * 2).min(MAX_BACKOFF);
+    }
+
+    fn reset(&mut self) {
+        self.delay = MIN_BACKOFF;
+    }
+}
+
+// ============================================================================
+// HELPER FUNCTIONS
+// ============================================================================
+
+/// Kiosk settings forced by the command line, if any
+fn command_line_settings() -> Option<KioskSettings> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let app = args.iter().find_map(|arg| arg.strip_prefix(CLI_APP_PREFIX)).map(String::from);
+    let forced = args.iter().any(|arg| arg == CLI_FLAG) || app.is_some();
+    forced.then(|| KioskSettings {
+        enabled: true,
+        app: app.filter(|app| !app.is_empty()),
+        sim_mode: args.iter().any(|arg| arg == CLI_SIM_FLAG),
+    })
+}
+
+/// Fullscreen main window without close / minimize / resize (or back to normal)
+fn lock_window(app_handle: &AppHandle, locked: bool) {
+    let Some(window) = app_handle.get_webview_window("main") else {
+        return;
+    };
+    let results = [
+        window.set_fullscreen(locked),
+        window.set_closable(!locked),
+        window.set_minimizable(!locked),
+        window.set_resizable(!locked),
+    ];
+    if let Some(e) = results.into_iter().find_map(|result| result.err()) {
+        eprintln!("[kiosk] ⚠️ Failed to {} the window: {}", if locked { "lock" } else { "unlock" }, e);
+    }
+}
+
+fn emit_status(app_handle: &AppHandle) {
+    let status = app_handle.state::<KioskState>().status.lock().unwrap().clone();
+    let _ = app_handle.emit("kiosk-status", status);
+}
+
+/// Record a restart attempt; returns whether it worked
+fn record_restart(app_handle: &AppHandle, daemon: bool, result: Result<(), String>) -> bool {
+    {
+        let state = app_handle.state::<KioskState>();
+        let mut status = state.status.lock().unwrap();
+        if daemon {
+            status.daemon_restarts += 1;
+        } else {
+            status.app_restarts += 1;
+        }
+        status.last_error = result.as_ref().err().cloned();
+    }
+    if let Err(e) = &result {
+        eprintln!("[kiosk] ❌ {}", e);
+    }
+    emit_status(app_handle);
+    result.is_ok()
+}
+
+async fn start_daemon(app_handle: &AppHandle, sim_mode: bool) -> Result<(), String> {
+    let handle = app_handle.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::start_daemon(handle.clone(), handle.state::<DaemonState>(), Some(sim_mode), None, None)
+    })
+    .await
+    .map_err(|e| format!("Failed to start daemon: {}", e))?
+    .map(|_| ())
+}
+
+/// Keep the daemon and the chosen app running
+async fn supervise(app_handle: AppHandle, settings: KioskSettings) {
+    let mut daemon_backoff = Backoff::new();
+    let mut app_backoff = Backoff::new();
+    loop {
+        match app_handle.state::<ConnectionManager>().get() {
+            ConnectionState::Ready { .. } | ConnectionState::WifiMode { degraded: false, .. } => {
+                daemon_backoff.reset();
+                if let Some(app) = &settings.app {
+                    let proxy = app_handle.state::<Arc<LocalProxyState>>();
+                    let client = DaemonClient::for_proxy(&proxy).await;
+                    if client.running_app().await.as_deref() == Some(app.as_str()) {
+                        app_backoff.reset();
+                    } else if app_backoff.ready() {
+                        println!("[kiosk] 🔁 Starting app {}", app);
+                        let result = crate::apps::launch_app(&app_handle, &client, app).await;
+                        if !record_restart(&app_handle, false, result) {
+                            app_backoff.failed();
+                        }
+                    }
+                }
+            }
+            // Nothing to start without a robot (unless simulated), or while starting / over WiFi
+            ConnectionState::Disconnected if !settings.sim_mode => {}
+            ConnectionState::DaemonStarting { .. } | ConnectionState::WifiMode { .. } => {}
+            ConnectionState::Disconnected | ConnectionState::UsbDetected { .. } | ConnectionState::Error { .. } => {
+                if daemon_backoff.ready() {
+                    println!("[kiosk] 🔁 Starting the daemon");
+                    let result = start_daemon(&app_handle, settings.sim_mode).await;
+                    if !record_restart(&app_handle, true, result) {
+                        daemon_backoff.failed();
+                    }
+                }
+            }
+        }
+        tokio::time::sleep(SUPERVISE_INTERVAL).await;
+    }
+}
+
+fn activate(app_handle: &AppHandle, settings: KioskSettings, source: KioskSource) {
+    let state = app_handle.state::<KioskState>();
+    if let Some(supervisor) = state.supervisor.lock().unwrap().take() {
+        supervisor.abort();
+    }
+    println!("[kiosk] 🔒 Kiosk mode on ({:?}, app: {:?})", source, settings.app);
+    ACTIVE.store(true, Ordering::SeqCst);
+    *state.status.lock().unwrap() = KioskStatus {
+        active: true,
+        source: Some(source),
+        app: settings.app.clone(),
+        sim_mode: settings.sim_mode,
+        ..KioskStatus::default()
+    };
+    lock_window(app_handle, true);
+    *state.supervisor.lock().unwrap() = Some(tauri::async_runtime::spawn(supervise(app_handle.clone(), settings)));
+    emit_status(app_handle);
+}
+
+fn deactivate(app_handle: &AppHandle) {
+    let state = app_handle.state::<KioskState>();
+    if let Some(supervisor) = state.supervisor.lock().unwrap().take() {
+        supervisor.abort();
+    }
+    if ACTIVE.swap(false, Ordering::SeqCst) {
+        println!("[kiosk] 🔓 Kiosk mode off");
+        lock_window(app_handle, false);
+    }
+    *state.status.lock().unwrap() = KioskStatus::default();
+    emit_status(app_handle);
+}
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+pub fn is_active() -> bool {
+    ACTIVE.load(Ordering::SeqCst)
+}
+
+/// Refuse a destructive action while kiosk mode is on
+pub fn ensure_allowed(action: &str) -> Result<(), String> {
+    if is_active() {
+        Err(format!("{} is disabled in kiosk mode", action))
+    } else {
+        Ok(())
+    }
+}
+
+// ============================================================================
+// SETUP
+// ============================================================================
+
+/// Turn kiosk mode on if the command line or the settings ask for it (call once in
+/// setup, after the settings are loaded)
+pub fn init(app_handle: &AppHandle) {
+    if let Some(settings) = command_line_settings() {
+        activate(app_handle, settings, KioskSource::CommandLine);
+        return;
+    }
+    let settings = app_handle.state::<SettingsState>().get().kiosk;
+    if settings.enabled {
+        activate(app_handle, settings, KioskSource::Settings);
+    }
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+#[tauri::command]
+pub fn get_kiosk_status(state: State<KioskState>) -> KioskStatus {
+    state.status.lock().unwrap().clone()
+}
+
+/// Turn kiosk mode on or off now (persisted; turning it off also ends a command-line kiosk)
+#[tauri::command]
+pub fn set_kiosk_mode(
+    app_handle: AppHandle,
+    enabled: bool,
+    app: Option<String>,
+    sim_mode: Option<bool>,
+) -> Result<KioskStatus, String> {
+    crate::lock::ensure_unlocked("Changing kiosk mode")?;
+    let settings = app_handle.state::<SettingsState>().update(|settings| {
+        settings.kiosk.enabled = enabled;
+        if let Some(app) = app {
+            settings.kiosk.app = Some(app.trim().to_string()).filter(|app| !app.is_empty());
+        }
+        if let Some(sim_mode) = sim_mode {
+            settings.kiosk.sim_mode = sim_mode;
+        }
+    })?;
+    if enabled {
+        activate(&app_handle, settings.kiosk, KioskSource::Settings);
+    } else {
+        deactivate(&app_handle);
+    }
+    Ok(app_handle.state::<KioskState>().status.lock().unwrap().clone())
+}
@@ -1,19 +1,60 @@
 // Modules
 #[macro_use]
 mod daemon;
+mod analytics;
+mod apps;
+mod assets;
+mod automation;
+mod backup;
+mod audio;
+mod camera;
+mod compatibility;
+mod daemon_api;
+mod deep_link;
+mod diagnostics;
+mod emergency;
+mod connection;
+mod crash_report;
+mod discovery;
+mod downloads;
+mod firmware;
+mod gamepad;
+mod hud;
+mod i18n;
+mod input;
+mod kiosk;
+mod lock;
+mod motor_health;
+mod mqtt;
+mod onboarding;
 mod permissions;
+mod power;
+mod presets;
+mod profiling;
 mod python;
+mod robots;
+mod routines;
+mod serial_console;
+mod settings;
 mod signing;
+mod simulation;
+mod telemetry;
+mod teleop;
+mod timeline;
+mod tray;
 mod update;
 mod usb;
+mod versions;
+mod webrtc_bridge;
 mod wifi;
 mod window;
 mod local_proxy;
 
 use std::sync::Arc;
 use tauri::{State, Manager};
-use daemon::{DaemonState, add_log, kill_daemon, cleanup_system_daemons, spawn_and_monitor_sidecar};
+use daemon::{DaemonState, PortConflict, add_log, kill_daemon, cleanup_system_daemons, spawn_and_monitor_sidecar};
 use local_proxy::LocalProxyState;
+use settings::SettingsState;
 
 #[cfg(not(windows))]
 use signal_hook::{consts::TERM_SIGNALS, iterator::Signals};
@@ -23,15 +64,58 @@ use signal_hook::{consts::TERM_SIGNALS, iterator::Signals};
 // ============================================================================
 
 #[tauri::command]
-fn start_daemon(app_handle: tauri::AppHandle, state: State<DaemonState>, sim_mode: Option<bool>) -> Result<String, String> {
-    let sim_mode = sim_mode.unwrap_or(false);
+fn start_daemon(app_handle: tauri::AppHandle, state: State<DaemonState>, sim_mode: Option<bool>, force: Option<bool>, port: Option<String>) -> Result<String, String> {
+    let settings = app_handle.state::<SettingsState>().get();
+    let sim_mode = sim_mode.unwrap_or(settings.default_sim_mode);
+    let force = force.unwrap_or(false);
+    // 🎯 Several robots plugged in: drive the preferred one if it is among them
+    let port = port.or_else(|| {
+        let preferred = settings.preferred_robot.as_deref()?;
+        crate::usb::get_reachy_robots()
+            .into_iter()
+            .find(|robot| robot.serial_number.as_deref() == Some(preferred))
+            .map(|robot| robot.port_name)
+    });
+    
+    // 0. 🔒 Never touch a daemon owned by another app instance
+    // (cleanup below would kill it and both instances would fight over the serial port)
+    if let Some(owner) = daemon::instance_lock::foreign_owner() {
+        add_log(&state, format!(
+            "❌ Daemon already running in another Reachy Mini Control instance (PID {})",
+            owner.app_pid
+        ));
+        return Err(owner.to_error_string());
+    }
+    
+    // 🖥️ The serial console and the daemon never hold the robot port together
+    serial_console::release_for_daemon(&app_handle);
+    
+    // 1. 🔍 Make sure no foreign process owns the daemon ports
+    // Without `force`, report the owner to the frontend instead of failing opaquely later
+    let conflicts = daemon::detect_port_conflicts();
+    if let Some(conflict) = conflicts.first() {
+        if !force {
+            add_log(&state, format!(
+                "❌ Port {} is already in use by {} (PID {})",
+                conflict.port, conflict.process_name, conflict.pid
+            ));
+            return Err(conflict.to_error_string());
+        }
+        for conflict in &conflicts {
+            add_log(&state, format!(
+                "⚠️ Force killing {} (PID {}) on port {}",
+                conflict.process_name, conflict.pid, conflict.port
+            ));
+            daemon::kill_process(conflict.pid)?;
+        }
+    }
     
     // 🎭 Simulation mode: mockup-sim backend (no physics engine needed)
     if sim_mode {
         add_log(&state, "🎭 Starting simulation mode (mockup-sim)...".to_string());
     }
     
-    // 1. ⚡ Aggressive cleanup of all existing daemons (including zombies)
+    // 2. ⚡ Aggressive cleanup of all existing daemons (including zombies)
     let cleanup_msg = if sim_mode {
         "🧹 Cleaning up existing daemons (simulation mode)..."
     } else {
@@ -40,10 +124,10 @@ fn start_daemon(app_handle: tauri::AppHandle, state: State<DaemonState>, sim_mod
     add_log(&state, cleanup_msg.to_string());
     kill_daemon(&state);
     
-    // 2. Spawn embedded daemon sidecar
-    spawn_and_monitor_sidecar(app_handle, &state, sim_mode)?;
+    // 3. Spawn embedded daemon sidecar
+    spawn_and_monitor_sidecar(app_handle, &state, sim_mode, port.as_deref())?;
     
-    // 3. Log success
+    // 4. Log success
     let success_msg = if sim_mode {
         "✓ Daemon started in simulation mode (mockup-sim) via embedded sidecar"
     } else {
@@ -65,6 +149,29 @@ fn stop_daemon(state: State<DaemonState>) -> Result<String, String> {
     Ok("Daemon stopped successfully".to_string())
 }
 
+/// Probe daemon ports (8000, 8042) for processes we did not spawn
+#[tauri::command]
+fn check_daemon_ports() -> Vec<PortConflict> {
+    daemon::detect_port_conflicts()
+}
+
+/// Force kill whatever is listening on a daemon port (user-confirmed)
+#[tauri::command]
+fn force_kill_port_owner(state: State<DaemonState>, port: u16) -> Result<usize, String> {
+    if !daemon::DAEMON_PORTS.contains(&port) {
+        return Err(format!("Port {} is not a daemon port", port));
+    }
+    let killed = daemon::kill_port_owners(port)?;
+    add_log(&state, format!("✓ Killed {} process(es) on port {}", killed, port));
+    Ok(killed)
+}
+
+/// Past daemon runs with crash statistics
+#[tauri::command]
+fn get_daemon_run_history(state: State<DaemonState>) -> daemon::history::DaemonRunHistory {
+    state.history.lock().unwrap().snapshot()
+}
+
 #[tauri::command]
 fn get_logs(state: State<DaemonState>) -> Vec<String> {
     let logs = state.logs.lock().unwrap();
@@ -87,12 +194,238 @@ async fn clear_local_proxy_target(state: State<'_, Arc<LocalProxyState>>) -> Res
     Ok(())
 }
 
+/// Store (or delete with None) the token injected into requests to the robot
+#[tauri::command]
+async fn set_proxy_robot_token(state: State<'_, Arc<LocalProxyState>>, token: Option<String>) -> Result<(), String> {
+    lock::ensure_unlocked("Changing the robot token")?;
+    let token = token.filter(|t| !t.trim().is_empty());
+    let stored = token.clone();
+    tauri::async_runtime::spawn_blocking(move || local_proxy::auth::store_robot_token(stored.as_deref()))
+        .await
+        .map_err(|e| format!("Task join error: {}", e))??;
+    state.auth.write().await.robot_token = token;
+    Ok(())
+}
+
+/// Require local clients to present the session token
+#[tauri::command]
+async fn set_proxy_auth_required(
+    state: State<'_, Arc<LocalProxyState>>,
+    settings: State<'_, SettingsState>,
+    required: bool,
+) -> Result<(), String> {
+    lock::ensure_unlocked("Changing the proxy token requirement")?;
+    let mut auth = state.auth.write().await;
+    if required && auth.local_token.is_empty() {
+        auth.local_token = local_proxy::auth::generate_token()?;
+    }
+    settings.update(|settings| settings.proxy_require_local_token = required)?;
+    auth.require_local_token = required;
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_proxy_ports(state: State<'_, Arc<LocalProxyState>>) -> Result<Vec<local_proxy::PortMapping>, String> {
+    Ok(state.port_mappings.read().await.clone())
+}
+
+/// Replace the proxied ports (local -> remote), persisted and applied live; empty restores the defaults
+#[tauri::command]
+async fn set_proxy_ports(
+    state: State<'_, Arc<LocalProxyState>>,
+    settings: State<'_, SettingsState>,
+    ports: Vec<local_proxy::PortMapping>,
+) -> Result<Vec<local_proxy::PortMapping>, String> {
+    lock::ensure_unlocked("Changing the proxied ports")?;
+    let ports = if ports.is_empty() { local_proxy::default_port_mappings() } else { ports };
+    local_proxy::set_port_mappings(&state, ports.clone()).await?;
+    settings.update(|settings| settings.proxy_ports = ports.clone())?;
+    Ok(ports)
+}
+
+/// Cap robot -> app traffic on a proxied port (None = unlimited), persisted and applied live
+#[tauri::command]
+async fn set_proxy_rate_limit(
+    state: State<'_, Arc<LocalProxyState>>,
+    settings: State<'_, SettingsState>,
+    port: u16,
+    rate_limit_kbps: Option<u32>,
+) -> Result<Vec<local_proxy::PortMapping>, String> {
+    lock::ensure_unlocked("Changing the proxy rate limits")?;
+    let mut ports = state.port_mappings.read().await.clone();
+    let mapping = ports
+        .iter_mut()
+        .find(|mapping| mapping.local == port)
+        .ok_or(format!("Port {} is not proxied", port))?;
+    mapping.rate_limit_kbps = rate_limit_kbps.filter(|&kbps| kbps > 0);
+    local_proxy::set_port_mappings(&state, ports.clone()).await?;
+    settings.update(|settings| settings.proxy_ports = ports.clone())?;
+    Ok(ports)
+}
+
+/// Enable/disable recording of proxied HTTP exchanges
+#[tauri::command]
+fn set_proxy_capture(state: State<'_, Arc<LocalProxyState>>, enabled: bool) {
+    state.capture.set_enabled(enabled);
+}
+
+/// Recorded HTTP exchanges, oldest first
+#[tauri::command]
+fn get_proxy_capture(state: State<'_, Arc<LocalProxyState>>) -> Vec<local_proxy::capture::CapturedExchange> {
+    state.capture.entries()
+}
+
+#[tauri::command]
+fn clear_proxy_capture(state: State<'_, Arc<LocalProxyState>>) {
+    state.capture.clear();
+}
+
+/// Proxy state and per-port metrics (also emitted periodically as `proxy-status`)
+#[tauri::command]
+async fn get_proxy_status(state: State<'_, Arc<LocalProxyState>>) -> Result<local_proxy::metrics::ProxyStatus, String> {
+    Ok(state.status().await)
+}
+
+/// Latest WiFi link rating (also emitted as `connection-quality`), None until measured
+#[tauri::command]
+fn get_connection_quality(state: State<'_, Arc<LocalProxyState>>) -> Option<local_proxy::quality::ConnectionQuality> {
+    state.quality.latest()
+}
+
+/// Auth configuration, including the session token the webview must send
+#[tauri::command]
+async fn get_proxy_auth_status(state: State<'_, Arc<LocalProxyState>>) -> Result<local_proxy::auth::ProxyAuthStatus, String> {
+    Ok((&*state.auth.read().await).into())
+}
+
+/// Route rules applied to third-party app UIs going through the proxy
+#[tauri::command]
+fn get_proxy_firewall(state: State<'_, Arc<LocalProxyState>>) -> local_proxy::firewall::FirewallConfig {
+    state.firewall.config()
+}
+
+/// Replace the route firewall configuration, persisted and applied live
+#[tauri::command]
+fn set_proxy_firewall(
+    state: State<'_, Arc<LocalProxyState>>,
+    settings: State<'_, SettingsState>,
+    config: local_proxy::firewall::FirewallConfig,
+) -> Result<(), String> {
+    lock::ensure_unlocked("Changing the proxy firewall")?;
+    config.validate()?;
+    settings.update(|settings| settings.proxy_firewall = config.clone())?;
+    state.firewall.set_config(config);
+    Ok(())
+}
+
+/// Answer a `proxy-route-confirm` request (unanswered ones are denied after 30 s)
+#[tauri::command]
+fn respond_proxy_route(state: State<'_, Arc<LocalProxyState>>, id: u64, allow: bool) -> Result<(), String> {
+    if state.firewall.respond(id, allow) {
+        Ok(())
+    } else {
+        Err(format!("No pending route confirmation #{}", id))
+    }
+}
+
+/// Saved WiFi robots, most recently used first
+#[tauri::command]
+fn list_remote_profiles(state: State<'_, Arc<LocalProxyState>>) -> Vec<local_proxy::profiles::RemoteProfile> {
+    state.profiles.list()
+}
+
+/// Create (id None) or update a saved WiFi robot
+///
+/// `token`: None keeps the stored one, an empty string deletes it.
+#[tauri::command]
+async fn save_remote_profile(
+    state: State<'_, Arc<LocalProxyState>>,
+    id: Option<String>,
+    name: String,
+    host: String,
+    token: Option<String>,
+    ports: Option<Vec<local_proxy::PortMapping>>,
+) -> Result<local_proxy::profiles::RemoteProfile, String> {
+    let name = name.trim().to_string();
+    let host = host.trim().to_string();
+    if name.is_empty() {
+        return Err("Profile name cannot be empty".to_string());
+    }
+    local_proxy::profiles::validate_host(&host)?;
+    let ports = ports.filter(|ports| !ports.is_empty());
+
+    let existing = match &id {
+        Some(id) => Some(state.profiles.get(id).ok_or(format!("Unknown profile: {}", id))?),
+        None => None,
+    };
+    if let Some(other) = state.profiles.find_by_host(&host) {
+        if existing.as_ref().map(|profile| &profile.id) != Some(&other.id) {
+            return Err(format!("{} is already saved as {}", host, other.name));
+        }
+    }
+    let mut profile = existing.unwrap_or_else(|| local_proxy::profiles::RemoteProfile {
+        id: local_proxy::profiles::generate_id(),
+        name: String::new(),
+        host: String::new(),
+        ports: None,
+        has_token: false,
+        last_used: None,
+    });
+    profile.name = name;
+    profile.host = host;
+    profile.ports = ports;
+
+    if let Some(token) = token {
+        let token = Some(token).filter(|t| !t.trim().is_empty());
+        profile.has_token = token.is_some();
+        let account = profile.token_account();
+        tauri::async_runtime::spawn_blocking(move || local_proxy::auth::store_token(&account, token.as_deref()))
+            .await
+            .map_err(|e| format!("Task join error: {}", e))??;
+    }
+
+    let saved = profile.clone();
+    state.profiles.update(move |profiles| {
+        match profiles.iter_mut().find(|existing| existing.id == profile.id) {
+            Some(existing) => *existing = profile,
+            None => profiles.push(profile),
+        }
+        Ok(())
+    })?;
+    Ok(saved)
+}
+
+#[tauri::command]
+async fn delete_remote_profile(state: State<'_, Arc<LocalProxyState>>, id: String) -> Result<(), String> {
+    let profile = state.profiles.get(&id).ok_or(format!("Unknown profile: {}", id))?;
+    if profile.has_token {
+        let account = profile.token_account();
+        tauri::async_runtime::spawn_blocking(move || local_proxy::auth::store_token(&account, None))
+            .await
+            .map_err(|e| format!("Task join error: {}", e))??;
+    }
+    state.profiles.update(|profiles| {
+        profiles.retain(|profile| profile.id != id);
+        Ok(())
+    })
+}
+
+/// Point the proxy at a saved WiFi robot (its token and ports are applied)
+#[tauri::command]
+async fn connect_remote_profile(state: State<'_, Arc<LocalProxyState>>, id: String) -> Result<(), String> {
+    let profile = state.profiles.get(&id).ok_or(format!("Unknown profile: {}", id))?;
+    local_proxy::set_target_host(&state, profile.host).await;
+    Ok(())
+}
+
 // ============================================================================
 // ENTRY POINT
 // ============================================================================
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    profiling::init();
+
     // Setup signal handler for brutal kill (SIGTERM, SIGINT, etc.) - Unix only
     #[cfg(not(windows))]
     {
@@ -135,22 +468,119 @@ pub fn run() {
     let local_proxy_state = Arc::new(LocalProxyState::new());
 
     builder
-        .manage(DaemonState {
-            process: std::sync::Mutex::new(None),
-            logs: std::sync::Mutex::new(std::collections::VecDeque::new()),
-        })
+        .manage(DaemonState::new())
+        .manage(SettingsState::new())
+        .manage(analytics::AnalyticsState::new())
+        .manage(connection::ConnectionManager::new())
+        .manage(telemetry::TelemetryState::default())
+        .manage(camera::CameraState::default())
+        .manage(motor_health::MotorHealthState::default())
+        .manage(deep_link::DeepLinkState::default())
+        .manage(usb::registry::RobotRegistryState::new())
+        .manage(robots::RobotsState::new())
+        .manage(onboarding::OnboardingTracker::new())
+        .manage(presets::PresetsState::new())
+        .manage(assets::SoundPackState::new())
+        .manage(apps::sandbox::SandboxState::new())
+        .manage(automation::AutomationState::default())
+        .manage(window::WindowLayoutState::new())
+        .manage(hud::HudState::default())
+        .manage(power::PowerState::default())
+        .manage(compatibility::CompatibilityState::default())
+        .manage(teleop::TeleopState::default())
+        .manage(gamepad::GamepadState::default())
+        .manage(serial_console::SerialConsoleState::default())
+        .manage(update::scheduler::UpdateCheckState::default())
+        .manage(downloads::DownloadCache::default())
+        .manage(routines::RoutinesState::default())
+        .manage(kiosk::KioskState::default())
+        .manage(lock::UiLockState::default())
+        .manage(input::InputState::default())
+        .manage(mqtt::MqttState::default())
+        .manage(webrtc_bridge::WebRtcBridgeState::default())
+        .manage(wifi::scan_stream::WifiScanStreamState::default())
+        .manage(wifi::hotspot::HotspotWatchState::default())
         .manage(local_proxy_state)
-        .setup(move |
-            #[cfg(target_os = "macos")]
-            app,
-            #[cfg(not(target_os = "macos"))]
-            _app
-        | {
-            // 🔌 Start USB device monitor (Windows: event-driven, no polling, no terminal flicker)
-            if let Err(e) = usb::start_monitor() {
+        .setup(move |app| {
+            // 💥 Panics are written to crash-reports/ and reported to the frontend
+            match crash_report::get_reports_dir(app.handle()) {
+                Ok(dir) => crash_report::panic_hook::install(app.handle().clone(), dir),
+                Err(e) => eprintln!("⚠️ Panic reports disabled: {}", e),
+            }
+            
+            // 📜 Load settings, locale catalogs, robot registry, download cache, UI lock PIN, input mappings, daemon run history and ownership lock from the app data directory
+            match app.path().app_data_dir() {
+                Ok(dir) => {
+                    let _span = profiling::span("load_app_data");
+                    app.state::<SettingsState>().load(app.handle(), &dir);
+                    i18n::init(&dir, app.state::<SettingsState>().get().language.as_deref());
+                    app.state::<usb::registry::RobotRegistryState>().load(&dir);
+                    app.state::<robots::RobotsState>().load(&dir);
+                    app.state::<onboarding::OnboardingTracker>().load(&dir);
+                    app.state::<presets::PresetsState>().load(&dir);
+                    app.state::<assets::SoundPackState>().load(&dir);
+                    app.state::<downloads::DownloadCache>().load(&dir);
+                    app.state::<apps::sandbox::SandboxState>().load(&dir);
+                    app.state::<automation::AutomationState>().load(&dir);
+                    app.state::<routines::RoutinesState>().load(&dir);
+                    app.state::<lock::UiLockState>().load(&dir);
+                    app.state::<input::InputState>().load(&dir);
+                    app.state::<window::WindowLayoutState>().load(&dir);
+                    app.state::<Arc<LocalProxyState>>().profiles.load(&dir);
+                    app.state::<analytics::AnalyticsState>().load(&dir);
+                    let state: State<DaemonState> = app.state();
+                    *state.history.lock().unwrap() = daemon::history::RunHistory::load(&dir);
+                    profiling::set_trace_dir(dir.clone());
+                    daemon::instance_lock::init(dir);
+                }
+                Err(e) => {
+                    eprintln!("⚠️ Failed to resolve app data dir: {}", e);
+                    i18n::set_language(None);
+                }
+            }
+            
+            // 🔒 Local proxy: status events, auth preference, port list and route firewall (robot token is read from the keychain when the proxy starts)
+            let settings = app.state::<SettingsState>().get();
+            let proxy_state = app.state::<Arc<LocalProxyState>>();
+            proxy_state.set_app_handle(app.handle().clone());
+            proxy_state.auth.blocking_write().require_local_token = settings.proxy_require_local_token;
+            *proxy_state.port_mappings.blocking_write() = settings.proxy_ports;
+            proxy_state.firewall.set_config(settings.proxy_firewall);
+            
+            // 🔌 Start USB device monitor (emits usb-robot-* and usb-device-* hotplug events)
+            usb::set_watch_list(app.state::<SettingsState>().get().usb_watch_list);
+            if let Err(e) = usb::start_monitor(app.handle().clone()) {
                 eprintln!("⚠️ Failed to start USB monitor: {}", e);
             }
             
+            // 🔁 Auto-start/stop daemon on robot plug/unplug (when enabled in settings)
+            daemon::autostart::start_watcher(app.handle().clone());
+            update::scheduler::start(app.handle().clone());
+            power::start_monitor(app.handle().clone());
+            connection::start(app.handle().clone());
+            compatibility::start_watcher(app.handle().clone());
+            routines::start(app.handle().clone());
+            kiosk::init(app.handle());
+            input::init(app.handle());
+            mqtt::init(app.handle());
+            analytics::start_uploader(app.handle().clone());
+            onboarding::init(app.handle());
+            automation::init(app.handle());
+            window::restore(app.handle());
+            
+            // 🧭 System tray (quick actions, minimize to tray)
+            if let Err(e) = tray::init(app.handle()) {
+                eprintln!("⚠️ Failed to create tray icon: {}", e);
+            }
+            
+            // 🛑 Emergency stop shortcut (works with the window in the background)
+            if let Err(e) = emergency::shortcut::register(app) {
+                eprintln!("⚠️ {}", e);
+            }
+            
+            // 🔗 reachy-mini:// links (launch URL and links opened while running)
+            deep_link::init(app.handle());
+            
             #[cfg(target_os = "macos")]
             {
                 let window = app.get_webview_window("main").unwrap();
@@ -174,40 +604,279 @@ pub fn run() {
                 permissions::request_all_permissions();
             }
             
+            profiling::end("app_init");
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
             start_daemon,
             stop_daemon,
             get_logs,
+            check_daemon_ports,
+            force_kill_port_owner,
+            get_daemon_run_history,
+            daemon::mode_switch::switch_daemon_mode,
+            crash_report::generate_crash_report,
+            crash_report::send_crash_report,
+            crash_report::get_panic_reports,
+            crash_report::dismiss_panic_reports,
+            crash_report::issue::report_issue,
+            i18n::get_locale_strings,
+            i18n::get_system_locale,
+            i18n::get_available_locales,
+            i18n::set_language_preference,
+            i18n::download_locale_catalog,
+            diagnostics::run_diagnostics,
             usb::check_usb_robot,
+            usb::get_usb_devices_status,
+            usb::set_usb_watch_list,
+            usb::registry::identify_usb_robot,
+            usb::registry::list_known_robots,
+            usb::registry::set_robot_nickname,
+            usb::registry::forget_robot,
+            usb::diagnostics::run_usb_diagnostics,
+            usb::driver::get_usb_driver_status,
+            usb::driver::open_usb_driver_download,
+            firmware::flash_firmware,
+            serial_console::open_serial_console,
+            serial_console::read_serial_console,
+            serial_console::write_serial_console,
+            serial_console::close_serial_console,
+            serial_console::open_serial_console_window,
+            connection::get_connection_state,
+            daemon_api::get_daemon_status,
+            daemon_api::get_robot_state,
+            daemon_api::get_motors_status,
+            daemon_api::set_motor_mode,
+            emergency::emergency_stop,
+            motor_health::start_motor_health_monitor,
+            motor_health::stop_motor_health_monitor,
+            motor_health::get_motor_health,
+            motor_health::get_motor_health_history,
+            motor_health::get_motor_health_thresholds,
+            motor_health::set_motor_health_thresholds,
+            apps::list_installed_apps,
+            apps::get_current_app,
+            apps::start_app,
+            apps::stop_app,
+            apps::install_app,
+            apps::uninstall_app,
+            apps::sandbox::get_app_policies,
+            apps::sandbox::set_app_policy,
+            apps::sandbox::remove_app_policy,
+            telemetry::start_telemetry_stream,
+            telemetry::stop_telemetry_stream,
+            telemetry::get_telemetry_buffer,
+            telemetry::start_telemetry_recording,
+            telemetry::stop_telemetry_recording,
+            telemetry::list_telemetry_recordings,
+            telemetry::replay_telemetry_recording,
+            telemetry::stop_telemetry_replay,
+            telemetry::start_imu_feed,
+            telemetry::stop_imu_feed,
+            telemetry::get_imu_orientation,
+            telemetry::reset_imu_yaw,
+            audio::list_audio_devices,
+            audio::set_daemon_audio_devices,
+            audio::list_voices,
+            audio::say_text,
+            camera::list_cameras,
+            camera::start_camera_preview,
+            camera::get_camera_preview,
+            camera::stop_camera_preview,
+            deep_link::get_pending_deep_link,
+            deep_link::confirm_deep_link,
+            deep_link::dismiss_deep_link,
+            daemon::config::get_daemon_config,
+            daemon::config::validate_daemon_config,
+            daemon::config::set_daemon_config,
+            teleop::start_teleop,
+            teleop::stop_teleop,
+            teleop::teleop_key,
+            teleop::get_teleop_status,
+            gamepad::start_gamepad_control,
+            gamepad::stop_gamepad_control,
+            gamepad::get_gamepad_status,
+            onboarding::get_onboarding_state,
+            onboarding::complete_onboarding_step,
+            onboarding::skip_onboarding_step,
+            onboarding::reset_onboarding,
+            backup::create_backup,
+            backup::restore_backup,
+            robots::list_robots,
+            robots::add_robot,
+            robots::rename_robot,
+            robots::remove_robot,
+            robots::switch_robot,
+            robots::hostname::get_robot_hostname,
+            robots::hostname::set_robot_hostname,
+            presets::list_presets,
+            presets::save_preset,
+            presets::delete_preset,
+            presets::go_to_preset,
+            assets::list_sound_packs,
+            assets::download_sound_pack,
+            assets::delete_sound_pack,
+            assets::set_active_sound_pack,
+            automation::get_automation_info,
+            automation::set_automation_enabled,
+            automation::regenerate_automation_token,
+            analytics::track_usage_event,
+            analytics::get_usage_events,
+            analytics::upload_usage_events,
+            analytics::purge_usage_events,
+            settings::get_settings,
+            settings::set_settings,
+            settings::set_auto_start_daemon,
+            settings::set_update_channel,
             window::apply_transparent_titlebar,
             window::close_window,
+            window::open_aux_window,
+            window::list_windows,
+            window::reset_window_layout,
+            hud::open_hud_window,
+            hud::get_hud_status,
+            power::get_power_status,
+            power::get_power_thresholds,
+            power::set_power_thresholds,
+            compatibility::get_daemon_compatibility,
+            compatibility::check_daemon_compatibility,
+            simulation::get_sim_parameters,
+            simulation::set_sim_parameters,
+            timeline::query_timeline,
+            timeline::get_timeline_clock,
+            profiling::get_startup_profile,
             signing::sign_python_binaries,
+            signing::get_signing_status,
             permissions::open_camera_settings,
             permissions::open_microphone_settings,
             permissions::open_wifi_settings,
             permissions::open_files_settings,
+            permissions::open_input_monitoring_settings,
+            permissions::get_permission_status,
+            #[cfg(target_os = "linux")]
+            permissions::linux::get_serial_access_status,
+            #[cfg(target_os = "linux")]
+            permissions::linux::fix_serial_access,
             wifi::scan_local_wifi_networks,
             wifi::get_current_wifi_ssid,
+            wifi::get_current_wifi_connection,
+            wifi::connect_to_network,
+            wifi::provisioning::provision_robot_wifi,
+            wifi::hotspot::get_hotspot_state,
+            wifi::hotspot::start_hotspot_watch,
+            wifi::hotspot::stop_hotspot_watch,
+            wifi::scan_stream::start_wifi_scan_stream,
+            wifi::scan_stream::stop_wifi_scan_stream,
             update::check_daemon_update,
             update::update_daemon,
+            update::update_daemon_from_file,
+            update::extras::list_daemon_extras,
+            update::extras::add_daemon_extra,
+            update::extras::remove_daemon_extra,
+            update::scheduler::get_cached_update_check,
+            update::scheduler::check_updates_now,
+            update::scheduler::skip_update_version,
+            update::scheduler::remind_update_later,
+            update::scheduler::set_update_check_interval,
+            versions::get_component_versions,
+            update::get_update_rollback_info,
+            update::rollback_daemon_update,
+            update::remove_local_runtime,
+            update::remote::check_robot_daemon_update,
+            update::remote::update_robot_daemon,
+            update::remote::get_robot_update_rollback_info,
+            update::remote::rollback_robot_daemon_update,
+            downloads::get_download_cache,
+            downloads::purge_download_cache,
+            routines::list_routines,
+            routines::save_routine,
+            routines::delete_routine,
+            routines::set_routine_enabled,
+            routines::run_routine_now,
+            routines::get_routine_history,
+            kiosk::get_kiosk_status,
+            kiosk::set_kiosk_mode,
+            lock::get_ui_lock_status,
+            lock::set_ui_lock_pin,
+            lock::remove_ui_lock_pin,
+            lock::unlock_ui,
+            lock::lock_ui,
+            input::get_input_config,
+            input::get_input_status,
+            input::list_input_devices,
+            input::set_input_sources,
+            input::save_input_mapping,
+            input::delete_input_mapping,
+            mqtt::get_mqtt_status,
+            mqtt::set_mqtt_settings,
+            webrtc_bridge::start_webrtc_bridge,
+            webrtc_bridge::stop_webrtc_bridge,
+            webrtc_bridge::get_webrtc_bridge_status,
             set_local_proxy_target,
-            clear_local_proxy_target
+            clear_local_proxy_target,
+            set_proxy_robot_token,
+            set_proxy_auth_required,
+            get_proxy_auth_status,
+            get_proxy_firewall,
+            set_proxy_firewall,
+            respond_proxy_route,
+            list_remote_profiles,
+            save_remote_profile,
+            delete_remote_profile,
+            connect_remote_profile,
+            get_proxy_status,
+            get_connection_quality,
+            get_proxy_ports,
+            set_proxy_ports,
+            set_proxy_rate_limit,
+            set_proxy_capture,
+            get_proxy_capture,
+            clear_proxy_capture,
+            discovery::discover_robots
         ])
         .on_window_event(|window, event| {
             match event {
-                tauri::WindowEvent::CloseRequested { .. } => {
+                tauri::WindowEvent::CloseRequested { api, .. } => {
                     // Only kill daemon if main window is closing
-                    if window.label() == "main" {
+                    let app_handle = window.app_handle();
+                    if window.label() == "main" && kiosk::is_active() {
+                        // 🔒 Exhibit window stays up
+                        api.prevent_close();
+                    } else if window.label() == "main"
+                        && app_handle.state::<SettingsState>().get().minimize_to_tray
+                        && tray::is_available(app_handle)
+                    {
+                        // 🧭 Keep running in the tray, the daemon stays up
+                        println!("🔽 Main window close requested - minimizing to tray");
+                        api.prevent_close();
+                        tray::hide_main_window(app_handle);
+                    } else if window.label() == "main" {
+                        // 💤 Park the robot first, the window is closed again once done
+                        let main_window = window.clone();
+                        if daemon::park::park_before_exit(app_handle, move |_| {
+                            let _ = main_window.close();
+                        }) {
+                            api.prevent_close();
+                            return;
+                        }
                         println!("🔴 Main window close requested - killing daemon");
+                        window::save_layout(app_handle);
                     let state: tauri::State<DaemonState> = window.state();
                     kill_daemon(&state);
                     } else {
                         println!("🔴 Secondary window close requested: {}", window.label());
                     }
                 }
+                tauri::WindowEvent::Moved(_) | tauri::WindowEvent::Resized(_) => {
+                    // 🪟 Geometry saved per window label, restored when it opens again
+                    window::track(window);
+                }
+                tauri::WindowEvent::Focused(focused) if window.label() == "main" => {
+                    // ⌨️ Teleop keys follow the main window focus
+                    teleop::on_focus_changed(window.app_handle(), *focused);
+                }
                 tauri::WindowEvent::Destroyed => {
+                    window::on_destroyed(window);
                     // Only cleanup if main window is destroyed
                     if window.label() == "main" {
                         println!("🔴 Main window destroyed - final cleanup");
@@ -221,12 +890,23 @@ pub fn run() {
         })
         .build(tauri::generate_context!())
         .expect("error while building tauri application")
-        .run(|_app_handle, event| {
+        .run(|app_handle, event| {
             match event {
-                tauri::RunEvent::ExitRequested { .. } => {
+                tauri::RunEvent::ExitRequested { api, code, .. } => {
+                    // 🔒 Kiosk: quitting from the OS is refused (exits requested by the app go through)
+                    if code.is_none() && kiosk::is_active() {
+                        api.prevent_exit();
+                        return;
+                    }
+                    // 💤 Park the robot first, exit is requested again once done
+                    if daemon::park::park_before_exit(app_handle, |app_handle| app_handle.exit(0)) {
+                        api.prevent_exit();
+                        return;
+                    }
                     // ⌘Q (Cmd+Q) on macOS triggers this event
                     // Kill daemon via port 8000 + process name (reliable cleanup)
                     println!("🔴 ExitRequested (Cmd+Q) - killing daemon");
+                    window::save_layout(app_handle);
                     cleanup_system_daemons();
                 }
                 tauri::RunEvent::Exit => {
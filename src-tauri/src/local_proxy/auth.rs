@@ -0,0 +1,209 @@
+//! Proxy authentication
+//!
+//! Two independent, optional protections:
+//! - robot token: injected as `Authorization: Bearer` into every request forwarded to
+//!   the remote robot. Stored in the OS keychain.
+//! - local token: required from local clients (header or `?proxy_token=` query, since
+//!   browsers can't set headers on WebSocket / <img> requests), so other processes on
+//!   localhost can't drive the robot through the proxy. Random, regenerated at each launch
+//!   and handed to the webview through a command.
+
+use serde::Serialize;
+
+const KEYCHAIN_SERVICE: &str = "com.pollen-robotics.reachy-mini";
+const KEYCHAIN_ROBOT_TOKEN: &str = "proxy-robot-token";
+pub const LOCAL_TOKEN_HEADER: &str = "x-proxy-token";
+pub const LOCAL_TOKEN_QUERY: &str = "proxy_token";
+
+#[derive(Clone)]
+pub struct ProxyAuth {
+    /// Token sent to the remote robot (None = no Authorization header injected)
+    pub robot_token: Option<String>,
+    /// Local clients must present local_token
+    pub require_local_token: bool,
+    /// Empty if no token could be generated: every local client is then refused
+    pub local_token: String,
+}
+
+impl ProxyAuth {
+    pub fn new() -> Self {
+        Self {
+            robot_token: None,
+            require_local_token: false,
+            local_token: generate_token().unwrap_or_else(|e| {
+                eprintln!("[proxy] ⚠️ {}", e);
+                String::new()
+            }),
+        }
+    }
+
+    /// Request heads must be rewritten (auth injected or local token stripped)
+    pub fn rewrites_requests(&self) -> bool {
+        self.robot_token.is_some() || self.require_local_token
+    }
+
+    /// Check the local token from a header value or query string
+    pub fn is_authorized(&self, header: Option<&str>, query: Option<&str>) -> bool {
+        if !self.require_local_token {
+            return true;
+        }
+        if self.local_token.is_empty() {
+            return false;
+        }
+        let from_header = header.map(|value| value.trim().trim_start_matches("Bearer ").trim());
+        let from_query = query.and_then(|query| {
+            query.split('&').find_map(|pair| {
+                let (key, value) = pair.split_once('=')?;
+                (key == LOCAL_TOKEN_QUERY).then_some(value)
+            })
+        });
+        from_header.or(from_query) == Some(self.local_token.as_str())
+    }
+}
+
+impl Default for ProxyAuth {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ProxyAuthStatus {
+    pub robot_token_set: bool,
+    pub require_local_token: bool,
+    /// Token the webview must send (header `X-Proxy-Token` or `?proxy_token=`)
+    pub local_token: String,
+}
+
+impl From<&ProxyAuth> for ProxyAuthStatus {
+    fn from(auth: &ProxyAuth) -> Self {
+        Self {
+            robot_token_set: auth.robot_token.is_some(),
+            require_local_token: auth.require_local_token,
+            local_token: auth.local_token.clone(),
+        }
+    }
+}
+
+/// 32 random bytes, hex encoded (an error if the OS has no randomness to give)
+pub(crate) fn generate_token() -> Result<String, String> {
+    let mut bytes = [0u8; 32];
+    getrandom::getrandom(&mut bytes).map_err(|e| format!("Failed to generate a random token: {}", e))?;
+    Ok(bytes.iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+// ============================================================================
+// KEYCHAIN
+// ============================================================================
+
+fn keychain_entry(account: &str) -> Result<keyring::Entry, String> {
+    keyring::Entry::new(KEYCHAIN_SERVICE, account)
+        .map_err(|e| format!("Failed to access keychain: {}", e))
+}
+
+/// Token stored in the OS keychain under `account` (blocking)
+pub fn load_token(account: &str) -> Option<String> {
+    let entry = keychain_entry(account).map_err(|e| eprintln!("[proxy] ⚠️ {}", e)).ok()?;
+    match entry.get_password() {
+        Ok(token) => Some(token),
+        Err(keyring::Error::NoEntry) => None,
+        Err(e) => {
+            eprintln!("[proxy] ⚠️ Failed to read robot token from keychain: {}", e);
+            None
+        }
+    }
+}
+
+/// Store (Some) or delete (None) a token in the OS keychain under `account` (blocking)
+pub fn store_token(account: &str, token: Option<&str>) -> Result<(), String> {
+    let entry = keychain_entry(account)?;
+    match token {
+        Some(token) => entry
+            .set_password(token)
+            .map_err(|e| format!("Failed to store {} in keychain: {}", account, e)),
+        None => match entry.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(e) => Err(format!("Failed to delete {} from keychain: {}", account, e)),
+        },
+    }
+}
+
+/// Robot token used for hosts without a saved profile (blocking)
+pub fn load_robot_token() -> Option<String> {
+    load_token(KEYCHAIN_ROBOT_TOKEN)
+}
+
+pub fn store_robot_token(token: Option<&str>) -> Result<(), String> {
+    store_token(KEYCHAIN_ROBOT_TOKEN, token)
+}
+
+// ============================================================================
+// REQUEST REWRITING
+// ============================================================================
+
+/// Remove the local token from a path's query string
+pub fn strip_local_token(path: &str) -> String {
+    let Some((base, query)) = path.split_once('?') else {
+        return path.to_string();
+    };
+    let kept: Vec<&str> = query
+        .split('&')
+        .filter(|pair| pair.split('=').next() != Some(LOCAL_TOKEN_QUERY))
+        .collect();
+    if kept.is_empty() {
+        base.to_string()
+    } else {
+        format!("{}?{}", base, kept.join("&"))
+    }
+}
+
+/// Outcome of checking and rewriting an HTTP request head
+pub enum RewrittenHead {
+    Forward(Vec<u8>),
+    Unauthorized,
+}
+
+/// Check the local token and rewrite a raw HTTP request head (up to and including the blank line)
+/// - drops the local token (header or query) and any client Authorization when injecting ours
+/// - injects the robot token
+/// - forces `Connection: close` so the next request on this connection goes through here too
+pub fn rewrite_request_head(head: &str, auth: &ProxyAuth) -> RewrittenHead {
+    let mut lines = head.split("\r\n");
+    let request_line = lines.next().unwrap_or_default();
+    let mut parts = request_line.splitn(3, ' ');
+    let (method, path, version) = (
+        parts.next().unwrap_or_default(),
+        parts.next().unwrap_or("/"),
+        parts.next().unwrap_or("HTTP/1.1"),
+    );
+
+    let headers: Vec<&str> = lines.filter(|line| !line.is_empty()).collect();
+    let header_value = |name: &str| {
+        headers.iter().find_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            key.trim().eq_ignore_ascii_case(name).then(|| value.trim())
+        })
+    };
+    let query = path.split_once('?').map(|(_, query)| query);
+    if !auth.is_authorized(header_value(LOCAL_TOKEN_HEADER), query) {
+        return RewrittenHead::Unauthorized;
+    }
+
+    let mut rewritten = format!("{} {} {}\r\n", method, strip_local_token(path), version);
+    for line in &headers {
+        let name = line.split(':').next().unwrap_or_default().trim();
+        let dropped = name.eq_ignore_ascii_case(LOCAL_TOKEN_HEADER)
+            || name.eq_ignore_ascii_case("connection")
+            || (auth.robot_token.is_some() && name.eq_ignore_ascii_case("authorization"));
+        if !dropped {
+            rewritten.push_str(line);
+            rewritten.push_str("\r\n");
+        }
+    }
+    if let Some(token) = &auth.robot_token {
+        rewritten.push_str(&format!("Authorization: Bearer {}\r\n", token));
+    }
+    rewritten.push_str("Connection: close\r\n\r\n");
+
+    RewrittenHead::Forward(rewritten.into_bytes())
+}
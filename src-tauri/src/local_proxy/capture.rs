@@ -0,0 +1,145 @@
+//! HTTP capture
+//!
+//! Opt-in recording of proxied HTTP exchanges (method, path, status, latency and the
+//! start of both bodies) into a ring buffer, for debugging daemon API issues in WiFi
+//! mode. Bodies are stored as received: chunked or compressed responses aren't decoded.
+
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Instant;
+
+/// Exchanges kept in the ring buffer
+const CAPTURE_CAPACITY: usize = 200;
+/// Body bytes kept per request / response
+const MAX_BODY_BYTES: usize = 4 * 1024;
+/// Raw response bytes inspected (head + start of body)
+const MAX_RESPONSE_BYTES: usize = 16 * 1024;
+
+#[derive(Debug, Serialize, Clone)]
+pub struct CapturedExchange {
+    pub id: u64,
+    /// Unix millis when the request was forwarded
+    pub timestamp: u64,
+    pub port: u16,
+    pub method: String,
+    pub path: String,
+    /// None if the robot never answered
+    pub status: Option<u16>,
+    /// Time to the first response byte
+    pub latency_ms: Option<u64>,
+    pub request_body: String,
+    pub response_body: String,
+    /// Bodies were cut at MAX_BODY_BYTES
+    pub truncated: bool,
+}
+
+#[derive(Default)]
+pub struct ProxyCapture {
+    enabled: AtomicBool,
+    next_id: AtomicU64,
+    entries: Mutex<VecDeque<CapturedExchange>>,
+}
+
+impl ProxyCapture {
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    pub fn entries(&self) -> Vec<CapturedExchange> {
+        self.entries.lock().unwrap().iter().cloned().collect()
+    }
+
+    pub fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+
+    fn push(&self, mut entry: CapturedExchange) {
+        entry.id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let mut entries = self.entries.lock().unwrap();
+        if entries.len() >= CAPTURE_CAPACITY {
+            entries.pop_front();
+        }
+        entries.push_back(entry);
+    }
+}
+
+/// One exchange being recorded; fed by the two copy directions, then finished
+pub struct ExchangeRecorder {
+    port: u16,
+    method: String,
+    path: String,
+    timestamp: u64,
+    started: Instant,
+    request_body: Vec<u8>,
+    request_truncated: bool,
+    response: Vec<u8>,
+    first_byte: Option<Instant>,
+}
+
+impl ExchangeRecorder {
+    /// Start recording from the forwarded request head and the body bytes already read
+    pub fn new(port: u16, head: &[u8], body_start: &[u8]) -> Self {
+        let head = String::from_utf8_lossy(head);
+        let mut parts = head.lines().next().unwrap_or_default().split(' ');
+        let mut recorder = Self {
+            port,
+            method: parts.next().unwrap_or_default().to_string(),
+            path: parts.next().unwrap_or_default().to_string(),
+            timestamp: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_millis() as u64)
+                .unwrap_or(0),
+            started: Instant::now(),
+            request_body: Vec::new(),
+            request_truncated: false,
+            response: Vec::new(),
+            first_byte: None,
+        };
+        recorder.on_request(body_start);
+        recorder
+    }
+
+    pub fn on_request(&mut self, chunk: &[u8]) {
+        let room = MAX_BODY_BYTES.saturating_sub(self.request_body.len());
+        self.request_truncated |= chunk.len() > room;
+        self.request_body.extend_from_slice(&chunk[..chunk.len().min(room)]);
+    }
+
+    pub fn on_response(&mut self, chunk: &[u8]) {
+        self.first_byte.get_or_insert_with(Instant::now);
+        let room = MAX_RESPONSE_BYTES.saturating_sub(self.response.len());
+        self.response.extend_from_slice(&chunk[..chunk.len().min(room)]);
+    }
+
+    pub fn finish(self, capture: &ProxyCapture) {
+        let (status, mut body) = match self.response.windows(4).position(|w| w == b"\r\n\r\n") {
+            Some(end) => {
+                let head = String::from_utf8_lossy(&self.response[..end]);
+                let status = head.split(' ').nth(1).and_then(|code| code.parse().ok());
+                (status, self.response[end + 4..].to_vec())
+            }
+            None => (None, Vec::new()),
+        };
+        let response_truncated = body.len() > MAX_BODY_BYTES;
+        body.truncate(MAX_BODY_BYTES);
+
+        capture.push(CapturedExchange {
+            id: 0,
+            timestamp: self.timestamp,
+            port: self.port,
+            method: self.method,
+            path: self.path,
+            status,
+            latency_ms: self.first_byte.map(|at| at.duration_since(self.started).as_millis() as u64),
+            request_body: String::from_utf8_lossy(&self.request_body).to_string(),
+            response_body: String::from_utf8_lossy(&body).to_string(),
+            truncated: self.request_truncated || response_truncated,
+        });
+    }
+}
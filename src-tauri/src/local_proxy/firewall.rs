@@ -0,0 +1,344 @@
+//! Route firewall
+//!
+//! Third-party app UIs (pages served by robot apps, opened in app webviews) reach the
+//! daemon API through the proxy like the app's own frontend does. Requests from such
+//! origins are matched against route rules: allowed, blocked (403), or held until the
+//! user confirms (`proxy-route-confirm` event, answered with `respond_proxy_route`).
+//!
+//! Requests without an `Origin` (SDK, scripts) and from trusted origins (this app's
+//! webview) are never filtered, except by `require_unlock` rules: those refuse a route to
+//! every client while the UI lock (see lock/mod.rs) is engaged. Nothing here depends on
+//! WiFi mode, so a local reverse proxy for USB mode can use the same firewall.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, RwLock};
+use std::time::Duration;
+use tokio::sync::oneshot;
+
+/// Origins of this app's own webview (production on macOS/Linux, Windows, dev server)
+const APP_ORIGINS: &[&str] = &["tauri://localhost", "http://tauri.localhost", "https://tauri.localhost", "http://localhost:1420"];
+/// Unanswered confirmations are denied after this long
+const CONFIRM_TIMEOUT: Duration = Duration::from_secs(30);
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum RouteAction {
+    Allow,
+    /// Ask the user (denied if unanswered)
+    Confirm,
+    Block,
+    /// Refused for every client, this app included, until the UI is unlocked with the PIN
+    RequireUnlock,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct RouteRule {
+    /// HTTP method, None = any
+    #[serde(default)]
+    pub method: Option<String>,
+    /// Path prefix, e.g. "/api/update/"
+    pub path: String,
+    pub action: RouteAction,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct FirewallConfig {
+    pub enabled: bool,
+    /// First matching rule wins; unmatched routes are allowed
+    pub rules: Vec<RouteRule>,
+    /// Origins treated like the app's own webview, e.g. "http://localhost:7860"
+    pub trusted_origins: Vec<String>,
+}
+
+fn rule(method: Option<&str>, path: &str, action: RouteAction) -> RouteRule {
+    RouteRule {
+        method: method.map(str::to_string),
+        path: path.to_string(),
+        action,
+    }
+}
+
+impl Default for FirewallConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            rules: vec![
+                // Firmware flashing and daemon self-update
+                rule(None, "/api/firmware", RouteAction::Block),
+                rule(Some("POST"), "/api/update/", RouteAction::Block),
+                // Motor limits and low-level motor configuration
+                rule(Some("POST"), "/api/motors/set_torque_limit", RouteAction::Block),
+                rule(Some("POST"), "/api/motors/set_current_limit", RouteAction::Block),
+                // Daemon lifecycle and installing / removing other apps
+                rule(Some("POST"), "/api/daemon/", RouteAction::Confirm),
+                rule(Some("POST"), "/api/apps/install", RouteAction::Confirm),
+                rule(Some("POST"), "/api/apps/remove/", RouteAction::Confirm),
+                // Calibration reset, gated by the UI lock
+                rule(Some("POST"), "/api/calibration", RouteAction::RequireUnlock),
+            ],
+            trusted_origins: Vec::new(),
+        }
+    }
+}
+
+impl FirewallConfig {
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(rule) = self.rules.iter().find(|rule| !rule.path.starts_with('/')) {
+            return Err(format!("Route rule path must start with '/': {}", rule.path));
+        }
+        Ok(())
+    }
+}
+
+/// Emitted as `proxy-route-confirm`
+#[derive(Debug, Serialize, Clone)]
+pub struct RouteConfirmRequest {
+    pub id: u64,
+    pub origin: String,
+    pub method: String,
+    pub path: String,
+}
+
+pub enum Verdict {
+    Allow,
+    Block(String),
+    /// Held: emit the request, then wait for the answer
+    Confirm(RouteConfirmRequest, oneshot::Receiver<bool>),
+}
+
+#[derive(Default)]
+pub struct RouteFirewall {
+    config: RwLock<FirewallConfig>,
+    pending: Mutex<HashMap<u64, oneshot::Sender<bool>>>,
+    next_id: AtomicU64,
+}
+
+// ============================================================================
+// HELPER FUNCTIONS
+// ============================================================================
+
+/// Method, path (without query) and Origin of a request head
+fn parse_head(head: &str) -> Option<(&str, &str, Option<&str>)> {
+    let mut lines = head.split("\r\n");
+    let mut request_line = lines.next()?.split(' ');
+    let method = request_line.next()?;
+    let path = request_line.next()?;
+    let path = path.split_once('?').map_or(path, |(path, _)| path);
+    let origin = lines.take_while(|line| !line.is_empty()).find_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        name.trim().eq_ignore_ascii_case("origin").then(|| value.trim())
+    });
+    Some((method, path, origin))
+}
+
+/// Path as the daemon routes it: percent-decoded, `//` collapsed, `.` / `..` resolved,
+/// lowercased (so `/API//%66irmware` can't sneak past a `/api/firmware` rule)
+fn normalize_path(path: &str) -> String {
+    let bytes = path.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes.get(i + 1..i + 3).and_then(|hex| std::str::from_utf8(hex).ok());
+        match (bytes[i], hex.and_then(|hex| u8::from_str_radix(hex, 16).ok())) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    let decoded = String::from_utf8_lossy(&decoded).to_lowercase();
+
+    let mut segments: Vec<&str> = Vec::new();
+    for segment in decoded.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                segments.pop();
+            }
+            segment => segments.push(segment),
+        }
+    }
+    let mut normalized = format!("/{}", segments.join("/"));
+    // Keep the trailing slash: rules like "/api/update/" rely on it
+    if decoded.ends_with('/') && !segments.is_empty() {
+        normalized.push('/');
+    }
+    normalized
+}
+
+impl RouteFirewall {
+    pub fn config(&self) -> FirewallConfig {
+        self.config.read().unwrap().clone()
+    }
+
+    pub fn set_config(&self, config: FirewallConfig) {
+        *self.config.write().unwrap() = config;
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.config.read().unwrap().enabled
+    }
+
+    /// Decide what happens to a request, from its complete head
+    pub fn check(&self, head: &str) -> Verdict {
+        let config = self.config.read().unwrap();
+        let Some((method, path, origin)) = parse_head(head) else {
+            return Verdict::Allow;
+        };
+        let path = normalize_path(path);
+        let matches = |rule: &&RouteRule| {
+            rule.method.as_deref().is_none_or(|rule_method| rule_method.eq_ignore_ascii_case(method))
+                && path.starts_with(&rule.path.to_lowercase())
+        };
+
+        // Lock-gated routes apply to every client, even with the firewall turned off
+        let locked = config
+            .rules
+            .iter()
+            .filter(|rule| rule.action == RouteAction::RequireUnlock)
+            .any(|rule| matches(&rule));
+        if locked {
+            if let Err(e) = crate::lock::ensure_unlocked(&format!("{} {}", method, path)) {
+                return Verdict::Block(e);
+            }
+        }
+
+        if !config.enabled {
+            return Verdict::Allow;
+        }
+        let Some(origin) = origin else {
+            return Verdict::Allow;
+        };
+        let origin = origin.trim_end_matches('/');
+        let trusted = APP_ORIGINS.contains(&origin)
+            || config.trusted_origins.iter().any(|trusted| trusted.trim_end_matches('/') == origin);
+        if trusted {
+            return Verdict::Allow;
+        }
+
+        let matched = config
+            .rules
+            .iter()
+            .filter(|rule| rule.action != RouteAction::RequireUnlock)
+            .find(matches);
+        match matched.map(|rule| rule.action) {
+            None | Some(RouteAction::Allow) | Some(RouteAction::RequireUnlock) => Verdict::Allow,
+            Some(RouteAction::Block) => Verdict::Block(format!("{} {} is not allowed from {}", method, path, origin)),
+            Some(RouteAction::Confirm) => {
+                let id = self.next_id.fetch_add(1, Ordering::Relaxed) + 1;
+                let (sender, receiver) = oneshot::channel();
+                self.pending.lock().unwrap().insert(id, sender);
+                let request = RouteConfirmRequest {
+                    id,
+                    origin: origin.to_string(),
+                    method: method.to_string(),
+                    path,
+                };
+                Verdict::Confirm(request, receiver)
+            }
+        }
+    }
+
+    /// Wait for the user's answer to a held request (denied on timeout)
+    pub async fn wait_confirmation(&self, id: u64, receiver: oneshot::Receiver<bool>) -> bool {
+        let allowed = matches!(tokio::time::timeout(CONFIRM_TIMEOUT, receiver).await, Ok(Ok(true)));
+        self.pending.lock().unwrap().remove(&id);
+        allowed
+    }
+
+    /// Answer a held request; false if it is no longer pending
+    pub fn respond(&self, id: u64, allow: bool) -> bool {
+        match self.pending.lock().unwrap().remove(&id) {
+            Some(sender) => sender.send(allow).is_ok(),
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const APP_ORIGIN: &str = "http://localhost:7860";
+
+    fn head(method: &str, path: &str, origin: Option<&str>) -> String {
+        let origin = origin.map(|origin| format!("Origin: {}\r\n", origin)).unwrap_or_default();
+        format!("{} {} HTTP/1.1\r\nHost: localhost:8000\r\n{}\r\n", method, path, origin)
+    }
+
+    fn is_blocked(firewall: &RouteFirewall, method: &str, path: &str) -> bool {
+        matches!(firewall.check(&head(method, path, Some(APP_ORIGIN))), Verdict::Block(_))
+    }
+
+    #[test]
+    fn blocks_firmware_from_third_party_origins() {
+        let firewall = RouteFirewall::default();
+        assert!(is_blocked(&firewall, "GET", "/api/firmware"));
+        assert!(is_blocked(&firewall, "POST", "/api/update/start"));
+        assert!(!is_blocked(&firewall, "GET", "/api/state/full"));
+    }
+
+    #[test]
+    fn blocks_encoded_and_disguised_paths() {
+        let firewall = RouteFirewall::default();
+        for path in [
+            "/api/%66irmware",
+            "/api//firmware",
+            "//api///firmware/update",
+            "/API/firmware",
+            "/api/FIRMWARE?x=1",
+            "/api/./firmware",
+            "/api/state/../firmware",
+            "/api%2Ffirmware",
+        ] {
+            assert!(is_blocked(&firewall, "GET", path), "{} was not blocked", path);
+        }
+        assert!(is_blocked(&firewall, "POST", "/api//update//start"));
+        assert!(is_blocked(&firewall, "post", "/Api/Update/start"));
+    }
+
+    #[test]
+    fn allows_requests_without_origin_or_from_trusted_origins() {
+        let firewall = RouteFirewall::default();
+        assert!(matches!(firewall.check(&head("GET", "/api/firmware", None)), Verdict::Allow));
+        let app_head = head("GET", "/api/firmware", Some("tauri://localhost"));
+        assert!(matches!(firewall.check(&app_head), Verdict::Allow));
+    }
+
+    #[test]
+    fn holds_confirm_routes_with_the_normalized_path() {
+        let firewall = RouteFirewall::default();
+        match firewall.check(&head("POST", "/api//apps/%69nstall", Some(APP_ORIGIN))) {
+            Verdict::Confirm(request, _) => assert_eq!(request.path, "/api/apps/install"),
+            _ => panic!("install was not held for confirmation"),
+        }
+    }
+
+    #[test]
+    fn lock_gated_routes_pass_while_no_pin_is_set() {
+        let firewall = RouteFirewall::default();
+        for origin in [None, Some("tauri://localhost"), Some(APP_ORIGIN)] {
+            let verdict = firewall.check(&head("POST", "/api/calibration/reset", origin));
+            assert!(matches!(verdict, Verdict::Allow));
+        }
+    }
+
+    #[test]
+    fn normalizes_paths() {
+        assert_eq!(normalize_path("/api//update/"), "/api/update/");
+        assert_eq!(normalize_path("/"), "/");
+        assert_eq!(normalize_path("/../.."), "/");
+        assert_eq!(normalize_path("/a%2"), "/a%2");
+    }
+}
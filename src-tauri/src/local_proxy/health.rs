@@ -0,0 +1,68 @@
+//! Remote host heartbeat
+//!
+//! Tracks consecutive failed probes of the robot so a single WiFi blip doesn't
+//! flip the UI: the proxy is reported degraded after a few missed heartbeats and
+//! recovered on the next successful one.
+
+use serde::Serialize;
+use std::time::Instant;
+
+/// Consecutive failed heartbeats before `proxy-degraded` is emitted
+const FAILURES_BEFORE_DEGRADED: u32 = 2;
+
+#[derive(Default)]
+pub struct Heartbeat {
+    failures: u32,
+    degraded_since: Option<Instant>,
+}
+
+/// Payload of `proxy-degraded`
+#[derive(Debug, Serialize, Clone)]
+pub struct ProxyDegraded {
+    pub target_host: String,
+    pub failed_heartbeats: u32,
+}
+
+/// Payload of `proxy-recovered`
+#[derive(Debug, Serialize, Clone)]
+pub struct ProxyRecovered {
+    pub target_host: String,
+    pub downtime_ms: u64,
+}
+
+pub enum HealthChange {
+    Degraded(ProxyDegraded),
+    Recovered(ProxyRecovered),
+}
+
+impl Heartbeat {
+    pub fn is_degraded(&self) -> bool {
+        self.degraded_since.is_some()
+    }
+
+    /// Record one heartbeat; returns the transition to report, if any
+    pub fn record(&mut self, target_host: &str, reachable: bool) -> Option<HealthChange> {
+        if reachable {
+            self.failures = 0;
+            let since = self.degraded_since.take()?;
+            return Some(HealthChange::Recovered(ProxyRecovered {
+                target_host: target_host.to_string(),
+                downtime_ms: since.elapsed().as_millis() as u64,
+            }));
+        }
+
+        self.failures += 1;
+        if self.failures < FAILURES_BEFORE_DEGRADED || self.degraded_since.is_some() {
+            return None;
+        }
+        self.degraded_since = Some(Instant::now());
+        Some(HealthChange::Degraded(ProxyDegraded {
+            target_host: target_host.to_string(),
+            failed_heartbeats: self.failures,
+        }))
+    }
+
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
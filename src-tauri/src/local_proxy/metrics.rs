@@ -0,0 +1,192 @@
+//! Proxy metrics
+//!
+//! Per-port counters (connections, bytes, last error) and the remote reachability
+//! found by the status loop, so the UI can tell a stopped proxy from an unreachable
+//! robot or a blocked port.
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+use super::shaping::RateLimiter;
+use super::PortMapping;
+
+#[derive(Default)]
+pub struct PortMetrics {
+    listening: AtomicBool,
+    active_connections: AtomicUsize,
+    total_connections: AtomicU64,
+    /// Bytes received from the robot (sent to local clients)
+    bytes_in: AtomicU64,
+    /// Bytes sent to the robot (received from local clients)
+    bytes_out: AtomicU64,
+    last_error: Mutex<Option<String>>,
+    /// None until the first probe
+    remote_reachable: Mutex<Option<bool>>,
+    /// WebSocket frames sent to local clients / dropped by the rate limiter
+    frames_forwarded: AtomicU64,
+    frames_dropped: AtomicU64,
+    /// Bytes in + out at the last throughput update, and the rate measured then
+    last_total_bytes: AtomicU64,
+    throughput_bytes_per_sec: AtomicU64,
+}
+
+impl PortMetrics {
+    pub fn set_listening(&self, listening: bool) {
+        self.listening.store(listening, Ordering::Relaxed);
+    }
+
+    pub fn record_error(&self, error: impl ToString) {
+        *self.last_error.lock().unwrap() = Some(error.to_string());
+    }
+
+    pub fn add_bytes_in(&self, bytes: u64) {
+        self.bytes_in.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    pub fn add_bytes_out(&self, bytes: u64) {
+        self.bytes_out.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    pub fn frame_forwarded(&self) {
+        self.frames_forwarded.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn frame_dropped(&self) {
+        self.frames_dropped.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Measure throughput since the previous call (called every `elapsed` by the status loop)
+    pub fn update_throughput(&self, elapsed: std::time::Duration) {
+        let total = self.bytes_in.load(Ordering::Relaxed) + self.bytes_out.load(Ordering::Relaxed);
+        let previous = self.last_total_bytes.swap(total, Ordering::Relaxed);
+        let rate = total.saturating_sub(previous) as f64 / elapsed.as_secs_f64().max(0.001);
+        self.throughput_bytes_per_sec.store(rate as u64, Ordering::Relaxed);
+    }
+
+    pub fn set_remote_reachable(&self, reachable: Option<bool>) {
+        *self.remote_reachable.lock().unwrap() = reachable;
+    }
+
+    /// Count a connection for as long as the guard lives
+    pub fn track_connection(self: &Arc<Self>) -> ConnectionGuard {
+        self.active_connections.fetch_add(1, Ordering::Relaxed);
+        self.total_connections.fetch_add(1, Ordering::Relaxed);
+        ConnectionGuard(self.clone())
+    }
+
+    fn snapshot(&self, mapping: &PortMapping) -> PortStatus {
+        PortStatus {
+            port: mapping.local,
+            remote_port: mapping.remote,
+            rate_limit_kbps: mapping.rate_limit_kbps,
+            listening: self.listening.load(Ordering::Relaxed),
+            active_connections: self.active_connections.load(Ordering::Relaxed),
+            total_connections: self.total_connections.load(Ordering::Relaxed),
+            bytes_in: self.bytes_in.load(Ordering::Relaxed),
+            bytes_out: self.bytes_out.load(Ordering::Relaxed),
+            last_error: self.last_error.lock().unwrap().clone(),
+            remote_reachable: *self.remote_reachable.lock().unwrap(),
+            frames_forwarded: self.frames_forwarded.load(Ordering::Relaxed),
+            frames_dropped: self.frames_dropped.load(Ordering::Relaxed),
+            throughput_bytes_per_sec: self.throughput_bytes_per_sec.load(Ordering::Relaxed),
+        }
+    }
+}
+
+pub struct ConnectionGuard(Arc<PortMetrics>);
+
+impl Drop for ConnectionGuard {
+    fn drop(&mut self) {
+        self.0.active_connections.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Metrics of every proxied port, keyed by local port
+#[derive(Default)]
+pub struct ProxyMetrics {
+    ports: Mutex<BTreeMap<u16, Arc<PortMetrics>>>,
+}
+
+impl ProxyMetrics {
+    /// Metrics of a local port (created on first use)
+    pub fn port(&self, port: u16) -> Arc<PortMetrics> {
+        self.ports.lock().unwrap().entry(port).or_default().clone()
+    }
+
+    /// Drop the metrics of ports no longer proxied
+    pub fn retain(&self, ports: &[u16]) {
+        self.ports.lock().unwrap().retain(|port, _| ports.contains(port));
+    }
+
+    pub fn ports(&self) -> Vec<Arc<PortMetrics>> {
+        self.ports.lock().unwrap().values().cloned().collect()
+    }
+
+    pub fn snapshot(&self, mappings: &[PortMapping]) -> Vec<PortStatus> {
+        mappings
+            .iter()
+            .map(|mapping| self.port(mapping.local).snapshot(mapping))
+            .collect()
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct PortStatus {
+    /// Local port
+    pub port: u16,
+    pub remote_port: u16,
+    pub rate_limit_kbps: Option<u32>,
+    pub listening: bool,
+    pub active_connections: usize,
+    pub total_connections: u64,
+    pub bytes_in: u64,
+    pub bytes_out: u64,
+    pub last_error: Option<String>,
+    pub remote_reachable: Option<bool>,
+    pub frames_forwarded: u64,
+    pub frames_dropped: u64,
+    /// Measured over the last status interval (for a connection-quality indicator)
+    pub throughput_bytes_per_sec: u64,
+}
+
+/// Payload of `get_proxy_status` and `proxy-status` events
+#[derive(Debug, Serialize, Clone)]
+pub struct ProxyStatus {
+    pub running: bool,
+    pub target_host: Option<String>,
+    /// Robot missed several heartbeats (see `proxy-degraded`)
+    pub degraded: bool,
+    pub ports: Vec<PortStatus>,
+}
+
+/// tokio::io::copy that reports chunks as they flow (long-lived video streams never complete),
+/// optionally rate limited
+pub async fn copy_counted<R, W>(
+    reader: &mut R,
+    writer: &mut W,
+    limiter: Option<&RateLimiter>,
+    mut on_chunk: impl FnMut(&[u8]),
+) -> std::io::Result<u64>
+where
+    R: AsyncRead + Unpin + ?Sized,
+    W: AsyncWrite + Unpin + ?Sized,
+{
+    let mut buf = vec![0u8; 16 * 1024];
+    let mut total = 0u64;
+    loop {
+        let n = reader.read(&mut buf).await?;
+        if n == 0 {
+            writer.shutdown().await.ok();
+            return Ok(total);
+        }
+        if let Some(limiter) = limiter {
+            limiter.acquire(n).await;
+        }
+        writer.write_all(&buf[..n]).await?;
+        total += n as u64;
+        on_chunk(&buf[..n]);
+    }
+}
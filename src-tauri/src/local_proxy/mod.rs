@@ -0,0 +1,839 @@
+//! Local Proxy Module
+//!
+//! Forwards HTTP and WebSocket connections from localhost to a remote host.
+//! Supports multiple ports (8000 for daemon API, 8042 for video streams by default),
+//! configurable at runtime with an optional local -> remote port mapping.
+//! This bypasses browser Private Network Access (PNA) restrictions.
+//!
+//! The proxy only runs when in WiFi mode (when a target host is set).
+//!
+//! Optional token authentication (see auth.rs): the robot token is injected into
+//! upstream requests, and local clients can be required to present a session token.
+//!
+//! Ports can be rate limited (see shaping.rs), typically the 8042 video path so it
+//! can't starve the control channel on a weak WiFi link.
+//!
+//! Opt-in HTTP capture (see capture.rs) records proxied exchanges for get_proxy_capture.
+//!
+//! Requests from third-party app UIs go through a route firewall (see firewall.rs) that
+//! blocks or asks confirmation for dangerous daemon endpoints.
+//!
+//! Known robots can be saved as profiles (see profiles.rs) with their own token and ports.
+//!
+//! While running, the robot is probed every few seconds (heartbeat) and per-port metrics
+//! are emitted as `proxy-status` (same payload as get_proxy_status). Missed heartbeats
+//! emit `proxy-degraded`, the next successful one `proxy-recovered`. WebSocket upstreams
+//! that drop are re-established transparently while the local client stays connected.
+//! Latency and loss are measured separately for a signal-bars indicator (see quality.rs).
+
+pub mod auth;
+pub mod capture;
+pub mod firewall;
+mod health;
+pub mod metrics;
+pub mod profiles;
+pub mod quality;
+mod shaping;
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::sync::{Arc, OnceLock};
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{RwLock, Mutex};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, ReadBuf};
+use tokio::task::JoinHandle;
+use tokio_tungstenite::connect_async;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::handshake::server::{ErrorResponse, Request, Response};
+use tokio_tungstenite::tungstenite::http::{header, HeaderValue, StatusCode};
+use futures_util::{StreamExt, SinkExt};
+
+use auth::{ProxyAuth, RewrittenHead};
+use capture::{ExchangeRecorder, ProxyCapture};
+use firewall::{RouteFirewall, Verdict};
+use health::{HealthChange, Heartbeat};
+use metrics::{copy_counted, PortMetrics, ProxyMetrics, ProxyStatus};
+use shaping::RateLimiter;
+
+/// Ports proxied by default (local -> remote with same port)
+const DEFAULT_PROXY_PORTS: &[u16] = &[8000, 8042];
+/// Largest HTTP request head accepted when requests are rewritten
+const MAX_REQUEST_HEAD: usize = 16 * 1024;
+/// Heartbeat (remote reachability probe) and `proxy-status` event period
+const STATUS_INTERVAL: Duration = Duration::from_secs(2);
+const PROBE_TIMEOUT: Duration = Duration::from_secs(1);
+/// How long a dropped WebSocket upstream is retried before the local client is closed
+const WS_RECONNECT_WINDOW: Duration = Duration::from_secs(15);
+const WS_RECONNECT_MAX_DELAY: Duration = Duration::from_secs(2);
+
+type RemoteWebSocket = tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<TcpStream>>;
+
+/// A proxied port: localhost:local -> target:remote
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct PortMapping {
+    pub local: u16,
+    pub remote: u16,
+    /// Cap on robot -> app traffic for this port (None = unlimited)
+    #[serde(default)]
+    pub rate_limit_kbps: Option<u32>,
+}
+
+pub fn default_port_mappings() -> Vec<PortMapping> {
+    DEFAULT_PROXY_PORTS
+        .iter()
+        .map(|&port| PortMapping { local: port, remote: port, rate_limit_kbps: None })
+        .collect()
+}
+
+/// Shared state for the proxy
+pub struct LocalProxyState {
+    pub target_host: RwLock<Option<String>>,
+    /// Ports to proxy (applied live while running)
+    pub port_mappings: RwLock<Vec<PortMapping>>,
+    /// Running listeners by local port (so we can abort them)
+    listeners: Mutex<HashMap<u16, (PortMapping, JoinHandle<()>)>>,
+    /// Heartbeat task; Some while the proxy runs
+    status_handle: Mutex<Option<JoinHandle<()>>>,
+    pub auth: RwLock<ProxyAuth>,
+    pub metrics: ProxyMetrics,
+    pub capture: ProxyCapture,
+    pub firewall: RouteFirewall,
+    heartbeat: std::sync::Mutex<Heartbeat>,
+    pub quality: quality::QualityMonitor,
+    /// Saved remote robots (loaded in setup)
+    pub profiles: profiles::ProfileStore,
+    /// Used to emit `proxy-status` (set in setup)
+    app_handle: OnceLock<AppHandle>,
+}
+
+impl LocalProxyState {
+    pub fn new() -> Self {
+        Self {
+            target_host: RwLock::new(None),
+            port_mappings: RwLock::new(default_port_mappings()),
+            listeners: Mutex::new(HashMap::new()),
+            status_handle: Mutex::new(None),
+            auth: RwLock::new(ProxyAuth::new()),
+            metrics: ProxyMetrics::default(),
+            capture: ProxyCapture::default(),
+            firewall: RouteFirewall::default(),
+            heartbeat: std::sync::Mutex::new(Heartbeat::default()),
+            quality: quality::QualityMonitor::default(),
+            profiles: profiles::ProfileStore::default(),
+            app_handle: OnceLock::new(),
+        }
+    }
+
+    pub fn set_app_handle(&self, app_handle: AppHandle) {
+        let _ = self.app_handle.set(app_handle);
+    }
+
+    pub async fn status(&self) -> ProxyStatus {
+        let mappings = self.port_mappings.read().await.clone();
+        ProxyStatus {
+            running: self.status_handle.lock().await.is_some(),
+            target_host: self.target_host.read().await.clone(),
+            degraded: self.heartbeat.lock().unwrap().is_degraded(),
+            ports: self.metrics.snapshot(&mappings),
+        }
+    }
+
+    fn emit<S: serde::Serialize + Clone>(&self, event: &str, payload: S) {
+        if let Some(app_handle) = self.app_handle.get() {
+            let _ = app_handle.emit(event, payload);
+        }
+    }
+
+    async fn emit_status(&self) {
+        self.emit("proxy-status", self.status().await);
+    }
+}
+
+impl Default for LocalProxyState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Start the local proxy servers on all configured ports.
+async fn start_local_proxy(state: Arc<LocalProxyState>) {
+    let mut status_handle = state.status_handle.lock().await;
+
+    // Don't start if already running
+    if status_handle.is_some() {
+        println!("[proxy] ⚠️  Proxy already running");
+        return;
+    }
+
+    sync_listeners(&state).await;
+
+    let state_clone = state.clone();
+    *status_handle = Some(tokio::spawn(async move {
+        run_status_loop(state_clone).await;
+    }));
+    quality::start(&state);
+
+    println!("[proxy] 🚀 Proxy started for WiFi mode");
+}
+
+/// Stop all running proxy servers
+async fn stop_local_proxy(state: &Arc<LocalProxyState>) {
+    let Some(status_handle) = state.status_handle.lock().await.take() else {
+        return;
+    };
+
+    // Abort all proxy tasks
+    status_handle.abort();
+    for (_, (_, handle)) in state.listeners.lock().await.drain() {
+        handle.abort();
+    }
+
+    quality::stop(state);
+    state.heartbeat.lock().unwrap().reset();
+    for metrics in state.metrics.ports() {
+        metrics.set_listening(false);
+        metrics.set_remote_reachable(None);
+    }
+    state.emit_status().await;
+
+    println!("[proxy] 🛑 Proxy stopped");
+}
+
+/// Start/stop listeners so they match port_mappings (changed mappings are restarted)
+async fn sync_listeners(state: &Arc<LocalProxyState>) {
+    let mappings = state.port_mappings.read().await.clone();
+    let mut listeners = state.listeners.lock().await;
+
+    listeners.retain(|local, (mapping, handle)| {
+        let keep = mappings.contains(mapping);
+        if !keep {
+            handle.abort();
+            state.metrics.port(*local).set_listening(false);
+            println!("[proxy] ➖ Stopped proxying localhost:{}", local);
+        }
+        keep
+    });
+
+    for &mapping in &mappings {
+        if listeners.contains_key(&mapping.local) {
+            continue;
+        }
+        let state_clone = state.clone();
+        let handle = tokio::spawn(async move {
+            start_port_proxy(state_clone, mapping).await;
+        });
+        listeners.insert(mapping.local, (mapping, handle));
+    }
+
+    let locals: Vec<u16> = mappings.iter().map(|m| m.local).collect();
+    state.metrics.retain(&locals);
+}
+
+/// Heartbeat: probe the remote ports and emit `proxy-status` until the proxy stops
+async fn run_status_loop(state: Arc<LocalProxyState>) {
+    loop {
+        if let Some(host) = state.target_host.read().await.clone() {
+            let mappings = state.port_mappings.read().await.clone();
+            let mut reachable = false;
+            for metrics in state.metrics.ports() {
+                metrics.update_throughput(STATUS_INTERVAL);
+            }
+            for mapping in &mappings {
+                let probe = tokio::time::timeout(PROBE_TIMEOUT, TcpStream::connect((host.as_str(), mapping.remote))).await;
+                let port_reachable = matches!(probe, Ok(Ok(_)));
+                state.metrics.port(mapping.local).set_remote_reachable(Some(port_reachable));
+                reachable |= port_reachable;
+            }
+
+            let change = state.heartbeat.lock().unwrap().record(&host, reachable);
+            match change {
+                Some(HealthChange::Degraded(payload)) => {
+                    println!("[proxy] ⚠️  {} missed {} heartbeats - degraded", host, payload.failed_heartbeats);
+                    state.emit("proxy-degraded", payload);
+                }
+                Some(HealthChange::Recovered(payload)) => {
+                    println!("[proxy] ✅ {} reachable again after {}ms", host, payload.downtime_ms);
+                    state.emit("proxy-recovered", payload);
+                }
+                None => {}
+            }
+        }
+        state.emit_status().await;
+        tokio::time::sleep(STATUS_INTERVAL).await;
+    }
+}
+
+/// Start a proxy server for a specific port
+async fn start_port_proxy(state: Arc<LocalProxyState>, mapping: PortMapping) {
+    let PortMapping { local: port, remote: remote_port, rate_limit_kbps } = mapping;
+    let metrics = state.metrics.port(port);
+    let limiter = rate_limit_kbps.map(|kbps| Arc::new(RateLimiter::new(kbps)));
+    let bind_addr = format!("127.0.0.1:{}", port);
+    let listener = match TcpListener::bind(&bind_addr).await {
+        Ok(l) => {
+            println!("[proxy] ✅ Listening on http://localhost:{} (remote port {})", port, remote_port);
+            if let Some(kbps) = rate_limit_kbps {
+                println!("[proxy] 🚦 Port {} limited to {} kbps", port, kbps);
+            }
+            metrics.set_listening(true);
+            l
+        }
+        Err(e) => {
+            metrics.record_error(format!("Failed to bind: {}", e));
+            if e.kind() == std::io::ErrorKind::AddrInUse {
+                println!("[proxy] ⏭️  Port {} already in use - skipping", port);
+            } else {
+                eprintln!("[proxy] ❌ Failed to bind to port {}: {}", port, e);
+            }
+            return;
+        }
+    };
+
+    loop {
+        match listener.accept().await {
+            Ok((stream, addr)) => {
+                let state_clone = state.clone();
+                let metrics = metrics.clone();
+                let limiter = limiter.clone();
+                tokio::spawn(async move {
+                    let _connection = metrics.track_connection();
+                    if let Err(e) = handle_connection(stream, state_clone, &metrics, limiter.as_deref(), addr, remote_port).await {
+                        eprintln!("[proxy] ❌ Connection error from {} on port {}: {}", addr, port, e);
+                        metrics.record_error(e);
+                    }
+                });
+            }
+            Err(e) => {
+                eprintln!("[proxy] ❌ Accept error on port {}: {}", port, e);
+                metrics.record_error(format!("Accept failed: {}", e));
+            }
+        }
+    }
+}
+
+/// Handle a connection - detect if WebSocket or HTTP and route accordingly
+async fn handle_connection(
+    mut stream: TcpStream,
+    state: Arc<LocalProxyState>,
+    metrics: &PortMetrics,
+    limiter: Option<&RateLimiter>,
+    addr: std::net::SocketAddr,
+    port: u16,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    // Get target host
+    let target_host = {
+        let host = state.target_host.read().await;
+        match host.as_ref() {
+            Some(h) => h.clone(),
+            None => {
+                eprintln!("[proxy] ❌ No target host configured");
+                let response = "HTTP/1.1 502 Bad Gateway\r\nContent-Length: 23\r\n\r\nNo target host configured";
+                stream.write_all(response.as_bytes()).await?;
+                return Ok(());
+            }
+        }
+    };
+
+    // Read the whole request head first: the firewall decides on the bytes that are forwarded
+    // (a peek could stop before the Origin header arrives)
+    let (head, body_start) = match read_request_head(&mut stream).await {
+        Ok(read) => read,
+        // Closed before sending anything (port probes)
+        Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(()),
+        Err(e) => return Err(e.into()),
+    };
+
+    // Check if this is a WebSocket upgrade request
+    let is_websocket = head.to_lowercase().contains("upgrade: websocket");
+
+    // Third-party app UIs don't get the dangerous daemon routes (checked on every request:
+    // with the firewall on, HTTP connections are closed after one request)
+    match state.firewall.check(&head) {
+        Verdict::Allow => {}
+        Verdict::Block(reason) => {
+            eprintln!("[proxy] 🚫 {} blocked: {}", addr, reason);
+            write_forbidden(&mut stream, &reason).await?;
+            return Ok(());
+        }
+        Verdict::Confirm(request, receiver) => {
+            println!("[proxy] ❓ {} {} from {}: waiting for confirmation", request.method, request.path, request.origin);
+            let id = request.id;
+            state.emit("proxy-route-confirm", request);
+            if !state.firewall.wait_confirmation(id, receiver).await {
+                eprintln!("[proxy] 🚫 {} request #{} denied", addr, id);
+                write_forbidden(&mut stream, "Request denied by the user").await?;
+                return Ok(());
+            }
+        }
+    }
+
+    // Snapshot so a token change doesn't affect connections already being set up
+    let auth = state.auth.read().await.clone();
+
+    let upstream = Upstream {
+        target_host: &target_host,
+        port,
+        auth: &auth,
+        metrics,
+        limiter,
+    };
+    if is_websocket {
+        let mut consumed = head.into_bytes();
+        consumed.extend_from_slice(&body_start);
+        handle_websocket(PrefixedStream { prefix: consumed, stream }, upstream, addr).await
+    } else {
+        let capture = state.capture.is_enabled().then_some(&state.capture);
+        let one_request = state.firewall.is_enabled();
+        handle_http(stream, (head, body_start), upstream, addr, capture, one_request).await
+    }
+}
+
+async fn write_forbidden(stream: &mut TcpStream, message: &str) -> std::io::Result<()> {
+    let response = format!(
+        "HTTP/1.1 403 Forbidden\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        message.len(),
+        message
+    );
+    stream.write_all(response.as_bytes()).await
+}
+
+/// Where and how a local connection is forwarded
+struct Upstream<'a> {
+    target_host: &'a str,
+    port: u16,
+    auth: &'a ProxyAuth,
+    metrics: &'a PortMetrics,
+    limiter: Option<&'a RateLimiter>,
+}
+
+/// Read an HTTP request head (up to the blank line); returns it with any body bytes read past it
+async fn read_request_head(stream: &mut TcpStream) -> std::io::Result<(String, Vec<u8>)> {
+    let mut received = Vec::new();
+    let mut chunk = [0u8; 4096];
+    loop {
+        if let Some(end) = received.windows(4).position(|w| w == b"\r\n\r\n") {
+            let rest = received.split_off(end + 4);
+            return Ok((String::from_utf8_lossy(&received).to_string(), rest));
+        }
+        if received.len() > MAX_REQUEST_HEAD {
+            return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "Request head too large"));
+        }
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            return Err(std::io::ErrorKind::UnexpectedEof.into());
+        }
+        received.extend_from_slice(&chunk[..n]);
+    }
+}
+
+/// A stream whose first bytes were already read: replays them before reading on
+struct PrefixedStream {
+    prefix: Vec<u8>,
+    stream: TcpStream,
+}
+
+impl AsyncRead for PrefixedStream {
+    fn poll_read(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<std::io::Result<()>> {
+        if self.prefix.is_empty() {
+            return Pin::new(&mut self.stream).poll_read(cx, buf);
+        }
+        let n = self.prefix.len().min(buf.remaining());
+        buf.put_slice(&self.prefix[..n]);
+        self.prefix.drain(..n);
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl AsyncWrite for PrefixedStream {
+    fn poll_write(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<std::io::Result<usize>> {
+        Pin::new(&mut self.stream).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.stream).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.stream).poll_shutdown(cx)
+    }
+}
+
+/// Why a WebSocket forwarding round ended
+enum WsEnd {
+    /// Local client closed or went away
+    Local,
+    /// Robot closed the stream cleanly
+    RemoteClosed,
+    /// Upstream dropped without a close frame: worth re-establishing
+    RemoteDropped,
+}
+
+/// Upstream handshake request, with the robot token when configured
+fn upstream_request(
+    remote_url: &str,
+    auth: &ProxyAuth,
+) -> Result<tokio_tungstenite::tungstenite::handshake::client::Request, Box<dyn std::error::Error + Send + Sync>> {
+    let mut request = remote_url.into_client_request()?;
+    if let Some(token) = &auth.robot_token {
+        request
+            .headers_mut()
+            .insert(header::AUTHORIZATION, HeaderValue::from_str(&format!("Bearer {}", token))?);
+    }
+    Ok(request)
+}
+
+/// Retry the upstream connection with backoff for up to WS_RECONNECT_WINDOW
+async fn reconnect_upstream(remote_url: &str, auth: &ProxyAuth) -> Option<RemoteWebSocket> {
+    let deadline = tokio::time::Instant::now() + WS_RECONNECT_WINDOW;
+    let mut delay = Duration::from_millis(250);
+    while tokio::time::Instant::now() < deadline {
+        tokio::time::sleep(delay).await;
+        let request = upstream_request(remote_url, auth).ok()?;
+        if let Ok((ws, _)) = connect_async(request).await {
+            return Some(ws);
+        }
+        delay = (delay * 2).min(WS_RECONNECT_MAX_DELAY);
+    }
+    None
+}
+
+/// Handle WebSocket connections
+// The handshake callback's error type is imposed by tungstenite
+#[allow(clippy::result_large_err)]
+async fn handle_websocket(
+    stream: PrefixedStream,
+    upstream: Upstream<'_>,
+    addr: std::net::SocketAddr,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let Upstream { target_host, port, auth, metrics, limiter } = upstream;
+    use tokio_tungstenite::tungstenite::protocol::CloseFrame;
+    use tokio_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
+    use tokio_tungstenite::tungstenite::Message;
+
+    // Capture the request path during handshake
+    let request_path = Arc::new(RwLock::new(String::from("/")));
+    let request_path_clone = request_path.clone();
+
+    // Accept with callback to capture path
+    let mut local_ws = tokio_tungstenite::accept_hdr_async(stream, |req: &Request, resp: Response| {
+        let token_header = req.headers().get(auth::LOCAL_TOKEN_HEADER).and_then(|v| v.to_str().ok());
+        if !auth.is_authorized(token_header, req.uri().query()) {
+            eprintln!("[proxy] 🔒 WS {} rejected: missing or invalid proxy token", addr);
+            let mut rejection = ErrorResponse::new(Some("Missing or invalid proxy token".to_string()));
+            *rejection.status_mut() = StatusCode::UNAUTHORIZED;
+            return Err(rejection);
+        }
+
+        let path = req.uri().path_and_query()
+            .map(|pq| auth::strip_local_token(pq.as_str()))
+            .unwrap_or_else(|| "/".to_string());
+
+        // Store in a thread-local or use blocking lock
+        if let Ok(mut p) = request_path_clone.try_write() {
+            *p = path;
+        }
+        Ok(resp)
+    }).await?;
+
+    // Get the captured path
+    let path = request_path.read().await.clone();
+    println!("[proxy] 🔌 WS {} -> ws://{}:{}{}", addr, target_host, port, path);
+
+    // Build remote URL with the same path and port
+    let remote_url = format!("ws://{}:{}{}", target_host, port, path);
+
+    // Connect to remote - if this fails, properly close the local WebSocket
+    let connected = match upstream_request(&remote_url, auth) {
+        Ok(request) => connect_async(request).await.map_err(|e| e.into()),
+        Err(e) => Err(e),
+    };
+    let mut remote_ws = match connected {
+        Ok((ws, _)) => ws,
+        Err(e) => {
+            eprintln!("[proxy] ❌ WS remote connection failed: {}", e);
+            // Send a proper close frame to the local client
+            let close_frame = CloseFrame {
+                code: CloseCode::Error,
+                reason: format!("Remote connection failed: {}", e).into(),
+            };
+            let _ = local_ws.close(Some(close_frame)).await;
+            return Err(e);
+        }
+    };
+
+    // Split the local WebSocket once; the remote one is re-split after each reconnect
+    let (mut local_write, mut local_read) = local_ws.split();
+
+    loop {
+        let (mut remote_write, mut remote_read) = remote_ws.split();
+
+        // Forward messages bidirectionally
+        let local_to_remote = async {
+            while let Some(msg) = local_read.next().await {
+                match msg {
+                    Ok(msg) => {
+                        if msg.is_close() {
+                            return WsEnd::Local;
+                        }
+                        metrics.add_bytes_out(msg.len() as u64);
+                        if remote_write.send(msg).await.is_err() {
+                            return WsEnd::RemoteDropped;
+                        }
+                    }
+                    Err(_) => return WsEnd::Local,
+                }
+            }
+            WsEnd::Local
+        };
+
+        let remote_to_local = async {
+            while let Some(msg) = remote_read.next().await {
+                match msg {
+                    Ok(msg) => {
+                        if msg.is_close() {
+                            return WsEnd::RemoteClosed;
+                        }
+                        // Over budget: drop the frame rather than queue it behind the control channel
+                        if let Some(limiter) = limiter {
+                            if (msg.is_binary() || msg.is_text()) && !limiter.try_acquire(msg.len()) {
+                                metrics.frame_dropped();
+                                continue;
+                            }
+                        }
+                        metrics.frame_forwarded();
+                        metrics.add_bytes_in(msg.len() as u64);
+                        if local_write.send(msg).await.is_err() {
+                            return WsEnd::Local;
+                        }
+                    }
+                    Err(_) => return WsEnd::RemoteDropped,
+                }
+            }
+            WsEnd::RemoteDropped
+        };
+
+        let end = tokio::select! {
+            end = local_to_remote => end,
+            end = remote_to_local => end,
+        };
+        if !matches!(end, WsEnd::RemoteDropped) {
+            break;
+        }
+
+        // Upstream went away without a close frame (WiFi blip): keep the local client and retry
+        println!("[proxy] 🔄 WS upstream {} dropped - reconnecting", remote_url);
+        match reconnect_upstream(&remote_url, auth).await {
+            Some(ws) => {
+                println!("[proxy] ✅ WS upstream {} re-established", remote_url);
+                remote_ws = ws;
+            }
+            None => {
+                eprintln!("[proxy] ❌ WS upstream {} lost", remote_url);
+                metrics.record_error(format!("WebSocket upstream lost: {}", remote_url));
+                let close_frame = CloseFrame {
+                    code: CloseCode::Away,
+                    reason: "Remote connection lost".into(),
+                };
+                let _ = local_write.send(Message::Close(Some(close_frame))).await;
+                break;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Handle HTTP connections by forwarding to remote
+async fn handle_http(
+    mut local_stream: TcpStream,
+    (head, body_start): (String, Vec<u8>),
+    upstream: Upstream<'_>,
+    addr: std::net::SocketAddr,
+    capture: Option<&ProxyCapture>,
+    one_request: bool,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let Upstream { target_host, port, auth, metrics, limiter } = upstream;
+    // With auth, capture or the firewall enabled, the request head is rewritten before
+    // forwarding (Connection: close, so every request on this connection is seen here)
+    let rewrites = auth.rewrites_requests() || capture.is_some() || one_request;
+    let head = if rewrites {
+        match auth::rewrite_request_head(&head, auth) {
+            RewrittenHead::Forward(head) => head,
+            RewrittenHead::Unauthorized => {
+                eprintln!("[proxy] 🔒 HTTP {} rejected: missing or invalid proxy token", addr);
+                let message = "Missing or invalid proxy token";
+                let response = format!(
+                    "HTTP/1.1 401 Unauthorized\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    message.len(),
+                    message
+                );
+                local_stream.write_all(response.as_bytes()).await?;
+                return Ok(());
+            }
+        }
+    } else {
+        head.into_bytes()
+    };
+
+    // Connect to remote server on the same port
+    let remote_addr = format!("{}:{}", target_host, port);
+    let mut remote_stream = match TcpStream::connect(&remote_addr).await {
+        Ok(s) => s,
+        Err(e) => {
+            metrics.record_error(format!("Remote connection failed: {}", e));
+            // Friendly error message - service may still be starting up
+            let (status, message) = if e.kind() == std::io::ErrorKind::ConnectionRefused {
+                ("503 Service Unavailable", "No content yet - service starting up")
+            } else {
+                ("502 Bad Gateway", "Remote service unavailable")
+            };
+            let response = format!(
+                "HTTP/1.1 {}\r\nContent-Type: text/plain\r\nContent-Length: {}\r\n\r\n{}",
+                status,
+                message.len(),
+                message
+            );
+            local_stream.write_all(response.as_bytes()).await?;
+            return Ok(());
+        }
+    };
+
+    // Log the request, then forward the head already read
+    let first_line = String::from_utf8_lossy(&head).lines().next().unwrap_or("").to_string();
+    let rewritten = if rewrites { " (rewritten)" } else { "" };
+    println!("[proxy] 📡 HTTP {} -> {}:{} | {}{}", addr, target_host, port, first_line, rewritten);
+    let recorder = capture.map(|_| std::sync::Mutex::new(ExchangeRecorder::new(port, &head, &body_start)));
+    remote_stream.write_all(&head).await?;
+    remote_stream.write_all(&body_start).await?;
+    metrics.add_bytes_out((head.len() + body_start.len()) as u64);
+
+    // Bidirectional copy between local and remote
+    let (mut local_read, mut local_write) = local_stream.split();
+    let (mut remote_read, mut remote_write) = remote_stream.split();
+
+    let client_to_server = copy_counted(&mut local_read, &mut remote_write, None, |chunk| {
+        metrics.add_bytes_out(chunk.len() as u64);
+        if let Some(recorder) = &recorder {
+            recorder.lock().unwrap().on_request(chunk);
+        }
+    });
+    let server_to_client = copy_counted(&mut remote_read, &mut local_write, limiter, |chunk| {
+        metrics.add_bytes_in(chunk.len() as u64);
+        if let Some(recorder) = &recorder {
+            recorder.lock().unwrap().on_response(chunk);
+        }
+    });
+
+    tokio::select! {
+        result = client_to_server => {
+            if let Err(e) = result {
+                if e.kind() != std::io::ErrorKind::NotConnected {
+                    eprintln!("[proxy] ❌ HTTP client->server error: {}", e);
+                }
+            }
+        }
+        result = server_to_client => {
+            if let Err(e) = result {
+                if e.kind() != std::io::ErrorKind::NotConnected {
+                    eprintln!("[proxy] ❌ HTTP server->client error: {}", e);
+                }
+            }
+        }
+    }
+
+    if let (Some(capture), Some(recorder)) = (capture, recorder) {
+        recorder.into_inner().unwrap().finish(capture);
+    }
+
+    Ok(())
+}
+
+/// Load the robot token from the keychain into the proxy
+pub async fn reload_robot_token(state: &Arc<LocalProxyState>) {
+    let token = tokio::task::spawn_blocking(auth::load_robot_token).await.unwrap_or(None);
+    state.auth.write().await.robot_token = token;
+}
+
+/// Replace the proxied ports; applied immediately when the proxy is running
+pub async fn set_port_mappings(state: &Arc<LocalProxyState>, mappings: Vec<PortMapping>) -> Result<(), String> {
+    for (i, mapping) in mappings.iter().enumerate() {
+        if mapping.local == 0 || mapping.remote == 0 {
+            return Err("Port 0 can't be proxied".to_string());
+        }
+        if mappings[..i].iter().any(|other| other.local == mapping.local) {
+            return Err(format!("Local port {} is mapped twice", mapping.local));
+        }
+    }
+
+    *state.port_mappings.write().await = mappings;
+    if state.status_handle.lock().await.is_some() {
+        sync_listeners(state).await;
+        state.emit_status().await;
+    }
+    Ok(())
+}
+
+/// Robot token and ports for a host: from its saved profile, else the keychain token and settings ports
+async fn apply_host_config(state: &Arc<LocalProxyState>, host: &str) {
+    let profile = state.profiles.find_by_host(host);
+    match &profile {
+        Some(profile) if profile.has_token => {
+            println!("[proxy] 📇 Using profile {}", profile.name);
+            let account = profile.token_account();
+            let token = tokio::task::spawn_blocking(move || auth::load_token(&account)).await.unwrap_or(None);
+            state.auth.write().await.robot_token = token;
+        }
+        Some(profile) => {
+            println!("[proxy] 📇 Using profile {}", profile.name);
+            state.auth.write().await.robot_token = None;
+        }
+        None => reload_robot_token(state).await,
+    }
+
+    let settings_ports = || {
+        let app_handle = state.app_handle.get()?;
+        Some(app_handle.state::<crate::settings::SettingsState>().get().proxy_ports)
+    };
+    if let Some(ports) = profile.as_ref().and_then(|profile| profile.ports.clone()).or_else(settings_ports) {
+        if let Err(e) = set_port_mappings(state, ports).await {
+            eprintln!("[proxy] ⚠️ {}", e);
+        }
+    }
+    if let Some(profile) = profile {
+        state.profiles.mark_used(&profile.id);
+    }
+}
+
+/// Set the target host for the proxy and start the proxy
+///
+/// A saved profile for this host brings its own robot token and ports.
+pub async fn set_target_host(state: &Arc<LocalProxyState>, host: String) {
+    apply_host_config(state, &host).await;
+
+    // Set the target host
+    {
+        let mut target = state.target_host.write().await;
+        println!("[proxy] 🎯 Target host set to: {}", host);
+        // Measurements of the previous robot don't apply
+        state.quality.reset();
+        *target = Some(host);
+    }
+
+    // Start the proxy
+    start_local_proxy(state.clone()).await;
+}
+
+/// Clear the target host and stop the proxy
+pub async fn clear_target_host(state: &Arc<LocalProxyState>) {
+    // Stop the proxy first
+    stop_local_proxy(state).await;
+
+    // Clear the target host
+    let mut target = state.target_host.write().await;
+    println!("[proxy] 🚫 Target host cleared");
+    *target = None;
+}
@@ -0,0 +1,116 @@
+//! Remote robot profiles
+//!
+//! Saved WiFi robots (name, host, optional port mapping) in
+//! `<app data>/remote_profiles.json`, so reconnecting to a known robot is one click.
+//! Each profile can have its own robot token, kept in the OS keychain (never in the
+//! JSON file). set_target_host applies the profile matching the host, if any.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use super::PortMapping;
+
+const PROFILES_FILE: &str = "remote_profiles.json";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RemoteProfile {
+    pub id: String,
+    pub name: String,
+    /// Hostname or IP address, without scheme or port
+    pub host: String,
+    /// Proxied ports for this robot (None = the ports from settings)
+    pub ports: Option<Vec<PortMapping>>,
+    /// A robot token is stored in the keychain for this profile
+    pub has_token: bool,
+    /// Unix millis of the last connection
+    pub last_used: Option<u64>,
+}
+
+impl RemoteProfile {
+    /// Keychain account of the profile's robot token
+    pub fn token_account(&self) -> String {
+        format!("profile-token-{}", self.id)
+    }
+}
+
+#[derive(Default)]
+pub struct ProfileStore {
+    profiles: Mutex<Vec<RemoteProfile>>,
+    path: Mutex<Option<PathBuf>>,
+}
+
+/// 8 random bytes, hex encoded
+pub fn generate_id() -> String {
+    let mut bytes = [0u8; 8];
+    let _ = getrandom::getrandom(&mut bytes);
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Hostname or IP only: what set_target_host expects
+pub fn validate_host(host: &str) -> Result<(), String> {
+    if host.is_empty() || host.contains(['/', ' ', ':']) {
+        return Err(format!("Invalid host (expected a hostname or IP address): {}", host));
+    }
+    Ok(())
+}
+
+impl ProfileStore {
+    /// Load profiles from the app data directory (empty if missing or corrupted)
+    pub fn load(&self, app_data_dir: &Path) {
+        let path = app_data_dir.join(PROFILES_FILE);
+        let profiles = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        *self.profiles.lock().unwrap() = profiles;
+        *self.path.lock().unwrap() = Some(path);
+    }
+
+    /// Profiles, most recently used first
+    pub fn list(&self) -> Vec<RemoteProfile> {
+        let mut profiles = self.profiles.lock().unwrap().clone();
+        profiles.sort_by_key(|profile| std::cmp::Reverse(profile.last_used));
+        profiles
+    }
+
+    pub fn get(&self, id: &str) -> Option<RemoteProfile> {
+        self.profiles.lock().unwrap().iter().find(|profile| profile.id == id).cloned()
+    }
+
+    pub fn find_by_host(&self, host: &str) -> Option<RemoteProfile> {
+        self.profiles
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|profile| profile.host.eq_ignore_ascii_case(host))
+            .cloned()
+    }
+
+    /// Apply a change and persist it
+    pub fn update<T>(&self, change: impl FnOnce(&mut Vec<RemoteProfile>) -> Result<T, String>) -> Result<T, String> {
+        let mut profiles = self.profiles.lock().unwrap();
+        let result = change(&mut profiles)?;
+
+        if let Some(path) = self.path.lock().unwrap().as_ref() {
+            let content = serde_json::to_string_pretty(&*profiles)
+                .map_err(|e| format!("Failed to serialize remote profiles: {}", e))?;
+            std::fs::write(path, content)
+                .map_err(|e| format!("Failed to write remote profiles: {}", e))?;
+        }
+
+        Ok(result)
+    }
+
+    pub fn mark_used(&self, id: &str) {
+        let result = self.update(|profiles| {
+            if let Some(profile) = profiles.iter_mut().find(|profile| profile.id == id) {
+                profile.last_used = Some(crate::daemon::history::now_millis());
+            }
+            Ok(())
+        });
+        if let Err(e) = result {
+            eprintln!("[proxy] ⚠️ {}", e);
+        }
+    }
+}
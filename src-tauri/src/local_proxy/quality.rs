@@ -0,0 +1,181 @@
+//! Connection quality monitor (WiFi mode)
+//!
+//! While the proxy runs, the daemon status endpoint is pinged over HTTP through the
+//! proxy every `PING_INTERVAL`, the same path the UI and teleoperation take (ICMP needs
+//! privileges the app doesn't have). Over the last `WINDOW` pings, latency (median,
+//! p95, jitter) and loss (unanswered within `PING_TIMEOUT`) are rated as 0-4 bars.
+//!
+//! Events:
+//! - `connection-quality`: ConnectionQuality, after every ping
+//! - `connection-quality-warning`: ConnectionQuality, once when the link drops below
+//!   `TELEOP_MIN_BARS` (re-armed when it is back above)
+
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::task::JoinHandle;
+
+use super::LocalProxyState;
+use crate::daemon_api::DaemonClient;
+
+const PING_INTERVAL: Duration = Duration::from_secs(1);
+const PING_TIMEOUT: Duration = Duration::from_millis(1500);
+/// Pings rated together
+const WINDOW: usize = 20;
+/// Pings needed before a rating is given
+const MIN_SAMPLES: usize = 3;
+/// (p95 latency ms, loss %) for 4, 3, 2 and 1 bars
+const THRESHOLDS: [(f64, f64); 4] = [(40.0, 0.5), (80.0, 2.0), (150.0, 5.0), (300.0, 15.0)];
+/// Below this, teleoperation lags noticeably
+const TELEOP_MIN_BARS: u8 = 2;
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct ConnectionQuality {
+    pub target_host: String,
+    /// 0 (unusable) to 4
+    pub bars: u8,
+    /// Milliseconds, over the answered pings of the window
+    pub latency_ms: Option<f64>,
+    pub latency_p95_ms: Option<f64>,
+    /// Mean difference between consecutive latencies
+    pub jitter_ms: Option<f64>,
+    pub loss_percent: f64,
+    pub samples: usize,
+    pub teleop_usable: bool,
+}
+
+#[derive(Default)]
+pub struct QualityMonitor {
+    /// Latency of each ping, None when lost
+    pings: Mutex<VecDeque<Option<f64>>>,
+    latest: Mutex<Option<ConnectionQuality>>,
+    warned: Mutex<bool>,
+    handle: Mutex<Option<JoinHandle<()>>>,
+}
+
+// ============================================================================
+// HELPER FUNCTIONS
+// ============================================================================
+
+fn bars(latency_p95_ms: Option<f64>, loss_percent: f64) -> u8 {
+    let Some(latency) = latency_p95_ms else {
+        return 0;
+    };
+    THRESHOLDS
+        .iter()
+        .position(|(max_latency, max_loss)| latency <= *max_latency && loss_percent <= *max_loss)
+        .map(|index| 4 - index as u8)
+        .unwrap_or(0)
+}
+
+/// Rate a window of pings (None until `MIN_SAMPLES`)
+fn rate(target_host: &str, pings: &VecDeque<Option<f64>>) -> Option<ConnectionQuality> {
+    if pings.len() < MIN_SAMPLES {
+        return None;
+    }
+    let answered: Vec<f64> = pings.iter().flatten().copied().collect();
+    let loss_percent = (pings.len() - answered.len()) as f64 * 100.0 / pings.len() as f64;
+
+    let mut sorted = answered.clone();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let percentile = |p: f64| -> Option<f64> {
+        let index = ((sorted.len() as f64 - 1.0) * p).round() as usize;
+        sorted.get(index).copied()
+    };
+    let latency_p95_ms = percentile(0.95);
+    let jitter_ms = (answered.len() > 1).then(|| {
+        answered.windows(2).map(|pair| (pair[1] - pair[0]).abs()).sum::<f64>() / (answered.len() - 1) as f64
+    });
+
+    let bars = bars(latency_p95_ms, loss_percent);
+    Some(ConnectionQuality {
+        target_host: target_host.to_string(),
+        bars,
+        latency_ms: percentile(0.5),
+        latency_p95_ms,
+        jitter_ms,
+        loss_percent,
+        samples: pings.len(),
+        teleop_usable: bars >= TELEOP_MIN_BARS,
+    })
+}
+
+impl QualityMonitor {
+    pub fn latest(&self) -> Option<ConnectionQuality> {
+        self.latest.lock().unwrap().clone()
+    }
+
+    /// Add a ping result; returns the new rating and whether it is a new warning
+    fn record(&self, target_host: &str, latency_ms: Option<f64>) -> Option<(ConnectionQuality, bool)> {
+        let quality = {
+            let mut pings = self.pings.lock().unwrap();
+            pings.push_back(latency_ms);
+            if pings.len() > WINDOW {
+                pings.pop_front();
+            }
+            rate(target_host, &pings)?
+        };
+        let mut warned = self.warned.lock().unwrap();
+        let warn = !quality.teleop_usable && !*warned;
+        *warned = !quality.teleop_usable;
+        *self.latest.lock().unwrap() = Some(quality.clone());
+        Some((quality, warn))
+    }
+
+    pub fn reset(&self) {
+        self.pings.lock().unwrap().clear();
+        *self.latest.lock().unwrap() = None;
+        *self.warned.lock().unwrap() = false;
+    }
+}
+
+async fn run(state: Arc<LocalProxyState>) {
+    loop {
+        let started = Instant::now();
+        if let Some(host) = state.target_host.read().await.clone() {
+            // Built each time: the session token can change while the proxy runs
+            let client = DaemonClient::for_proxy(&state).await.with_timeout(PING_TIMEOUT);
+            let sent = Instant::now();
+            let latency_ms = client
+                .is_healthy()
+                .await
+                .then(|| sent.elapsed().as_secs_f64() * 1000.0);
+            if let Some((quality, warn)) = state.quality.record(&host, latency_ms) {
+                if warn {
+                    println!(
+                        "[proxy] 📶 Weak link to {}: {} bar(s), p95 {:?} ms, {:.0}% loss",
+                        host, quality.bars, quality.latency_p95_ms, quality.loss_percent
+                    );
+                    state.emit("connection-quality-warning", quality.clone());
+                }
+                state.emit("connection-quality", quality);
+            }
+        }
+        tokio::time::sleep(PING_INTERVAL.saturating_sub(started.elapsed())).await;
+    }
+}
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// Start pinging the robot (with the proxy)
+pub fn start(state: &Arc<LocalProxyState>) {
+    let mut handle = state.quality.handle.lock().unwrap();
+    if handle.is_none() {
+        *handle = Some(tokio::spawn(run(state.clone())));
+    }
+}
+
+/// Stop pinging and forget the measurements (with the proxy)
+pub fn stop(state: &LocalProxyState) {
+    if let Some(handle) = state.quality.handle.lock().unwrap().take() {
+        handle.abort();
+    }
+    state.quality.reset();
+}
@@ -0,0 +1,58 @@
+//! Bandwidth shaping
+//!
+//! Token bucket shared by every connection of a rate-limited port (typically the
+//! 8042 video path), so video can't saturate a weak WiFi link and starve the control
+//! channel on 8000. WebSocket frames over budget are dropped (a late video frame is
+//! useless), raw HTTP streams are delayed instead since they can't be cut mid-stream.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Burst allowed above the sustained rate
+const BURST_SECS: f64 = 0.5;
+
+pub struct RateLimiter {
+    bytes_per_sec: f64,
+    burst: f64,
+    /// (available tokens, last refill)
+    bucket: Mutex<(f64, Instant)>,
+}
+
+impl RateLimiter {
+    pub fn new(rate_limit_kbps: u32) -> Self {
+        let bytes_per_sec = rate_limit_kbps as f64 * 1000.0 / 8.0;
+        let burst = (bytes_per_sec * BURST_SECS).max(64.0 * 1024.0);
+        Self {
+            bytes_per_sec,
+            burst,
+            bucket: Mutex::new((burst, Instant::now())),
+        }
+    }
+
+    /// Refill, then take `bytes` (may go negative); returns the resulting balance
+    fn take(&self, bytes: usize, allow_debt: bool) -> Option<f64> {
+        let mut bucket = self.bucket.lock().unwrap();
+        let now = Instant::now();
+        let (tokens, last) = *bucket;
+        let refilled = (tokens + now.duration_since(last).as_secs_f64() * self.bytes_per_sec).min(self.burst);
+        if !allow_debt && refilled < bytes as f64 {
+            *bucket = (refilled, now);
+            return None;
+        }
+        *bucket = (refilled - bytes as f64, now);
+        Some(refilled - bytes as f64)
+    }
+
+    /// Take budget for a frame; false = over budget, drop it
+    pub fn try_acquire(&self, bytes: usize) -> bool {
+        self.take(bytes, false).is_some()
+    }
+
+    /// Take budget for stream bytes, waiting for the bucket to refill when in debt
+    pub async fn acquire(&self, bytes: usize) {
+        let balance = self.take(bytes, true).unwrap_or(0.0);
+        if balance < 0.0 {
+            tokio::time::sleep(Duration::from_secs_f64(-balance / self.bytes_per_sec)).await;
+        }
+    }
+}
@@ -0,0 +1,287 @@
+/// PIN lock for shared machines (classrooms)
+///
+/// Once a PIN is set, dangerous commands (daemon updates and rollbacks, firmware
+/// flashing, app removal, backup restore, serial console writes, robot renaming) and the
+/// settings that turn other protections off (proxy firewall, token, ports and rate limits,
+/// app sandbox policies, automation server, update channel and index, analytics and crash
+/// report endpoints) are refused through `ensure_unlocked` until `unlock_ui` is called with
+/// the right PIN; the unlock lasts `timeout_minutes`, or until `lock_ui`.
+///
+/// Calibration reset has no backend command (the frontend calls the daemon API), so it is
+/// gated by a `require_unlock` rule of the proxy route firewall, which asks `ensure_unlocked`
+/// for every client, this app's webview included.
+/// The PIN is stored salted and hashed in `<app data>/ui-lock.json`, never in clear.
+///
+/// Wrong PINs are counted: after MAX_ATTEMPTS in a row, unlocking is refused for
+/// LOCKOUT. Emits `ui-lock-changed` (UiLockStatus) when the PIN or the lock changes.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, State};
+
+const LOCK_FILE: &str = "ui-lock.json";
+const DEFAULT_TIMEOUT_MINUTES: u32 = 10;
+const MAX_TIMEOUT_MINUTES: u32 = 8 * 60;
+const PIN_LENGTH: std::ops::RangeInclusive<usize> = 4..=12;
+/// SHA-256 rounds, so a copied lock file can't be brute-forced instantly
+const HASH_ROUNDS: u32 = 100_000;
+const MAX_ATTEMPTS: u32 = 5;
+const LOCKOUT: Duration = Duration::from_secs(60);
+
+/// Read by the guards without going through the state
+static PIN_SET: AtomicBool = AtomicBool::new(false);
+static UNLOCKED_UNTIL: Mutex<Option<Instant>> = Mutex::new(None);
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct LockConfig {
+    /// Hex salt and hash of the PIN, None = no PIN, nothing locked
+    pin: Option<(String, String)>,
+    timeout_minutes: u32,
+}
+
+impl Default for LockConfig {
+    fn default() -> Self {
+        Self {
+            pin: None,
+            timeout_minutes: DEFAULT_TIMEOUT_MINUTES,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct UiLockStatus {
+    pub pin_set: bool,
+    /// Dangerous commands are allowed (always true without a PIN)
+    pub unlocked: bool,
+    /// Seconds until the lock comes back, while unlocked with a PIN
+    pub remaining_secs: Option<u64>,
+    pub timeout_minutes: u32,
+    /// Unlocking refused for this long after too many wrong PINs
+    pub lockout_secs: Option<u64>,
+}
+
+#[derive(Default)]
+struct Attempts {
+    failed: u32,
+    blocked_until: Option<Instant>,
+}
+
+#[derive(Default)]
+pub struct UiLockState {
+    config: Mutex<LockConfig>,
+    path: Mutex<Option<PathBuf>>,
+    attempts: Mutex<Attempts>,
+}
+
+// ============================================================================
+// HELPER FUNCTIONS
+// ============================================================================
+
+fn hash_pin(salt: &str, pin: &str) -> String {
+    let mut digest = Sha256::digest(format!("{}:{}", salt, pin));
+    for _ in 1..HASH_ROUNDS {
+        digest = Sha256::digest(digest);
+    }
+    digest.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Compare without stopping at the first difference
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len() && a.bytes().zip(b.bytes()).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+fn validate_pin(pin: &str) -> Result<(), String> {
+    if !PIN_LENGTH.contains(&pin.len()) || !pin.chars().all(|c| c.is_ascii_digit()) {
+        return Err(format!(
+            "The PIN must be {} to {} digits",
+            PIN_LENGTH.start(),
+            PIN_LENGTH.end()
+        ));
+    }
+    Ok(())
+}
+
+/// Time left before the lock comes back (clears an expired unlock)
+fn remaining() -> Option<Duration> {
+    let mut unlocked_until = UNLOCKED_UNTIL.lock().unwrap();
+    let remaining = unlocked_until.and_then(|until| until.checked_duration_since(Instant::now()));
+    if remaining.is_none() {
+        *unlocked_until = None;
+    }
+    remaining
+}
+
+impl Attempts {
+    fn lockout(&self) -> Option<Duration> {
+        self.blocked_until.and_then(|until| until.checked_duration_since(Instant::now()))
+    }
+}
+
+impl UiLockState {
+    /// Load the PIN from the app data directory (no PIN if missing or corrupted)
+    pub fn load(&self, app_data_dir: &Path) {
+        let path = app_data_dir.join(LOCK_FILE);
+        let config: LockConfig = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        PIN_SET.store(config.pin.is_some(), Ordering::SeqCst);
+        *self.config.lock().unwrap() = config;
+        *self.path.lock().unwrap() = Some(path);
+    }
+
+    fn status(&self) -> UiLockStatus {
+        let lockout = self.attempts.lock().unwrap().lockout();
+        let config = self.config.lock().unwrap();
+        let remaining = config.pin.as_ref().and_then(|_| remaining());
+        UiLockStatus {
+            pin_set: config.pin.is_some(),
+            unlocked: config.pin.is_none() || remaining.is_some(),
+            remaining_secs: remaining.map(|remaining| remaining.as_secs()),
+            timeout_minutes: config.timeout_minutes,
+            lockout_secs: lockout.map(|lockout| lockout.as_secs() + 1),
+        }
+    }
+
+    /// Check a PIN against the stored hash, counting failures (Ok without a PIN)
+    fn check_pin(&self, pin: &str) -> Result<(), String> {
+        let mut attempts = self.attempts.lock().unwrap();
+        if let Some(lockout) = attempts.lockout() {
+            return Err(format!("Too many wrong PINs, try again in {} s", lockout.as_secs() + 1));
+        }
+        let Some((salt, hash)) = self.config.lock().unwrap().pin.clone() else {
+            return Ok(());
+        };
+        if constant_time_eq(&hash_pin(&salt, pin), &hash) {
+            *attempts = Attempts::default();
+            return Ok(());
+        }
+        attempts.failed += 1;
+        if attempts.failed >= MAX_ATTEMPTS {
+            eprintln!("[lock] 🚫 {} wrong PINs, unlocking blocked for {:?}", attempts.failed, LOCKOUT);
+            *attempts = Attempts {
+                failed: 0,
+                blocked_until: Some(Instant::now() + LOCKOUT),
+            };
+        }
+        Err("Wrong PIN".to_string())
+    }
+
+    /// Replace the config and persist it
+    fn save(&self, config: LockConfig) -> Result<(), String> {
+        if let Some(path) = self.path.lock().unwrap().as_ref() {
+            let content = serde_json::to_string_pretty(&config)
+                .map_err(|e| format!("Failed to serialize UI lock: {}", e))?;
+            std::fs::write(path, content).map_err(|e| format!("Failed to write UI lock: {}", e))?;
+        }
+        PIN_SET.store(config.pin.is_some(), Ordering::SeqCst);
+        *self.config.lock().unwrap() = config;
+        Ok(())
+    }
+}
+
+fn emit_status(app_handle: &AppHandle, state: &UiLockState) -> UiLockStatus {
+    let status = state.status();
+    let _ = app_handle.emit("ui-lock-changed", &status);
+    status
+}
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// Refuse a dangerous action while a PIN is set and the UI isn't unlocked
+pub fn ensure_unlocked(action: &str) -> Result<(), String> {
+    if PIN_SET.load(Ordering::SeqCst) && remaining().is_none() {
+        Err(format!("{} requires unlocking with the PIN", action))
+    } else {
+        Ok(())
+    }
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+#[tauri::command]
+pub fn get_ui_lock_status(state: State<UiLockState>) -> UiLockStatus {
+    state.status()
+}
+
+/// Set or change the PIN and / or the unlock duration (the current PIN is required once
+/// one is set)
+#[tauri::command]
+pub fn set_ui_lock_pin(
+    app_handle: AppHandle,
+    state: State<UiLockState>,
+    current_pin: Option<String>,
+    new_pin: Option<String>,
+    timeout_minutes: Option<u32>,
+) -> Result<UiLockStatus, String> {
+    if state.config.lock().unwrap().pin.is_some() {
+        state.check_pin(current_pin.as_deref().unwrap_or_default())?;
+    }
+    let mut config = state.config.lock().unwrap().clone();
+    if let Some(timeout_minutes) = timeout_minutes {
+        if !(1..=MAX_TIMEOUT_MINUTES).contains(&timeout_minutes) {
+            return Err(format!("The unlock duration must be 1 to {} minutes", MAX_TIMEOUT_MINUTES));
+        }
+        config.timeout_minutes = timeout_minutes;
+    }
+    if let Some(pin) = new_pin {
+        validate_pin(&pin)?;
+        let salt = crate::local_proxy::auth::generate_token()?;
+        let hash = hash_pin(&salt, &pin);
+        config.pin = Some((salt, hash));
+        // A new PIN starts locked
+        *UNLOCKED_UNTIL.lock().unwrap() = None;
+        println!("[lock] 🔑 PIN set");
+    }
+    state.save(config)?;
+    Ok(emit_status(&app_handle, &state))
+}
+
+/// Remove the PIN: nothing is locked anymore
+#[tauri::command]
+pub fn remove_ui_lock_pin(
+    app_handle: AppHandle,
+    state: State<UiLockState>,
+    current_pin: String,
+) -> Result<UiLockStatus, String> {
+    state.check_pin(&current_pin)?;
+    let config = LockConfig {
+        pin: None,
+        ..state.config.lock().unwrap().clone()
+    };
+    state.save(config)?;
+    *UNLOCKED_UNTIL.lock().unwrap() = None;
+    println!("[lock] 🔑 PIN removed");
+    Ok(emit_status(&app_handle, &state))
+}
+
+/// Allow the dangerous commands for the configured duration
+#[tauri::command]
+pub fn unlock_ui(app_handle: AppHandle, state: State<UiLockState>, pin: String) -> Result<UiLockStatus, String> {
+    state.check_pin(&pin)?;
+    let timeout_minutes = state.config.lock().unwrap().timeout_minutes;
+    *UNLOCKED_UNTIL.lock().unwrap() = Some(Instant::now() + Duration::from_secs(timeout_minutes as u64 * 60));
+    println!("[lock] 🔓 Unlocked for {} min", timeout_minutes);
+    Ok(emit_status(&app_handle, &state))
+}
+
+/// Lock again before the unlock expires
+#[tauri::command]
+pub fn lock_ui(app_handle: AppHandle, state: State<UiLockState>) -> UiLockStatus {
+    if UNLOCKED_UNTIL.lock().unwrap().take().is_some() {
+        println!("[lock] 🔒 Locked");
+    }
+    emit_status(&app_handle, &state)
+}
@@ -0,0 +1,321 @@
+/// Motor health monitor
+///
+/// Polls the per-motor readings the daemon reports with the motors status
+/// (temperature, input voltage, load, hardware error flags), keeps a rolling history
+/// per motor for the dashboard and raises alerts when thresholds are crossed.
+///
+/// Events:
+/// - `motor-overheating`: { motor, temperature, threshold, level: "warning" | "critical" },
+///   once per crossing (re-armed after cooling down by `TEMPERATURE_HYSTERESIS_C`)
+/// - `motor-hardware-error`: { motor, errors } when a motor raises new error flags
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::daemon_api::DaemonClient;
+use crate::local_proxy::LocalProxyState;
+
+const DEFAULT_INTERVAL: Duration = Duration::from_millis(1000);
+const MIN_INTERVAL: Duration = Duration::from_millis(200);
+/// Samples kept per motor (ten minutes at the default interval)
+const HISTORY_CAPACITY: usize = 600;
+const TEMPERATURE_HYSTERESIS_C: f64 = 5.0;
+
+/// Dynamixel "Hardware Error Status" bits
+const HARDWARE_ERROR_BITS: &[(u8, &str)] = &[
+    (0, "input_voltage"),
+    (2, "overheating"),
+    (3, "motor_encoder"),
+    (4, "electrical_shock"),
+    (5, "overload"),
+];
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+#[derive(Debug, Serialize, Clone)]
+pub struct MotorSample {
+    /// Unix millis
+    pub t: u64,
+    /// °C
+    pub temperature: Option<f64>,
+    /// Volts
+    pub voltage: Option<f64>,
+    /// Percent of the maximum torque (signed with the direction)
+    pub load: Option<f64>,
+    pub errors: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct MotorHealth {
+    pub motor: String,
+    pub latest: MotorSample,
+    /// Highest temperature in the history
+    pub max_temperature: Option<f64>,
+    pub overheating: Option<AlertLevel>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, PartialOrd)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertLevel {
+    Warning,
+    Critical,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MotorHealthThresholds {
+    pub temperature_warning: f64,
+    /// Dynamixel XL330 default temperature limit
+    pub temperature_critical: f64,
+}
+
+impl Default for MotorHealthThresholds {
+    fn default() -> Self {
+        Self {
+            temperature_warning: 60.0,
+            temperature_critical: 70.0,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+struct OverheatingAlert {
+    motor: String,
+    temperature: f64,
+    threshold: f64,
+    level: AlertLevel,
+}
+
+#[derive(Debug, Serialize, Clone)]
+struct HardwareErrorAlert {
+    motor: String,
+    errors: Vec<String>,
+}
+
+#[derive(Default)]
+struct MotorRecord {
+    history: VecDeque<MotorSample>,
+    overheating: Option<AlertLevel>,
+}
+
+#[derive(Default)]
+pub struct MotorHealthState {
+    motors: Mutex<BTreeMap<String, MotorRecord>>,
+    thresholds: Mutex<MotorHealthThresholds>,
+    monitor: Mutex<Option<JoinHandle<()>>>,
+}
+
+// ============================================================================
+// HELPER FUNCTIONS
+// ============================================================================
+
+impl MotorHealthState {
+    pub fn is_monitoring(&self) -> bool {
+        self.monitor.lock().unwrap().is_some()
+    }
+}
+
+fn number(reading: &serde_json::Value, keys: &[&str]) -> Option<f64> {
+    keys.iter().find_map(|key| reading[*key].as_f64())
+}
+
+/// Error flags as a bit field or a list of names
+fn error_flags(reading: &serde_json::Value) -> Vec<String> {
+    let value = &reading["hardware_error_status"];
+    let value = if value.is_null() { &reading["errors"] } else { value };
+    match value {
+        serde_json::Value::Number(bits) => {
+            let bits = bits.as_u64().unwrap_or(0);
+            HARDWARE_ERROR_BITS
+                .iter()
+                .filter(|(bit, _)| bits & (1 << bit) != 0)
+                .map(|(_, name)| name.to_string())
+                .collect()
+        }
+        serde_json::Value::Array(names) => names.iter().filter_map(|name| name.as_str().map(String::from)).collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Per-motor readings of a motors status: `motors` as a list of { id | name, ... }
+/// or an object keyed by motor name (empty on daemons that don't report them)
+fn parse_readings(status: &serde_json::Map<String, serde_json::Value>, t: u64) -> Vec<(String, MotorSample)> {
+    let entries: Vec<(String, &serde_json::Value)> = match status.get("motors") {
+        Some(serde_json::Value::Array(motors)) => motors
+            .iter()
+            .filter_map(|reading| {
+                let name = reading["name"]
+                    .as_str()
+                    .map(String::from)
+                    .or_else(|| reading["id"].as_u64().map(|id| id.to_string()))?;
+                Some((name, reading))
+            })
+            .collect(),
+        Some(serde_json::Value::Object(motors)) => motors.iter().map(|(name, reading)| (name.clone(), reading)).collect(),
+        _ => Vec::new(),
+    };
+
+    entries
+        .into_iter()
+        .map(|(name, reading)| {
+            let sample = MotorSample {
+                t,
+                temperature: number(reading, &["temperature", "present_temperature"]),
+                voltage: number(reading, &["voltage", "present_input_voltage"]),
+                load: number(reading, &["load", "present_load"]),
+                errors: error_flags(reading),
+            };
+            (name, sample)
+        })
+        .collect()
+}
+
+/// Alert level for a temperature; an alert stays up until the motor cools down by the hysteresis
+fn overheating_level(temperature: f64, previous: Option<AlertLevel>, thresholds: &MotorHealthThresholds) -> Option<AlertLevel> {
+    let cooling_down = |threshold: f64| temperature > threshold - TEMPERATURE_HYSTERESIS_C;
+    if temperature >= thresholds.temperature_critical
+        || (previous == Some(AlertLevel::Critical) && cooling_down(thresholds.temperature_critical))
+    {
+        Some(AlertLevel::Critical)
+    } else if temperature >= thresholds.temperature_warning
+        || (previous.is_some() && cooling_down(thresholds.temperature_warning))
+    {
+        Some(AlertLevel::Warning)
+    } else {
+        None
+    }
+}
+
+impl MotorHealthState {
+    /// Store new readings and emit the alerts they trigger
+    fn record(&self, app_handle: &AppHandle, readings: Vec<(String, MotorSample)>) {
+        let thresholds = self.thresholds.lock().unwrap().clone();
+        let mut motors = self.motors.lock().unwrap();
+
+        for (motor, sample) in readings {
+            let record = motors.entry(motor.clone()).or_default();
+
+            if let Some(temperature) = sample.temperature {
+                let level = overheating_level(temperature, record.overheating, &thresholds);
+                if let Some(level) = level.filter(|_| level > record.overheating) {
+                    let threshold = match level {
+                        AlertLevel::Warning => thresholds.temperature_warning,
+                        AlertLevel::Critical => thresholds.temperature_critical,
+                    };
+                    eprintln!("[motor-health] 🔥 Motor {} at {:.1}°C ({:?})", motor, temperature, level);
+                    let _ = app_handle.emit("motor-overheating", OverheatingAlert {
+                        motor: motor.clone(),
+                        temperature,
+                        threshold,
+                        level,
+                    });
+                }
+                record.overheating = level;
+            }
+
+            let previous_errors = record.history.back().map(|last| last.errors.clone()).unwrap_or_default();
+            if sample.errors.iter().any(|error| !previous_errors.contains(error)) {
+                eprintln!("[motor-health] ⚠️ Motor {} hardware error: {}", motor, sample.errors.join(", "));
+                let _ = app_handle.emit("motor-hardware-error", HardwareErrorAlert {
+                    motor: motor.clone(),
+                    errors: sample.errors.clone(),
+                });
+            }
+
+            record.history.push_back(sample);
+            if record.history.len() > HISTORY_CAPACITY {
+                record.history.pop_front();
+            }
+        }
+    }
+}
+
+async fn run_monitor(app_handle: AppHandle, interval: Duration) {
+    loop {
+        let client = DaemonClient::for_proxy(&app_handle.state::<Arc<LocalProxyState>>()).await;
+        if let Ok(status) = client.motors_status().await {
+            let readings = parse_readings(&status.extra, crate::daemon::history::now_millis());
+            app_handle.state::<MotorHealthState>().record(&app_handle, readings);
+        }
+        tokio::time::sleep(interval).await;
+    }
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Start polling motor health (restarts the monitor if already running)
+#[tauri::command]
+pub fn start_motor_health_monitor(app_handle: AppHandle, state: State<MotorHealthState>, interval_ms: Option<u64>) {
+    let interval = interval_ms
+        .map(Duration::from_millis)
+        .unwrap_or(DEFAULT_INTERVAL)
+        .max(MIN_INTERVAL);
+    let handle = tauri::async_runtime::spawn(run_monitor(app_handle, interval));
+    if let Some(previous) = state.monitor.lock().unwrap().replace(handle) {
+        previous.abort();
+    }
+    println!("[motor-health] 🩺 Monitoring motors every {:?}", interval);
+}
+
+#[tauri::command]
+pub fn stop_motor_health_monitor(state: State<MotorHealthState>) {
+    if let Some(monitor) = state.monitor.lock().unwrap().take() {
+        monitor.abort();
+        println!("[motor-health] ⏹️ Monitor stopped");
+    }
+}
+
+/// Latest reading of every motor seen since the monitor started
+#[tauri::command]
+pub fn get_motor_health(state: State<MotorHealthState>) -> Vec<MotorHealth> {
+    state
+        .motors
+        .lock()
+        .unwrap()
+        .iter()
+        .filter_map(|(motor, record)| {
+            Some(MotorHealth {
+                motor: motor.clone(),
+                latest: record.history.back()?.clone(),
+                max_temperature: record
+                    .history
+                    .iter()
+                    .filter_map(|sample| sample.temperature)
+                    .reduce(f64::max),
+                overheating: record.overheating,
+            })
+        })
+        .collect()
+}
+
+/// Rolling history of a motor, oldest first (`limit` most recent samples)
+#[tauri::command]
+pub fn get_motor_health_history(state: State<MotorHealthState>, motor: String, limit: Option<usize>) -> Vec<MotorSample> {
+    let motors = state.motors.lock().unwrap();
+    let Some(record) = motors.get(&motor) else {
+        return Vec::new();
+    };
+    let skip = limit.map(|limit| record.history.len().saturating_sub(limit)).unwrap_or(0);
+    record.history.iter().skip(skip).cloned().collect()
+}
+
+#[tauri::command]
+pub fn get_motor_health_thresholds(state: State<MotorHealthState>) -> MotorHealthThresholds {
+    state.thresholds.lock().unwrap().clone()
+}
+
+#[tauri::command]
+pub fn set_motor_health_thresholds(state: State<MotorHealthState>, thresholds: MotorHealthThresholds) -> Result<(), String> {
+    if thresholds.temperature_warning >= thresholds.temperature_critical {
+        return Err("The warning temperature must be below the critical one".to_string());
+    }
+    *state.thresholds.lock().unwrap() = thresholds;
+    Ok(())
+}
@@ -0,0 +1,361 @@
+/// MQTT bridge for home automation (Home Assistant, Node-RED...)
+///
+/// While the `mqtt` settings are enabled, the app connects to the broker and:
+/// - publishes a robot summary (connection, running app, head pose, battery) as retained
+///   JSON on `<base>/state` when it changes, and `online` / `offline` (last will) on
+///   `<base>/availability`
+/// - runs the commands received on `<base>/command/play_motion` ({ "dataset", "name" })
+///   and `<base>/command/say` (text, or { "text", "voice" }), answering each on
+///   `<base>/command/result` with { "command", "ok", "error" }
+/// - announces the sensors to Home Assistant (MQTT discovery) if enabled
+///
+/// The broker password is kept in the OS keychain, never in settings.json.
+
+use rumqttc::{AsyncClient, Event, LastWill, MqttOptions, Packet, QoS, TlsConfiguration, Transport};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Manager, State};
+
+use crate::connection::{ConnectionManager, ConnectionState};
+use crate::daemon_api::DaemonClient;
+use crate::local_proxy::LocalProxyState;
+use crate::settings::{MqttSettings, SettingsState};
+
+const KEYCHAIN_PASSWORD: &str = "mqtt-password";
+const KEEP_ALIVE: Duration = Duration::from_secs(30);
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+const STATE_TIMEOUT: Duration = Duration::from_secs(2);
+const DISCOVERY_PREFIX: &str = "homeassistant";
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct MqttStatus {
+    pub enabled: bool,
+    pub connected: bool,
+    /// "host:port" of the broker in use
+    pub broker: Option<String>,
+    pub password_set: bool,
+    pub last_error: Option<String>,
+}
+
+/// Payload of `<base>/state` (angles in degrees)
+#[derive(Debug, Serialize, Clone, PartialEq)]
+struct RobotSummary {
+    connection: String,
+    sim_mode: bool,
+    app: Option<String>,
+    head_roll: Option<f64>,
+    head_pitch: Option<f64>,
+    head_yaw: Option<f64>,
+    body_yaw: Option<f64>,
+    battery_percent: Option<f64>,
+    charging: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PlayMotionCommand {
+    dataset: String,
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SayCommand {
+    text: String,
+    #[serde(default)]
+    voice: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct CommandResult {
+    command: String,
+    ok: bool,
+    error: Option<String>,
+}
+
+#[derive(Default)]
+pub struct MqttState {
+    bridge: Mutex<Option<JoinHandle<()>>>,
+    status: Mutex<MqttStatus>,
+}
+
+// ============================================================================
+// HELPER FUNCTIONS
+// ============================================================================
+
+fn validate(settings: &MqttSettings) -> Result<(), String> {
+    if settings.enabled {
+        crate::local_proxy::profiles::validate_host(settings.host.trim()).map_err(|_| format!("Invalid broker host: {}", settings.host))?;
+    }
+    let base = settings.base_topic.trim();
+    if base.is_empty() || base.starts_with('/') || base.ends_with('/') || base.contains(['+', '#', ' ']) {
+        return Err(format!("Invalid base topic: {}", settings.base_topic));
+    }
+    if !(1..=3600).contains(&settings.publish_interval_secs) {
+        return Err("The publish interval must be 1 to 3600 seconds".to_string());
+    }
+    Ok(())
+}
+
+/// Radians to degrees, rounded to 0.1° so sensor noise doesn't republish the state
+fn degrees(radians: f64) -> f64 {
+    (radians.to_degrees() * 10.0).round() / 10.0
+}
+
+async fn summary(app_handle: &AppHandle) -> RobotSummary {
+    let connection = app_handle.state::<ConnectionManager>().get();
+    let battery = crate::power::get_power_status(app_handle.state());
+    let mut summary = RobotSummary {
+        connection: serde_json::to_value(&connection)
+            .ok()
+            .and_then(|value| value.get("state").and_then(|state| state.as_str()).map(String::from))
+            .unwrap_or_default(),
+        sim_mode: matches!(connection, ConnectionState::Ready { sim_mode: true, .. }),
+        app: None,
+        head_roll: None,
+        head_pitch: None,
+        head_yaw: None,
+        body_yaw: None,
+        battery_percent: battery.as_ref().map(|battery| battery.percent.round()),
+        charging: battery.and_then(|battery| battery.charging),
+    };
+    if !matches!(connection, ConnectionState::Ready { .. } | ConnectionState::WifiMode { .. }) {
+        return summary;
+    }
+
+    let proxy = app_handle.state::<Arc<LocalProxyState>>();
+    let client = DaemonClient::for_proxy(&proxy).await.with_timeout(STATE_TIMEOUT);
+    summary.app = client.running_app().await;
+    if let Ok(state) = client.robot_state().await {
+        if let Some(pose) = state.head_pose {
+            summary.head_roll = Some(degrees(pose.roll));
+            summary.head_pitch = Some(degrees(pose.pitch));
+            summary.head_yaw = Some(degrees(pose.yaw));
+        }
+        summary.body_yaw = state.body_yaw.map(degrees);
+    }
+    summary
+}
+
+/// Home Assistant discovery configs (topic, payload) for the state sensors
+fn discovery_configs(base: &str) -> Vec<(String, serde_json::Value)> {
+    let node = base.replace('/', "_");
+    let sensors = [
+        ("connection", "Connection", "{{ value_json.connection }}", None, None),
+        ("app", "Running app", "{{ value_json.app or 'none' }}", None, None),
+        ("battery", "Battery", "{{ value_json.battery_percent }}", Some("battery"), Some("%")),
+        ("head_yaw", "Head yaw", "{{ value_json.head_yaw }}", None, Some("°")),
+    ];
+    sensors
+        .into_iter()
+        .map(|(key, name, template, device_class, unit)| {
+            let mut config = serde_json::json!({
+                "name": name,
+                "unique_id": format!("{}_{}", node, key),
+                "state_topic": format!("{}/state", base),
+                "value_template": template,
+                "availability_topic": format!("{}/availability", base),
+                "device_class": device_class,
+                "unit_of_measurement": unit,
+                "device": {
+                    "identifiers": [node],
+                    "name": "Reachy Mini",
+                    "manufacturer": "Pollen Robotics",
+                },
+            });
+            // Home Assistant rejects null options
+            if let Some(config) = config.as_object_mut() {
+                config.retain(|_, value| !value.is_null());
+            }
+            (format!("{}/sensor/{}/{}/config", DISCOVERY_PREFIX, node, key), config)
+        })
+        .collect()
+}
+
+async fn run_command(app_handle: &AppHandle, command: &str, payload: &[u8]) -> Result<(), String> {
+    match command {
+        "play_motion" => {
+            let motion: PlayMotionCommand =
+                serde_json::from_slice(payload).map_err(|e| format!("Expected {{ \"dataset\", \"name\" }}: {}", e))?;
+            let params = serde_json::json!({ "dataset": motion.dataset, "name": motion.name });
+            crate::automation::dispatch(app_handle, "play_motion", &params).await.map(|_| ())
+        }
+        "say" => {
+            let say = serde_json::from_slice::<SayCommand>(payload).unwrap_or_else(|_| SayCommand {
+                text: String::from_utf8_lossy(payload).to_string(),
+                voice: None,
+            });
+            crate::audio::say_text(app_handle.clone(), app_handle.state(), say.text, say.voice)
+                .await
+                .map(|_| ())
+        }
+        _ => Err(format!("Unknown command: {}", command)),
+    }
+}
+
+fn set_error(app_handle: &AppHandle, error: Option<String>) {
+    let state = app_handle.state::<MqttState>();
+    let mut status = state.status.lock().unwrap();
+    if let Some(e) = &error {
+        // Logged once per outage
+        if status.connected || status.last_error.is_none() {
+            eprintln!("[mqtt] ⚠️ {}", e);
+        }
+    }
+    status.connected = error.is_none();
+    status.last_error = error;
+}
+
+/// Bridge task: keep the broker connection, publish the state and run the commands
+async fn run(app_handle: AppHandle, settings: MqttSettings, password: Option<String>) {
+    let base = settings.base_topic.trim().to_string();
+    let availability = format!("{}/availability", base);
+    let command_prefix = format!("{}/command/", base);
+
+    let client_id = format!("reachy-mini-control-{}", crate::local_proxy::profiles::generate_id());
+    let mut options = MqttOptions::new(client_id, settings.host.trim(), settings.port);
+    options.set_keep_alive(KEEP_ALIVE);
+    options.set_last_will(LastWill::new(&availability, "offline", QoS::AtLeastOnce, true));
+    if let Some(username) = settings.username.as_deref().filter(|username| !username.is_empty()) {
+        options.set_credentials(username, password.unwrap_or_default());
+    }
+    if settings.tls {
+        options.set_transport(Transport::tls_with_config(TlsConfiguration::Native));
+    }
+
+    let (client, mut eventloop) = AsyncClient::new(options, 16);
+    let mut ticker = tokio::time::interval(Duration::from_secs(settings.publish_interval_secs));
+    let mut published: Option<RobotSummary> = None;
+    loop {
+        tokio::select! {
+            event = eventloop.poll() => match event {
+                Ok(Event::Incoming(Packet::ConnAck(_))) => {
+                    println!("[mqtt] 📡 Connected to {}:{}", settings.host, settings.port);
+                    set_error(&app_handle, None);
+                    published = None;
+                    let _ = client.subscribe(format!("{}+", command_prefix), QoS::AtLeastOnce).await;
+                    let _ = client.publish(&availability, QoS::AtLeastOnce, true, "online").await;
+                    if settings.home_assistant_discovery {
+                        for (topic, config) in discovery_configs(&base) {
+                            let _ = client.publish(topic, QoS::AtLeastOnce, true, config.to_string()).await;
+                        }
+                    }
+                }
+                Ok(Event::Incoming(Packet::Publish(message))) => {
+                    let Some(command) = message.topic.strip_prefix(&command_prefix).map(String::from) else {
+                        continue;
+                    };
+                    if command == "result" {
+                        continue;
+                    }
+                    println!("[mqtt] 📥 Command {}", command);
+                    let handle = app_handle.clone();
+                    let client = client.clone();
+                    let result_topic = format!("{}result", command_prefix);
+                    tauri::async_runtime::spawn(async move {
+                        let result = run_command(&handle, &command, &message.payload).await;
+                        if let Err(e) = &result {
+                            eprintln!("[mqtt] ❌ Command {} failed: {}", command, e);
+                        }
+                        let result = CommandResult { command, ok: result.is_ok(), error: result.err() };
+                        let payload = serde_json::to_string(&result).unwrap_or_default();
+                        let _ = client.publish(result_topic, QoS::AtLeastOnce, false, payload).await;
+                    });
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    set_error(&app_handle, Some(format!("Broker {}:{}: {}", settings.host, settings.port, e)));
+                    tokio::time::sleep(RECONNECT_DELAY).await;
+                }
+            },
+            _ = ticker.tick() => {
+                if !app_handle.state::<MqttState>().status.lock().unwrap().connected {
+                    continue;
+                }
+                let current = summary(&app_handle).await;
+                if published.as_ref() != Some(&current) {
+                    let payload = serde_json::to_string(&current).unwrap_or_default();
+                    if client.publish(format!("{}/state", base), QoS::AtLeastOnce, true, payload).await.is_ok() {
+                        published = Some(current);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Stop the bridge, then start it again with the saved settings if enabled
+fn restart(app_handle: &AppHandle, password: Option<String>) {
+    let state = app_handle.state::<MqttState>();
+    if let Some(bridge) = state.bridge.lock().unwrap().take() {
+        bridge.abort();
+    }
+    let settings = app_handle.state::<SettingsState>().get().mqtt;
+    *state.status.lock().unwrap() = MqttStatus {
+        enabled: settings.enabled,
+        broker: settings.enabled.then(|| format!("{}:{}", settings.host.trim(), settings.port)),
+        password_set: password.is_some(),
+        ..MqttStatus::default()
+    };
+    if settings.enabled {
+        *state.bridge.lock().unwrap() = Some(tauri::async_runtime::spawn(run(app_handle.clone(), settings, password)));
+    }
+}
+
+/// Broker password from the keychain (blocking)
+fn load_password() -> Option<String> {
+    crate::local_proxy::auth::load_token(KEYCHAIN_PASSWORD)
+}
+
+// ============================================================================
+// SETUP
+// ============================================================================
+
+/// Start the bridge if enabled (call once in setup, after the settings are loaded)
+pub fn init(app_handle: &AppHandle) {
+    if !app_handle.state::<SettingsState>().get().mqtt.enabled {
+        return;
+    }
+    let handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        let password = tauri::async_runtime::spawn_blocking(load_password).await.ok().flatten();
+        restart(&handle, password);
+    });
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+#[tauri::command]
+pub fn get_mqtt_status(state: State<MqttState>) -> MqttStatus {
+    state.status.lock().unwrap().clone()
+}
+
+/// Save the bridge settings and apply them now; password None keeps the stored one,
+/// an empty password removes it
+#[tauri::command]
+pub async fn set_mqtt_settings(
+    app_handle: AppHandle,
+    settings: MqttSettings,
+    password: Option<String>,
+) -> Result<MqttStatus, String> {
+    validate(&settings)?;
+    let password = tauri::async_runtime::spawn_blocking(move || {
+        if let Some(password) = password {
+            let password = Some(password).filter(|password| !password.is_empty());
+            crate::local_proxy::auth::store_token(KEYCHAIN_PASSWORD, password.as_deref())?;
+        }
+        Ok::<_, String>(load_password())
+    })
+    .await
+    .map_err(|e| format!("Failed to access keychain: {}", e))??;
+
+    app_handle.state::<SettingsState>().update(|current| current.mqtt = settings)?;
+    restart(&app_handle, password);
+    Ok(app_handle.state::<MqttState>().status.lock().unwrap().clone())
+}
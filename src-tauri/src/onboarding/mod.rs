@@ -0,0 +1,244 @@
+/// First-run onboarding progress
+///
+/// Tracks the setup steps (permissions granted, Python environment ready, robot
+/// detected, calibration done, first app run) in `<app data>/onboarding.json`, so the
+/// wizard resumes where it left off after a crash or restart.
+///
+/// The environment and robot steps are detected here, the first app run is recorded
+/// when an app starts; permissions and calibration are reported by the frontend.
+/// Emits `onboarding-changed` with the new state on every change.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Listener, Manager, State};
+
+use crate::connection::{ConnectionManager, ConnectionState};
+
+const ONBOARDING_FILE: &str = "onboarding.json";
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+/// Setup steps, in wizard order
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum OnboardingStep {
+    Permissions,
+    VenvReady,
+    RobotDetected,
+    Calibration,
+    FirstAppRun,
+}
+
+const STEPS: [OnboardingStep; 5] = [
+    OnboardingStep::Permissions,
+    OnboardingStep::VenvReady,
+    OnboardingStep::RobotDetected,
+    OnboardingStep::Calibration,
+    OnboardingStep::FirstAppRun,
+];
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum StepStatus {
+    Pending,
+    Done,
+    Skipped,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+struct OnboardingStore {
+    /// Finished steps (done or skipped) with the unix millis they were finished at
+    steps: BTreeMap<OnboardingStep, (StepStatus, u64)>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct StepState {
+    pub step: OnboardingStep,
+    pub status: StepStatus,
+    pub finished_at: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct OnboardingState {
+    pub steps: Vec<StepState>,
+    /// First pending step (where the wizard resumes), None once everything is finished
+    pub current: Option<OnboardingStep>,
+    pub finished: bool,
+}
+
+pub struct OnboardingTracker {
+    store: Mutex<OnboardingStore>,
+    path: Mutex<Option<PathBuf>>,
+}
+
+// ============================================================================
+// HELPER FUNCTIONS
+// ============================================================================
+
+impl OnboardingStore {
+    fn state(&self) -> OnboardingState {
+        let steps: Vec<StepState> = STEPS
+            .iter()
+            .map(|&step| match self.steps.get(&step) {
+                Some(&(status, finished_at)) => StepState { step, status, finished_at: Some(finished_at) },
+                None => StepState { step, status: StepStatus::Pending, finished_at: None },
+            })
+            .collect();
+        let current = steps.iter().find(|step| step.status == StepStatus::Pending).map(|step| step.step);
+        OnboardingState {
+            steps,
+            current,
+            finished: current.is_none(),
+        }
+    }
+}
+
+impl OnboardingTracker {
+    pub fn new() -> Self {
+        Self {
+            store: Mutex::new(OnboardingStore::default()),
+            path: Mutex::new(None),
+        }
+    }
+
+    /// Load progress from the app data directory (nothing done if missing or corrupted)
+    pub fn load(&self, app_data_dir: &Path) {
+        let path = app_data_dir.join(ONBOARDING_FILE);
+        let store = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        *self.store.lock().unwrap() = store;
+        *self.path.lock().unwrap() = Some(path);
+    }
+
+    pub fn state(&self) -> OnboardingState {
+        self.store.lock().unwrap().state()
+    }
+
+    /// Apply a change, persist it if something changed, returns the new state in that case
+    fn update(&self, change: impl FnOnce(&mut OnboardingStore) -> bool) -> Result<Option<OnboardingState>, String> {
+        let mut store = self.store.lock().unwrap();
+        if !change(&mut store) {
+            return Ok(None);
+        }
+
+        if let Some(path) = self.path.lock().unwrap().as_ref() {
+            let content = serde_json::to_string_pretty(&*store)
+                .map_err(|e| format!("Failed to serialize onboarding progress: {}", e))?;
+            std::fs::write(path, content)
+                .map_err(|e| format!("Failed to write onboarding progress: {}", e))?;
+        }
+        Ok(Some(store.state()))
+    }
+}
+
+impl Default for OnboardingTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Set a step's status (done wins over skipped), emitting `onboarding-changed` if it changed
+fn set_status(app_handle: &AppHandle, step: OnboardingStep, status: StepStatus) -> Result<OnboardingState, String> {
+    let tracker = app_handle.state::<OnboardingTracker>();
+    let changed = tracker.update(|store| match (store.steps.get(&step), status) {
+        (Some((StepStatus::Done, _)), _) => false,
+        (Some((current, _)), _) if *current == status => false,
+        (_, StepStatus::Pending) => store.steps.remove(&step).is_some(),
+        _ => {
+            store.steps.insert(step, (status, crate::daemon::history::now_millis()));
+            true
+        }
+    })?;
+    match changed {
+        Some(state) => {
+            println!("[onboarding] 🧭 {:?}: {:?}", step, status);
+            let _ = app_handle.emit("onboarding-changed", &state);
+            Ok(state)
+        }
+        None => Ok(tracker.state()),
+    }
+}
+
+/// Record a finished step (no-op once done)
+pub fn mark_done(app_handle: &AppHandle, step: OnboardingStep) {
+    if let Err(e) = set_status(app_handle, step, StepStatus::Done) {
+        eprintln!("[onboarding] ⚠️ {}", e);
+    }
+}
+
+/// Mark the steps that can be checked from here (environment, robot)
+fn detect(app_handle: &AppHandle) {
+    let venv_ready = crate::update::get_local_venv_path(app_handle)
+        .and_then(|venv| crate::update::get_python_path(&venv))
+        .is_ok();
+    if venv_ready {
+        mark_done(app_handle, OnboardingStep::VenvReady);
+    }
+
+    let robot_connected = matches!(
+        app_handle.state::<ConnectionManager>().get(),
+        ConnectionState::UsbDetected { .. }
+            | ConnectionState::Ready { sim_mode: false, .. }
+            | ConnectionState::WifiMode { .. }
+    );
+    if robot_connected || crate::usb::get_reachy_port().is_some() {
+        mark_done(app_handle, OnboardingStep::RobotDetected);
+    }
+}
+
+// ============================================================================
+// SETUP
+// ============================================================================
+
+/// Detect the steps already done and follow the robot connection (call once in setup)
+pub fn init(app_handle: &AppHandle) {
+    detect(app_handle);
+
+    let handle = app_handle.clone();
+    app_handle.listen_any("connection-state-changed", move |_| {
+        if !handle.state::<OnboardingTracker>().state().finished {
+            detect(&handle);
+        }
+    });
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+#[tauri::command]
+pub fn get_onboarding_state(app_handle: AppHandle, tracker: State<OnboardingTracker>) -> OnboardingState {
+    detect(&app_handle);
+    tracker.state()
+}
+
+/// Report a step done by the frontend (permissions, calibration)
+#[tauri::command]
+pub fn complete_onboarding_step(app_handle: AppHandle, step: OnboardingStep) -> Result<OnboardingState, String> {
+    set_status(&app_handle, step, StepStatus::Done)
+}
+
+/// Skip a step (e.g. simulation only, no robot yet), it stays skipped until done or reset
+#[tauri::command]
+pub fn skip_onboarding_step(app_handle: AppHandle, step: OnboardingStep) -> Result<OnboardingState, String> {
+    set_status(&app_handle, step, StepStatus::Skipped)
+}
+
+/// Replay the whole wizard
+#[tauri::command]
+pub fn reset_onboarding(app_handle: AppHandle, tracker: State<OnboardingTracker>) -> Result<OnboardingState, String> {
+    tracker.update(|store| {
+        store.steps.clear();
+        true
+    })?;
+    detect(&app_handle);
+    let state = tracker.state();
+    let _ = app_handle.emit("onboarding-changed", &state);
+    Ok(state)
+}
@@ -0,0 +1,265 @@
+/// Serial port access on Linux
+///
+/// The robot's board shows up as /dev/ttyACM*, owned by root:dialout. The .deb installs
+/// a udev rule opening it to every user and adds the user to `dialout`, but AppImage
+/// users (or anyone who skipped a step) get "Permission denied" from the daemon.
+///
+/// `serial_access` tells what is missing: the udev rule, the group membership, or only a
+/// new login session (groups are read at login). `fix_serial_access` installs the rule
+/// and adds the user to `dialout` in one pkexec prompt.
+
+use serde::Serialize;
+use std::ffi::{CStr, CString};
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+const RULES_PATH: &str = "/etc/udev/rules.d/99-reachy-mini.rules";
+const RULES: &str = include_str!("../../linux/99-reachy-mini.rules");
+const SERIAL_GROUP: &str = "dialout";
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum UdevRuleStatus {
+    Installed,
+    /// Present but different from the one shipped with the app
+    Outdated,
+    Missing,
+}
+
+/// What the user has to do, in order
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SerialAccessAction {
+    /// `fix_serial_access` (admin password)
+    InstallUdevRule,
+    /// `fix_serial_access` (admin password)
+    AddToGroup,
+    /// Group added but not active in this session
+    LogOutAndIn,
+    /// Rule installed after the robot was plugged in
+    ReplugRobot,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct SerialAccessReport {
+    /// Robot serial port (None = no robot plugged in)
+    pub port: Option<String>,
+    /// Read/write access to `port`
+    pub port_accessible: Option<bool>,
+    pub udev_rule: UdevRuleStatus,
+    pub group: &'static str,
+    /// The user is a member of `group` (takes effect at next login)
+    pub in_group: bool,
+    /// `group` is active in this session
+    pub group_active: bool,
+    pub actions: Vec<SerialAccessAction>,
+    /// Nothing stands between the daemon and the robot
+    pub ok: bool,
+    pub message: String,
+}
+
+struct GroupMembership {
+    user: String,
+    configured: bool,
+    active: bool,
+}
+
+// ============================================================================
+// HELPER FUNCTIONS
+// ============================================================================
+
+fn udev_rule_status() -> UdevRuleStatus {
+    match std::fs::read_to_string(RULES_PATH) {
+        Ok(content) if content.trim() == RULES.trim() => UdevRuleStatus::Installed,
+        Ok(_) => UdevRuleStatus::Outdated,
+        Err(_) => UdevRuleStatus::Missing,
+    }
+}
+
+/// Membership in `SERIAL_GROUP` from the user database (NSS, so LDAP/sssd users too)
+/// and from this process's groups
+fn group_membership() -> Option<GroupMembership> {
+    let group_name = CString::new(SERIAL_GROUP).ok()?;
+    unsafe {
+        let passwd = libc::getpwuid(libc::getuid());
+        if passwd.is_null() {
+            return None;
+        }
+        let user = CStr::from_ptr((*passwd).pw_name).to_string_lossy().into_owned();
+        let primary_gid = (*passwd).pw_gid;
+
+        let group = libc::getgrnam(group_name.as_ptr());
+        if group.is_null() {
+            return None;
+        }
+        let gid = (*group).gr_gid;
+        let mut configured = primary_gid == gid;
+        let mut member = (*group).gr_mem;
+        while !configured && !member.is_null() && !(*member).is_null() {
+            configured = CStr::from_ptr(*member).to_string_lossy() == user;
+            member = member.add(1);
+        }
+
+        let count = libc::getgroups(0, std::ptr::null_mut());
+        let mut groups = vec![0; count.max(0) as usize];
+        let count = libc::getgroups(count, groups.as_mut_ptr());
+        groups.truncate(count.max(0) as usize);
+        let active = libc::getegid() == gid || groups.contains(&gid);
+
+        Some(GroupMembership { user, configured, active })
+    }
+}
+
+fn is_accessible(port: &str) -> bool {
+    let Ok(path) = CString::new(port) else {
+        return false;
+    };
+    unsafe { libc::access(path.as_ptr(), libc::R_OK | libc::W_OK) == 0 }
+}
+
+fn message(actions: &[SerialAccessAction], port: Option<&str>) -> String {
+    let steps: Vec<&str> = actions
+        .iter()
+        .map(|action| match action {
+            SerialAccessAction::InstallUdevRule => "install the Reachy Mini udev rule",
+            SerialAccessAction::AddToGroup => "add your user to the dialout group",
+            SerialAccessAction::LogOutAndIn => "log out and back in",
+            SerialAccessAction::ReplugRobot => "unplug and replug the robot",
+        })
+        .collect();
+    match (steps.is_empty(), port) {
+        (true, Some(port)) => format!("{} is accessible", port),
+        (true, None) => "Serial access is set up (no robot plugged in)".to_string(),
+        (false, _) => format!("To access the robot: {}", steps.join(", then ")),
+    }
+}
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// What stands between the user and the robot's serial port
+pub fn serial_access() -> SerialAccessReport {
+    let port = crate::usb::get_reachy_port();
+    let port_accessible = port.as_deref().map(is_accessible);
+    let udev_rule = udev_rule_status();
+    let membership = group_membership();
+    let in_group = membership.as_ref().is_some_and(|membership| membership.configured);
+    let group_active = membership.as_ref().is_some_and(|membership| membership.active);
+
+    // The rule opens the port to everyone: the group only matters without it
+    let mut actions = Vec::new();
+    if port_accessible != Some(true) {
+        if udev_rule != UdevRuleStatus::Installed {
+            actions.push(SerialAccessAction::InstallUdevRule);
+        }
+        if !in_group {
+            actions.push(SerialAccessAction::AddToGroup);
+        } else if !group_active {
+            actions.push(SerialAccessAction::LogOutAndIn);
+        }
+        if port.is_some() && actions.is_empty() {
+            actions.push(SerialAccessAction::ReplugRobot);
+        }
+    }
+
+    SerialAccessReport {
+        message: message(&actions, port.as_deref()),
+        ok: actions.is_empty(),
+        port,
+        port_accessible,
+        udev_rule,
+        group: SERIAL_GROUP,
+        in_group,
+        group_active,
+        actions,
+    }
+}
+
+/// Install the udev rule and/or add the user to the group, as root through pkexec
+fn fix(report: &SerialAccessReport) -> Result<(), String> {
+    let install_rule = report.udev_rule != UdevRuleStatus::Installed;
+    let add_to_group = !report.in_group;
+    if !install_rule && !add_to_group {
+        return Ok(());
+    }
+    let user = group_membership()
+        .map(|membership| membership.user)
+        .ok_or_else(|| format!("Could not read your user or the {} group", SERIAL_GROUP))?;
+
+    let mut script = vec!["set -e".to_string()];
+    if install_rule {
+        script.push(format!("cat > {0} && chmod 0644 {0}", RULES_PATH));
+        script.push("udevadm control --reload-rules".to_string());
+        script.push("udevadm trigger --subsystem-match=tty".to_string());
+    }
+    if add_to_group {
+        script.push(format!("usermod -aG {} \"$1\"", SERIAL_GROUP));
+    }
+
+    // The rule goes through stdin and the user name as an argument, never through the
+    // script text (and no temporary file another user could swap before root reads it)
+    let child = Command::new("pkexec")
+        .args(["sh", "-c", &script.join("\n"), "sh", &user])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn();
+    let mut child = match child {
+        Ok(child) => child,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Err(format!(
+                "pkexec is not available: copy the Reachy Mini udev rule to {} and run `sudo usermod -aG {} {}` in a terminal",
+                RULES_PATH, SERIAL_GROUP, user
+            ));
+        }
+        Err(e) => return Err(format!("Failed to run pkexec: {}", e)),
+    };
+    if let Some(mut stdin) = child.stdin.take() {
+        if install_rule {
+            // Fails only if the authentication was cancelled (reported below)
+            let _ = stdin.write_all(RULES.as_bytes());
+        }
+    }
+    let output = child
+        .wait_with_output()
+        .map_err(|e| format!("Failed to run pkexec: {}", e))?;
+    match output.status.code() {
+        Some(0) => Ok(()),
+        // Authentication dialog dismissed / not authorized
+        Some(126) | Some(127) => Err("Administrator authentication was cancelled".to_string()),
+        _ => Err(format!(
+            "Failed to set up serial access: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )),
+    }
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Serial port access status, with the steps left to reach the robot
+#[tauri::command]
+pub fn get_serial_access_status() -> SerialAccessReport {
+    serial_access()
+}
+
+/// Install the udev rule and add the user to `dialout` (one admin prompt), then report
+/// what is left (usually logging out and back in, or replugging the robot)
+#[tauri::command]
+pub async fn fix_serial_access() -> Result<SerialAccessReport, String> {
+    tauri::async_runtime::spawn_blocking(|| {
+        let report = serial_access();
+        fix(&report)?;
+        let report = serial_access();
+        println!("[permissions] 🔌 Serial access set up: {}", report.message);
+        Ok(report)
+    })
+    .await
+    .map_err(|e| format!("Serial access setup task failed: {}", e))?
+}
@@ -1,8 +1,145 @@
 /// Module for managing cross-platform permissions (camera, microphone, etc.)
 /// 
-/// Note: Camera/microphone permissions are managed by tauri-plugin-macos-permissions
-/// This module only provides functions to open System Settings
-/// and the initialization function at startup.
+/// Note: Camera/microphone permissions are requested by tauri-plugin-macos-permissions
+/// This module provides functions to open System Settings, the initialization function
+/// at startup, and `get_permission_status` (queried from AVFoundation / IOKit on macOS)
+/// for the onboarding UI. Serial port access on Linux (udev rule, dialout group) is in
+/// `linux`.
+
+use serde::Serialize;
+
+#[cfg(target_os = "linux")]
+pub mod linux;
+
+// ============================================================================
+// Permission status
+// ============================================================================
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum PermissionKind {
+    Camera,
+    Microphone,
+    /// Keyboard / HID input from other apps (gamepads on macOS)
+    InputMonitoring,
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+pub enum PermissionStatus {
+    Granted,
+    Denied,
+    /// Blocked by a device policy (parental controls, MDM): the user can't change it
+    Restricted,
+    /// Not asked yet: the OS prompts on first use
+    NotDetermined,
+    /// This platform doesn't report it
+    Unknown,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct PermissionEntry {
+    pub kind: PermissionKind,
+    pub status: PermissionStatus,
+    /// Needed for the app's main features (else only for some apps or options)
+    pub required: bool,
+    /// Command opening the matching settings page
+    pub settings_command: &'static str,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct PermissionReport {
+    pub permissions: Vec<PermissionEntry>,
+    /// No required permission denied or restricted
+    pub all_required_granted: bool,
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+    use super::PermissionStatus;
+    use cocoa::base::{id, nil};
+    use cocoa::foundation::NSString;
+    use objc::{class, msg_send, sel, sel_impl};
+
+    #[link(name = "IOKit", kind = "framework")]
+    extern "C" {
+        fn IOHIDCheckAccess(request_type: u32) -> u32;
+    }
+
+    /// kIOHIDRequestTypeListenEvent
+    const HID_REQUEST_LISTEN_EVENT: u32 = 1;
+
+    /// AVAuthorizationStatus for AVMediaTypeVideo ("vide") or AVMediaTypeAudio ("soun")
+    pub fn capture_status(media_type: &str) -> PermissionStatus {
+        let status: i64 = unsafe {
+            let media_type: id = NSString::alloc(nil).init_str(media_type);
+            let status: i64 = msg_send![class!(AVCaptureDevice), authorizationStatusForMediaType: media_type];
+            let _: () = msg_send![media_type, release];
+            status
+        };
+        match status {
+            0 => PermissionStatus::NotDetermined,
+            1 => PermissionStatus::Restricted,
+            2 => PermissionStatus::Denied,
+            3 => PermissionStatus::Granted,
+            _ => PermissionStatus::Unknown,
+        }
+    }
+
+    /// IOHIDAccessType: granted, denied or unknown (not asked yet)
+    pub fn input_monitoring_status() -> PermissionStatus {
+        match unsafe { IOHIDCheckAccess(HID_REQUEST_LISTEN_EVENT) } {
+            0 => PermissionStatus::Granted,
+            1 => PermissionStatus::Denied,
+            _ => PermissionStatus::NotDetermined,
+        }
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn query_status(kind: PermissionKind) -> PermissionStatus {
+    match kind {
+        PermissionKind::Camera => macos::capture_status("vide"),
+        PermissionKind::Microphone => macos::capture_status("soun"),
+        PermissionKind::InputMonitoring => macos::input_monitoring_status(),
+    }
+}
+
+#[cfg(not(target_os = "macos"))]
+fn query_status(_kind: PermissionKind) -> PermissionStatus {
+    PermissionStatus::Unknown
+}
+
+/// Camera, microphone and (macOS) input monitoring status, for the onboarding UI
+#[tauri::command]
+pub fn get_permission_status() -> PermissionReport {
+    let mut kinds = vec![
+        (PermissionKind::Camera, true, "open_camera_settings"),
+        (PermissionKind::Microphone, true, "open_microphone_settings"),
+    ];
+    if cfg!(target_os = "macos") {
+        kinds.push((PermissionKind::InputMonitoring, false, "open_input_monitoring_settings"));
+    }
+
+    let permissions: Vec<PermissionEntry> = kinds
+        .into_iter()
+        .map(|(kind, required, settings_command)| PermissionEntry {
+            kind,
+            status: query_status(kind),
+            required,
+            settings_command,
+        })
+        .collect();
+    let all_required_granted = permissions
+        .iter()
+        .filter(|permission| permission.required)
+        .all(|permission| !matches!(permission.status, PermissionStatus::Denied | PermissionStatus::Restricted));
+    PermissionReport {
+        permissions,
+        all_required_granted,
+    }
+}
 
 /// Log configured permissions at app startup (macOS only)
 #[cfg(target_os = "macos")]
@@ -115,6 +252,32 @@ pub fn open_files_settings() -> Result<(), String> {
     Ok(())
 }
 
+/// Open System Settings to Privacy & Security > Input Monitoring (macOS)
+#[tauri::command]
+#[cfg(target_os = "macos")]
+pub fn open_input_monitoring_settings() -> Result<(), String> {
+    use std::process::Command;
+    
+    let output = Command::new("open")
+        .arg("x-apple.systempreferences:com.apple.preference.security?Privacy_ListenEvent")
+        .output()
+        .map_err(|e| format!("Failed to open System Settings: {}", e))?;
+    
+    if !output.status.success() {
+        return Err(format!("Failed to open System Settings: {}", 
+            String::from_utf8_lossy(&output.stderr)));
+    }
+    
+    Ok(())
+}
+
+/// Input Monitoring is a macOS permission (no-op elsewhere)
+#[tauri::command]
+#[cfg(not(target_os = "macos"))]
+pub fn open_input_monitoring_settings() -> Result<(), String> {
+    Ok(())
+}
+
 // ============================================================================
 // Windows Implementation
 // ============================================================================
@@ -0,0 +1,273 @@
+/// Battery and power status (wireless Reachy Mini)
+///
+/// The wireless robot reports its battery in the daemon status, either as a `battery`
+/// object or as flat `battery_*` keys, at the top level or in the backend status.
+/// Robots without a battery (USB Lite, simulation) report nothing: the status is None.
+///
+/// A background monitor polls the daemon every `POLL_INTERVAL` while a robot is
+/// connected. When the daemon gives no runtime, it is estimated from the discharge rate
+/// over the last minutes. Events:
+/// - `power-status`: PowerStatus | null, when it changed
+/// - `battery-low`: { percent, level: "warning" | "critical" }, once per crossing
+///   (re-armed when charging or `LEVEL_HYSTERESIS` above the threshold)
+/// - `battery-safe-park`: { error } after a critical level parked the robot
+///   (running app stopped, sleep pose, motors compliant) when `auto_park` is on
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::connection::{ConnectionManager, ConnectionState};
+use crate::daemon_api::{DaemonClient, DaemonStatus};
+use crate::local_proxy::LocalProxyState;
+use crate::motor_health::AlertLevel;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(15);
+const STATUS_TIMEOUT: Duration = Duration::from_secs(3);
+const PARK_TIMEOUT: Duration = Duration::from_secs(10);
+/// Discharge samples used for the runtime estimate
+const ESTIMATE_WINDOW_MS: u64 = 10 * 60 * 1000;
+const MIN_ESTIMATE_SPAN_MS: u64 = 2 * 60 * 1000;
+/// Percent
+const LEVEL_HYSTERESIS: f64 = 5.0;
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct PowerStatus {
+    /// 0-100
+    pub percent: f64,
+    pub charging: Option<bool>,
+    /// Volts
+    pub voltage: Option<f64>,
+    /// Minutes left on battery (None while charging or not known yet)
+    pub runtime_minutes: Option<f64>,
+    /// The runtime comes from the discharge rate, not from the robot
+    pub runtime_estimated: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PowerThresholds {
+    /// Percent
+    pub low: f64,
+    /// Percent
+    pub critical: f64,
+    /// Park the robot when the battery reaches `critical` (not charging)
+    pub auto_park: bool,
+}
+
+impl Default for PowerThresholds {
+    fn default() -> Self {
+        Self {
+            low: 20.0,
+            critical: 7.0,
+            auto_park: true,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+struct BatteryAlert {
+    percent: f64,
+    level: AlertLevel,
+}
+
+#[derive(Debug, Serialize, Clone)]
+struct SafeParkResult {
+    error: Option<String>,
+}
+
+#[derive(Default)]
+pub struct PowerState {
+    status: Mutex<Option<PowerStatus>>,
+    /// (unix millis, percent) while discharging
+    discharge: Mutex<VecDeque<(u64, f64)>>,
+    alert: Mutex<Option<AlertLevel>>,
+    thresholds: Mutex<PowerThresholds>,
+}
+
+// ============================================================================
+// HELPER FUNCTIONS
+// ============================================================================
+
+/// Battery readings of a daemon status (None if the robot has no battery)
+pub fn parse_battery(status: &DaemonStatus) -> Option<PowerStatus> {
+    let backend = status.backend_status.as_ref();
+    let nested = status
+        .extra
+        .get("battery")
+        .or_else(|| backend.and_then(|backend| backend.get("battery")))
+        .filter(|battery| battery.is_object());
+    let find = |nested_keys: &[&str], flat_keys: &[&str]| -> Option<&serde_json::Value> {
+        let nested = nested.and_then(|battery| nested_keys.iter().find_map(|key| battery.get(*key)));
+        nested.or_else(|| {
+            flat_keys.iter().find_map(|key| {
+                status
+                    .extra
+                    .get(*key)
+                    .or_else(|| backend.and_then(|backend| backend.get(*key)))
+            })
+        })
+    };
+
+    let percent = find(&["level", "percent"], &["battery_level", "battery_percent", "battery"])?.as_f64()?;
+    let runtime_minutes = find(&["time_remaining_min", "runtime_minutes"], &["battery_time_remaining_min"])
+        .and_then(|value| value.as_f64());
+    Some(PowerStatus {
+        percent: percent.clamp(0.0, 100.0),
+        charging: find(&["charging", "is_charging"], &["battery_charging"]).and_then(|value| value.as_bool()),
+        voltage: find(&["voltage"], &["battery_voltage"]).and_then(|value| value.as_f64()),
+        runtime_estimated: false,
+        runtime_minutes,
+    })
+}
+
+/// Minutes left at the discharge rate of the samples (None below `MIN_ESTIMATE_SPAN_MS`)
+fn estimate_runtime(samples: &VecDeque<(u64, f64)>, percent: f64) -> Option<f64> {
+    let (first_t, first_percent) = *samples.front()?;
+    let (last_t, last_percent) = *samples.back()?;
+    let span = last_t.saturating_sub(first_t);
+    let drop = first_percent - last_percent;
+    if span < MIN_ESTIMATE_SPAN_MS || drop <= 0.0 {
+        return None;
+    }
+    let percent_per_minute = drop / (span as f64 / 60_000.0);
+    Some(percent / percent_per_minute)
+}
+
+/// Alert level for a battery level; an alert stays up until charging or recharged by the hysteresis
+fn battery_level(status: &PowerStatus, previous: Option<AlertLevel>, thresholds: &PowerThresholds) -> Option<AlertLevel> {
+    if status.charging == Some(true) {
+        return None;
+    }
+    let recovering = |threshold: f64| status.percent < threshold + LEVEL_HYSTERESIS;
+    if status.percent <= thresholds.critical
+        || (previous == Some(AlertLevel::Critical) && recovering(thresholds.critical))
+    {
+        Some(AlertLevel::Critical)
+    } else if status.percent <= thresholds.low || (previous.is_some() && recovering(thresholds.low)) {
+        Some(AlertLevel::Warning)
+    } else {
+        None
+    }
+}
+
+impl PowerState {
+    /// Store a new reading (with the runtime estimate), emit the events it triggers;
+    /// returns true when the robot must be parked
+    fn record(&self, app_handle: &AppHandle, status: Option<PowerStatus>, t: u64) -> bool {
+        let status = status.map(|mut status| {
+            let mut discharge = self.discharge.lock().unwrap();
+            if status.charging == Some(true) {
+                discharge.clear();
+            } else {
+                discharge.push_back((t, status.percent));
+                while discharge.front().is_some_and(|(first, _)| t.saturating_sub(*first) > ESTIMATE_WINDOW_MS) {
+                    discharge.pop_front();
+                }
+                if status.runtime_minutes.is_none() {
+                    status.runtime_minutes = estimate_runtime(&discharge, status.percent);
+                    status.runtime_estimated = status.runtime_minutes.is_some();
+                }
+            }
+            status
+        });
+
+        let mut park = false;
+        let thresholds = self.thresholds.lock().unwrap().clone();
+        let mut alert = self.alert.lock().unwrap();
+        let level = status.as_ref().and_then(|status| battery_level(status, *alert, &thresholds));
+        if let (Some(level), Some(status)) = (level.filter(|_| level > *alert), &status) {
+            eprintln!("[power] 🪫 Battery at {:.0}% ({:?})", status.percent, level);
+            let _ = app_handle.emit("battery-low", BatteryAlert {
+                percent: status.percent,
+                level,
+            });
+            park = level == AlertLevel::Critical && thresholds.auto_park;
+        }
+        *alert = level;
+
+        let mut current = self.status.lock().unwrap();
+        if *current != status {
+            let _ = app_handle.emit("power-status", &status);
+            *current = status;
+        }
+        park
+    }
+}
+
+/// Stop the running app, play the sleep move and set the motors compliant
+async fn safe_park(app_handle: &AppHandle, client: &DaemonClient) {
+    println!("[power] 💤 Battery critical: parking robot");
+    let error = match tokio::time::timeout(PARK_TIMEOUT, crate::daemon::park::park(client)).await {
+        Ok(Ok(())) => None,
+        Ok(Err(e)) => Some(e),
+        Err(_) => Some(format!("Park sequence timed out after {:?}", PARK_TIMEOUT)),
+    };
+    match &error {
+        Some(e) => eprintln!("[power] ⚠️ Safe park failed: {}", e),
+        None => println!("[power] ✅ Robot parked"),
+    }
+    let _ = app_handle.emit("battery-safe-park", SafeParkResult { error });
+}
+
+async fn poll(client: &DaemonClient) -> Option<PowerStatus> {
+    client.daemon_status().await.ok().as_ref().and_then(parse_battery)
+}
+
+async fn run_monitor(app_handle: AppHandle) {
+    loop {
+        let connected = matches!(
+            app_handle.state::<ConnectionManager>().get(),
+            ConnectionState::Ready { sim_mode: false, .. } | ConnectionState::WifiMode { .. }
+        );
+        let proxy = app_handle.state::<Arc<LocalProxyState>>();
+        let client = DaemonClient::for_proxy(&proxy).await.with_timeout(STATUS_TIMEOUT);
+        let status = if connected { poll(&client).await } else { None };
+        let state = app_handle.state::<PowerState>();
+        if state.record(&app_handle, status, crate::daemon::history::now_millis()) {
+            safe_park(&app_handle, &client).await;
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
+
+// ============================================================================
+// SETUP
+// ============================================================================
+
+/// Start the battery monitor (call once in setup)
+pub fn start_monitor(app_handle: AppHandle) {
+    tauri::async_runtime::spawn(run_monitor(app_handle));
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Battery status from the last poll (None without a battery-powered robot)
+#[tauri::command]
+pub fn get_power_status(state: State<PowerState>) -> Option<PowerStatus> {
+    state.status.lock().unwrap().clone()
+}
+
+#[tauri::command]
+pub fn get_power_thresholds(state: State<PowerState>) -> PowerThresholds {
+    state.thresholds.lock().unwrap().clone()
+}
+
+#[tauri::command]
+pub fn set_power_thresholds(state: State<PowerState>, thresholds: PowerThresholds) -> Result<(), String> {
+    if !(0.0..=100.0).contains(&thresholds.low) || !(0.0..=100.0).contains(&thresholds.critical) {
+        return Err("Battery thresholds must be between 0 and 100%".to_string());
+    }
+    if thresholds.critical >= thresholds.low {
+        return Err("The critical battery level must be below the low one".to_string());
+    }
+    *state.thresholds.lock().unwrap() = thresholds;
+    Ok(())
+}
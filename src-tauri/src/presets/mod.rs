@@ -0,0 +1,244 @@
+/// Head pose presets
+///
+/// Named poses the head can be sent to: built-in ones (neutral, look up, nod position,
+/// sleep pose) plus user presets saved in `<app data>/presets.json`. A user preset with
+/// the name of a built-in one replaces it.
+///
+/// `go_to_preset` checks the pose is reachable (within the ranges the daemon accepts)
+/// before starting a minimum-jerk move through the daemon. Without a duration, it is
+/// planned from the distance to the current pose so small and large moves look alike.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tauri::State;
+
+use crate::daemon_api::{DaemonClient, GotoRequest, HeadPose, BODY_YAW_LIMIT};
+use crate::local_proxy::LocalProxyState;
+
+const PRESETS_FILE: &str = "presets.json";
+/// Planned transitions: rotation speed, translation speed and duration bounds
+const PLANNED_SPEED_DEG_S: f64 = 90.0;
+const PLANNED_SPEED_M_S: f64 = 0.05;
+const MIN_DURATION_S: f64 = 0.5;
+const MAX_DURATION_S: f64 = 10.0;
+const INTERPOLATION: &str = "minjerk";
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PosePreset {
+    pub name: String,
+    pub head_pose: HeadPose,
+    /// [left, right] in radians, None = antennas don't move
+    #[serde(default)]
+    pub antennas: Option<[f64; 2]>,
+    /// None = body doesn't move
+    #[serde(default)]
+    pub body_yaw: Option<f64>,
+    #[serde(default)]
+    pub builtin: bool,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct PresetMove {
+    /// Daemon move id
+    pub uuid: String,
+    pub duration: f64,
+}
+
+pub struct PresetsState {
+    /// User presets
+    presets: Mutex<Vec<PosePreset>>,
+    path: Mutex<Option<PathBuf>>,
+}
+
+// ============================================================================
+// HELPER FUNCTIONS
+// ============================================================================
+
+fn builtin(name: &str, head_pose: HeadPose, antennas: Option<[f64; 2]>) -> PosePreset {
+    PosePreset {
+        name: name.to_string(),
+        head_pose,
+        antennas,
+        body_yaw: None,
+        builtin: true,
+    }
+}
+
+/// Presets available out of the box
+fn builtin_presets() -> Vec<PosePreset> {
+    vec![
+        builtin("neutral", HeadPose::default(), Some([0.0, 0.0])),
+        builtin("look_up", HeadPose { pitch: -0.4, ..Default::default() }, None),
+        // Head slightly down, where a nod starts from
+        builtin("nod", HeadPose { pitch: 0.3, ..Default::default() }, None),
+        // Head lowered and tucked in, antennas folded back
+        builtin("sleep", HeadPose { z: -0.03, pitch: 0.5, ..Default::default() }, Some([-3.05, 3.05])),
+    ]
+}
+
+/// Reject poses the daemon would clamp rather than silently reaching another pose
+fn check_reachable(preset: &PosePreset) -> Result<(), String> {
+    let pose = preset.head_pose;
+    let clamped = pose.clamped();
+    let axes = [
+        ("x", pose.x, clamped.x),
+        ("y", pose.y, clamped.y),
+        ("z", pose.z, clamped.z),
+        ("pitch", pose.pitch, clamped.pitch),
+        ("yaw", pose.yaw, clamped.yaw),
+        ("roll", pose.roll, clamped.roll),
+    ];
+    if let Some((axis, value, limit)) = axes.iter().find(|(_, value, clamped)| value != clamped) {
+        return Err(format!("Preset '{}' is out of reach: {} {} (limit {})", preset.name, axis, value, limit));
+    }
+    if let Some(body_yaw) = preset.body_yaw {
+        if body_yaw.abs() > BODY_YAW_LIMIT {
+            return Err(format!("Preset '{}' is out of reach: body yaw {}", preset.name, body_yaw));
+        }
+    }
+    Ok(())
+}
+
+/// Duration for a move from `from` to `to` at the planned speeds
+fn plan_duration(from: &HeadPose, to: &HeadPose, body_yaw: Option<(f64, f64)>) -> f64 {
+    let rotation = [to.pitch - from.pitch, to.yaw - from.yaw, to.roll - from.roll]
+        .into_iter()
+        .chain(body_yaw.map(|(from, to)| to - from))
+        .fold(0.0_f64, |max, delta| max.max(delta.abs()));
+    let translation = ((to.x - from.x).powi(2) + (to.y - from.y).powi(2) + (to.z - from.z).powi(2)).sqrt();
+    let duration = (rotation.to_degrees() / PLANNED_SPEED_DEG_S).max(translation / PLANNED_SPEED_M_S);
+    duration.clamp(MIN_DURATION_S, MAX_DURATION_S)
+}
+
+impl PresetsState {
+    pub fn new() -> Self {
+        Self {
+            presets: Mutex::new(Vec::new()),
+            path: Mutex::new(None),
+        }
+    }
+
+    /// Load user presets from the app data directory (none if missing or corrupted)
+    pub fn load(&self, app_data_dir: &Path) {
+        let path = app_data_dir.join(PRESETS_FILE);
+        let presets = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        *self.presets.lock().unwrap() = presets;
+        *self.path.lock().unwrap() = Some(path);
+    }
+
+    /// Built-in presets (unless overridden) followed by user presets
+    pub fn list(&self) -> Vec<PosePreset> {
+        let user = self.presets.lock().unwrap().clone();
+        let mut presets: Vec<PosePreset> = builtin_presets()
+            .into_iter()
+            .filter(|preset| !user.iter().any(|other| other.name == preset.name))
+            .collect();
+        presets.extend(user);
+        presets
+    }
+
+    pub fn get(&self, name: &str) -> Option<PosePreset> {
+        self.list().into_iter().find(|preset| preset.name == name)
+    }
+
+    /// Apply a change to the user presets and persist them
+    fn update(&self, change: impl FnOnce(&mut Vec<PosePreset>)) -> Result<(), String> {
+        let mut presets = self.presets.lock().unwrap();
+        change(&mut presets);
+
+        if let Some(path) = self.path.lock().unwrap().as_ref() {
+            let content = serde_json::to_string_pretty(&*presets)
+                .map_err(|e| format!("Failed to serialize presets: {}", e))?;
+            std::fs::write(path, content).map_err(|e| format!("Failed to write presets: {}", e))?;
+        }
+        Ok(())
+    }
+}
+
+impl Default for PresetsState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+#[tauri::command]
+pub fn list_presets(state: State<PresetsState>) -> Vec<PosePreset> {
+    state.list()
+}
+
+/// Save a user preset (replaces the one with the same name)
+#[tauri::command]
+pub fn save_preset(state: State<PresetsState>, preset: PosePreset) -> Result<Vec<PosePreset>, String> {
+    let name = preset.name.trim().to_string();
+    if name.is_empty() {
+        return Err("Preset name cannot be empty".to_string());
+    }
+    let preset = PosePreset { name, builtin: false, ..preset };
+    check_reachable(&preset)?;
+
+    state.update(|presets| {
+        presets.retain(|other| other.name != preset.name);
+        presets.push(preset);
+    })?;
+    Ok(state.list())
+}
+
+/// Delete a user preset (a built-in one it replaced comes back)
+#[tauri::command]
+pub fn delete_preset(state: State<PresetsState>, name: String) -> Result<Vec<PosePreset>, String> {
+    if !state.presets.lock().unwrap().iter().any(|preset| preset.name == name) {
+        return Err(format!("No user preset named '{}'", name));
+    }
+    state.update(|presets| presets.retain(|preset| preset.name != name))?;
+    Ok(state.list())
+}
+
+/// Move to a preset over `duration` seconds (planned from the current pose if None)
+#[tauri::command]
+pub async fn go_to_preset(
+    state: State<'_, PresetsState>,
+    proxy: State<'_, Arc<LocalProxyState>>,
+    name: String,
+    duration: Option<f64>,
+) -> Result<PresetMove, String> {
+    let preset = state.get(&name).ok_or_else(|| format!("Unknown preset: {}", name))?;
+    check_reachable(&preset)?;
+    let client = DaemonClient::for_proxy(&proxy).await;
+
+    let duration = match duration {
+        Some(duration) if duration > 0.0 && duration <= MAX_DURATION_S => duration,
+        Some(duration) => return Err(format!("Duration must be between 0 and {}s: {}", MAX_DURATION_S, duration)),
+        None => {
+            // Planned from neutral when the daemon doesn't report the current pose
+            let current = client.robot_state().await.ok();
+            let from = current.as_ref().and_then(|state| state.head_pose).unwrap_or_default();
+            let body_yaw = preset
+                .body_yaw
+                .map(|to| (current.as_ref().and_then(|state| state.body_yaw).unwrap_or(0.0), to));
+            plan_duration(&from, &preset.head_pose, body_yaw)
+        }
+    };
+
+    let request = GotoRequest {
+        head_pose: Some(preset.head_pose),
+        antennas: preset.antennas,
+        body_yaw: preset.body_yaw,
+        duration,
+        interpolation: INTERPOLATION.to_string(),
+    };
+    let uuid = client.goto(&request).await?;
+    println!("[presets] 🎯 Going to '{}' in {:.1}s", preset.name, duration);
+    Ok(PresetMove { uuid, duration })
+}
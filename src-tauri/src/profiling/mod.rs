@@ -0,0 +1,239 @@
+/// Startup profiling
+///
+/// Records a span for each phase of the first launch, in milliseconds since the process
+/// started, so a slow first launch can be pinned on one phase:
+/// - `app_init`: process start → end of the Tauri setup
+/// - `load_app_data`: settings and persisted state read during setup
+/// - `signing`: macOS re-signing of the Python binaries
+/// - `daemon_spawn`: daemon start requested → Python process spawned by uv-trampoline
+///   (includes the venv setup below)
+/// - `venv_setup`, `venv_copy <label>`, `pyvenv_patch`: uv-trampoline's local venv setup
+///   (Windows / Linux first launch), from its `--json` events
+/// - `daemon_boot`: Python process spawned → first healthy status (`ready_ms`)
+///
+/// Only the first occurrence of each phase is kept. With `REACHY_MINI_PROFILE=1` in the
+/// environment, spans are also printed as they end and written once the daemon is ready
+/// to `<app data>/startup-profile.json`, in the Chrome trace format (chrome://tracing,
+/// Perfetto).
+
+use serde::Serialize;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+use std::time::Instant;
+
+const TRACING_ENV_VAR: &str = "REACHY_MINI_PROFILE";
+const TRACE_FILE: &str = "startup-profile.json";
+
+static START: OnceLock<Instant> = OnceLock::new();
+static PROFILE: Mutex<Profile> = Mutex::new(Profile {
+    spans: Vec::new(),
+    ready_ms: None,
+    trace_dir: None,
+});
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+#[derive(Debug, Serialize, Clone)]
+pub struct Span {
+    pub name: String,
+    /// Millis since process start
+    pub start_ms: u64,
+    /// None while the phase is running
+    pub end_ms: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct StartupProfile {
+    pub spans: Vec<Span>,
+    /// Millis from process start to the first healthy daemon status
+    pub ready_ms: Option<u64>,
+    /// `REACHY_MINI_PROFILE` is set (spans printed, trace file written)
+    pub tracing: bool,
+    pub trace_file: Option<String>,
+}
+
+struct Profile {
+    spans: Vec<Span>,
+    ready_ms: Option<u64>,
+    /// `<app data>` (set in setup)
+    trace_dir: Option<PathBuf>,
+}
+
+/// Ends its span when dropped
+pub struct SpanGuard(&'static str);
+
+impl Drop for SpanGuard {
+    fn drop(&mut self) {
+        end(self.0);
+    }
+}
+
+// ============================================================================
+// HELPER FUNCTIONS
+// ============================================================================
+
+fn elapsed_ms() -> u64 {
+    START.get_or_init(Instant::now).elapsed().as_millis() as u64
+}
+
+fn tracing_enabled() -> bool {
+    std::env::var(TRACING_ENV_VAR).is_ok_and(|value| !value.is_empty() && value != "0")
+}
+
+/// Chrome trace events ("complete" events, microseconds)
+fn chrome_trace(profile: &Profile) -> serde_json::Value {
+    let mut events: Vec<serde_json::Value> = profile
+        .spans
+        .iter()
+        .filter_map(|span| {
+            let end = span.end_ms?;
+            Some(serde_json::json!({
+                "name": span.name,
+                "ph": "X",
+                "ts": span.start_ms * 1000,
+                "dur": end.saturating_sub(span.start_ms) * 1000,
+                "pid": 1,
+                "tid": 1,
+            }))
+        })
+        .collect();
+    if let Some(ready) = profile.ready_ms {
+        events.push(serde_json::json!({ "name": "daemon_ready", "ph": "i", "s": "g", "ts": ready * 1000, "pid": 1, "tid": 1 }));
+    }
+    serde_json::json!({ "traceEvents": events, "displayTimeUnit": "ms" })
+}
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// Start the clock and the `app_init` span (call first thing in `run`)
+pub fn init() {
+    begin("app_init");
+}
+
+/// Where the trace file goes (call in setup)
+pub fn set_trace_dir(dir: PathBuf) {
+    PROFILE.lock().unwrap().trace_dir = Some(dir);
+}
+
+/// Start a phase (ignored if it already ran)
+pub fn begin(name: &str) {
+    let start_ms = elapsed_ms();
+    let mut profile = PROFILE.lock().unwrap();
+    if profile.spans.iter().any(|span| span.name == name) {
+        return;
+    }
+    profile.spans.push(Span {
+        name: name.to_string(),
+        start_ms,
+        end_ms: None,
+    });
+}
+
+/// End a running phase (ignored if not running)
+pub fn end(name: &str) {
+    let end_ms = elapsed_ms();
+    let mut profile = PROFILE.lock().unwrap();
+    let Some(span) = profile.spans.iter_mut().find(|span| span.name == name && span.end_ms.is_none()) else {
+        return;
+    };
+    span.end_ms = Some(end_ms);
+    if tracing_enabled() {
+        println!("[profile] ⏱️ {}: {} ms", name, end_ms.saturating_sub(span.start_ms));
+    }
+}
+
+/// Phase running until the guard is dropped
+pub fn span(name: &'static str) -> SpanGuard {
+    begin(name);
+    SpanGuard(name)
+}
+
+/// Phase that started when the last one ended (for steps only reported once done)
+fn record_since_last_end(name: &str) {
+    let end_ms = elapsed_ms();
+    let mut profile = PROFILE.lock().unwrap();
+    if profile.spans.iter().any(|span| span.name == name) {
+        return;
+    }
+    let start_ms = profile.spans.iter().filter_map(|span| span.end_ms).max().unwrap_or(0);
+    profile.spans.push(Span {
+        name: name.to_string(),
+        start_ms,
+        end_ms: Some(end_ms),
+    });
+    if tracing_enabled() {
+        println!("[profile] ⏱️ {}: {} ms", name, end_ms.saturating_sub(start_ms));
+    }
+}
+
+/// Spans from uv-trampoline's venv setup and spawn events
+pub fn on_trampoline_event(event: &serde_json::Value) {
+    let label = event["label"].as_str().unwrap_or_default();
+    match event["event"].as_str().unwrap_or_default() {
+        "setup_started" => begin("venv_setup"),
+        "copy_progress" => begin(&format!("venv_copy {}", label)),
+        "copy_finished" => {
+            // A copy resumed with nothing left to do sends no progress
+            begin(&format!("venv_copy {}", label));
+            end(&format!("venv_copy {}", label));
+        }
+        "pyvenv_patched" => record_since_last_end("pyvenv_patch"),
+        "setup_complete" | "setup_failed" => end("venv_setup"),
+        "spawned" => {
+            end("daemon_spawn");
+            begin("daemon_boot");
+        }
+        "spawn_failed" => end("daemon_spawn"),
+        _ => {}
+    }
+}
+
+/// The daemon answered its status endpoint for the first time
+pub fn mark_ready() {
+    end("daemon_boot");
+    let mut profile = PROFILE.lock().unwrap();
+    if profile.ready_ms.is_some() {
+        return;
+    }
+    let ready_ms = elapsed_ms();
+    profile.ready_ms = Some(ready_ms);
+    if !tracing_enabled() {
+        return;
+    }
+    println!("[profile] ✅ Daemon ready {} ms after launch", ready_ms);
+    if let Some(dir) = &profile.trace_dir {
+        let path = dir.join(TRACE_FILE);
+        match serde_json::to_string_pretty(&chrome_trace(&profile)) {
+            Ok(content) => match std::fs::write(&path, content) {
+                Ok(()) => println!("[profile] 📝 Startup trace written to {:?}", path),
+                Err(e) => eprintln!("[profile] ⚠️ Failed to write {:?}: {}", path, e),
+            },
+            Err(e) => eprintln!("[profile] ⚠️ Failed to serialize the startup trace: {}", e),
+        }
+    }
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Phases of this launch so far
+#[tauri::command]
+pub fn get_startup_profile() -> StartupProfile {
+    let profile = PROFILE.lock().unwrap();
+    let tracing = tracing_enabled();
+    StartupProfile {
+        spans: profile.spans.clone(),
+        ready_ms: profile.ready_ms,
+        tracing,
+        trace_file: profile
+            .trace_dir
+            .as_ref()
+            .filter(|_| tracing)
+            .map(|dir| dir.join(TRACE_FILE).to_string_lossy().to_string()),
+    }
+}
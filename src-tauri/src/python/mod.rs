@@ -1,7 +1,8 @@
 // Helper to build daemon arguments
 // IMPORTANT: Use .venv/bin/python3 directly instead of "uv run python" to ensure
 // we use the venv Python with all installed packages, not the cpython bundle
-pub fn build_daemon_args(sim_mode: bool) -> Result<Vec<String>, String> {
+// `config_args` come from the daemon configuration (see daemon::config)
+pub fn build_daemon_args(sim_mode: bool, serial_port: Option<&str>, log_level: &str, config_args: &[String]) -> Result<Vec<String>, String> {
     // Use Python from .venv directly (not via uv run)
     // This ensures we use the venv with all installed packages
     #[cfg(target_os = "windows")]
@@ -28,12 +29,18 @@ pub fn build_daemon_args(sim_mode: bool) -> Result<Vec<String>, String> {
     
     // Common daemon arguments
     args.push("--desktop-app-daemon".to_string());
-    args.push("--no-wake-up-on-start".to_string()); // Robot starts sleeping, toggle controls wake
-    args.push("--preload-datasets".to_string());    // Pre-download emotions/dances at startup
+    args.push("--log-level".to_string());
+    args.push(log_level.to_string());
+    // Wake-up on start, dataset preloading, kinematics... (defaults: robot starts sleeping, datasets preloaded)
+    args.extend(config_args.iter().cloned());
     
     if sim_mode {
         // Use --mockup-sim for mockup simulation (no MuJoCo required)
         args.push("--mockup-sim".to_string());
+    } else if let Some(port) = serial_port {
+        // Pin the robot when several are plugged in (default: auto-detect)
+        args.push("--serialport".to_string());
+        args.push(port.to_string());
     }
     
     Ok(args)
@@ -0,0 +1,185 @@
+/// Robot hostname and display name
+///
+/// Every wireless Reachy Mini ships as `reachy-mini.local`, so a classroom of them can't
+/// be told apart. The hostname and display name are read and set through the daemon's
+/// system API; the robot then re-announces itself over mDNS under `<hostname>.local`.
+///
+/// After a rename, everything saved for `<old>.local` follows: remote profiles and
+/// configured robots point to the new host, and the proxy is retargeted when it was
+/// connected through the old name. Robots reached by IP keep their host.
+///
+/// Only in WiFi mode: a USB robot's daemon runs on this computer, whose hostname is not
+/// the robot's to change.
+
+use serde::Serialize;
+use std::sync::Arc;
+use tauri::{AppHandle, Manager};
+
+use super::{RobotKind, RobotsState};
+use crate::connection::{ConnectionManager, ConnectionState};
+use crate::daemon_api::{DaemonClient, RobotHostname};
+use crate::local_proxy::{self, LocalProxyState};
+
+const MDNS_SUFFIX: &str = ".local";
+const MAX_DISPLAY_NAME_CHARS: usize = 64;
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+#[derive(Debug, Serialize, Clone)]
+pub struct HostnameChange {
+    pub identity: RobotHostname,
+    /// mDNS host the robot now answers on
+    pub host: String,
+    pub profiles_updated: usize,
+    pub robots_updated: usize,
+    /// The proxy was switched to the new host
+    pub retargeted: bool,
+}
+
+// ============================================================================
+// HELPER FUNCTIONS
+// ============================================================================
+
+/// Lowercase hostname label (RFC 1123), ".local" suffix accepted
+fn normalize_hostname(hostname: &str) -> Result<String, String> {
+    let hostname = hostname.trim().to_lowercase();
+    let hostname = hostname.strip_suffix(MDNS_SUFFIX).unwrap_or(&hostname).to_string();
+    let valid = (1..=63).contains(&hostname.len())
+        && hostname.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        && !hostname.starts_with('-')
+        && !hostname.ends_with('-');
+    if valid {
+        Ok(hostname)
+    } else {
+        Err(format!(
+            "Invalid hostname (1-63 letters, digits and hyphens, not starting or ending with a hyphen): {}",
+            hostname
+        ))
+    }
+}
+
+/// Client of the robot reached over WiFi, with its current host
+async fn wifi_client(app_handle: &AppHandle) -> Result<(DaemonClient, String), String> {
+    let ConnectionState::WifiMode { host, .. } = app_handle.state::<ConnectionManager>().get() else {
+        return Err("Connect to a wireless robot over WiFi to change its name".to_string());
+    };
+    let proxy = app_handle.state::<Arc<LocalProxyState>>();
+    Ok((DaemonClient::for_proxy(&proxy).await, host))
+}
+
+fn system_error(error: String) -> String {
+    if error.contains(" 404 ") {
+        "This robot's daemon can't change its name: update the daemon".to_string()
+    } else {
+        error
+    }
+}
+
+/// Point saved profiles and configured robots at `new_host`; returns how many changed
+fn update_saved_hosts(app_handle: &AppHandle, old_host: &str, new_host: &str, display_name: Option<&str>) -> (usize, usize) {
+    let proxy = app_handle.state::<Arc<LocalProxyState>>();
+    let profiles = proxy.profiles.update(|profiles| {
+        let mut count = 0;
+        for profile in profiles.iter_mut().filter(|profile| profile.host.eq_ignore_ascii_case(old_host)) {
+            profile.host = new_host.to_string();
+            if let Some(name) = display_name {
+                profile.name = name.to_string();
+            }
+            count += 1;
+        }
+        Ok(count)
+    });
+
+    let robots = app_handle.state::<RobotsState>().update(|store| {
+        let mut count = 0;
+        for robot in store.robots.iter_mut() {
+            let RobotKind::Wifi { host } = &mut robot.kind else {
+                continue;
+            };
+            if !host.eq_ignore_ascii_case(old_host) {
+                continue;
+            }
+            *host = new_host.to_string();
+            let old_id = std::mem::replace(&mut robot.id, robot.kind.id());
+            if store.active.as_deref() == Some(old_id.as_str()) {
+                store.active = Some(robot.id.clone());
+            }
+            if let Some(name) = display_name {
+                robot.name = name.to_string();
+            }
+            count += 1;
+        }
+        Ok(count)
+    });
+
+    let saved = |result: Result<usize, String>| {
+        result.unwrap_or_else(|e| {
+            eprintln!("[robots] ⚠️ {}", e);
+            0
+        })
+    };
+    (saved(profiles), saved(robots))
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Hostname and display name of the robot connected over WiFi
+#[tauri::command]
+pub async fn get_robot_hostname(app_handle: AppHandle) -> Result<RobotHostname, String> {
+    let (client, _) = wifi_client(&app_handle).await?;
+    client.robot_hostname().await.map_err(system_error)
+}
+
+/// Rename the robot connected over WiFi (display_name None = unchanged)
+#[tauri::command]
+pub async fn set_robot_hostname(
+    app_handle: AppHandle,
+    hostname: String,
+    display_name: Option<String>,
+) -> Result<HostnameChange, String> {
+    crate::lock::ensure_unlocked("Renaming the robot")?;
+    let hostname = normalize_hostname(&hostname)?;
+    let display_name = display_name.map(|name| name.trim().to_string()).filter(|name| !name.is_empty());
+    if display_name.as_ref().is_some_and(|name| name.chars().count() > MAX_DISPLAY_NAME_CHARS) {
+        return Err(format!("Display name too long (at most {} characters)", MAX_DISPLAY_NAME_CHARS));
+    }
+    let (client, current_host) = wifi_client(&app_handle).await?;
+    let previous = client.robot_hostname().await.map_err(system_error)?;
+
+    println!("[robots] 🏷️ Renaming robot {} to {}", previous.hostname, hostname);
+    let identity = client
+        .set_robot_hostname(&RobotHostname {
+            hostname: hostname.clone(),
+            display_name: display_name.or(previous.display_name),
+        })
+        .await
+        .map_err(system_error)?;
+    if let Err(e) = client.refresh_mdns().await {
+        // Older daemons re-announce on their own when the hostname changes
+        eprintln!("[robots] ⚠️ mDNS refresh: {}", e);
+    }
+
+    let old_host = format!("{}{}", previous.hostname, MDNS_SUFFIX);
+    let new_host = format!("{}{}", identity.hostname, MDNS_SUFFIX);
+    let (profiles_updated, robots_updated) =
+        update_saved_hosts(&app_handle, &old_host, &new_host, identity.display_name.as_deref());
+
+    // Connected through the name that just went away: follow the robot
+    let retargeted = current_host.eq_ignore_ascii_case(&old_host) && old_host != new_host;
+    if retargeted {
+        let proxy = app_handle.state::<Arc<LocalProxyState>>();
+        local_proxy::set_target_host(&proxy, new_host.clone()).await;
+    }
+
+    Ok(HostnameChange {
+        identity,
+        host: new_host,
+        profiles_updated,
+        robots_updated,
+        retargeted,
+    })
+}
@@ -0,0 +1,305 @@
+/// Configured robots and session switching
+///
+/// Labs with several Reachy Minis configure them all here (USB by serial number, WiFi
+/// by host, simulation) in `<app data>/robots.json`, then switch the UI from one to the
+/// other. Each robot keeps its own session (the app it was running); switching saves the
+/// current one, tears down its connection (local daemon or WiFi proxy target), brings up
+/// the other robot and restores its session.
+///
+/// One robot is connected at a time: the local daemon and the WiFi proxy both serve on
+/// the fixed daemon ports (8000 / 8042).
+///
+/// Emits `robot-switch` with { step, message, robot_id } at each step.
+///
+/// Wireless robots can be renamed (hostname and display name, see hostname).
+
+pub mod hostname;
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::daemon::DaemonState;
+use crate::daemon_api::DaemonClient;
+use crate::local_proxy::LocalProxyState;
+
+const ROBOTS_FILE: &str = "robots.json";
+const HEALTHY_TIMEOUT: Duration = Duration::from_secs(60);
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RobotKind {
+    Usb { serial_number: String },
+    Wifi { host: String },
+    Simulation,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RobotProfile {
+    /// Derived from the kind (usb:<serial>, wifi:<host>, simulation)
+    pub id: String,
+    pub name: String,
+    #[serde(flatten)]
+    pub kind: RobotKind,
+    /// App running when the user last switched away, restarted when switching back
+    pub last_app: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct RobotsOverview {
+    pub robots: Vec<RobotProfile>,
+    /// Robot the UI currently drives
+    pub active: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+struct SwitchProgress {
+    step: &'static str,
+    message: String,
+    robot_id: String,
+}
+
+pub struct RobotsState {
+    store: Mutex<RobotsOverview>,
+    path: Mutex<Option<PathBuf>>,
+}
+
+// ============================================================================
+// HELPER FUNCTIONS
+// ============================================================================
+
+impl RobotKind {
+    fn id(&self) -> String {
+        match self {
+            RobotKind::Usb { serial_number } => format!("usb:{}", serial_number),
+            RobotKind::Wifi { host } => format!("wifi:{}", host),
+            RobotKind::Simulation => "simulation".to_string(),
+        }
+    }
+}
+
+impl RobotsState {
+    pub fn new() -> Self {
+        Self {
+            store: Mutex::new(RobotsOverview::default()),
+            path: Mutex::new(None),
+        }
+    }
+
+    /// Load configured robots from the app data directory (empty if missing or corrupted)
+    pub fn load(&self, app_data_dir: &Path) {
+        let path = app_data_dir.join(ROBOTS_FILE);
+        let store = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        *self.store.lock().unwrap() = store;
+        *self.path.lock().unwrap() = Some(path);
+    }
+
+    pub fn get(&self) -> RobotsOverview {
+        self.store.lock().unwrap().clone()
+    }
+
+    fn find(&self, id: &str) -> Option<RobotProfile> {
+        self.store.lock().unwrap().robots.iter().find(|robot| robot.id == id).cloned()
+    }
+
+    /// Apply a change and persist it
+    fn update<T>(&self, change: impl FnOnce(&mut RobotsOverview) -> Result<T, String>) -> Result<T, String> {
+        let mut store = self.store.lock().unwrap();
+        let result = change(&mut store)?;
+
+        if let Some(path) = self.path.lock().unwrap().as_ref() {
+            let content = serde_json::to_string_pretty(&*store)
+                .map_err(|e| format!("Failed to serialize robots: {}", e))?;
+            std::fs::write(path, content)
+                .map_err(|e| format!("Failed to write robots: {}", e))?;
+        }
+
+        Ok(result)
+    }
+}
+
+impl Default for RobotsState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn emit_progress(app_handle: &AppHandle, step: &'static str, message: String, robot_id: &str) {
+    println!("[robots] {}", message);
+    let _ = app_handle.emit("robot-switch", SwitchProgress { step, message, robot_id: robot_id.to_string() });
+}
+
+/// Stop whatever connection is up (local daemon, WiFi proxy target)
+async fn disconnect(app_handle: &AppHandle) -> Result<(), String> {
+    let proxy = app_handle.state::<Arc<LocalProxyState>>();
+    if proxy.target_host.read().await.is_some() {
+        crate::local_proxy::clear_target_host(&proxy).await;
+    }
+
+    if app_handle.state::<DaemonState>().process.lock().unwrap().is_some() {
+        let handle = app_handle.clone();
+        tauri::async_runtime::spawn_blocking(move || {
+            crate::daemon::kill_daemon(&handle.state::<DaemonState>());
+        })
+        .await
+        .map_err(|e| format!("Failed to stop daemon: {}", e))?;
+        crate::daemon::mode_switch::wait_for_port_release().await;
+    }
+    Ok(())
+}
+
+/// Bring up the connection to a robot
+async fn connect(app_handle: &AppHandle, robot: &RobotProfile) -> Result<(), String> {
+    let (sim_mode, port) = match &robot.kind {
+        RobotKind::Wifi { host } => {
+            let proxy = app_handle.state::<Arc<LocalProxyState>>();
+            crate::local_proxy::set_target_host(&proxy, host.clone()).await;
+            return Ok(());
+        }
+        RobotKind::Simulation => (true, None),
+        RobotKind::Usb { serial_number } => {
+            let port = crate::usb::get_reachy_robots()
+                .into_iter()
+                .find(|usb| usb.serial_number.as_deref() == Some(serial_number))
+                .map(|usb| usb.port_name)
+                .ok_or_else(|| format!("{} is not plugged in", robot.name))?;
+            (false, Some(port))
+        }
+    };
+
+    let handle = app_handle.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::start_daemon(handle.clone(), handle.state::<DaemonState>(), Some(sim_mode), None, port)
+    })
+    .await
+    .map_err(|e| format!("Failed to start daemon: {}", e))??;
+    Ok(())
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+#[tauri::command]
+pub fn list_robots(state: State<RobotsState>) -> RobotsOverview {
+    state.get()
+}
+
+#[tauri::command]
+pub fn add_robot(state: State<RobotsState>, name: String, kind: RobotKind) -> Result<RobotProfile, String> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("Robot name cannot be empty".to_string());
+    }
+    let robot = RobotProfile {
+        id: kind.id(),
+        name,
+        kind,
+        last_app: None,
+    };
+    state.update(|store| {
+        if store.robots.iter().any(|existing| existing.id == robot.id) {
+            return Err(format!("Robot {} is already configured", robot.id));
+        }
+        store.robots.push(robot.clone());
+        Ok(robot)
+    })
+}
+
+#[tauri::command]
+pub fn rename_robot(state: State<RobotsState>, id: String, name: String) -> Result<(), String> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("Robot name cannot be empty".to_string());
+    }
+    state.update(|store| {
+        let robot = store
+            .robots
+            .iter_mut()
+            .find(|robot| robot.id == id)
+            .ok_or_else(|| format!("Unknown robot: {}", id))?;
+        robot.name = name;
+        Ok(())
+    })
+}
+
+#[tauri::command]
+pub fn remove_robot(state: State<RobotsState>, id: String) -> Result<(), String> {
+    state.update(|store| {
+        if store.active.as_deref() == Some(id.as_str()) {
+            return Err("Switch to another robot before removing this one".to_string());
+        }
+        store.robots.retain(|robot| robot.id != id);
+        Ok(())
+    })
+}
+
+/// Save the active robot's session, connect to another one and restore its session
+#[tauri::command]
+pub async fn switch_robot(app_handle: AppHandle, id: String) -> Result<RobotsOverview, String> {
+    let state = app_handle.state::<RobotsState>();
+    let robot = state.find(&id).ok_or_else(|| format!("Unknown robot: {}", id))?;
+    let previous = state.get().active;
+    if previous.as_deref() == Some(id.as_str()) {
+        return Ok(state.get());
+    }
+    let proxy = app_handle.state::<Arc<LocalProxyState>>();
+    let client = DaemonClient::for_proxy(&proxy).await;
+
+    // 1. Remember what the current robot was running
+    if let Some(previous) = previous {
+        emit_progress(&app_handle, "saving_session", "Saving current session...".to_string(), &id);
+        let running_app = client.running_app().await;
+        state.update(|store| {
+            if let Some(robot) = store.robots.iter_mut().find(|robot| robot.id == previous) {
+                robot.last_app = running_app;
+            }
+            Ok(())
+        })?;
+    }
+
+    // 2. Disconnect, then connect to the other robot
+    emit_progress(&app_handle, "disconnecting", "Disconnecting current robot...".to_string(), &id);
+    disconnect(&app_handle).await?;
+    state.update(|store| {
+        store.active = None;
+        Ok(())
+    })?;
+
+    emit_progress(&app_handle, "connecting", format!("Connecting to {}...", robot.name), &id);
+    if let Err(e) = connect(&app_handle, &robot).await {
+        emit_progress(&app_handle, "error", e.clone(), &id);
+        return Err(e);
+    }
+
+    emit_progress(&app_handle, "waiting_healthy", format!("Waiting for {} to be ready...", robot.name), &id);
+    if !client.wait_healthy(HEALTHY_TIMEOUT).await {
+        let message = format!("{} did not become ready", robot.name);
+        emit_progress(&app_handle, "error", message.clone(), &id);
+        return Err(message);
+    }
+
+    // 3. Restore its session
+    if let Some(app) = &robot.last_app {
+        emit_progress(&app_handle, "restoring_session", format!("Restarting app {}...", app), &id);
+        if let Err(e) = client.start_app(app).await {
+            eprintln!("[robots] ⚠️ Failed to restart app {}: {}", app, e);
+        }
+    }
+
+    state.update(|store| {
+        store.active = Some(id.clone());
+        Ok(())
+    })?;
+    emit_progress(&app_handle, "done", format!("Now driving {}", robot.name), &id);
+    Ok(state.get())
+}
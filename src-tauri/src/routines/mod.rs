@@ -0,0 +1,588 @@
+/// Scheduler for recurring robot routines
+///
+/// Routines are actions run at set local times ("start the daemon at 9:00 on weekdays",
+/// "play wave every 60 minutes between 10:00 and 17:00", "park at 18:00"), saved in
+/// `<app data>/routines.json` and run by a background task while the app is open.
+///
+/// Rules:
+/// - catch-up: an occurrence missed while the app was closed or the computer asleep runs
+///   once if it is at most `catch_up_minutes` old (None = missed runs are skipped). Only
+///   the latest missed occurrence counts, never a backlog.
+/// - conflicts: routines run one at a time, in order. A routine moving the robot while an
+///   app drives it is skipped, or stops the app first (`on_conflict`).
+///
+/// Each run (done, skipped, missed or failed) is emitted as `routine-run` (RoutineRun)
+/// and kept in the history.
+
+use chrono::{Datelike, Local, NaiveDate, NaiveTime, TimeZone, Timelike};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::daemon::history::now_millis;
+use crate::daemon::DaemonState;
+use crate::daemon_api::DaemonClient;
+use crate::local_proxy::LocalProxyState;
+
+const ROUTINES_FILE: &str = "routines.json";
+const TICK_INTERVAL: Duration = Duration::from_secs(15);
+/// An occurrence found this late by a tick is still on time (ticks, a previous routine)
+const GRACE_MS: u64 = 2 * 60 * 1000;
+/// How far occurrences are looked for (a weekly routine fits)
+const SEARCH_DAYS: usize = 8;
+const MIN_INTERVAL_MINUTES: u32 = 5;
+const MAX_HISTORY: usize = 100;
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RoutineAction {
+    StartDaemon {
+        #[serde(default)]
+        sim_mode: Option<bool>,
+    },
+    StopDaemon,
+    /// Recorded move, e.g. dataset "pollen-robotics/reachy-mini-emotions-library", name "wave"
+    PlayMotion { dataset: String, name: String },
+    RunApp { name: String },
+    StopApp,
+    /// Head to its rest pose
+    Park,
+}
+
+/// Times are local "HH:MM"
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Recurrence {
+    /// Once a day at `time`; `weekdays` 1 (Monday) to 7, empty = every day
+    Daily {
+        time: String,
+        #[serde(default)]
+        weekdays: Vec<u8>,
+    },
+    /// Every `minutes` from `from` (default 00:00) to `to` (default end of day)
+    Interval {
+        minutes: u32,
+        #[serde(default)]
+        from: Option<String>,
+        #[serde(default)]
+        to: Option<String>,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictPolicy {
+    /// An app is driving the robot: don't run
+    #[default]
+    Skip,
+    /// Stop the running app, then run
+    StopApp,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Routine {
+    pub id: String,
+    pub name: String,
+    pub enabled: bool,
+    pub action: RoutineAction,
+    pub recurrence: Recurrence,
+    #[serde(default)]
+    pub catch_up_minutes: Option<u32>,
+    #[serde(default)]
+    pub on_conflict: ConflictPolicy,
+    /// Latest occurrence handled (run, skipped or missed), unix millis
+    #[serde(default)]
+    pub last_fired_at: Option<u64>,
+    #[serde(default)]
+    pub last_run: Option<RoutineRun>,
+}
+
+/// A routine as created or edited in the UI (id None = new)
+#[derive(Debug, Deserialize, Clone)]
+pub struct RoutineDraft {
+    #[serde(default)]
+    pub id: Option<String>,
+    pub name: String,
+    #[serde(default = "enabled_by_default")]
+    pub enabled: bool,
+    pub action: RoutineAction,
+    pub recurrence: Recurrence,
+    #[serde(default)]
+    pub catch_up_minutes: Option<u32>,
+    #[serde(default)]
+    pub on_conflict: ConflictPolicy,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct RoutineInfo {
+    #[serde(flatten)]
+    pub routine: Routine,
+    /// Unix millis, None when disabled
+    pub next_run_at: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum RunTrigger {
+    Scheduled,
+    CatchUp,
+    Manual,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum RunOutcome {
+    Done,
+    Skipped,
+    /// Too late to catch up
+    Missed,
+    Failed,
+}
+
+/// Payload of the `routine-run` event
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RoutineRun {
+    pub routine_id: String,
+    pub routine_name: String,
+    pub trigger: RunTrigger,
+    /// Occurrence it was run for (None when run manually)
+    pub scheduled_at: Option<u64>,
+    pub started_at: u64,
+    pub outcome: RunOutcome,
+    pub message: String,
+}
+
+/// (routine, occurrence, trigger) to run, and (routine, occurrence) found too late to catch up
+type DueRoutines = (Vec<(Routine, u64, RunTrigger)>, Vec<(Routine, u64)>);
+
+pub struct RoutinesState {
+    routines: Mutex<Vec<Routine>>,
+    history: Mutex<VecDeque<RoutineRun>>,
+    path: Mutex<Option<PathBuf>>,
+    /// Held while a routine runs (one at a time)
+    executing: tokio::sync::Mutex<()>,
+}
+
+// ============================================================================
+// HELPER FUNCTIONS
+// ============================================================================
+
+fn enabled_by_default() -> bool {
+    true
+}
+
+fn parse_time(time: &str) -> Result<NaiveTime, String> {
+    NaiveTime::parse_from_str(time.trim(), "%H:%M").map_err(|_| format!("Invalid time (expected HH:MM): {}", time))
+}
+
+fn validate(draft: &RoutineDraft) -> Result<(), String> {
+    if draft.name.trim().is_empty() {
+        return Err("A routine needs a name".to_string());
+    }
+    match &draft.recurrence {
+        Recurrence::Daily { time, weekdays } => {
+            parse_time(time)?;
+            if let Some(day) = weekdays.iter().find(|day| !(1..=7).contains(*day)) {
+                return Err(format!("Invalid weekday (1 = Monday to 7 = Sunday): {}", day));
+            }
+        }
+        Recurrence::Interval { minutes, from, to } => {
+            if *minutes < MIN_INTERVAL_MINUTES {
+                return Err(format!("Interval too short (at least {} minutes)", MIN_INTERVAL_MINUTES));
+            }
+            let from = from.as_deref().map(parse_time).transpose()?;
+            let to = to.as_deref().map(parse_time).transpose()?;
+            if let (Some(from), Some(to)) = (from, to) {
+                if to < from {
+                    return Err("The interval window ends before it starts".to_string());
+                }
+            }
+        }
+    }
+    match &draft.action {
+        RoutineAction::PlayMotion { dataset, name } if dataset.trim().is_empty() || name.trim().is_empty() => {
+            Err("Choose the motion to play".to_string())
+        }
+        RoutineAction::RunApp { name } if name.trim().is_empty() => Err("Choose the app to run".to_string()),
+        _ => Ok(()),
+    }
+}
+
+fn local_date(millis: u64) -> Option<NaiveDate> {
+    Local.timestamp_millis_opt(millis as i64).single().map(|time| time.date_naive())
+}
+
+/// Occurrences of a routine on a local date (unix millis, ascending); times skipped by
+/// a DST change don't occur
+fn day_occurrences(recurrence: &Recurrence, date: NaiveDate) -> Vec<u64> {
+    let at = |time: NaiveTime| {
+        Local
+            .from_local_datetime(&date.and_time(time))
+            .earliest()
+            .map(|time| time.timestamp_millis() as u64)
+    };
+    match recurrence {
+        Recurrence::Daily { time, weekdays } => {
+            let weekday = date.weekday().number_from_monday() as u8;
+            if !weekdays.is_empty() && !weekdays.contains(&weekday) {
+                return Vec::new();
+            }
+            parse_time(time).ok().and_then(at).into_iter().collect()
+        }
+        Recurrence::Interval { minutes, from, to } => {
+            let bound = |time: &Option<String>| time.as_deref().and_then(|time| parse_time(time).ok());
+            let start = bound(from).map(|time| time.num_seconds_from_midnight()).unwrap_or(0);
+            let end = bound(to).map(|time| time.num_seconds_from_midnight()).unwrap_or(86_399);
+            let step = (*minutes).max(MIN_INTERVAL_MINUTES) as usize * 60;
+            (start..=end)
+                .step_by(step)
+                .filter_map(|seconds| NaiveTime::from_num_seconds_from_midnight_opt(seconds, 0))
+                .filter_map(at)
+                .collect()
+        }
+    }
+}
+
+/// Latest occurrence in (after, upto]
+fn latest_occurrence(recurrence: &Recurrence, after: u64, upto: u64) -> Option<u64> {
+    let mut date = local_date(upto)?;
+    for _ in 0..SEARCH_DAYS {
+        if let Some(time) = day_occurrences(recurrence, date).into_iter().rev().find(|time| *time <= upto) {
+            return (time > after).then_some(time);
+        }
+        date = date.pred_opt()?;
+    }
+    None
+}
+
+/// First occurrence after `after`
+fn next_occurrence(recurrence: &Recurrence, after: u64) -> Option<u64> {
+    let mut date = local_date(after)?;
+    for _ in 0..SEARCH_DAYS {
+        if let Some(time) = day_occurrences(recurrence, date).into_iter().find(|time| *time > after) {
+            return Some(time);
+        }
+        date = date.succ_opt()?;
+    }
+    None
+}
+
+impl RoutinesState {
+    pub fn new() -> Self {
+        Self {
+            routines: Mutex::new(Vec::new()),
+            history: Mutex::new(VecDeque::new()),
+            path: Mutex::new(None),
+            executing: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    /// Load routines from the app data directory (none if missing or corrupted)
+    pub fn load(&self, app_data_dir: &Path) {
+        let path = app_data_dir.join(ROUTINES_FILE);
+        let routines = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        *self.routines.lock().unwrap() = routines;
+        *self.path.lock().unwrap() = Some(path);
+    }
+
+    fn list(&self) -> Vec<RoutineInfo> {
+        let now = now_millis();
+        self.routines
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|routine| RoutineInfo {
+                next_run_at: routine
+                    .enabled
+                    .then(|| next_occurrence(&routine.recurrence, now))
+                    .flatten(),
+                routine: routine.clone(),
+            })
+            .collect()
+    }
+
+    fn find(&self, id: &str) -> Option<Routine> {
+        self.routines.lock().unwrap().iter().find(|routine| routine.id == id).cloned()
+    }
+
+    /// Apply a change and persist it
+    fn update<T>(&self, change: impl FnOnce(&mut Vec<Routine>) -> Result<T, String>) -> Result<T, String> {
+        let mut routines = self.routines.lock().unwrap();
+        let result = change(&mut routines)?;
+
+        if let Some(path) = self.path.lock().unwrap().as_ref() {
+            let content = serde_json::to_string_pretty(&*routines)
+                .map_err(|e| format!("Failed to serialize routines: {}", e))?;
+            std::fs::write(path, content).map_err(|e| format!("Failed to write routines: {}", e))?;
+        }
+
+        Ok(result)
+    }
+
+    /// Routines with an occurrence since the last tick, marked as handled
+    fn take_due(&self, now: u64) -> DueRoutines {
+        // Most ticks find nothing: don't rewrite the file for them
+        let any_due = self.routines.lock().unwrap().iter().any(|routine| {
+            routine.enabled && latest_occurrence(&routine.recurrence, routine.last_fired_at.unwrap_or(now), now).is_some()
+        });
+        if !any_due {
+            return (Vec::new(), Vec::new());
+        }
+        let taken = self.update(|routines| {
+            let (mut due, mut missed) = (Vec::new(), Vec::new());
+            for routine in routines.iter_mut().filter(|routine| routine.enabled) {
+                let since = routine.last_fired_at.unwrap_or(now);
+                let Some(at) = latest_occurrence(&routine.recurrence, since, now) else {
+                    continue;
+                };
+                routine.last_fired_at = Some(at);
+                let late = now - at;
+                if late <= GRACE_MS {
+                    due.push((routine.clone(), at, RunTrigger::Scheduled));
+                } else if routine.catch_up_minutes.is_some_and(|minutes| late <= minutes as u64 * 60_000) {
+                    due.push((routine.clone(), at, RunTrigger::CatchUp));
+                } else {
+                    missed.push((routine.clone(), at));
+                }
+            }
+            Ok((due, missed))
+        });
+        taken.unwrap_or_else(|e| {
+            eprintln!("[routines] ⚠️ {}", e);
+            (Vec::new(), Vec::new())
+        })
+    }
+
+    fn record(&self, run: &RoutineRun) {
+        let result = self.update(|routines| {
+            if let Some(routine) = routines.iter_mut().find(|routine| routine.id == run.routine_id) {
+                routine.last_run = Some(run.clone());
+            }
+            Ok(())
+        });
+        if let Err(e) = result {
+            eprintln!("[routines] ⚠️ {}", e);
+        }
+        let mut history = self.history.lock().unwrap();
+        history.push_back(run.clone());
+        if history.len() > MAX_HISTORY {
+            history.pop_front();
+        }
+    }
+}
+
+impl Default for RoutinesState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Run the action; Ok with the outcome (done or skipped) and what happened
+async fn perform(app_handle: &AppHandle, action: &RoutineAction, on_conflict: ConflictPolicy) -> Result<(RunOutcome, String), String> {
+    let proxy = app_handle.state::<Arc<LocalProxyState>>();
+    let client = DaemonClient::for_proxy(&proxy).await;
+
+    match action {
+        RoutineAction::StartDaemon { sim_mode } => {
+            if client.is_healthy().await {
+                return Ok((RunOutcome::Skipped, "The daemon is already running".to_string()));
+            }
+            let params = serde_json::json!({ "sim_mode": sim_mode });
+            let result = crate::automation::dispatch(app_handle, "start_daemon", &params).await?;
+            return Ok((RunOutcome::Done, result.as_str().unwrap_or("Daemon started").to_string()));
+        }
+        RoutineAction::StopDaemon => {
+            return crate::stop_daemon(app_handle.state::<DaemonState>()).map(|message| (RunOutcome::Done, message));
+        }
+        _ => {}
+    }
+
+    if !client.is_healthy().await {
+        return Err("The daemon is not running".to_string());
+    }
+    if *action == RoutineAction::StopApp {
+        crate::apps::stop_running_app(app_handle, &client).await?;
+        return Ok((RunOutcome::Done, "App stopped".to_string()));
+    }
+
+    // Moving the robot while an app drives it
+    if let Some(app) = client.running_app().await {
+        match on_conflict {
+            ConflictPolicy::Skip => return Ok((RunOutcome::Skipped, format!("App {} is running", app))),
+            ConflictPolicy::StopApp => crate::apps::stop_running_app(app_handle, &client).await?,
+        }
+    }
+
+    let message = match action {
+        RoutineAction::PlayMotion { dataset, name } => {
+            client.play_recorded_move(dataset, name).await?;
+            format!("Playing {}", name)
+        }
+        RoutineAction::RunApp { name } => {
+            crate::apps::launch_app(app_handle, &client, name).await?;
+            format!("App {} started", name)
+        }
+        RoutineAction::Park => {
+            client.goto_sleep().await?;
+            "Robot parked".to_string()
+        }
+        _ => unreachable!("handled above"),
+    };
+    Ok((RunOutcome::Done, message))
+}
+
+fn report(app_handle: &AppHandle, run: RoutineRun) -> RoutineRun {
+    println!("[routines] ⏰ {} ({:?}): {:?} - {}", run.routine_name, run.trigger, run.outcome, run.message);
+    app_handle.state::<RoutinesState>().record(&run);
+    let _ = app_handle.emit("routine-run", run.clone());
+    run
+}
+
+async fn execute(app_handle: &AppHandle, routine: &Routine, scheduled_at: Option<u64>, trigger: RunTrigger) -> RoutineRun {
+    let state = app_handle.state::<RoutinesState>();
+    let _turn = state.executing.lock().await;
+    let started_at = now_millis();
+    let (outcome, message) = perform(app_handle, &routine.action, routine.on_conflict)
+        .await
+        .unwrap_or_else(|e| (RunOutcome::Failed, e));
+    report(app_handle, RoutineRun {
+        routine_id: routine.id.clone(),
+        routine_name: routine.name.clone(),
+        trigger,
+        scheduled_at,
+        started_at,
+        outcome,
+        message,
+    })
+}
+
+async fn run(app_handle: AppHandle) {
+    loop {
+        let now = now_millis();
+        let (due, missed) = app_handle.state::<RoutinesState>().take_due(now);
+        for (routine, at) in missed {
+            report(&app_handle, RoutineRun {
+                routine_id: routine.id,
+                routine_name: routine.name,
+                trigger: RunTrigger::Scheduled,
+                scheduled_at: Some(at),
+                started_at: now,
+                outcome: RunOutcome::Missed,
+                message: "The app was not running at that time".to_string(),
+            });
+        }
+        for (routine, at, trigger) in due {
+            execute(&app_handle, &routine, Some(at), trigger).await;
+        }
+        tokio::time::sleep(TICK_INTERVAL).await;
+    }
+}
+
+// ============================================================================
+// SETUP
+// ============================================================================
+
+/// Start running routines (call once in setup, after `load`)
+pub fn start(app_handle: AppHandle) {
+    tauri::async_runtime::spawn(run(app_handle));
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Routines with their next run
+#[tauri::command]
+pub fn list_routines(state: State<RoutinesState>) -> Vec<RoutineInfo> {
+    state.list()
+}
+
+/// Create a routine, or replace the one with the draft's id
+///
+/// Occurrences before the save are not caught up.
+#[tauri::command]
+pub fn save_routine(state: State<RoutinesState>, routine: RoutineDraft) -> Result<Vec<RoutineInfo>, String> {
+    validate(&routine)?;
+    let now = now_millis();
+    state.update(|routines| {
+        let existing = routine
+            .id
+            .as_ref()
+            .map(|id| routines.iter().position(|saved| saved.id == *id).ok_or_else(|| format!("Unknown routine: {}", id)))
+            .transpose()?;
+        let saved = Routine {
+            id: routine.id.clone().unwrap_or_else(crate::local_proxy::profiles::generate_id),
+            name: routine.name.trim().to_string(),
+            enabled: routine.enabled,
+            action: routine.action,
+            recurrence: routine.recurrence,
+            catch_up_minutes: routine.catch_up_minutes,
+            on_conflict: routine.on_conflict,
+            last_fired_at: Some(now),
+            last_run: existing.and_then(|index| routines[index].last_run.clone()),
+        };
+        match existing {
+            Some(index) => routines[index] = saved,
+            None => routines.push(saved),
+        }
+        Ok(())
+    })?;
+    Ok(state.list())
+}
+
+#[tauri::command]
+pub fn delete_routine(state: State<RoutinesState>, id: String) -> Result<Vec<RoutineInfo>, String> {
+    state.update(|routines| {
+        let count = routines.len();
+        routines.retain(|routine| routine.id != id);
+        if routines.len() == count {
+            return Err(format!("Unknown routine: {}", id));
+        }
+        Ok(())
+    })?;
+    Ok(state.list())
+}
+
+/// Pause or resume a routine (occurrences while paused are not caught up)
+#[tauri::command]
+pub fn set_routine_enabled(state: State<RoutinesState>, id: String, enabled: bool) -> Result<Vec<RoutineInfo>, String> {
+    let now = now_millis();
+    state.update(|routines| {
+        let routine = routines
+            .iter_mut()
+            .find(|routine| routine.id == id)
+            .ok_or_else(|| format!("Unknown routine: {}", id))?;
+        if enabled && !routine.enabled {
+            routine.last_fired_at = Some(now);
+        }
+        routine.enabled = enabled;
+        Ok(())
+    })?;
+    Ok(state.list())
+}
+
+/// Run a routine now, outside its schedule
+#[tauri::command]
+pub async fn run_routine_now(app_handle: AppHandle, id: String) -> Result<RoutineRun, String> {
+    let routine = app_handle
+        .state::<RoutinesState>()
+        .find(&id)
+        .ok_or_else(|| format!("Unknown routine: {}", id))?;
+    Ok(execute(&app_handle, &routine, None, RunTrigger::Manual).await)
+}
+
+/// Latest runs, oldest first
+#[tauri::command]
+pub fn get_routine_history(state: State<RoutinesState>) -> Vec<RoutineRun> {
+    state.history.lock().unwrap().iter().cloned().collect()
+}
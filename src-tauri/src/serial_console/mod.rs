@@ -0,0 +1,256 @@
+/// Serial console module
+///
+/// Raw terminal on the robot's serial port for debugging, shown in its own window.
+/// The console and the daemon never hold the port together:
+/// - opening the console stops a running daemon (restarted when the console closes)
+/// - starting the daemon closes the console first (see release_for_daemon)
+///
+/// Emits `serial-console-data` with { data } for incoming bytes and
+/// `serial-console-closed` with { port, reason } when the session ends.
+
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::io::{Read, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::daemon::DaemonState;
+
+const DEFAULT_BAUD_RATE: u32 = 1_000_000;
+/// Bytes kept for read_serial_console between two reads
+const BUFFER_CAPACITY: usize = 64 * 1024;
+const READ_TIMEOUT: Duration = Duration::from_millis(100);
+const PORT_RELEASE_TIMEOUT: Duration = Duration::from_secs(10);
+
+struct ConsoleSession {
+    port_name: String,
+    writer: Box<dyn serialport::SerialPort>,
+    buffer: Arc<Mutex<VecDeque<u8>>>,
+    stop: Arc<AtomicBool>,
+    reader: Option<std::thread::JoinHandle<()>>,
+    /// The daemon was stopped to open the console
+    stopped_daemon: bool,
+}
+
+#[derive(Default)]
+pub struct SerialConsoleState {
+    session: Mutex<Option<ConsoleSession>>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct SerialConsoleInfo {
+    pub port_name: String,
+    pub baud_rate: u32,
+    pub stopped_daemon: bool,
+}
+
+#[derive(Debug, Serialize, Clone)]
+struct SerialConsoleData {
+    data: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+struct SerialConsoleClosed {
+    port: String,
+    /// "closed" | "daemon_starting" | "port_error"
+    reason: &'static str,
+}
+
+// ============================================================================
+// SESSION
+// ============================================================================
+
+/// Read from the port until stopped, buffering and forwarding incoming bytes
+fn spawn_reader(
+    app_handle: AppHandle,
+    mut port: Box<dyn serialport::SerialPort>,
+    buffer: Arc<Mutex<VecDeque<u8>>>,
+    stop: Arc<AtomicBool>,
+) -> std::thread::JoinHandle<()> {
+    std::thread::spawn(move || {
+        let mut chunk = [0u8; 1024];
+        while !stop.load(Ordering::SeqCst) {
+            match port.read(&mut chunk) {
+                Ok(0) => {}
+                Ok(len) => {
+                    {
+                        let mut buffer = buffer.lock().unwrap();
+                        buffer.extend(&chunk[..len]);
+                        let overflow = buffer.len().saturating_sub(BUFFER_CAPACITY);
+                        buffer.drain(..overflow);
+                    }
+                    let data = String::from_utf8_lossy(&chunk[..len]).to_string();
+                    let _ = app_handle.emit("serial-console-data", SerialConsoleData { data });
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::TimedOut => {}
+                Err(e) => {
+                    // Robot unplugged or port gone: drop the session
+                    eprintln!("[serial-console] ❌ Read failed: {}", e);
+                    let _ = app_handle.emit("serial-console-closed", SerialConsoleClosed {
+                        port: port.name().unwrap_or_default(),
+                        reason: "port_error",
+                    });
+                    app_handle.state::<SerialConsoleState>().session.lock().unwrap().take();
+                    return;
+                }
+            }
+        }
+    })
+}
+
+/// End the session; returns it so the caller can decide about the daemon
+fn end_session(state: &SerialConsoleState) -> Option<ConsoleSession> {
+    let mut session = state.session.lock().unwrap().take()?;
+    session.stop.store(true, Ordering::SeqCst);
+    if let Some(reader) = session.reader.take() {
+        let _ = reader.join();
+    }
+    println!("[serial-console] Closed {}", session.port_name);
+    Some(session)
+}
+
+/// Close the console before the daemon takes the port (called by start_daemon)
+pub fn release_for_daemon(app_handle: &AppHandle) {
+    let Some(state) = app_handle.try_state::<SerialConsoleState>() else { return };
+    if let Some(session) = end_session(&state) {
+        let _ = app_handle.emit("serial-console-closed", SerialConsoleClosed {
+            port: session.port_name,
+            reason: "daemon_starting",
+        });
+    }
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Open the serial console (stops the daemon if it's running)
+#[tauri::command]
+pub async fn open_serial_console(
+    app_handle: AppHandle,
+    port: Option<String>,
+    baud_rate: Option<u32>,
+) -> Result<SerialConsoleInfo, String> {
+    let port_name = port
+        .or_else(crate::usb::get_reachy_port)
+        .ok_or("No Reachy Mini connected over USB")?;
+    let baud_rate = baud_rate.unwrap_or(DEFAULT_BAUD_RATE);
+
+    if app_handle.state::<SerialConsoleState>().session.lock().unwrap().is_some() {
+        return Err("Serial console is already open".to_string());
+    }
+
+    let handle = app_handle.clone();
+    let name = port_name.clone();
+    let (port, stopped_daemon) = tauri::async_runtime::spawn_blocking(move || {
+        let stopped_daemon = {
+            let daemon_state = handle.state::<DaemonState>();
+            let running = daemon_state.process.lock().unwrap().is_some();
+            if running {
+                println!("[serial-console] Stopping daemon to free {}", name);
+                crate::daemon::kill_daemon(&daemon_state);
+            }
+            running
+        };
+        crate::usb::wait_for_port_release(&name, PORT_RELEASE_TIMEOUT)?;
+
+        let port = serialport::new(&name, baud_rate)
+            .timeout(READ_TIMEOUT)
+            .open()
+            .map_err(|e| format!("Failed to open {}: {}", name, e))?;
+        Ok::<_, String>((port, stopped_daemon))
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))??;
+
+    let reader_port = port
+        .try_clone()
+        .map_err(|e| format!("Failed to clone {}: {}", port_name, e))?;
+    let buffer = Arc::new(Mutex::new(VecDeque::new()));
+    let stop = Arc::new(AtomicBool::new(false));
+    let reader = spawn_reader(app_handle.clone(), reader_port, buffer.clone(), stop.clone());
+
+    *app_handle.state::<SerialConsoleState>().session.lock().unwrap() = Some(ConsoleSession {
+        port_name: port_name.clone(),
+        writer: port,
+        buffer,
+        stop,
+        reader: Some(reader),
+        stopped_daemon,
+    });
+    println!("[serial-console] Opened {} at {} baud", port_name, baud_rate);
+
+    Ok(SerialConsoleInfo {
+        port_name,
+        baud_rate,
+        stopped_daemon,
+    })
+}
+
+/// Data received since the last read (also pushed through `serial-console-data`)
+#[tauri::command]
+pub fn read_serial_console(state: State<SerialConsoleState>) -> Result<String, String> {
+    let session = state.session.lock().unwrap();
+    let session = session.as_ref().ok_or("Serial console is not open")?;
+    let bytes: Vec<u8> = session.buffer.lock().unwrap().drain(..).collect();
+    Ok(String::from_utf8_lossy(&bytes).to_string())
+}
+
+#[tauri::command]
+pub fn write_serial_console(state: State<SerialConsoleState>, data: String) -> Result<(), String> {
+    crate::lock::ensure_unlocked("Writing to the serial console")?;
+    let mut session = state.session.lock().unwrap();
+    let session = session.as_mut().ok_or("Serial console is not open")?;
+    session
+        .writer
+        .write_all(data.as_bytes())
+        .and_then(|_| session.writer.flush())
+        .map_err(|e| format!("Failed to write to {}: {}", session.port_name, e))
+}
+
+/// Close the console and restart the daemon if opening the console stopped it
+#[tauri::command]
+pub async fn close_serial_console(app_handle: AppHandle) -> Result<(), String> {
+    let Some(session) = end_session(&app_handle.state::<SerialConsoleState>()) else {
+        return Ok(());
+    };
+    let _ = app_handle.emit("serial-console-closed", SerialConsoleClosed {
+        port: session.port_name.clone(),
+        reason: "closed",
+    });
+
+    if session.stopped_daemon {
+        let handle = app_handle.clone();
+        let port = session.port_name.clone();
+        drop(session);
+        tauri::async_runtime::spawn_blocking(move || {
+            crate::start_daemon(handle.clone(), handle.state::<DaemonState>(), Some(false), None, Some(port))
+        })
+        .await
+        .map_err(|e| format!("Task join error: {}", e))??;
+    }
+    Ok(())
+}
+
+/// Open (or focus) the serial console window; closing it closes the console
+#[tauri::command]
+pub fn open_serial_console_window(app_handle: AppHandle) -> Result<(), String> {
+    let Some(window) = crate::window::open_aux(&app_handle, &crate::window::SERIAL_CONSOLE)? else {
+        return Ok(());
+    };
+
+    let handle = app_handle.clone();
+    window.on_window_event(move |event| {
+        if let tauri::WindowEvent::Destroyed = event {
+            let handle = handle.clone();
+            tauri::async_runtime::spawn(async move {
+                if let Err(e) = close_serial_console(handle).await {
+                    eprintln!("[serial-console] ⚠️ Failed to close console: {}", e);
+                }
+            });
+        }
+    });
+    Ok(())
+}
@@ -0,0 +1,435 @@
+/// Settings module
+///
+/// Backend-owned preferences persisted to `<app data>/settings.json`.
+/// Loaded once in setup; every update is written back to disk immediately
+/// and pushed to the frontend as a `settings-changed` event.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, State};
+
+use crate::gamepad::GamepadSettings;
+use crate::local_proxy::firewall::FirewallConfig;
+use crate::local_proxy::PortMapping;
+use crate::usb::UsbWatchEntry;
+
+const SETTINGS_FILE: &str = "settings.json";
+
+/// Settings applied live by their own commands (proxy, USB monitor), or guarded by the PIN
+/// lock (update channel, automation server): not writable through `set_settings`
+const MANAGED_KEYS: &[&str] = &[
+    "usb_watch_list",
+    "proxy_require_local_token",
+    "proxy_ports",
+    "proxy_firewall",
+    "language",
+    "kiosk",
+    "mqtt",
+    "update_channel",
+    "nightly_index_url",
+    "automation_enabled",
+];
+
+/// Where diagnostics leave the machine: changing them needs the UI unlocked (see lock/mod.rs)
+const UPLOAD_KEYS: &[&str] = &["analytics_endpoint", "crash_report_endpoint"];
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+/// What to do when the USB robot is unplugged while the daemon runs
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DisconnectAction {
+    StopDaemon,
+    SetCompliant,
+    Nothing,
+}
+
+/// Which daemon releases the updater offers
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum UpdateChannel {
+    /// Final releases only
+    Stable,
+    /// Final releases and release candidates / betas
+    Rc,
+    /// Everything, including .dev builds
+    Nightly,
+}
+
+/// Daemon log level, passed as `--log-level`
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum LogVerbosity {
+    Error,
+    Warning,
+    Info,
+    Debug,
+}
+
+impl LogVerbosity {
+    pub fn as_daemon_arg(self) -> &'static str {
+        match self {
+            Self::Error => "ERROR",
+            Self::Warning => "WARNING",
+            Self::Info => "INFO",
+            Self::Debug => "DEBUG",
+        }
+    }
+}
+
+/// Main window geometry, saved by the frontend on resize/move and restored at launch
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct WindowPreferences {
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub x: Option<i32>,
+    pub y: Option<i32>,
+    pub maximized: bool,
+    pub always_on_top: bool,
+}
+
+/// Kiosk / exhibit mode (see kiosk module)
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct KioskSettings {
+    pub enabled: bool,
+    /// Robot app started once the daemon is up, and restarted if it stops
+    pub app: Option<String>,
+    /// Run the daemon in simulation (exhibits without a robot plugged in)
+    pub sim_mode: bool,
+}
+
+/// MQTT bridge to a home-automation broker (see mqtt module; the password is in the keychain)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(default)]
+pub struct MqttSettings {
+    pub enabled: bool,
+    /// Broker hostname or IP
+    pub host: String,
+    pub port: u16,
+    pub tls: bool,
+    pub username: Option<String>,
+    /// Prefix of every topic, e.g. "reachy_mini" -> "reachy_mini/state"
+    pub base_topic: String,
+    /// How often the state is checked (published when it changed)
+    pub publish_interval_secs: u64,
+    /// Announce the sensors to Home Assistant (MQTT discovery)
+    pub home_assistant_discovery: bool,
+}
+
+impl Default for MqttSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            host: String::new(),
+            port: 1883,
+            tls: false,
+            username: None,
+            base_topic: "reachy_mini".to_string(),
+            publish_interval_secs: 5,
+            home_assistant_discovery: true,
+        }
+    }
+}
+
+/// Keyboard teleoperation (see teleop module)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct TeleopSettings {
+    /// Head rotation speed while a key is held
+    pub speed_deg_s: f64,
+    /// Head goes back to center once every key is released
+    pub recenter_on_release: bool,
+    /// Keys also work while the app is in the background (else only with the main window focused)
+    pub global_shortcuts: bool,
+}
+
+impl Default for TeleopSettings {
+    fn default() -> Self {
+        Self {
+            speed_deg_s: 60.0,
+            recenter_on_release: true,
+            global_shortcuts: false,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
+pub struct AppSettings {
+    /// Start the daemon automatically when the USB robot is plugged in
+    pub auto_start_daemon: bool,
+    pub on_robot_disconnect: DisconnectAction,
+    pub update_channel: UpdateChannel,
+    /// PyPI-compatible index serving nightly builds (e.g. "https://test.pypi.org")
+    /// None = nightlies are looked up on pypi.org
+    pub nightly_index_url: Option<String>,
+    /// Background update check interval (0 = disabled)
+    pub update_check_interval_hours: u64,
+    /// Versions the user chose to skip, e.g. "daemon:1.2.0", "app:0.9.1"
+    pub skipped_update_versions: Vec<String>,
+    /// "Remind me later": no update notification before this time (unix millis)
+    pub update_remind_after: Option<u64>,
+    /// USB devices whose presence is reported (robot, accessories)
+    pub usb_watch_list: Vec<UsbWatchEntry>,
+    /// Local proxy only accepts clients presenting the session token
+    pub proxy_require_local_token: bool,
+    /// Ports forwarded by the WiFi-mode local proxy
+    pub proxy_ports: Vec<PortMapping>,
+    /// Daemon routes blocked or confirmed for third-party app UIs going through the proxy
+    pub proxy_firewall: FirewallConfig,
+    /// Serial number of the robot to drive when several are plugged in
+    pub preferred_robot: Option<String>,
+    /// start_daemon runs in simulation when the frontend doesn't say
+    pub default_sim_mode: bool,
+    pub daemon_log_verbosity: LogVerbosity,
+    /// Anonymous usage analytics (PostHog, checked by the frontend before capturing,
+    /// and the analytics module)
+    pub telemetry_opt_in: bool,
+    /// Where the analytics module uploads usage events (None = kept on this computer)
+    pub analytics_endpoint: Option<String>,
+    /// Where send_crash_report uploads report zips (None = save and attach manually)
+    pub crash_report_endpoint: Option<String>,
+    pub window: WindowPreferences,
+    /// Daemon microphone / speaker (see audio module), None = system default
+    pub audio_input_device: Option<String>,
+    pub audio_output_device: Option<String>,
+    /// Closing the main window hides it in the system tray, the daemon keeps running
+    pub minimize_to_tray: bool,
+    /// Park the USB robot (sleep pose, then compliant) before the daemon is killed on quit
+    pub park_on_exit: bool,
+    pub teleop: TeleopSettings,
+    pub gamepad: GamepadSettings,
+    /// Local automation socket for scripts (see automation module)
+    pub automation_enabled: bool,
+    /// UI language, e.g. "fr" (None = OS locale, see i18n module)
+    pub language: Option<String>,
+    pub kiosk: KioskSettings,
+    pub mqtt: MqttSettings,
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            auto_start_daemon: false,
+            on_robot_disconnect: DisconnectAction::StopDaemon,
+            update_channel: UpdateChannel::Stable,
+            nightly_index_url: None,
+            update_check_interval_hours: 24,
+            skipped_update_versions: Vec::new(),
+            update_remind_after: None,
+            usb_watch_list: crate::usb::default_watch_list(),
+            proxy_require_local_token: false,
+            proxy_ports: crate::local_proxy::default_port_mappings(),
+            proxy_firewall: FirewallConfig::default(),
+            preferred_robot: None,
+            default_sim_mode: false,
+            daemon_log_verbosity: LogVerbosity::Info,
+            telemetry_opt_in: false,
+            analytics_endpoint: None,
+            crash_report_endpoint: None,
+            window: WindowPreferences::default(),
+            audio_input_device: None,
+            audio_output_device: None,
+            minimize_to_tray: false,
+            park_on_exit: true,
+            teleop: TeleopSettings::default(),
+            gamepad: GamepadSettings::default(),
+            automation_enabled: false,
+            language: None,
+            kiosk: KioskSettings::default(),
+            mqtt: MqttSettings::default(),
+        }
+    }
+}
+
+impl AppSettings {
+    fn validate(&self) -> Result<(), String> {
+        if let Some(url) = &self.nightly_index_url {
+            if !url.starts_with("http://") && !url.starts_with("https://") {
+                return Err(format!("Invalid index URL: {}", url));
+            }
+        }
+        if let Some(url) = &self.analytics_endpoint {
+            if !url.starts_with("http://") && !url.starts_with("https://") {
+                return Err(format!("Invalid analytics endpoint: {}", url));
+            }
+        }
+        if !(1.0..=360.0).contains(&self.teleop.speed_deg_s) {
+            return Err(format!("Teleop speed must be between 1 and 360°/s: {}", self.teleop.speed_deg_s));
+        }
+        self.gamepad.validate()?;
+        self.proxy_firewall.validate()?;
+        if let Some(url) = &self.crash_report_endpoint {
+            if !url.starts_with("http://") && !url.starts_with("https://") {
+                return Err(format!("Invalid crash report endpoint: {}", url));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Parse and validate the content of a settings file (e.g. from a backup)
+pub fn parse_settings(content: &[u8]) -> Result<AppSettings, String> {
+    let settings: AppSettings = serde_json::from_slice(content)
+        .map_err(|e| format!("Invalid settings: {}", e))?;
+    settings.validate()?;
+    Ok(settings)
+}
+
+pub struct SettingsState {
+    settings: Mutex<AppSettings>,
+    path: Mutex<Option<PathBuf>>,
+    app_handle: Mutex<Option<AppHandle>>,
+}
+
+impl SettingsState {
+    pub fn new() -> Self {
+        Self {
+            settings: Mutex::new(AppSettings::default()),
+            path: Mutex::new(None),
+            app_handle: Mutex::new(None),
+        }
+    }
+
+    /// Load settings from the app data directory (defaults if missing, corrupted or invalid)
+    pub fn load(&self, app_handle: &AppHandle, app_data_dir: &Path) {
+        *self.app_handle.lock().unwrap() = Some(app_handle.clone());
+        let path = app_data_dir.join(SETTINGS_FILE);
+        let settings = std::fs::read(&path)
+            .ok()
+            .and_then(|content| match parse_settings(&content) {
+                Ok(settings) => Some(settings),
+                Err(e) => {
+                    eprintln!("[settings] ⚠️ Ignoring {:?}: {}", path, e);
+                    None
+                }
+            })
+            .unwrap_or_default();
+        *self.settings.lock().unwrap() = settings;
+        *self.path.lock().unwrap() = Some(path);
+    }
+
+    pub fn get(&self) -> AppSettings {
+        self.settings.lock().unwrap().clone()
+    }
+
+    /// Apply a change, persist it and notify the frontend
+    pub fn update(&self, change: impl FnOnce(&mut AppSettings)) -> Result<AppSettings, String> {
+        let mut settings = self.settings.lock().unwrap();
+        let mut updated = settings.clone();
+        change(&mut updated);
+        updated.validate()?;
+        *settings = updated;
+
+        if let Some(path) = self.path.lock().unwrap().as_ref() {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)
+                    .map_err(|e| format!("Failed to create settings dir: {}", e))?;
+            }
+            let content = serde_json::to_string_pretty(&*settings)
+                .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+            std::fs::write(path, content)
+                .map_err(|e| format!("Failed to write settings: {}", e))?;
+        }
+
+        if let Some(app_handle) = self.app_handle.lock().unwrap().as_ref() {
+            let _ = app_handle.emit("settings-changed", &*settings);
+        }
+        Ok(settings.clone())
+    }
+}
+
+impl Default for SettingsState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+#[tauri::command]
+pub fn get_settings(state: State<SettingsState>) -> AppSettings {
+    state.get()
+}
+
+/// Update any subset of the settings: `patch` is a JSON object of the fields to change
+/// (`null` clears an optional field, nested objects like `window` are replaced whole)
+#[tauri::command]
+pub fn set_settings(state: State<SettingsState>, patch: serde_json::Value) -> Result<AppSettings, String> {
+    let serde_json::Value::Object(patch) = patch else {
+        return Err("Settings patch must be an object".to_string());
+    };
+    if let Some(key) = patch.keys().find(|key| MANAGED_KEYS.contains(&key.as_str())) {
+        return Err(format!("'{}' must be changed with its dedicated command", key));
+    }
+    if patch.keys().any(|key| UPLOAD_KEYS.contains(&key.as_str())) {
+        crate::lock::ensure_unlocked("Changing where reports are uploaded")?;
+    }
+
+    let mut merged = serde_json::to_value(state.get())
+        .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+    if let Some(current) = merged.as_object_mut() {
+        for (key, value) in patch {
+            if !current.contains_key(&key) {
+                return Err(format!("Unknown setting: {}", key));
+            }
+            current.insert(key, value);
+        }
+    }
+    let settings: AppSettings = serde_json::from_value(merged)
+        .map_err(|e| format!("Invalid settings: {}", e))?;
+
+    state.update(|current| {
+        *current = AppSettings {
+            usb_watch_list: std::mem::take(&mut current.usb_watch_list),
+            proxy_require_local_token: current.proxy_require_local_token,
+            proxy_ports: std::mem::take(&mut current.proxy_ports),
+            proxy_firewall: std::mem::take(&mut current.proxy_firewall),
+            language: current.language.take(),
+            kiosk: std::mem::take(&mut current.kiosk),
+            mqtt: std::mem::take(&mut current.mqtt),
+            update_channel: current.update_channel,
+            nightly_index_url: current.nightly_index_url.take(),
+            automation_enabled: current.automation_enabled,
+            ..settings
+        }
+    })
+}
+
+/// Enable/disable daemon auto-start on USB robot connection
+#[tauri::command]
+pub fn set_auto_start_daemon(
+    state: State<SettingsState>,
+    enabled: bool,
+    on_robot_disconnect: Option<DisconnectAction>,
+) -> Result<AppSettings, String> {
+    state.update(|settings| {
+        settings.auto_start_daemon = enabled;
+        if let Some(action) = on_robot_disconnect {
+            settings.on_robot_disconnect = action;
+        }
+    })
+}
+
+/// Select the daemon update channel
+#[tauri::command]
+pub fn set_update_channel(
+    state: State<SettingsState>,
+    channel: UpdateChannel,
+    nightly_index_url: Option<String>,
+) -> Result<AppSettings, String> {
+    crate::lock::ensure_unlocked("Changing the update channel")?;
+    state.update(|settings| {
+        settings.update_channel = channel;
+        if nightly_index_url.is_some() {
+            settings.nightly_index_url = nightly_index_url.map(|url| url.trim_end_matches('/').to_string());
+        }
+    })
+}
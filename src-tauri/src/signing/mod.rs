@@ -1,5 +1,6 @@
+use serde::Serialize;
 #[cfg(target_os = "macos")]
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Re-sign Python binaries (.so, .dylib) in .venv after pip install
 /// This fixes the Team ID mismatch issue on macOS where pip-installed binaries
@@ -12,6 +13,8 @@ pub async fn sign_python_binaries() -> Result<String, String> {
     use std::process::Command;
     use std::env;
     
+    let _span = crate::profiling::span("signing");
+    
     // Run the signing work in a blocking thread to avoid blocking the async runtime
     let result = tauri::async_runtime::spawn_blocking(move || {
         println!("[tauri] 🔐 Starting Python binaries re-signing...");
@@ -20,67 +23,7 @@ pub async fn sign_python_binaries() -> Result<String, String> {
     let exe_path = env::current_exe()
         .map_err(|e| format!("Failed to get current executable path: {}", e))?;
     
-    // Try to find .venv in different locations:
-    // - Production: Contents/Resources/.venv (in .app bundle)
-    // - Dev mode: target/debug/.venv or current_dir/.venv
-    let venv_dir = if exe_path.to_string_lossy().contains(".app/Contents/MacOS") {
-        // Production mode: in app bundle
-        let app_bundle = exe_path
-            .parent() // Contents/MacOS
-            .and_then(|p| p.parent()) // Contents
-            .and_then(|p| p.parent()) // .app bundle
-            .ok_or("Failed to find app bundle path")?;
-        
-        let resources_dir = app_bundle.join("Contents/Resources");
-        resources_dir.join(".venv")
-    } else {
-        // Dev mode: try to find .venv relative to current dir or target/debug
-        let current_dir = env::current_dir()
-            .map_err(|e| format!("Failed to get current directory: {}", e))?;
-        
-        // Try multiple locations in dev mode:
-        // 1. binaries/.venv (if we're in src-tauri/)
-        // 2. src-tauri/binaries/.venv (if we're in project root)
-        // 3. target/debug/.venv
-        // 4. current_dir/.venv
-        
-        // Check if we're in src-tauri/ directory by checking the last component
-        let is_in_src_tauri = current_dir
-            .file_name()
-            .and_then(|name| name.to_str())
-            .map(|name| name == "src-tauri")
-            .unwrap_or(false);
-        
-        // Try multiple locations in dev mode:
-        let binaries_venv = if is_in_src_tauri {
-            // We're in src-tauri/, look for binaries/.venv
-            current_dir.join("binaries/.venv")
-        } else {
-            // We're in project root, look for src-tauri/binaries/.venv
-            current_dir.join("src-tauri/binaries/.venv")
-        };
-        
-        if binaries_venv.exists() {
-            println!("[tauri] 📁 Found .venv at: {}", binaries_venv.display());
-            binaries_venv
-        } else {
-            let target_venv = if is_in_src_tauri {
-                current_dir.join("target/debug/.venv")
-            } else {
-                current_dir.join("src-tauri/target/debug/.venv")
-            };
-            
-            if target_venv.exists() {
-                println!("[tauri] 📁 Found .venv at: {}", target_venv.display());
-                target_venv
-            } else {
-                // Fallback: try current_dir/.venv
-                let fallback_venv = current_dir.join(".venv");
-                println!("[tauri] 📁 Trying fallback .venv at: {}", fallback_venv.display());
-                fallback_venv
-            }
-        }
-    };
+    let venv_dir = find_venv_dir(&exe_path)?;
     
     if !venv_dir.exists() {
         return Err(format!("Python virtual environment (.venv) not found at: {}", venv_dir.display()));
@@ -311,6 +254,313 @@ pub fn sign_python_binaries() -> Result<String, String> {
     Ok("Code signing not required on this platform".to_string())
 }
 
+// ============================================================================
+// Signing status
+// ============================================================================
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+pub enum SignatureKind {
+    DeveloperId,
+    /// Signed with a certificate that isn't Developer ID (Apple Development...)
+    OtherCertificate,
+    Adhoc,
+    Unsigned,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct FileSignature {
+    pub path: String,
+    pub kind: SignatureKind,
+    /// First `Authority=` of the certificate chain
+    pub authority: Option<String>,
+    pub team_id: Option<String>,
+    /// `codesign --verify --strict` passes (false once the file changed after signing)
+    pub valid: bool,
+    /// Why this file would be rejected (None = accepted)
+    pub problem: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct SigningStatus {
+    /// Only macOS checks signatures
+    pub supported: bool,
+    /// None in dev mode (no .app bundle)
+    pub app: Option<FileSignature>,
+    /// Gatekeeper (`spctl`) accepts the app
+    pub gatekeeper_accepted: Option<bool>,
+    /// Gatekeeper source, e.g. "Notarized Developer ID"
+    pub gatekeeper_source: Option<String>,
+    pub notarized: bool,
+    pub venv_dir: Option<String>,
+    /// Mach-O files checked in the venv
+    pub files_checked: usize,
+    pub developer_id: usize,
+    pub other_certificate: usize,
+    pub adhoc: usize,
+    pub unsigned: usize,
+    /// Venv binaries Gatekeeper or library validation would reject
+    pub failing: Vec<FileSignature>,
+}
+
+/// Mach-O (thin or universal) from the magic number, without spawning `file`
+#[cfg(target_os = "macos")]
+fn is_mach_o(path: &Path) -> bool {
+    use std::io::Read;
+    let mut magic = [0u8; 4];
+    let read = std::fs::File::open(path).and_then(|mut file| file.read_exact(&mut magic));
+    read.is_ok()
+        && matches!(
+            u32::from_be_bytes(magic),
+            0xfeedface | 0xfeedfacf | 0xcefaedfe | 0xcffaedfe | 0xcafebabe
+        )
+}
+
+/// Signature details from `codesign -dv` and `codesign --verify`
+#[cfg(target_os = "macos")]
+fn read_signature(path: &Path) -> FileSignature {
+    use std::process::Command;
+
+    let details = Command::new("codesign")
+        .args(["-d", "--verbose=2"])
+        .arg(path)
+        .output()
+        .map(|output| String::from_utf8_lossy(&output.stderr).to_string())
+        .unwrap_or_default();
+    let field = |name: &str| {
+        details
+            .lines()
+            .find_map(|line| line.strip_prefix(name))
+            .map(|value| value.trim().to_string())
+    };
+    let authority = field("Authority=");
+    let team_id = field("TeamIdentifier=").filter(|team| team != "not set");
+    let kind = if details.contains("not signed at all") {
+        SignatureKind::Unsigned
+    } else if details.contains("Signature=adhoc") {
+        SignatureKind::Adhoc
+    } else if authority.as_deref().is_some_and(|authority| authority.starts_with("Developer ID Application")) {
+        SignatureKind::DeveloperId
+    } else if authority.is_some() {
+        SignatureKind::OtherCertificate
+    } else {
+        SignatureKind::Unsigned
+    };
+    let valid = kind != SignatureKind::Unsigned
+        && Command::new("codesign")
+            .args(["--verify", "--strict"])
+            .arg(path)
+            .output()
+            .map(|output| output.status.success())
+            .unwrap_or(false);
+
+    FileSignature {
+        path: path.display().to_string(),
+        kind,
+        authority,
+        team_id,
+        valid,
+        problem: None,
+    }
+}
+
+/// Gatekeeper assessment of the app bundle: (accepted, source)
+#[cfg(target_os = "macos")]
+fn assess_app(bundle: &Path) -> (bool, Option<String>) {
+    use std::process::Command;
+
+    match Command::new("spctl").args(["--assess", "--type", "execute", "-vv"]).arg(bundle).output() {
+        Ok(output) => {
+            let details = String::from_utf8_lossy(&output.stderr);
+            let source = details
+                .lines()
+                .find_map(|line| line.strip_prefix("source="))
+                .map(|source| source.trim().to_string());
+            (output.status.success(), source)
+        }
+        Err(_) => (false, None),
+    }
+}
+
+/// Why a venv binary would be rejected next to an app signed as `app`
+#[cfg(target_os = "macos")]
+fn venv_problem(file: &FileSignature, app: Option<&FileSignature>) -> Option<String> {
+    if file.kind == SignatureKind::Unsigned {
+        return Some("not signed".to_string());
+    }
+    if !file.valid {
+        return Some("signature invalid (modified after signing)".to_string());
+    }
+    // A Developer ID app with the hardened runtime only loads libraries of its own team
+    let app = app.filter(|app| app.kind == SignatureKind::DeveloperId)?;
+    match file.kind {
+        SignatureKind::DeveloperId => {}
+        SignatureKind::Adhoc => return Some("adhoc signature in a Developer ID app".to_string()),
+        _ => return Some(format!("signed by {} in a Developer ID app", file.authority.as_deref().unwrap_or("(unknown)"))),
+    }
+    if file.team_id != app.team_id {
+        return Some(format!(
+            "team {} instead of {}",
+            file.team_id.as_deref().unwrap_or("(none)"),
+            app.team_id.as_deref().unwrap_or("(none)")
+        ));
+    }
+    None
+}
+
+/// Signature state of the app bundle and the venv's binaries: adhoc / Developer ID /
+/// unsigned, notarization, and the files Gatekeeper or library validation would reject
+/// (what `sign_python_binaries` fixes)
+#[cfg(target_os = "macos")]
+#[tauri::command]
+pub async fn get_signing_status() -> Result<SigningStatus, String> {
+    tauri::async_runtime::spawn_blocking(|| {
+        let exe_path = std::env::current_exe()
+            .map_err(|e| format!("Failed to get current executable path: {}", e))?;
+        let bundle = exe_path
+            .to_string_lossy()
+            .contains(".app/Contents/MacOS")
+            .then(|| exe_path.parent()?.parent()?.parent().map(Path::to_path_buf))
+            .flatten();
+
+        let mut status = SigningStatus {
+            supported: true,
+            ..Default::default()
+        };
+        if let Some(bundle) = &bundle {
+            let (accepted, source) = assess_app(bundle);
+            status.notarized = source.as_deref().is_some_and(|source| source.contains("Notarized"));
+            status.gatekeeper_accepted = Some(accepted);
+            status.gatekeeper_source = source;
+            let mut app = read_signature(bundle);
+            if !accepted {
+                app.problem = Some("rejected by Gatekeeper".to_string());
+            }
+            status.app = Some(app);
+        }
+
+        let venv_dir = find_venv_dir(&exe_path)?;
+        if venv_dir.exists() {
+            let mut files = find_files(&venv_dir, "*.dylib")?;
+            files.extend(find_files(&venv_dir, "*.so")?);
+            files.extend(["bin/python3", "bin/python3.12"].iter().map(|bin| venv_dir.join(bin)));
+            // bin/python3 usually links to python3.12
+            let mut files: Vec<PathBuf> = files.iter().filter_map(|file| file.canonicalize().ok()).collect();
+            files.sort();
+            files.dedup();
+            for file in files.iter().filter(|file| is_mach_o(file)) {
+                let mut signature = read_signature(file);
+                match signature.kind {
+                    SignatureKind::DeveloperId => status.developer_id += 1,
+                    SignatureKind::OtherCertificate => status.other_certificate += 1,
+                    SignatureKind::Adhoc => status.adhoc += 1,
+                    SignatureKind::Unsigned => status.unsigned += 1,
+                }
+                status.files_checked += 1;
+                signature.problem = venv_problem(&signature, status.app.as_ref());
+                if signature.problem.is_some() {
+                    status.failing.push(signature);
+                }
+            }
+            status.venv_dir = Some(venv_dir.display().to_string());
+        }
+
+        println!(
+            "[tauri] 🔏 Signing status: {} venv binaries checked, {} would be rejected",
+            status.files_checked,
+            status.failing.len()
+        );
+        Ok(status)
+    })
+    .await
+    .map_err(|e| format!("Failed to execute signing status task: {}", e))?
+}
+
+#[cfg(not(target_os = "macos"))]
+#[tauri::command]
+pub fn get_signing_status() -> SigningStatus {
+    // Nothing to check on non-macOS
+    SigningStatus::default()
+}
+
+/// Locate the Python venv the daemon runs from (local copy, app bundle or dev tree)
+#[cfg(target_os = "macos")]
+fn find_venv_dir(exe_path: &Path) -> Result<PathBuf, String> {
+    use std::env;
+
+    // Try to find .venv in different locations:
+    // - Production: local copy in Application Support (made by uv-trampoline)
+    // - Production: Contents/Resources/.venv (in .app bundle)
+    // - Dev mode: target/debug/.venv or current_dir/.venv
+    let in_app_bundle = exe_path.to_string_lossy().contains(".app/Contents/MacOS");
+    let local_venv = env::var("HOME")
+        .ok()
+        .map(|home| PathBuf::from(home).join("Library/Application Support/Reachy Mini Control/.venv"))
+        .filter(|venv| in_app_bundle && venv.exists());
+    let venv_dir = if let Some(local_venv) = local_venv {
+        local_venv
+    } else if in_app_bundle {
+        // Production mode: in app bundle
+        let app_bundle = exe_path
+            .parent() // Contents/MacOS
+            .and_then(|p| p.parent()) // Contents
+            .and_then(|p| p.parent()) // .app bundle
+            .ok_or("Failed to find app bundle path")?;
+        
+        let resources_dir = app_bundle.join("Contents/Resources");
+        resources_dir.join(".venv")
+    } else {
+        // Dev mode: try to find .venv relative to current dir or target/debug
+        let current_dir = env::current_dir()
+            .map_err(|e| format!("Failed to get current directory: {}", e))?;
+        
+        // Try multiple locations in dev mode:
+        // 1. binaries/.venv (if we're in src-tauri/)
+        // 2. src-tauri/binaries/.venv (if we're in project root)
+        // 3. target/debug/.venv
+        // 4. current_dir/.venv
+        
+        // Check if we're in src-tauri/ directory by checking the last component
+        let is_in_src_tauri = current_dir
+            .file_name()
+            .and_then(|name| name.to_str())
+            .map(|name| name == "src-tauri")
+            .unwrap_or(false);
+        
+        // Try multiple locations in dev mode:
+        let binaries_venv = if is_in_src_tauri {
+            // We're in src-tauri/, look for binaries/.venv
+            current_dir.join("binaries/.venv")
+        } else {
+            // We're in project root, look for src-tauri/binaries/.venv
+            current_dir.join("src-tauri/binaries/.venv")
+        };
+        
+        if binaries_venv.exists() {
+            println!("[tauri] 📁 Found .venv at: {}", binaries_venv.display());
+            binaries_venv
+        } else {
+            let target_venv = if is_in_src_tauri {
+                current_dir.join("target/debug/.venv")
+            } else {
+                current_dir.join("src-tauri/target/debug/.venv")
+            };
+            
+            if target_venv.exists() {
+                println!("[tauri] 📁 Found .venv at: {}", target_venv.display());
+                target_venv
+            } else {
+                // Fallback: try current_dir/.venv
+                let fallback_venv = current_dir.join(".venv");
+                println!("[tauri] 📁 Trying fallback .venv at: {}", fallback_venv.display());
+                fallback_venv
+            }
+        }
+    };
+    Ok(venv_dir)
+}
+
 /// Helper to find files matching a pattern recursively
 #[cfg(target_os = "macos")]
 fn find_files(dir: &PathBuf, pattern: &str) -> Result<Vec<PathBuf>, String> {
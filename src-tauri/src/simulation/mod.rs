@@ -0,0 +1,86 @@
+/// Simulation physics control
+///
+/// Forwards physics parameters to the daemon's simulation API, e.g. to slow down the
+/// Stewart platform dynamics while teaching. Only with a local daemon in simulation
+/// mode, on a simulation backend that runs physics (the mockup simulation has none).
+///
+/// Ranges are checked here so a typo can't blow up the simulation:
+/// - `realtime_factor`: 0.05 - 4 (1 = real time)
+/// - `gravity`: 0 - 30 m/s² (9.81 on Earth)
+/// - `timestep`: 0.1 - 20 ms
+
+use std::ops::RangeInclusive;
+use tauri::{AppHandle, Manager};
+
+use crate::connection::{ConnectionManager, ConnectionState};
+use crate::daemon_api::{DaemonClient, SimParameters};
+
+const REALTIME_FACTOR_RANGE: RangeInclusive<f64> = 0.05..=4.0;
+/// m/s²
+const GRAVITY_RANGE: RangeInclusive<f64> = 0.0..=30.0;
+/// Seconds
+const TIMESTEP_RANGE: RangeInclusive<f64> = 0.0001..=0.02;
+
+// ============================================================================
+// HELPER FUNCTIONS
+// ============================================================================
+
+fn check_range(name: &str, value: Option<f64>, range: &RangeInclusive<f64>) -> Result<(), String> {
+    match value {
+        Some(value) if !value.is_finite() || !range.contains(&value) => Err(format!(
+            "{} must be between {} and {} (got {})",
+            name,
+            range.start(),
+            range.end(),
+            value
+        )),
+        _ => Ok(()),
+    }
+}
+
+fn validate(parameters: &SimParameters) -> Result<(), String> {
+    check_range("Realtime factor", parameters.realtime_factor, &REALTIME_FACTOR_RANGE)?;
+    check_range("Gravity", parameters.gravity, &GRAVITY_RANGE)?;
+    check_range("Timestep", parameters.timestep, &TIMESTEP_RANGE)
+}
+
+/// Client of the local daemon, if it runs in simulation mode
+fn sim_client(app_handle: &AppHandle) -> Result<DaemonClient, String> {
+    match app_handle.state::<ConnectionManager>().get() {
+        ConnectionState::Ready { sim_mode: true, .. } => Ok(DaemonClient::new()),
+        _ => Err("Start the daemon in simulation mode to change physics parameters".to_string()),
+    }
+}
+
+fn simulation_error(error: String) -> String {
+    if error.contains(" 404 ") {
+        "This simulation has no physics control: use the MuJoCo simulation with an up-to-date daemon".to_string()
+    } else {
+        error
+    }
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+#[tauri::command]
+pub async fn get_sim_parameters(app_handle: AppHandle) -> Result<SimParameters, String> {
+    sim_client(&app_handle)?.sim_parameters().await.map_err(simulation_error)
+}
+
+/// Change the simulation physics (None leaves a parameter unchanged), returns the
+/// parameters now in use
+#[tauri::command]
+pub async fn set_sim_parameters(
+    app_handle: AppHandle,
+    realtime_factor: Option<f64>,
+    gravity: Option<f64>,
+    timestep: Option<f64>,
+) -> Result<SimParameters, String> {
+    let parameters = SimParameters { realtime_factor, gravity, timestep };
+    validate(&parameters)?;
+    let client = sim_client(&app_handle)?;
+    println!("[simulation] ⚙️ Setting physics parameters: {:?}", parameters);
+    client.set_sim_parameters(&parameters).await.map_err(simulation_error)
+}
@@ -0,0 +1,232 @@
+//! IMU orientation feed
+//!
+//! Reads the IMU readings of the telemetry stream (`imu.accelerometer` in m/s² and
+//! `imu.gyroscope` in rad/s, as `[x, y, z]` or `{x, y, z}`) and fuses them with a
+//! complementary filter: gyroscope integration corrected by the gravity direction for
+//! roll and pitch, yaw from the gyroscope only (drifts, see `reset_imu_yaw`).
+//!
+//! Filtering runs on every sample; the viewer gets `imu-orientation` events at a lower
+//! rate (`emit_hz`), so a busy UI never makes the filter drop samples.
+//!
+//! Handling detection emits `robot-handling` with { kind, tilt, acceleration }:
+//! - `knocked_over`: tilted past `KNOCKED_OVER_TILT_DEG` for `KNOCKED_OVER_DURATION_MS`
+//! - `picked_up`: acceleration away from 1 g for `PICKED_UP_DURATION_MS` while upright
+//! - `upright`: upright and steady again after one of the above
+
+use serde::Serialize;
+
+use super::TelemetrySample;
+
+const GRAVITY: f64 = 9.81;
+/// Weight of the gyroscope in the filter (the accelerometer corrects the rest)
+const GYRO_WEIGHT: f64 = 0.98;
+/// Longer gaps (stream hiccup) restart the integration
+const MAX_DT_S: f64 = 0.2;
+/// The accelerometer is only trusted as a gravity reference close to 1 g
+const GRAVITY_TOLERANCE: f64 = 0.15;
+const KNOCKED_OVER_TILT_DEG: f64 = 60.0;
+const KNOCKED_OVER_DURATION_MS: u64 = 500;
+/// m/s² away from 1 g
+const PICKED_UP_ACCELERATION: f64 = 2.0;
+const PICKED_UP_DURATION_MS: u64 = 150;
+const UPRIGHT_TILT_DEG: f64 = 15.0;
+const UPRIGHT_DURATION_MS: u64 = 1000;
+pub const DEFAULT_EMIT_HZ: u32 = 30;
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+/// Degrees
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+pub struct Orientation {
+    /// Unix millis of the sample
+    pub t: u64,
+    pub roll: f64,
+    pub pitch: f64,
+    pub yaw: f64,
+    /// Angle between the robot's vertical axis and gravity
+    pub tilt: f64,
+    /// Norm of the acceleration, m/s²
+    pub acceleration: f64,
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum HandlingKind {
+    PickedUp,
+    KnockedOver,
+    Upright,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct HandlingEvent {
+    pub kind: HandlingKind,
+    pub tilt: f64,
+    pub acceleration: f64,
+}
+
+struct Reading {
+    accelerometer: [f64; 3],
+    gyroscope: [f64; 3],
+}
+
+/// Complementary filter and handling detector (one per stream)
+pub struct ImuFeed {
+    /// Radians
+    roll: f64,
+    pitch: f64,
+    yaw: f64,
+    last_t: Option<u64>,
+    latest: Option<Orientation>,
+    emit_interval_ms: u64,
+    last_emit: u64,
+    handling: Option<HandlingKind>,
+    /// Since when the current candidate condition holds (unix millis)
+    condition_since: Option<(HandlingKind, u64)>,
+}
+
+// ============================================================================
+// HELPER FUNCTIONS
+// ============================================================================
+
+fn vector(value: &serde_json::Value) -> Option<[f64; 3]> {
+    match value {
+        serde_json::Value::Array(items) if items.len() == 3 => {
+            Some([items[0].as_f64()?, items[1].as_f64()?, items[2].as_f64()?])
+        }
+        serde_json::Value::Object(_) => Some([value["x"].as_f64()?, value["y"].as_f64()?, value["z"].as_f64()?]),
+        _ => None,
+    }
+}
+
+fn reading(state: &serde_json::Value) -> Option<Reading> {
+    let imu = &state["imu"];
+    let find = |keys: &[&str]| keys.iter().find_map(|key| vector(&imu[*key]));
+    Some(Reading {
+        accelerometer: find(&["accelerometer", "accel", "acceleration"])?,
+        gyroscope: find(&["gyroscope", "gyro", "angular_velocity"])?,
+    })
+}
+
+fn norm(v: [f64; 3]) -> f64 {
+    (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt()
+}
+
+impl ImuFeed {
+    pub fn new(emit_hz: u32) -> Self {
+        Self {
+            roll: 0.0,
+            pitch: 0.0,
+            yaw: 0.0,
+            last_t: None,
+            latest: None,
+            emit_interval_ms: 1000 / u64::from(emit_hz.clamp(1, 1000)),
+            last_emit: 0,
+            handling: None,
+            condition_since: None,
+        }
+    }
+
+    pub fn latest(&self) -> Option<Orientation> {
+        self.latest
+    }
+
+    pub fn reset_yaw(&mut self) {
+        self.yaw = 0.0;
+    }
+
+    /// Fuse a sample; returns the orientation when it is due for emission, and a
+    /// handling event when one was detected
+    pub fn update(&mut self, sample: &TelemetrySample) -> (Option<Orientation>, Option<HandlingEvent>) {
+        let Some(reading) = reading(&sample.state) else {
+            return (None, None);
+        };
+        let [ax, ay, az] = reading.accelerometer;
+        let acceleration = norm(reading.accelerometer);
+        let accel_roll = ay.atan2(az);
+        let accel_pitch = (-ax).atan2((ay * ay + az * az).sqrt());
+
+        let dt = self
+            .last_t
+            .map(|last| sample.t.saturating_sub(last) as f64 / 1000.0)
+            .filter(|dt| *dt > 0.0 && *dt <= MAX_DT_S);
+        self.last_t = Some(sample.t);
+        let gravity_reliable = (acceleration / GRAVITY - 1.0).abs() < GRAVITY_TOLERANCE;
+        match dt {
+            Some(dt) => {
+                let [gx, gy, gz] = reading.gyroscope;
+                let roll = self.roll + gx * dt;
+                let pitch = self.pitch + gy * dt;
+                self.yaw += gz * dt;
+                let weight = if gravity_reliable { GYRO_WEIGHT } else { 1.0 };
+                self.roll = weight * roll + (1.0 - weight) * accel_roll;
+                self.pitch = weight * pitch + (1.0 - weight) * accel_pitch;
+            }
+            // First sample or gap: start from the gravity direction
+            None => {
+                self.roll = accel_roll;
+                self.pitch = accel_pitch;
+            }
+        }
+
+        let tilt = if acceleration > 0.0 {
+            (az / acceleration).clamp(-1.0, 1.0).acos().to_degrees()
+        } else {
+            0.0
+        };
+        let orientation = Orientation {
+            t: sample.t,
+            roll: self.roll.to_degrees(),
+            pitch: self.pitch.to_degrees(),
+            yaw: self.yaw.to_degrees(),
+            tilt,
+            acceleration,
+        };
+        self.latest = Some(orientation);
+
+        let handling = self.detect_handling(sample.t, tilt, acceleration).map(|kind| HandlingEvent {
+            kind,
+            tilt,
+            acceleration,
+        });
+        let due = sample.t.saturating_sub(self.last_emit) >= self.emit_interval_ms;
+        if due {
+            self.last_emit = sample.t;
+        }
+        (due.then_some(orientation), handling)
+    }
+
+    /// New handling state once its condition held long enough
+    fn detect_handling(&mut self, t: u64, tilt: f64, acceleration: f64) -> Option<HandlingKind> {
+        let candidate = if tilt > KNOCKED_OVER_TILT_DEG {
+            Some((HandlingKind::KnockedOver, KNOCKED_OVER_DURATION_MS))
+        } else if (acceleration - GRAVITY).abs() > PICKED_UP_ACCELERATION {
+            Some((HandlingKind::PickedUp, PICKED_UP_DURATION_MS))
+        } else if tilt < UPRIGHT_TILT_DEG && self.handling.is_some() {
+            Some((HandlingKind::Upright, UPRIGHT_DURATION_MS))
+        } else {
+            None
+        };
+        let Some((kind, duration)) = candidate else {
+            self.condition_since = None;
+            return None;
+        };
+
+        let since = match self.condition_since {
+            Some((current, since)) if current == kind => since,
+            _ => {
+                self.condition_since = Some((kind, t));
+                t
+            }
+        };
+        // Picked up while already knocked over is the same incident
+        let repeated = self.handling == Some(kind)
+            || (kind == HandlingKind::PickedUp && self.handling == Some(HandlingKind::KnockedOver));
+        if t.saturating_sub(since) < duration || repeated {
+            return None;
+        }
+        self.handling = (kind != HandlingKind::Upright).then_some(kind);
+        Some(kind)
+    }
+}
@@ -0,0 +1,301 @@
+/// Robot telemetry bridge
+///
+/// Subscribes to the daemon's full-state WebSocket (joints, head pose, antennas, IMU
+/// when available) on localhost:8000, which is the local daemon or the remote robot
+/// through the WiFi-mode local proxy. Samples are kept in a ring buffer and can be
+/// recorded to disk (see recording) and replayed into the viewer. While the IMU feed is
+/// on, the IMU readings are fused into an orientation (see imu).
+///
+/// Events:
+/// - `telemetry-stream-status`: { connected, error }
+/// - `telemetry-replay-sample`: a recorded sample, at its original pace
+/// - `telemetry-replay-finished`: { path, samples }
+/// - `imu-orientation`, `robot-handling`: see imu
+
+pub mod imu;
+pub mod recording;
+
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Emitter, Manager, State};
+use tokio_tungstenite::tungstenite::Message;
+
+use crate::local_proxy::{auth::LOCAL_TOKEN_QUERY, LocalProxyState};
+use imu::{ImuFeed, Orientation};
+use recording::{Recorder, RecordingFormat, RecordingInfo};
+
+const WS_URL: &str = "ws://localhost:8000/api/state/ws/full";
+const DEFAULT_FREQUENCY_HZ: u32 = 50;
+/// Stream frequency when the IMU feed starts the stream
+const IMU_FREQUENCY_HZ: u32 = 100;
+/// Samples kept in memory (one minute at the default frequency)
+const BUFFER_CAPACITY: usize = 3000;
+const RECONNECT_DELAY: Duration = Duration::from_secs(2);
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TelemetrySample {
+    /// Unix millis when the sample was received (timeline clock)
+    pub t: u64,
+    /// Full state as sent by the daemon
+    pub state: serde_json::Value,
+}
+
+#[derive(Debug, Serialize, Clone)]
+struct StreamStatus {
+    connected: bool,
+    error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+struct ReplayFinished {
+    path: String,
+    samples: usize,
+}
+
+#[derive(Default)]
+pub struct TelemetryState {
+    buffer: Mutex<VecDeque<TelemetrySample>>,
+    stream: Mutex<Option<JoinHandle<()>>>,
+    recorder: Mutex<Option<Recorder>>,
+    replay: Mutex<Option<JoinHandle<()>>>,
+    /// Some while the IMU feed is on
+    imu: Mutex<Option<ImuFeed>>,
+}
+
+impl TelemetryState {
+    fn push(&self, app_handle: &AppHandle, sample: TelemetrySample) {
+        if let Some(feed) = self.imu.lock().unwrap().as_mut() {
+            let (orientation, handling) = feed.update(&sample);
+            if let Some(orientation) = orientation {
+                let _ = app_handle.emit("imu-orientation", orientation);
+            }
+            if let Some(handling) = handling {
+                println!("[telemetry] 🤲 Robot handling: {:?}", handling.kind);
+                crate::timeline::record_event(crate::timeline::Source::Telemetry, "robot-handling", &handling);
+                let _ = app_handle.emit("robot-handling", handling);
+            }
+        }
+        if let Some(recorder) = self.recorder.lock().unwrap().as_mut() {
+            if let Err(e) = recorder.write(&sample) {
+                eprintln!("[telemetry] ⚠️ {}", e);
+            }
+        }
+        let mut buffer = self.buffer.lock().unwrap();
+        buffer.push_back(sample);
+        if buffer.len() > BUFFER_CAPACITY {
+            buffer.pop_front();
+        }
+    }
+
+    /// Buffered samples received between `from` and `to` (unix millis, inclusive)
+    pub fn samples_between(&self, from: u64, to: u64) -> Vec<TelemetrySample> {
+        let buffer = self.buffer.lock().unwrap();
+        buffer.iter().filter(|sample| (from..=to).contains(&sample.t)).cloned().collect()
+    }
+}
+
+// ============================================================================
+// STREAM
+// ============================================================================
+
+async fn stream_url(app_handle: &AppHandle, frequency: u32) -> String {
+    let mut url = format!(
+        "{}?frequency={}&with_head_pose=true&with_head_joints=true&with_body_yaw=true&with_antenna_positions=true&with_doa=true&with_imu=true",
+        WS_URL, frequency
+    );
+    let proxy = app_handle.state::<Arc<LocalProxyState>>();
+    let auth = proxy.auth.read().await;
+    if auth.require_local_token {
+        url.push_str(&format!("&{}={}", LOCAL_TOKEN_QUERY, auth.local_token));
+    }
+    url
+}
+
+fn emit_status(app_handle: &AppHandle, connected: bool, error: Option<String>) {
+    let status = StreamStatus { connected, error };
+    crate::timeline::record_event(crate::timeline::Source::Telemetry, "telemetry-stream-status", &status);
+    let _ = app_handle.emit("telemetry-stream-status", status);
+}
+
+/// Receive samples until the stream is stopped, reconnecting when the daemon goes away
+async fn run_stream(app_handle: AppHandle, frequency: u32) {
+    loop {
+        let url = stream_url(&app_handle, frequency).await;
+        match tokio_tungstenite::connect_async(url.as_str()).await {
+            Ok((mut ws, _)) => {
+                println!("[telemetry] 📡 Connected to daemon state stream ({} Hz)", frequency);
+                emit_status(&app_handle, true, None);
+                while let Some(message) = ws.next().await {
+                    let text = match message {
+                        Ok(Message::Text(text)) => text,
+                        Ok(Message::Close(_)) | Err(_) => break,
+                        Ok(_) => continue,
+                    };
+                    if let Ok(state) = serde_json::from_str(&text) {
+                        app_handle.state::<TelemetryState>().push(&app_handle, TelemetrySample {
+                            t: crate::timeline::now_millis(),
+                            state,
+                        });
+                    }
+                }
+                emit_status(&app_handle, false, Some("Stream closed".to_string()));
+            }
+            Err(e) => emit_status(&app_handle, false, Some(e.to_string())),
+        }
+        tokio::time::sleep(RECONNECT_DELAY).await;
+    }
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Subscribe to the daemon state stream (restarts it if already running)
+#[tauri::command]
+pub fn start_telemetry_stream(app_handle: AppHandle, state: State<TelemetryState>, frequency: Option<u32>) {
+    let frequency = frequency.filter(|&hz| hz > 0).unwrap_or(DEFAULT_FREQUENCY_HZ);
+    let handle = tauri::async_runtime::spawn(run_stream(app_handle, frequency));
+    if let Some(previous) = state.stream.lock().unwrap().replace(handle) {
+        previous.abort();
+    }
+}
+
+#[tauri::command]
+pub fn stop_telemetry_stream(app_handle: AppHandle, state: State<TelemetryState>) {
+    if let Some(handle) = state.stream.lock().unwrap().take() {
+        handle.abort();
+        emit_status(&app_handle, false, None);
+    }
+}
+
+/// Buffered samples, oldest first (the last `limit` ones if given)
+#[tauri::command]
+pub fn get_telemetry_buffer(state: State<TelemetryState>, limit: Option<usize>) -> Vec<TelemetrySample> {
+    let buffer = state.buffer.lock().unwrap();
+    let skip = limit.map(|limit| buffer.len().saturating_sub(limit)).unwrap_or(0);
+    buffer.iter().skip(skip).cloned().collect()
+}
+
+/// Record incoming samples to a file (default: `<app data>/telemetry/session-<millis>.<ext>`)
+#[tauri::command]
+pub fn start_telemetry_recording(
+    app_handle: AppHandle,
+    state: State<TelemetryState>,
+    format: RecordingFormat,
+    path: Option<String>,
+) -> Result<String, String> {
+    let mut recorder = state.recorder.lock().unwrap();
+    if recorder.is_some() {
+        return Err("A recording is already in progress".to_string());
+    }
+    let app_data_dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to resolve app data dir: {}", e))?;
+    let new_recorder = Recorder::create(&app_data_dir, path.map(PathBuf::from), format)?;
+    let path = new_recorder.info().path.clone();
+    *recorder = Some(new_recorder);
+    println!("[telemetry] ⏺️ Recording to {}", path);
+    Ok(path)
+}
+
+#[tauri::command]
+pub fn stop_telemetry_recording(state: State<TelemetryState>) -> Result<RecordingInfo, String> {
+    let recorder = state
+        .recorder
+        .lock()
+        .unwrap()
+        .take()
+        .ok_or("No recording in progress")?;
+    let info = recorder.finish()?;
+    println!("[telemetry] ⏹️ Recorded {} samples to {}", info.samples, info.path);
+    Ok(info)
+}
+
+#[tauri::command]
+pub fn list_telemetry_recordings(app_handle: AppHandle) -> Result<Vec<String>, String> {
+    let app_data_dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to resolve app data dir: {}", e))?;
+    Ok(recording::list_recordings(&app_data_dir))
+}
+
+/// Replay a JSON-lines recording as `telemetry-replay-sample` events (speed 2.0 = twice as fast)
+#[tauri::command]
+pub fn replay_telemetry_recording(
+    app_handle: AppHandle,
+    state: State<TelemetryState>,
+    path: String,
+    speed: Option<f64>,
+) -> Result<usize, String> {
+    let samples = recording::read_jsonl(std::path::Path::new(&path))?;
+    let count = samples.len();
+    let speed = speed.filter(|&s| s > 0.0).unwrap_or(1.0);
+
+    let handle = tauri::async_runtime::spawn(async move {
+        let mut previous_t = samples.first().map(|sample| sample.t).unwrap_or(0);
+        for sample in samples {
+            let gap = sample.t.saturating_sub(previous_t);
+            previous_t = sample.t;
+            tokio::time::sleep(Duration::from_secs_f64(gap as f64 / 1000.0 / speed)).await;
+            let _ = app_handle.emit("telemetry-replay-sample", sample);
+        }
+        let _ = app_handle.emit("telemetry-replay-finished", ReplayFinished { path, samples: count });
+    });
+    if let Some(previous) = state.replay.lock().unwrap().replace(handle) {
+        previous.abort();
+    }
+    Ok(count)
+}
+
+/// Fuse the IMU readings of the stream into an orientation (`imu-orientation` at `emit_hz`,
+/// default 30) and detect handling (`robot-handling`); starts the stream if needed
+#[tauri::command]
+pub fn start_imu_feed(app_handle: AppHandle, state: State<TelemetryState>, emit_hz: Option<u32>) {
+    let emit_hz = emit_hz.filter(|&hz| hz > 0).unwrap_or(imu::DEFAULT_EMIT_HZ);
+    *state.imu.lock().unwrap() = Some(ImuFeed::new(emit_hz));
+    let stream_running = state.stream.lock().unwrap().is_some();
+    if !stream_running {
+        start_telemetry_stream(app_handle, state, Some(IMU_FREQUENCY_HZ));
+    }
+    println!("[telemetry] 🧭 IMU feed on ({} Hz)", emit_hz);
+}
+
+/// Stop the IMU feed (the telemetry stream keeps running)
+#[tauri::command]
+pub fn stop_imu_feed(state: State<TelemetryState>) {
+    if state.imu.lock().unwrap().take().is_some() {
+        println!("[telemetry] ⏹️ IMU feed off");
+    }
+}
+
+/// Latest filtered orientation (None if the feed is off or the robot reports no IMU)
+#[tauri::command]
+pub fn get_imu_orientation(state: State<TelemetryState>) -> Option<Orientation> {
+    state.imu.lock().unwrap().as_ref().and_then(ImuFeed::latest)
+}
+
+/// Take the current heading as yaw 0
+#[tauri::command]
+pub fn reset_imu_yaw(state: State<TelemetryState>) {
+    if let Some(feed) = state.imu.lock().unwrap().as_mut() {
+        feed.reset_yaw();
+    }
+}
+
+#[tauri::command]
+pub fn stop_telemetry_replay(state: State<TelemetryState>) {
+    if let Some(handle) = state.replay.lock().unwrap().take() {
+        handle.abort();
+    }
+}
@@ -0,0 +1,188 @@
+//! Telemetry recording
+//!
+//! Writes samples to `<app data>/telemetry/` as they arrive:
+//! - JSON lines: one `{ "t": <unix millis>, "state": {...} }` object per line, replayable
+//! - CSV: one column per scalar of the state (`head_joints.0`, `body_yaw`, ...), columns
+//!   fixed by the first sample, for spreadsheets and pandas
+
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use super::TelemetrySample;
+
+pub const RECORDINGS_DIR: &str = "telemetry";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum RecordingFormat {
+    Jsonl,
+    Csv,
+}
+
+impl RecordingFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            Self::Jsonl => "jsonl",
+            Self::Csv => "csv",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct RecordingInfo {
+    pub path: String,
+    pub format: RecordingFormat,
+    pub samples: u64,
+    /// Unix millis of the first and last sample
+    pub started_at: Option<u64>,
+    pub ended_at: Option<u64>,
+}
+
+pub struct Recorder {
+    writer: BufWriter<File>,
+    info: RecordingInfo,
+    /// CSV columns, set from the first sample
+    columns: Option<Vec<String>>,
+}
+
+/// Flatten a JSON value into (dotted path, scalar) pairs
+fn flatten(prefix: &str, value: &serde_json::Value, out: &mut Vec<(String, String)>) {
+    let key = |child: &str| if prefix.is_empty() { child.to_string() } else { format!("{}.{}", prefix, child) };
+    match value {
+        serde_json::Value::Object(map) => {
+            for (name, child) in map {
+                flatten(&key(name), child, out);
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for (index, child) in items.iter().enumerate() {
+                flatten(&key(&index.to_string()), child, out);
+            }
+        }
+        serde_json::Value::Null => out.push((prefix.to_string(), String::new())),
+        serde_json::Value::String(s) => out.push((prefix.to_string(), s.clone())),
+        other => out.push((prefix.to_string(), other.to_string())),
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+impl Recorder {
+    /// Create a recording file (named after the current time when no path is given)
+    pub fn create(app_data_dir: &Path, path: Option<PathBuf>, format: RecordingFormat) -> Result<Self, String> {
+        let path = match path {
+            Some(path) => path,
+            None => {
+                let dir = app_data_dir.join(RECORDINGS_DIR);
+                std::fs::create_dir_all(&dir)
+                    .map_err(|e| format!("Failed to create recordings dir: {}", e))?;
+                dir.join(format!("session-{}.{}", crate::daemon::history::now_millis(), format.extension()))
+            }
+        };
+        let file = File::create(&path).map_err(|e| format!("Failed to create {:?}: {}", path, e))?;
+        Ok(Self {
+            writer: BufWriter::new(file),
+            info: RecordingInfo {
+                path: path.to_string_lossy().into_owned(),
+                format,
+                samples: 0,
+                started_at: None,
+                ended_at: None,
+            },
+            columns: None,
+        })
+    }
+
+    pub fn info(&self) -> &RecordingInfo {
+        &self.info
+    }
+
+    pub fn write(&mut self, sample: &TelemetrySample) -> Result<(), String> {
+        let result = match self.info.format {
+            RecordingFormat::Jsonl => serde_json::to_string(sample)
+                .map_err(std::io::Error::other)
+                .and_then(|line| writeln!(self.writer, "{}", line)),
+            RecordingFormat::Csv => self.write_csv(sample),
+        };
+        result.map_err(|e| format!("Failed to write telemetry sample: {}", e))?;
+
+        self.info.samples += 1;
+        self.info.started_at.get_or_insert(sample.t);
+        self.info.ended_at = Some(sample.t);
+        Ok(())
+    }
+
+    fn write_csv(&mut self, sample: &TelemetrySample) -> std::io::Result<()> {
+        let mut fields = Vec::new();
+        flatten("", &sample.state, &mut fields);
+
+        let columns = match &self.columns {
+            Some(columns) => columns,
+            None => {
+                let columns: Vec<String> = fields.iter().map(|(name, _)| name.clone()).collect();
+                let header: Vec<String> = std::iter::once("t".to_string())
+                    .chain(columns.iter().map(|name| csv_field(name)))
+                    .collect();
+                writeln!(self.writer, "{}", header.join(","))?;
+                self.columns.insert(columns)
+            }
+        };
+
+        let row: Vec<String> = std::iter::once(sample.t.to_string())
+            .chain(columns.iter().map(|column| {
+                fields
+                    .iter()
+                    .find(|(name, _)| name == column)
+                    .map(|(_, value)| csv_field(value))
+                    .unwrap_or_default()
+            }))
+            .collect();
+        writeln!(self.writer, "{}", row.join(","))
+    }
+
+    pub fn finish(mut self) -> Result<RecordingInfo, String> {
+        self.writer
+            .flush()
+            .map_err(|e| format!("Failed to flush recording: {}", e))?;
+        Ok(self.info)
+    }
+}
+
+/// Recordings in `<app data>/telemetry/`, newest first
+pub fn list_recordings(app_data_dir: &Path) -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(app_data_dir.join(RECORDINGS_DIR)) else {
+        return Vec::new();
+    };
+    let mut files: Vec<(std::time::SystemTime, String)> = entries
+        .flatten()
+        .filter(|entry| entry.path().is_file())
+        .map(|entry| {
+            let modified = entry.metadata().and_then(|m| m.modified()).unwrap_or(std::time::UNIX_EPOCH);
+            (modified, entry.path().to_string_lossy().into_owned())
+        })
+        .collect();
+    files.sort_by_key(|(modified, _)| std::cmp::Reverse(*modified));
+    files.into_iter().map(|(_, path)| path).collect()
+}
+
+/// Samples of a JSON-lines recording (CSV flattens the state and can't be replayed)
+pub fn read_jsonl(path: &Path) -> Result<Vec<TelemetrySample>, String> {
+    if path.extension().and_then(|ext| ext.to_str()) != Some("jsonl") {
+        return Err("Only JSON-lines recordings can be replayed".to_string());
+    }
+    let file = File::open(path).map_err(|e| format!("Failed to open {:?}: {}", path, e))?;
+    BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| serde_json::from_str(&line).map_err(|e| format!("Invalid recording line: {}", e)))
+        .collect()
+}
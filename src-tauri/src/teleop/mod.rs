@@ -0,0 +1,269 @@
+/// Keyboard teleoperation
+///
+/// Arrow keys / WASD turn the head (Q / E roll it) while teleop runs. Keys come from
+/// global shortcuts (see shortcuts), registered while the main window is focused or all
+/// the time with the `teleop.global_shortcuts` setting, or are forwarded by the webview
+/// (`teleop_key`, refreshed by key auto-repeat).
+///
+/// Held keys move a head pose target at `teleop.speed_deg_s`, sent to the daemon at most
+/// every `SEND_INTERVAL` and only when it changed, one request at a time. Releasing every
+/// key brings the head back to center (dead-man) unless `teleop.recenter_on_release` is off.
+
+pub mod shortcuts;
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager, State};
+
+use crate::daemon_api::{DaemonClient, HeadPose, MoveTarget};
+use crate::local_proxy::LocalProxyState;
+use crate::settings::{SettingsState, TeleopSettings};
+
+/// 20 Hz at most
+const SEND_INTERVAL: Duration = Duration::from_millis(50);
+const SET_TARGET_TIMEOUT: Duration = Duration::from_millis(500);
+/// A key forwarded by the webview is released if not repeated within this delay
+const WINDOW_KEY_TIMEOUT: Duration = Duration::from_secs(1);
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum TeleopKey {
+    Up,
+    Down,
+    Left,
+    Right,
+    RollLeft,
+    RollRight,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum KeySource {
+    Shortcut,
+    Window,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct TeleopStatus {
+    pub active: bool,
+    pub pose: HeadPose,
+    pub held_keys: Vec<TeleopKey>,
+}
+
+#[derive(Default)]
+pub struct TeleopState {
+    task: Mutex<Option<tauri::async_runtime::JoinHandle<()>>>,
+    held: Mutex<HashMap<TeleopKey, (KeySource, Instant)>>,
+    pose: Mutex<HeadPose>,
+}
+
+// ============================================================================
+// HELPER FUNCTIONS
+// ============================================================================
+
+impl TeleopKey {
+    /// (pitch, yaw, roll) direction: negative pitch looks up, positive yaw turns left
+    fn direction(self) -> (f64, f64, f64) {
+        match self {
+            Self::Up => (-1.0, 0.0, 0.0),
+            Self::Down => (1.0, 0.0, 0.0),
+            Self::Left => (0.0, 1.0, 0.0),
+            Self::Right => (0.0, -1.0, 0.0),
+            Self::RollLeft => (0.0, 0.0, -1.0),
+            Self::RollRight => (0.0, 0.0, 1.0),
+        }
+    }
+}
+
+fn toward_zero(value: f64, step: f64) -> f64 {
+    if value.abs() <= step {
+        0.0
+    } else {
+        value - step * value.signum()
+    }
+}
+
+impl TeleopState {
+    fn is_active(&self) -> bool {
+        self.task.lock().unwrap().is_some()
+    }
+
+    fn status(&self) -> TeleopStatus {
+        TeleopStatus {
+            active: self.is_active(),
+            pose: *self.pose.lock().unwrap(),
+            held_keys: self.held.lock().unwrap().keys().copied().collect(),
+        }
+    }
+
+    fn release_all(&self) {
+        self.held.lock().unwrap().clear();
+    }
+
+    /// Advance the target by `dt` seconds of the held keys
+    fn step(&self, settings: &TeleopSettings, dt: f64) -> HeadPose {
+        let now = Instant::now();
+        let mut held = self.held.lock().unwrap();
+        held.retain(|_, (source, seen)| *source == KeySource::Shortcut || now - *seen < WINDOW_KEY_TIMEOUT);
+
+        let step = settings.speed_deg_s.to_radians() * dt;
+        let mut pose = self.pose.lock().unwrap();
+        if held.is_empty() {
+            if settings.recenter_on_release {
+                pose.pitch = toward_zero(pose.pitch, step);
+                pose.yaw = toward_zero(pose.yaw, step);
+                pose.roll = toward_zero(pose.roll, step);
+            }
+        } else {
+            for key in held.keys() {
+                let (pitch, yaw, roll) = key.direction();
+                pose.pitch += pitch * step;
+                pose.yaw += yaw * step;
+                pose.roll += roll * step;
+            }
+            *pose = pose.clamped();
+        }
+        *pose
+    }
+}
+
+/// Press or release a key (ignored while teleop is stopped)
+pub fn set_key(app_handle: &AppHandle, key: TeleopKey, source: KeySource, pressed: bool) {
+    let state = app_handle.state::<TeleopState>();
+    if !state.is_active() {
+        return;
+    }
+    let mut held = state.held.lock().unwrap();
+    if pressed {
+        held.insert(key, (source, Instant::now()));
+    } else {
+        held.remove(&key);
+    }
+}
+
+/// Stream the target to the daemon until the task is aborted
+async fn run(app_handle: AppHandle) {
+    let proxy = app_handle.state::<Arc<LocalProxyState>>();
+    let client = DaemonClient::for_proxy(&proxy).await.with_timeout(SET_TARGET_TIMEOUT);
+    let mut interval = tokio::time::interval(SEND_INTERVAL);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    let mut last_sent: Option<HeadPose> = None;
+    let mut failing = false;
+
+    loop {
+        interval.tick().await;
+        let settings = app_handle.state::<SettingsState>().get().teleop;
+        let pose = app_handle.state::<TeleopState>().step(&settings, SEND_INTERVAL.as_secs_f64());
+        if last_sent == Some(pose) {
+            continue;
+        }
+
+        let target = MoveTarget {
+            target_head_pose: Some(pose),
+            ..Default::default()
+        };
+        match client.set_target(&target).await {
+            Ok(()) => {
+                if failing {
+                    println!("[teleop] ✅ Daemon reachable again");
+                }
+                failing = false;
+                last_sent = Some(pose);
+            }
+            Err(e) => {
+                // Logged once per outage, retried at the next tick
+                if !failing {
+                    eprintln!("[teleop] ⚠️ {}", e);
+                }
+                failing = true;
+            }
+        }
+    }
+}
+
+/// Register or drop the key shortcuts with the main window focus (unless global)
+pub fn on_focus_changed(app_handle: &AppHandle, focused: bool) {
+    let state = app_handle.state::<TeleopState>();
+    if !state.is_active() || app_handle.state::<SettingsState>().get().teleop.global_shortcuts {
+        return;
+    }
+    let result = if focused {
+        shortcuts::register(app_handle)
+    } else {
+        state.release_all();
+        shortcuts::unregister(app_handle)
+    };
+    if let Err(e) = result {
+        eprintln!("[teleop] ⚠️ {}", e);
+    }
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Start keyboard teleoperation (head target starts centered)
+#[tauri::command]
+pub fn start_teleop(app_handle: AppHandle, state: State<TeleopState>) -> Result<TeleopStatus, String> {
+    let mut task = state.task.lock().unwrap();
+    if task.is_none() {
+        *state.pose.lock().unwrap() = HeadPose::default();
+        state.release_all();
+        *task = Some(tauri::async_runtime::spawn(run(app_handle.clone())));
+        println!("[teleop] 🎮 Keyboard teleop started");
+    }
+    drop(task);
+
+    let global = app_handle.state::<SettingsState>().get().teleop.global_shortcuts;
+    let focused = app_handle
+        .get_webview_window("main")
+        .and_then(|window| window.is_focused().ok())
+        .unwrap_or(false);
+    if global || focused {
+        if let Err(e) = shortcuts::register(&app_handle) {
+            eprintln!("[teleop] ⚠️ {}", e);
+        }
+    }
+    Ok(state.status())
+}
+
+/// Stop teleoperation, recentering the head if `recenter_on_release` is on
+#[tauri::command]
+pub async fn stop_teleop(app_handle: AppHandle, proxy: State<'_, Arc<LocalProxyState>>) -> Result<(), String> {
+    let state = app_handle.state::<TeleopState>();
+    let Some(task) = state.task.lock().unwrap().take() else {
+        return Ok(());
+    };
+    task.abort();
+    state.release_all();
+    if let Err(e) = shortcuts::unregister(&app_handle) {
+        eprintln!("[teleop] ⚠️ {}", e);
+    }
+    println!("[teleop] ⏹️ Keyboard teleop stopped");
+
+    if app_handle.state::<SettingsState>().get().teleop.recenter_on_release {
+        *state.pose.lock().unwrap() = HeadPose::default();
+        let target = MoveTarget {
+            target_head_pose: Some(HeadPose::default()),
+            ..Default::default()
+        };
+        DaemonClient::for_proxy(&proxy).await.set_target(&target).await?;
+    }
+    Ok(())
+}
+
+/// Key event forwarded by the webview (keydown repeats keep the key held)
+#[tauri::command]
+pub fn teleop_key(app_handle: AppHandle, key: TeleopKey, pressed: bool) {
+    set_key(&app_handle, key, KeySource::Window, pressed);
+}
+
+#[tauri::command]
+pub fn get_teleop_status(state: State<TeleopState>) -> TeleopStatus {
+    state.status()
+}
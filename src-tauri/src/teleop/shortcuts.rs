@@ -0,0 +1,55 @@
+//! Teleop key shortcuts
+//!
+//! Arrow keys, WASD and Q / E as global shortcuts (pressed and released events, so a
+//! held key keeps the head moving). Only registered while teleop needs them: other apps
+//! get these keys back as soon as they are unregistered.
+
+use tauri::AppHandle;
+use tauri_plugin_global_shortcut::{Code, GlobalShortcutExt, Shortcut, ShortcutState};
+
+use super::{KeySource, TeleopKey};
+
+const BINDINGS: &[(Code, TeleopKey)] = &[
+    (Code::ArrowUp, TeleopKey::Up),
+    (Code::KeyW, TeleopKey::Up),
+    (Code::ArrowDown, TeleopKey::Down),
+    (Code::KeyS, TeleopKey::Down),
+    (Code::ArrowLeft, TeleopKey::Left),
+    (Code::KeyA, TeleopKey::Left),
+    (Code::ArrowRight, TeleopKey::Right),
+    (Code::KeyD, TeleopKey::Right),
+    (Code::KeyQ, TeleopKey::RollLeft),
+    (Code::KeyE, TeleopKey::RollRight),
+];
+
+fn shortcuts() -> Vec<Shortcut> {
+    BINDINGS.iter().map(|(code, _)| Shortcut::new(None, *code)).collect()
+}
+
+pub fn register(app_handle: &AppHandle) -> Result<(), String> {
+    let global_shortcut = app_handle.global_shortcut();
+    if shortcuts().iter().all(|shortcut| global_shortcut.is_registered(*shortcut)) {
+        return Ok(());
+    }
+    global_shortcut
+        .on_shortcuts(shortcuts(), |app_handle, shortcut, event| {
+            if let Some((_, key)) = BINDINGS.iter().find(|(code, _)| shortcut.key == *code && shortcut.mods.is_empty()) {
+                super::set_key(app_handle, *key, KeySource::Shortcut, event.state() == ShortcutState::Pressed);
+            }
+        })
+        .map_err(|e| format!("Failed to register teleop keys: {}", e))
+}
+
+pub fn unregister(app_handle: &AppHandle) -> Result<(), String> {
+    let global_shortcut = app_handle.global_shortcut();
+    let registered: Vec<Shortcut> = shortcuts()
+        .into_iter()
+        .filter(|shortcut| global_shortcut.is_registered(*shortcut))
+        .collect();
+    if registered.is_empty() {
+        return Ok(());
+    }
+    global_shortcut
+        .unregister_multiple(registered)
+        .map_err(|e| format!("Failed to unregister teleop keys: {}", e))
+}
@@ -0,0 +1,187 @@
+/// Correlated timeline of app logs, daemon logs and telemetry events
+///
+/// Everything that happens is stamped with one clock: unix millis anchored at app start
+/// and advanced by a monotonic clock, so entries from every source stay ordered even if
+/// the system clock jumps (NTP sync, sleep). Telemetry samples and app logs use the same
+/// clock (`now_millis`); daemon output carries no usable timestamp and is stamped when
+/// its line is received.
+///
+/// The last `CAPACITY` entries are kept in memory. `query_timeline` returns the entries
+/// of a window (and optionally the telemetry samples of that window), for the "what
+/// happened at 14:32:07" debugging view.
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::{Mutex, OnceLock};
+use std::time::Instant;
+use tauri::State;
+
+use crate::telemetry::{TelemetrySample, TelemetryState};
+
+const CAPACITY: usize = 5000;
+/// Widest window a query may ask for
+const MAX_WINDOW_MS: u64 = 60 * 60 * 1000;
+const DEFAULT_QUERY_LIMIT: usize = 1000;
+
+static CLOCK: OnceLock<(Instant, u64)> = OnceLock::new();
+static TIMELINE: Mutex<Timeline> = Mutex::new(Timeline {
+    entries: VecDeque::new(),
+    next_seq: 0,
+});
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Source {
+    App,
+    Daemon,
+    Telemetry,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct TimelineEntry {
+    /// Unix millis on the timeline clock
+    pub t: u64,
+    /// Insertion order (ties on `t`)
+    pub seq: u64,
+    pub source: Source,
+    /// "debug", "info", "warning" or "error" when known
+    pub level: Option<&'static str>,
+    pub message: String,
+    /// Structured payload (e.g. the event emitted to the frontend)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct TimelineWindow {
+    pub from: u64,
+    pub to: u64,
+    pub entries: Vec<TimelineEntry>,
+    /// More entries matched than the limit (the latest ones were dropped)
+    pub truncated: bool,
+    /// Telemetry samples of the window, when asked for
+    pub samples: Vec<TelemetrySample>,
+    /// Timeline clock when queried
+    pub now: u64,
+}
+
+struct Timeline {
+    entries: VecDeque<TimelineEntry>,
+    next_seq: u64,
+}
+
+// ============================================================================
+// HELPER FUNCTIONS
+// ============================================================================
+
+/// Level of a log line from its usual prefixes ("ERROR:root:...", "[WARNING] ...", "INFO - ...")
+fn detect_level(line: &str) -> Option<&'static str> {
+    let head: String = line.chars().take(40).collect::<String>().to_uppercase();
+    [
+        ("CRITICAL", "error"),
+        ("ERROR", "error"),
+        ("TRACEBACK", "error"),
+        ("WARNING", "warning"),
+        ("WARN", "warning"),
+        ("INFO", "info"),
+        ("DEBUG", "debug"),
+    ]
+    .iter()
+    .find(|(marker, _)| head.contains(marker))
+    .map(|(_, level)| *level)
+}
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// Current time on the timeline clock (unix millis, never goes backwards)
+pub fn now_millis() -> u64 {
+    let (anchor, anchor_millis) = CLOCK.get_or_init(|| (Instant::now(), crate::daemon::history::now_millis()));
+    anchor_millis + anchor.elapsed().as_millis() as u64
+}
+
+/// Add an entry stamped now
+pub fn record(source: Source, level: Option<&'static str>, message: impl Into<String>, data: Option<serde_json::Value>) {
+    let t = now_millis();
+    let mut timeline = TIMELINE.lock().unwrap();
+    let seq = timeline.next_seq;
+    timeline.next_seq += 1;
+    timeline.entries.push_back(TimelineEntry {
+        t,
+        seq,
+        source,
+        level,
+        message: message.into(),
+        data,
+    });
+    if timeline.entries.len() > CAPACITY {
+        timeline.entries.pop_front();
+    }
+}
+
+/// Add a raw log line, its level guessed from its prefix
+pub fn record_line(source: Source, line: &str) {
+    record(source, detect_level(line), line.trim_end(), None);
+}
+
+/// Add an event emitted to the frontend, with its payload
+pub fn record_event(source: Source, name: &str, payload: &impl Serialize) {
+    record(source, None, name, serde_json::to_value(payload).ok());
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Entries between `from` and `to` (timeline millis, inclusive), oldest first
+///
+/// `sources` None = every source; `include_samples` adds the telemetry samples still in
+/// the telemetry buffer for that window.
+#[tauri::command]
+pub fn query_timeline(
+    telemetry: State<TelemetryState>,
+    from: u64,
+    to: u64,
+    sources: Option<Vec<Source>>,
+    include_samples: Option<bool>,
+    limit: Option<usize>,
+) -> Result<TimelineWindow, String> {
+    if to < from {
+        return Err("The end of the window is before its start".to_string());
+    }
+    if to - from > MAX_WINDOW_MS {
+        return Err(format!("Timeline window too wide (at most {} minutes)", MAX_WINDOW_MS / 60_000));
+    }
+    let limit = limit.unwrap_or(DEFAULT_QUERY_LIMIT).max(1);
+
+    let mut entries: Vec<TimelineEntry> = TIMELINE
+        .lock()
+        .unwrap()
+        .entries
+        .iter()
+        .filter(|entry| (from..=to).contains(&entry.t))
+        .filter(|entry| sources.as_ref().is_none_or(|sources| sources.contains(&entry.source)))
+        .cloned()
+        .collect();
+    entries.sort_by_key(|entry| (entry.t, entry.seq));
+    let truncated = entries.len() > limit;
+    entries.truncate(limit);
+
+    let samples = if include_samples.unwrap_or(false) {
+        telemetry.samples_between(from, to)
+    } else {
+        Vec::new()
+    };
+    Ok(TimelineWindow { from, to, entries, truncated, samples, now: now_millis() })
+}
+
+/// Current time on the timeline clock, to turn a wall-clock time picked in the UI into a window
+#[tauri::command]
+pub fn get_timeline_clock() -> u64 {
+    now_millis()
+}
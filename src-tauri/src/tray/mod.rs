@@ -0,0 +1,231 @@
+/// System tray module
+///
+/// Tray icon with the connection status, the current robot name and quick actions
+/// (show window, start / stop daemon, emergency stop, quit, quit and power down the
+/// robot), so the app can keep running out of the dock / taskbar (long-running demos).
+///
+/// With the `minimize_to_tray` setting, closing the main window hides it instead of
+/// killing the daemon; quitting from the tray (or ⌘Q) still cleans up.
+/// The menu follows `connection-state-changed` and `locale-changed` (labels come from the
+/// i18n catalog).
+
+use tauri::menu::{Menu, MenuEvent, MenuItem, PredefinedMenuItem};
+use tauri::tray::TrayIconBuilder;
+use tauri::{AppHandle, Listener, Manager, Wry};
+
+use crate::connection::{ConnectionManager, ConnectionState};
+use crate::daemon_api::DaemonClient;
+use crate::i18n::{tr, tr_with};
+use crate::local_proxy::LocalProxyState;
+
+const TRAY_ID: &str = "main";
+
+/// Menu items updated with the connection state and the language
+pub struct TrayState {
+    status: MenuItem<Wry>,
+    robot: MenuItem<Wry>,
+    show: MenuItem<Wry>,
+    start_daemon: MenuItem<Wry>,
+    stop_daemon: MenuItem<Wry>,
+    emergency_stop: MenuItem<Wry>,
+    quit: MenuItem<Wry>,
+    power_down: MenuItem<Wry>,
+}
+
+// ============================================================================
+// HELPER FUNCTIONS
+// ============================================================================
+
+fn status_label(state: &ConnectionState) -> String {
+    match state {
+        ConnectionState::Disconnected => tr("tray.status.disconnected"),
+        ConnectionState::UsbDetected { .. } => tr("tray.status.usb_detected"),
+        ConnectionState::DaemonStarting { .. } => tr("tray.status.daemon_starting"),
+        ConnectionState::Ready { sim_mode: true, .. } => tr("tray.status.ready_sim"),
+        ConnectionState::Ready { .. } => tr("tray.status.ready"),
+        ConnectionState::WifiMode { degraded: false, .. } => tr("tray.status.wifi"),
+        ConnectionState::WifiMode { degraded: true, .. } => tr("tray.status.wifi_degraded"),
+        ConnectionState::Error { message } => tr_with("tray.status.error", &[("message", message)]),
+    }
+}
+
+/// Nickname from the robot registry, else serial number / port / host
+fn robot_name(app_handle: &AppHandle, state: &ConnectionState) -> Option<String> {
+    let usb_name = |serial_number: Option<&str>, port: &str| {
+        let nickname = serial_number.and_then(|serial| {
+            app_handle
+                .state::<crate::usb::registry::RobotRegistryState>()
+                .nickname(serial)
+        });
+        nickname.unwrap_or_else(|| match serial_number {
+            Some(serial) => tr_with("tray.robot.serial", &[("serial", serial)]),
+            None => tr_with("tray.robot.port", &[("port", port)]),
+        })
+    };
+
+    match state {
+        ConnectionState::UsbDetected { port, serial_number } => Some(usb_name(serial_number.as_deref(), port)),
+        ConnectionState::DaemonStarting { sim_mode: true, .. } | ConnectionState::Ready { sim_mode: true, .. } => {
+            Some(tr("tray.robot.simulation"))
+        }
+        ConnectionState::DaemonStarting { port: Some(port), .. } | ConnectionState::Ready { port: Some(port), .. } => {
+            let serial_number = crate::usb::get_reachy_robots()
+                .into_iter()
+                .find(|robot| &robot.port_name == port)
+                .and_then(|robot| robot.serial_number);
+            Some(usb_name(serial_number.as_deref(), port))
+        }
+        ConnectionState::WifiMode { host, .. } => Some(host.clone()),
+        _ => None,
+    }
+}
+
+/// Update the menu and tooltip from the current connection state and language
+fn refresh(app_handle: &AppHandle) {
+    let Some(tray_state) = app_handle.try_state::<TrayState>() else {
+        return;
+    };
+    let state = app_handle.state::<ConnectionManager>().get();
+    let status = status_label(&state);
+    let robot = robot_name(app_handle, &state);
+
+    let daemon_active = matches!(state, ConnectionState::DaemonStarting { .. } | ConnectionState::Ready { .. });
+    let robot_reachable = matches!(state, ConnectionState::Ready { sim_mode: false, .. } | ConnectionState::WifiMode { .. });
+
+    let _ = tray_state.status.set_text(&status);
+    let _ = tray_state.robot.set_text(robot.clone().unwrap_or_else(|| tr("tray.robot.none")));
+    let _ = tray_state.show.set_text(tr("tray.show"));
+    let _ = tray_state.start_daemon.set_text(tr("tray.start_daemon"));
+    let _ = tray_state.stop_daemon.set_text(tr("tray.stop_daemon"));
+    let _ = tray_state.emergency_stop.set_text(tr("tray.emergency_stop"));
+    let _ = tray_state.quit.set_text(tr("tray.quit"));
+    let _ = tray_state.power_down.set_text(tr("tray.power_down"));
+    let _ = tray_state.start_daemon.set_enabled(!daemon_active && !matches!(state, ConnectionState::WifiMode { .. }));
+    let _ = tray_state.stop_daemon.set_enabled(daemon_active);
+    let _ = tray_state.power_down.set_enabled(robot_reachable);
+
+    if let Some(tray) = app_handle.tray_by_id(TRAY_ID) {
+        let tooltip = match &robot {
+            Some(robot) => format!("Reachy Mini Control - {} ({})", robot, status),
+            None => format!("Reachy Mini Control - {}", status),
+        };
+        let _ = tray.set_tooltip(Some(tooltip));
+    }
+}
+
+pub fn show_main_window(app_handle: &AppHandle) {
+    #[cfg(target_os = "macos")]
+    let _ = app_handle.set_activation_policy(tauri::ActivationPolicy::Regular);
+
+    if let Some(window) = app_handle.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.unminimize();
+        let _ = window.set_focus();
+    }
+}
+
+/// Hide the main window, and the app from the dock (macOS) / taskbar
+pub fn hide_main_window(app_handle: &AppHandle) {
+    if let Some(window) = app_handle.get_webview_window("main") {
+        let _ = window.hide();
+    }
+
+    #[cfg(target_os = "macos")]
+    let _ = app_handle.set_activation_policy(tauri::ActivationPolicy::Accessory);
+}
+
+/// The tray icon exists (closing the window can hide it instead of quitting)
+pub fn is_available(app_handle: &AppHandle) -> bool {
+    app_handle.tray_by_id(TRAY_ID).is_some()
+}
+
+/// Put the robot to sleep and stop its backend, then quit
+fn quit_and_power_down(app_handle: &AppHandle) {
+    let app_handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        let proxy = app_handle.state::<std::sync::Arc<LocalProxyState>>();
+        let client = DaemonClient::for_proxy(&proxy).await;
+        println!("[tray] 💤 Powering down robot before quitting");
+        if let Err(e) = client.stop_daemon(true).await {
+            eprintln!("[tray] ⚠️ Failed to power down robot: {}", e);
+        }
+        crate::daemon::park::mark_done();
+        app_handle.exit(0);
+    });
+}
+
+fn on_menu_event(app_handle: &AppHandle, event: MenuEvent) {
+    match event.id.as_ref() {
+        "show" => show_main_window(app_handle),
+        "start_daemon" => {
+            if let Err(e) = crate::start_daemon(app_handle.clone(), app_handle.state(), None, None, None) {
+                eprintln!("[tray] ❌ Failed to start daemon: {}", e);
+            }
+        }
+        "stop_daemon" => {
+            let _ = crate::stop_daemon(app_handle.state());
+        }
+        "emergency_stop" => {
+            let app_handle = app_handle.clone();
+            tauri::async_runtime::spawn(async move {
+                crate::emergency::stop(&app_handle).await;
+            });
+        }
+        "power_down" => quit_and_power_down(app_handle),
+        "quit" => app_handle.exit(0),
+        _ => {}
+    }
+}
+
+// ============================================================================
+// SETUP
+// ============================================================================
+
+/// Create the tray icon (call once in setup)
+pub fn init(app_handle: &AppHandle) -> tauri::Result<()> {
+    // Labels are set by refresh()
+    let tray_state = TrayState {
+        status: MenuItem::with_id(app_handle, "status", "", false, None::<&str>)?,
+        robot: MenuItem::with_id(app_handle, "robot", "", false, None::<&str>)?,
+        show: MenuItem::with_id(app_handle, "show", "", true, None::<&str>)?,
+        start_daemon: MenuItem::with_id(app_handle, "start_daemon", "", true, None::<&str>)?,
+        stop_daemon: MenuItem::with_id(app_handle, "stop_daemon", "", false, None::<&str>)?,
+        emergency_stop: MenuItem::with_id(app_handle, "emergency_stop", "", true, None::<&str>)?,
+        quit: MenuItem::with_id(app_handle, "quit", "", true, None::<&str>)?,
+        power_down: MenuItem::with_id(app_handle, "power_down", "", false, None::<&str>)?,
+    };
+
+    let menu = Menu::with_items(
+        app_handle,
+        &[
+            &tray_state.robot,
+            &tray_state.status,
+            &PredefinedMenuItem::separator(app_handle)?,
+            &tray_state.show,
+            &tray_state.start_daemon,
+            &tray_state.stop_daemon,
+            &tray_state.emergency_stop,
+            &PredefinedMenuItem::separator(app_handle)?,
+            &tray_state.quit,
+            &tray_state.power_down,
+        ],
+    )?;
+
+    let mut builder = TrayIconBuilder::with_id(TRAY_ID)
+        .menu(&menu)
+        .tooltip("Reachy Mini Control")
+        .on_menu_event(on_menu_event);
+    if let Some(icon) = app_handle.default_window_icon() {
+        builder = builder.icon(icon.clone());
+    }
+    builder.build(app_handle)?;
+
+    app_handle.manage(tray_state);
+    refresh(app_handle);
+
+    let handle = app_handle.clone();
+    app_handle.listen_any("connection-state-changed", move |_| refresh(&handle));
+    let handle = app_handle.clone();
+    app_handle.listen_any("locale-changed", move |_| refresh(&handle));
+    Ok(())
+}
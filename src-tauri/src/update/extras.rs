@@ -0,0 +1,237 @@
+/// Optional extras of the reachy-mini package (e.g. `reachy-mini[mujoco]`)
+///
+/// pip doesn't record which extras were requested, so installed extras are detected
+/// from the reachy-mini METADATA: an extra counts as installed when every package it
+/// requires is present in site-packages. This also picks up extras pre-bundled in the venv.
+
+use serde::Serialize;
+use std::collections::HashSet;
+use std::path::Path;
+use tauri::{AppHandle, Manager, State};
+
+use crate::daemon::DaemonState;
+
+#[derive(Debug, Serialize, Clone)]
+pub struct DaemonExtra {
+    pub name: String,
+    pub installed: bool,
+    /// Packages pulled in by this extra
+    pub packages: Vec<String>,
+}
+
+/// Normalize a distribution name (PEP 503): "Foo.Bar-baz" -> "foo_bar_baz"
+fn normalize_name(name: &str) -> String {
+    name.to_lowercase().replace(['-', '.'], "_")
+}
+
+/// Parse a "Requires-Dist:" value into (package name, extra it belongs to)
+/// e.g. `mujoco>=3.0; extra == "mujoco"` -> ("mujoco", Some("mujoco"))
+fn parse_requirement(value: &str) -> Option<(String, Option<String>)> {
+    let (requirement, marker) = match value.split_once(';') {
+        Some((requirement, marker)) => (requirement, Some(marker)),
+        None => (value, None),
+    };
+    let name: String = requirement
+        .trim()
+        .chars()
+        .take_while(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        .collect();
+    if name.is_empty() {
+        return None;
+    }
+
+    let extra = marker.and_then(|marker| {
+        let rest = marker.split("extra ==").nth(1)?;
+        let rest = rest.trim_start().trim_start_matches(['"', '\'']);
+        let end = rest.find(['"', '\''])?;
+        Some(rest[..end].to_string())
+    });
+
+    Some((normalize_name(&name), extra))
+}
+
+/// Names of all distributions installed in site-packages (normalized)
+fn installed_packages(venv_path: &Path) -> Result<HashSet<String>, String> {
+    let site_packages = super::get_site_packages(venv_path)?;
+    let entries = std::fs::read_dir(&site_packages)
+        .map_err(|e| format!("Failed to read site-packages: {}", e))?;
+
+    Ok(entries
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let dist = name.strip_suffix(".dist-info")?;
+            dist.split('-').next().map(normalize_name)
+        })
+        .collect())
+}
+
+/// Base requirements of reachy-mini and the extras it declares
+fn read_extras(venv_path: &Path) -> Result<(Vec<String>, Vec<DaemonExtra>), String> {
+    let metadata = super::read_daemon_metadata(venv_path)?;
+    let installed = installed_packages(venv_path)?;
+
+    let mut base = Vec::new();
+    let mut extras: Vec<DaemonExtra> = Vec::new();
+    for line in metadata.lines() {
+        if let Some(name) = line.strip_prefix("Provides-Extra:") {
+            extras.push(DaemonExtra {
+                name: name.trim().to_string(),
+                installed: false,
+                packages: Vec::new(),
+            });
+        } else if let Some(value) = line.strip_prefix("Requires-Dist:") {
+            match parse_requirement(value) {
+                Some((package, None)) => base.push(package),
+                Some((package, Some(extra))) => {
+                    if let Some(entry) = extras.iter_mut().find(|e| e.name == extra) {
+                        entry.packages.push(package);
+                    }
+                }
+                None => {}
+            }
+        }
+    }
+
+    for extra in extras.iter_mut() {
+        extra.installed = !extra.packages.is_empty()
+            && extra.packages.iter().all(|package| installed.contains(package));
+    }
+
+    Ok((base, extras))
+}
+
+/// Names of the extras currently installed (used by updates to keep them)
+pub(super) fn get_installed_extras(venv_path: &Path) -> Vec<String> {
+    read_extras(venv_path)
+        .map(|(_, extras)| extras.into_iter().filter(|e| e.installed).map(|e| e.name).collect())
+        .unwrap_or_default()
+}
+
+/// Requirement spec with extras, e.g. "reachy-mini[mujoco,vision]==1.2.0"
+pub(super) fn requirement_with_extras(version: &str, extras: &[String]) -> String {
+    if extras.is_empty() {
+        format!("reachy-mini=={}", version)
+    } else {
+        format!("reachy-mini[{}]=={}", extras.join(","), version)
+    }
+}
+
+/// Local artifact with extras, e.g. "/path/reachy_mini-1.2.0-py3-none-any.whl[mujoco]"
+pub(super) fn artifact_with_extras(path: &Path, extras: &[String]) -> String {
+    if extras.is_empty() {
+        path.to_string_lossy().to_string()
+    } else {
+        format!("{}[{}]", path.to_string_lossy(), extras.join(","))
+    }
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// List the optional extras declared by reachy-mini and whether they're installed
+#[tauri::command]
+pub fn list_daemon_extras(app_handle: AppHandle) -> Result<Vec<DaemonExtra>, String> {
+    let venv_path = super::get_local_venv_path(&app_handle)?;
+    read_extras(&venv_path).map(|(_, extras)| extras)
+}
+
+/// Install an optional extra for the current reachy-mini version
+#[tauri::command]
+pub async fn add_daemon_extra(
+    app_handle: AppHandle,
+    state: State<'_, DaemonState>,
+    extra: String,
+) -> Result<String, String> {
+    crate::kiosk::ensure_allowed("Changing the daemon extras")?;
+    crate::lock::ensure_unlocked("Changing the daemon extras")?;
+    let venv_path = super::get_local_venv_path(&app_handle)?;
+    let (_, extras) = read_extras(&venv_path)?;
+    if !extras.iter().any(|e| e.name == extra) {
+        return Err(format!("Unknown extra: {}", extra));
+    }
+
+    let version = super::get_local_daemon_version(&venv_path)?;
+    println!("[update] Adding extra [{}] to reachy-mini {}", extra, version);
+
+    // Same as updates: only files whose published hash was checked are installed
+    let requirement = requirement_with_extras(&version, std::slice::from_ref(&extra));
+    let pip_path = super::get_pip_path(&venv_path)?;
+    let wheelhouse = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?
+        .join(super::DOWNLOAD_DIR)
+        .join(super::WHEELHOUSE_DIR);
+    super::verify::download_verified_set(&pip_path, &requirement, &[], &[super::PYPI_URL], &wheelhouse).await?;
+    let args = vec![
+        "install".to_string(),
+        "--no-index".to_string(),
+        "--find-links".to_string(),
+        wheelhouse.to_string_lossy().to_string(),
+        requirement,
+    ];
+    super::install_into_venv(&app_handle, &state, args).await?;
+
+    Ok(format!("Extra [{}] installed. Reconnect to use it.", extra))
+}
+
+/// Remove an optional extra: uninstalls the packages only it requires
+#[tauri::command]
+pub async fn remove_daemon_extra(
+    app_handle: AppHandle,
+    state: State<'_, DaemonState>,
+    extra: String,
+) -> Result<String, String> {
+    crate::kiosk::ensure_allowed("Changing the daemon extras")?;
+    crate::lock::ensure_unlocked("Changing the daemon extras")?;
+    let venv_path = super::get_local_venv_path(&app_handle)?;
+    let (base, extras) = read_extras(&venv_path)?;
+    let target = extras
+        .iter()
+        .find(|e| e.name == extra)
+        .ok_or_else(|| format!("Unknown extra: {}", extra))?;
+
+    // Keep packages still needed by the base install or by another installed extra
+    let still_needed: HashSet<&String> = base
+        .iter()
+        .chain(
+            extras
+                .iter()
+                .filter(|e| e.installed && e.name != extra)
+                .flat_map(|e| e.packages.iter()),
+        )
+        .collect();
+    let to_remove: Vec<String> = target
+        .packages
+        .iter()
+        .filter(|package| !still_needed.contains(package))
+        .cloned()
+        .collect();
+
+    if to_remove.is_empty() {
+        return Ok(format!("Extra [{}] has no packages to remove", extra));
+    }
+
+    println!("[update] Removing extra [{}]: {:?}", extra, to_remove);
+    crate::stop_daemon(state.clone())?;
+    tokio::time::sleep(tokio::time::Duration::from_millis(1000)).await;
+
+    let pip_path = super::get_pip_path(&venv_path)?;
+    let output = std::process::Command::new(&pip_path)
+        .args(["uninstall", "-y"])
+        .args(&to_remove)
+        .output()
+        .map_err(|e| format!("Failed to run pip: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "pip uninstall failed with exit code {:?}:\n{}",
+            output.status.code(),
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+
+    Ok(format!("Extra [{}] removed. Reconnect to apply.", extra))
+}
@@ -2,14 +2,21 @@
 /// 
 /// This module provides functionality to check for and install daemon updates
 /// independently of the Python daemon's update routes. It directly queries PyPI
-/// and manages the local venv using pip.
+/// and manages the local venv using pip. The daemon of a wireless robot is updated
+/// through its own update API (see `remote`).
+
+pub mod extras;
+pub mod remote;
+pub mod scheduler;
+mod verify;
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
-use tauri::{AppHandle, Manager, State};
+use tauri::{AppHandle, Emitter, Manager, State};
 
 use crate::daemon::DaemonState;
+use crate::settings::{SettingsState, UpdateChannel};
 
 // ============================================================================
 // TYPES
@@ -20,6 +27,10 @@ pub struct DaemonUpdateInfo {
     pub current_version: String,
     pub available_version: String,
     pub is_available: bool,
+    /// Release notes of the available version (Markdown, from GitHub releases)
+    pub release_notes: Option<String>,
+    /// Where the full changelog can be read
+    pub release_notes_url: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -31,8 +42,61 @@ struct PyPiResponse {
 #[derive(Debug, Deserialize)]
 struct PackageInfo {
     version: String,
+    #[serde(default)]
+    project_urls: Option<HashMap<String, String>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PyPiVersionResponse {
+    info: PackageInfo,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitHubRelease {
+    body: Option<String>,
+    html_url: String,
+}
+
+/// Result of remove_local_runtime
+#[derive(Debug, Serialize, Clone)]
+pub struct RuntimeCleanupReport {
+    pub removed: Vec<String>,
+    pub reclaimed_bytes: u64,
 }
 
+/// Package set recorded before the last update, used by rollback_daemon_update
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct UpdateSnapshot {
+    /// reachy-mini version before the update
+    pub version: String,
+    /// Unix millis
+    pub created_at: u64,
+}
+
+/// Payload of the `update-progress` event emitted while pip runs
+#[derive(Debug, Serialize, Clone)]
+pub struct UpdateProgress {
+    /// "verifying" | "collecting" | "downloading" | "installing" | "installed" | "rolling_back"
+    pub step: &'static str,
+    pub package: Option<String>,
+    /// Download percentage of the current package (pip >= 24.1 only)
+    pub percent: Option<u8>,
+    pub message: String,
+}
+
+const PYPI_URL: &str = "https://pypi.org";
+
+/// Verified release artifacts are downloaded here before pip installs them
+const DOWNLOAD_DIR: &str = "update-downloads";
+/// Verified dependencies of the release, inside DOWNLOAD_DIR
+const WHEELHOUSE_DIR: &str = "wheelhouse";
+
+const ROLLBACK_DIR: &str = "update-rollback";
+const ROLLBACK_SNAPSHOT_FILE: &str = "snapshot.json";
+const ROLLBACK_REQUIREMENTS_FILE: &str = "requirements.txt";
+/// Distributions of the snapshot, so a rollback never needs the index
+const ROLLBACK_WHEELS_DIR: &str = "wheels";
+
 // ============================================================================
 // HELPER FUNCTIONS
 // ============================================================================
@@ -41,7 +105,9 @@ struct PackageInfo {
 /// This is the directory that contains the .venv that uv-trampoline will copy
 /// - In dev: src-tauri/binaries/.venv
 /// - In production: App.app/Contents/Resources/binaries/.venv
-fn get_local_venv_path(app_handle: &AppHandle) -> Result<PathBuf, String> {
+/// - On macOS, the copy in ~/Library/Application Support/Reachy Mini Control once uv-trampoline
+///   made it, so pip never writes inside the signed app bundle
+pub(crate) fn get_local_venv_path(app_handle: &AppHandle) -> Result<PathBuf, String> {
     #[cfg(target_os = "windows")]
     {
         // On Windows, the source venv is in Program Files (MSI install)
@@ -112,6 +178,18 @@ fn get_local_venv_path(app_handle: &AppHandle) -> Result<PathBuf, String> {
             }
         }
         
+        // Local copy made by uv-trampoline (only valid once fully copied)
+        #[cfg(target_os = "macos")]
+        {
+            if let Ok(home) = std::env::var("HOME") {
+                let local_dir = PathBuf::from(home).join("Library/Application Support/Reachy Mini Control");
+                if local_dir.join(".venv").exists() && local_dir.join(".setup-complete").exists() {
+                    println!("[update] ✅ Using local venv: {:?}", local_dir);
+                    return Ok(local_dir);
+                }
+            }
+        }
+        
         // In production (macOS app bundle), the executable is in:
         // App.app/Contents/MacOS/
         // The resources are in App.app/Contents/Resources/
@@ -147,10 +225,21 @@ fn get_local_venv_path(app_handle: &AppHandle) -> Result<PathBuf, String> {
 }
 
 /// Get the currently installed version of reachy-mini from the local venv
-fn get_local_daemon_version(venv_path: &Path) -> Result<String, String> {
-    // Try to read version from dist-info METADATA file
-    // Path: .venv/lib/python3.12/site-packages/reachy_mini-X.Y.Z.dist-info/METADATA
+pub(crate) fn get_local_daemon_version(venv_path: &Path) -> Result<String, String> {
+    let content = read_daemon_metadata(venv_path)?;
+    
+    // Parse METADATA file for "Version: X.Y.Z"
+    for line in content.lines() {
+        if line.starts_with("Version: ") {
+            return Ok(line.replace("Version: ", "").trim().to_string());
+        }
+    }
     
+    Err("reachy-mini version not found in venv".to_string())
+}
+
+/// Get the site-packages directory of the local venv
+fn get_site_packages(venv_path: &Path) -> Result<PathBuf, String> {
     #[cfg(target_os = "windows")]
     let site_packages = venv_path.join(".venv").join("Lib").join("site-packages");
     
@@ -161,6 +250,14 @@ fn get_local_daemon_version(venv_path: &Path) -> Result<String, String> {
         return Err(format!("Site-packages not found at {:?}", site_packages));
     }
     
+    Ok(site_packages)
+}
+
+/// Read the dist-info METADATA file of reachy-mini
+/// Path: .venv/lib/python3.12/site-packages/reachy_mini-X.Y.Z.dist-info/METADATA
+fn read_daemon_metadata(venv_path: &Path) -> Result<String, String> {
+    let site_packages = get_site_packages(venv_path)?;
+    
     // Find reachy_mini-*.dist-info directory
     let entries = std::fs::read_dir(&site_packages)
         .map_err(|e| format!("Failed to read site-packages: {}", e))?;
@@ -172,25 +269,391 @@ fn get_local_daemon_version(venv_path: &Path) -> Result<String, String> {
         if name.starts_with("reachy_mini-") && name.ends_with(".dist-info") {
             let metadata_path = entry.path().join("METADATA");
             if metadata_path.exists() {
-                let content = std::fs::read_to_string(&metadata_path)
-                    .map_err(|e| format!("Failed to read METADATA: {}", e))?;
-                
-                // Parse METADATA file for "Version: X.Y.Z"
-                for line in content.lines() {
-                    if line.starts_with("Version: ") {
-                        return Ok(line.replace("Version: ", "").trim().to_string());
-                    }
+                return std::fs::read_to_string(&metadata_path)
+                    .map_err(|e| format!("Failed to read METADATA: {}", e));
+            }
+        }
+    }
+    
+    Err("reachy-mini not found in venv".to_string())
+}
+
+/// Get the pip executable of the local venv
+pub(crate) fn get_pip_path(venv_path: &Path) -> Result<PathBuf, String> {
+    #[cfg(target_os = "windows")]
+    let pip_path = venv_path.join(".venv").join("Scripts").join("pip.exe");
+    
+    #[cfg(not(target_os = "windows"))]
+    let pip_path = venv_path.join(".venv").join("bin").join("pip");
+    
+    if !pip_path.exists() {
+        return Err(format!("pip not found at {:?}", pip_path));
+    }
+    
+    Ok(pip_path)
+}
+
+/// Get the python executable of the local venv
+pub(crate) fn get_python_path(venv_path: &Path) -> Result<PathBuf, String> {
+    #[cfg(target_os = "windows")]
+    let python_path = venv_path.join(".venv").join("Scripts").join("python.exe");
+    
+    #[cfg(not(target_os = "windows"))]
+    let python_path = venv_path.join(".venv").join("bin").join("python");
+    
+    if !python_path.exists() {
+        return Err(format!("python not found at {:?}", python_path));
+    }
+    
+    Ok(python_path)
+}
+
+/// Whether pip supports `--progress-bar raw` (pip >= 24.1), which prints
+/// machine-readable "Progress <done> of <total>" lines when output is piped
+fn pip_supports_raw_progress(pip_path: &Path) -> bool {
+    let Ok(output) = std::process::Command::new(pip_path).arg("--version").output() else {
+        return false;
+    };
+    // "pip 24.2 from /path/to/site-packages/pip (python 3.12)"
+    let version = String::from_utf8_lossy(&output.stdout);
+    let mut parts = version
+        .split_whitespace()
+        .nth(1)
+        .unwrap_or_default()
+        .split('.')
+        .map(|p| p.parse::<u32>().unwrap_or(0));
+    let major = parts.next().unwrap_or(0);
+    let minor = parts.next().unwrap_or(0);
+    (major, minor) >= (24, 1)
+}
+
+/// Turn a line of pip output into a progress event (None for lines we don't track)
+fn parse_pip_line(line: &str) -> Option<UpdateProgress> {
+    let line = line.trim();
+    let first_word = |rest: &str| rest.split_whitespace().next().map(String::from);
+    
+    if let Some(rest) = line.strip_prefix("Progress ") {
+        // "Progress 1048576 of 2097152"
+        let mut numbers = rest.split(" of ").map(|n| n.trim().parse::<u64>().ok());
+        let (Some(Some(done)), Some(Some(total))) = (numbers.next(), numbers.next()) else {
+            return None;
+        };
+        let percent = done.saturating_mul(100).checked_div(total).map_or(0, |percent| percent.min(100) as u8);
+        return Some(UpdateProgress {
+            step: "downloading",
+            package: None,
+            percent: Some(percent),
+            message: line.to_string(),
+        });
+    }
+    
+    let (step, package) = if let Some(rest) = line.strip_prefix("Collecting ") {
+        ("collecting", first_word(rest))
+    } else if let Some(rest) = line.strip_prefix("Downloading ") {
+        ("downloading", first_word(rest))
+    } else if let Some(rest) = line.strip_prefix("Installing collected packages: ") {
+        ("installing", Some(rest.to_string()))
+    } else if line.starts_with("Successfully installed") {
+        ("installed", None)
+    } else {
+        return None;
+    };
+    
+    Some(UpdateProgress {
+        step,
+        package,
+        percent: None,
+        message: line.to_string(),
+    })
+}
+
+/// Run pip, reporting each recognized output line
+pub(crate) fn run_pip_with_progress(
+    pip_path: &Path,
+    args: &[String],
+    on_progress: impl Fn(UpdateProgress),
+) -> Result<(), String> {
+    use std::io::{BufRead, BufReader, Read};
+    use std::process::Stdio;
+    
+    let mut command = std::process::Command::new(pip_path);
+    command.args(args);
+    if pip_supports_raw_progress(pip_path) {
+        command.args(["--progress-bar", "raw"]);
+    }
+    
+    println!("[update] Running: {:?}", command);
+    
+    let mut child = command
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to run pip: {}", e))?;
+    
+    // Drain stderr on its own thread so a full pipe can't block pip
+    let mut stderr_pipe = child.stderr.take();
+    let stderr_thread = std::thread::spawn(move || {
+        let mut stderr = String::new();
+        if let Some(pipe) = stderr_pipe.as_mut() {
+            let _ = pipe.read_to_string(&mut stderr);
+        }
+        stderr
+    });
+    
+    let mut current_package: Option<String> = None;
+    if let Some(stdout) = child.stdout.take() {
+        for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+            if !line.starts_with("Progress ") {
+                println!("[update] pip: {}", line);
+            }
+            if let Some(mut progress) = parse_pip_line(&line) {
+                // Raw progress lines don't name the package - use the last download
+                if progress.package.is_some() {
+                    current_package = progress.package.clone();
+                } else if progress.percent.is_some() {
+                    progress.package = current_package.clone();
                 }
+                on_progress(progress);
             }
         }
     }
     
-    Err("reachy-mini version not found in venv".to_string())
+    let status = child
+        .wait()
+        .map_err(|e| format!("Failed to wait for pip: {}", e))?;
+    let stderr = stderr_thread.join().unwrap_or_default();
+    
+    if !stderr.is_empty() {
+        println!("[update] pip stderr:\n{}", stderr);
+    }
+    
+    if !status.success() {
+        return Err(format!(
+            "pip failed with exit code {:?}:\n{}",
+            status.code(),
+            stderr
+        ));
+    }
+    
+    Ok(())
+}
+
+/// Directory holding the pre-update snapshot: <app data>/update-rollback/
+fn get_rollback_dir(app_handle: &AppHandle) -> Result<PathBuf, String> {
+    app_handle
+        .path()
+        .app_data_dir()
+        .map(|dir| dir.join(ROLLBACK_DIR))
+        .map_err(|e| format!("Failed to get app data dir: {}", e))
+}
+
+fn load_rollback_snapshot(app_handle: &AppHandle) -> Option<UpdateSnapshot> {
+    let path = get_rollback_dir(app_handle).ok()?.join(ROLLBACK_SNAPSHOT_FILE);
+    let content = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// `pip download` every requirement into `wheels_dir` (without dependencies: the freeze
+/// is the whole set). Files already there are reused first, so the snapshot also works
+/// offline once a previous update downloaded the installed set; files that are no longer
+/// part of the set are removed.
+fn download_rollback_wheels(pip_path: &Path, requirements_path: &Path, wheels_dir: &Path) -> Result<(), String> {
+    std::fs::create_dir_all(wheels_dir)
+        .map_err(|e| format!("Failed to create rollback wheels dir: {}", e))?;
+    let download = |offline: bool| {
+        let mut command = std::process::Command::new(pip_path);
+        command
+            .args(["download", "--no-deps", "-r"])
+            .arg(requirements_path)
+            .arg("-d")
+            .arg(wheels_dir);
+        if offline {
+            command.arg("--no-index").arg("--find-links").arg(wheels_dir);
+        }
+        command.output().map_err(|e| format!("Failed to run pip download: {}", e))
+    };
+    
+    let mut output = download(true)?;
+    if !output.status.success() {
+        output = download(false)?;
+    }
+    if !output.status.success() {
+        return Err(format!(
+            "Failed to download the installed packages for rollback:\n{}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    
+    // "Saved <path>" / "File was already downloaded <path>" name the files of the set
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let kept: std::collections::HashSet<String> = stdout
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            line.strip_prefix("Saved ").or_else(|| line.strip_prefix("File was already downloaded "))
+        })
+        .filter_map(|path| Path::new(path.trim()).file_name().map(|name| name.to_string_lossy().into_owned()))
+        .collect();
+    if !kept.is_empty() {
+        for entry in std::fs::read_dir(wheels_dir).into_iter().flatten().flatten() {
+            if !kept.contains(entry.file_name().to_string_lossy().as_ref()) {
+                let _ = std::fs::remove_file(entry.path());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Save the current version, its `pip freeze` and the matching distributions before upgrading
+///
+/// Direct references (`name @ file:///...`, apps installed from a folder) are left out:
+/// an update doesn't change them, and their source may be gone.
+fn save_rollback_snapshot(app_handle: &AppHandle, venv_path: &Path, pip_path: &Path) -> Result<UpdateSnapshot, String> {
+    let version = get_local_daemon_version(venv_path)?;
+    
+    let output = std::process::Command::new(pip_path)
+        .args(["freeze", "--exclude-editable"])
+        .output()
+        .map_err(|e| format!("Failed to run pip freeze: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "pip freeze failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+    
+    let requirements: String = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter(|line| !line.contains(" @ "))
+        .map(|line| format!("{}\n", line))
+        .collect();
+    
+    let dir = get_rollback_dir(app_handle)?;
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create rollback dir: {}", e))?;
+    // The previous snapshot is only replaced once this one is complete
+    let _ = std::fs::remove_file(dir.join(ROLLBACK_SNAPSHOT_FILE));
+    let requirements_path = dir.join(ROLLBACK_REQUIREMENTS_FILE);
+    std::fs::write(&requirements_path, requirements)
+        .map_err(|e| format!("Failed to write requirements snapshot: {}", e))?;
+    download_rollback_wheels(pip_path, &requirements_path, &dir.join(ROLLBACK_WHEELS_DIR))?;
+    
+    let snapshot = UpdateSnapshot {
+        version,
+        created_at: crate::daemon::history::now_millis(),
+    };
+    let content = serde_json::to_string_pretty(&snapshot)
+        .map_err(|e| format!("Failed to serialize snapshot: {}", e))?;
+    std::fs::write(dir.join(ROLLBACK_SNAPSHOT_FILE), content)
+        .map_err(|e| format!("Failed to write snapshot: {}", e))?;
+    
+    println!("[update] 📸 Snapshot saved (reachy-mini {})", snapshot.version);
+    Ok(snapshot)
+}
+
+/// Base URL of the PyPI-compatible index serving the given channel
+fn get_index_url(channel: UpdateChannel, nightly_index_url: Option<&str>) -> String {
+    match (channel, nightly_index_url) {
+        (UpdateChannel::Nightly, Some(url)) => url.to_string(),
+        _ => PYPI_URL.to_string(),
+    }
+}
+
+/// Whether a release belongs to the given channel
+fn matches_channel(version: &str, channel: UpdateChannel) -> bool {
+    let Ok(parsed) = parse_version(version) else {
+        return false;
+    };
+    match channel {
+        UpdateChannel::Stable => parsed.pre.is_empty(),
+        UpdateChannel::Rc => !parsed.pre.as_str().starts_with("dev"),
+        UpdateChannel::Nightly => true,
+    }
+}
+
+/// Reinstall the package set recorded before the last update from its saved distributions
+/// (downgrades anything the update changed, no index needed). Returns the restored version.
+fn restore_rollback_snapshot(app_handle: &AppHandle, venv_path: &Path, pip_path: &Path) -> Result<String, String> {
+    let snapshot = load_rollback_snapshot(app_handle)
+        .ok_or_else(|| "No previous version available for rollback".to_string())?;
+    let dir = get_rollback_dir(app_handle)?;
+    let requirements_path = dir.join(ROLLBACK_REQUIREMENTS_FILE);
+    let wheels_dir = dir.join(ROLLBACK_WHEELS_DIR);
+    if !requirements_path.exists() || !wheels_dir.is_dir() {
+        return Err(format!("Rollback snapshot incomplete in {:?}", dir));
+    }
+    
+    println!("[update] Rolling back daemon to {}", snapshot.version);
+    
+    let requirements = requirements_path.to_string_lossy().to_string();
+    let wheels = wheels_dir.to_string_lossy().to_string();
+    let args = ["install", "--no-index", "--find-links", wheels.as_str(), "-r", requirements.as_str()];
+    
+    println!("[update] Running: {:?} {:?}", pip_path, args);
+    
+    let output = std::process::Command::new(pip_path)
+        .args(args)
+        .output()
+        .map_err(|e| format!("Failed to run pip: {}", e))?;
+    
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!(
+            "Rollback failed with exit code {:?}:\n{}",
+            output.status.code(),
+            stderr
+        ));
+    }
+    
+    let restored = get_local_daemon_version(venv_path)?;
+    if restored != snapshot.version {
+        return Err(format!(
+            "Rollback incomplete: expected {}, venv has {}",
+            snapshot.version, restored
+        ));
+    }
+    
+    println!("[update] ✅ Daemon rolled back to {}", restored);
+    Ok(restored)
 }
 
-/// Get the latest version available on PyPI
-async fn get_pypi_version(package_name: &str, pre_release: bool) -> Result<String, String> {
-    let url = format!("https://pypi.org/pypi/{}/json", package_name);
+/// Check that the venv is usable after an install:
+/// no broken requirements (`pip check`) and the daemon package imports
+fn verify_venv(venv_path: &Path, pip_path: &Path) -> Result<(), String> {
+    let output = std::process::Command::new(pip_path)
+        .arg("check")
+        .output()
+        .map_err(|e| format!("Failed to run pip check: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "Broken dependencies after install:\n{}",
+            String::from_utf8_lossy(&output.stdout).trim()
+        ));
+    }
+    
+    let python_path = get_python_path(venv_path)?;
+    let output = std::process::Command::new(&python_path)
+        .args(["-c", "import reachy_mini"])
+        .output()
+        .map_err(|e| format!("Failed to run python: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "reachy_mini cannot be imported after install:\n{}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    
+    println!("[update] ✅ Venv integrity check passed");
+    Ok(())
+}
+
+/// Get the latest version available on the channel's index
+async fn get_pypi_version(
+    package_name: &str,
+    channel: UpdateChannel,
+    index_url: &str,
+) -> Result<String, String> {
+    let url = format!("{}/pypi/{}/json", index_url, package_name);
     
     println!("[update] Fetching PyPI info from: {}", url);
     
@@ -207,26 +670,113 @@ async fn get_pypi_version(package_name: &str, pre_release: bool) -> Result<Strin
         .await
         .map_err(|e| format!("Failed to parse PyPI JSON: {}", e))?;
     
-    if pre_release {
-        // Get all versions and sort them
-        let mut versions: Vec<String> = data.releases.keys().cloned().collect();
-        versions.sort_by(|a, b| compare_semver(a, b));
-        
-        if let Some(latest) = versions.last() {
-            println!("[update] Latest version (including pre-release): {}", latest);
-            Ok(latest.clone())
-        } else {
-            Err("No versions found on PyPI".to_string())
-        }
-    } else {
+    if channel == UpdateChannel::Stable && index_url == PYPI_URL {
         // Return the stable version from info
         println!("[update] Latest stable version: {}", data.info.version);
-        Ok(data.info.version)
+        return Ok(data.info.version);
+    }
+    
+    // Get all versions of the channel (skipping releases without files) and sort them
+    let mut versions: Vec<String> = data
+        .releases
+        .iter()
+        .filter(|(version, files)| !files.is_empty() && matches_channel(version, channel))
+        .map(|(version, _)| version.clone())
+        .collect();
+    versions.sort_by(|a, b| compare_semver(a, b));
+    
+    if let Some(latest) = versions.last() {
+        println!("[update] Latest version ({:?} channel): {}", channel, latest);
+        Ok(latest.clone())
+    } else {
+        Err(format!("No {:?} versions found on {}", channel, index_url))
+    }
+}
+
+/// Extract "owner/repo" from the first GitHub URL of the project
+fn find_github_repo(project_urls: &HashMap<String, String>) -> Option<String> {
+    project_urls.values().find_map(|url| {
+        let path = url.split("github.com/").nth(1)?;
+        let mut segments = path.split('/').filter(|s| !s.is_empty());
+        let owner = segments.next()?;
+        let repo = segments.next()?.trim_end_matches(".git");
+        Some(format!("{}/{}", owner, repo))
+    })
+}
+
+/// Fetch the release notes of a version: PyPI project_urls -> GitHub release of that tag
+/// Returns (notes, url); never fails the update check, missing notes are just None
+async fn get_release_notes(index_url: &str, package_name: &str, version: &str) -> (Option<String>, Option<String>) {
+    let client = match reqwest::Client::builder()
+        .user_agent("reachy-mini-desktop-app")
+        .timeout(std::time::Duration::from_secs(10))
+        .build()
+    {
+        Ok(client) => client,
+        Err(_) => return (None, None),
+    };
+    
+    let url = format!("{}/pypi/{}/{}/json", index_url, package_name, version);
+    let project_urls = match client.get(&url).send().await {
+        Ok(response) => response
+            .json::<PyPiVersionResponse>()
+            .await
+            .ok()
+            .and_then(|data| data.info.project_urls)
+            .unwrap_or_default(),
+        Err(e) => {
+            eprintln!("[update] ⚠️ Failed to fetch release info: {}", e);
+            return (None, None);
+        }
+    };
+    
+    // Prefer an explicit changelog link if the project declares one
+    let changelog_url = project_urls
+        .iter()
+        .find(|(label, _)| {
+            let label = label.to_lowercase();
+            label.contains("changelog") || label.contains("release notes")
+        })
+        .map(|(_, url)| url.clone());
+    
+    let Some(repo) = find_github_repo(&project_urls) else {
+        return (None, changelog_url);
+    };
+    
+    // Tags are usually "v1.2.3", sometimes plain "1.2.3"
+    for tag in [format!("v{}", version), version.to_string()] {
+        let url = format!("https://api.github.com/repos/{}/releases/tags/{}", repo, tag);
+        let Ok(response) = client.get(&url).send().await else {
+            break;
+        };
+        if !response.status().is_success() {
+            continue;
+        }
+        if let Ok(release) = response.json::<GitHubRelease>().await {
+            println!("[update] Release notes found for {} ({})", version, tag);
+            return (release.body, Some(changelog_url.unwrap_or(release.html_url)));
+        }
     }
+    
+    (None, changelog_url)
+}
+
+/// Resolve the channel of a request: explicit channel, then legacy pre_release flag, then settings
+fn resolve_channel(
+    app_handle: &AppHandle,
+    channel: Option<UpdateChannel>,
+    pre_release: Option<bool>,
+) -> (UpdateChannel, String) {
+    let settings = app_handle.state::<SettingsState>().get();
+    let channel = channel
+        .or(pre_release.map(|pre| if pre { UpdateChannel::Rc } else { UpdateChannel::Stable }))
+        .unwrap_or(settings.update_channel);
+    let index_url = get_index_url(channel, settings.nightly_index_url.as_deref());
+    (channel, index_url)
 }
 
 /// Parse a version string, handling PyPI pre-release formats (e.g., "1.2.5rc1" -> "1.2.5-rc.1")
-fn parse_version(version_str: &str) -> Result<semver::Version, String> {
+pub(crate) fn parse_version(version_str: &str) -> Result<semver::Version, String> {
     // First, try standard semver parsing
     if let Ok(ver) = semver::Version::parse(version_str) {
         return Ok(ver);
@@ -242,6 +792,13 @@ fn parse_version(version_str: &str) -> Result<semver::Version, String> {
     let minor = parts[1];
     let patch_part = parts[2];
     
+    // Check for ".devN" suffix: "1.2.5.dev3" -> "1.2.5-dev.3"
+    if let Some(dev_num) = parts.get(3).and_then(|p| p.strip_prefix("dev")) {
+        let clean_version = format!("{}.{}.{}-dev.{}", major, minor, patch_part, dev_num);
+        return semver::Version::parse(&clean_version)
+            .map_err(|e| format!("Failed to parse cleaned version '{}': {}", clean_version, e));
+    }
+    
     // Check for "rc" in patch part
     if patch_part.contains("rc") {
         let rc_parts: Vec<&str> = patch_part.split("rc").collect();
@@ -309,9 +866,11 @@ fn is_update_available(current: &str, available: &str) -> Result<bool, String> {
 #[tauri::command]
 pub async fn check_daemon_update(
     app_handle: AppHandle,
-    pre_release: bool,
+    pre_release: Option<bool>,
+    channel: Option<UpdateChannel>,
 ) -> Result<DaemonUpdateInfo, String> {
-    println!("[update] Checking for daemon updates (pre_release: {})", pre_release);
+    let (channel, index_url) = resolve_channel(&app_handle, channel, pre_release);
+    println!("[update] Checking for daemon updates (channel: {:?})", channel);
     
     // 1. Get local version
     let venv_path = get_local_venv_path(&app_handle)?;
@@ -319,17 +878,26 @@ pub async fn check_daemon_update(
     println!("[update] Current version: {}", current_version);
     
     // 2. Get PyPI version
-    let available_version = get_pypi_version("reachy-mini", pre_release).await?;
+    let available_version = get_pypi_version("reachy-mini", channel, &index_url).await?;
     println!("[update] Available version: {}", available_version);
     
     // 3. Compare versions
     let is_available = is_update_available(&current_version, &available_version)?;
     println!("[update] Update available: {}", is_available);
     
+    // 4. Fetch "what's new" for the candidate version
+    let (release_notes, release_notes_url) = if is_available {
+        get_release_notes(&index_url, "reachy-mini", &available_version).await
+    } else {
+        (None, None)
+    };
+    
     Ok(DaemonUpdateInfo {
         current_version,
         available_version,
         is_available,
+        release_notes,
+        release_notes_url,
     })
 }
 
@@ -338,64 +906,181 @@ pub async fn check_daemon_update(
 pub async fn update_daemon(
     app_handle: AppHandle,
     state: State<'_, DaemonState>,
-    pre_release: bool,
+    pre_release: Option<bool>,
+    channel: Option<UpdateChannel>,
 ) -> Result<String, String> {
-    println!("[update] Starting daemon update (pre_release: {})", pre_release);
+    crate::kiosk::ensure_allowed("Updating the daemon")?;
+    crate::lock::ensure_unlocked("Updating the daemon")?;
+    let (channel, index_url) = resolve_channel(&app_handle, channel, pre_release);
+    println!("[update] Starting daemon update (channel: {:?})", channel);
     
-    // 1. Stop the daemon gracefully
-    println!("[update] Stopping daemon...");
-    crate::stop_daemon(state.clone())?;
+    // Resolve the target version first so pip can't pick a release outside the channel
+    let target_version = get_pypi_version("reachy-mini", channel, &index_url).await?;
     
-    // Wait a bit for the daemon to stop completely
-    tokio::time::sleep(tokio::time::Duration::from_millis(1000)).await;
+    // Download the release ourselves and check its sha256 before pip installs it
+    let _ = app_handle.emit("update-progress", UpdateProgress {
+        step: "verifying",
+        package: Some(format!("reachy-mini {}", target_version)),
+        percent: None,
+        message: "Downloading and verifying package hash...".to_string(),
+    });
+    let download_dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?
+        .join(DOWNLOAD_DIR);
+    let artifact = verify::download_verified_artifact(&app_handle, &index_url, "reachy-mini", &target_version, &download_dir).await?;
     
-    // 2. Get venv path and pip executable
+    // Resolve its dependencies, check each file's hash, then install from those files only,
+    // keeping the extras currently installed (e.g. [mujoco] for simulation)
     let venv_path = get_local_venv_path(&app_handle)?;
+    let pip_path = get_pip_path(&venv_path)?;
+    let installed_extras = extras::get_installed_extras(&venv_path);
+    let requirement = extras::artifact_with_extras(&artifact, &installed_extras);
+    let mut pip_args = Vec::new();
+    if channel != UpdateChannel::Stable {
+        pip_args.push("--pre".to_string());
+    }
+    if index_url != PYPI_URL {
+        pip_args.push("--extra-index-url".to_string());
+        pip_args.push(format!("{}/simple", index_url));
+    }
+    let _ = app_handle.emit("update-progress", UpdateProgress {
+        step: "verifying",
+        package: Some(format!("reachy-mini {}", target_version)),
+        percent: None,
+        message: "Downloading and verifying dependencies...".to_string(),
+    });
+    let wheelhouse = download_dir.join(WHEELHOUSE_DIR);
+    let mut index_urls = vec![index_url.as_str()];
+    if index_url != PYPI_URL {
+        // The nightly index serves reachy-mini, dependencies come from PyPI
+        index_urls.push(PYPI_URL);
+    }
+    verify::download_verified_set(&pip_path, &requirement, &pip_args, &index_urls, &wheelhouse).await?;
     
-    #[cfg(target_os = "windows")]
-    let pip_path = venv_path.join(".venv").join("Scripts").join("pip.exe");
+    let args = vec![
+        "install".to_string(),
+        "--upgrade".to_string(),
+        "--no-index".to_string(),
+        "--find-links".to_string(),
+        wheelhouse.to_string_lossy().to_string(),
+        requirement,
+    ];
+    install_into_venv(&app_handle, &state, args).await?;
     
-    #[cfg(not(target_os = "windows"))]
-    let pip_path = venv_path.join(".venv").join("bin").join("pip");
+    Ok("Daemon updated successfully. Reconnect to use the new version.".to_string())
+}
+
+/// Update the daemon from a local wheel or sdist (offline / air-gapped networks)
+#[tauri::command]
+pub async fn update_daemon_from_file(
+    app_handle: AppHandle,
+    state: State<'_, DaemonState>,
+    path: String,
+) -> Result<String, String> {
+    crate::kiosk::ensure_allowed("Updating the daemon")?;
+    crate::lock::ensure_unlocked("Updating the daemon")?;
+    let file = PathBuf::from(&path);
+    if !file.is_file() {
+        return Err(format!("File not found: {}", path));
+    }
     
-    if !pip_path.exists() {
-        return Err(format!("pip not found at {:?}", pip_path));
+    let file_name = file
+        .file_name()
+        .map(|n| n.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    if !file_name.starts_with("reachy_mini-") && !file_name.starts_with("reachy-mini-") {
+        return Err(format!("Not a reachy-mini package: {}", file_name));
+    }
+    if !(file_name.ends_with(".whl") || file_name.ends_with(".tar.gz")) {
+        return Err(format!("Unsupported package format (expected .whl or .tar.gz): {}", file_name));
     }
     
-    println!("[update] Using pip at: {:?}", pip_path);
+    println!("[update] Starting offline daemon update from {:?}", file);
     
-    // 3. Build pip command
-    // Note: No [mujoco] extra for desktop app (USB mode only, no simulation)
-    let mut args = vec!["install", "--upgrade", "reachy-mini"];
-    if pre_release {
-        args.push("--pre");
+    // --no-index: never hit the network; dependencies are looked up next to the file
+    let mut args = vec![
+        "install".to_string(),
+        "--upgrade".to_string(),
+        "--no-index".to_string(),
+    ];
+    if let Some(dir) = file.parent() {
+        args.push("--find-links".to_string());
+        args.push(dir.to_string_lossy().to_string());
     }
+    args.push(file.to_string_lossy().to_string());
     
-    println!("[update] Running: {:?} {:?}", pip_path, args);
+    install_into_venv(&app_handle, &state, args).await?;
     
-    // 4. Execute pip install
-    let output = std::process::Command::new(&pip_path)
-        .args(&args)
-        .output()
-        .map_err(|e| format!("Failed to run pip: {}", e))?;
+    Ok("Daemon updated successfully. Reconnect to use the new version.".to_string())
+}
+
+/// Shared install flow: stop daemon, snapshot for rollback, pip install, re-sign binaries
+async fn install_into_venv(
+    app_handle: &AppHandle,
+    state: &State<'_, DaemonState>,
+    args: Vec<String>,
+) -> Result<(), String> {
+    // 1. Stop the daemon gracefully
+    println!("[update] Stopping daemon...");
+    crate::stop_daemon(state.clone())?;
     
-    // Log output
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let stderr = String::from_utf8_lossy(&output.stderr);
+    // Wait a bit for the daemon to stop completely
+    tokio::time::sleep(tokio::time::Duration::from_millis(1000)).await;
     
-    if !stdout.is_empty() {
-        println!("[update] pip stdout:\n{}", stdout);
-    }
-    if !stderr.is_empty() {
-        println!("[update] pip stderr:\n{}", stderr);
-    }
+    // 2. Get venv path and pip executable
+    let venv_path = get_local_venv_path(app_handle)?;
+    let pip_path = get_pip_path(&venv_path)?;
     
-    if !output.status.success() {
-        return Err(format!(
-            "pip update failed with exit code {:?}:\n{}",
-            output.status.code(),
-            stderr
-        ));
+    println!("[update] Using pip at: {:?}", pip_path);
+    
+    // Snapshot the current package set so a broken release can be rolled back
+    let has_snapshot = match save_rollback_snapshot(app_handle, &venv_path, &pip_path) {
+        Ok(_) => true,
+        Err(e) => {
+            eprintln!("[update] ⚠️ Failed to snapshot current version, rollback won't be available: {}", e);
+            false
+        }
+    };
+    
+    // 3. Execute pip install, streaming progress to the UI, then check the venv is still usable
+    let handle = app_handle.clone();
+    tokio::task::spawn_blocking(move || {
+        let result = run_pip_with_progress(&pip_path, &args, |progress| {
+            let _ = handle.emit("update-progress", progress);
+        })
+            .and_then(|_| verify_venv(&venv_path, &pip_path));
+        let Err(e) = result else {
+            return Ok(());
+        };
+        
+        eprintln!("[update] ❌ Install failed: {}", e);
+        if !has_snapshot {
+            return Err(e);
+        }
+        
+        let _ = handle.emit("update-progress", UpdateProgress {
+            step: "rolling_back",
+            package: None,
+            percent: None,
+            message: "Update failed, restoring previous version...".to_string(),
+        });
+        match restore_rollback_snapshot(&handle, &venv_path, &pip_path) {
+            Ok(version) => Err(format!("{}\n\nPrevious version {} was restored.", e, version)),
+            Err(rollback_err) => Err(format!("{}\n\nAutomatic rollback also failed: {}", e, rollback_err)),
+        }
+    })
+    .await
+    .map_err(|e| format!("pip task failed: {}", e))??;
+    
+    // 4. Re-sign newly installed native binaries (macOS Team ID)
+    #[cfg(target_os = "macos")]
+    let signed = crate::signing::sign_python_binaries().await;
+    #[cfg(not(target_os = "macos"))]
+    let signed = crate::signing::sign_python_binaries();
+    if let Err(e) = signed {
+        eprintln!("[update] ⚠️ Failed to re-sign Python binaries: {}", e);
     }
     
     println!("[update] Daemon updated successfully!");
@@ -405,6 +1090,82 @@ pub async fn update_daemon(
     // 5. DON'T restart daemon here
     // Let the user reconnect - uv-trampoline will copy the updated venv at next launch
     
-    Ok("Daemon updated successfully. Reconnect to use the new version.".to_string())
+    Ok(())
+}
+
+/// Get the snapshot available for rollback (None if no update was done yet)
+#[tauri::command]
+pub fn get_update_rollback_info(app_handle: AppHandle) -> Option<UpdateSnapshot> {
+    load_rollback_snapshot(&app_handle)
+}
+
+/// Restore the package set recorded before the last update
+/// Used when a new version fails to boot (e.g. broken pre-release)
+#[tauri::command]
+pub async fn rollback_daemon_update(
+    app_handle: AppHandle,
+    state: State<'_, DaemonState>,
+) -> Result<String, String> {
+    crate::kiosk::ensure_allowed("Rolling back the daemon")?;
+    crate::lock::ensure_unlocked("Rolling back the daemon")?;
+    if load_rollback_snapshot(&app_handle).is_none() {
+        return Err("No previous version available for rollback".to_string());
+    }
+    
+    // 1. Stop the daemon
+    crate::stop_daemon(state.clone())?;
+    tokio::time::sleep(tokio::time::Duration::from_millis(1000)).await;
+    
+    // 2. Reinstall the recorded package set
+    let venv_path = get_local_venv_path(&app_handle)?;
+    let pip_path = get_pip_path(&venv_path)?;
+    let handle = app_handle.clone();
+    let restored = tokio::task::spawn_blocking(move || restore_rollback_snapshot(&handle, &venv_path, &pip_path))
+        .await
+        .map_err(|e| format!("Rollback task failed: {}", e))??;
+    
+    Ok(format!("Daemon rolled back to {}. Reconnect to use it.", restored))
 }
 
+/// Delete the copied Python runtime (venv, cpython, uv) and the update caches
+/// Uninstalling the app leaves them behind; uv-trampoline copies them again on next launch
+#[tauri::command]
+pub async fn remove_local_runtime(
+    app_handle: AppHandle,
+    state: State<'_, DaemonState>,
+) -> Result<RuntimeCleanupReport, String> {
+    crate::kiosk::ensure_allowed("Removing the local runtime")?;
+    crate::lock::ensure_unlocked("Removing the local runtime")?;
+    // 1. Stop the daemon (its files are in use)
+    crate::stop_daemon(state.clone())?;
+    tokio::time::sleep(tokio::time::Duration::from_millis(1000)).await;
+    
+    // 2. Remove the local runtime copy, then the downloads and rollback snapshot
+    let app_data_dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?;
+    let report = tokio::task::spawn_blocking(move || {
+        let runtime = uv_wrapper::remove_local_runtime()?;
+        let mut report = RuntimeCleanupReport {
+            removed: runtime.removed.iter().map(|p| p.display().to_string()).collect(),
+            reclaimed_bytes: runtime.bytes_reclaimed,
+        };
+        for cache_dir in [DOWNLOAD_DIR, ROLLBACK_DIR].map(|dir| app_data_dir.join(dir)) {
+            if cache_dir.exists() {
+                report.reclaimed_bytes += uv_wrapper::remove_path_reclaiming(&cache_dir)?;
+                report.removed.push(cache_dir.display().to_string());
+            }
+        }
+        Ok::<_, String>(report)
+    })
+    .await
+    .map_err(|e| format!("Cleanup task failed: {}", e))??;
+    
+    println!(
+        "[update] 🧹 Removed local runtime: {} entries, {} MB reclaimed",
+        report.removed.len(),
+        report.reclaimed_bytes / (1024 * 1024)
+    );
+    Ok(report)
+}
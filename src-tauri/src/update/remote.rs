@@ -0,0 +1,335 @@
+/// Update of the daemon running on a wireless robot (WiFi mode)
+///
+/// The robot's daemon is not in the local venv: it updates itself through its update API,
+/// reached over the local proxy. The target version is resolved here with the same
+/// channel and index as the local update, so both paths install the same release, and
+/// the robot's previous version is recorded per host for rollback.
+///
+/// Flow: start the self-update job, relay its logs, wait for the robot to restart and
+/// answer again, then check the version it reports. If the job fails or the robot comes
+/// back with another version, the previous version is reinstalled the same way.
+///
+/// Progress is emitted as `robot-update-progress` (UpdateProgress), with the steps
+/// "starting" | "installing" | "rebooting" | "verifying" | "installed" | "rolling_back".
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Manager};
+
+use super::{
+    get_index_url, get_pypi_version, get_release_notes, get_rollback_dir, is_update_available, matches_channel,
+    resolve_channel, DaemonUpdateInfo, UpdateProgress, UpdateSnapshot, PYPI_URL,
+};
+use crate::connection::{ConnectionManager, ConnectionState};
+use crate::daemon_api::DaemonClient;
+use crate::local_proxy::LocalProxyState;
+use crate::settings::{SettingsState, UpdateChannel};
+
+const PROGRESS_EVENT: &str = "robot-update-progress";
+/// Previous version of each robot, by host: <app data>/update-rollback/robots.json
+const ROBOT_SNAPSHOTS_FILE: &str = "robots.json";
+
+const JOB_POLL_INTERVAL: Duration = Duration::from_secs(1);
+/// pip on the robot's CPU, over its WiFi
+const JOB_TIMEOUT: Duration = Duration::from_secs(20 * 60);
+/// Unanswered job polls after which the robot is considered to be restarting
+const MAX_MISSED_POLLS: u32 = 3;
+/// How long the daemon may keep answering after its job completed before it restarts
+const RESTART_GRACE: Duration = Duration::from_secs(30);
+const REBOOT_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+/// One robot update at a time (the job runs on the robot, not in the app)
+static IN_PROGRESS: AtomicBool = AtomicBool::new(false);
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+/// Clears `IN_PROGRESS` when the update ends
+struct UpdateLock;
+
+impl UpdateLock {
+    fn acquire() -> Result<Self, String> {
+        if IN_PROGRESS.swap(true, Ordering::SeqCst) {
+            return Err("A robot update is already running".to_string());
+        }
+        Ok(UpdateLock)
+    }
+}
+
+impl Drop for UpdateLock {
+    fn drop(&mut self) {
+        IN_PROGRESS.store(false, Ordering::SeqCst);
+    }
+}
+
+// ============================================================================
+// HELPER FUNCTIONS
+// ============================================================================
+
+fn emit_progress(app_handle: &AppHandle, step: &'static str, package: Option<String>, message: impl Into<String>) {
+    let _ = app_handle.emit(PROGRESS_EVENT, UpdateProgress {
+        step,
+        package,
+        percent: None,
+        message: message.into(),
+    });
+}
+
+/// Client of the robot reached over WiFi, with its current host
+async fn wifi_client(app_handle: &AppHandle) -> Result<(DaemonClient, String), String> {
+    let ConnectionState::WifiMode { host, .. } = app_handle.state::<ConnectionManager>().get() else {
+        return Err("Connect to a wireless robot over WiFi to update its daemon".to_string());
+    };
+    let proxy = app_handle.state::<Arc<LocalProxyState>>();
+    Ok((DaemonClient::for_proxy(&proxy).await, host))
+}
+
+fn update_error(error: String) -> String {
+    if error.contains(" 404 ") {
+        "This robot's daemon can't be updated from the app: update it once from the robot's dashboard".to_string()
+    } else {
+        error
+    }
+}
+
+async fn robot_version(client: &DaemonClient) -> Result<String, String> {
+    client
+        .daemon_status()
+        .await?
+        .version
+        .ok_or_else(|| "The robot's daemon does not report its version".to_string())
+}
+
+fn load_robot_snapshots(app_handle: &AppHandle) -> HashMap<String, UpdateSnapshot> {
+    get_rollback_dir(app_handle)
+        .ok()
+        .and_then(|dir| std::fs::read_to_string(dir.join(ROBOT_SNAPSHOTS_FILE)).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_robot_snapshot(app_handle: &AppHandle, host: &str, version: &str) -> Result<(), String> {
+    let mut snapshots = load_robot_snapshots(app_handle);
+    snapshots.insert(host.to_lowercase(), UpdateSnapshot {
+        version: version.to_string(),
+        created_at: crate::daemon::history::now_millis(),
+    });
+    let dir = get_rollback_dir(app_handle)?;
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create rollback dir: {}", e))?;
+    let content = serde_json::to_string_pretty(&snapshots)
+        .map_err(|e| format!("Failed to serialize robot snapshots: {}", e))?;
+    std::fs::write(dir.join(ROBOT_SNAPSHOTS_FILE), content)
+        .map_err(|e| format!("Failed to write robot snapshots: {}", e))?;
+    println!("[update] 📸 Robot {} snapshot saved (reachy-mini {})", host, version);
+    Ok(())
+}
+
+/// Index serving a given version: pypi.org for releases and release candidates,
+/// the nightly index (when configured) for .dev builds
+fn index_for_version(app_handle: &AppHandle, version: &str) -> String {
+    if matches_channel(version, UpdateChannel::Rc) {
+        return PYPI_URL.to_string();
+    }
+    let settings = app_handle.state::<SettingsState>().get();
+    get_index_url(UpdateChannel::Nightly, settings.nightly_index_url.as_deref())
+}
+
+/// Run the robot's self-update to `version` and wait for it to come back;
+/// returns the version it then reports
+async fn install_on_robot(
+    app_handle: &AppHandle,
+    client: &DaemonClient,
+    version: &str,
+    index_url: &str,
+) -> Result<String, String> {
+    let package = Some(format!("reachy-mini {}", version));
+    emit_progress(app_handle, "starting", package.clone(), "Starting the update on the robot...");
+    let pre_release = !matches_channel(version, UpdateChannel::Stable);
+    let job_id = client
+        .start_self_update(version, index_url, pre_release)
+        .await
+        .map_err(update_error)?;
+    println!("[update] 🤖 Robot update job {} started (reachy-mini {})", job_id, version);
+
+    // 1. Relay the job's logs until it ends or the robot stops answering to restart
+    let deadline = Instant::now() + JOB_TIMEOUT;
+    let mut seen_logs = 0;
+    let mut missed_polls = 0;
+    let mut restarting = false;
+    loop {
+        tokio::time::sleep(JOB_POLL_INTERVAL).await;
+        match client.self_update_status(&job_id).await {
+            Ok(status) => {
+                missed_polls = 0;
+                for line in status.logs.iter().skip(seen_logs) {
+                    emit_progress(app_handle, "installing", package.clone(), line.clone());
+                }
+                seen_logs = seen_logs.max(status.logs.len());
+                match status.outcome() {
+                    Some(true) => break,
+                    Some(false) => {
+                        let reason = status.logs.last().cloned().unwrap_or_else(|| "no details".to_string());
+                        return Err(format!("Update failed on the robot: {}", reason));
+                    }
+                    None => {}
+                }
+            }
+            Err(e) => {
+                missed_polls += 1;
+                if missed_polls >= MAX_MISSED_POLLS {
+                    println!("[update] 🤖 Robot stopped answering during its update ({}), waiting for restart", e);
+                    restarting = true;
+                    break;
+                }
+            }
+        }
+        if Instant::now() > deadline {
+            return Err(format!("The robot's update did not finish within {} minutes", JOB_TIMEOUT.as_secs() / 60));
+        }
+    }
+
+    // 2. Wait for the restart: the daemon goes down (unless it already did), then answers again
+    emit_progress(app_handle, "rebooting", package.clone(), "Waiting for the robot to restart...");
+    if !restarting {
+        let grace = Instant::now() + RESTART_GRACE;
+        while Instant::now() < grace && client.is_healthy().await {
+            tokio::time::sleep(JOB_POLL_INTERVAL).await;
+        }
+    }
+    if !client.wait_healthy(REBOOT_TIMEOUT).await {
+        return Err(format!(
+            "The robot did not come back within {} minutes after its update: check its power and WiFi",
+            REBOOT_TIMEOUT.as_secs() / 60
+        ));
+    }
+
+    // 3. Check what it now runs
+    emit_progress(app_handle, "verifying", package, "Checking the robot's daemon version...");
+    robot_version(client).await
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Check if an update is available for the daemon of the robot connected over WiFi
+#[tauri::command]
+pub async fn check_robot_daemon_update(
+    app_handle: AppHandle,
+    pre_release: Option<bool>,
+    channel: Option<UpdateChannel>,
+) -> Result<DaemonUpdateInfo, String> {
+    let (channel, index_url) = resolve_channel(&app_handle, channel, pre_release);
+    let (client, host) = wifi_client(&app_handle).await?;
+    println!("[update] Checking robot {} for daemon updates (channel: {:?})", host, channel);
+
+    let current_version = robot_version(&client).await?;
+    let available_version = get_pypi_version("reachy-mini", channel, &index_url).await?;
+    let is_available = is_update_available(&current_version, &available_version)?;
+    println!("[update] Robot runs {}, {} available (update: {})", current_version, available_version, is_available);
+
+    let (release_notes, release_notes_url) = if is_available {
+        get_release_notes(&index_url, "reachy-mini", &available_version).await
+    } else {
+        (None, None)
+    };
+
+    Ok(DaemonUpdateInfo {
+        current_version,
+        available_version,
+        is_available,
+        release_notes,
+        release_notes_url,
+    })
+}
+
+/// Update the daemon of the robot connected over WiFi to the latest version of the channel
+/// (rolled back to the previous version if the update fails)
+#[tauri::command]
+pub async fn update_robot_daemon(
+    app_handle: AppHandle,
+    pre_release: Option<bool>,
+    channel: Option<UpdateChannel>,
+) -> Result<String, String> {
+    crate::kiosk::ensure_allowed("Updating the robot's daemon")?;
+    crate::lock::ensure_unlocked("Updating the robot's daemon")?;
+    let _lock = UpdateLock::acquire()?;
+    let (channel, index_url) = resolve_channel(&app_handle, channel, pre_release);
+    let (client, host) = wifi_client(&app_handle).await?;
+
+    let previous = robot_version(&client).await?;
+    let target = get_pypi_version("reachy-mini", channel, &index_url).await?;
+    if !is_update_available(&previous, &target)? {
+        return Ok(format!("The robot's daemon is already up to date ({}).", previous));
+    }
+    println!("[update] 🤖 Updating robot {} from {} to {} (channel: {:?})", host, previous, target, channel);
+
+    let has_snapshot = match save_robot_snapshot(&app_handle, &host, &previous) {
+        Ok(()) => true,
+        Err(e) => {
+            eprintln!("[update] ⚠️ Failed to record the robot's version, rollback won't be available: {}", e);
+            false
+        }
+    };
+
+    let error = match install_on_robot(&app_handle, &client, &target, &index_url).await {
+        Ok(version) if version == target => {
+            emit_progress(&app_handle, "installed", Some(format!("reachy-mini {}", version)), "Robot updated");
+            println!("[update] ✅ Robot {} updated to {}", host, version);
+            return Ok(format!("Robot daemon updated to {}.", version));
+        }
+        Ok(version) => format!("The robot reports {} after its update instead of {}", version, target),
+        Err(e) => e,
+    };
+    eprintln!("[update] ❌ Robot update failed: {}", error);
+
+    // Same semantics as the local venv: put the previous version back
+    if !has_snapshot {
+        return Err(error);
+    }
+    if !client.is_healthy().await {
+        return Err(format!("{}\n\nThe robot is unreachable, its previous version {} could not be restored.", error, previous));
+    }
+    emit_progress(&app_handle, "rolling_back", None, "Update failed, restoring previous version...");
+    let rollback_index = index_for_version(&app_handle, &previous);
+    match install_on_robot(&app_handle, &client, &previous, &rollback_index).await {
+        Ok(version) if version == previous => Err(format!("{}\n\nPrevious version {} was restored.", error, version)),
+        Ok(version) => Err(format!("{}\n\nAutomatic rollback also failed: the robot reports {}", error, version)),
+        Err(rollback_err) => Err(format!("{}\n\nAutomatic rollback also failed: {}", error, rollback_err)),
+    }
+}
+
+/// Version the robot connected over WiFi ran before its last update (None if never updated from here)
+#[tauri::command]
+pub fn get_robot_update_rollback_info(app_handle: AppHandle) -> Option<UpdateSnapshot> {
+    let ConnectionState::WifiMode { host, .. } = app_handle.state::<ConnectionManager>().get() else {
+        return None;
+    };
+    load_robot_snapshots(&app_handle).remove(&host.to_lowercase())
+}
+
+/// Reinstall the version the robot connected over WiFi ran before its last update
+#[tauri::command]
+pub async fn rollback_robot_daemon_update(app_handle: AppHandle) -> Result<String, String> {
+    crate::kiosk::ensure_allowed("Rolling back the robot's daemon")?;
+    crate::lock::ensure_unlocked("Rolling back the robot's daemon")?;
+    let _lock = UpdateLock::acquire()?;
+    let (client, host) = wifi_client(&app_handle).await?;
+    let snapshot = load_robot_snapshots(&app_handle)
+        .remove(&host.to_lowercase())
+        .ok_or_else(|| "No previous version available for rollback".to_string())?;
+
+    println!("[update] 🤖 Rolling back robot {} to {}", host, snapshot.version);
+    emit_progress(&app_handle, "rolling_back", None, format!("Restoring reachy-mini {}...", snapshot.version));
+    let index_url = index_for_version(&app_handle, &snapshot.version);
+    let restored = install_on_robot(&app_handle, &client, &snapshot.version, &index_url).await?;
+    if restored != snapshot.version {
+        return Err(format!("Rollback incomplete: expected {}, the robot reports {}", snapshot.version, restored));
+    }
+
+    println!("[update] ✅ Robot {} rolled back to {}", host, restored);
+    Ok(format!("Robot daemon rolled back to {}.", restored))
+}
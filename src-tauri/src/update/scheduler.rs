@@ -0,0 +1,204 @@
+/// Background update check scheduler
+///
+/// Periodically checks for daemon (PyPI) and app (Tauri updater) updates, caches the
+/// result and emits `update-available` when something new is found, honoring the
+/// "skip this version" and "remind me later" choices persisted in settings.
+
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager, State};
+use tauri_plugin_updater::UpdaterExt;
+
+use super::DaemonUpdateInfo;
+use crate::daemon::history::now_millis;
+use crate::settings::{AppSettings, SettingsState};
+
+/// Delay before the first check, so startup isn't slowed down
+const INITIAL_DELAY_SECS: u64 = 30;
+/// How often to re-read settings while checks are disabled
+const DISABLED_POLL_SECS: u64 = 3600;
+const HOUR_MS: u64 = 60 * 60 * 1000;
+/// Longest background check interval (a month)
+const MAX_CHECK_INTERVAL_HOURS: u64 = 30 * 24;
+/// Longest "remind me later" (a year)
+const MAX_REMIND_HOURS: u64 = 365 * 24;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum UpdateKind {
+    Daemon,
+    App,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct AppUpdateInfo {
+    pub current_version: String,
+    pub available_version: String,
+    pub release_notes: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct UpdateCheckResult {
+    /// Unix millis
+    pub checked_at: u64,
+    /// None if the check failed
+    pub daemon: Option<DaemonUpdateInfo>,
+    /// None if the app is up to date or the check failed
+    pub app: Option<AppUpdateInfo>,
+}
+
+/// Last background check result
+#[derive(Default)]
+pub struct UpdateCheckState {
+    last_result: Mutex<Option<UpdateCheckResult>>,
+}
+
+fn skip_key(kind: UpdateKind, version: &str) -> String {
+    match kind {
+        UpdateKind::Daemon => format!("daemon:{}", version),
+        UpdateKind::App => format!("app:{}", version),
+    }
+}
+
+/// Whether the result should be notified given the user's skip / remind-later choices
+fn should_notify(result: &UpdateCheckResult, settings: &AppSettings) -> bool {
+    if settings.update_remind_after.is_some_and(|after| now_millis() < after) {
+        return false;
+    }
+    let is_skipped = |kind, version: &str| settings.skipped_update_versions.contains(&skip_key(kind, version));
+
+    let daemon_new = result
+        .daemon
+        .as_ref()
+        .is_some_and(|d| d.is_available && !is_skipped(UpdateKind::Daemon, &d.available_version));
+    let app_new = result
+        .app
+        .as_ref()
+        .is_some_and(|a| !is_skipped(UpdateKind::App, &a.available_version));
+    daemon_new || app_new
+}
+
+async fn check_app_update(app_handle: &AppHandle) -> Option<AppUpdateInfo> {
+    let updater = match app_handle.updater() {
+        Ok(updater) => updater,
+        Err(e) => {
+            eprintln!("[update-scheduler] ⚠️ Updater unavailable: {}", e);
+            return None;
+        }
+    };
+    match updater.check().await {
+        Ok(update) => update.map(|update| AppUpdateInfo {
+            current_version: update.current_version,
+            available_version: update.version,
+            release_notes: update.body,
+        }),
+        Err(e) => {
+            eprintln!("[update-scheduler] ⚠️ App update check failed: {}", e);
+            None
+        }
+    }
+}
+
+/// Check both daemon and app updates, cache the result and notify if needed
+async fn run_check(app_handle: &AppHandle) -> UpdateCheckResult {
+    let daemon = match super::check_daemon_update(app_handle.clone(), None, None).await {
+        Ok(info) => Some(info),
+        Err(e) => {
+            eprintln!("[update-scheduler] ⚠️ Daemon update check failed: {}", e);
+            None
+        }
+    };
+    let app = check_app_update(app_handle).await;
+
+    let result = UpdateCheckResult {
+        checked_at: now_millis(),
+        daemon,
+        app,
+    };
+    *app_handle.state::<UpdateCheckState>().last_result.lock().unwrap() = Some(result.clone());
+
+    let settings = app_handle.state::<SettingsState>().get();
+    if should_notify(&result, &settings) {
+        println!("[update-scheduler] 🔔 Update available");
+        let _ = app_handle.emit("update-available", result.clone());
+    }
+
+    result
+}
+
+/// Start the background scheduler (call once in setup)
+pub fn start(app_handle: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(tokio::time::Duration::from_secs(INITIAL_DELAY_SECS)).await;
+
+        loop {
+            // Clamped again: the settings file may have been edited by hand
+            let interval_hours = app_handle
+                .state::<SettingsState>()
+                .get()
+                .update_check_interval_hours
+                .min(MAX_CHECK_INTERVAL_HOURS);
+            if interval_hours == 0 || crate::kiosk::is_active() {
+                tokio::time::sleep(tokio::time::Duration::from_secs(DISABLED_POLL_SECS)).await;
+                continue;
+            }
+
+            run_check(&app_handle).await;
+            tokio::time::sleep(tokio::time::Duration::from_secs(interval_hours * 3600)).await;
+        }
+    });
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Last background check result (None if no check ran yet)
+#[tauri::command]
+pub fn get_cached_update_check(state: State<UpdateCheckState>) -> Option<UpdateCheckResult> {
+    state.last_result.lock().unwrap().clone()
+}
+
+/// Run a check now (also refreshes the cache)
+#[tauri::command]
+pub async fn check_updates_now(app_handle: AppHandle) -> Result<UpdateCheckResult, String> {
+    Ok(run_check(&app_handle).await)
+}
+
+/// Never notify about this version again
+#[tauri::command]
+pub fn skip_update_version(
+    state: State<SettingsState>,
+    kind: UpdateKind,
+    version: String,
+) -> Result<AppSettings, String> {
+    let key = skip_key(kind, &version);
+    state.update(|settings| {
+        if !settings.skipped_update_versions.contains(&key) {
+            settings.skipped_update_versions.push(key);
+        }
+    })
+}
+
+/// Silence update notifications for a while (default: 24h)
+#[tauri::command]
+pub fn remind_update_later(state: State<SettingsState>, hours: Option<u64>) -> Result<AppSettings, String> {
+    let hours = hours.unwrap_or(24);
+    if hours > MAX_REMIND_HOURS {
+        return Err(format!("Reminders can be postponed by {} hours at most", MAX_REMIND_HOURS));
+    }
+    state.update(|settings| {
+        settings.update_remind_after = Some(now_millis() + hours * HOUR_MS);
+    })
+}
+
+/// Set the background check interval (0 disables background checks)
+#[tauri::command]
+pub fn set_update_check_interval(state: State<SettingsState>, hours: u64) -> Result<AppSettings, String> {
+    if hours > MAX_CHECK_INTERVAL_HOURS {
+        return Err(format!("The check interval can be {} hours at most", MAX_CHECK_INTERVAL_HOURS));
+    }
+    state.update(|settings| {
+        settings.update_check_interval_hours = hours;
+    })
+}
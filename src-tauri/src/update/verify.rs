@@ -0,0 +1,166 @@
+/// Verified downloads for daemon updates
+///
+/// The reachy-mini wheel (or sdist) is downloaded by us and its sha256 checked against
+/// the digest published in the index JSON API before pip ever sees it. Its dependencies
+/// are then resolved with `pip download` into a wheelhouse, and every file there is
+/// checked the same way; pip finally installs from the wheelhouse only (`--no-index`),
+/// so nothing unverified is installed.
+
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+#[derive(Debug, Deserialize)]
+struct ReleaseFiles {
+    urls: Vec<ReleaseFile>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReleaseFile {
+    filename: String,
+    url: String,
+    packagetype: String,
+    digests: FileDigests,
+    #[serde(default)]
+    yanked: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct FileDigests {
+    sha256: String,
+}
+
+/// Pure-Python wheel first, then sdist
+fn pick_artifact(files: &[ReleaseFile]) -> Option<&ReleaseFile> {
+    let candidates = || files.iter().filter(|f| !f.yanked);
+    candidates()
+        .find(|f| f.packagetype == "bdist_wheel" && f.filename.ends_with("-none-any.whl"))
+        .or_else(|| candidates().find(|f| f.packagetype == "sdist"))
+}
+
+/// Download the artifact of `package_name==version` into `dest_dir`, verifying its sha256
+/// (through the download cache). Returns the path of the verified file
+pub(super) async fn download_verified_artifact(
+    app_handle: &AppHandle,
+    index_url: &str,
+    package_name: &str,
+    version: &str,
+    dest_dir: &Path,
+) -> Result<PathBuf, String> {
+    let url = format!("{}/pypi/{}/{}/json", index_url, package_name, version);
+    let files: ReleaseFiles = reqwest::get(&url)
+        .await
+        .map_err(|e| format!("Failed to fetch release info: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse release info: {}", e))?;
+
+    let artifact = pick_artifact(&files.urls)
+        .ok_or_else(|| format!("No installable artifact published for {} {}", package_name, version))?;
+
+    // Cached by hash: a release already downloaded (or partly downloaded) isn't fetched again
+    let cached = crate::downloads::fetch(app_handle, &artifact.url, &artifact.digests.sha256, &artifact.filename).await?;
+
+    // Only keep the latest verified artifact around
+    let _ = std::fs::remove_dir_all(dest_dir);
+    std::fs::create_dir_all(dest_dir)
+        .map_err(|e| format!("Failed to create download dir: {}", e))?;
+    // pip needs the artifact under its own file name
+    let path = dest_dir.join(&artifact.filename);
+    std::fs::copy(&cached, &path)
+        .map_err(|e| format!("Failed to save {}: {}", artifact.filename, e))?;
+
+    Ok(path)
+}
+
+/// Project and version of a distribution file name
+/// ("reachy_mini-1.2.0-py3-none-any.whl", "numpy-2.0.0.tar.gz")
+fn parse_dist_filename(filename: &str) -> Option<(&str, &str)> {
+    if let Some(stem) = filename.strip_suffix(".whl") {
+        let mut parts = stem.split('-');
+        return Some((parts.next()?, parts.next()?));
+    }
+    let stem = filename.strip_suffix(".tar.gz").or_else(|| filename.strip_suffix(".zip"))?;
+    stem.rsplit_once('-')
+}
+
+/// sha256 the index publishes for `filename` (None if it doesn't list the file)
+async fn published_sha256(index_url: &str, filename: &str) -> Result<Option<String>, String> {
+    let (name, version) = parse_dist_filename(filename)
+        .ok_or_else(|| format!("Unexpected distribution file: {}", filename))?;
+    let url = format!("{}/pypi/{}/{}/json", index_url, name, version);
+    let response = reqwest::get(&url)
+        .await
+        .map_err(|e| format!("Failed to fetch release info of {}: {}", filename, e))?;
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    let files: ReleaseFiles = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse release info of {}: {}", filename, e))?;
+    Ok(files
+        .urls
+        .into_iter()
+        .find(|file| file.filename == filename)
+        .map(|file| file.digests.sha256.to_lowercase()))
+}
+
+/// `pip download` `requirement` and all its dependencies into `dest_dir`, then check
+/// every file against the sha256 published by one of `index_urls`. `pip_args` are
+/// passed to pip download (index and pre-release options).
+pub(super) async fn download_verified_set(
+    pip_path: &Path,
+    requirement: &str,
+    pip_args: &[String],
+    index_urls: &[&str],
+    dest_dir: &Path,
+) -> Result<(), String> {
+    let _ = std::fs::remove_dir_all(dest_dir);
+    std::fs::create_dir_all(dest_dir)
+        .map_err(|e| format!("Failed to create download dir: {}", e))?;
+
+    let mut command = std::process::Command::new(pip_path);
+    command
+        .args(["download", "--disable-pip-version-check", "-d"])
+        .arg(dest_dir)
+        .args(pip_args)
+        .arg(requirement);
+    let output = tauri::async_runtime::spawn_blocking(move || command.output())
+        .await
+        .map_err(|e| format!("pip download task failed: {}", e))?
+        .map_err(|e| format!("Failed to run pip download: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "Failed to download the dependencies:\n{}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    let files: Vec<PathBuf> = std::fs::read_dir(dest_dir)
+        .map_err(|e| format!("Failed to read download dir: {}", e))?
+        .flatten()
+        .map(|entry| entry.path())
+        .collect();
+    for path in files {
+        let filename = path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+        let hash_path = path.clone();
+        let actual = tauri::async_runtime::spawn_blocking(move || crate::downloads::sha256_file(&hash_path))
+            .await
+            .map_err(|e| format!("Hash task failed: {}", e))??;
+
+        let mut published = Vec::new();
+        for index_url in index_urls {
+            if let Some(sha256) = published_sha256(index_url, &filename).await? {
+                published.push(sha256);
+            }
+        }
+        if published.is_empty() {
+            return Err(format!("{} is not published by the index: cannot verify it", filename));
+        }
+        if !published.contains(&actual) {
+            return Err(format!("Hash mismatch for {}: the downloaded file was not the published one", filename));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,113 @@
+// USB device enumeration
+//
+// Lists the VID:PID of every USB device plugged in (not only serial ports), so the
+// monitor can report accessories such as the camera module:
+// - Linux: /sys/bus/usb/devices
+// - macOS: IOUSB registry (ioreg)
+// - Windows: SetupAPI hardware IDs of the USB enumerator
+
+/// VID:PID of every connected USB device (may contain duplicates)
+#[cfg(target_os = "linux")]
+pub fn list_usb_ids() -> Vec<(u16, u16)> {
+    let read_hex = |path: std::path::PathBuf| -> Option<u16> {
+        let content = std::fs::read_to_string(path).ok()?;
+        u16::from_str_radix(content.trim(), 16).ok()
+    };
+
+    let Ok(entries) = std::fs::read_dir("/sys/bus/usb/devices") else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let dir = entry.path();
+            Some((read_hex(dir.join("idVendor"))?, read_hex(dir.join("idProduct"))?))
+        })
+        .collect()
+}
+
+/// VID:PID of every connected USB device (may contain duplicates)
+#[cfg(target_os = "macos")]
+pub fn list_usb_ids() -> Vec<(u16, u16)> {
+    let Ok(output) = std::process::Command::new("ioreg")
+        .args(["-p", "IOUSB", "-l", "-w0"])
+        .output()
+    else {
+        return Vec::new();
+    };
+    let content = String::from_utf8_lossy(&output.stdout);
+
+    // One "+-o" block per device: `"idVendor" = 6790`, `"idProduct" = 21971`
+    let value_of = |block: &str, key: &str| -> Option<u16> {
+        block
+            .lines()
+            .find_map(|line| line.split_once(&format!("\"{}\" = ", key)))
+            .and_then(|(_, value)| value.trim().parse().ok())
+    };
+    content
+        .split("+-o ")
+        .filter_map(|block| Some((value_of(block, "idVendor")?, value_of(block, "idProduct")?)))
+        .collect()
+}
+
+/// VID:PID of every connected USB device (may contain duplicates)
+#[cfg(target_os = "windows")]
+pub fn list_usb_ids() -> Vec<(u16, u16)> {
+    use windows::core::w;
+    use windows::Win32::Devices::DeviceAndDriverInstallation::{
+        SetupDiDestroyDeviceInfoList, SetupDiEnumDeviceInfo, SetupDiGetClassDevsW,
+        SetupDiGetDeviceRegistryPropertyW, DIGCF_ALLCLASSES, DIGCF_PRESENT, SPDRP_HARDWAREID, SP_DEVINFO_DATA,
+    };
+
+    let mut ids = Vec::new();
+    unsafe {
+        let Ok(devices) = SetupDiGetClassDevsW(None, w!("USB"), None, DIGCF_ALLCLASSES | DIGCF_PRESENT) else {
+            return ids;
+        };
+
+        let mut index = 0;
+        loop {
+            let mut info = SP_DEVINFO_DATA {
+                cbSize: std::mem::size_of::<SP_DEVINFO_DATA>() as u32,
+                ..Default::default()
+            };
+            if SetupDiEnumDeviceInfo(devices, index, &mut info).is_err() {
+                break;
+            }
+            index += 1;
+
+            // REG_MULTI_SZ (UTF-16), first entry: "USB\VID_1A86&PID_55D3&REV_0444"
+            let mut buffer = [0u8; 1024];
+            if SetupDiGetDeviceRegistryPropertyW(devices, &info, SPDRP_HARDWAREID, None, Some(&mut buffer), None).is_err() {
+                continue;
+            }
+            let wide: Vec<u16> = buffer
+                .chunks_exact(2)
+                .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+                .take_while(|c| *c != 0)
+                .collect();
+            if let Some(id) = parse_hardware_id(&String::from_utf16_lossy(&wide)) {
+                ids.push(id);
+            }
+        }
+
+        let _ = SetupDiDestroyDeviceInfoList(devices);
+    }
+    ids
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+pub fn list_usb_ids() -> Vec<(u16, u16)> {
+    Vec::new()
+}
+
+/// "USB\VID_1A86&PID_55D3&REV_0444" -> (0x1a86, 0x55d3)
+#[cfg(target_os = "windows")]
+fn parse_hardware_id(hardware_id: &str) -> Option<(u16, u16)> {
+    let upper = hardware_id.to_uppercase();
+    let hex_after = |key: &str| -> Option<u16> {
+        let start = upper.find(key)? + key.len();
+        u16::from_str_radix(upper.get(start..start + 4)?, 16).ok()
+    };
+    Some((hex_after("VID_")?, hex_after("PID_")?))
+}
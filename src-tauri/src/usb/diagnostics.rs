@@ -0,0 +1,252 @@
+/// USB link diagnostics
+///
+/// Short test of the serial link to the motor bus, to tell bad cables and hubs
+/// apart from software issues when the robot "feels laggy":
+/// - round-trip latency: individual pings to every motor
+/// - throughput: repeated reads of a motor's control table
+/// - error rate: timeouts and corrupted replies over the whole test
+///
+/// The daemon is stopped during the test (it holds the port) and restarted afterwards.
+
+use serde::Serialize;
+use std::io::{Read, Write};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager};
+
+use super::identity::{build_packet, dynamixel_crc, ping_motors, BUS_BAUDRATE, INSTRUCTION_PING, INSTRUCTION_STATUS, PACKET_HEADER};
+use crate::daemon::DaemonState;
+
+const PING_ROUNDS: usize = 50;
+const READ_ROUNDS: usize = 100;
+/// Shorter test for the app self-check (diagnostics module)
+const QUICK_PING_ROUNDS: usize = 5;
+const QUICK_READ_ROUNDS: usize = 10;
+const INSTRUCTION_READ: u8 = 0x02;
+/// Control table block read for the throughput test (EEPROM area, present on every model)
+const READ_LENGTH: u16 = 64;
+const REPLY_TIMEOUT: Duration = Duration::from_millis(50);
+const PORT_RELEASE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// p95 latency / error rate above which the link is reported as degraded or bad
+const DEGRADED_LATENCY_MS: f64 = 5.0;
+const BAD_LATENCY_MS: f64 = 20.0;
+const DEGRADED_ERROR_RATE: f64 = 0.005;
+const BAD_ERROR_RATE: f64 = 0.05;
+
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct LatencyStats {
+    pub min_ms: f64,
+    pub avg_ms: f64,
+    pub p95_ms: f64,
+    pub max_ms: f64,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct UsbDiagnosticsReport {
+    pub port_name: String,
+    pub baud_rate: u32,
+    /// Physical USB location (a long path usually means a hub in between)
+    pub location: Option<String>,
+    pub motor_ids: Vec<u8>,
+    pub requests: u32,
+    pub timeouts: u32,
+    pub corrupted: u32,
+    pub error_rate: f64,
+    pub latency: LatencyStats,
+    pub throughput_bytes_per_sec: f64,
+    pub duration_ms: u64,
+    /// "good" | "degraded" | "bad"
+    pub verdict: &'static str,
+}
+
+enum ReplyError {
+    Timeout,
+    Corrupted,
+}
+
+/// Read one status packet, returning its parameters
+fn read_status(port: &mut Box<dyn serialport::SerialPort>, expected_id: u8) -> Result<Vec<u8>, ReplyError> {
+    let deadline = Instant::now() + REPLY_TIMEOUT;
+    let mut received = Vec::new();
+    let mut chunk = [0u8; 256];
+
+    while Instant::now() < deadline {
+        match port.read(&mut chunk) {
+            Ok(len) => received.extend_from_slice(&chunk[..len]),
+            Err(e) if e.kind() == std::io::ErrorKind::TimedOut => continue,
+            Err(_) => return Err(ReplyError::Corrupted),
+        }
+
+        let Some(start) = received.windows(PACKET_HEADER.len()).position(|w| w == PACKET_HEADER) else {
+            continue;
+        };
+        let Some(length_bytes) = received.get(start + 5..start + 7) else { continue };
+        let length = u16::from_le_bytes([length_bytes[0], length_bytes[1]]) as usize;
+        let Some(packet) = received.get(start..start + 7 + length) else { continue };
+
+        // header(4) id(1) len(2) | instruction(1) error(1) params... crc(2)
+        let crc_offset = packet.len() - 2;
+        let crc = u16::from_le_bytes([packet[crc_offset], packet[crc_offset + 1]]);
+        if length < 4 || packet[4] != expected_id || packet[7] != INSTRUCTION_STATUS || crc != dynamixel_crc(&packet[..crc_offset]) {
+            return Err(ReplyError::Corrupted);
+        }
+        return Ok(packet[9..crc_offset].to_vec());
+    }
+
+    Err(ReplyError::Timeout)
+}
+
+fn latency_stats(mut samples: Vec<f64>) -> LatencyStats {
+    if samples.is_empty() {
+        return LatencyStats::default();
+    }
+    samples.sort_by(|a, b| a.total_cmp(b));
+    let p95_index = ((samples.len() as f64 * 0.95).ceil() as usize).saturating_sub(1);
+    LatencyStats {
+        min_ms: samples[0],
+        avg_ms: samples.iter().sum::<f64>() / samples.len() as f64,
+        p95_ms: samples[p95_index],
+        max_ms: samples[samples.len() - 1],
+    }
+}
+
+fn verdict(error_rate: f64, latency: &LatencyStats) -> &'static str {
+    if error_rate > BAD_ERROR_RATE || latency.p95_ms > BAD_LATENCY_MS {
+        "bad"
+    } else if error_rate > DEGRADED_ERROR_RATE || latency.p95_ms > DEGRADED_LATENCY_MS {
+        "degraded"
+    } else {
+        "good"
+    }
+}
+
+/// Run the test on a free port
+fn run_diagnostics(port_name: &str, ping_rounds: usize, read_rounds: usize) -> Result<UsbDiagnosticsReport, String> {
+    let started = Instant::now();
+    let motor_ids: Vec<u8> = ping_motors(port_name)?.iter().map(|motor| motor.id).collect();
+    if motor_ids.is_empty() {
+        return Err("No motor answered on the bus (is the robot powered?)".to_string());
+    }
+
+    let mut port = serialport::new(port_name, BUS_BAUDRATE)
+        .timeout(Duration::from_millis(5))
+        .open()
+        .map_err(|e| format!("Failed to open {}: {}", port_name, e))?;
+    let _ = port.clear(serialport::ClearBuffer::All);
+
+    let mut requests = 0u32;
+    let mut timeouts = 0u32;
+    let mut corrupted = 0u32;
+    let mut send = |port: &mut Box<dyn serialport::SerialPort>, id: u8, instruction: u8, params: &[u8]| {
+        requests += 1;
+        let _ = port.clear(serialport::ClearBuffer::Input);
+        port.write_all(&build_packet(id, instruction, params))
+            .map_err(|e| format!("Failed to write to {}: {}", port_name, e))?;
+        let reply = read_status(port, id);
+        match &reply {
+            Err(ReplyError::Timeout) => timeouts += 1,
+            Err(ReplyError::Corrupted) => corrupted += 1,
+            Ok(_) => {}
+        }
+        Ok::<_, String>(reply.ok())
+    };
+
+    // 1. Round-trip latency
+    let mut latencies = Vec::new();
+    for _ in 0..ping_rounds {
+        for &id in &motor_ids {
+            let sent_at = Instant::now();
+            if send(&mut port, id, INSTRUCTION_PING, &[])?.is_some() {
+                latencies.push(sent_at.elapsed().as_secs_f64() * 1000.0);
+            }
+        }
+    }
+
+    // 2. Throughput (payload bytes received per second)
+    let mut read_params = 0u16.to_le_bytes().to_vec();
+    read_params.extend_from_slice(&READ_LENGTH.to_le_bytes());
+    let mut bytes_received = 0usize;
+    let read_started = Instant::now();
+    for round in 0..read_rounds {
+        let id = motor_ids[round % motor_ids.len()];
+        if let Some(data) = send(&mut port, id, INSTRUCTION_READ, &read_params)? {
+            bytes_received += data.len();
+        }
+    }
+    let read_secs = read_started.elapsed().as_secs_f64();
+
+    let latency = latency_stats(latencies);
+    let error_rate = (timeouts + corrupted) as f64 / requests.max(1) as f64;
+
+    Ok(UsbDiagnosticsReport {
+        port_name: port_name.to_string(),
+        baud_rate: BUS_BAUDRATE,
+        location: super::get_reachy_robots()
+            .into_iter()
+            .find(|robot| robot.port_name == port_name)
+            .and_then(|robot| robot.location),
+        motor_ids,
+        requests,
+        timeouts,
+        corrupted,
+        error_rate,
+        verdict: verdict(error_rate, &latency),
+        latency,
+        throughput_bytes_per_sec: if read_secs > 0.0 { bytes_received as f64 / read_secs } else { 0.0 },
+        duration_ms: started.elapsed().as_millis() as u64,
+    })
+}
+
+/// A few rounds of the test, on a port the daemon doesn't hold
+pub fn run_quick_diagnostics(port_name: &str) -> Result<UsbDiagnosticsReport, String> {
+    run_diagnostics(port_name, QUICK_PING_ROUNDS, QUICK_READ_ROUNDS)
+}
+
+/// Measure latency, throughput and error rate of the robot's USB link
+#[tauri::command]
+pub async fn run_usb_diagnostics(app_handle: AppHandle, port: Option<String>) -> Result<UsbDiagnosticsReport, String> {
+    let port = port
+        .or_else(super::get_reachy_port)
+        .ok_or("No Reachy Mini connected over USB")?;
+
+    let daemon_running = app_handle.state::<DaemonState>().process.lock().unwrap().is_some();
+    crate::serial_console::release_for_daemon(&app_handle);
+
+    let handle = app_handle.clone();
+    let test_port = port.clone();
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        if daemon_running {
+            println!("[USB Diagnostics] Stopping daemon to free {}", test_port);
+            crate::daemon::kill_daemon(&handle.state::<DaemonState>());
+        }
+        super::wait_for_port_release(&test_port, PORT_RELEASE_TIMEOUT)?;
+        run_diagnostics(&test_port, PING_ROUNDS, READ_ROUNDS)
+    })
+    .await
+    .map_err(|e| format!("Task join error: {}", e))
+    .and_then(|r| r);
+
+    if daemon_running {
+        let handle = app_handle.clone();
+        let daemon_port = port.clone();
+        let restarted = tauri::async_runtime::spawn_blocking(move || {
+            crate::start_daemon(handle.clone(), handle.state::<DaemonState>(), Some(false), None, Some(daemon_port))
+        })
+        .await;
+        if !matches!(restarted, Ok(Ok(_))) {
+            eprintln!("[USB Diagnostics] ⚠️ Failed to restart daemon after the test");
+        }
+    }
+
+    if let Ok(report) = &result {
+        println!(
+            "[USB Diagnostics] {}: p95 {:.2}ms, {:.1}% errors, {:.0} B/s -> {}",
+            report.port_name,
+            report.latency.p95_ms,
+            report.error_rate * 100.0,
+            report.throughput_bytes_per_sec,
+            report.verdict
+        );
+    }
+    result
+}
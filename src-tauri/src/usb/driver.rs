@@ -0,0 +1,160 @@
+/// USB serial driver check (Windows)
+///
+/// Fresh Windows installs may have no driver for the robot's WCH bridge (1a86:55d3):
+/// the board then shows up as an unknown device without a COM port, and the monitor
+/// finds no robot. The PnP entry of the device tells whether a driver is bound and
+/// working (`ConfigManagerErrorCode`, 28 = no driver installed).
+///
+/// Installing needs the vendor driver: `open_usb_driver_download` opens its download
+/// page. Its installer binds the driver to the plugged-in board (replugging works too).
+
+use serde::Serialize;
+use tauri::AppHandle;
+use tauri_plugin_opener::OpenerExt;
+
+#[cfg(target_os = "windows")]
+use super::monitor::REACHY_MINI_USB_ID;
+
+/// WCH driver for the CH34x USB-serial bridges (covers 1a86:55d3)
+pub const DRIVER_DOWNLOAD_URL: &str = "https://www.wch-ic.com/downloads/CH343SER_EXE.html";
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+pub enum DriverState {
+    /// The OS driver is built in (macOS, Linux)
+    NotNeeded,
+    /// No robot plugged in: nothing to check
+    NoDevice,
+    Working,
+    /// Device enumerated without a driver
+    Missing,
+    /// Driver bound but the device doesn't start
+    Failed,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct UsbDriverReport {
+    pub state: DriverState,
+    /// Device name in the Device Manager
+    pub device_name: Option<String>,
+    /// Driver service (e.g. "CH343SER", "usbser")
+    pub driver: Option<String>,
+    /// Device Manager error code
+    pub problem_code: Option<u64>,
+    pub message: String,
+    pub download_url: &'static str,
+}
+
+// ============================================================================
+// HELPER FUNCTIONS
+// ============================================================================
+
+/// PnP entries of the robot's USB bridge
+#[cfg(target_os = "windows")]
+fn pnp_entries() -> Result<Vec<serde_json::Value>, String> {
+    use std::process::Command;
+
+    let (vid, pid) = REACHY_MINI_USB_ID;
+    let script = format!(
+        "Get-CimInstance Win32_PnPEntity -Filter \"PNPDeviceID LIKE 'USB\\\\VID_{:04X}&PID_{:04X}%'\" | \
+         Select-Object Name,Service,ConfigManagerErrorCode | ConvertTo-Json",
+        vid, pid
+    );
+    let output = Command::new("powershell")
+        .args(["-NoProfile", "-Command", &script])
+        .output()
+        .map_err(|e| format!("Failed to run powershell: {}", e))?;
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap_or(serde_json::Value::Null);
+    // A single device is serialized as an object, several as an array
+    Ok(match json {
+        serde_json::Value::Array(entries) => entries,
+        serde_json::Value::Null => Vec::new(),
+        entry => vec![entry],
+    })
+}
+
+#[cfg(target_os = "windows")]
+fn report(state: DriverState, entry: Option<&serde_json::Value>, message: String) -> UsbDriverReport {
+    UsbDriverReport {
+        state,
+        device_name: entry.and_then(|entry| entry["Name"].as_str()).map(str::to_string),
+        driver: entry.and_then(|entry| entry["Service"].as_str()).map(str::to_string),
+        problem_code: entry.and_then(|entry| entry["ConfigManagerErrorCode"].as_u64()).filter(|code| *code != 0),
+        message,
+        download_url: DRIVER_DOWNLOAD_URL,
+    }
+}
+
+// ============================================================================
+// PUBLIC API
+// ============================================================================
+
+/// Driver state of the robot's USB bridge (blocking: runs PowerShell on Windows)
+#[cfg(target_os = "windows")]
+pub fn driver_status() -> Result<UsbDriverReport, String> {
+    let entries = pnp_entries()?;
+    let code = |entry: &serde_json::Value| entry["ConfigManagerErrorCode"].as_u64().unwrap_or(0);
+
+    // With several robots, report the first broken one
+    let Some(entry) = entries.iter().find(|entry| code(entry) != 0).or(entries.first()) else {
+        return Ok(report(DriverState::NoDevice, None, "No Reachy Mini board plugged in".to_string()));
+    };
+    Ok(match code(entry) {
+        0 => {
+            let message = format!("Driver {} working", entry["Service"].as_str().unwrap_or("(unknown)"));
+            report(DriverState::Working, Some(entry), message)
+        }
+        28 => report(
+            DriverState::Missing,
+            Some(entry),
+            "The robot is plugged in but has no driver: install the WCH CH34x serial driver, then replug the robot"
+                .to_string(),
+        ),
+        code => report(
+            DriverState::Failed,
+            Some(entry),
+            format!(
+                "The robot's USB driver doesn't start (Device Manager error {}): reinstall the WCH CH34x serial driver, then replug the robot",
+                code
+            ),
+        ),
+    })
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn driver_status() -> Result<UsbDriverReport, String> {
+    Ok(UsbDriverReport {
+        state: DriverState::NotNeeded,
+        device_name: None,
+        driver: None,
+        problem_code: None,
+        message: "The serial driver is built into the OS".to_string(),
+        download_url: DRIVER_DOWNLOAD_URL,
+    })
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Driver state of the robot's USB bridge (Windows; `not_needed` elsewhere)
+#[tauri::command]
+pub async fn get_usb_driver_status() -> Result<UsbDriverReport, String> {
+    tauri::async_runtime::spawn_blocking(driver_status)
+        .await
+        .map_err(|e| format!("Task join error: {}", e))?
+}
+
+/// Open the driver download page in the browser (guided install)
+#[tauri::command]
+pub fn open_usb_driver_download(app_handle: AppHandle) -> Result<(), String> {
+    app_handle
+        .opener()
+        .open_url(DRIVER_DOWNLOAD_URL, None::<&str>)
+        .map_err(|e| format!("Failed to open the driver download page: {}", e))
+}
@@ -0,0 +1,197 @@
+/// Robot identity readout
+///
+/// Combines what the USB descriptor tells us (serial number, device release) with
+/// a short Dynamixel Protocol 2.0 broadcast ping on the serial port, which lists the
+/// motors on the bus with their model and firmware.
+///
+/// The bus is only touched when the daemon is stopped: it holds the port otherwise.
+
+use serde::Serialize;
+use std::io::{Read, Write};
+use std::time::{Duration, Instant};
+
+use super::get_reachy_robots;
+
+/// Motor bus baudrate used by the daemon
+pub(super) const BUS_BAUDRATE: u32 = 1_000_000;
+/// How long to collect ping replies (each motor answers after its return delay)
+const PING_LISTEN_TIME: Duration = Duration::from_millis(300);
+const BROADCAST_ID: u8 = 0xFE;
+pub(super) const INSTRUCTION_PING: u8 = 0x01;
+pub(super) const INSTRUCTION_STATUS: u8 = 0x55;
+const INSTRUCTION_WRITE: u8 = 0x03;
+/// Control table address of Torque Enable (XL330 / XC330)
+const TORQUE_ENABLE_ADDRESS: u16 = 64;
+pub(super) const PACKET_HEADER: [u8; 4] = [0xFF, 0xFF, 0xFD, 0x00];
+
+#[derive(Debug, Serialize, Clone)]
+pub struct MotorInfo {
+    pub id: u8,
+    pub model_number: u16,
+    /// Known model name (None for unknown model numbers)
+    pub model: Option<&'static str>,
+    pub firmware_version: u8,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct RobotIdentity {
+    pub port_name: String,
+    pub serial_number: Option<String>,
+    pub location: Option<String>,
+    /// Device release number reported over USB (bcdDevice)
+    pub hardware_revision: Option<String>,
+    /// Motors answering on the bus (None if the bus couldn't be read, e.g. daemon running)
+    pub motors: Option<Vec<MotorInfo>>,
+}
+
+// ============================================================================
+// DYNAMIXEL PROTOCOL 2.0
+// ============================================================================
+
+/// CRC-16 used by Dynamixel Protocol 2.0 (polynomial 0x8005, not reflected)
+pub(super) fn dynamixel_crc(data: &[u8]) -> u16 {
+    let mut crc: u16 = 0;
+    for &byte in data {
+        crc ^= (byte as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 { (crc << 1) ^ 0x8005 } else { crc << 1 };
+        }
+    }
+    crc
+}
+
+/// Instruction packet: header, id, length, instruction, params, CRC
+pub(super) fn build_packet(id: u8, instruction: u8, params: &[u8]) -> Vec<u8> {
+    let length = (params.len() + 3) as u16; // instruction + params + CRC
+    let mut packet = PACKET_HEADER.to_vec();
+    packet.push(id);
+    packet.extend_from_slice(&length.to_le_bytes());
+    packet.push(instruction);
+    packet.extend_from_slice(params);
+    let crc = dynamixel_crc(&packet);
+    packet.extend_from_slice(&crc.to_le_bytes());
+    packet
+}
+
+fn build_broadcast_ping() -> Vec<u8> {
+    build_packet(BROADCAST_ID, INSTRUCTION_PING, &[])
+}
+
+fn model_name(model_number: u16) -> Option<&'static str> {
+    match model_number {
+        1190 => Some("XL330-M077"),
+        1200 => Some("XL330-M288"),
+        1230 => Some("XC330-M181"),
+        1240 => Some("XC330-M288"),
+        _ => None,
+    }
+}
+
+/// Parse every valid ping status packet in the received bytes
+fn parse_ping_replies(data: &[u8]) -> Vec<MotorInfo> {
+    let mut motors = Vec::new();
+    let mut offset = 0;
+
+    while let Some(start) = data[offset..]
+        .windows(PACKET_HEADER.len())
+        .position(|window| window == PACKET_HEADER)
+        .map(|position| offset + position)
+    {
+        // header(4) id(1) len(2) | instruction(1) error(1) model(2) firmware(1) crc(2)
+        let Some(packet) = data.get(start..start + 14) else { break };
+        let length = u16::from_le_bytes([packet[5], packet[6]]);
+        let crc = u16::from_le_bytes([packet[12], packet[13]]);
+
+        if length == 7 && packet[7] == INSTRUCTION_STATUS && crc == dynamixel_crc(&packet[..12]) {
+            let model_number = u16::from_le_bytes([packet[9], packet[10]]);
+            motors.push(MotorInfo {
+                id: packet[4],
+                model_number,
+                model: model_name(model_number),
+                firmware_version: packet[11],
+            });
+            offset = start + 14;
+        } else {
+            offset = start + 1;
+        }
+    }
+
+    motors.sort_by_key(|motor| motor.id);
+    motors.dedup_by_key(|motor| motor.id);
+    motors
+}
+
+/// Open the port briefly and ping every motor on the bus
+pub(super) fn ping_motors(port_name: &str) -> Result<Vec<MotorInfo>, String> {
+    let mut port = serialport::new(port_name, BUS_BAUDRATE)
+        .timeout(Duration::from_millis(20))
+        .open()
+        .map_err(|e| format!("Failed to open {}: {}", port_name, e))?;
+    let _ = port.clear(serialport::ClearBuffer::All);
+
+    port.write_all(&build_broadcast_ping())
+        .map_err(|e| format!("Failed to write to {}: {}", port_name, e))?;
+
+    let mut received = Vec::new();
+    let mut buffer = [0u8; 256];
+    let deadline = Instant::now() + PING_LISTEN_TIME;
+    while Instant::now() < deadline {
+        match port.read(&mut buffer) {
+            Ok(len) => received.extend_from_slice(&buffer[..len]),
+            Err(e) if e.kind() == std::io::ErrorKind::TimedOut => {}
+            Err(e) => return Err(format!("Failed to read from {}: {}", port_name, e)),
+        }
+    }
+
+    Ok(parse_ping_replies(&received))
+}
+
+/// Broadcast Torque Enable = 0: every motor on the bus goes compliant at once
+/// (no replies to broadcast writes). Only works on a port the daemon doesn't hold.
+pub fn broadcast_torque_off(port_name: &str) -> Result<(), String> {
+    let mut port = serialport::new(port_name, BUS_BAUDRATE)
+        .timeout(Duration::from_millis(20))
+        .open()
+        .map_err(|e| format!("Failed to open {}: {}", port_name, e))?;
+    let mut params = TORQUE_ENABLE_ADDRESS.to_le_bytes().to_vec();
+    params.push(0);
+    let packet = build_packet(BROADCAST_ID, INSTRUCTION_WRITE, &params);
+    // A corrupted packet is silently dropped by the motors: send it a few times
+    for _ in 0..3 {
+        port.write_all(&packet)
+            .map_err(|e| format!("Failed to write to {}: {}", port_name, e))?;
+        let _ = port.flush();
+        std::thread::sleep(Duration::from_millis(5));
+    }
+    Ok(())
+}
+
+/// Read the identity of the robot on `port_name`
+/// `read_bus`: false when the daemon holds the port (USB descriptor info only)
+pub fn read_robot_identity(port_name: &str, read_bus: bool) -> Result<RobotIdentity, String> {
+    let robot = get_reachy_robots()
+        .into_iter()
+        .find(|robot| robot.port_name == port_name)
+        .ok_or_else(|| format!("No Reachy Mini connected on {}", port_name))?;
+
+    let motors = if read_bus {
+        match ping_motors(port_name) {
+            Ok(motors) => Some(motors),
+            Err(e) => {
+                eprintln!("[USB Identity] ⚠️ {}", e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    Ok(RobotIdentity {
+        hardware_revision: crate::versions::get_firmware_version(port_name),
+        port_name: robot.port_name,
+        serial_number: robot.serial_number,
+        location: robot.location,
+        motors,
+    })
+}
+
@@ -1,19 +1,67 @@
 /// USB Device Detection Module
 /// 
-/// This module provides USB device detection with two strategies:
+/// This module provides USB device detection with a background monitor per platform:
 /// - Windows: Event-driven detection using WM_DEVICECHANGE (NO polling, NO terminal flicker)
-/// - Other platforms: Direct detection (no background monitoring needed)
+/// - Linux: Event-driven detection using udev
+/// - macOS: Low-frequency polling of the serial port list
+///
+/// Plug/unplug transitions are pushed as `usb-robot-connected` / `usb-robot-disconnected` events.
 
+mod devices;
+pub mod diagnostics;
+pub mod driver;
+pub mod identity;
 mod monitor;
+pub mod registry;
 
-pub use monitor::start_monitor;
+pub use monitor::{
+    default_watch_list, get_reachy_port, get_reachy_robots, get_usb_devices, set_watch_list, start_monitor,
+    UsbDeviceStatus, UsbRobot, UsbWatchEntry,
+};
+// Only needed outside this module by the macOS firmware readout (versions)
+#[cfg(target_os = "macos")]
+pub use monitor::REACHY_MINI_USB_ID;
 
-/// Check if Reachy Mini USB robot is connected
+use crate::settings::SettingsState;
+use tauri::State;
+
+/// List every Reachy Mini USB robot connected (empty if none)
 /// 
-/// On Windows: Uses event-driven detection (no polling, no terminal flicker)
-/// On other platforms: Direct check using serialport
+/// Returns the port cached by the monitor (no enumeration on each call)
+#[tauri::command]
+pub fn check_usb_robot() -> Result<Vec<UsbRobot>, String> {
+    Ok(get_reachy_robots())
+}
+
+/// Presence of every device in the USB watch list
 #[tauri::command]
-pub fn check_usb_robot() -> Result<Option<String>, String> {
-    Ok(monitor::get_reachy_port())
+pub fn get_usb_devices_status() -> Vec<UsbDeviceStatus> {
+    get_usb_devices()
 }
 
+/// Replace (and persist) the USB watch list; an empty list restores the default
+#[tauri::command]
+pub fn set_usb_watch_list(
+    state: State<SettingsState>,
+    watch_list: Vec<UsbWatchEntry>,
+) -> Result<Vec<UsbDeviceStatus>, String> {
+    let watch_list = if watch_list.is_empty() { default_watch_list() } else { watch_list };
+    state.update(|settings| settings.usb_watch_list = watch_list.clone())?;
+    set_watch_list(watch_list);
+    monitor::force_update();
+    Ok(get_usb_devices())
+}
+
+/// Wait until the serial port can be opened (e.g. daemon fully gone after being stopped)
+pub fn wait_for_port_release(port: &str, timeout: std::time::Duration) -> Result<(), String> {
+    let deadline = std::time::Instant::now() + timeout;
+    loop {
+        match serialport::new(port, 115_200).open() {
+            Ok(_) => return Ok(()),
+            Err(e) if std::time::Instant::now() >= deadline => {
+                return Err(format!("Serial port {} is still busy: {}", port, e));
+            }
+            Err(_) => std::thread::sleep(std::time::Duration::from_millis(250)),
+        }
+    }
+}
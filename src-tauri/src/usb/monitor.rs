@@ -1,9 +1,18 @@
-/// USB Device Monitor - Event-driven USB detection for Windows
+/// USB Device Monitor - Event-driven USB detection
 /// 
-/// This module provides event-driven USB device detection using Windows WM_DEVICECHANGE messages.
-/// This completely eliminates the need for polling, preventing terminal flicker issues on Windows.
+/// This module keeps a cached view of the connected Reachy Mini robots and of the
+/// devices in the watch list (accessories), and pushes `usb-robot-connected` /
+/// `usb-robot-disconnected` and `usb-device-connected` / `usb-device-disconnected`
+/// events to the frontend:
+/// - Windows: WM_DEVICECHANGE messages on a hidden message-only window (no polling, no terminal flicker)
+/// - Linux: udev monitor on the tty and usb subsystems
+/// - macOS (and others): low-frequency poller on the serial port list
 
-use std::sync::{Arc, Mutex};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex, OnceLock};
+use tauri::{AppHandle, Emitter, Manager};
+
+use super::registry::RobotRegistryState;
 
 #[cfg(target_os = "windows")]
 use windows::{
@@ -13,88 +22,262 @@ use windows::{
     Win32::UI::WindowsAndMessaging::*,
 };
 
+/// Poll interval for platforms without device notifications
+#[cfg(not(target_os = "windows"))]
+const POLL_INTERVAL_MS: u64 = 2000;
+
+/// Reachy Mini USB-to-serial bridge (VID:PID = 1a86:55d3 - CH340)
+pub const REACHY_MINI_USB_ID: (u16, u16) = (0x1a86, 0x55d3);
+
+/// A USB device to watch for (robot, camera module, gripper...)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct UsbWatchEntry {
+    pub vid: u16,
+    pub pid: u16,
+    pub label: String,
+}
+
+/// Watch list used until the user configures one
+pub fn default_watch_list() -> Vec<UsbWatchEntry> {
+    vec![UsbWatchEntry {
+        vid: REACHY_MINI_USB_ID.0,
+        pid: REACHY_MINI_USB_ID.1,
+        label: "Reachy Mini".to_string(),
+    }]
+}
+
+/// Presence of a watched device
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct UsbDeviceStatus {
+    #[serde(flatten)]
+    pub entry: UsbWatchEntry,
+    pub present: bool,
+}
+
+/// A connected Reachy Mini
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct UsbRobot {
+    pub port_name: String,
+    pub serial_number: Option<String>,
+    /// Physical USB location (e.g. "1-1.2" on Linux, locationID on macOS)
+    pub location: Option<String>,
+}
+
 /// Shared state for USB device monitoring
 pub struct UsbMonitorState {
-    /// Current Reachy Mini port (VID:PID = 1a86:55d3)
-    pub reachy_port: Option<String>,
+    /// Connected Reachy Mini robots, sorted by port name
+    pub robots: Vec<UsbRobot>,
     /// All available serial ports with their info
     pub available_ports: Vec<serialport::SerialPortInfo>,
+    /// Devices to report presence for
+    pub watch_list: Vec<UsbWatchEntry>,
+    /// Presence of each watched device, in watch list order
+    pub devices: Vec<UsbDeviceStatus>,
 }
 
 impl UsbMonitorState {
     pub fn new() -> Self {
         UsbMonitorState {
-            reachy_port: None,
+            robots: Vec::new(),
             available_ports: Vec::new(),
+            watch_list: default_watch_list(),
+            devices: Vec::new(),
         }
     }
 
-    /// Update the list of available ports and find Reachy Mini
+    /// Update the list of available ports, find every Reachy Mini and watched device
     pub fn update(&mut self) {
         match serialport::available_ports() {
             Ok(ports) => {
                 self.available_ports = ports.clone();
                 
-                // Find Reachy Mini port (VID:PID = 1a86:55d3 - CH340 USB-to-serial)
-                self.reachy_port = ports.iter()
-                    .find_map(|port| {
+                // Find Reachy Mini ports
+                let mut robots: Vec<UsbRobot> = ports.iter()
+                    .filter_map(|port| {
                         if let serialport::SerialPortType::UsbPort(usb_info) = &port.port_type {
-                            if usb_info.vid == 0x1a86 && usb_info.pid == 0x55d3 {
-                                return Some(port.port_name.clone());
+                            if (usb_info.vid, usb_info.pid) == REACHY_MINI_USB_ID {
+                                return Some(UsbRobot {
+                                    port_name: port.port_name.clone(),
+                                    serial_number: usb_info.serial_number.clone(),
+                                    location: get_usb_location(&port.port_name, usb_info.serial_number.as_deref()),
+                                });
                             }
                         }
                         None
-                    });
+                    })
+                    .collect();
+                robots.sort_by(|a, b| a.port_name.cmp(&b.port_name));
+                self.robots = robots;
             }
             Err(e) => {
                 eprintln!("[USB Monitor] Failed to enumerate ports: {}", e);
             }
         }
+
+        self.update_devices();
+    }
+
+    /// Refresh the presence of watched devices
+    /// Serial ports are enough while only USB-to-serial devices are watched
+    fn update_devices(&mut self) {
+        let serial_ids: Vec<(u16, u16)> = self.available_ports.iter()
+            .filter_map(|port| match &port.port_type {
+                serialport::SerialPortType::UsbPort(usb_info) => Some((usb_info.vid, usb_info.pid)),
+                _ => None,
+            })
+            .collect();
+        let needs_enumeration = self.watch_list.iter()
+            .any(|entry| (entry.vid, entry.pid) != REACHY_MINI_USB_ID);
+        let ids = if needs_enumeration {
+            let mut ids = super::devices::list_usb_ids();
+            ids.extend(serial_ids);
+            ids
+        } else {
+            serial_ids
+        };
+
+        self.devices = self.watch_list.iter()
+            .map(|entry| UsbDeviceStatus {
+                entry: entry.clone(),
+                present: ids.contains(&(entry.vid, entry.pid)),
+            })
+            .collect();
     }
 }
 
-#[cfg(target_os = "windows")]
+/// USB bus path of the device behind a tty, read from sysfs
+#[cfg(target_os = "linux")]
+fn get_usb_location(port: &str, _serial_number: Option<&str>) -> Option<String> {
+    use std::path::Path;
+
+    let tty = Path::new(port).file_name()?;
+    let device = std::fs::canonicalize(Path::new("/sys/class/tty").join(tty).join("device")).ok()?;
+
+    // The USB device directory (e.g. .../1-1.2) is the first ancestor with a devpath file
+    device
+        .ancestors()
+        .find(|dir| dir.join("devpath").exists())
+        .and_then(|dir| dir.file_name())
+        .map(|name| name.to_string_lossy().to_string())
+}
+
+/// locationID of the USB device with this serial number, read from the IOUSB registry
+#[cfg(target_os = "macos")]
+fn get_usb_location(_port: &str, serial_number: Option<&str>) -> Option<String> {
+    let serial_number = serial_number?;
+    let output = std::process::Command::new("ioreg")
+        .args(["-p", "IOUSB", "-l", "-w0"])
+        .output()
+        .ok()?;
+    let content = String::from_utf8_lossy(&output.stdout);
+
+    // One "+-o" block per device: `"USB Serial Number" = "5A7A..."`, `"locationID" = 336592896`
+    let value_of = |block: &str, key: &str| -> Option<String> {
+        block
+            .lines()
+            .find_map(|line| line.split_once(&format!("\"{}\" = ", key)))
+            .map(|(_, value)| value.trim().trim_matches('"').to_string())
+    };
+    content.split("+-o ").find_map(|block| {
+        if value_of(block, "USB Serial Number")? != serial_number {
+            return None;
+        }
+        let location: u32 = value_of(block, "locationID")?.parse().ok()?;
+        Some(format!("0x{:08x}", location))
+    })
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn get_usb_location(_port: &str, _serial_number: Option<&str>) -> Option<String> {
+    None
+}
+
 pub type UsbMonitorStateArc = Arc<Mutex<UsbMonitorState>>;
 
-#[cfg(target_os = "windows")]
 lazy_static::lazy_static! {
     /// Global USB monitor state
     static ref USB_MONITOR: UsbMonitorStateArc = Arc::new(Mutex::new(UsbMonitorState::new()));
 }
 
-/// Get the current Reachy Mini port from the monitor
+/// App handle used to emit hotplug events (set by start_monitor)
+static APP_HANDLE: OnceLock<AppHandle> = OnceLock::new();
+
+#[derive(Debug, Serialize, Clone)]
+struct UsbRobotEvent {
+    port: String,
+    robot: UsbRobot,
+    /// Nickname from the robot registry, if this robot was named before
+    nickname: Option<String>,
+}
+
+/// Get every connected Reachy Mini from the monitor
+pub fn get_reachy_robots() -> Vec<UsbRobot> {
+    USB_MONITOR.lock().map(|state| state.robots.clone()).unwrap_or_default()
+}
+
+/// Get the first connected Reachy Mini port from the monitor
 pub fn get_reachy_port() -> Option<String> {
-    #[cfg(target_os = "windows")]
-    {
-        USB_MONITOR.lock().ok()?.reachy_port.clone()
-    }
-    
-    #[cfg(not(target_os = "windows"))]
-    {
-        // Fallback to direct check on non-Windows platforms
-        match serialport::available_ports() {
-            Ok(ports) => {
-                ports.iter().find_map(|port| {
-                    if let serialport::SerialPortType::UsbPort(usb_info) = &port.port_type {
-                        if usb_info.vid == 0x1a86 && usb_info.pid == 0x55d3 {
-                            return Some(port.port_name.clone());
-                        }
-                    }
-                    None
-                })
-            }
-            Err(_) => None
-        }
+    USB_MONITOR.lock().ok()?.robots.first().map(|robot| robot.port_name.clone())
+}
+
+/// Presence of every watched device
+pub fn get_usb_devices() -> Vec<UsbDeviceStatus> {
+    USB_MONITOR.lock().map(|state| state.devices.clone()).unwrap_or_default()
+}
+
+/// Replace the watch list (applied on the next update)
+pub fn set_watch_list(watch_list: Vec<UsbWatchEntry>) {
+    if let Ok(mut state) = USB_MONITOR.lock() {
+        state.watch_list = watch_list;
     }
 }
 
 /// Force an immediate update of the USB device list
+/// Emits a hotplug event for every Reachy Mini port and watched device that appeared or disappeared
 pub fn force_update() {
-    #[cfg(target_os = "windows")]
-    {
-        if let Ok(mut state) = USB_MONITOR.lock() {
+    let (previous, current, previous_devices, current_devices) = match USB_MONITOR.lock() {
+        Ok(mut state) => {
+            let previous = state.robots.clone();
+            let previous_devices = state.devices.clone();
             state.update();
+            (previous, state.robots.clone(), previous_devices, state.devices.clone())
+        }
+        Err(_) => return,
+    };
+
+    for device in &current_devices {
+        let was_present = previous_devices.iter().any(|d| d.entry == device.entry && d.present);
+        if device.present == was_present {
+            continue;
         }
+        let event = if device.present { "usb-device-connected" } else { "usb-device-disconnected" };
+        println!("[USB Monitor] {} {}", device.entry.label, if device.present { "connected" } else { "disconnected" });
+        if let Some(app_handle) = APP_HANDLE.get() {
+            let _ = app_handle.emit(event, device.clone());
+        }
+    }
+
+    let is_in = |robots: &[UsbRobot], port: &str| robots.iter().any(|robot| robot.port_name == port);
+    for robot in previous.iter().filter(|robot| !is_in(&current, &robot.port_name)) {
+        println!("[USB Monitor] Reachy Mini disconnected from: {}", robot.port_name);
+        emit_event("usb-robot-disconnected", robot.clone());
+    }
+    for robot in current.iter().filter(|robot| !is_in(&previous, &robot.port_name)) {
+        println!("[USB Monitor] Reachy Mini detected at: {}", robot.port_name);
+        emit_event("usb-robot-connected", robot.clone());
+    }
+}
+
+fn emit_event(event: &str, robot: UsbRobot) {
+    if let Some(app_handle) = APP_HANDLE.get() {
+        let nickname = robot.serial_number.as_deref().and_then(|serial_number| {
+            app_handle.try_state::<RobotRegistryState>()?.nickname(serial_number)
+        });
+        let _ = app_handle.emit(event, UsbRobotEvent {
+            port: robot.port_name.clone(),
+            robot,
+            nickname,
+        });
     }
 }
 
@@ -112,9 +295,7 @@ extern "system" fn wnd_proc(hwnd: HWND, msg: u32, wparam: WPARAM, lparam: LPARAM
             if event == DBT_DEVICEARRIVAL || event == DBT_DEVICEREMOVECOMPLETE {
                 // Device change detected - update port list
                 // We update on all device changes since serial port events may not always have detailed type info
-                if let Ok(mut state) = USB_MONITOR.lock() {
-                    state.update();
-                }
+                force_update();
             }
             
             LRESULT(0)
@@ -127,10 +308,19 @@ extern "system" fn wnd_proc(hwnd: HWND, msg: u32, wparam: WPARAM, lparam: LPARAM
     }
 }
 
-#[cfg(target_os = "windows")]
 /// Start the USB device monitor in a background thread
+pub fn start_monitor(app_handle: AppHandle) -> std::result::Result<(), String> {
+    let _ = APP_HANDLE.set(app_handle);
+
+    // Initial scan (emits usb-robot-connected if the robot is already plugged in)
+    force_update();
+
+    spawn_platform_monitor()
+}
+
+#[cfg(target_os = "windows")]
 /// This creates a hidden message-only window to receive WM_DEVICECHANGE messages
-pub fn start_monitor() -> std::result::Result<(), String> {
+fn spawn_platform_monitor() -> std::result::Result<(), String> {
     std::thread::spawn(|| {
         unsafe {
             let result: windows::core::Result<()> = (|| {
@@ -171,14 +361,6 @@ pub fn start_monitor() -> std::result::Result<(), String> {
                 // since WM_DEVICECHANGE will fire anyway for USB events
                 println!("[USB Monitor] Event-driven monitor started successfully on window {:?}", hwnd);
 
-                // Do an initial scan
-                if let Ok(mut state) = USB_MONITOR.lock() {
-                    state.update();
-                    if let Some(port) = &state.reachy_port {
-                        println!("[USB Monitor] Reachy Mini detected at: {}", port);
-                    }
-                }
-
                 // Message loop
                 let mut msg = MSG::default();
                 while GetMessageA(&mut msg, None, 0, 0).into() {
@@ -197,9 +379,72 @@ pub fn start_monitor() -> std::result::Result<(), String> {
     Ok(())
 }
 
-#[cfg(not(target_os = "windows"))]
-/// Dummy function for non-Windows platforms
-pub fn start_monitor() -> Result<(), String> {
-    println!("[USB Monitor] Event-driven monitoring not available on this platform, using direct checks");
+#[cfg(target_os = "linux")]
+/// Listen to udev events on the tty and usb subsystems (serial ports and devices appearing/disappearing)
+/// Falls back to polling if udev is unavailable (e.g. some containers)
+fn spawn_platform_monitor() -> std::result::Result<(), String> {
+    std::thread::spawn(|| {
+        if let Err(e) = run_udev_monitor() {
+            eprintln!("[USB Monitor] udev monitor unavailable ({}), falling back to polling", e);
+            run_poller();
+        }
+    });
+
     Ok(())
 }
+
+#[cfg(target_os = "linux")]
+fn run_udev_monitor() -> std::result::Result<(), String> {
+    use std::os::unix::io::AsRawFd;
+
+    let context = libudev::Context::new().map_err(|e| format!("Failed to create udev context: {}", e))?;
+    let mut monitor = libudev::Monitor::new(&context).map_err(|e| format!("Failed to create udev monitor: {}", e))?;
+    monitor
+        .match_subsystem("tty")
+        .and_then(|_| monitor.match_subsystem_devtype("usb", "usb_device"))
+        .map_err(|e| format!("Failed to filter udev events: {}", e))?;
+    let mut socket = monitor.listen().map_err(|e| format!("Failed to listen to udev events: {}", e))?;
+
+    println!("[USB Monitor] udev monitor started");
+    let mut fds = [libc::pollfd {
+        fd: socket.as_raw_fd(),
+        events: libc::POLLIN,
+        revents: 0,
+    }];
+
+    loop {
+        // Block until the socket has events (the udev socket is non-blocking)
+        let ready = unsafe { libc::poll(fds.as_mut_ptr(), 1, -1) };
+        if ready < 0 {
+            let error = std::io::Error::last_os_error();
+            if error.kind() == std::io::ErrorKind::Interrupted {
+                continue;
+            }
+            return Err(format!("udev poll failed: {}", error));
+        }
+
+        let mut changed = false;
+        while let Some(event) = socket.receive_event() {
+            changed |= matches!(event.event_type(), libudev::EventType::Add | libudev::EventType::Remove);
+        }
+        if changed {
+            force_update();
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
+/// Fallback poller (macOS): re-enumerates serial ports at a low frequency
+fn spawn_platform_monitor() -> std::result::Result<(), String> {
+    std::thread::spawn(run_poller);
+    Ok(())
+}
+
+#[cfg(not(target_os = "windows"))]
+fn run_poller() {
+    println!("[USB Monitor] Polling monitor started (every {}ms)", POLL_INTERVAL_MS);
+    loop {
+        std::thread::sleep(std::time::Duration::from_millis(POLL_INTERVAL_MS));
+        force_update();
+    }
+}
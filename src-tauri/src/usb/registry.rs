@@ -0,0 +1,187 @@
+/// Robot identity registry
+///
+/// Remembers every robot identified over USB (keyed by USB serial number) with an
+/// optional user-assigned nickname, persisted to `<app data>/robot_registry.json`.
+/// Loaded once in setup; every update is written back to disk immediately.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Manager, State};
+
+use super::identity::{read_robot_identity, RobotIdentity};
+use crate::daemon::DaemonState;
+
+const REGISTRY_FILE: &str = "robot_registry.json";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct KnownRobot {
+    pub serial_number: String,
+    pub nickname: Option<String>,
+    pub hardware_revision: Option<String>,
+    /// Unix millis
+    pub first_seen: u64,
+    pub last_seen: u64,
+    pub last_port: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct IdentifiedRobot {
+    pub identity: RobotIdentity,
+    /// Registry entry (None if the robot exposes no serial number)
+    pub known: Option<KnownRobot>,
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+pub struct RobotRegistryState {
+    robots: Mutex<Vec<KnownRobot>>,
+    path: Mutex<Option<PathBuf>>,
+}
+
+impl RobotRegistryState {
+    pub fn new() -> Self {
+        Self {
+            robots: Mutex::new(Vec::new()),
+            path: Mutex::new(None),
+        }
+    }
+
+    /// Load the registry from the app data directory (empty if missing or corrupted)
+    pub fn load(&self, app_data_dir: &Path) {
+        let path = app_data_dir.join(REGISTRY_FILE);
+        let robots = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        *self.robots.lock().unwrap() = robots;
+        *self.path.lock().unwrap() = Some(path);
+    }
+
+    pub fn list(&self) -> Vec<KnownRobot> {
+        self.robots.lock().unwrap().clone()
+    }
+
+    pub fn nickname(&self, serial_number: &str) -> Option<String> {
+        self.robots
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|robot| robot.serial_number == serial_number)
+            .and_then(|robot| robot.nickname.clone())
+    }
+
+    /// Apply a change and persist it
+    fn update<T>(&self, change: impl FnOnce(&mut Vec<KnownRobot>) -> T) -> Result<T, String> {
+        let mut robots = self.robots.lock().unwrap();
+        let result = change(&mut robots);
+
+        if let Some(path) = self.path.lock().unwrap().as_ref() {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)
+                    .map_err(|e| format!("Failed to create registry dir: {}", e))?;
+            }
+            let content = serde_json::to_string_pretty(&*robots)
+                .map_err(|e| format!("Failed to serialize robot registry: {}", e))?;
+            std::fs::write(path, content)
+                .map_err(|e| format!("Failed to write robot registry: {}", e))?;
+        }
+
+        Ok(result)
+    }
+
+    /// Record a sighting of an identified robot
+    fn record(&self, identity: &RobotIdentity) -> Result<Option<KnownRobot>, String> {
+        let Some(serial_number) = identity.serial_number.clone() else {
+            return Ok(None);
+        };
+        let now = now_millis();
+
+        self.update(|robots| {
+            let index = match robots.iter().position(|robot| robot.serial_number == serial_number) {
+                Some(index) => index,
+                None => {
+                    robots.push(KnownRobot {
+                        serial_number,
+                        nickname: None,
+                        hardware_revision: None,
+                        first_seen: now,
+                        last_seen: now,
+                        last_port: None,
+                    });
+                    robots.len() - 1
+                }
+            };
+            let robot = &mut robots[index];
+            robot.last_seen = now;
+            robot.last_port = Some(identity.port_name.clone());
+            if identity.hardware_revision.is_some() {
+                robot.hardware_revision = identity.hardware_revision.clone();
+            }
+            Some(robot.clone())
+        })
+    }
+}
+
+impl Default for RobotRegistryState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Read the identity of a connected robot and record it in the registry
+/// Defaults to the first connected robot; the motor bus is skipped while the daemon runs
+#[tauri::command]
+pub async fn identify_usb_robot(app_handle: AppHandle, port: Option<String>) -> Result<IdentifiedRobot, String> {
+    let port = port
+        .or_else(super::get_reachy_port)
+        .ok_or("No Reachy Mini connected over USB")?;
+    let daemon_running = app_handle.state::<DaemonState>().process.lock().unwrap().is_some();
+
+    let identity = tauri::async_runtime::spawn_blocking(move || read_robot_identity(&port, !daemon_running))
+        .await
+        .map_err(|e| format!("Task join error: {}", e))??;
+    let known = app_handle.state::<RobotRegistryState>().record(&identity)?;
+
+    Ok(IdentifiedRobot { identity, known })
+}
+
+#[tauri::command]
+pub fn list_known_robots(state: State<RobotRegistryState>) -> Vec<KnownRobot> {
+    state.list()
+}
+
+/// Set (or clear, with None / empty) a robot's nickname
+#[tauri::command]
+pub fn set_robot_nickname(
+    state: State<RobotRegistryState>,
+    serial_number: String,
+    nickname: Option<String>,
+) -> Result<KnownRobot, String> {
+    let nickname = nickname
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty());
+
+    state
+        .update(|robots| {
+            let robot = robots.iter_mut().find(|robot| robot.serial_number == serial_number)?;
+            robot.nickname = nickname;
+            Some(robot.clone())
+        })?
+        .ok_or_else(|| format!("Unknown robot: {}", serial_number))
+}
+
+#[tauri::command]
+pub fn forget_robot(state: State<RobotRegistryState>, serial_number: String) -> Result<(), String> {
+    state.update(|robots| robots.retain(|robot| robot.serial_number != serial_number))
+}
@@ -0,0 +1,114 @@
+/// Component versions
+///
+/// Single source of truth for every version shown in the UI and written to crash
+/// reports: app, daemon (reachy-mini in the venv), bundled Python, robot USB firmware
+/// and the kinematics WASM build.
+
+use serde::Serialize;
+use std::path::Path;
+use tauri::AppHandle;
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ComponentVersions {
+    pub app: String,
+    /// reachy-mini version installed in the venv
+    pub daemon: Option<String>,
+    /// Python version of the bundled venv
+    pub python: Option<String>,
+    /// Firmware revision reported by the robot's USB interface (None if unplugged)
+    pub firmware: Option<String>,
+    /// Build hash of the bundled kinematics WASM
+    pub kinematics_wasm: Option<String>,
+}
+
+// ============================================================================
+// HELPERS
+// ============================================================================
+
+/// Read the Python version from the venv's pyvenv.cfg
+/// uv writes "version_info = 3.12.8", the stdlib venv writes "version = 3.12.8"
+fn get_python_version(venv_path: &Path) -> Option<String> {
+    let content = std::fs::read_to_string(venv_path.join(".venv").join("pyvenv.cfg")).ok()?;
+    content.lines().find_map(|line| {
+        let (key, value) = line.split_once('=')?;
+        match key.trim() {
+            "version_info" | "version" => Some(value.trim().to_string()),
+            _ => None,
+        }
+    })
+}
+
+/// Format a USB bcdDevice value: 0x0264 -> "2.64"
+fn format_bcd(bcd: u16) -> String {
+    format!("{:x}.{:02x}", bcd >> 8, bcd & 0xff)
+}
+
+/// bcdDevice of the robot's USB device, read from sysfs
+#[cfg(target_os = "linux")]
+pub(crate) fn get_firmware_version(port: &str) -> Option<String> {
+    let tty = Path::new(port).file_name()?;
+    let device = std::fs::canonicalize(Path::new("/sys/class/tty").join(tty).join("device")).ok()?;
+
+    // ttyUSB*: .../1-1/1-1:1.0/ttyUSB0 - ttyACM*: .../1-1/1-1:1.0
+    device.ancestors().take(3).find_map(|dir| {
+        let content = std::fs::read_to_string(dir.join("bcdDevice")).ok()?;
+        u16::from_str_radix(content.trim(), 16).ok().map(format_bcd)
+    })
+}
+
+/// bcdDevice of the robot's USB device, read from the IOUSB registry
+#[cfg(target_os = "macos")]
+pub(crate) fn get_firmware_version(_port: &str) -> Option<String> {
+    let output = std::process::Command::new("ioreg")
+        .args(["-p", "IOUSB", "-l", "-w0"])
+        .output()
+        .ok()?;
+    let content = String::from_utf8_lossy(&output.stdout);
+
+    // One "+-o" block per device: `"idVendor" = 6790`, `"bcdDevice" = 612`, ...
+    let value_of = |block: &str, key: &str| -> Option<u32> {
+        block
+            .lines()
+            .find_map(|line| line.split_once(&format!("\"{}\" = ", key)))
+            .and_then(|(_, value)| value.trim().parse().ok())
+    };
+    content.split("+-o ").find_map(|block| {
+        let (vid, pid) = crate::usb::REACHY_MINI_USB_ID;
+        if value_of(block, "idVendor")? != vid as u32 || value_of(block, "idProduct")? != pid as u32 {
+            return None;
+        }
+        value_of(block, "bcdDevice").map(|bcd| format_bcd(bcd as u16))
+    })
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+pub(crate) fn get_firmware_version(_port: &str) -> Option<String> {
+    None
+}
+
+/// Collect every component version (missing ones are None)
+pub fn collect_component_versions(app_handle: &AppHandle) -> ComponentVersions {
+    let venv_path = crate::update::get_local_venv_path(app_handle).ok();
+    let kinematics_wasm = option_env!("KINEMATICS_WASM_HASH")
+        .filter(|hash| !hash.is_empty())
+        .map(String::from);
+
+    ComponentVersions {
+        app: app_handle.package_info().version.to_string(),
+        daemon: venv_path
+            .as_deref()
+            .and_then(|venv| crate::update::get_local_daemon_version(venv).ok()),
+        python: venv_path.as_deref().and_then(get_python_version),
+        firmware: crate::usb::get_reachy_port().and_then(|port| get_firmware_version(&port)),
+        kinematics_wasm,
+    }
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+#[tauri::command]
+pub fn get_component_versions(app_handle: AppHandle) -> ComponentVersions {
+    collect_component_versions(&app_handle)
+}
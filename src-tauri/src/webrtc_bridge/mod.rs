@@ -0,0 +1,487 @@
+/// WebRTC bridge for remote viewing
+///
+/// Relays the robot's camera and microphone to remote operators over WebRTC, for a much
+/// lower latency than the MJPEG stream through the proxy. The robot already encodes both
+/// for its own WebRTC producer (GStreamer webrtcsink, signalling on port 8443): the bridge
+/// joins it as a consumer and forwards the same RTP to every viewer (relay.rs), so the
+/// robot sends a single stream whatever the audience and nothing is re-encoded.
+///
+/// Viewers use WHEP (HTTP offer / answer, no trickle) on `http://<this computer>:<port>`:
+/// - `POST /whep` with an SDP offer: 201 with the answer, `Location: /whep/<id>`
+/// - `DELETE /whep/<id>`: hang up
+/// - `GET /`: minimal viewer page (viewer.html)
+///
+/// WHEP requests need the token generated at each start (`Authorization: Bearer` or
+/// `?token=`). When the robot's stream drops, viewers are disconnected and the bridge
+/// reconnects to the robot every RECONNECT_DELAY.
+///
+/// Emits `webrtc-bridge-status` (WebRtcBridgeStatus) when the robot's stream connects or drops.
+
+mod relay;
+
+use futures_util::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Emitter, Manager, State};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio_tungstenite::tungstenite::Message;
+
+use crate::connection::{ConnectionManager, ConnectionState};
+use relay::Relay;
+
+const SIGNALLING_PORT: u16 = 8443;
+const DEFAULT_PORT: u16 = 8044;
+const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+const MAX_HEAD_BYTES: usize = 16 * 1024;
+const MAX_BODY_BYTES: usize = 64 * 1024;
+const VIEWER_PAGE: &str = include_str!("viewer.html");
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+/// STUN / TURN server offered to both the robot and the viewers
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct IceServer {
+    pub urls: Vec<String>,
+    pub username: Option<String>,
+    pub credential: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct WebRtcBridgeStatus {
+    pub running: bool,
+    /// Receiving the robot's stream
+    pub connected: bool,
+    /// MIME types of the relayed tracks (e.g. video/H264, audio/opus)
+    pub tracks: Vec<String>,
+    pub viewers: usize,
+    pub port: Option<u16>,
+    pub token: Option<String>,
+    /// Viewer page on this computer's LAN address, token included
+    pub viewer_url: Option<String>,
+    pub last_error: Option<String>,
+}
+
+struct RunningBridge {
+    port: u16,
+    token: String,
+    upstream: JoinHandle<()>,
+    server: JoinHandle<()>,
+}
+
+impl Drop for RunningBridge {
+    fn drop(&mut self) {
+        self.upstream.abort();
+        self.server.abort();
+    }
+}
+
+#[derive(Default)]
+pub struct WebRtcBridgeState {
+    running: Mutex<Option<RunningBridge>>,
+    /// Relay of the current robot session, once the robot's offer is answered
+    relay: Mutex<Option<Arc<Relay>>>,
+    last_error: Mutex<Option<String>>,
+}
+
+struct HttpRequest {
+    method: String,
+    path: String,
+    query: Option<String>,
+    authorization: Option<String>,
+    body: Vec<u8>,
+}
+
+// ============================================================================
+// HELPER FUNCTIONS
+// ============================================================================
+
+impl WebRtcBridgeState {
+    fn relay(&self) -> Option<Arc<Relay>> {
+        self.relay.lock().unwrap().clone()
+    }
+
+    fn status(&self) -> WebRtcBridgeStatus {
+        let running = self.running.lock().unwrap();
+        let relay = self.relay();
+        WebRtcBridgeStatus {
+            running: running.is_some(),
+            connected: relay.as_ref().is_some_and(|relay| relay.is_connected()),
+            tracks: relay.as_ref().map(|relay| relay.tracks()).unwrap_or_default(),
+            viewers: relay.as_ref().map(|relay| relay.viewer_count()).unwrap_or(0),
+            port: running.as_ref().map(|bridge| bridge.port),
+            token: running.as_ref().map(|bridge| bridge.token.clone()),
+            viewer_url: running
+                .as_ref()
+                .map(|bridge| format!("http://{}:{}/?token={}", lan_address(), bridge.port, bridge.token)),
+            last_error: self.last_error.lock().unwrap().clone(),
+        }
+    }
+}
+
+fn emit_status(app_handle: &AppHandle) {
+    let status = app_handle.state::<WebRtcBridgeState>().status();
+    let _ = app_handle.emit("webrtc-bridge-status", status);
+}
+
+/// Address other machines reach this computer at (the route to the internet, no packet sent)
+fn lan_address() -> String {
+    std::net::UdpSocket::bind("0.0.0.0:0")
+        .and_then(|socket| {
+            socket.connect("8.8.8.8:80")?;
+            socket.local_addr()
+        })
+        .map(|addr| addr.ip().to_string())
+        .unwrap_or_else(|_| "127.0.0.1".to_string())
+}
+
+/// Host running the robot's WebRTC producer
+fn robot_host(app_handle: &AppHandle) -> Result<String, String> {
+    match app_handle.state::<ConnectionManager>().get() {
+        ConnectionState::WifiMode { host, .. } => Ok(host),
+        ConnectionState::Ready { sim_mode: false, .. } => Ok("127.0.0.1".to_string()),
+        ConnectionState::Ready { sim_mode: true, .. } => Err("The simulation has no camera stream".to_string()),
+        _ => Err("Robot not connected".to_string()),
+    }
+}
+
+// ============================================================================
+// ROBOT SESSION
+// ============================================================================
+
+/// Consume the robot's stream until it ends (always an error: the bridge retries)
+async fn run_session(app_handle: &AppHandle, ice_servers: &[IceServer]) -> Result<(), String> {
+    let host = robot_host(app_handle)?;
+    let url = format!("ws://{}:{}", host, SIGNALLING_PORT);
+    let (mut ws, _) = tokio_tungstenite::connect_async(url.as_str())
+        .await
+        .map_err(|e| format!("Failed to reach the robot's stream signalling at {}: {}", url, e))?;
+
+    let relay = Arc::new(Relay::new(ice_servers).await?);
+    let mut connected = relay.watch_connected();
+    let mut peer_id: Option<String> = None;
+    let mut producer: Option<String> = None;
+    let mut session_id: Option<String> = None;
+
+    let result = loop {
+        let text = tokio::select! {
+            message = ws.next() => match message {
+                Some(Ok(Message::Text(text))) => text,
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break Err("Signalling connection closed".to_string()),
+                Some(Ok(_)) => continue,
+            },
+            _ = relay.upstream_ended() => break Err("Lost the robot's stream".to_string()),
+            Ok(()) = connected.changed() => {
+                emit_status(app_handle);
+                continue;
+            }
+        };
+        let Ok(message) = serde_json::from_str::<serde_json::Value>(&text) else {
+            continue;
+        };
+
+        let mut reply = None;
+        match message["type"].as_str().unwrap_or_default() {
+            "welcome" => {
+                peer_id = message["peerId"].as_str().map(String::from);
+                reply = Some(serde_json::json!({
+                    "type": "setPeerStatus",
+                    "roles": ["listener"],
+                    "meta": { "name": "Reachy Mini Control bridge" },
+                }));
+            }
+            // Registered as listener: ask for the producers
+            "peerStatusChanged" if peer_id.is_some() && message["peerId"].as_str() == peer_id.as_deref() => {
+                reply = Some(serde_json::json!({ "type": "list" }));
+            }
+            "peerStatusChanged" | "list" if producer.is_none() => {
+                let producers = match message["producers"].as_array() {
+                    Some(producers) => producers.iter().filter_map(|p| p["id"].as_str()).collect(),
+                    None => message["roles"]
+                        .as_array()
+                        .is_some_and(|roles| roles.iter().any(|role| role == "producer"))
+                        .then(|| message["peerId"].as_str())
+                        .flatten()
+                        .into_iter()
+                        .collect::<Vec<_>>(),
+                };
+                if let Some(id) = producers.first() {
+                    producer = Some(id.to_string());
+                    reply = Some(serde_json::json!({ "type": "startSession", "peerId": id }));
+                }
+            }
+            "sessionStarted" => session_id = message["sessionId"].as_str().map(String::from),
+            "peer" if message["sdp"]["type"] == "offer" => {
+                let offer = message["sdp"]["sdp"].as_str().unwrap_or_default().to_string();
+                match relay.answer_upstream(offer).await {
+                    Ok(answer) => {
+                        reply = Some(serde_json::json!({
+                            "type": "peer",
+                            "sessionId": session_id,
+                            "sdp": { "type": "answer", "sdp": answer },
+                        }));
+                        let state = app_handle.state::<WebRtcBridgeState>();
+                        *state.relay.lock().unwrap() = Some(relay.clone());
+                        *state.last_error.lock().unwrap() = None;
+                        println!("[webrtc] 📡 Receiving the robot's stream from {}", host);
+                    }
+                    Err(e) => break Err(e),
+                }
+            }
+            "peer" if message["ice"].is_object() => {
+                let candidate = message["ice"]["candidate"].as_str().unwrap_or_default().to_string();
+                let sdp_mline_index = message["ice"]["sdpMLineIndex"].as_u64().map(|index| index as u16);
+                if !candidate.is_empty() {
+                    let _ = relay.add_upstream_candidate(candidate, sdp_mline_index).await;
+                }
+            }
+            "endSession" => break Err("The robot ended the stream".to_string()),
+            "error" => {
+                break Err(format!(
+                    "Signalling error: {}",
+                    message["details"].as_str().unwrap_or("unknown")
+                ))
+            }
+            _ => {}
+        }
+
+        if let Some(reply) = reply {
+            if let Err(e) = ws.send(Message::Text(reply.to_string())).await {
+                break Err(format!("Signalling connection lost: {}", e));
+            }
+        }
+    };
+
+    app_handle.state::<WebRtcBridgeState>().relay.lock().unwrap().take();
+    relay.close().await;
+    let _ = ws.close(None).await;
+    result
+}
+
+/// Keep a robot session up until the bridge is stopped
+async fn run_upstream(app_handle: AppHandle, ice_servers: Vec<IceServer>) {
+    loop {
+        if let Err(e) = run_session(&app_handle, &ice_servers).await {
+            let state = app_handle.state::<WebRtcBridgeState>();
+            let mut last_error = state.last_error.lock().unwrap();
+            if last_error.as_deref() != Some(e.as_str()) {
+                eprintln!("[webrtc] ⚠️ {}", e);
+                *last_error = Some(e);
+            }
+        }
+        emit_status(&app_handle);
+        tokio::time::sleep(RECONNECT_DELAY).await;
+    }
+}
+
+// ============================================================================
+// WHEP SERVER
+// ============================================================================
+
+async fn read_request(socket: &mut TcpStream) -> Option<HttpRequest> {
+    let mut data = Vec::new();
+    let mut buf = [0u8; 4096];
+    let head_end = loop {
+        let n = socket.read(&mut buf).await.ok()?;
+        if n == 0 {
+            return None;
+        }
+        data.extend_from_slice(&buf[..n]);
+        if let Some(end) = data.windows(4).position(|window| window == b"\r\n\r\n") {
+            break end;
+        }
+        if data.len() > MAX_HEAD_BYTES {
+            return None;
+        }
+    };
+
+    let head = String::from_utf8_lossy(&data[..head_end]).to_string();
+    let mut lines = head.lines();
+    let mut request_line = lines.next()?.split_whitespace();
+    let method = request_line.next()?.to_string();
+    let target = request_line.next()?;
+    let (path, query) = match target.split_once('?') {
+        Some((path, query)) => (path.to_string(), Some(query.to_string())),
+        None => (target.to_string(), None),
+    };
+
+    let mut content_length = 0;
+    let mut authorization = None;
+    for line in lines {
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        match name.trim().to_ascii_lowercase().as_str() {
+            "content-length" => content_length = value.trim().parse().ok()?,
+            "authorization" => authorization = Some(value.trim().to_string()),
+            _ => {}
+        }
+    }
+    if content_length > MAX_BODY_BYTES {
+        return None;
+    }
+
+    let mut body = data.split_off(head_end + 4);
+    while body.len() < content_length {
+        let n = socket.read(&mut buf).await.ok()?;
+        if n == 0 {
+            return None;
+        }
+        body.extend_from_slice(&buf[..n]);
+    }
+    body.truncate(content_length);
+
+    Some(HttpRequest { method, path, query, authorization, body })
+}
+
+fn is_authorized(request: &HttpRequest, token: &str) -> bool {
+    let from_header = request
+        .authorization
+        .as_deref()
+        .map(|value| value.trim_start_matches("Bearer ").trim());
+    let from_query = request.query.as_deref().and_then(|query| {
+        query.split('&').find_map(|pair| {
+            let (key, value) = pair.split_once('=')?;
+            (key == "token").then_some(value)
+        })
+    });
+    from_header.filter(|value| !value.is_empty()).or(from_query) == Some(token)
+}
+
+async fn write_response(
+    socket: &mut TcpStream,
+    status: &str,
+    content_type: &str,
+    extra_headers: &str,
+    body: &[u8],
+) -> std::io::Result<()> {
+    let head = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\n{}Access-Control-Allow-Origin: *\r\nAccess-Control-Expose-Headers: Location\r\nCache-Control: no-cache\r\nConnection: close\r\n\r\n",
+        status,
+        content_type,
+        body.len(),
+        extra_headers
+    );
+    socket.write_all(head.as_bytes()).await?;
+    socket.write_all(body).await
+}
+
+async fn serve_client(app_handle: AppHandle, mut socket: TcpStream, token: String) -> std::io::Result<()> {
+    let Some(request) = read_request(&mut socket).await else {
+        return write_response(&mut socket, "400 Bad Request", "text/plain", "", b"Bad request").await;
+    };
+    let state = app_handle.state::<WebRtcBridgeState>();
+
+    match (request.method.as_str(), request.path.as_str()) {
+        ("GET", "/") => write_response(&mut socket, "200 OK", "text/html; charset=utf-8", "", VIEWER_PAGE.as_bytes()).await,
+        ("OPTIONS", _) => {
+            let headers = "Access-Control-Allow-Methods: GET, POST, DELETE, OPTIONS\r\nAccess-Control-Allow-Headers: Authorization, Content-Type\r\n";
+            write_response(&mut socket, "204 No Content", "text/plain", headers, b"").await
+        }
+        _ if !is_authorized(&request, &token) => {
+            write_response(&mut socket, "401 Unauthorized", "text/plain", "", b"Invalid token").await
+        }
+        ("POST", "/whep") => {
+            let relay = state.relay().filter(|relay| !relay.tracks().is_empty());
+            let Some(relay) = relay else {
+                let error = b"The robot's stream is not available yet";
+                return write_response(&mut socket, "503 Service Unavailable", "text/plain", "", error).await;
+            };
+            let offer = String::from_utf8_lossy(&request.body).to_string();
+            match relay.add_viewer(offer).await {
+                Ok((id, answer)) => {
+                    println!("[webrtc] 👀 Viewer {} connected ({} watching)", id, relay.viewer_count());
+                    let location = format!("Location: /whep/{}\r\n", id);
+                    write_response(&mut socket, "201 Created", "application/sdp", &location, answer.as_bytes()).await
+                }
+                Err(e) => write_response(&mut socket, "400 Bad Request", "text/plain", "", e.as_bytes()).await,
+            }
+        }
+        ("DELETE", path) if path.starts_with("/whep/") => {
+            let id = path.trim_start_matches("/whep/");
+            let removed = match state.relay() {
+                Some(relay) => relay.remove_viewer(id).await,
+                None => false,
+            };
+            if removed {
+                println!("[webrtc] 👀 Viewer {} left", id);
+                write_response(&mut socket, "200 OK", "text/plain", "", b"").await
+            } else {
+                write_response(&mut socket, "404 Not Found", "text/plain", "", b"Unknown session").await
+            }
+        }
+        _ => write_response(&mut socket, "404 Not Found", "text/plain", "", b"Not found").await,
+    }
+}
+
+/// Stop the server and the robot session
+async fn stop(app_handle: &AppHandle) {
+    let state = app_handle.state::<WebRtcBridgeState>();
+    let running = state.running.lock().unwrap().take();
+    let relay = state.relay.lock().unwrap().take();
+    if let Some(relay) = relay {
+        relay.close().await;
+    }
+    if running.is_some() {
+        println!("[webrtc] 🌐 Bridge stopped");
+        emit_status(app_handle);
+    }
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
+
+/// Start relaying the robot's stream (restarts the bridge if already running)
+#[tauri::command]
+pub async fn start_webrtc_bridge(
+    app_handle: AppHandle,
+    state: State<'_, WebRtcBridgeState>,
+    port: Option<u16>,
+    ice_servers: Option<Vec<IceServer>>,
+) -> Result<WebRtcBridgeStatus, String> {
+    stop(&app_handle).await;
+
+    let port = port.unwrap_or(DEFAULT_PORT);
+    let listener = TcpListener::bind(("0.0.0.0", port))
+        .await
+        .map_err(|e| format!("Failed to listen on port {}: {}", port, e))?;
+    let port = listener
+        .local_addr()
+        .map_err(|e| format!("Failed to listen on port {}: {}", port, e))?
+        .port();
+    let token = crate::local_proxy::auth::generate_token()?;
+
+    let handle = app_handle.clone();
+    let server_token = token.clone();
+    let server = tauri::async_runtime::spawn(async move {
+        while let Ok((socket, _)) = listener.accept().await {
+            let (handle, token) = (handle.clone(), server_token.clone());
+            tokio::spawn(async move {
+                let _ = serve_client(handle, socket, token).await;
+            });
+        }
+    });
+    let upstream = tauri::async_runtime::spawn(run_upstream(app_handle.clone(), ice_servers.unwrap_or_default()));
+
+    *state.last_error.lock().unwrap() = None;
+    *state.running.lock().unwrap() = Some(RunningBridge { port, token, upstream, server });
+    println!("[webrtc] 🌐 Bridge listening on port {}", port);
+    emit_status(&app_handle);
+    Ok(state.status())
+}
+
+/// Stop the bridge, disconnecting every viewer
+#[tauri::command]
+pub async fn stop_webrtc_bridge(app_handle: AppHandle) {
+    stop(&app_handle).await;
+}
+
+#[tauri::command]
+pub fn get_webrtc_bridge_status(state: State<WebRtcBridgeState>) -> WebRtcBridgeStatus {
+    state.status()
+}
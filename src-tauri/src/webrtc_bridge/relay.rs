@@ -0,0 +1,281 @@
+/// WebRTC relay (webrtc-rs)
+///
+/// One upstream peer connection receives the robot's tracks (already encoded by the
+/// robot, nothing is transcoded); each RTP packet is copied to a local track per
+/// upstream track, which every viewer peer connection sends. A video keyframe is
+/// requested upstream when a viewer joins so its picture starts right away.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::sync::{watch, Notify};
+use webrtc::api::interceptor_registry::register_default_interceptors;
+use webrtc::api::media_engine::MediaEngine;
+use webrtc::api::{APIBuilder, API};
+use webrtc::ice_transport::ice_candidate::RTCIceCandidateInit;
+use webrtc::ice_transport::ice_server::RTCIceServer;
+use webrtc::interceptor::registry::Registry;
+use webrtc::peer_connection::configuration::RTCConfiguration;
+use webrtc::peer_connection::peer_connection_state::RTCPeerConnectionState;
+use webrtc::peer_connection::sdp::session_description::RTCSessionDescription;
+use webrtc::peer_connection::RTCPeerConnection;
+use webrtc::rtcp::payload_feedbacks::picture_loss_indication::PictureLossIndication;
+use webrtc::rtp_transceiver::rtp_codec::RTPCodecType;
+use webrtc::rtp_transceiver::rtp_receiver::RTCRtpReceiver;
+use webrtc::rtp_transceiver::RTCRtpTransceiver;
+use webrtc::track::track_local::track_local_static_rtp::TrackLocalStaticRTP;
+use webrtc::track::track_local::{TrackLocal, TrackLocalWriter};
+use webrtc::track::track_remote::TrackRemote;
+
+use super::IceServer;
+
+/// Local track, its MIME type and the upstream SSRC to request keyframes from (0 for audio)
+type LocalTracks = Arc<Mutex<Vec<(Arc<TrackLocalStaticRTP>, String, u32)>>>;
+type Viewers = Arc<Mutex<HashMap<String, Arc<RTCPeerConnection>>>>;
+
+pub struct Relay {
+    api: API,
+    config: RTCConfiguration,
+    upstream: Arc<RTCPeerConnection>,
+    tracks: LocalTracks,
+    viewers: Viewers,
+    connected: Arc<watch::Sender<bool>>,
+    ended: Arc<Notify>,
+}
+
+fn build_api() -> Result<API, String> {
+    let mut media_engine = MediaEngine::default();
+    media_engine
+        .register_default_codecs()
+        .map_err(|e| format!("Failed to set up WebRTC codecs: {}", e))?;
+    let registry = register_default_interceptors(Registry::new(), &mut media_engine)
+        .map_err(|e| format!("Failed to set up WebRTC interceptors: {}", e))?;
+    Ok(APIBuilder::new()
+        .with_media_engine(media_engine)
+        .with_interceptor_registry(registry)
+        .build())
+}
+
+/// Local description once ICE gathering is done (no trickle: one SDP holds the candidates)
+async fn complete_answer(pc: &RTCPeerConnection, offer: String) -> Result<String, String> {
+    let offer = RTCSessionDescription::offer(offer).map_err(|e| format!("Invalid SDP offer: {}", e))?;
+    pc.set_remote_description(offer)
+        .await
+        .map_err(|e| format!("Failed to apply SDP offer: {}", e))?;
+    let answer = pc
+        .create_answer(None)
+        .await
+        .map_err(|e| format!("Failed to create SDP answer: {}", e))?;
+    let mut gathered = pc.gathering_complete_promise().await;
+    pc.set_local_description(answer)
+        .await
+        .map_err(|e| format!("Failed to apply SDP answer: {}", e))?;
+    let _ = gathered.recv().await;
+    pc.local_description()
+        .await
+        .map(|description| description.sdp)
+        .ok_or_else(|| "No local description".to_string())
+}
+
+/// Copy the packets of an upstream track to a new local track until it ends
+fn forward(track: Arc<TrackRemote>, tracks: LocalTracks) {
+    let codec = track.codec();
+    let mime_type = codec.capability.mime_type.clone();
+    let local = Arc::new(TrackLocalStaticRTP::new(
+        codec.capability,
+        track.id(),
+        "reachy-mini".to_string(),
+    ));
+    let ssrc = if track.kind() == RTPCodecType::Video { track.ssrc() } else { 0 };
+    tracks.lock().unwrap().push((local.clone(), mime_type, ssrc));
+
+    tokio::spawn(async move {
+        while let Ok((packet, _)) = track.read_rtp().await {
+            // No viewer bound yet is not an error worth stopping for
+            let _ = local.write_rtp(&packet).await;
+        }
+        tracks.lock().unwrap().retain(|(other, _, _)| !Arc::ptr_eq(other, &local));
+    });
+}
+
+impl Relay {
+    pub async fn new(ice_servers: &[IceServer]) -> Result<Self, String> {
+        let api = build_api()?;
+        let config = RTCConfiguration {
+            ice_servers: ice_servers
+                .iter()
+                .map(|server| RTCIceServer {
+                    urls: server.urls.clone(),
+                    username: server.username.clone().unwrap_or_default(),
+                    credential: server.credential.clone().unwrap_or_default(),
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        };
+        let upstream = Arc::new(
+            api.new_peer_connection(config.clone())
+                .await
+                .map_err(|e| format!("Failed to create peer connection: {}", e))?,
+        );
+
+        let tracks: LocalTracks = Arc::new(Mutex::new(Vec::new()));
+        let forwarded = tracks.clone();
+        upstream.on_track(Box::new(
+            move |track: Arc<TrackRemote>, _: Arc<RTCRtpReceiver>, _: Arc<RTCRtpTransceiver>| {
+                forward(track, forwarded.clone());
+                Box::pin(async {})
+            },
+        ));
+
+        let connected = Arc::new(watch::Sender::new(false));
+        let ended = Arc::new(Notify::new());
+        let (connected_flag, ended_signal) = (connected.clone(), ended.clone());
+        upstream.on_peer_connection_state_change(Box::new(move |state: RTCPeerConnectionState| {
+            connected_flag.send_replace(state == RTCPeerConnectionState::Connected);
+            if matches!(state, RTCPeerConnectionState::Failed | RTCPeerConnectionState::Closed) {
+                ended_signal.notify_one();
+            }
+            Box::pin(async {})
+        }));
+
+        Ok(Self {
+            api,
+            config,
+            upstream,
+            tracks,
+            viewers: Arc::new(Mutex::new(HashMap::new())),
+            connected,
+            ended,
+        })
+    }
+
+    /// Media is flowing from the robot
+    pub fn is_connected(&self) -> bool {
+        *self.connected.borrow()
+    }
+
+    /// Notified on each change of `is_connected`
+    pub fn watch_connected(&self) -> watch::Receiver<bool> {
+        self.connected.subscribe()
+    }
+
+    /// Resolves when the upstream connection failed or was closed
+    pub async fn upstream_ended(&self) {
+        self.ended.notified().await
+    }
+
+    /// Answer the robot's SDP offer
+    pub async fn answer_upstream(&self, offer: String) -> Result<String, String> {
+        complete_answer(&self.upstream, offer).await
+    }
+
+    pub async fn add_upstream_candidate(&self, candidate: String, sdp_mline_index: Option<u16>) -> Result<(), String> {
+        self.upstream
+            .add_ice_candidate(RTCIceCandidateInit {
+                candidate,
+                sdp_mline_index,
+                ..Default::default()
+            })
+            .await
+            .map_err(|e| format!("Invalid ICE candidate: {}", e))
+    }
+
+    /// MIME types of the robot's tracks (e.g. video/H264, audio/opus)
+    pub fn tracks(&self) -> Vec<String> {
+        self.tracks.lock().unwrap().iter().map(|(_, mime_type, _)| mime_type.clone()).collect()
+    }
+
+    pub fn viewer_count(&self) -> usize {
+        self.viewers.lock().unwrap().len()
+    }
+
+    /// Answer a viewer's SDP offer with the robot's tracks; returns its id and the answer
+    pub async fn add_viewer(&self, offer: String) -> Result<(String, String), String> {
+        let tracks = self.tracks.lock().unwrap().clone();
+        if tracks.is_empty() {
+            return Err("The robot's stream has not started yet".to_string());
+        }
+        let pc = Arc::new(
+            self.api
+                .new_peer_connection(self.config.clone())
+                .await
+                .map_err(|e| format!("Failed to create peer connection: {}", e))?,
+        );
+        for (track, _, _) in &tracks {
+            let sender = pc
+                .add_track(track.clone() as Arc<dyn TrackLocal + Send + Sync>)
+                .await
+                .map_err(|e| format!("Failed to add track: {}", e))?;
+            // RTCP from the viewer has to be read for the interceptors (NACK, reports) to run
+            tokio::spawn(async move {
+                let mut buf = vec![0u8; 1500];
+                while sender.read(&mut buf).await.is_ok() {}
+            });
+        }
+
+        let id = crate::local_proxy::profiles::generate_id();
+        let viewers = self.viewers.clone();
+        let viewer_id = id.clone();
+        pc.on_peer_connection_state_change(Box::new(move |state: RTCPeerConnectionState| {
+            let ended = matches!(
+                state,
+                RTCPeerConnectionState::Failed | RTCPeerConnectionState::Closed | RTCPeerConnectionState::Disconnected
+            );
+            let removed = ended.then(|| viewers.lock().unwrap().remove(&viewer_id)).flatten();
+            Box::pin(async move {
+                if let Some(pc) = removed {
+                    let _ = pc.close().await;
+                }
+            })
+        }));
+
+        let answer = match complete_answer(&pc, offer).await {
+            Ok(answer) => answer,
+            Err(e) => {
+                let _ = pc.close().await;
+                return Err(e);
+            }
+        };
+        self.viewers.lock().unwrap().insert(id.clone(), pc);
+        self.request_keyframe().await;
+        Ok((id, answer))
+    }
+
+    pub async fn remove_viewer(&self, id: &str) -> bool {
+        let removed = self.viewers.lock().unwrap().remove(id);
+        match removed {
+            Some(pc) => {
+                let _ = pc.close().await;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Ask the robot for a video keyframe (PLI)
+    async fn request_keyframe(&self) {
+        let ssrcs: Vec<u32> = self
+            .tracks
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(_, _, ssrc)| *ssrc)
+            .filter(|ssrc| *ssrc != 0)
+            .collect();
+        for media_ssrc in ssrcs {
+            let _ = self
+                .upstream
+                .write_rtcp(&[Box::new(PictureLossIndication { sender_ssrc: 0, media_ssrc })])
+                .await;
+        }
+    }
+
+    /// Close the upstream and every viewer
+    pub async fn close(&self) {
+        let viewers: Vec<_> = self.viewers.lock().unwrap().drain().map(|(_, pc)| pc).collect();
+        for pc in viewers {
+            let _ = pc.close().await;
+        }
+        let _ = self.upstream.close().await;
+    }
+}
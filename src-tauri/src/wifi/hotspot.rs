@@ -0,0 +1,113 @@
+// Robot hotspot detection
+// Tells apart "the OS says we joined the hotspot" from "the robot's provisioning API
+// actually answers" (DHCP lease, routing), as a small state machine:
+//   NotOnHotspot -> OnHotspotNoApi -> Ready
+// The watcher emits `hotspot-state-changed` on every transition.
+
+use serde::Serialize;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, State};
+
+use super::{get_current_connection_sync, is_reachy_hotspot};
+
+/// Robot address on its own hotspot (NetworkManager shared connection)
+const HOTSPOT_GATEWAY: &str = "10.42.0.1";
+const HOTSPOT_SUBNET_PREFIX: &str = "10.42.0.";
+const API_TIMEOUT: Duration = Duration::from_secs(2);
+const DEFAULT_WATCH_INTERVAL_SECS: u64 = 2;
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+pub enum HotspotStateKind {
+    NotOnHotspot,
+    OnHotspotNoApi,
+    Ready,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct HotspotState {
+    pub state: HotspotStateKind,
+    pub ssid: Option<String>,
+    pub ip: Option<String>,
+    /// Provisioning API base URL (set when Ready)
+    pub api_url: Option<String>,
+}
+
+#[derive(Default)]
+pub struct HotspotWatchState {
+    task: Mutex<Option<tauri::async_runtime::JoinHandle<()>>>,
+}
+
+/// Detect the current hotspot state
+pub async fn detect_hotspot_state(client: &reqwest::Client) -> HotspotState {
+    let connection = tokio::task::spawn_blocking(get_current_connection_sync)
+        .await
+        .ok()
+        .and_then(|result| result.ok())
+        .flatten();
+    let ssid = connection.as_ref().map(|c| c.ssid.clone());
+    let ip = connection.as_ref().and_then(|c| c.ip.clone());
+
+    // SSID match, or an address in the hotspot subnet (renamed hotspot)
+    let on_hotspot = ssid.as_deref().is_some_and(is_reachy_hotspot)
+        || ip.as_deref().is_some_and(|ip| ip.starts_with(HOTSPOT_SUBNET_PREFIX));
+    if !on_hotspot {
+        return HotspotState {
+            state: HotspotStateKind::NotOnHotspot,
+            ssid,
+            ip,
+            api_url: None,
+        };
+    }
+
+    let api_url = format!("http://{}:8000", HOTSPOT_GATEWAY);
+    let api_ready = client
+        .get(format!("{}/wifi/status", api_url))
+        .timeout(API_TIMEOUT)
+        .send()
+        .await
+        .is_ok_and(|r| r.status().is_success());
+
+    HotspotState {
+        state: if api_ready { HotspotStateKind::Ready } else { HotspotStateKind::OnHotspotNoApi },
+        ssid,
+        ip,
+        api_url: api_ready.then_some(api_url),
+    }
+}
+
+#[tauri::command]
+pub async fn get_hotspot_state() -> Result<HotspotState, String> {
+    Ok(detect_hotspot_state(&reqwest::Client::new()).await)
+}
+
+/// Start watching the hotspot state (emits `hotspot-state-changed` on transitions)
+#[tauri::command]
+pub fn start_hotspot_watch(app_handle: AppHandle, state: State<HotspotWatchState>, interval_secs: Option<u64>) {
+    let interval = interval_secs.unwrap_or(DEFAULT_WATCH_INTERVAL_SECS).max(1);
+
+    let task = tauri::async_runtime::spawn(async move {
+        let client = reqwest::Client::new();
+        let mut last: Option<HotspotState> = None;
+        loop {
+            let current = detect_hotspot_state(&client).await;
+            if last.as_ref() != Some(&current) {
+                println!("[wifi] Hotspot state: {:?}", current.state);
+                let _ = app_handle.emit("hotspot-state-changed", current.clone());
+                last = Some(current);
+            }
+            tokio::time::sleep(Duration::from_secs(interval)).await;
+        }
+    });
+
+    if let Some(old_task) = state.task.lock().unwrap().replace(task) {
+        old_task.abort();
+    }
+}
+
+#[tauri::command]
+pub fn stop_hotspot_watch(state: State<HotspotWatchState>) {
+    if let Some(task) = state.task.lock().unwrap().take() {
+        task.abort();
+    }
+}
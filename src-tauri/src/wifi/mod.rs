@@ -5,11 +5,140 @@
 use serde::Serialize;
 use std::process::Command;
 
+pub mod hotspot;
+pub mod provisioning;
+pub mod scan_stream;
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum WifiSecurity {
+    Open,
+    Wep,
+    Wpa,
+    Wpa2,
+    Wpa3,
+    Enterprise,
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+pub enum WifiBand {
+    #[serde(rename = "2.4GHz")]
+    Ghz2_4,
+    #[serde(rename = "5GHz")]
+    Ghz5,
+    #[serde(rename = "6GHz")]
+    Ghz6,
+}
+
 #[derive(Debug, Serialize, Clone)]
 pub struct WifiNetwork {
     pub ssid: String,
     pub signal_strength: Option<i32>, // dBm or percentage
     pub is_reachy_hotspot: bool,
+    pub security: Option<WifiSecurity>,
+    pub band: Option<WifiBand>,
+    pub channel: Option<u32>,
+    /// The robot only joins 2.4 GHz open/WPA2 networks (unknown values are given the benefit of the doubt)
+    pub is_robot_compatible: bool,
+}
+
+impl WifiNetwork {
+    fn new(
+        ssid: String,
+        signal_strength: Option<i32>,
+        security: Option<WifiSecurity>,
+        band: Option<WifiBand>,
+        channel: Option<u32>,
+    ) -> Self {
+        let band = band.or_else(|| channel.and_then(band_from_channel));
+        let is_robot_compatible = !matches!(band, Some(WifiBand::Ghz5) | Some(WifiBand::Ghz6))
+            && matches!(security, None | Some(WifiSecurity::Open) | Some(WifiSecurity::Wpa2));
+        Self {
+            is_reachy_hotspot: is_reachy_hotspot(&ssid),
+            ssid,
+            signal_strength,
+            security,
+            band,
+            channel,
+            is_robot_compatible,
+        }
+    }
+}
+
+/// Network being parsed from multi-line scan output
+#[derive(Default)]
+struct PendingNetwork {
+    ssid: Option<String>,
+    signal: Option<i32>,
+    security: Option<WifiSecurity>,
+    band: Option<WifiBand>,
+    channel: Option<u32>,
+}
+
+impl PendingNetwork {
+    /// Finish the current network (if any) and reset for the next one
+    fn take(&mut self) -> Option<WifiNetwork> {
+        let pending = std::mem::take(self);
+        pending
+            .ssid
+            .map(|ssid| WifiNetwork::new(ssid, pending.signal, pending.security, pending.band, pending.channel))
+    }
+}
+
+/// Classify a security description from any platform
+/// ("WPA2-Personal", "WPA1 WPA2", "WPA2/WPA3 Personal", "None", "802.1X", ...)
+fn parse_security(value: &str) -> Option<WifiSecurity> {
+    let value = value.trim().to_lowercase();
+    if value.contains("802.1x") || value.contains("enterprise") || value.contains("eap") {
+        Some(WifiSecurity::Enterprise)
+    } else if value.contains("wpa2") {
+        // WPA2/WPA3 transition networks still accept WPA2 clients
+        Some(WifiSecurity::Wpa2)
+    } else if value.contains("wpa3") || value.contains("sae") {
+        Some(WifiSecurity::Wpa3)
+    } else if value.contains("wpa") {
+        Some(WifiSecurity::Wpa)
+    } else if value.contains("wep") {
+        Some(WifiSecurity::Wep)
+    } else if value.is_empty() || value == "--" || value.contains("none") || value.contains("open") {
+        Some(WifiSecurity::Open)
+    } else {
+        None
+    }
+}
+
+/// 2.4 GHz channels are 1-14; 5 GHz channels start at 32
+/// (6 GHz reuses low channel numbers, so only a frequency can identify it)
+fn band_from_channel(channel: u32) -> Option<WifiBand> {
+    match channel {
+        1..=14 => Some(WifiBand::Ghz2_4),
+        32..=177 => Some(WifiBand::Ghz5),
+        _ => None,
+    }
+}
+
+fn band_from_frequency_mhz(frequency: u32) -> Option<WifiBand> {
+    match frequency {
+        2400..=2500 => Some(WifiBand::Ghz2_4),
+        4900..=5900 => Some(WifiBand::Ghz5),
+        5925..=7125 => Some(WifiBand::Ghz6),
+        _ => None,
+    }
+}
+
+/// Parse a band label: "2.4 GHz", "5 GHz", "2GHz", "6GHz"
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+fn parse_band(value: &str) -> Option<WifiBand> {
+    let value = value.trim().to_lowercase();
+    if value.starts_with("2.4") || value.starts_with("2ghz") || value.starts_with("2 ghz") {
+        Some(WifiBand::Ghz2_4)
+    } else if value.starts_with('5') {
+        Some(WifiBand::Ghz5)
+    } else if value.starts_with('6') {
+        Some(WifiBand::Ghz6)
+    } else {
+        None
+    }
 }
 
 /// Get the current WiFi SSID the computer is connected to
@@ -48,7 +177,7 @@ fn get_current_ssid_sync() -> Result<Option<String>, String> {
 fn get_current_ssid_macos() -> Result<Option<String>, String> {
     // Use networksetup to get current WiFi network
     let output = Command::new("networksetup")
-        .args(["-getairportnetwork", "en0"])
+        .args(["-getairportnetwork", &wifi_interface()])
         .output()
         .map_err(|e| format!("Failed to run networksetup: {}", e))?;
     
@@ -128,6 +257,561 @@ fn get_current_ssid_linux() -> Result<Option<String>, String> {
     Ok(None)
 }
 
+// ============================================================================
+// Current connection
+// ============================================================================
+
+/// Network the computer is currently connected to
+#[derive(Debug, Serialize, Clone)]
+pub struct WifiConnection {
+    pub ssid: String,
+    pub ip: Option<String>,
+    pub signal_strength: Option<i32>, // dBm or percentage
+}
+
+/// Get the current WiFi connection (SSID, IP, signal)
+/// Returns None if not connected to WiFi
+#[tauri::command]
+pub async fn get_current_wifi_connection() -> Result<Option<WifiConnection>, String> {
+    tokio::task::spawn_blocking(get_current_connection_sync)
+        .await
+        .map_err(|e| format!("Task join error: {}", e))?
+}
+
+fn get_current_connection_sync() -> Result<Option<WifiConnection>, String> {
+    #[cfg(target_os = "macos")]
+    {
+        get_current_connection_macos()
+    }
+    
+    #[cfg(target_os = "windows")]
+    {
+        get_current_connection_windows()
+    }
+    
+    #[cfg(target_os = "linux")]
+    {
+        get_current_connection_linux()
+    }
+    
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    {
+        Ok(None)
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn get_current_connection_macos() -> Result<Option<WifiConnection>, String> {
+    let Some(ssid) = get_current_ssid_macos()? else {
+        return Ok(None);
+    };
+    
+    let ip = Command::new("ipconfig")
+        .args(["getifaddr", &wifi_interface()])
+        .output()
+        .ok()
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+        .filter(|ip| !ip.is_empty());
+    
+    // "Current Network Information:" section of system_profiler:
+    //   Current Network Information:
+    //     NetworkName:
+    //       Signal / Noise: -50 dBm / -86 dBm
+    let signal_strength = Command::new("system_profiler")
+        .arg("SPAirPortDataType")
+        .output()
+        .ok()
+        .and_then(|o| {
+            let stdout = String::from_utf8_lossy(&o.stdout).to_string();
+            let section = stdout.split("Current Network Information:").nth(1)?.to_string();
+            section
+                .lines()
+                .find_map(|line| line.trim().strip_prefix("Signal / Noise:").map(String::from))
+                .and_then(|value| value.split('/').next()?.replace("dBm", "").trim().parse().ok())
+        });
+    
+    Ok(Some(WifiConnection { ssid, ip, signal_strength }))
+}
+
+#[cfg(target_os = "windows")]
+fn get_current_connection_windows() -> Result<Option<WifiConnection>, String> {
+    let output = Command::new("netsh")
+        .args(["wlan", "show", "interfaces"])
+        .output()
+        .map_err(|e| format!("Failed to run netsh: {}", e))?;
+    
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let value_of = |line: &str| line.find(':').map(|pos| line[pos + 1..].trim().to_string());
+    
+    let mut interface: Option<String> = None;
+    let mut ssid: Option<String> = None;
+    let mut signal_strength: Option<i32> = None;
+    for line in stdout.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("Name") && interface.is_none() {
+            interface = value_of(trimmed);
+        } else if trimmed.starts_with("SSID") && !trimmed.starts_with("SSID BSSID") {
+            ssid = value_of(trimmed).filter(|s| !s.is_empty());
+        } else if trimmed.starts_with("Signal") {
+            signal_strength = value_of(trimmed).and_then(|v| v.replace('%', "").parse().ok());
+        }
+    }
+    
+    let Some(ssid) = ssid else {
+        return Ok(None);
+    };
+    
+    // "IP Address:                           192.168.1.23"
+    let ip = interface.and_then(|name| {
+        let output = Command::new("netsh")
+            .args(["interface", "ipv4", "show", "addresses"])
+            .arg(format!("name={}", name))
+            .output()
+            .ok()?;
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .find(|line| line.trim().starts_with("IP Address"))
+            .and_then(|line| value_of(line.trim()))
+    });
+    
+    Ok(Some(WifiConnection { ssid, ip, signal_strength }))
+}
+
+#[cfg(target_os = "linux")]
+fn get_current_connection_linux() -> Result<Option<WifiConnection>, String> {
+    // Format: "yes:NetworkName:72" for the active connection
+    let output = Command::new("nmcli")
+        .args(["-t", "-f", "active,ssid,signal", "dev", "wifi"])
+        .output();
+    
+    let active = output.ok().and_then(|output| {
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(split_terse_fields)
+            .find(|fields| fields.first().map(String::as_str) == Some("yes"))
+    });
+    
+    let (ssid, signal_strength) = match active {
+        Some(fields) => (
+            fields.get(1).cloned().unwrap_or_default(),
+            fields.get(2).and_then(|s| s.parse().ok()),
+        ),
+        // Fallback to iwgetid (SSID only)
+        None => match get_current_ssid_linux()? {
+            Some(ssid) => (ssid, None),
+            None => return Ok(None),
+        },
+    };
+    if ssid.is_empty() {
+        return Ok(None);
+    }
+    
+    // Connected wifi device, e.g. "wlan0:wifi:connected"
+    let ip = Command::new("nmcli")
+        .args(["-t", "-f", "DEVICE,TYPE,STATE", "dev"])
+        .output()
+        .ok()
+        .and_then(|output| {
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .map(split_terse_fields)
+                .find(|f| f.get(1).map(String::as_str) == Some("wifi") && f.get(2).map(String::as_str) == Some("connected"))
+                .and_then(|f| f.first().cloned())
+        })
+        .and_then(|device| {
+            // "192.168.1.23/24"
+            let output = Command::new("nmcli")
+                .args(["-g", "IP4.ADDRESS", "dev", "show", &device])
+                .output()
+                .ok()?;
+            let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+            stdout
+                .lines()
+                .next()
+                .and_then(|address| address.split('/').next())
+                .map(|ip| ip.trim().to_string())
+                .filter(|ip| !ip.is_empty())
+        });
+    
+    Ok(Some(WifiConnection { ssid, ip, signal_strength }))
+}
+
+// ============================================================================
+// Connect
+// ============================================================================
+
+/// How long to wait for the OS to report the new SSID after a connect command
+const CONNECT_TIMEOUT_SECS: u64 = 30;
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum WifiConnectErrorKind {
+    WrongPassword,
+    NotFound,
+    Timeout,
+    Other,
+}
+
+/// Structured connect error, serialized as "WIFI_CONNECT_FAILED:{json}" for the frontend
+#[derive(Debug, Serialize, Clone)]
+pub struct WifiConnectError {
+    pub kind: WifiConnectErrorKind,
+    pub ssid: String,
+    pub message: String,
+}
+
+impl WifiConnectError {
+    fn new(kind: WifiConnectErrorKind, ssid: &str, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            ssid: ssid.to_string(),
+            message: message.into(),
+        }
+    }
+
+    pub fn to_error_string(&self) -> String {
+        format!(
+            "WIFI_CONNECT_FAILED:{}",
+            serde_json::to_string(self).unwrap_or_default()
+        )
+    }
+}
+
+/// Payload of the `wifi-connect-status` event
+#[derive(Debug, Serialize, Clone)]
+struct WifiConnectStatus {
+    ssid: String,
+    /// "connecting" | "connected" | "failed"
+    status: &'static str,
+    error: Option<WifiConnectError>,
+}
+
+/// Connect the computer to a WiFi network (password None for open networks)
+/// Emits `wifi-connect-status` events while connecting
+#[tauri::command]
+pub async fn connect_to_network(
+    app_handle: tauri::AppHandle,
+    ssid: String,
+    password: Option<String>,
+) -> Result<(), String> {
+    use tauri::Emitter;
+    
+    let _ = app_handle.emit("wifi-connect-status", WifiConnectStatus {
+        ssid: ssid.clone(),
+        status: "connecting",
+        error: None,
+    });
+    
+    let target = ssid.clone();
+    let result = tokio::task::spawn_blocking(move || connect_sync(&target, password.as_deref()))
+        .await
+        .map_err(|e| format!("Task join error: {}", e))?;
+    
+    match result {
+        Ok(()) => {
+            println!("[wifi] ✅ Connected to {}", ssid);
+            let _ = app_handle.emit("wifi-connect-status", WifiConnectStatus {
+                ssid,
+                status: "connected",
+                error: None,
+            });
+            Ok(())
+        }
+        Err(error) => {
+            eprintln!("[wifi] ❌ Failed to connect to {}: {}", ssid, error.message);
+            let _ = app_handle.emit("wifi-connect-status", WifiConnectStatus {
+                ssid,
+                status: "failed",
+                error: Some(error.clone()),
+            });
+            Err(error.to_error_string())
+        }
+    }
+}
+
+/// Synchronous connect: run the platform command, then wait for the OS to report the SSID
+pub(crate) fn connect_sync(ssid: &str, password: Option<&str>) -> Result<(), WifiConnectError> {
+    #[cfg(target_os = "macos")]
+    let result = connect_macos(ssid, password);
+    
+    #[cfg(target_os = "windows")]
+    let result = connect_windows(ssid, password);
+    
+    #[cfg(target_os = "linux")]
+    let result = connect_linux(ssid, password);
+    
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    let result = {
+        let _ = password;
+        Err(WifiConnectError::new(
+            WifiConnectErrorKind::Other,
+            ssid,
+            "WiFi connection not supported on this platform",
+        ))
+    };
+    
+    result?;
+    wait_for_ssid(ssid)
+}
+
+/// Poll the current SSID until it matches (association can lag behind the command)
+fn wait_for_ssid(ssid: &str) -> Result<(), WifiConnectError> {
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(CONNECT_TIMEOUT_SECS);
+    while std::time::Instant::now() < deadline {
+        if let Ok(Some(current)) = get_current_ssid_sync() {
+            if current == ssid {
+                return Ok(());
+            }
+        }
+        std::thread::sleep(std::time::Duration::from_millis(500));
+    }
+    Err(WifiConnectError::new(
+        WifiConnectErrorKind::Timeout,
+        ssid,
+        format!("Not connected to {} after {}s", ssid, CONNECT_TIMEOUT_SECS),
+    ))
+}
+
+/// BSD name of the Wi-Fi interface ("en0" on most Macs, not all of them)
+#[cfg(target_os = "macos")]
+pub(crate) fn wifi_interface() -> String {
+    Command::new("networksetup")
+        .arg("-listallhardwareports")
+        .output()
+        .ok()
+        .and_then(|output| parse_wifi_interface(&String::from_utf8_lossy(&output.stdout)))
+        .unwrap_or_else(|| "en0".to_string())
+}
+
+/// Device of the "Wi-Fi" (or older "AirPort") block of `networksetup -listallhardwareports`:
+///   Hardware Port: Wi-Fi
+///   Device: en1
+#[cfg(target_os = "macos")]
+fn parse_wifi_interface(output: &str) -> Option<String> {
+    let mut in_wifi_port = false;
+    for line in output.lines() {
+        let line = line.trim();
+        if let Some(port) = line.strip_prefix("Hardware Port:") {
+            in_wifi_port = matches!(port.trim(), "Wi-Fi" | "AirPort");
+        } else if in_wifi_port {
+            if let Some(device) = line.strip_prefix("Device:") {
+                let device = device.trim();
+                if !device.is_empty() {
+                    return Some(device.to_string());
+                }
+            }
+        }
+    }
+    None
+}
+
+#[cfg(target_os = "macos")]
+#[link(name = "CoreWLAN", kind = "framework")]
+extern "C" {}
+
+/// CWErr codes returned by CoreWLAN when joining a network
+#[cfg(target_os = "macos")]
+mod cw_error {
+    pub const TIMEOUT: i64 = -3905;
+    pub const CHALLENGE_FAILURE: i64 = -3912;
+    pub const INVALID_PMK: i64 = -3924;
+    pub const SUPPLICANT_TIMEOUT: i64 = -3925;
+}
+
+/// Join through CoreWLAN so the passphrase never appears in a process argument list
+#[cfg(target_os = "macos")]
+fn connect_macos(ssid: &str, password: Option<&str>) -> Result<(), WifiConnectError> {
+    use cocoa::base::{id, nil, BOOL, YES};
+    use cocoa::foundation::{NSAutoreleasePool, NSString};
+    use objc::{class, msg_send, sel, sel_impl};
+
+    unsafe fn error_message(error: id) -> String {
+        if error == nil {
+            return "Unknown CoreWLAN error".to_string();
+        }
+        let description: id = msg_send![error, localizedDescription];
+        let utf8: *const std::os::raw::c_char = msg_send![description, UTF8String];
+        if utf8.is_null() {
+            return "Unknown CoreWLAN error".to_string();
+        }
+        std::ffi::CStr::from_ptr(utf8).to_string_lossy().into_owned()
+    }
+
+    let interface_name = wifi_interface();
+    unsafe {
+        let pool = NSAutoreleasePool::new(nil);
+        let result = (|| {
+            let name: id = NSString::alloc(nil).init_str(&interface_name);
+            let client: id = msg_send![class!(CWWiFiClient), sharedWiFiClient];
+            let interface: id = msg_send![client, interfaceWithName: name];
+            let _: () = msg_send![name, release];
+            if interface == nil {
+                return Err(WifiConnectError::new(
+                    WifiConnectErrorKind::Other,
+                    ssid,
+                    format!("Wi-Fi interface {} not found", interface_name),
+                ));
+            }
+
+            let ssid_string: id = NSString::alloc(nil).init_str(ssid);
+            let mut error: id = nil;
+            let networks: id = msg_send![interface, scanForNetworksWithName: ssid_string error: &mut error as *mut id];
+            let _: () = msg_send![ssid_string, release];
+            if networks == nil {
+                return Err(WifiConnectError::new(WifiConnectErrorKind::Other, ssid, error_message(error)));
+            }
+            let network: id = msg_send![networks, anyObject];
+            if network == nil {
+                return Err(WifiConnectError::new(
+                    WifiConnectErrorKind::NotFound,
+                    ssid,
+                    format!("Could not find network {}", ssid),
+                ));
+            }
+
+            let password_string: id = match password {
+                Some(password) => NSString::alloc(nil).init_str(password),
+                None => nil,
+            };
+            let mut error: id = nil;
+            let joined: BOOL = msg_send![interface, associateToNetwork: network password: password_string error: &mut error as *mut id];
+            if password_string != nil {
+                let _: () = msg_send![password_string, release];
+            }
+            if joined == YES {
+                return Ok(());
+            }
+
+            let code: i64 = if error == nil { 0 } else { msg_send![error, code] };
+            let kind = match code {
+                cw_error::TIMEOUT => WifiConnectErrorKind::Timeout,
+                cw_error::CHALLENGE_FAILURE | cw_error::INVALID_PMK | cw_error::SUPPLICANT_TIMEOUT
+                    if password.is_some() =>
+                {
+                    WifiConnectErrorKind::WrongPassword
+                }
+                _ => WifiConnectErrorKind::Other,
+            };
+            Err(WifiConnectError::new(kind, ssid, error_message(error)))
+        })();
+        pool.drain();
+        result
+    }
+}
+
+/// Escape a value for the WLAN profile XML
+#[cfg(target_os = "windows")]
+fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(target_os = "windows")]
+fn connect_windows(ssid: &str, password: Option<&str>) -> Result<(), WifiConnectError> {
+    let other = |message: String| WifiConnectError::new(WifiConnectErrorKind::Other, ssid, message);
+    
+    // netsh can only connect through a profile - write a temporary one
+    let security = match password {
+        Some(password) => format!(
+            "<authEncryption><authentication>WPA2PSK</authentication><encryption>AES</encryption><useOneX>false</useOneX></authEncryption>\
+             <sharedKey><keyType>passPhrase</keyType><protected>false</protected><keyMaterial>{}</keyMaterial></sharedKey>",
+            xml_escape(password)
+        ),
+        None => "<authEncryption><authentication>open</authentication><encryption>none</encryption><useOneX>false</useOneX></authEncryption>".to_string(),
+    };
+    let profile = format!(
+        "<?xml version=\"1.0\"?>\
+         <WLANProfile xmlns=\"http://www.microsoft.com/networking/WLAN/profile/v1\">\
+         <name>{name}</name><SSIDConfig><SSID><name>{name}</name></SSID></SSIDConfig>\
+         <connectionType>ESS</connectionType><connectionMode>manual</connectionMode>\
+         <MSM><security>{security}</security></MSM></WLANProfile>",
+        name = xml_escape(ssid),
+        security = security
+    );
+    
+    let profile_path = std::env::temp_dir().join("reachy-mini-wifi-profile.xml");
+    std::fs::write(&profile_path, profile).map_err(|e| other(format!("Failed to write WiFi profile: {}", e)))?;
+    
+    let output = Command::new("netsh")
+        .args(["wlan", "add", "profile"])
+        .arg(format!("filename={}", profile_path.display()))
+        .arg("user=current")
+        .output();
+    // The profile contains the password in clear text - don't leave it around
+    let _ = std::fs::remove_file(&profile_path);
+    let output = output.map_err(|e| other(format!("Failed to run netsh: {}", e)))?;
+    if !output.status.success() {
+        return Err(other(String::from_utf8_lossy(&output.stdout).trim().to_string()));
+    }
+    
+    let output = Command::new("netsh")
+        .args(["wlan", "connect"])
+        .arg(format!("name={}", ssid))
+        .output()
+        .map_err(|e| other(format!("Failed to run netsh: {}", e)))?;
+    if !output.status.success() {
+        let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        let kind = if stdout.contains("is not available") {
+            WifiConnectErrorKind::NotFound
+        } else {
+            WifiConnectErrorKind::Other
+        };
+        return Err(WifiConnectError::new(kind, ssid, stdout));
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+fn connect_linux(ssid: &str, password: Option<&str>) -> Result<(), WifiConnectError> {
+    use std::io::Write;
+    use std::process::Stdio;
+    
+    let other = |message: String| WifiConnectError::new(WifiConnectErrorKind::Other, ssid, message);
+    let timeout = CONNECT_TIMEOUT_SECS.to_string();
+    // --ask makes nmcli prompt for the passphrase on stdin: on the command line, any
+    // local user could read it from `ps` / /proc/<pid>/cmdline
+    let mut command = Command::new("nmcli");
+    if password.is_some() {
+        command.arg("--ask");
+    }
+    let mut child = command
+        .args(["--wait", timeout.as_str(), "device", "wifi", "connect", ssid])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| other(format!("Failed to run nmcli: {}", e)))?;
+    if let (Some(mut stdin), Some(password)) = (child.stdin.take(), password) {
+        // Fails only if nmcli already exited (e.g. unknown SSID): its stderr says why
+        let _ = stdin.write_all(password.as_bytes()).and_then(|_| stdin.write_all(b"\n"));
+    }
+    // stdin is closed here: nmcli gets EOF instead of waiting at a second prompt
+    let output = child
+        .wait_with_output()
+        .map_err(|e| other(format!("Failed to run nmcli: {}", e)))?;
+    
+    if output.status.success() {
+        return Ok(());
+    }
+    
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    let lower = stderr.to_lowercase();
+    let kind = if lower.contains("secrets were required") || lower.contains("802-11-wireless-security.psk") {
+        WifiConnectErrorKind::WrongPassword
+    } else if lower.contains("no network with ssid") {
+        WifiConnectErrorKind::NotFound
+    } else if lower.contains("timeout") || lower.contains("timed out") {
+        WifiConnectErrorKind::Timeout
+    } else {
+        WifiConnectErrorKind::Other
+    };
+    Err(WifiConnectError::new(kind, ssid, stderr))
+}
+
 /// Scan available WiFi networks on the local machine (async, non-blocking)
 /// Returns a list of SSIDs with signal strength
 #[tauri::command]
@@ -198,14 +882,23 @@ fn scan_macos() -> Result<Vec<WifiNetwork>, String> {
     let mut networks = Vec::new();
     let mut seen_ssids = std::collections::HashSet::new();
     let mut in_other_networks = false;
-    let mut current_ssid: Option<String> = None;
-    let mut current_signal: Option<i32> = None;
+    let mut current = PendingNetwork::default();
+    
+    let mut push_network = |network: Option<WifiNetwork>, networks: &mut Vec<WifiNetwork>| {
+        if let Some(network) = network {
+            if seen_ssids.insert(network.ssid.clone()) {
+                networks.push(network);
+            }
+        }
+    };
     
     // Parse system_profiler output
     // Format:
     //   Other Local Wi-Fi Networks:
     //     NetworkName:
     //       PHY Mode: ...
+    //       Channel: 6 (2GHz, 20MHz)
+    //       Security: WPA2 Personal
     //       Signal / Noise: -50 dBm / -86 dBm
     for line in stdout.lines() {
         let trimmed = line.trim();
@@ -219,16 +912,7 @@ fn scan_macos() -> Result<Vec<WifiNetwork>, String> {
         // Stop parsing if we hit another major section
         if in_other_networks && !trimmed.is_empty() && !line.starts_with(' ') && !line.starts_with('\t') {
             // Save last network if exists
-            if let Some(ssid) = current_ssid.take() {
-                if !seen_ssids.contains(&ssid) {
-                    seen_ssids.insert(ssid.clone());
-                    networks.push(WifiNetwork {
-                        is_reachy_hotspot: is_reachy_hotspot(&ssid),
-                        ssid,
-                        signal_strength: current_signal.take(),
-                    });
-                }
-            }
+            push_network(current.take(), &mut networks);
             break;
         }
         
@@ -239,22 +923,12 @@ fn scan_macos() -> Result<Vec<WifiNetwork>, String> {
             
             if trimmed.ends_with(':') && !trimmed.contains('/') && leading_spaces >= 10 && leading_spaces <= 16 {
                 // Save previous network
-                if let Some(ssid) = current_ssid.take() {
-                    if !seen_ssids.contains(&ssid) {
-                        seen_ssids.insert(ssid.clone());
-                        networks.push(WifiNetwork {
-                            is_reachy_hotspot: is_reachy_hotspot(&ssid),
-                            ssid,
-                            signal_strength: current_signal.take(),
-                        });
-                    }
-                }
+                push_network(current.take(), &mut networks);
                 
                 // Start new network
                 let ssid = trimmed.trim_end_matches(':').to_string();
                 if !ssid.is_empty() && !ssid.contains("Wi-Fi") {
-                    current_ssid = Some(ssid);
-                    current_signal = None;
+                    current.ssid = Some(ssid);
                 }
             }
             
@@ -264,23 +938,29 @@ fn scan_macos() -> Result<Vec<WifiNetwork>, String> {
                 if let Some(signal_part) = trimmed.split(':').nth(1) {
                     if let Some(dbm_str) = signal_part.split('/').next() {
                         let clean = dbm_str.trim().replace("dBm", "").trim().to_string();
-                        current_signal = clean.parse().ok();
+                        current.signal = clean.parse().ok();
                     }
                 }
             }
+            
+            // Format: "Channel: 36 (5GHz, 80MHz)"
+            if let Some(value) = trimmed.strip_prefix("Channel:") {
+                let value = value.trim();
+                current.channel = value.split_whitespace().next().and_then(|c| c.parse().ok());
+                current.band = value
+                    .split_once('(')
+                    .and_then(|(_, details)| parse_band(details.split(',').next().unwrap_or("")));
+            }
+            
+            // Format: "Security: WPA2 Personal"
+            if let Some(value) = trimmed.strip_prefix("Security:") {
+                current.security = parse_security(value);
+            }
         }
     }
     
     // Don't forget the last network
-    if let Some(ssid) = current_ssid {
-        if !seen_ssids.contains(&ssid) {
-            networks.push(WifiNetwork {
-                is_reachy_hotspot: is_reachy_hotspot(&ssid),
-                ssid,
-                signal_strength: current_signal,
-            });
-        }
-    }
+    push_network(current.take(), &mut networks);
     
     // Sort: Reachy hotspots first, then by signal strength
     networks.sort_by(|a, b| {
@@ -321,8 +1001,9 @@ fn scan_windows() -> Result<Vec<WifiNetwork>, String> {
     
     let stdout = String::from_utf8_lossy(&output.stdout);
     let mut networks = Vec::new();
-    let mut current_ssid: Option<String> = None;
-    let mut current_signal: Option<i32> = None;
+    let mut current = PendingNetwork::default();
+    
+    let value_of = |line: &str| line.find(':').map(|pos| line[pos + 1..].trim().to_string());
     
     for line in stdout.lines() {
         let trimmed = line.trim();
@@ -330,40 +1011,38 @@ fn scan_windows() -> Result<Vec<WifiNetwork>, String> {
         // Parse SSID line
         if trimmed.starts_with("SSID") && trimmed.contains(':') {
             // Save previous network if exists
-            if let Some(ssid) = current_ssid.take() {
-                if !ssid.is_empty() {
-                    networks.push(WifiNetwork {
-                        is_reachy_hotspot: is_reachy_hotspot(&ssid),
-                        ssid,
-                        signal_strength: current_signal.take(),
-                    });
-                }
+            if let Some(network) = current.take().filter(|n| !n.ssid.is_empty()) {
+                networks.push(network);
             }
             
             // Extract new SSID
-            if let Some(pos) = trimmed.find(':') {
-                current_ssid = Some(trimmed[pos + 1..].trim().to_string());
-            }
+            current.ssid = value_of(trimmed);
         }
         
         // Parse Signal line (percentage)
         if trimmed.starts_with("Signal") && trimmed.contains(':') {
-            if let Some(pos) = trimmed.find(':') {
-                let signal_str = trimmed[pos + 1..].trim().replace('%', "");
-                current_signal = signal_str.parse().ok();
+            if let Some(signal_str) = value_of(trimmed) {
+                current.signal = signal_str.replace('%', "").parse().ok();
             }
         }
+        
+        // "Authentication : WPA2-Personal"
+        if trimmed.starts_with("Authentication") {
+            current.security = value_of(trimmed).and_then(|v| parse_security(&v));
+        }
+        
+        // "Band : 2.4 GHz" (recent Windows only) / "Channel : 6"
+        if trimmed.starts_with("Band") {
+            current.band = value_of(trimmed).and_then(|v| parse_band(&v));
+        }
+        if trimmed.starts_with("Channel") && !trimmed.starts_with("Channel Utilization") {
+            current.channel = value_of(trimmed).and_then(|v| v.parse().ok());
+        }
     }
     
     // Don't forget the last network
-    if let Some(ssid) = current_ssid {
-        if !ssid.is_empty() {
-            networks.push(WifiNetwork {
-                is_reachy_hotspot: is_reachy_hotspot(&ssid),
-                ssid,
-                signal_strength: current_signal,
-            });
-        }
+    if let Some(network) = current.take().filter(|n| !n.ssid.is_empty()) {
+        networks.push(network);
     }
     
     // Sort: Reachy hotspots first, then by signal
@@ -384,13 +1063,32 @@ fn scan_windows() -> Result<Vec<WifiNetwork>, String> {
 // Linux Implementation
 // ============================================================================
 
+/// Split a `nmcli -t` line into fields (':' separated, literal colons escaped as "\:")
+#[cfg(target_os = "linux")]
+fn split_terse_fields(line: &str) -> Vec<String> {
+    let mut fields = vec![String::new()];
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                if let Some(escaped) = chars.next() {
+                    fields.last_mut().unwrap().push(escaped);
+                }
+            }
+            ':' => fields.push(String::new()),
+            _ => fields.last_mut().unwrap().push(c),
+        }
+    }
+    fields
+}
+
 #[cfg(target_os = "linux")]
 fn scan_linux() -> Result<Vec<WifiNetwork>, String> {
     use std::process::Command;
     
     // Try nmcli first (most common on modern distros)
     let output = Command::new("nmcli")
-        .args(["-t", "-f", "SSID,SIGNAL", "device", "wifi", "list", "--rescan", "yes"])
+        .args(["-t", "-f", "SSID,SIGNAL,SECURITY,CHAN,FREQ", "device", "wifi", "list", "--rescan", "yes"])
         .output();
     
     match output {
@@ -400,17 +1098,20 @@ fn scan_linux() -> Result<Vec<WifiNetwork>, String> {
             let mut seen_ssids = std::collections::HashSet::new();
             
             for line in stdout.lines() {
-                let parts: Vec<&str> = line.split(':').collect();
+                // Format: "SSID:SIGNAL:SECURITY:CHAN:FREQ" e.g. "Home:72:WPA2:6:2437 MHz"
+                let parts = split_terse_fields(line);
                 if parts.len() >= 2 {
                     let ssid = parts[0].trim().to_string();
                     if !ssid.is_empty() && !seen_ssids.contains(&ssid) {
                         seen_ssids.insert(ssid.clone());
                         let signal: Option<i32> = parts[1].trim().parse().ok();
-                        networks.push(WifiNetwork {
-                            is_reachy_hotspot: is_reachy_hotspot(&ssid),
-                            ssid,
-                            signal_strength: signal,
-                        });
+                        let security = parts.get(2).and_then(|v| parse_security(v));
+                        let channel = parts.get(3).and_then(|v| v.trim().parse().ok());
+                        let band = parts
+                            .get(4)
+                            .and_then(|v| v.split_whitespace().next()?.parse().ok())
+                            .and_then(band_from_frequency_mhz);
+                        networks.push(WifiNetwork::new(ssid, signal, security, band, channel));
                     }
                 }
             }
@@ -435,21 +1136,29 @@ fn scan_linux() -> Result<Vec<WifiNetwork>, String> {
     
     let stdout = String::from_utf8_lossy(&output.stdout);
     let mut networks = Vec::new();
-    let mut current_ssid: Option<String> = None;
-    let mut current_signal: Option<i32> = None;
+    // iwlist prints cell details (frequency, encryption) before ESSID,
+    // so collect fields per "Cell" and finish the network at the next cell
+    let mut current = PendingNetwork::default();
+    let mut encryption_on = false;
+    
+    let finish = |current: &mut PendingNetwork, encryption_on: bool, networks: &mut Vec<WifiNetwork>| {
+        if current.security.is_none() {
+            current.security = Some(if encryption_on { WifiSecurity::Wep } else { WifiSecurity::Open });
+        }
+        if let Some(network) = current.take() {
+            networks.push(network);
+        }
+    };
     
     for line in stdout.lines() {
         let trimmed = line.trim();
         
+        if trimmed.starts_with("Cell ") {
+            finish(&mut current, encryption_on, &mut networks);
+            encryption_on = false;
+        }
+        
         if trimmed.starts_with("ESSID:") {
-            if let Some(ssid) = current_ssid.take() {
-                networks.push(WifiNetwork {
-                    is_reachy_hotspot: is_reachy_hotspot(&ssid),
-                    ssid,
-                    signal_strength: current_signal.take(),
-                });
-            }
-            
             // Extract SSID (remove quotes)
             let ssid = trimmed
                 .replace("ESSID:", "")
@@ -457,7 +1166,7 @@ fn scan_linux() -> Result<Vec<WifiNetwork>, String> {
                 .trim()
                 .to_string();
             if !ssid.is_empty() {
-                current_ssid = Some(ssid);
+                current.ssid = Some(ssid);
             }
         }
         
@@ -466,23 +1175,43 @@ fn scan_linux() -> Result<Vec<WifiNetwork>, String> {
             if let Some(pos) = trimmed.find("Signal level=") {
                 let signal_str = &trimmed[pos + 13..];
                 let signal_str = signal_str.split_whitespace().next().unwrap_or("");
-                current_signal = signal_str.replace("dBm", "").parse().ok();
+                current.signal = signal_str.replace("dBm", "").parse().ok();
+            }
+        }
+        
+        // "Frequency:2.437 GHz (Channel 6)"
+        if let Some(value) = trimmed.strip_prefix("Frequency:") {
+            current.band = value
+                .split_whitespace()
+                .next()
+                .and_then(|ghz| ghz.parse::<f64>().ok())
+                .and_then(|ghz| band_from_frequency_mhz((ghz * 1000.0) as u32));
+            current.channel = value
+                .split("Channel ")
+                .nth(1)
+                .and_then(|c| c.trim_end_matches(')').parse().ok());
+        }
+        
+        if trimmed.starts_with("Encryption key:") {
+            encryption_on = trimmed.ends_with("on");
+        }
+        
+        // "IE: IEEE 802.11i/WPA2 Version 1", "IE: WPA Version 1", "Authentication Suites (1) : SAE"
+        if trimmed.starts_with("IE:") || trimmed.starts_with("Authentication Suites") {
+            if let Some(security) = parse_security(trimmed.trim_start_matches("IE:")) {
+                // Keep the strongest WPA2-compatible classification seen for the cell
+                if current.security != Some(WifiSecurity::Wpa2) {
+                    current.security = Some(security);
+                }
             }
         }
     }
     
     // Last network
-    if let Some(ssid) = current_ssid {
-        networks.push(WifiNetwork {
-            is_reachy_hotspot: is_reachy_hotspot(&ssid),
-            ssid,
-            signal_strength: current_signal,
-        });
-    }
+    finish(&mut current, encryption_on, &mut networks);
     
     // Sort: Reachy hotspots first
     networks.sort_by(|a, b| b.is_reachy_hotspot.cmp(&a.is_reachy_hotspot));
     
     Ok(networks)
 }
-
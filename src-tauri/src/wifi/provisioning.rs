@@ -0,0 +1,239 @@
+// Robot hotspot provisioning flow
+// Joins the Reachy hotspot, sends the WiFi credentials to the robot, restores the
+// computer's previous WiFi connection and waits for the robot to show up on the LAN.
+// Progress is reported through `wifi-provisioning-progress` events.
+
+use serde::Serialize;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+
+use super::{connect_sync, get_current_ssid_sync, wait_for_ssid, WifiConnectError, WifiConnectErrorKind};
+
+/// Robot daemon hosts while the computer is on the robot's hotspot
+const HOTSPOT_HOSTS: &[&str] = &["10.42.0.1", "reachy-mini.local"];
+/// Robot daemon hosts once it joined the local network
+const LAN_HOSTS: &[&str] = &["reachy-mini.local", "reachy-mini.home"];
+
+const HOTSPOT_REACHABLE_TIMEOUT: Duration = Duration::from_secs(30);
+const ROBOT_CONNECT_TIMEOUT: Duration = Duration::from_secs(60);
+const ROBOT_ON_LAN_TIMEOUT: Duration = Duration::from_secs(90);
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Serialize, Clone)]
+struct ProvisioningProgress {
+    /// "joining_hotspot" | "sending_credentials" | "robot_connecting" | "restoring_wifi" | "waiting_robot" | "done" | "failed"
+    step: &'static str,
+    message: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ProvisioningResult {
+    /// Host the robot answers on in the local network (None if not found in time)
+    pub robot_host: Option<String>,
+    /// WiFi network the computer is back on
+    pub restored_ssid: Option<String>,
+}
+
+fn emit_progress(app_handle: &AppHandle, step: &'static str, message: impl Into<String>) {
+    let message = message.into();
+    println!("[wifi-provisioning] {}", message);
+    let _ = app_handle.emit("wifi-provisioning-progress", ProvisioningProgress { step, message });
+}
+
+/// Reconnect to a network the OS already knows (saved password / profile)
+fn reconnect_known_network(ssid: &str) -> Result<(), WifiConnectError> {
+    use std::process::Command;
+
+    #[cfg(target_os = "macos")]
+    let output = Command::new("networksetup")
+        .args(["-setairportnetwork", &super::wifi_interface(), ssid])
+        .output();
+
+    #[cfg(target_os = "windows")]
+    let output = Command::new("netsh").args(["wlan", "connect"]).arg(format!("name={}", ssid)).output();
+
+    #[cfg(target_os = "linux")]
+    let output = Command::new("nmcli").args(["connection", "up", "id", ssid]).output();
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    let output: std::io::Result<std::process::Output> =
+        Err(std::io::Error::new(std::io::ErrorKind::Unsupported, "unsupported platform"));
+
+    match output {
+        Ok(output) if output.status.success() => wait_for_ssid(ssid),
+        Ok(output) => Err(WifiConnectError::new(
+            WifiConnectErrorKind::Other,
+            ssid,
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        )),
+        Err(e) => Err(WifiConnectError::new(WifiConnectErrorKind::Other, ssid, e.to_string())),
+    }
+}
+
+/// First host answering `path` within the timeout
+async fn find_host(client: &reqwest::Client, hosts: &[&str], path: &str, timeout: Duration) -> Option<String> {
+    let deadline = Instant::now() + timeout;
+    while Instant::now() < deadline {
+        for host in hosts {
+            let response = client
+                .get(format!("http://{}:8000{}", host, path))
+                .timeout(Duration::from_secs(2))
+                .send()
+                .await;
+            if matches!(response, Ok(r) if r.status().is_success()) {
+                return Some(host.to_string());
+            }
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+    None
+}
+
+/// Send the credentials and wait for the robot to report the connection
+/// The robot drops its hotspot while joining, so losing contact counts as progress
+async fn send_credentials(
+    client: &reqwest::Client,
+    host: &str,
+    target_ssid: &str,
+    target_password: &str,
+) -> Result<(), String> {
+    let base_url = format!("http://{}:8000", host);
+    let response = client
+        .post(format!("{}/wifi/connect", base_url))
+        .query(&[("ssid", target_ssid), ("password", target_password)])
+        .timeout(Duration::from_secs(10))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to send WiFi credentials: {}", e))?;
+    if !response.status().is_success() {
+        let body: serde_json::Value = response.json().await.unwrap_or_default();
+        return Err(format!(
+            "Robot rejected WiFi credentials: {}",
+            body["detail"].as_str().unwrap_or("unknown error")
+        ));
+    }
+
+    let deadline = Instant::now() + ROBOT_CONNECT_TIMEOUT;
+    let mut consecutive_errors = 0;
+    while Instant::now() < deadline {
+        tokio::time::sleep(POLL_INTERVAL).await;
+
+        let status = match client
+            .get(format!("{}/wifi/status", base_url))
+            .timeout(Duration::from_secs(3))
+            .send()
+            .await
+        {
+            Ok(response) => response.json::<serde_json::Value>().await.unwrap_or_default(),
+            Err(_) => {
+                consecutive_errors += 1;
+                if consecutive_errors >= 3 {
+                    // Robot left the hotspot to join the target network
+                    return Ok(());
+                }
+                continue;
+            }
+        };
+        consecutive_errors = 0;
+
+        match status["mode"].as_str() {
+            Some("wlan") if status["connected_network"].as_str() == Some(target_ssid) => return Ok(()),
+            Some("hotspot") => {
+                // Back on hotspot: the robot couldn't join
+                let error: serde_json::Value = match client.get(format!("{}/wifi/error", base_url)).send().await {
+                    Ok(response) => response.json().await.unwrap_or_default(),
+                    Err(_) => serde_json::Value::Null,
+                };
+                let _ = client.post(format!("{}/wifi/reset_error", base_url)).send().await;
+                return Err(format!(
+                    "Robot could not join {}: {}",
+                    target_ssid,
+                    error["error"].as_str().unwrap_or("check the password and try again")
+                ));
+            }
+            _ => {}
+        }
+    }
+
+    Err(format!("Robot did not connect to {} in time", target_ssid))
+}
+
+/// Put the computer back on its previous network (or the target network if it had none)
+async fn restore_wifi(app_handle: &AppHandle, previous_ssid: Option<String>, target_ssid: &str) -> Option<String> {
+    let ssid = previous_ssid.unwrap_or_else(|| target_ssid.to_string());
+    emit_progress(app_handle, "restoring_wifi", format!("Reconnecting to {}...", ssid));
+
+    let target = ssid.clone();
+    let result = tokio::task::spawn_blocking(move || reconnect_known_network(&target)).await;
+    match result {
+        Ok(Ok(())) => Some(ssid),
+        Ok(Err(e)) => {
+            eprintln!("[wifi-provisioning] ⚠️ Failed to reconnect to {}: {}", ssid, e.message);
+            None
+        }
+        Err(e) => {
+            eprintln!("[wifi-provisioning] ⚠️ Reconnect task failed: {}", e);
+            None
+        }
+    }
+}
+
+/// Provision the robot's WiFi through its hotspot, end to end
+#[tauri::command]
+pub async fn provision_robot_wifi(
+    app_handle: AppHandle,
+    hotspot_ssid: String,
+    target_ssid: String,
+    target_password: String,
+    hotspot_password: Option<String>,
+) -> Result<ProvisioningResult, String> {
+    let client = reqwest::Client::new();
+    let previous_ssid = tokio::task::spawn_blocking(get_current_ssid_sync)
+        .await
+        .map_err(|e| format!("Task join error: {}", e))?
+        .unwrap_or(None)
+        .filter(|ssid| *ssid != hotspot_ssid);
+
+    // 1. Join the robot hotspot
+    emit_progress(&app_handle, "joining_hotspot", format!("Joining {}...", hotspot_ssid));
+    let hotspot = hotspot_ssid.clone();
+    tokio::task::spawn_blocking(move || connect_sync(&hotspot, hotspot_password.as_deref()))
+        .await
+        .map_err(|e| format!("Task join error: {}", e))?
+        .map_err(|e| e.to_error_string())?;
+
+    // 2. Send credentials (restore the previous network whatever happens)
+    let sent = match find_host(&client, HOTSPOT_HOSTS, "/wifi/status", HOTSPOT_REACHABLE_TIMEOUT).await {
+        Some(host) => {
+            emit_progress(&app_handle, "sending_credentials", format!("Sending {} credentials to the robot...", target_ssid));
+            let result = send_credentials(&client, &host, &target_ssid, &target_password).await;
+            if result.is_ok() {
+                emit_progress(&app_handle, "robot_connecting", format!("Robot is joining {}...", target_ssid));
+            }
+            result
+        }
+        None => Err(format!("Robot not reachable on {}", hotspot_ssid)),
+    };
+
+    // 3. Back to the computer's previous network
+    let restored_ssid = restore_wifi(&app_handle, previous_ssid, &target_ssid).await;
+
+    if let Err(e) = sent {
+        emit_progress(&app_handle, "failed", e.clone());
+        return Err(e);
+    }
+
+    // 4. Wait for the robot to appear on the LAN
+    emit_progress(&app_handle, "waiting_robot", "Waiting for the robot on the local network...");
+    let robot_host = find_host(&client, LAN_HOSTS, "/api/daemon/status", ROBOT_ON_LAN_TIMEOUT).await;
+
+    match &robot_host {
+        Some(host) => emit_progress(&app_handle, "done", format!("Robot found at {}", host)),
+        None => emit_progress(&app_handle, "done", "Robot configured, but not found on the local network yet"),
+    }
+
+    Ok(ProvisioningResult {
+        robot_host,
+        restored_ssid,
+    })
+}
@@ -0,0 +1,102 @@
+// Continuous WiFi scan
+// Re-scans in the background and emits `wifi-networks-updated` only when the list
+// actually changed, so the UI doesn't have to poll scan_local_wifi_networks.
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, State};
+
+use super::{scan_wifi_sync, WifiNetwork};
+
+const DEFAULT_INTERVAL_SECS: u64 = 10;
+/// Lower bound: a scan itself takes a few seconds on macOS (system_profiler)
+const MIN_INTERVAL_SECS: u64 = 3;
+/// Signal variations below this are scan noise, not a change
+const SIGNAL_CHANGE_THRESHOLD: i32 = 5;
+
+#[derive(Default)]
+pub struct WifiScanStreamState {
+    task: Mutex<Option<tauri::async_runtime::JoinHandle<()>>>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+struct WifiNetworksUpdate {
+    networks: Vec<WifiNetwork>,
+    added: Vec<String>,
+    removed: Vec<String>,
+}
+
+/// Diff two scans; None if nothing meaningful changed
+fn diff_networks(previous: &[WifiNetwork], current: &[WifiNetwork]) -> Option<WifiNetworksUpdate> {
+    let previous_by_ssid: HashMap<&str, Option<i32>> =
+        previous.iter().map(|n| (n.ssid.as_str(), n.signal_strength)).collect();
+    let current_by_ssid: HashMap<&str, Option<i32>> =
+        current.iter().map(|n| (n.ssid.as_str(), n.signal_strength)).collect();
+
+    let added: Vec<String> = current
+        .iter()
+        .filter(|n| !previous_by_ssid.contains_key(n.ssid.as_str()))
+        .map(|n| n.ssid.clone())
+        .collect();
+    let removed: Vec<String> = previous
+        .iter()
+        .filter(|n| !current_by_ssid.contains_key(n.ssid.as_str()))
+        .map(|n| n.ssid.clone())
+        .collect();
+    let signal_changed = current.iter().any(|n| {
+        match (previous_by_ssid.get(n.ssid.as_str()), n.signal_strength) {
+            (Some(Some(before)), Some(now)) => (before - now).abs() >= SIGNAL_CHANGE_THRESHOLD,
+            _ => false,
+        }
+    });
+
+    if added.is_empty() && removed.is_empty() && !signal_changed {
+        return None;
+    }
+    Some(WifiNetworksUpdate {
+        networks: current.to_vec(),
+        added,
+        removed,
+    })
+}
+
+/// Start (or restart) the background scan
+#[tauri::command]
+pub fn start_wifi_scan_stream(
+    app_handle: AppHandle,
+    state: State<WifiScanStreamState>,
+    interval_secs: Option<u64>,
+) {
+    let interval = interval_secs.unwrap_or(DEFAULT_INTERVAL_SECS).max(MIN_INTERVAL_SECS);
+
+    let task = tauri::async_runtime::spawn(async move {
+        let mut previous: Vec<WifiNetwork> = Vec::new();
+        loop {
+            match tokio::task::spawn_blocking(scan_wifi_sync).await {
+                Ok(Ok(networks)) => {
+                    if let Some(update) = diff_networks(&previous, &networks) {
+                        let _ = app_handle.emit("wifi-networks-updated", update);
+                    }
+                    previous = networks;
+                }
+                Ok(Err(e)) => eprintln!("[wifi] ⚠️ Background scan failed: {}", e),
+                Err(e) => eprintln!("[wifi] ⚠️ Background scan task failed: {}", e),
+            }
+            tokio::time::sleep(tokio::time::Duration::from_secs(interval)).await;
+        }
+    });
+
+    if let Some(old_task) = state.task.lock().unwrap().replace(task) {
+        old_task.abort();
+    }
+    println!("[wifi] 📡 Background scan started (every {}s)", interval);
+}
+
+#[tauri::command]
+pub fn stop_wifi_scan_stream(state: State<WifiScanStreamState>) {
+    if let Some(task) = state.task.lock().unwrap().take() {
+        task.abort();
+        println!("[wifi] 📡 Background scan stopped");
+    }
+}
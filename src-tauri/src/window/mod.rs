@@ -1,4 +1,337 @@
-use tauri::{Manager, AppHandle};
+/// Window management
+///
+/// Remembers size, position and monitor per window label in `<app data>/windows.json`
+/// and puts windows back there when they open again (a position on a monitor that is
+/// gone is dropped, the window is centered instead).
+///
+/// Auxiliary windows (log console, 3D viewer, serial console, HUD) are opened through
+/// `open_aux_window`, one instance per label, loading the frontend route of `AUX_WINDOWS`.
+/// The ones still open when the app quits are reopened at the next launch, unless their
+/// lifecycle says otherwise (the serial console takes the robot's port from the daemon).
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager, PhysicalPosition, PhysicalSize, State, WebviewUrl, WebviewWindow, WebviewWindowBuilder, Window};
+
+const LAYOUT_FILE: &str = "windows.json";
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+pub struct AuxWindow {
+    pub label: &'static str,
+    /// Frontend route (index.html#<route>)
+    pub route: &'static str,
+    pub title: &'static str,
+    pub size: (f64, f64),
+    pub min_size: (f64, f64),
+    /// Reopened at launch if it was open when the app quit
+    pub restore_on_launch: bool,
+    pub always_on_top: bool,
+    pub decorations: bool,
+}
+
+pub const LOG_CONSOLE: AuxWindow = AuxWindow {
+    label: "log-console",
+    route: "log-console",
+    title: "Reachy Mini - Logs",
+    size: (720.0, 480.0),
+    min_size: (400.0, 240.0),
+    restore_on_launch: true,
+    always_on_top: false,
+    decorations: true,
+};
+
+pub const VIEWER_3D: AuxWindow = AuxWindow {
+    label: "viewer-3d",
+    route: "viewer-3d",
+    title: "Reachy Mini - 3D Viewer",
+    size: (640.0, 640.0),
+    min_size: (320.0, 320.0),
+    restore_on_launch: true,
+    always_on_top: false,
+    decorations: true,
+};
+
+pub const SERIAL_CONSOLE: AuxWindow = AuxWindow {
+    label: "serial-console",
+    route: "serial-console",
+    title: "Reachy Mini - Serial Console",
+    size: (720.0, 480.0),
+    min_size: (480.0, 320.0),
+    restore_on_launch: false,
+    always_on_top: false,
+    decorations: true,
+};
+
+pub const HUD: AuxWindow = AuxWindow {
+    label: "hud",
+    route: "hud",
+    title: "Reachy Mini - HUD",
+    size: (300.0, 140.0),
+    min_size: (240.0, 110.0),
+    restore_on_launch: true,
+    always_on_top: true,
+    decorations: false,
+};
+
+pub const AUX_WINDOWS: &[&AuxWindow] = &[&LOG_CONSOLE, &VIEWER_3D, &SERIAL_CONSOLE, &HUD];
+
+/// Outer position and inner size in physical pixels
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct WindowGeometry {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub maximized: bool,
+    /// Monitor the window was on
+    pub monitor: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+struct WindowLayout {
+    windows: BTreeMap<String, WindowGeometry>,
+    /// Auxiliary windows open when the app last quit
+    open: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct WindowInfo {
+    pub label: String,
+    pub open: bool,
+    pub geometry: Option<WindowGeometry>,
+}
+
+pub struct WindowLayoutState {
+    layout: Mutex<WindowLayout>,
+    path: Mutex<Option<PathBuf>>,
+    /// Set once the app quits: windows closing from then on stay in `open`
+    quitting: Mutex<bool>,
+}
+
+// ============================================================================
+// HELPER FUNCTIONS
+// ============================================================================
+
+impl WindowLayoutState {
+    pub fn new() -> Self {
+        Self {
+            layout: Mutex::new(WindowLayout::default()),
+            path: Mutex::new(None),
+            quitting: Mutex::new(false),
+        }
+    }
+
+    /// Load the layout from the app data directory (empty if missing or corrupted)
+    pub fn load(&self, app_data_dir: &Path) {
+        let path = app_data_dir.join(LAYOUT_FILE);
+        let layout = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
+        *self.layout.lock().unwrap() = layout;
+        *self.path.lock().unwrap() = Some(path);
+    }
+
+    fn save(&self) {
+        let layout = self.layout.lock().unwrap();
+        let Some(path) = self.path.lock().unwrap().clone() else {
+            return;
+        };
+        let result = serde_json::to_string_pretty(&*layout)
+            .map_err(|e| e.to_string())
+            .and_then(|content| std::fs::write(&path, content).map_err(|e| e.to_string()));
+        if let Err(e) = result {
+            eprintln!("[window] ⚠️ Failed to save window layout: {}", e);
+        }
+    }
+
+    fn geometry(&self, label: &str) -> Option<WindowGeometry> {
+        self.layout.lock().unwrap().windows.get(label).cloned()
+    }
+}
+
+impl Default for WindowLayoutState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn aux_window(label: &str) -> Option<&'static AuxWindow> {
+    AUX_WINDOWS.iter().copied().find(|aux| aux.label == label)
+}
+
+/// Current geometry of a window (None while minimized: nothing worth keeping)
+fn read_geometry(window: &Window) -> Option<WindowGeometry> {
+    if window.is_minimized().unwrap_or(false) {
+        return None;
+    }
+    let position = window.outer_position().ok()?;
+    let size = window.inner_size().ok()?;
+    Some(WindowGeometry {
+        x: position.x,
+        y: position.y,
+        width: size.width,
+        height: size.height,
+        maximized: window.is_maximized().unwrap_or(false),
+        monitor: window.current_monitor().ok().flatten().and_then(|monitor| monitor.name().cloned()),
+    })
+}
+
+/// The saved position is still on a connected monitor
+fn is_on_screen(app_handle: &AppHandle, geometry: &WindowGeometry) -> bool {
+    let monitors = app_handle.available_monitors().unwrap_or_default();
+    monitors.iter().any(|monitor| {
+        let position = monitor.position();
+        let size = monitor.size();
+        let same_monitor = geometry.monitor.is_none() || monitor.name() == geometry.monitor.as_ref();
+        same_monitor
+            && geometry.x >= position.x
+            && geometry.y >= position.y
+            && geometry.x < position.x + size.width as i32
+            && geometry.y < position.y + size.height as i32
+    })
+}
+
+/// Put a window back where it was (size only for resizable windows)
+fn apply_geometry(app_handle: &AppHandle, window: &WebviewWindow) {
+    let Some(geometry) = app_handle.state::<WindowLayoutState>().geometry(window.label()) else {
+        return;
+    };
+    if window.is_resizable().unwrap_or(false) && geometry.width > 0 && geometry.height > 0 {
+        let _ = window.set_size(PhysicalSize::new(geometry.width, geometry.height));
+    }
+    if is_on_screen(app_handle, &geometry) {
+        let _ = window.set_position(PhysicalPosition::new(geometry.x, geometry.y));
+    } else {
+        let _ = window.center();
+    }
+    if geometry.maximized {
+        let _ = window.maximize();
+    }
+}
+
+/// Open an auxiliary window, or focus it if already open (returns the new window only)
+pub fn open_aux(app_handle: &AppHandle, aux: &AuxWindow) -> Result<Option<WebviewWindow>, String> {
+    if let Some(window) = app_handle.get_webview_window(aux.label) {
+        window
+            .set_focus()
+            .map_err(|e| format!("Failed to focus {}: {}", aux.label, e))?;
+        return Ok(None);
+    }
+
+    let window = WebviewWindowBuilder::new(
+        app_handle,
+        aux.label,
+        WebviewUrl::App(format!("index.html#{}", aux.route).into()),
+    )
+    .title(aux.title)
+    .inner_size(aux.size.0, aux.size.1)
+    .min_inner_size(aux.min_size.0, aux.min_size.1)
+    .always_on_top(aux.always_on_top)
+    .decorations(aux.decorations)
+    .build()
+    .map_err(|e| format!("Failed to open {} window: {}", aux.label, e))?;
+    apply_geometry(app_handle, &window);
+
+    let state = app_handle.state::<WindowLayoutState>();
+    let mut layout = state.layout.lock().unwrap();
+    if !layout.open.iter().any(|label| label == aux.label) {
+        layout.open.push(aux.label.to_string());
+    }
+    println!("[window] 🪟 Opened {}", aux.label);
+    Ok(Some(window))
+}
+
+/// Open a window with what its lifecycle needs besides the window itself
+fn open_known(app_handle: &AppHandle, aux: &'static AuxWindow) -> Result<(), String> {
+    if aux.label == SERIAL_CONSOLE.label {
+        crate::serial_console::open_serial_console_window(app_handle.clone())
+    } else if aux.label == HUD.label {
+        crate::hud::open_hud_window(app_handle.clone())
+    } else {
+        open_aux(app_handle, aux).map(|_| ())
+    }
+}
+
+// ============================================================================
+// WINDOW EVENTS
+// ============================================================================
+
+/// Window moved or resized: keep its geometry (saved when it closes or the app quits)
+pub fn track(window: &Window) {
+    if let Some(geometry) = read_geometry(window) {
+        let state = window.app_handle().state::<WindowLayoutState>();
+        state.layout.lock().unwrap().windows.insert(window.label().to_string(), geometry);
+    }
+}
+
+/// Window closed by the user: it won't be reopened at launch
+pub fn on_destroyed(window: &Window) {
+    let state = window.app_handle().state::<WindowLayoutState>();
+    if !*state.quitting.lock().unwrap() {
+        state.layout.lock().unwrap().open.retain(|label| label != window.label());
+    }
+    state.save();
+}
+
+/// The app is quitting: remember the open auxiliary windows and every geometry
+pub fn save_layout(app_handle: &AppHandle) {
+    let state = app_handle.state::<WindowLayoutState>();
+    *state.quitting.lock().unwrap() = true;
+    {
+        let mut layout = state.layout.lock().unwrap();
+        layout.open = AUX_WINDOWS
+            .iter()
+            .filter(|aux| aux.restore_on_launch && app_handle.get_webview_window(aux.label).is_some())
+            .map(|aux| aux.label.to_string())
+            .collect();
+    }
+    for window in app_handle.webview_windows().values() {
+        if let Some(geometry) = read_geometry(&window.as_ref().window()) {
+            state.layout.lock().unwrap().windows.insert(window.label().to_string(), geometry);
+        }
+    }
+    state.save();
+}
+
+// ============================================================================
+// SETUP
+// ============================================================================
+
+/// Restore the main window and reopen the auxiliary windows of the last session
+/// (call once in setup, after `load`)
+pub fn restore(app_handle: &AppHandle) {
+    if let Some(main) = app_handle.get_webview_window("main") {
+        apply_geometry(app_handle, &main);
+    }
+    let open = app_handle.state::<WindowLayoutState>().layout.lock().unwrap().open.clone();
+    for label in open {
+        match aux_window(&label).filter(|aux| aux.restore_on_launch) {
+            Some(aux) => {
+                if let Err(e) = open_known(app_handle, aux) {
+                    eprintln!("[window] ⚠️ {}", e);
+                }
+            }
+            None => app_handle
+                .state::<WindowLayoutState>()
+                .layout
+                .lock()
+                .unwrap()
+                .open
+                .retain(|open| *open != label),
+        }
+    }
+}
+
+// ============================================================================
+// TAURI COMMANDS
+// ============================================================================
 
 #[tauri::command]
 pub fn apply_transparent_titlebar(_app: AppHandle, _window_label: String) -> Result<(), String> {
@@ -7,16 +340,16 @@ pub fn apply_transparent_titlebar(_app: AppHandle, _window_label: String) -> Res
         if let Some(window) = _app.get_webview_window(&_window_label) {
             use cocoa::base::{id, YES};
             use objc::{msg_send, sel, sel_impl};
-            
+
             let ns_window_result = window.ns_window();
             match ns_window_result {
                 Ok(ns_window_ptr) => {
                     unsafe {
                         let ns_window = ns_window_ptr as id;
-                        
+
                         // Transparent titlebar and fullscreen content
                         let _: () = msg_send![ns_window, setTitlebarAppearsTransparent: YES];
-                        
+
                         // Full size content view so content goes under titlebar
                         let style_mask: u64 = msg_send![ns_window, styleMask];
                         let new_style = style_mask | (1 << 15); // NSWindowStyleMaskFullSizeContentView
@@ -30,7 +363,7 @@ pub fn apply_transparent_titlebar(_app: AppHandle, _window_label: String) -> Res
             Err(format!("Window '{}' not found", _window_label))
         }
     }
-    
+
     #[cfg(not(target_os = "macos"))]
     {
         // No-op on non-macOS
@@ -50,3 +383,34 @@ pub fn close_window(app: AppHandle, window_label: String) -> Result<(), String>
     Ok(())
 }
 
+/// Open (or focus) an auxiliary window: "log-console", "viewer-3d", "serial-console" or "hud"
+#[tauri::command]
+pub fn open_aux_window(app_handle: AppHandle, label: String) -> Result<(), String> {
+    let aux = aux_window(&label).ok_or_else(|| format!("Unknown window: {}", label))?;
+    open_known(&app_handle, aux)
+}
+
+/// Known windows with their saved geometry
+#[tauri::command]
+pub fn list_windows(app_handle: AppHandle, state: State<WindowLayoutState>) -> Vec<WindowInfo> {
+    std::iter::once("main")
+        .chain(AUX_WINDOWS.iter().map(|aux| aux.label))
+        .map(|label| WindowInfo {
+            label: label.to_string(),
+            open: app_handle.get_webview_window(label).is_some(),
+            geometry: state.geometry(label),
+        })
+        .collect()
+}
+
+/// Forget saved geometries (windows open with their default size next time)
+#[tauri::command]
+pub fn reset_window_layout(state: State<WindowLayoutState>) {
+    {
+        let mut layout = state.layout.lock().unwrap();
+        layout.windows.clear();
+        layout.open.clear();
+    }
+    state.save();
+    println!("[window] 🧹 Window layout reset");
+}
@@ -0,0 +1,106 @@
+import React, { useState, useEffect } from 'react';
+import { Box, Button, Typography } from '@mui/material';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+
+const CONNECTION_LABELS = {
+  disconnected: 'Disconnected',
+  usb_detected: 'Robot detected',
+  daemon_starting: 'Starting…',
+  ready: 'Ready',
+  wifi_mode: 'WiFi',
+  error: 'Error',
+};
+
+const CONNECTION_COLORS = {
+  ready: 'success.main',
+  wifi_mode: 'success.main',
+  daemon_starting: 'warning.main',
+  usb_detected: 'warning.main',
+  error: 'error.main',
+};
+
+/**
+ * Robot HUD window
+ * Compact always-on-top status for presenters (opened with open_hud_window).
+ * The whole window can be dragged, it has no title bar.
+ */
+export default function HudWindow() {
+  const [status, setStatus] = useState(null);
+  const [stopResult, setStopResult] = useState(null);
+
+  useEffect(() => {
+    invoke('get_hud_status')
+      .then(setStatus)
+      .catch(e => console.error('Error fetching HUD status:', e));
+    const unlisten = listen('hud-status', event => setStatus(event.payload));
+    return () => {
+      unlisten.then(unlisten => unlisten());
+    };
+  }, []);
+
+  const emergencyStop = async () => {
+    try {
+      const result = await invoke('emergency_stop');
+      setStopResult(result.stopped ? 'Stopped' : `Failed: ${result.errors.join(', ')}`);
+    } catch (e) {
+      setStopResult(`Failed: ${e}`);
+    }
+  };
+
+  const state = status?.connection?.state;
+  const degraded = state === 'wifi_mode' && status.connection.degraded;
+  const temperature = status?.max_temperature;
+
+  return (
+    <Box
+      data-tauri-drag-region
+      sx={{
+        height: '100vh',
+        p: 1.5,
+        display: 'flex',
+        flexDirection: 'column',
+        justifyContent: 'space-between',
+        bgcolor: 'background.paper',
+        userSelect: 'none',
+      }}
+    >
+      <Box data-tauri-drag-region sx={{ display: 'flex', alignItems: 'center', gap: 1 }}>
+        <Box
+          sx={{
+            width: 10,
+            height: 10,
+            borderRadius: '50%',
+            bgcolor: degraded ? 'warning.main' : CONNECTION_COLORS[state] || 'text.disabled',
+          }}
+        />
+        <Typography data-tauri-drag-region variant="body2" fontWeight={600}>
+          {CONNECTION_LABELS[state] || '…'}
+          {degraded ? ' (weak)' : ''}
+        </Typography>
+      </Box>
+      <Box data-tauri-drag-region sx={{ display: 'flex', gap: 2 }}>
+        <Typography
+          data-tauri-drag-region
+          variant="caption"
+          color={status?.overheating ? 'error.main' : 'text.secondary'}
+        >
+          {temperature != null ? `🌡️ ${temperature.toFixed(0)}°C` : '🌡️ –'}
+        </Typography>
+        {status?.battery_percent != null && (
+          <Typography data-tauri-drag-region variant="caption" color="text.secondary">
+            🔋 {status.battery_percent.toFixed(0)}%
+          </Typography>
+        )}
+        {stopResult && (
+          <Typography data-tauri-drag-region variant="caption" color="text.secondary">
+            {stopResult}
+          </Typography>
+        )}
+      </Box>
+      <Button variant="contained" color="error" size="small" onClick={emergencyStop}>
+        Emergency stop
+      </Button>
+    </Box>
+  );
+}
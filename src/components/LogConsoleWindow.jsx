@@ -0,0 +1,33 @@
+import React, { useState, useEffect } from 'react';
+import { Box } from '@mui/material';
+import { invoke } from '@tauri-apps/api/core';
+import LogConsole from './LogConsole';
+import useAppStore from '../store/useAppStore';
+
+const POLL_INTERVAL_MS = 1000;
+
+/**
+ * Log Console window
+ * Daemon logs in their own resizable window (opened with open_aux_window('log-console')).
+ */
+export default function LogConsoleWindow() {
+  const darkMode = useAppStore(state => state.darkMode);
+  const [logs, setLogs] = useState([]);
+
+  useEffect(() => {
+    const fetchLogs = () =>
+      invoke('get_logs')
+        .then(setLogs)
+        .catch(e => console.error('Error fetching logs:', e));
+
+    fetchLogs();
+    const interval = setInterval(fetchLogs, POLL_INTERVAL_MS);
+    return () => clearInterval(interval);
+  }, []);
+
+  return (
+    <Box sx={{ height: '100vh', p: 1 }}>
+      <LogConsole logs={logs} darkMode={darkMode} includeStoreLogs={false} height="100%" />
+    </Box>
+  );
+}
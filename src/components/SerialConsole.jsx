@@ -0,0 +1,116 @@
+import React, { useState, useEffect, useRef } from 'react';
+import { Box, Button, TextField, Typography } from '@mui/material';
+import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
+
+/**
+ * Serial Console window
+ * Raw terminal on the robot's serial port (opened with open_serial_console_window).
+ * Opening the console stops the daemon; closing it (or the window) restarts it.
+ */
+export default function SerialConsole() {
+  const [output, setOutput] = useState('');
+  const [input, setInput] = useState('');
+  const [session, setSession] = useState(null);
+  const [status, setStatus] = useState(null);
+  const outputRef = useRef(null);
+
+  useEffect(() => {
+    const unlistenData = listen('serial-console-data', event => {
+      setOutput(prev => (prev + event.payload.data).slice(-100000));
+    });
+    const unlistenClosed = listen('serial-console-closed', event => {
+      setSession(null);
+      setStatus(`Closed (${event.payload.reason})`);
+    });
+
+    return () => {
+      unlistenData.then(unlisten => unlisten());
+      unlistenClosed.then(unlisten => unlisten());
+    };
+  }, []);
+
+  useEffect(() => {
+    if (outputRef.current) {
+      outputRef.current.scrollTop = outputRef.current.scrollHeight;
+    }
+  }, [output]);
+
+  const open = async () => {
+    try {
+      const info = await invoke('open_serial_console', {});
+      setSession(info);
+      setStatus(
+        `Connected to ${info.port_name} at ${info.baud_rate} baud` +
+          (info.stopped_daemon ? ' (daemon stopped)' : '')
+      );
+    } catch (e) {
+      setStatus(`Error: ${e}`);
+    }
+  };
+
+  const close = async () => {
+    try {
+      await invoke('close_serial_console');
+    } catch (e) {
+      setStatus(`Error: ${e}`);
+    }
+  };
+
+  const send = async () => {
+    try {
+      await invoke('write_serial_console', { data: `${input}\n` });
+      setInput('');
+    } catch (e) {
+      setStatus(`Error: ${e}`);
+    }
+  };
+
+  return (
+    <Box sx={{ display: 'flex', flexDirection: 'column', height: '100vh', p: 2, gap: 1 }}>
+      <Box sx={{ display: 'flex', alignItems: 'center', gap: 1 }}>
+        <Button variant="outlined" size="small" onClick={session ? close : open}>
+          {session ? 'Close' : 'Open'}
+        </Button>
+        <Button size="small" onClick={() => setOutput('')}>
+          Clear
+        </Button>
+        <Typography variant="caption" color="text.secondary">
+          {status}
+        </Typography>
+      </Box>
+      <Box
+        ref={outputRef}
+        component="pre"
+        sx={{
+          flex: 1,
+          m: 0,
+          p: 1,
+          overflow: 'auto',
+          fontFamily: 'monospace',
+          fontSize: '12px',
+          bgcolor: 'background.paper',
+          border: 1,
+          borderColor: 'divider',
+          borderRadius: 1,
+          whiteSpace: 'pre-wrap',
+        }}
+      >
+        {output}
+      </Box>
+      <TextField
+        size="small"
+        placeholder={session ? 'Type a command and press Enter' : 'Open the console first'}
+        disabled={!session}
+        value={input}
+        onChange={e => setInput(e.target.value)}
+        onKeyDown={e => {
+          if (e.key === 'Enter') {
+            send();
+          }
+        }}
+        inputProps={{ style: { fontFamily: 'monospace' } }}
+      />
+    </Box>
+  );
+}
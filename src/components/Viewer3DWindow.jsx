@@ -0,0 +1,15 @@
+import React from 'react';
+import { Box } from '@mui/material';
+import Viewer3D from './viewer3d';
+
+/**
+ * 3D Viewer window
+ * Live robot model in its own window (opened with open_aux_window('viewer-3d')).
+ */
+export default function Viewer3DWindow() {
+  return (
+    <Box sx={{ width: '100vw', height: '100vh' }}>
+      <Viewer3D isActive={true} initialMode="normal" forceLoad={true} hideControls={false} />
+    </Box>
+  );
+}
@@ -286,13 +286,14 @@ export default function SettingsOverlay({ open, onClose, darkMode }) {
         }
       } else {
         // USB/Simulation mode: Use Tauri command
-        await invoke('update_daemon', { preRelease });
+        // The result also says which packages were checked against their hash
+        const message = await invoke('update_daemon', { preRelease });
 
         // Show success toast
-        showToast('Daemon updated successfully! Reconnect to use the new version.', 'success');
+        showToast(message, 'success');
 
         // Also log for developers
-        logSuccess('Daemon updated successfully! Reconnect to use the new version.');
+        logSuccess(message);
 
         // Give user time to see the toast (2 seconds)
         await new Promise(resolve => setTimeout(resolve, 2000));
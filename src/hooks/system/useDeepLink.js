@@ -189,6 +189,11 @@ export function useDeepLink({
       const state = callbacksRef.current;
       const { onInstallRequest, showToast, isActive } = state;
 
+      // reachy-mini:// links are handled by the backend (deep-link-request event)
+      if (!url.startsWith('reachymini:')) {
+        return;
+      }
+
       try {
         // Parse the URL - handle both formats:
         // reachymini://install/app-name
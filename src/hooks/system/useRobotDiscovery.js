@@ -119,7 +119,8 @@ async function checkWifiRobot(isRobotBlacklisted) {
  */
 async function checkUsbRobot() {
   try {
-    const portName = await invoke('check_usb_robot');
+    const robots = await invoke('check_usb_robot');
+    const portName = robots[0]?.port_name ?? null;
     return { available: portName !== null, portName };
   } catch (e) {
     console.error('USB check error:', e);
@@ -48,7 +48,9 @@ export const useUsbDetection = () => {
       }
 
       // Normal mode: real USB check
-      const portName = await invoke('check_usb_robot');
+      // List of connected robots ({ port_name, serial_number, location }), first one is used
+      const robots = await invoke('check_usb_robot');
+      const portName = robots[0]?.port_name ?? null;
 
       // Ensure at least minimum delay for smooth UX on first check only
       if (isFirstCheck) {
@@ -5,10 +5,17 @@ import { ThemeProvider, createTheme, CssBaseline } from '@mui/material';
 // 🎨 AUTOMATIC MODE DETECTION
 // VITE_WEB_MODE=true → Web-only dashboard (served by daemon)
 // /dev path → DevPlayground
+// #serial-console → Serial Console window
+// #log-console / #viewer-3d → Log Console / 3D Viewer windows
+// #hud → Robot HUD window
 // Otherwise → Normal Tauri App
 const isWebMode = import.meta.env.VITE_WEB_MODE === 'true' || !window.__TAURI__;
 const isDevPath = window.location.pathname === '/dev' || window.location.hash === '#dev';
 const DEV_MODE = isDevPath && !isWebMode;
+const SERIAL_CONSOLE_MODE = window.location.hash === '#serial-console' && !isWebMode;
+const LOG_CONSOLE_MODE = window.location.hash === '#log-console' && !isWebMode;
+const VIEWER_3D_MODE = window.location.hash === '#viewer-3d' && !isWebMode;
+const HUD_MODE = window.location.hash === '#hud' && !isWebMode;
 
 // Mock Tauri APIs if not in Tauri (browser/web mode)
 if (typeof window !== 'undefined' && !window.__TAURI__) {
@@ -34,6 +41,10 @@ if (typeof window !== 'undefined' && !window.__TAURI__) {
 import App from './components/App';
 import DevPlayground from './components/DevPlayground';
 import WebApp from './components/WebApp';
+import SerialConsole from './components/SerialConsole';
+import LogConsoleWindow from './components/LogConsoleWindow';
+import Viewer3DWindow from './components/Viewer3DWindow';
+import HudWindow from './components/HudWindow';
 import robotModelCache from './utils/robotModelCache';
 import useAppStore from './store/useAppStore';
 
@@ -206,8 +217,20 @@ function ThemeWrapper({ children }) {
 }
 
 // Choose component to display based on mode
-// Priority: WebMode > DevMode > Normal App
-const RootComponent = isWebMode ? WebApp : DEV_MODE ? DevPlayground : App;
+// Priority: WebMode > DevMode > Auxiliary windows > Normal App
+const RootComponent = isWebMode
+  ? WebApp
+  : DEV_MODE
+    ? DevPlayground
+    : SERIAL_CONSOLE_MODE
+      ? SerialConsole
+      : LOG_CONSOLE_MODE
+        ? LogConsoleWindow
+        : VIEWER_3D_MODE
+          ? Viewer3DWindow
+          : HUD_MODE
+            ? HudWindow
+            : App;
 
 console.log(`[Main] Mode: ${isWebMode ? 'WEB' : DEV_MODE ? 'DEV' : 'TAURI'}`);
 
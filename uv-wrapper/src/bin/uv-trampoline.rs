@@ -1,19 +1,29 @@
 use std::env;
 use std::path::PathBuf;
 use std::process::{Command, ExitCode};
+#[cfg(target_os = "macos")]
 use std::fs;
 
-use uv_wrapper::{find_cpython_folder, lookup_bin_folder, patching_pyvenv_cfg};
+use uv_wrapper::{emit_event, find_cpython_folder, isolate_process_tree, load_config, lookup_bin_folder, patching_pyvenv_cfg, resolve_console_script, set_json_output, SetupError, TrampolineEvent};
 
 #[cfg(target_os = "windows")]
-use uv_wrapper::{get_local_app_data_dir, is_program_files_path, setup_local_venv_windows};
+use uv_wrapper::{get_local_app_data_dir, is_program_files_path, setup_local_venv_windows, CopyMode};
 
 #[cfg(target_os = "linux")]
 use uv_wrapper::{get_xdg_data_home, is_system_lib_path, setup_local_venv_linux};
 
+#[cfg(target_os = "macos")]
+use uv_wrapper::{get_app_support_dir, is_app_bundle_path, setup_local_venv_macos};
+
+use uv_wrapper::{verify_local_venv, VerifyMode};
+
 #[cfg(not(target_os = "windows"))]
 use signal_hook::{consts::TERM_SIGNALS, flag::register};
 
+/// How long the child gets to exit after SIGTERM before it is killed
+#[cfg(not(target_os = "windows"))]
+const CHILD_STOP_GRACE: std::time::Duration = std::time::Duration::from_secs(3);
+
 /// Determines possible folders according to the platform
 /// 
 /// The uv installation script can install the executable:
@@ -28,8 +38,18 @@ fn get_possible_bin_folders() -> Vec<&'static str> {
     ];
     
     // On macOS, apps are in a bundle with structure App.app/Contents/Resources
+    // BUT: pip installing inside the signed bundle breaks its signature and is wiped on
+    // app updates, so we copy to ~/Library/Application Support/Reachy Mini Control/
     #[cfg(target_os = "macos")]
     {
+        // Priority 1: Application Support (writable, outside the bundle)
+        if let Some(local_dir) = get_app_support_dir() {
+            // We need to leak the string to get a static reference
+            // This is fine because we only call this function once
+            let local_path: &'static str = Box::leak(local_dir.to_string_lossy().into_owned().into_boxed_str());
+            folders.insert(0, local_path); // Insert at beginning for priority
+        }
+        
         folders.push("../Resources");
         folders.push("../Resources/bin");
         folders.push("../Resources/binaries");
@@ -313,14 +333,152 @@ fn resign_all_venv_binaries(venv_dir: &PathBuf, signing_identity: &str) -> Resul
     Ok(())
 }
 
-#[cfg(not(target_os = "macos"))]
-fn resign_all_venv_binaries(_venv_dir: &PathBuf, _signing_identity: &str) -> Result<(), String> {
-    // No-op on non-macOS
-    Ok(())
+
+/// Report a failed local venv setup (the caller falls back to the bundled venv)
+fn report_setup_error(e: &SetupError) {
+    match e {
+        SetupError::InsufficientSpace { required, available } => {
+            emit_event(&TrampolineEvent::InsufficientSpace {
+                required: *required,
+                available: *available,
+            });
+            eprintln!("❌ {}", e);
+            eprintln!(
+                "   Free up at least {} MB on the disk holding your user profile, then restart the app",
+                (required - available).div_ceil(1024 * 1024)
+            );
+        }
+        SetupError::Failed(error) => {
+            emit_event(&TrampolineEvent::SetupFailed { error: error.clone() });
+            eprintln!("⚠️  Failed to setup local venv: {}", e);
+        }
+    }
+}
+
+/// Bundled install dir and local copy of the venv, on platforms that copy it
+#[cfg(target_os = "windows")]
+fn local_venv_dirs() -> Option<(PathBuf, PathBuf)> {
+    let exe_dir = env::current_exe().ok()?.parent()?.to_path_buf();
+    if !is_program_files_path(&exe_dir) {
+        return None;
+    }
+    Some((exe_dir, get_local_app_data_dir()?))
+}
+
+#[cfg(target_os = "linux")]
+fn local_venv_dirs() -> Option<(PathBuf, PathBuf)> {
+    let exe_dir = env::current_exe().ok()?.parent()?.to_path_buf();
+    if !is_system_lib_path(&exe_dir) {
+        return None;
+    }
+    Some((PathBuf::from("/usr/lib/Reachy Mini Control"), get_xdg_data_home()?))
+}
+
+#[cfg(target_os = "macos")]
+fn local_venv_dirs() -> Option<(PathBuf, PathBuf)> {
+    let exe_dir = env::current_exe().ok()?.parent()?.to_path_buf();
+    if !is_app_bundle_path(&exe_dir) {
+        return None;
+    }
+    Some((find_bundle_resources_dir(&exe_dir)?, get_app_support_dir()?))
+}
+
+/// Folder holding the bundled .venv inside App.app/Contents/Resources
+#[cfg(target_os = "macos")]
+fn find_bundle_resources_dir(exe_dir: &std::path::Path) -> Option<PathBuf> {
+    ["../Resources/binaries", "../Resources"]
+        .iter()
+        .map(|folder| exe_dir.join(folder))
+        .find(|dir| dir.join(".venv").exists())
+}
+
+/// `uv-trampoline repair`: fully verify the local venv copy and restore damaged files
+fn repair_local_venv() -> ExitCode {
+    let Some((bundle_dir, local_dir)) = local_venv_dirs() else {
+        println!("✅ Not running from a system install, there is no local venv copy to repair");
+        return ExitCode::SUCCESS;
+    };
+    if !local_dir.join(".venv").exists() {
+        println!("📦 No local venv yet, it will be created on next launch");
+        return ExitCode::SUCCESS;
+    }
+
+    println!("🔍 Verifying local venv at {:?} against {:?}...", local_dir, bundle_dir);
+    match verify_local_venv(&bundle_dir, &local_dir, VerifyMode::Full, true) {
+        Ok(report) => {
+            println!("   Checked {} files", report.checked);
+            for path in &report.missing {
+                println!("   ❓ missing: {}", path);
+            }
+            for path in &report.corrupted {
+                println!("   ❌ corrupted: {}", path);
+            }
+            if !report.changed_by_pip.is_empty() {
+                println!("   ℹ️  {} files of packages updated by pip, kept as is", report.changed_by_pip.len());
+            }
+            if report.pyvenv_cfg_repaired {
+                println!("   🔧 pyvenv.cfg repaired");
+            }
+            if report.is_healthy() {
+                println!("✅ Local venv is intact");
+            } else {
+                println!("🩹 Restored {} files", report.repaired);
+            }
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("❌ Error: Unable to repair local venv: {}", e);
+            ExitCode::FAILURE
+        }
+    }
 }
 
 fn main() -> ExitCode {
-    let args = env::args().skip(1).collect::<Vec<String>>();
+    let mut args = env::args().skip(1).collect::<Vec<String>>();
+
+    // Trampoline flag (not forwarded): structured event lines on stdout for the app
+    if args.first().map(String::as_str) == Some("--json") {
+        args.remove(0);
+        set_json_output(true);
+    }
+
+    // Trampoline flag: `--script <name> [--] <args>` runs a console script of the venv
+    let script = if args.first().map(String::as_str) == Some("--script") {
+        args.remove(0);
+        if args.is_empty() {
+            eprintln!("❌ Error: --script requires a script name");
+            return ExitCode::FAILURE;
+        }
+        let name = args.remove(0);
+        if args.first().map(String::as_str) == Some("--") {
+            args.remove(0);
+        }
+        Some(name)
+    } else {
+        None
+    };
+
+    // Trampoline subcommand (uv itself has no `repair` command)
+    if args.first().map(String::as_str) == Some("repair") {
+        return repair_local_venv();
+    }
+
+    // Own process group / job, so the app can stop everything launched from here
+    if let Err(e) = isolate_process_tree() {
+        eprintln!("⚠️  Warning: Unable to isolate process tree: {}", e);
+    }
+
+    let config = match load_config() {
+        Ok((config, Some(path))) => {
+            println!("⚙️  Using config {:?}", path);
+            config
+        }
+        Ok((config, None)) => config,
+        Err(e) => {
+            eprintln!("⚠️  Ignoring trampoline config: {}", e);
+            Default::default()
+        }
+    };
 
     let uv_exe = if cfg!(target_os = "windows") {
         "uv.exe"
@@ -337,14 +495,15 @@ fn main() -> ExitCode {
             .and_then(|p| p.parent().map(|p| p.to_path_buf()))
             .unwrap_or_else(|| PathBuf::from("."));
         
-        if is_program_files_path(&exe_dir) {
+        if config.venv_dir.is_none() && is_program_files_path(&exe_dir) {
             println!("📍 Running from Program Files, checking local venv...");
-            match setup_local_venv_windows(&exe_dir) {
+            let mode = if config.hardlink_venv { CopyMode::Hardlink } else { CopyMode::Copy };
+            match setup_local_venv_windows(&exe_dir, mode) {
                 Ok(local_dir) => {
                     println!("✅ Using local venv at {:?}", local_dir);
                 }
                 Err(e) => {
-                    eprintln!("⚠️  Failed to setup local venv: {}", e);
+                    report_setup_error(&e);
                     eprintln!("   Will try to use Program Files directly (may fail)");
                 }
             }
@@ -360,7 +519,7 @@ fn main() -> ExitCode {
             .and_then(|p| p.parent().map(|p| p.to_path_buf()))
             .unwrap_or_else(|| PathBuf::from("."));
         
-        if is_system_lib_path(&exe_dir) {
+        if config.venv_dir.is_none() && is_system_lib_path(&exe_dir) {
             println!("📍 Running from /usr/lib/, checking local venv...");
             // Look for the actual install dir with the venv
             let install_dir = PathBuf::from("/usr/lib/Reachy Mini Control");
@@ -370,7 +529,7 @@ fn main() -> ExitCode {
                         println!("✅ Using local venv at {:?}", local_dir);
                     }
                     Err(e) => {
-                        eprintln!("⚠️  Failed to setup local venv: {}", e);
+                        report_setup_error(&e);
                         eprintln!("   Will try to use /usr/lib/ directly (may fail)");
                     }
                 }
@@ -378,7 +537,43 @@ fn main() -> ExitCode {
         }
     }
     
-    let possible_folders = get_possible_bin_folders();
+    // On macOS, if running from the .app bundle, copy venv to ~/Library/Application Support/
+    // This also sidesteps AppTranslocation, where the bundle is mounted read-only
+    #[cfg(target_os = "macos")]
+    {
+        let exe_dir = env::current_exe()
+            .ok()
+            .and_then(|p| p.parent().map(|p| p.to_path_buf()))
+            .unwrap_or_else(|| PathBuf::from("."));
+        
+        if config.venv_dir.is_none() && is_app_bundle_path(&exe_dir) {
+            println!("📍 Running from the app bundle, checking local venv...");
+            if let Some(resources_dir) = find_bundle_resources_dir(&exe_dir) {
+                match setup_local_venv_macos(&resources_dir) {
+                    Ok(local_dir) => {
+                        println!("✅ Using local venv at {:?}", local_dir);
+                    }
+                    Err(e) => {
+                        report_setup_error(&e);
+                        eprintln!("   Will try to use the app bundle directly (may break its signature)");
+                    }
+                }
+            }
+        }
+    }
+    
+    // Config overrides: an explicit venv dir, or folders searched before (or instead of) the defaults
+    let venv_dir = config.venv_dir.as_ref().map(|dir| dir.to_string_lossy().into_owned());
+    let possible_folders: Vec<&str> = match &venv_dir {
+        Some(dir) => vec![dir.as_str()],
+        None => {
+            let mut folders: Vec<&str> = config.bin_folders.iter().map(String::as_str).collect();
+            if !config.replace_default_bin_folders {
+                folders.extend(get_possible_bin_folders());
+            }
+            folders
+        }
+    };
     let uv_folder = match lookup_bin_folder(&possible_folders, uv_exe) {
         Some(folder) => folder,
         None => {
@@ -413,12 +608,12 @@ fn main() -> ExitCode {
         // Check if this is an AppTranslocation error
         if e.contains("APP_TRANSLOCATION_ERROR") {
             eprintln!("❌ AppTranslocation Error: {}", e);
-            eprintln!("");
+            eprintln!();
             eprintln!("📱 Please move the app to the Applications folder:");
             eprintln!("   1. Open Finder");
             eprintln!("   2. Drag 'Reachy Mini Control.app' to Applications");
             eprintln!("   3. Launch from Applications");
-            eprintln!("");
+            eprintln!();
             eprintln!("This is required because macOS isolates apps downloaded from the internet.");
             return ExitCode::FAILURE;
         }
@@ -438,7 +633,22 @@ fn main() -> ExitCode {
     // Check if the first argument is a Python executable path (e.g., .venv/bin/python3)
     // If so, execute it directly instead of passing through uv
     println!("🔍 Checking args: {:?}", args);
-    let mut cmd = if !args.is_empty() && args[0].contains("python") {
+    let mut cmd = if let Some(name) = &script {
+        let launch = match resolve_console_script(&working_dir, name) {
+            Ok(launch) => launch,
+            Err(e) => {
+                eprintln!("❌ Error: {}", e);
+                return ExitCode::FAILURE;
+            }
+        };
+        println!("📜 Running venv script {}: {:?} {:?}", name, launch.program, launch.args);
+        let mut c = Command::new(&launch.program);
+        c.env("UV_WORKING_DIR", &working_dir)
+         .env("UV_PYTHON_INSTALL_DIR", &working_dir)
+         .args(&launch.args)
+         .args(&args);
+        c
+    } else if !args.is_empty() && args[0].contains("python") {
         println!("✅ Detected Python executable: {}", args[0]);
         
         // Convert Unix-style path to Windows-style if needed
@@ -508,22 +718,30 @@ fn main() -> ExitCode {
     cmd.env("PATH", &new_path);
     println!("📍 Added {} to PATH for subprocess", working_dir.display());
     
+    // Proxy and extra variables from the config file
+    for (name, value) in config.env_vars() {
+        println!("⚙️  Setting {} from config", name);
+        cmd.env(name, value);
+    }
+    
     // Check if this is a pip install command (for auto-signing after installation)
     #[cfg(target_os = "macos")]
     let is_pip_install = !args.is_empty() && args[0] == "pip" && args.len() >= 2 && args[1] == "install";
     
-    #[cfg(not(target_os = "macos"))]
-    let is_pip_install = false;
-    
     println!("🚀 Launching process: {:?}", cmd);
     
     let mut child = match cmd.spawn() {
         Ok(child) => child,
         Err(e) => {
+            emit_event(&TrampolineEvent::SpawnFailed { error: e.to_string() });
             eprintln!("❌ Error: Unable to spawn process: {}", e);
             return ExitCode::FAILURE;
         }
     };
+    emit_event(&TrampolineEvent::Spawned {
+        program: cmd.get_program().to_string_lossy().into_owned(),
+        pid: child.id(),
+    });
 
     // Signal handling configuration on Unix
     #[cfg(not(target_os = "windows"))]
@@ -539,12 +757,24 @@ fn main() -> ExitCode {
         }
         
         // Wait loop with signal checking
+        let mut kill_deadline: Option<std::time::Instant> = None;
     loop {
             // Check if a termination signal was received
             if term_now.load(Ordering::Relaxed) {
-                eprintln!("🛑 Termination signal received, stopping child process...");
-                let _ = child.kill();
-                break;
+                match kill_deadline {
+                    // Let the daemon shut down cleanly (torque off) before forcing it
+                    None => {
+                        eprintln!("🛑 Termination signal received, stopping child process...");
+                        unsafe { libc::kill(child.id() as libc::pid_t, libc::SIGTERM) };
+                        kill_deadline = Some(std::time::Instant::now() + CHILD_STOP_GRACE);
+                    }
+                    Some(deadline) if std::time::Instant::now() >= deadline => {
+                        eprintln!("🛑 Child process still running, killing it...");
+                        let _ = child.kill();
+                        break;
+                    }
+                    Some(_) => {}
+                }
             }
             
         match child.try_wait() {
@@ -553,6 +783,7 @@ fn main() -> ExitCode {
                     if exit_code != 0 {
                         eprintln!("⚠️  Process exited with code: {}", exit_code);
                     }
+                    emit_event(&TrampolineEvent::Exited { code: exit_code });
                     
                     // If pip install succeeded, re-sign all binaries in .venv
                     // This applies entitlements (disable-library-validation) to Python binaries
@@ -633,7 +864,11 @@ fn main() -> ExitCode {
         
         // Wait for process to terminate after kill
         match child.wait() {
-            Ok(status) => ExitCode::from(status.code().unwrap_or(1) as u8),
+            Ok(status) => {
+                let exit_code = status.code().unwrap_or(1);
+                emit_event(&TrampolineEvent::Exited { code: exit_code });
+                ExitCode::from(exit_code as u8)
+            }
             Err(e) => {
                 eprintln!("❌ Error during final wait: {}", e);
                 ExitCode::FAILURE
@@ -650,6 +885,7 @@ fn main() -> ExitCode {
                 if exit_code != 0 {
                     eprintln!("⚠️  Process exited with code: {}", exit_code);
                 }
+                emit_event(&TrampolineEvent::Exited { code: exit_code });
                 ExitCode::from(exit_code as u8)
             }
             Err(e) => {
@@ -0,0 +1,112 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::verify::MANIFEST_FILE;
+use crate::{get_app_support_dir, get_local_app_data_dir, get_xdg_data_home, SETUP_COMPLETE_MARKER};
+
+/// Files written next to the copied venv, besides .venv and cpython-*
+const RUNTIME_FILES: &[&str] = &[
+    "uv", "uvx", "uv.exe", "uvx.exe",
+    SETUP_COMPLETE_MARKER, MANIFEST_FILE,
+];
+
+/// What remove_local_runtime deleted
+#[derive(Debug, Clone, Default)]
+pub struct RemovedRuntime {
+    pub removed: Vec<PathBuf>,
+    pub bytes_reclaimed: u64,
+}
+
+/// Writable directory the bundled runtime is copied to on this platform:
+/// %LOCALAPPDATA%, $XDG_DATA_HOME or ~/Library/Application Support
+pub fn get_local_runtime_dir() -> Option<PathBuf> {
+    get_local_app_data_dir()
+        .or_else(get_xdg_data_home)
+        .or_else(get_app_support_dir)
+}
+
+/// Size of a file or directory tree, without following symlinks
+pub(crate) fn disk_usage(path: &Path) -> u64 {
+    let Ok(metadata) = fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if !metadata.is_dir() {
+        return metadata.len();
+    }
+    fs::read_dir(path)
+        .map(|entries| entries.flatten().map(|entry| disk_usage(&entry.path())).sum())
+        .unwrap_or(0)
+}
+
+/// Clear read-only flags so Windows lets us delete the tree (cpython ships read-only files)
+#[cfg(target_os = "windows")]
+fn make_writable(path: &Path) {
+    let Ok(metadata) = fs::symlink_metadata(path) else {
+        return;
+    };
+    if metadata.is_dir() {
+        if let Ok(entries) = fs::read_dir(path) {
+            for entry in entries.flatten() {
+                make_writable(&entry.path());
+            }
+        }
+    }
+    let mut permissions = metadata.permissions();
+    if permissions.readonly() {
+        #[allow(clippy::permissions_set_readonly_false)]
+        permissions.set_readonly(false);
+        let _ = fs::set_permissions(path, permissions);
+    }
+}
+
+/// Remove a file or directory tree; returns the space reclaimed
+pub fn remove_path_reclaiming(path: &Path) -> Result<u64, String> {
+    let size = disk_usage(path);
+
+    #[cfg(target_os = "windows")]
+    make_writable(path);
+
+    let metadata = fs::symlink_metadata(path)
+        .map_err(|e| format!("Failed to read metadata of {:?}: {}", path, e))?;
+    let result = if metadata.is_dir() {
+        fs::remove_dir_all(path)
+    } else {
+        fs::remove_file(path)
+    };
+    result
+        .map(|_| size)
+        .map_err(|e| format!("Failed to remove {:?}: {}", path, e))
+}
+
+/// Delete the local copy of the venv, cpython and uv made by the setup_local_venv_* functions.
+///
+/// Only the entries we created are removed: the directory itself is kept since the app
+/// may store other data there. The daemon must be stopped first (files in use).
+pub fn remove_local_runtime() -> Result<RemovedRuntime, String> {
+    let local_dir = get_local_runtime_dir()
+        .ok_or_else(|| "No local runtime directory on this platform".to_string())?;
+
+    let mut report = RemovedRuntime::default();
+    let Ok(entries) = fs::read_dir(&local_dir) else {
+        return Ok(report);
+    };
+
+    let mut targets: Vec<PathBuf> = entries
+        .flatten()
+        .filter(|entry| {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            name == ".venv" || name.starts_with("cpython-") || RUNTIME_FILES.contains(&name.as_ref())
+        })
+        .map(|entry| entry.path())
+        .collect();
+    // Marker first: an interrupted removal must not leave a venv that looks complete
+    targets.sort_by_key(|path| path.file_name().is_none_or(|name| name != SETUP_COMPLETE_MARKER));
+
+    for path in targets {
+        report.bytes_reclaimed += remove_path_reclaiming(&path)?;
+        report.removed.push(path);
+    }
+
+    Ok(report)
+}
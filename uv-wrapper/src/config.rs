@@ -0,0 +1,114 @@
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+/// Config file name, looked up next to the executable then in the user config dir
+pub const CONFIG_FILE: &str = "reachy-trampoline.toml";
+
+/// Explicit config file path (takes precedence over the lookup)
+pub const CONFIG_ENV_VAR: &str = "REACHY_TRAMPOLINE_CONFIG";
+
+/// Overrides for layouts the built-in search doesn't know (NixOS, Flatpak, custom installs)
+///
+/// ```toml
+/// bin_folders = ["/opt/reachy/lib"]     # searched before the defaults
+/// replace_default_bin_folders = false  # true: search only bin_folders
+/// venv_dir = "/opt/reachy/lib"         # folder holding uv, .venv and cpython-*
+/// hardlink_venv = true                 # Windows: hardlink the local venv instead of copying
+///
+/// [proxy]
+/// http = "http://proxy:3128"
+/// https = "http://proxy:3128"
+/// no_proxy = "localhost,127.0.0.1"
+///
+/// [env]
+/// PYTHONUTF8 = "1"
+/// ```
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TrampolineConfig {
+    pub bin_folders: Vec<String>,
+    pub replace_default_bin_folders: bool,
+    /// Skips the local venv setup and the bin folder search
+    pub venv_dir: Option<PathBuf>,
+    /// Windows: hardlink the files of the local venv copy (same volume only)
+    pub hardlink_venv: bool,
+    pub proxy: ProxyConfig,
+    /// Extra environment variables passed to uv / Python
+    pub env: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProxyConfig {
+    pub http: Option<String>,
+    pub https: Option<String>,
+    pub no_proxy: Option<String>,
+}
+
+impl TrampolineConfig {
+    /// Environment variables to set on the child process (proxy variables then `env`)
+    pub fn env_vars(&self) -> Vec<(String, String)> {
+        let mut vars = Vec::new();
+        for (names, value) in [
+            (["HTTP_PROXY", "http_proxy"], &self.proxy.http),
+            (["HTTPS_PROXY", "https_proxy"], &self.proxy.https),
+            (["NO_PROXY", "no_proxy"], &self.proxy.no_proxy),
+        ] {
+            if let Some(value) = value {
+                vars.extend(names.iter().map(|name| (name.to_string(), value.clone())));
+            }
+        }
+        vars.extend(self.env.iter().map(|(k, v)| (k.clone(), v.clone())));
+        vars
+    }
+}
+
+/// User config directory for the trampoline config
+/// - Windows: %APPDATA%\Reachy Mini Control\
+/// - macOS: ~/Library/Application Support/Reachy Mini Control/
+/// - Linux: $XDG_CONFIG_HOME/reachy-mini-control/ or ~/.config/reachy-mini-control/
+fn user_config_dir() -> Option<PathBuf> {
+    #[cfg(target_os = "windows")]
+    return env::var("APPDATA").ok().map(|dir| PathBuf::from(dir).join("Reachy Mini Control"));
+
+    #[cfg(target_os = "macos")]
+    return crate::get_app_support_dir();
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    return env::var("XDG_CONFIG_HOME")
+        .ok()
+        .map(PathBuf::from)
+        .or_else(|| env::var("HOME").ok().map(|home| PathBuf::from(home).join(".config")))
+        .map(|dir| dir.join("reachy-mini-control"));
+}
+
+/// Where the config file was found, if any
+pub fn find_config_file() -> Option<PathBuf> {
+    if let Ok(path) = env::var(CONFIG_ENV_VAR) {
+        return Some(PathBuf::from(path));
+    }
+    let next_to_exe = env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(|dir| dir.join(CONFIG_FILE)));
+    let in_user_config = user_config_dir().map(|dir| dir.join(CONFIG_FILE));
+
+    [next_to_exe, in_user_config]
+        .into_iter()
+        .flatten()
+        .find(|path| path.is_file())
+}
+
+/// Load the trampoline config; defaults when there is no config file
+pub fn load_config() -> Result<(TrampolineConfig, Option<PathBuf>), String> {
+    let Some(path) = find_config_file() else {
+        return Ok((TrampolineConfig::default(), None));
+    };
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+    let config = toml::from_str(&content)
+        .map_err(|e| format!("Invalid config {:?}: {}", path, e))?;
+    Ok((config, Some(path)))
+}
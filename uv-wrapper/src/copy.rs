@@ -0,0 +1,424 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+/// Upper bound on copy threads (disk bound, more threads don't help)
+const MAX_COPY_THREADS: usize = 8;
+
+/// Progress reported after each file copied or skipped
+#[derive(Debug, Clone)]
+pub struct CopyProgress {
+    pub files_done: usize,
+    pub files_total: usize,
+    pub bytes_done: u64,
+    pub bytes_total: u64,
+}
+
+impl CopyProgress {
+    pub fn percent(&self) -> u32 {
+        if self.bytes_total == 0 {
+            return 100;
+        }
+        (self.bytes_done * 100 / self.bytes_total) as u32
+    }
+}
+
+/// How files are materialized in the destination
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CopyMode {
+    #[default]
+    Copy,
+    /// Hardlink to the source when on the same volume (no extra disk space), copy otherwise.
+    /// Linked files share their content with the source: never modify them in place.
+    Hardlink,
+}
+
+/// What a copy did
+#[derive(Debug, Clone, Default)]
+pub struct CopyStats {
+    pub files_copied: usize,
+    /// Part of files_copied that were hardlinked rather than copied
+    pub files_linked: usize,
+    /// Already up to date in the destination (resumed copy)
+    pub files_skipped: usize,
+    pub symlinks: usize,
+    pub bytes_copied: u64,
+}
+
+struct FileEntry {
+    src: PathBuf,
+    dst: PathBuf,
+    len: u64,
+    modified: Option<SystemTime>,
+    permissions: fs::Permissions,
+}
+
+#[derive(Default)]
+struct Tree {
+    dirs: Vec<PathBuf>,
+    files: Vec<FileEntry>,
+    /// (link path in destination, link target)
+    symlinks: Vec<(PathBuf, PathBuf)>,
+}
+
+/// Walk `src` without following symlinks
+fn collect_tree(src: &Path, dst: &Path, tree: &mut Tree) -> Result<(), String> {
+    tree.dirs.push(dst.to_path_buf());
+
+    let entries = fs::read_dir(src)
+        .map_err(|e| format!("Failed to read directory {:?}: {}", src, e))?;
+
+    for entry in entries {
+        let entry = entry.map_err(|e| format!("Failed to read entry: {}", e))?;
+        let src_path = entry.path();
+        let dst_path = dst.join(entry.file_name());
+        let metadata = fs::symlink_metadata(&src_path)
+            .map_err(|e| format!("Failed to read metadata of {:?}: {}", src_path, e))?;
+
+        if metadata.file_type().is_symlink() {
+            let target = fs::read_link(&src_path)
+                .map_err(|e| format!("Failed to read link {:?}: {}", src_path, e))?;
+            tree.symlinks.push((dst_path, target));
+        } else if metadata.is_dir() {
+            collect_tree(&src_path, &dst_path, tree)?;
+        } else {
+            tree.files.push(FileEntry {
+                src: src_path,
+                dst: dst_path,
+                len: metadata.len(),
+                modified: metadata.modified().ok(),
+                permissions: metadata.permissions(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Destination already holds this file (same size and mtime): left by an interrupted copy
+fn is_up_to_date(file: &FileEntry) -> bool {
+    let Ok(metadata) = fs::symlink_metadata(&file.dst) else {
+        return false;
+    };
+    metadata.is_file() && metadata.len() == file.len && file.modified.is_some() && metadata.modified().ok() == file.modified
+}
+
+/// Copy one file, keeping permissions and mtime (so a resumed copy can skip it);
+/// returns true if it was hardlinked instead
+fn copy_file(file: &FileEntry, mode: CopyMode) -> Result<bool, String> {
+    let error = |e: std::io::Error| format!("Failed to copy {:?} to {:?}: {}", file.src, file.dst, e);
+
+    // A stale copy may be read-only (common in cpython): replace it rather than overwrite
+    if let Ok(metadata) = fs::symlink_metadata(&file.dst) {
+        // Windows refuses to delete read-only files
+        let mut permissions = metadata.permissions();
+        if permissions.readonly() {
+            #[allow(clippy::permissions_set_readonly_false)]
+            permissions.set_readonly(false);
+            let _ = fs::set_permissions(&file.dst, permissions);
+        }
+        fs::remove_file(&file.dst).map_err(error)?;
+    }
+
+    // Fails across volumes or on filesystems without links (FAT): fall back to a copy
+    if mode == CopyMode::Hardlink && fs::hard_link(&file.src, &file.dst).is_ok() {
+        return Ok(true);
+    }
+
+    let mut reader = fs::File::open(&file.src).map_err(error)?;
+    let mut writer = fs::File::create(&file.dst).map_err(error)?;
+    std::io::copy(&mut reader, &mut writer).map_err(error)?;
+    if let Some(modified) = file.modified {
+        writer.set_modified(modified).map_err(error)?;
+    }
+    drop(writer);
+
+    // Permissions last: the file may be read-only
+    fs::set_permissions(&file.dst, file.permissions.clone()).map_err(error)?;
+    Ok(false)
+}
+
+/// Hardlinking `src_file` into `dst_dir` works (same volume, filesystem with links).
+/// CopyMode::Hardlink silently falls back to a copy otherwise, so callers check this
+/// before assuming the copy takes no space.
+pub fn can_hardlink(src_file: &Path, dst_dir: &Path) -> bool {
+    let probe = dst_dir.join(".hardlink-probe");
+    let _ = fs::remove_file(&probe);
+    let linked = fs::hard_link(src_file, &probe).is_ok();
+    let _ = fs::remove_file(&probe);
+    linked
+}
+
+/// Copy a single file the way copy_dir_with_progress does (used to repair a local venv)
+pub(crate) fn copy_single_file(src: &Path, dst: &Path) -> Result<(), String> {
+    let metadata = fs::metadata(src)
+        .map_err(|e| format!("Failed to read metadata of {:?}: {}", src, e))?;
+    if let Some(parent) = dst.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create directory {:?}: {}", parent, e))?;
+    }
+    copy_file(&FileEntry {
+        src: src.to_path_buf(),
+        dst: dst.to_path_buf(),
+        len: metadata.len(),
+        modified: metadata.modified().ok(),
+        permissions: metadata.permissions(),
+    }, CopyMode::Copy)
+    .map(|_| ())
+}
+
+/// Recreate a symlink; falls back to copying its target where links can't be created
+/// (Windows without developer mode)
+fn copy_symlink(link: &Path, target: &Path) -> Result<(), String> {
+    if let Ok(existing) = fs::read_link(link) {
+        if existing == target {
+            return Ok(());
+        }
+    }
+    if fs::symlink_metadata(link).is_ok() {
+        let _ = fs::remove_file(link).or_else(|_| fs::remove_dir_all(link));
+    }
+
+    #[cfg(unix)]
+    let result = std::os::unix::fs::symlink(target, link);
+
+    #[cfg(windows)]
+    let result = {
+        let resolved = link.parent().map(|p| p.join(target)).unwrap_or_else(|| target.to_path_buf());
+        if resolved.is_dir() {
+            std::os::windows::fs::symlink_dir(target, link)
+        } else {
+            std::os::windows::fs::symlink_file(target, link)
+        }
+    };
+
+    match result {
+        Ok(()) => Ok(()),
+        Err(e) => {
+            let resolved = link.parent().map(|p| p.join(target)).unwrap_or_else(|| target.to_path_buf());
+            if resolved.is_dir() {
+                copy_dir_with_progress(&resolved, link, |_| {}).map(|_| ())
+            } else if resolved.is_file() {
+                fs::copy(&resolved, link)
+                    .map(|_| ())
+                    .map_err(|copy_err| format!("Failed to create link {:?} ({}) or copy it: {}", link, e, copy_err))
+            } else {
+                Err(format!("Failed to create link {:?} -> {:?}: {}", link, target, e))
+            }
+        }
+    }
+}
+
+/// Copy a directory recursively
+pub fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<(), String> {
+    copy_dir_with_progress(src, dst, |_| {}).map(|_| ())
+}
+
+/// Copy a directory recursively with parallel file copies, preserving symlinks and
+/// permissions. Files already present in `dst` with the same size and mtime are
+/// skipped, so an interrupted copy resumes where it stopped.
+///
+/// `on_progress` is called from the copy threads after each file.
+pub fn copy_dir_with_progress(
+    src: &Path,
+    dst: &Path,
+    on_progress: impl Fn(&CopyProgress) + Sync,
+) -> Result<CopyStats, String> {
+    copy_dir_with_mode(src, dst, CopyMode::Copy, on_progress)
+}
+
+/// copy_dir_with_progress, hardlinking files instead of copying them with CopyMode::Hardlink
+pub fn copy_dir_with_mode(
+    src: &Path,
+    dst: &Path,
+    mode: CopyMode,
+    on_progress: impl Fn(&CopyProgress) + Sync,
+) -> Result<CopyStats, String> {
+    if !src.exists() {
+        return Err(format!("Source directory does not exist: {:?}", src));
+    }
+
+    let mut tree = Tree::default();
+    collect_tree(src, dst, &mut tree)?;
+
+    for dir in &tree.dirs {
+        fs::create_dir_all(dir)
+            .map_err(|e| format!("Failed to create directory {:?}: {}", dir, e))?;
+    }
+
+    let files_total = tree.files.len();
+    let bytes_total: u64 = tree.files.iter().map(|f| f.len).sum();
+    let next = AtomicUsize::new(0);
+    let files_done = AtomicUsize::new(0);
+    let bytes_done = AtomicU64::new(0);
+    let files_skipped = AtomicUsize::new(0);
+    let files_linked = AtomicUsize::new(0);
+    let bytes_copied = AtomicU64::new(0);
+    let first_error = Mutex::new(None::<String>);
+
+    let threads = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(4)
+        .clamp(1, MAX_COPY_THREADS);
+
+    std::thread::scope(|scope| {
+        for _ in 0..threads {
+            scope.spawn(|| loop {
+                if first_error.lock().unwrap().is_some() {
+                    return;
+                }
+                let Some(file) = tree.files.get(next.fetch_add(1, Ordering::Relaxed)) else {
+                    return;
+                };
+
+                if is_up_to_date(file) {
+                    files_skipped.fetch_add(1, Ordering::Relaxed);
+                } else {
+                    match copy_file(file, mode) {
+                        Ok(true) => {
+                            files_linked.fetch_add(1, Ordering::Relaxed);
+                        }
+                        Ok(false) => {
+                            bytes_copied.fetch_add(file.len, Ordering::Relaxed);
+                        }
+                        Err(e) => {
+                            first_error.lock().unwrap().get_or_insert(e);
+                            return;
+                        }
+                    }
+                }
+
+                on_progress(&CopyProgress {
+                    files_done: files_done.fetch_add(1, Ordering::Relaxed) + 1,
+                    files_total,
+                    bytes_done: bytes_done.fetch_add(file.len, Ordering::Relaxed) + file.len,
+                    bytes_total,
+                });
+            });
+        }
+    });
+
+    if let Some(e) = first_error.into_inner().unwrap() {
+        return Err(e);
+    }
+
+    for (link, target) in &tree.symlinks {
+        copy_symlink(link, target)?;
+    }
+
+    let files_skipped = files_skipped.into_inner();
+    Ok(CopyStats {
+        files_copied: files_total - files_skipped,
+        files_skipped,
+        files_linked: files_linked.into_inner(),
+        symlinks: tree.symlinks.len(),
+        bytes_copied: bytes_copied.into_inner(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_dir, write_test_file};
+
+    #[test]
+    fn resumes_an_interrupted_copy() {
+        let root = test_dir("copy-resume");
+        let (src, dst) = (root.join("src"), root.join("dst"));
+        write_test_file(&src.join("a.py"), "a");
+        write_test_file(&src.join("pkg/b.py"), "b");
+        write_test_file(&src.join("pkg/c.py"), "c");
+
+        let first = copy_dir_with_progress(&src, &dst, |_| {}).unwrap();
+        assert_eq!((first.files_copied, first.files_skipped), (3, 0));
+
+        // Interrupted copy: one file missing, one truncated
+        fs::remove_file(dst.join("a.py")).unwrap();
+        fs::write(dst.join("pkg/b.py"), "").unwrap();
+
+        let resumed = copy_dir_with_progress(&src, &dst, |_| {}).unwrap();
+        assert_eq!((resumed.files_copied, resumed.files_skipped), (2, 1));
+        assert_eq!(fs::read_to_string(dst.join("a.py")).unwrap(), "a");
+        assert_eq!(fs::read_to_string(dst.join("pkg/b.py")).unwrap(), "b");
+        assert_eq!(fs::read_to_string(dst.join("pkg/c.py")).unwrap(), "c");
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn recopies_files_changed_in_the_source() {
+        let root = test_dir("copy-changed");
+        let (src, dst) = (root.join("src"), root.join("dst"));
+        write_test_file(&src.join("a.py"), "old");
+        copy_dir_with_progress(&src, &dst, |_| {}).unwrap();
+
+        // New app version: same path, different content
+        write_test_file(&src.join("a.py"), "new version");
+
+        let stats = copy_dir_with_progress(&src, &dst, |_| {}).unwrap();
+        assert_eq!((stats.files_copied, stats.files_skipped), (1, 0));
+        assert_eq!(fs::read_to_string(dst.join("a.py")).unwrap(), "new version");
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn reports_progress_for_every_file() {
+        let root = test_dir("copy-progress");
+        let (src, dst) = (root.join("src"), root.join("dst"));
+        write_test_file(&src.join("a.py"), "aa");
+        write_test_file(&src.join("b.py"), "bbb");
+
+        let last = Mutex::new(None::<CopyProgress>);
+        copy_dir_with_progress(&src, &dst, |progress| {
+            let mut last = last.lock().unwrap();
+            if last.as_ref().is_none_or(|l| progress.files_done > l.files_done) {
+                *last = Some(progress.clone());
+            }
+        })
+        .unwrap();
+
+        let last = last.into_inner().unwrap().unwrap();
+        assert_eq!((last.files_done, last.files_total), (2, 2));
+        assert_eq!((last.bytes_done, last.bytes_total, last.percent()), (5, 5, 100));
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn hardlinks_files_and_keeps_symlinks() {
+        let root = test_dir("copy-hardlink");
+        let (src, dst) = (root.join("src"), root.join("dst"));
+        write_test_file(&src.join("bin/python3.12"), "python");
+        std::os::unix::fs::symlink("python3.12", src.join("bin/python")).unwrap();
+
+        let stats = copy_dir_with_mode(&src, &dst, CopyMode::Hardlink, |_| {}).unwrap();
+        assert_eq!((stats.files_copied, stats.files_linked, stats.symlinks), (1, 1, 1));
+        assert_eq!(fs::read_link(dst.join("bin/python")).unwrap(), PathBuf::from("python3.12"));
+
+        use std::os::unix::fs::MetadataExt;
+        let inode = |dir: &Path| fs::metadata(dir.join("bin/python3.12")).unwrap().ino();
+        assert_eq!(inode(&src), inode(&dst));
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn probes_hardlink_support_without_leaving_files() {
+        let root = test_dir("copy-hardlink-probe");
+        let (src, dst) = (root.join("src"), root.join("dst"));
+        write_test_file(&src.join("pyvenv.cfg"), "home = /x");
+        fs::create_dir_all(&dst).unwrap();
+
+        assert!(can_hardlink(&src.join("pyvenv.cfg"), &dst));
+        // Nothing to link (or a link the filesystem refuses): the caller must plan a copy
+        assert!(!can_hardlink(&src.join("missing.cfg"), &dst));
+        assert_eq!(fs::read_dir(&dst).unwrap().count(), 0);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,29 @@
+use std::fmt;
+
+/// Error of the setup_local_venv_* functions
+#[derive(Debug, Clone)]
+pub enum SetupError {
+    /// Not enough free space on the destination volume (bytes)
+    InsufficientSpace { required: u64, available: u64 },
+    Failed(String),
+}
+
+impl fmt::Display for SetupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SetupError::InsufficientSpace { required, available } => write!(
+                f,
+                "Not enough disk space: {} MB required, {} MB available",
+                required / (1024 * 1024),
+                available / (1024 * 1024)
+            ),
+            SetupError::Failed(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl From<String> for SetupError {
+    fn from(message: String) -> Self {
+        SetupError::Failed(message)
+    }
+}
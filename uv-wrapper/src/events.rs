@@ -0,0 +1,88 @@
+use serde::Serialize;
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Prefix of event lines on stdout, telling them apart from uv / Python output
+pub const EVENT_PREFIX: &str = "::uv-trampoline:: ";
+
+static JSON_OUTPUT: AtomicBool = AtomicBool::new(false);
+
+/// Structured status emitted with `uv-trampoline --json`, one JSON object per line
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum TrampolineEvent {
+    SetupStarted {
+        source: String,
+        target: String,
+    },
+    CopyProgress {
+        /// ".venv" or the cpython folder name
+        label: String,
+        files_done: usize,
+        files_total: usize,
+        bytes_done: u64,
+        bytes_total: u64,
+        percent: u32,
+    },
+    CopyFinished {
+        label: String,
+        /// Already in place from an interrupted copy
+        files_skipped: usize,
+    },
+    PyvenvPatched {
+        path: String,
+    },
+    VenvRepaired {
+        repaired: usize,
+        missing: usize,
+        corrupted: usize,
+    },
+    /// The app was updated: the local venv is being brought up to date
+    ResyncPrepared {
+        stale_files_removed: usize,
+        replaced_packages: Vec<String>,
+    },
+    SetupComplete {
+        local_dir: String,
+    },
+    SetupFailed {
+        error: String,
+    },
+    /// Bytes; the copy was not started
+    InsufficientSpace {
+        required: u64,
+        available: u64,
+    },
+    Spawned {
+        program: String,
+        pid: u32,
+    },
+    SpawnFailed {
+        error: String,
+    },
+    Exited {
+        code: i32,
+    },
+}
+
+/// Enable event lines on stdout (off by default, human-readable prints only)
+pub fn set_json_output(enabled: bool) {
+    JSON_OUTPUT.store(enabled, Ordering::Relaxed);
+}
+
+pub fn json_output_enabled() -> bool {
+    JSON_OUTPUT.load(Ordering::Relaxed)
+}
+
+/// Print an event line if JSON output is enabled
+pub fn emit_event(event: &TrampolineEvent) {
+    if !json_output_enabled() {
+        return;
+    }
+    if let Ok(json) = serde_json::to_string(event) {
+        // Single write so lines from copy threads don't interleave
+        let mut stdout = std::io::stdout().lock();
+        let _ = writeln!(stdout, "{}{}", EVENT_PREFIX, json);
+        let _ = stdout.flush();
+    }
+}
@@ -1,5 +1,75 @@
 use std::{env, process::Command, path::PathBuf, fs};
 
+mod cleanup;
+mod config;
+mod copy;
+mod error;
+mod events;
+mod process_group;
+mod resync;
+mod scripts;
+mod space;
+mod verify;
+
+pub use cleanup::{get_local_runtime_dir, remove_local_runtime, remove_path_reclaiming, RemovedRuntime};
+pub use config::{find_config_file, load_config, ProxyConfig, TrampolineConfig, CONFIG_ENV_VAR, CONFIG_FILE};
+pub use copy::{can_hardlink, copy_dir_recursive, copy_dir_with_mode, copy_dir_with_progress, CopyMode, CopyProgress, CopyStats};
+pub use error::SetupError;
+pub use process_group::isolate_process_tree;
+pub use resync::{bundle_fingerprint, is_bundle_updated, prepare_resync, write_bundle_version, ResyncReport, BUNDLE_VERSION_FILE};
+pub use scripts::{resolve_console_script, ScriptLaunch};
+pub use space::{available_space, check_disk_space};
+pub use events::{emit_event, json_output_enabled, set_json_output, TrampolineEvent, EVENT_PREFIX};
+pub use verify::{verify_local_venv, write_venv_manifest, VerifyMode, VerifyReport};
+
+/// Written once the local venv is fully set up; a copy interrupted before this is resumed
+const SETUP_COMPLETE_MARKER: &str = ".setup-complete";
+
+/// Empty scratch directory for a test (removed first if a previous run left it)
+#[cfg(test)]
+pub(crate) fn test_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("uv-wrapper-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Write a file, creating its parent folders (test fixtures)
+#[cfg(test)]
+pub(crate) fn write_test_file(path: &std::path::Path, content: &str) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, content).unwrap();
+}
+
+/// Site-packages of the test venvs
+#[cfg(test)]
+pub(crate) const TEST_SITE_PACKAGES: &str = ".venv/lib/python3.12/site-packages";
+
+/// Bundle with reachy_mini 1.0 and a local copy of it, manifest and bundle version
+/// included. `extra_files` (path relative to site-packages, content) are installed with
+/// the package and listed in its RECORD. Returns (root, bundle, local)
+#[cfg(test)]
+pub(crate) fn installed_test_venv(name: &str, extra_files: &[(&str, &str)]) -> (PathBuf, PathBuf, PathBuf) {
+    let root = test_dir(name);
+    let (bundle, local) = (root.join("bundle"), root.join("local"));
+    let site_packages = bundle.join(TEST_SITE_PACKAGES);
+    write_test_file(&bundle.join(".venv/pyvenv.cfg"), "version = 3.12\n");
+    write_test_file(&bundle.join("cpython-3.12-linux/bin/python3"), "python");
+    write_test_file(&site_packages.join("reachy_mini/__init__.py"), "v1");
+    write_test_file(&site_packages.join("reachy_mini/legacy.py"), "legacy");
+    let mut record = "reachy_mini/__init__.py,,\nreachy_mini/legacy.py,,\nreachy_mini-1.0.dist-info/RECORD,,\n".to_string();
+    for (path, content) in extra_files {
+        write_test_file(&site_packages.join(path), content);
+        record.push_str(&format!("{},,\n", path));
+    }
+    write_test_file(&site_packages.join("reachy_mini-1.0.dist-info/RECORD"), &record);
+    copy_dir_recursive(&bundle, &local).unwrap();
+    patching_pyvenv_cfg(&local, "cpython-3.12-linux").unwrap();
+    write_venv_manifest(&bundle, &local).unwrap();
+    write_bundle_version(&bundle, &local).unwrap();
+    (root, bundle, local)
+}
+
 /// Gets the local app data directory for Windows
 /// Returns %LOCALAPPDATA%\Reachy Mini Control\
 #[cfg(target_os = "windows")]
@@ -35,6 +105,31 @@ pub fn get_xdg_data_home() -> Option<PathBuf> {
     None
 }
 
+/// Gets the Application Support directory for macOS
+/// Returns ~/Library/Application Support/Reachy Mini Control/
+#[cfg(target_os = "macos")]
+pub fn get_app_support_dir() -> Option<PathBuf> {
+    env::var("HOME").ok().map(|home| {
+        PathBuf::from(home).join("Library/Application Support/Reachy Mini Control")
+    })
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn get_app_support_dir() -> Option<PathBuf> {
+    None
+}
+
+/// Check if we're running from inside a .app bundle (signed, replaced on app updates)
+#[cfg(target_os = "macos")]
+pub fn is_app_bundle_path(path: &std::path::Path) -> bool {
+    path.to_string_lossy().contains(".app/Contents")
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn is_app_bundle_path(_path: &std::path::Path) -> bool {
+    false
+}
+
 /// Check if we're running from /usr/lib/ (read-only system directory on Linux)
 #[cfg(target_os = "linux")]
 pub fn is_system_lib_path(path: &std::path::Path) -> bool {
@@ -59,143 +154,96 @@ pub fn is_program_files_path(_path: &std::path::Path) -> bool {
     false
 }
 
-/// Copy a directory recursively
-pub fn copy_dir_recursive(src: &std::path::Path, dst: &std::path::Path) -> Result<(), String> {
-    if !src.exists() {
-        return Err(format!("Source directory does not exist: {:?}", src));
-    }
-    
-    // Create destination directory if it doesn't exist
-    if !dst.exists() {
-        fs::create_dir_all(dst)
-            .map_err(|e| format!("Failed to create directory {:?}: {}", dst, e))?;
-    }
-    
-    let entries = fs::read_dir(src)
-        .map_err(|e| format!("Failed to read directory {:?}: {}", src, e))?;
-    
-    for entry in entries {
-        let entry = entry.map_err(|e| format!("Failed to read entry: {}", e))?;
-        let src_path = entry.path();
-        let dst_path = dst.join(entry.file_name());
-        
-        if src_path.is_dir() {
-            copy_dir_recursive(&src_path, &dst_path)?;
-        } else {
-            fs::copy(&src_path, &dst_path)
-                .map_err(|e| format!("Failed to copy {:?} to {:?}: {}", src_path, dst_path, e))?;
-        }
-    }
-    
-    Ok(())
-}
+/// Copy a bundled folder to the local directory, printing progress every 10%
+fn copy_with_progress_output(src: &std::path::Path, dst: &std::path::Path, label: &str, mode: CopyMode) -> Result<(), String> {
+    use std::sync::atomic::{AtomicU32, Ordering};
 
-/// Setup local venv on Windows by copying from Program Files to %LOCALAPPDATA%
-/// Returns the local directory path if setup was successful or already done
-#[cfg(target_os = "windows")]
-pub fn setup_local_venv_windows(program_files_dir: &std::path::Path) -> Result<PathBuf, String> {
-    let local_dir = get_local_app_data_dir()
-        .ok_or_else(|| "LOCALAPPDATA environment variable not set".to_string())?;
-    
-    // Check if local venv already exists and is valid
-    let local_venv = local_dir.join(".venv");
-    let local_pyvenv_cfg = local_venv.join("pyvenv.cfg");
-    
-    if local_pyvenv_cfg.exists() {
-        // Check if the pyvenv.cfg points to a valid cpython
-        let content = fs::read_to_string(&local_pyvenv_cfg)
-            .map_err(|e| format!("Failed to read local pyvenv.cfg: {}", e))?;
-        
-        // Check if home path exists
-        for line in content.lines() {
-            if line.starts_with("home = ") {
-                let home_path = line.trim_start_matches("home = ");
-                if std::path::Path::new(home_path).exists() {
-                    println!("✅ Local venv already configured at {:?}", local_dir);
-                    return Ok(local_dir);
-                }
-            }
+    let last_reported = AtomicU32::new(0);
+    let last_emitted = AtomicU32::new(0);
+    let stats = copy_dir_with_mode(src, dst, mode, |progress| {
+        let percent = progress.percent();
+        if percent > last_emitted.fetch_max(percent, Ordering::Relaxed) {
+            emit_event(&TrampolineEvent::CopyProgress {
+                label: label.to_string(),
+                files_done: progress.files_done,
+                files_total: progress.files_total,
+                bytes_done: progress.bytes_done,
+                bytes_total: progress.bytes_total,
+                percent,
+            });
         }
-        println!("⚠️  Local venv exists but has invalid paths, reconfiguring...");
-    }
-    
-    println!("📦 Setting up local Python environment...");
-    println!("   Source: {:?}", program_files_dir);
-    println!("   Target: {:?}", local_dir);
-    
-    // Create local directory
-    fs::create_dir_all(&local_dir)
-        .map_err(|e| format!("Failed to create local directory: {}", e))?;
-    
-    // Copy .venv
-    let src_venv = program_files_dir.join(".venv");
-    if src_venv.exists() {
-        println!("   📁 Copying .venv...");
-        // Remove existing local venv if it exists (to ensure clean copy)
-        if local_venv.exists() {
-            fs::remove_dir_all(&local_venv)
-                .map_err(|e| format!("Failed to remove old local venv: {}", e))?;
+        let percent = percent / 10 * 10;
+        if percent > last_reported.fetch_max(percent, Ordering::Relaxed) {
+            println!("      {}% ({}/{} files)", percent, progress.files_done, progress.files_total);
         }
-        copy_dir_recursive(&src_venv, &local_venv)?;
-        println!("   ✅ .venv copied");
-    } else {
-        return Err(format!(".venv not found at {:?}", src_venv));
+    })?;
+    if stats.files_skipped > 0 {
+        println!("   ↩️  {}: resumed, {} files already in place", label, stats.files_skipped);
     }
-    
-    // Copy cpython folder
-    let cpython_folder = find_cpython_folder(program_files_dir)?;
-    let src_cpython = program_files_dir.join(&cpython_folder);
-    let dst_cpython = local_dir.join(&cpython_folder);
-    
-    if src_cpython.exists() {
-        println!("   📁 Copying {}...", cpython_folder);
-        if dst_cpython.exists() {
-            fs::remove_dir_all(&dst_cpython)
-                .map_err(|e| format!("Failed to remove old cpython: {}", e))?;
-        }
-        copy_dir_recursive(&src_cpython, &dst_cpython)?;
-        println!("   ✅ {} copied", cpython_folder);
-    } else {
-        return Err(format!("cpython folder not found at {:?}", src_cpython));
+    if stats.files_linked > 0 {
+        println!("   🔗 {}: {} files hardlinked", label, stats.files_linked);
     }
-    
-    // Copy uv.exe and uvx.exe
-    for exe in &["uv.exe", "uvx.exe"] {
-        let src_exe = program_files_dir.join(exe);
-        let dst_exe = local_dir.join(exe);
-        if src_exe.exists() {
-            fs::copy(&src_exe, &dst_exe)
-                .map_err(|e| format!("Failed to copy {}: {}", exe, e))?;
-            println!("   ✅ {} copied", exe);
+    emit_event(&TrampolineEvent::CopyFinished {
+        label: label.to_string(),
+        files_skipped: stats.files_skipped,
+    });
+    Ok(())
+}
+
+/// Restore files of an existing local venv that went missing (e.g. removed by a disk cleaner)
+fn quick_repair_local_venv(bundle_dir: &std::path::Path, local_dir: &std::path::Path) {
+    match verify_local_venv(bundle_dir, local_dir, VerifyMode::Quick, true) {
+        Ok(report) if !report.is_healthy() => {
+            println!(
+                "   🩹 Restored {} damaged venv files ({} missing, {} corrupted)",
+                report.repaired,
+                report.missing.len(),
+                report.corrupted.len()
+            );
+            emit_event(&TrampolineEvent::VenvRepaired {
+                repaired: report.repaired,
+                missing: report.missing.len(),
+                corrupted: report.corrupted.len(),
+            });
         }
+        Ok(_) => {}
+        Err(e) => eprintln!("⚠️  Failed to verify local venv: {}", e),
     }
-    
-    // Patch pyvenv.cfg with local paths
-    println!("   🔧 Patching pyvenv.cfg...");
-    patching_pyvenv_cfg(&local_dir, &cpython_folder)?;
-    println!("   ✅ pyvenv.cfg patched");
-    
-    println!("✅ Local Python environment ready at {:?}", local_dir);
-    Ok(local_dir)
 }
 
+/// uv binaries shipped next to the bundled venv
+#[cfg(target_os = "windows")]
+const UV_BINARIES: &[&str] = &["uv.exe", "uvx.exe"];
 #[cfg(not(target_os = "windows"))]
-pub fn setup_local_venv_windows(_program_files_dir: &std::path::Path) -> Result<PathBuf, String> {
-    Err("setup_local_venv_windows is only available on Windows".to_string())
-}
+const UV_BINARIES: &[&str] = &["uv", "uvx"];
 
-/// Setup local venv on Linux by copying from /usr/lib/ to ~/.local/share/
+/// Setup a local venv in `local_dir` by copying the read-only bundled one from `bundle_dir`
+/// (the setup_local_venv_* functions only pick both directories for their platform)
+/// With CopyMode::Hardlink, files are linked rather than copied (same volume only),
+/// so the local venv costs almost no extra disk space
 /// Returns the local directory path if setup was successful or already done
-#[cfg(target_os = "linux")]
-pub fn setup_local_venv_linux(system_lib_dir: &std::path::Path) -> Result<PathBuf, String> {
-    let local_dir = get_xdg_data_home()
-        .ok_or_else(|| "HOME environment variable not set".to_string())?;
+pub fn setup_local_venv(bundle_dir: &std::path::Path, local_dir: &std::path::Path, mode: CopyMode) -> Result<PathBuf, SetupError> {
+    let local_dir = local_dir.to_path_buf();
     
     // Check if local venv already exists and is valid
     let local_venv = local_dir.join(".venv");
     let local_pyvenv_cfg = local_venv.join("pyvenv.cfg");
     
-    if local_pyvenv_cfg.exists() {
+    let setup_marker = local_dir.join(SETUP_COMPLETE_MARKER);
+    
+    // App updated since the copy: clean up, then resync below (only changed files are copied)
+    if setup_marker.exists() && is_bundle_updated(bundle_dir, &local_dir) {
+        println!("🔄 New app version detected, re-syncing local venv...");
+        let report = prepare_resync(bundle_dir, &local_dir)?;
+        println!(
+            "   🧹 {} stale files removed, {} packages replaced by the bundled version",
+            report.stale_files_removed,
+            report.replaced_packages.len()
+        );
+        let _ = fs::remove_file(&setup_marker);
+    }
+    
+    if local_pyvenv_cfg.exists() && setup_marker.exists() {
         // Check if the pyvenv.cfg points to a valid cpython
         let content = fs::read_to_string(&local_pyvenv_cfg)
             .map_err(|e| format!("Failed to read local pyvenv.cfg: {}", e))?;
@@ -206,6 +254,10 @@ pub fn setup_local_venv_linux(system_lib_dir: &std::path::Path) -> Result<PathBu
                 let home_path = line.trim_start_matches("home = ");
                 if std::path::Path::new(home_path).exists() {
                     println!("✅ Local venv already configured at {:?}", local_dir);
+                    quick_repair_local_venv(bundle_dir, &local_dir);
+                    emit_event(&TrampolineEvent::SetupComplete {
+                        local_dir: local_dir.display().to_string(),
+                    });
                     return Ok(local_dir);
                 }
             }
@@ -214,48 +266,55 @@ pub fn setup_local_venv_linux(system_lib_dir: &std::path::Path) -> Result<PathBu
     }
     
     println!("📦 Setting up local Python environment...");
-    println!("   Source: {:?}", system_lib_dir);
+    println!("   Source: {:?}", bundle_dir);
     println!("   Target: {:?}", local_dir);
+    emit_event(&TrampolineEvent::SetupStarted {
+        source: bundle_dir.display().to_string(),
+        target: local_dir.display().to_string(),
+    });
     
     // Create local directory
     fs::create_dir_all(&local_dir)
         .map_err(|e| format!("Failed to create local directory: {}", e))?;
+    let _ = fs::remove_file(&setup_marker);
+    
+    // Fail early rather than halfway through the copy with a bare io error
+    let cpython_folder = find_cpython_folder(bundle_dir)?;
+    // (hardlinks take no space, but they fall back to a copy across volumes)
+    let src_venv = bundle_dir.join(".venv");
+    let linked = mode == CopyMode::Hardlink && can_hardlink(&src_venv.join("pyvenv.cfg"), &local_dir);
+    if mode == CopyMode::Hardlink && !linked {
+        println!("   ⚠️  Hardlinks not supported to {:?}, copying instead", local_dir);
+    }
+    if !linked {
+        check_disk_space(bundle_dir, &local_dir, &[".venv", &cpython_folder])?;
+    }
     
     // Copy .venv
-    let src_venv = system_lib_dir.join(".venv");
     if src_venv.exists() {
         println!("   📁 Copying .venv...");
-        // Remove existing local venv if it exists (to ensure clean copy)
-        if local_venv.exists() {
-            fs::remove_dir_all(&local_venv)
-                .map_err(|e| format!("Failed to remove old local venv: {}", e))?;
-        }
-        copy_dir_recursive(&src_venv, &local_venv)?;
+        // Files left by an interrupted copy are kept if unchanged (resume)
+        copy_with_progress_output(&src_venv, &local_venv, ".venv", mode)?;
         println!("   ✅ .venv copied");
     } else {
-        return Err(format!(".venv not found at {:?}", src_venv));
+        return Err(format!(".venv not found at {:?}", src_venv).into());
     }
     
     // Copy cpython folder
-    let cpython_folder = find_cpython_folder(system_lib_dir)?;
-    let src_cpython = system_lib_dir.join(&cpython_folder);
+    let src_cpython = bundle_dir.join(&cpython_folder);
     let dst_cpython = local_dir.join(&cpython_folder);
     
     if src_cpython.exists() {
         println!("   📁 Copying {}...", cpython_folder);
-        if dst_cpython.exists() {
-            fs::remove_dir_all(&dst_cpython)
-                .map_err(|e| format!("Failed to remove old cpython: {}", e))?;
-        }
-        copy_dir_recursive(&src_cpython, &dst_cpython)?;
+        copy_with_progress_output(&src_cpython, &dst_cpython, &cpython_folder, mode)?;
         println!("   ✅ {} copied", cpython_folder);
     } else {
-        return Err(format!("cpython folder not found at {:?}", src_cpython));
+        return Err(format!("cpython folder not found at {:?}", src_cpython).into());
     }
     
     // Copy uv and uvx binaries
-    for bin in &["uv", "uvx"] {
-        let src_bin = system_lib_dir.join(bin);
+    for bin in UV_BINARIES {
+        let src_bin = bundle_dir.join(bin);
         let dst_bin = local_dir.join(bin);
         if src_bin.exists() {
             fs::copy(&src_bin, &dst_bin)
@@ -275,18 +334,69 @@ pub fn setup_local_venv_linux(system_lib_dir: &std::path::Path) -> Result<PathBu
         }
     }
     
+    // pyvenv.cfg is patched in place: it must not share its content with the bundle
+    if mode == CopyMode::Hardlink {
+        copy::copy_single_file(&src_venv.join("pyvenv.cfg"), &local_pyvenv_cfg)?;
+    }
+    
     // Patch pyvenv.cfg with local paths
     println!("   🔧 Patching pyvenv.cfg...");
     patching_pyvenv_cfg(&local_dir, &cpython_folder)?;
     println!("   ✅ pyvenv.cfg patched");
     
+    // Manifest of the bundled files, used to detect files removed by disk cleaners later
+    println!("   🧾 Writing venv manifest...");
+    write_venv_manifest(bundle_dir, &local_dir)?;
+    write_bundle_version(bundle_dir, &local_dir)?;
+    
+    fs::write(&setup_marker, "")
+        .map_err(|e| format!("Failed to write setup marker: {}", e))?;
+    
     println!("✅ Local Python environment ready at {:?}", local_dir);
+    emit_event(&TrampolineEvent::SetupComplete {
+        local_dir: local_dir.display().to_string(),
+    });
     Ok(local_dir)
 }
 
+/// Setup local venv on Windows by copying from Program Files to %LOCALAPPDATA%
+#[cfg(target_os = "windows")]
+pub fn setup_local_venv_windows(program_files_dir: &std::path::Path, mode: CopyMode) -> Result<PathBuf, SetupError> {
+    let local_dir = get_local_app_data_dir()
+        .ok_or_else(|| "LOCALAPPDATA environment variable not set".to_string())?;
+    setup_local_venv(program_files_dir, &local_dir, mode)
+}
+
+#[cfg(not(target_os = "windows"))]
+pub fn setup_local_venv_windows(_program_files_dir: &std::path::Path, _mode: CopyMode) -> Result<PathBuf, SetupError> {
+    Err(SetupError::Failed("setup_local_venv_windows is only available on Windows".to_string()))
+}
+
+/// Setup local venv on Linux by copying from /usr/lib/ to ~/.local/share/
+#[cfg(target_os = "linux")]
+pub fn setup_local_venv_linux(system_lib_dir: &std::path::Path) -> Result<PathBuf, SetupError> {
+    let local_dir = get_xdg_data_home()
+        .ok_or_else(|| "HOME environment variable not set".to_string())?;
+    setup_local_venv(system_lib_dir, &local_dir, CopyMode::Copy)
+}
+
 #[cfg(not(target_os = "linux"))]
-pub fn setup_local_venv_linux(_system_lib_dir: &std::path::Path) -> Result<PathBuf, String> {
-    Err("setup_local_venv_linux is only available on Linux".to_string())
+pub fn setup_local_venv_linux(_system_lib_dir: &std::path::Path) -> Result<PathBuf, SetupError> {
+    Err(SetupError::Failed("setup_local_venv_linux is only available on Linux".to_string()))
+}
+
+/// Setup local venv on macOS by copying from the .app bundle to ~/Library/Application Support/
+/// Installing packages inside the bundle breaks its code signature and is lost on app updates
+#[cfg(target_os = "macos")]
+pub fn setup_local_venv_macos(resources_dir: &std::path::Path) -> Result<PathBuf, SetupError> {
+    let local_dir = get_app_support_dir()
+        .ok_or_else(|| "HOME environment variable not set".to_string())?;
+    setup_local_venv(resources_dir, &local_dir, CopyMode::Copy)
+}
+
+#[cfg(not(target_os = "macos"))]
+pub fn setup_local_venv_macos(_resources_dir: &std::path::Path) -> Result<PathBuf, SetupError> {
+    Err(SetupError::Failed("setup_local_venv_macos is only available on macOS".to_string()))
 }
 
 /// Gets the folder containing the current executable
@@ -342,8 +452,7 @@ pub fn run_command(cmd: &str) -> Result<std::process::ExitStatus, std::io::Error
     
     // Check exit code and return error if non-zero
     if !status.success() {
-        return Err(std::io::Error::new(
-            std::io::ErrorKind::Other,
+        return Err(std::io::Error::other(
             format!("Command failed with exit code: {:?}", status.code())
         ));
     }
@@ -418,7 +527,12 @@ pub fn patching_pyvenv_cfg(uv_folder: &std::path::Path, cpython_folder: &str) ->
 
     // Try to write the patched file
     match std::fs::write(&pyvenv_cfg_path, new_content) {
-        Ok(_) => Ok(()),
+        Ok(_) => {
+            emit_event(&TrampolineEvent::PyvenvPatched {
+                path: pyvenv_cfg_path.display().to_string(),
+            });
+            Ok(())
+        }
         Err(e) => {
             let error_msg = format!("Unable to write patched pyvenv.cfg: {}", e);
     
@@ -435,3 +549,41 @@ pub fn patching_pyvenv_cfg(uv_folder: &std::path::Path, cpython_folder: &str) ->
     }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sets_up_then_resyncs_a_local_venv() {
+        let root = test_dir("setup");
+        let (bundle, local) = (root.join("bundle"), root.join("local"));
+        write_test_file(&bundle.join(".venv/pyvenv.cfg"), "home = /usr/lib/reachy-mini/cpython/bin\n");
+        write_test_file(&bundle.join(".venv/lib/python3.12/site-packages/reachy_mini/old.py"), "old");
+        write_test_file(&bundle.join("cpython-3.12-linux/bin/python3"), "python");
+        write_test_file(&bundle.join(UV_BINARIES[0]), "uv");
+
+        setup_local_venv(&bundle, &local, CopyMode::Copy).unwrap();
+        let pyvenv_cfg = fs::read_to_string(local.join(".venv/pyvenv.cfg")).unwrap();
+        let home = local.join("cpython-3.12-linux");
+        #[cfg(not(target_os = "windows"))]
+        let home = home.join("bin");
+        assert_eq!(pyvenv_cfg, format!("home = {}", home.display()));
+        assert!(local.join(UV_BINARIES[0]).exists());
+        assert!(local.join(SETUP_COMPLETE_MARKER).exists());
+        // The bundled pyvenv.cfg is untouched
+        assert!(fs::read_to_string(bundle.join(".venv/pyvenv.cfg")).unwrap().contains("/usr/lib/reachy-mini"));
+
+        // App update: another Python build, a dropped file
+        fs::rename(bundle.join("cpython-3.12-linux"), bundle.join("cpython-3.13-linux")).unwrap();
+        fs::remove_file(bundle.join(".venv/lib/python3.12/site-packages/reachy_mini/old.py")).unwrap();
+
+        setup_local_venv(&bundle, &local, CopyMode::Copy).unwrap();
+        assert!(!local.join(".venv/lib/python3.12/site-packages/reachy_mini/old.py").exists());
+        assert!(!local.join("cpython-3.12-linux").exists());
+        assert!(fs::read_to_string(local.join(".venv/pyvenv.cfg")).unwrap().contains("cpython-3.13-linux"));
+        assert!(!is_bundle_updated(&bundle, &local));
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
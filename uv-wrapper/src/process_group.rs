@@ -0,0 +1,53 @@
+/// Make this process the root of a tree the app can terminate as a whole, including
+/// grandchildren (uvicorn workers, app subprocesses) that re-parent when their parent dies.
+///
+/// - Unix: become a process group leader, so `kill -- -<pid>` reaches every descendant.
+///   Skipped when attached to a terminal, where leaving the foreground group would
+///   break Ctrl-C.
+/// - Windows: join a Job Object killed on close: when the trampoline dies, the whole
+///   tree goes with it.
+#[cfg(unix)]
+pub fn isolate_process_tree() -> Result<(), String> {
+    if unsafe { libc::isatty(libc::STDIN_FILENO) } == 1 {
+        return Ok(());
+    }
+    if unsafe { libc::setpgid(0, 0) } != 0 {
+        return Err(format!("setpgid failed: {}", std::io::Error::last_os_error()));
+    }
+    Ok(())
+}
+
+#[cfg(windows)]
+pub fn isolate_process_tree() -> Result<(), String> {
+    use windows_sys::Win32::System::JobObjects::{
+        AssignProcessToJobObject, CreateJobObjectW, JobObjectExtendedLimitInformation,
+        SetInformationJobObject, JOBOBJECT_EXTENDED_LIMIT_INFORMATION, JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE,
+    };
+    use windows_sys::Win32::System::Threading::GetCurrentProcess;
+
+    unsafe {
+        // The handle is intentionally never closed: the job lives as long as we do
+        let job = CreateJobObjectW(std::ptr::null(), std::ptr::null());
+        if job.is_null() {
+            return Err(format!("CreateJobObject failed: {}", std::io::Error::last_os_error()));
+        }
+
+        let mut info: JOBOBJECT_EXTENDED_LIMIT_INFORMATION = std::mem::zeroed();
+        info.BasicLimitInformation.LimitFlags = JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE;
+        if SetInformationJobObject(
+            job,
+            JobObjectExtendedLimitInformation,
+            &info as *const _ as *const core::ffi::c_void,
+            std::mem::size_of::<JOBOBJECT_EXTENDED_LIMIT_INFORMATION>() as u32,
+        ) == 0
+        {
+            return Err(format!("SetInformationJobObject failed: {}", std::io::Error::last_os_error()));
+        }
+
+        // Children created from now on inherit the job
+        if AssignProcessToJobObject(job, GetCurrentProcess()) == 0 {
+            return Err(format!("AssignProcessToJobObject failed: {}", std::io::Error::last_os_error()));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,245 @@
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::verify::{list_files, read_manifest, MANIFEST_FILE};
+use crate::{emit_event, find_cpython_folder, TrampolineEvent};
+
+/// Fingerprint of the bundle the local venv was copied from
+pub const BUNDLE_VERSION_FILE: &str = ".bundle-version";
+
+/// What prepare_resync changed
+#[derive(Debug, Clone, Default)]
+pub struct ResyncReport {
+    /// Files of the previous bundle that the new one no longer ships
+    pub stale_files_removed: usize,
+    /// Locally installed versions of bundled packages, replaced by the bundle's
+    pub replaced_packages: Vec<String>,
+}
+
+/// site-packages folders of a venv (Lib/site-packages on Windows, lib/python3.X/site-packages elsewhere)
+pub(crate) fn site_packages_dirs(venv: &Path) -> Vec<PathBuf> {
+    let mut dirs = vec![venv.join("Lib").join("site-packages")];
+    if let Ok(entries) = fs::read_dir(venv.join("lib")) {
+        dirs.extend(entries.flatten().map(|entry| entry.path().join("site-packages")));
+    }
+    dirs.into_iter().filter(|dir| dir.is_dir()).collect()
+}
+
+/// `*.dist-info` folder names, which carry the name and version of every installed package
+pub(crate) fn dist_info_names(site_packages: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(site_packages) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .filter(|name| name.ends_with(".dist-info"))
+        .collect()
+}
+
+/// Normalized project name of a dist-info folder ("Reachy_Mini-1.2.0.dist-info" -> "reachy_mini")
+fn dist_info_project(name: &str) -> String {
+    name.split('-').next().unwrap_or(name).to_lowercase().replace(['-', '.'], "_")
+}
+
+/// Cheap fingerprint of a bundle: the versions of its packages and its Python build.
+/// Changes whenever an app update ships a different daemon or dependency.
+pub fn bundle_fingerprint(bundle_dir: &Path) -> Result<String, String> {
+    let mut names: Vec<String> = site_packages_dirs(&bundle_dir.join(".venv"))
+        .iter()
+        .flat_map(|dir| dist_info_names(dir))
+        .collect();
+    names.sort();
+    names.push(find_cpython_folder(bundle_dir)?);
+
+    let mut hasher = Sha256::new();
+    for name in &names {
+        hasher.update(name.as_bytes());
+        hasher.update(b"\n");
+    }
+    Ok(hasher.finalize().iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+/// Record the fingerprint of the bundle the local venv now matches
+pub fn write_bundle_version(bundle_dir: &Path, local_dir: &Path) -> Result<(), String> {
+    fs::write(local_dir.join(BUNDLE_VERSION_FILE), bundle_fingerprint(bundle_dir)?)
+        .map_err(|e| format!("Failed to write bundle version: {}", e))
+}
+
+/// The bundle differs from the one the local venv was copied from
+///
+/// Local venvs set up before the version was recorded are assumed in sync (the user may
+/// have updated the daemon since), their version is recorded for the next app update.
+pub fn is_bundle_updated(bundle_dir: &Path, local_dir: &Path) -> bool {
+    let Ok(current) = bundle_fingerprint(bundle_dir) else {
+        // Unreadable bundle: nothing to sync from
+        return false;
+    };
+    match fs::read_to_string(local_dir.join(BUNDLE_VERSION_FILE)) {
+        Ok(recorded) => recorded.trim() != current,
+        Err(_) => {
+            let _ = fs::write(local_dir.join(BUNDLE_VERSION_FILE), current);
+            false
+        }
+    }
+}
+
+/// Remove a file and the folders it leaves empty, up to `root`
+fn remove_file_and_empty_parents(root: &Path, path: &Path) -> bool {
+    if fs::remove_file(path).is_err() {
+        return false;
+    }
+    let mut parent = path.parent();
+    while let Some(dir) = parent {
+        if dir == root || fs::remove_dir(dir).is_err() {
+            break;
+        }
+        parent = dir.parent();
+    }
+    true
+}
+
+/// Uninstall a local package by its RECORD, keeping files the new bundle ships
+fn remove_dist_info(local_dir: &Path, site_packages: &Path, dist_info: &str, bundle_files: &HashSet<String>) {
+    let dist_info_dir = site_packages.join(dist_info);
+    if let Ok(record) = fs::read_to_string(dist_info_dir.join("RECORD")) {
+        for line in record.lines() {
+            let Some(relative) = line.split(',').next().filter(|p| !p.is_empty()) else {
+                continue;
+            };
+            let path = site_packages.join(relative);
+            let in_bundle = path
+                .strip_prefix(local_dir)
+                .ok()
+                .map(|p| p.to_string_lossy().replace('\\', "/"))
+                .is_some_and(|p| bundle_files.contains(&p));
+            if !in_bundle {
+                remove_file_and_empty_parents(local_dir, &path);
+            }
+        }
+    }
+    let _ = fs::remove_dir_all(&dist_info_dir);
+}
+
+/// Bring a local venv up to date with a newer bundle without recopying it all.
+///
+/// Packages the user installed on top of the bundle are kept. For packages the bundle
+/// ships, the bundle's version wins: a locally installed version is uninstalled first so
+/// two versions never coexist. Files are then copied by the caller (unchanged ones are
+/// skipped by the resumable copy).
+pub fn prepare_resync(bundle_dir: &Path, local_dir: &Path) -> Result<ResyncReport, String> {
+    let mut report = ResyncReport::default();
+    let cpython_folder = find_cpython_folder(bundle_dir)?;
+
+    let mut bundle_list = Vec::new();
+    for folder in [".venv", cpython_folder.as_str()] {
+        list_files(bundle_dir, folder, &mut bundle_list)?;
+    }
+    let bundle_files: HashSet<String> = bundle_list.into_iter().collect();
+
+    // 1. Local versions of packages the bundle now ships in another version
+    let bundle_dist_infos: Vec<String> = site_packages_dirs(&bundle_dir.join(".venv"))
+        .iter()
+        .flat_map(|dir| dist_info_names(dir))
+        .collect();
+    let bundle_projects: HashSet<String> = bundle_dist_infos.iter().map(|name| dist_info_project(name)).collect();
+    for site_packages in site_packages_dirs(&local_dir.join(".venv")) {
+        for name in dist_info_names(&site_packages) {
+            if bundle_projects.contains(&dist_info_project(&name)) && !bundle_dist_infos.contains(&name) {
+                remove_dist_info(local_dir, &site_packages, &name, &bundle_files);
+                report.replaced_packages.push(name);
+            }
+        }
+    }
+
+    // 2. Files of the previous bundle that the new one dropped (the manifest lists them)
+    if let Ok(previous) = read_manifest(local_dir) {
+        for entry in previous.iter().filter(|entry| !bundle_files.contains(&entry.path)) {
+            if remove_file_and_empty_parents(local_dir, &local_dir.join(&entry.path)) {
+                report.stale_files_removed += 1;
+            }
+        }
+    }
+
+    // 3. A previous Python build
+    if let Ok(entries) = fs::read_dir(local_dir) {
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if name.starts_with("cpython-") && name != cpython_folder {
+                let _ = fs::remove_dir_all(entry.path());
+            }
+        }
+    }
+
+    // The manifest now describes a mix: rewritten once the copy is done
+    let _ = fs::remove_file(local_dir.join(MANIFEST_FILE));
+
+    emit_event(&TrampolineEvent::ResyncPrepared {
+        stale_files_removed: report.stale_files_removed,
+        replaced_packages: report.replaced_packages.clone(),
+    });
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{installed_test_venv, write_test_file, TEST_SITE_PACKAGES as SITE_PACKAGES};
+
+    #[test]
+    fn removes_files_the_new_bundle_dropped() {
+        let (root, bundle, local) = installed_test_venv("resync-stale", &[]);
+        let local_site_packages = local.join(SITE_PACKAGES);
+        // Installed by the user on top of the bundle: not in the manifest
+        write_test_file(&local_site_packages.join("user_app/main.py"), "app");
+
+        fs::remove_file(bundle.join(SITE_PACKAGES).join("reachy_mini/legacy.py")).unwrap();
+
+        let report = prepare_resync(&bundle, &local).unwrap();
+        assert_eq!(report.stale_files_removed, 1);
+        assert!(report.replaced_packages.is_empty());
+        assert!(!local_site_packages.join("reachy_mini/legacy.py").exists());
+        assert!(local_site_packages.join("reachy_mini/__init__.py").exists());
+        assert!(local_site_packages.join("user_app/main.py").exists());
+        // Rewritten by the caller once the copy is done
+        assert!(!local.join(MANIFEST_FILE).exists());
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn replaces_locally_upgraded_bundled_packages() {
+        let (root, bundle, local) = installed_test_venv("resync-replaced", &[]);
+        let local_site_packages = local.join(SITE_PACKAGES);
+        // The user upgraded reachy_mini in place: 1.0 replaced by 1.1
+        fs::remove_dir_all(local_site_packages.join("reachy_mini-1.0.dist-info")).unwrap();
+        write_test_file(&local_site_packages.join("reachy_mini/extra.py"), "1.1");
+        write_test_file(
+            &local_site_packages.join("reachy_mini-1.1.dist-info/RECORD"),
+            "reachy_mini/__init__.py,,\nreachy_mini/extra.py,,\nreachy_mini-1.1.dist-info/RECORD,,\n",
+        );
+        write_test_file(&local_site_packages.join("user_app-0.1.dist-info/RECORD"), "");
+        // New bundle: reachy_mini 2.0 and another Python build
+        let bundle_site_packages = bundle.join(SITE_PACKAGES);
+        fs::rename(
+            bundle_site_packages.join("reachy_mini-1.0.dist-info"),
+            bundle_site_packages.join("reachy_mini-2.0.dist-info"),
+        )
+        .unwrap();
+        fs::rename(bundle.join("cpython-3.12-linux"), bundle.join("cpython-3.13-linux")).unwrap();
+        assert!(is_bundle_updated(&bundle, &local));
+
+        let report = prepare_resync(&bundle, &local).unwrap();
+        assert_eq!(report.replaced_packages, vec!["reachy_mini-1.1.dist-info".to_string()]);
+        assert!(!local_site_packages.join("reachy_mini-1.1.dist-info").exists());
+        assert!(!local_site_packages.join("reachy_mini/extra.py").exists());
+        // Shipped by the bundle again: kept, the copy overwrites it if needed
+        assert!(local_site_packages.join("reachy_mini/__init__.py").exists());
+        assert!(local_site_packages.join("user_app-0.1.dist-info/RECORD").exists());
+        assert!(!local.join("cpython-3.12-linux").exists());
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,83 @@
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// How to launch a console script installed in the venv
+#[derive(Debug, Clone)]
+pub struct ScriptLaunch {
+    pub program: PathBuf,
+    /// Arguments before the user's own (the script path when run through python)
+    pub args: Vec<PathBuf>,
+}
+
+#[cfg(target_os = "windows")]
+fn venv_bin_dir(uv_folder: &Path) -> PathBuf {
+    uv_folder.join(".venv").join("Scripts")
+}
+
+#[cfg(not(target_os = "windows"))]
+fn venv_bin_dir(uv_folder: &Path) -> PathBuf {
+    uv_folder.join(".venv").join("bin")
+}
+
+#[cfg(target_os = "windows")]
+fn venv_python(uv_folder: &Path) -> PathBuf {
+    venv_bin_dir(uv_folder).join("python.exe")
+}
+
+#[cfg(not(target_os = "windows"))]
+fn venv_python(uv_folder: &Path) -> PathBuf {
+    venv_bin_dir(uv_folder).join("python3")
+}
+
+/// Whether the file is a Python entry point: a `#!...python` shebang, or the
+/// `#!/bin/sh` + `'''exec'` polyglot pip/uv write when the interpreter path has spaces
+fn is_python_script(path: &Path) -> bool {
+    let mut head = [0u8; 512];
+    let Ok(n) = fs::File::open(path).and_then(|mut f| f.read(&mut head)) else {
+        return false;
+    };
+    let head = String::from_utf8_lossy(&head[..n]);
+    let mut lines = head.lines();
+    match lines.next() {
+        Some(shebang) if shebang.starts_with("#!") => {
+            shebang.contains("python") || lines.next().is_some_and(|line| line.starts_with("'''exec'"))
+        }
+        _ => false,
+    }
+}
+
+/// Resolve a console script of the venv (e.g. `reachy-mini-calibrate`) to a command line.
+///
+/// Python scripts run through the venv interpreter instead of their shebang: the shebang
+/// points to where the venv was built (stale once copied locally) and breaks on paths
+/// with spaces. On Windows the launcher .exe embeds a zip python can run directly.
+pub fn resolve_console_script(uv_folder: &Path, name: &str) -> Result<ScriptLaunch, String> {
+    if name.is_empty() || name.contains(['/', '\\']) || name.starts_with('.') {
+        return Err(format!("Invalid script name: {:?}", name));
+    }
+    let bin_dir = venv_bin_dir(uv_folder);
+    let python = venv_python(uv_folder);
+
+    #[cfg(target_os = "windows")]
+    {
+        let script_py = bin_dir.join(format!("{}-script.py", name));
+        let launcher = bin_dir.join(format!("{}.exe", name));
+        for script in [script_py, launcher] {
+            if script.is_file() {
+                return Ok(ScriptLaunch { program: python, args: vec![script] });
+            }
+        }
+    }
+
+    let script = bin_dir.join(name);
+    if !script.is_file() {
+        return Err(format!("Script '{}' not found in {:?}", name, bin_dir));
+    }
+    if is_python_script(&script) {
+        Ok(ScriptLaunch { program: python, args: vec![script] })
+    } else {
+        // Native executable or non-Python script: its own format knows how to run
+        Ok(ScriptLaunch { program: script, args: Vec::new() })
+    }
+}
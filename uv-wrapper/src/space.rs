@@ -0,0 +1,60 @@
+use std::path::Path;
+
+use crate::cleanup::disk_usage;
+use crate::error::SetupError;
+
+/// Headroom kept on top of the copy so pip can still install updates afterwards
+const SPACE_MARGIN: u64 = 200 * 1024 * 1024;
+
+/// Free space available to the current user on the volume holding `path`
+/// (the closest existing ancestor when `path` doesn't exist yet)
+pub fn available_space(path: &Path) -> Result<u64, String> {
+    let existing = path
+        .ancestors()
+        .find(|p| p.exists())
+        .ok_or_else(|| format!("No existing parent for {:?}", path))?;
+    free_space(existing)
+}
+
+#[cfg(unix)]
+fn free_space(path: &Path) -> Result<u64, String> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let c_path = CString::new(path.as_os_str().as_bytes())
+        .map_err(|e| format!("Invalid path {:?}: {}", path, e))?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) } != 0 {
+        return Err(format!("Failed to read free space of {:?}: {}", path, std::io::Error::last_os_error()));
+    }
+    #[allow(clippy::unnecessary_cast)] // field types differ between platforms
+    Ok(stat.f_bavail as u64 * stat.f_frsize as u64)
+}
+
+#[cfg(windows)]
+fn free_space(path: &Path) -> Result<u64, String> {
+    use std::os::windows::ffi::OsStrExt;
+    use windows_sys::Win32::Storage::FileSystem::GetDiskFreeSpaceExW;
+
+    let wide: Vec<u16> = path.as_os_str().encode_wide().chain(std::iter::once(0)).collect();
+    let mut available = 0u64;
+    let ok = unsafe { GetDiskFreeSpaceExW(wide.as_ptr(), &mut available, std::ptr::null_mut(), std::ptr::null_mut()) };
+    if ok == 0 {
+        return Err(format!("Failed to read free space of {:?}: {}", path, std::io::Error::last_os_error()));
+    }
+    Ok(available)
+}
+
+/// Check the destination volume can hold the copy of `folders` from `bundle_dir` to
+/// `local_dir`. Files left by an interrupted copy are already paid for.
+pub fn check_disk_space(bundle_dir: &Path, local_dir: &Path, folders: &[&str]) -> Result<(), SetupError> {
+    let bundle_size: u64 = folders.iter().map(|f| disk_usage(&bundle_dir.join(f))).sum();
+    let already_copied: u64 = folders.iter().map(|f| disk_usage(&local_dir.join(f))).sum();
+    let required = bundle_size.saturating_sub(already_copied) + SPACE_MARGIN;
+    let available = available_space(local_dir)?;
+
+    if available < required {
+        return Err(SetupError::InsufficientSpace { required, available });
+    }
+    Ok(())
+}
@@ -0,0 +1,359 @@
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use crate::copy::copy_single_file;
+use crate::resync::{dist_info_names, site_packages_dirs};
+use crate::{find_cpython_folder, patching_pyvenv_cfg};
+
+/// Manifest of the bundled files copied to the local directory: "<sha256> <size> <relative path>"
+pub const MANIFEST_FILE: &str = ".venv-manifest";
+
+/// Patched after the copy, so never identical to the bundled original
+const PATCHED_FILES: &[&str] = &[".venv/pyvenv.cfg"];
+
+const MAX_HASH_THREADS: usize = 8;
+
+/// How thoroughly verify_local_venv checks each file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VerifyMode {
+    /// Presence and size only (cheap enough for every launch)
+    Quick,
+    /// Presence, size and SHA-256
+    Full,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct VerifyReport {
+    pub checked: usize,
+    /// Relative paths absent from the local directory
+    pub missing: Vec<String>,
+    /// Relative paths whose size or hash differs from the manifest
+    pub corrupted: Vec<String>,
+    /// Files of bundled packages that pip has since upgraded or uninstalled: differing
+    /// from the manifest is expected, they are reported but never restored
+    pub changed_by_pip: Vec<String>,
+    pub repaired: usize,
+    /// pyvenv.cfg was missing or pointed to a missing interpreter
+    pub pyvenv_cfg_repaired: bool,
+}
+
+impl VerifyReport {
+    pub fn is_healthy(&self) -> bool {
+        self.missing.is_empty() && self.corrupted.is_empty()
+    }
+}
+
+pub(crate) struct ManifestEntry {
+    sha256: String,
+    size: u64,
+    pub(crate) path: String,
+}
+
+fn sha256_file(path: &Path) -> Result<String, String> {
+    let mut file = fs::File::open(path)
+        .map_err(|e| format!("Failed to open {:?}: {}", path, e))?;
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0u8; 64 * 1024];
+    loop {
+        let n = file.read(&mut buf)
+            .map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+    Ok(hasher.finalize().iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+/// Relative paths (with '/') of every regular file under `root/folder`
+pub(crate) fn list_files(root: &Path, folder: &str, files: &mut Vec<String>) -> Result<(), String> {
+    let dir = root.join(folder);
+    let entries = fs::read_dir(&dir)
+        .map_err(|e| format!("Failed to read directory {:?}: {}", dir, e))?;
+
+    for entry in entries {
+        let entry = entry.map_err(|e| format!("Failed to read entry: {}", e))?;
+        let relative = format!("{}/{}", folder, entry.file_name().to_string_lossy());
+        let file_type = entry.file_type()
+            .map_err(|e| format!("Failed to read type of {:?}: {}", entry.path(), e))?;
+        if file_type.is_dir() {
+            list_files(root, &relative, files)?;
+        } else if file_type.is_file() && !PATCHED_FILES.contains(&relative.as_str()) {
+            files.push(relative);
+        }
+    }
+    Ok(())
+}
+
+/// Run `f` on every item from a few threads; returns the first error
+fn parallel_for_each<T: Sync>(items: &[T], f: impl Fn(&T) -> Result<(), String> + Sync) -> Result<(), String> {
+    let next = AtomicUsize::new(0);
+    let first_error = Mutex::new(None::<String>);
+    let threads = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(4)
+        .clamp(1, MAX_HASH_THREADS);
+
+    std::thread::scope(|scope| {
+        for _ in 0..threads {
+            scope.spawn(|| {
+                while let Some(item) = items.get(next.fetch_add(1, Ordering::Relaxed)) {
+                    if first_error.lock().unwrap().is_some() {
+                        return;
+                    }
+                    if let Err(e) = f(item) {
+                        first_error.lock().unwrap().get_or_insert(e);
+                        return;
+                    }
+                }
+            });
+        }
+    });
+
+    match first_error.into_inner().unwrap() {
+        Some(e) => Err(e),
+        None => Ok(()),
+    }
+}
+
+/// Hash the bundled .venv and cpython folder and write the manifest to `local_dir`
+pub fn write_venv_manifest(bundle_dir: &Path, local_dir: &Path) -> Result<usize, String> {
+    let cpython_folder = find_cpython_folder(bundle_dir)?;
+    let mut files = Vec::new();
+    for folder in [".venv", cpython_folder.as_str()] {
+        list_files(bundle_dir, folder, &mut files)?;
+    }
+
+    let entries = Mutex::new(Vec::with_capacity(files.len()));
+    parallel_for_each(&files, |relative| {
+        let path = bundle_dir.join(relative);
+        let size = fs::metadata(&path)
+            .map_err(|e| format!("Failed to read metadata of {:?}: {}", path, e))?
+            .len();
+        let sha256 = sha256_file(&path)?;
+        entries.lock().unwrap().push(format!("{} {} {}", sha256, size, relative));
+        Ok(())
+    })?;
+
+    let mut entries = entries.into_inner().unwrap();
+    entries.sort_by(|a, b| a.split(' ').nth(2).cmp(&b.split(' ').nth(2)));
+    fs::write(local_dir.join(MANIFEST_FILE), entries.join("\n") + "\n")
+        .map_err(|e| format!("Failed to write venv manifest: {}", e))?;
+    Ok(entries.len())
+}
+
+pub(crate) fn read_manifest(local_dir: &Path) -> Result<Vec<ManifestEntry>, String> {
+    let path = local_dir.join(MANIFEST_FILE);
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read venv manifest {:?}: {}", path, e))?;
+
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let mut parts = line.splitn(3, ' ');
+            match (parts.next(), parts.next().and_then(|s| s.parse().ok()), parts.next()) {
+                (Some(sha256), Some(size), Some(path)) => Ok(ManifestEntry {
+                    sha256: sha256.to_string(),
+                    size,
+                    path: path.to_string(),
+                }),
+                _ => Err(format!("Invalid venv manifest line: {}", line)),
+            }
+        })
+        .collect()
+}
+
+/// Normalize a path relative to `local_dir` ("a/b/../c" -> "a/c"); None if it escapes it
+fn normalize_relative(path: &Path) -> Option<String> {
+    let mut parts: Vec<String> = Vec::new();
+    for component in path.components() {
+        match component {
+            Component::Normal(part) => parts.push(part.to_string_lossy().into_owned()),
+            Component::ParentDir => {
+                parts.pop()?;
+            }
+            Component::CurDir => {}
+            _ => return None,
+        }
+    }
+    Some(parts.join("/"))
+}
+
+/// Owner of every file installed by a bundled package: relative path -> relative path of
+/// the bundled dist-info folder whose RECORD lists it
+fn bundled_file_owners(bundle_dir: &Path) -> HashMap<String, String> {
+    let mut owners = HashMap::new();
+    for site_packages in site_packages_dirs(&bundle_dir.join(".venv")) {
+        let Some(prefix) = site_packages.strip_prefix(bundle_dir).ok().map(Path::to_path_buf) else {
+            continue;
+        };
+        for dist_info in dist_info_names(&site_packages) {
+            let Ok(record) = fs::read_to_string(site_packages.join(&dist_info).join("RECORD")) else {
+                continue;
+            };
+            let Some(owner) = normalize_relative(&prefix.join(&dist_info)) else {
+                continue;
+            };
+            for line in record.lines() {
+                let Some(relative) = line.split(',').next().filter(|p| !p.is_empty()) else {
+                    continue;
+                };
+                if let Some(path) = normalize_relative(&prefix.join(relative)) {
+                    owners.insert(path, owner.clone());
+                }
+            }
+        }
+    }
+    owners
+}
+
+/// pyvenv.cfg exists and its `home` points to an existing interpreter folder
+fn is_pyvenv_cfg_valid(local_dir: &Path) -> bool {
+    let Ok(content) = fs::read_to_string(local_dir.join(".venv").join("pyvenv.cfg")) else {
+        return false;
+    };
+    content
+        .lines()
+        .find_map(|line| line.strip_prefix("home = "))
+        .is_some_and(|home| Path::new(home).exists())
+}
+
+/// Check the local copy of the venv and cpython against the manifest of the bundled
+/// originals, optionally restoring missing or corrupted files from `bundle_dir`.
+///
+/// The manifest is created from the bundle when missing (older installs).
+///
+/// Pip legitimately changes the local venv (daemon updates, extras, app installs): files
+/// of a bundled package whose dist-info folder is gone locally were upgraded or removed
+/// by pip, they land in `changed_by_pip` instead of being restored over the new version.
+pub fn verify_local_venv(
+    bundle_dir: &Path,
+    local_dir: &Path,
+    mode: VerifyMode,
+    repair: bool,
+) -> Result<VerifyReport, String> {
+    if !local_dir.join(MANIFEST_FILE).exists() {
+        println!("   🧾 No venv manifest yet, hashing bundled files...");
+        write_venv_manifest(bundle_dir, local_dir)?;
+    }
+    let manifest = read_manifest(local_dir)?;
+
+    let missing = Mutex::new(Vec::new());
+    let corrupted = Mutex::new(Vec::new());
+    parallel_for_each(&manifest, |entry| {
+        let path = local_dir.join(&entry.path);
+        match fs::metadata(&path) {
+            Err(_) => missing.lock().unwrap().push(entry.path.clone()),
+            Ok(metadata) if metadata.len() != entry.size => corrupted.lock().unwrap().push(entry.path.clone()),
+            Ok(_) if mode == VerifyMode::Full && sha256_file(&path).ok().as_deref() != Some(entry.sha256.as_str()) => {
+                corrupted.lock().unwrap().push(entry.path.clone())
+            }
+            Ok(_) => {}
+        }
+        Ok(())
+    })?;
+
+    let mut report = VerifyReport {
+        checked: manifest.len(),
+        missing: missing.into_inner().unwrap(),
+        corrupted: corrupted.into_inner().unwrap(),
+        ..Default::default()
+    };
+
+    let owners = bundled_file_owners(bundle_dir);
+    let changed_by_pip = |relative: &String| {
+        owners
+            .get(relative)
+            .is_some_and(|dist_info| !local_dir.join(dist_info).is_dir())
+    };
+    report.changed_by_pip = report.missing.iter().chain(&report.corrupted).filter(|p| changed_by_pip(p)).cloned().collect();
+    report.missing.retain(|p| !changed_by_pip(p));
+    report.corrupted.retain(|p| !changed_by_pip(p));
+    report.missing.sort();
+    report.corrupted.sort();
+    report.changed_by_pip.sort();
+
+    if !repair {
+        return Ok(report);
+    }
+
+    let damaged: Vec<PathBuf> = report.missing.iter().chain(&report.corrupted).map(PathBuf::from).collect();
+    for relative in &damaged {
+        copy_single_file(&bundle_dir.join(relative), &local_dir.join(relative))?;
+    }
+    report.repaired = damaged.len();
+
+    if !is_pyvenv_cfg_valid(local_dir) {
+        let local_cfg = local_dir.join(".venv").join("pyvenv.cfg");
+        if !local_cfg.exists() {
+            copy_single_file(&bundle_dir.join(".venv").join("pyvenv.cfg"), &local_cfg)?;
+        }
+        patching_pyvenv_cfg(local_dir, &find_cpython_folder(local_dir)?)?;
+        report.pyvenv_cfg_repaired = true;
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{write_test_file, TEST_SITE_PACKAGES as SITE_PACKAGES};
+
+    /// reachy_mini 1.0 with its console script, installed in the bundle and the local copy
+    fn installed(name: &str) -> (PathBuf, PathBuf, PathBuf) {
+        crate::installed_test_venv(name, &[("../../../bin/reachy-mini-daemon", "#!python")])
+    }
+
+    #[test]
+    fn restores_files_removed_from_the_local_copy() {
+        let (root, bundle, local) = installed("verify-restore");
+        fs::remove_file(local.join(SITE_PACKAGES).join("reachy_mini/legacy.py")).unwrap();
+        fs::remove_file(local.join("cpython-3.12-linux/bin/python3")).unwrap();
+
+        let report = verify_local_venv(&bundle, &local, VerifyMode::Quick, true).unwrap();
+        assert_eq!(report.missing.len(), 2);
+        assert!(report.changed_by_pip.is_empty());
+        assert_eq!(report.repaired, 2);
+        assert!(local.join(SITE_PACKAGES).join("reachy_mini/legacy.py").exists());
+        assert!(local.join("cpython-3.12-linux/bin/python3").exists());
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn keeps_packages_upgraded_by_pip() {
+        let (root, bundle, local) = installed("verify-upgraded");
+        let local_site_packages = local.join(SITE_PACKAGES);
+        // pip upgraded reachy_mini 1.0 to 1.1: legacy.py dropped, __init__.py rewritten
+        fs::remove_dir_all(local_site_packages.join("reachy_mini-1.0.dist-info")).unwrap();
+        fs::remove_file(local_site_packages.join("reachy_mini/legacy.py")).unwrap();
+        write_test_file(&local_site_packages.join("reachy_mini/__init__.py"), "version 1.1");
+        write_test_file(&local.join(".venv/bin/reachy-mini-daemon"), "#!python 1.1");
+        write_test_file(
+            &local_site_packages.join("reachy_mini-1.1.dist-info/RECORD"),
+            "reachy_mini/__init__.py,,\nreachy_mini-1.1.dist-info/RECORD,,\n",
+        );
+        // Also removed by a disk cleaner
+        fs::remove_file(local.join("cpython-3.12-linux/bin/python3")).unwrap();
+
+        let report = verify_local_venv(&bundle, &local, VerifyMode::Full, true).unwrap();
+        assert_eq!(report.missing, vec!["cpython-3.12-linux/bin/python3".to_string()]);
+        assert!(report.corrupted.is_empty());
+        assert_eq!(report.changed_by_pip.len(), 4);
+        assert_eq!(report.repaired, 1);
+        assert!(!local_site_packages.join("reachy_mini-1.0.dist-info").exists());
+        assert!(!local_site_packages.join("reachy_mini/legacy.py").exists());
+        assert_eq!(fs::read_to_string(local_site_packages.join("reachy_mini/__init__.py")).unwrap(), "version 1.1");
+        assert_eq!(fs::read_to_string(local.join(".venv/bin/reachy-mini-daemon")).unwrap(), "#!python 1.1");
+        assert!(local.join("cpython-3.12-linux/bin/python3").exists());
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}